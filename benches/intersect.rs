@@ -0,0 +1,90 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![feature(test)]
+
+extern crate cgmath;
+extern crate rand;
+extern crate test;
+
+use cgmath::*;
+use rand::{FromEntropy, IsaacRng, Rng};
+use test::Bencher;
+
+const LEN: usize = 1 << 13;
+
+fn point3(rng: &mut IsaacRng) -> Point3<f32> {
+    Point3::from_vec(rng.gen::<Vector3<f32>>() * 10.0 - Vector3::new(5.0, 5.0, 5.0))
+}
+
+fn ray3(rng: &mut IsaacRng) -> Ray3<f32> {
+    Ray3::new(
+        Point3::new(0.0, 0.0, -20.0),
+        (point3(rng) - Point3::new(0.0, 0.0, -20.0)).normalize(),
+    )
+}
+
+fn frustum() -> Frustum<f32> {
+    let view = Matrix4::look_at(
+        Point3::new(0.0, 0.0, 10.0),
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::unit_y(),
+    );
+    Frustum::from_matrix4(perspective(Deg(60.0), 1.5, 0.1, 100.0) * view).unwrap()
+}
+
+macro_rules! bench_intersection {
+    ($name: ident, $Result: ty, |$rng: ident| $gen: expr) => {
+        #[bench]
+        fn $name(bh: &mut Bencher) {
+            let mut $rng = IsaacRng::from_entropy();
+
+            let elems: Vec<_> = (0..LEN).map(|_| $gen).collect();
+            let mut i = 0;
+
+            bh.iter(|| {
+                i = (i + 1) & (LEN - 1);
+
+                let res: $Result = unsafe { elems.get_unchecked(i).intersection() };
+                test::black_box(res)
+            })
+        }
+    };
+}
+
+bench_intersection!(_bench_ray3_plane, Option<Point3<f32>>, |rng| (
+    ray3(&mut rng),
+    Plane::from_point_normal(point3(&mut rng), rng.gen::<Vector3<f32>>().normalize())
+));
+bench_intersection!(_bench_ray3_sphere, Option<Point3<f32>>, |rng| (
+    ray3(&mut rng),
+    Sphere::new(point3(&mut rng), 1.0)
+));
+bench_intersection!(_bench_ray3_aabb3, Option<Point3<f32>>, |rng| (
+    ray3(&mut rng),
+    Aabb3::new(point3(&mut rng), point3(&mut rng))
+));
+bench_intersection!(_bench_ray3_triangle3, Option<Point3<f32>>, |rng| (
+    ray3(&mut rng),
+    Triangle3::new(point3(&mut rng), point3(&mut rng), point3(&mut rng))
+));
+bench_intersection!(_bench_frustum_sphere, Relation, |rng| (
+    frustum(),
+    Sphere::new(point3(&mut rng), 1.0)
+));
+bench_intersection!(_bench_frustum_aabb3, Relation, |rng| (
+    frustum(),
+    Aabb3::new(point3(&mut rng), point3(&mut rng))
+));
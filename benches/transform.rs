@@ -0,0 +1,113 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![feature(test)]
+
+extern crate cgmath;
+extern crate rand;
+extern crate test;
+
+use cgmath::*;
+use rand::{FromEntropy, IsaacRng, Rng};
+use test::Bencher;
+
+const LEN: usize = 1 << 13;
+
+fn points(rng: &mut IsaacRng) -> Vec<Point3<f32>> {
+    (0..LEN)
+        .map(|_| Point3::from_vec(rng.gen::<Vector3<f32>>()))
+        .collect()
+}
+
+fn decomposed(rng: &mut IsaacRng) -> Decomposed<Vector3<f32>, Quaternion<f32>> {
+    Decomposed {
+        scale: rng.gen(),
+        rot: rng.gen::<Quaternion<f32>>().normalize(),
+        disp: rng.gen(),
+    }
+}
+
+fn bench_transform_points<T: Transform<Point3<f32>>>(bh: &mut Bencher, transform: T) {
+    let mut rng = IsaacRng::from_entropy();
+
+    let points = points(&mut rng);
+    let mut out = vec![Point3::origin(); LEN];
+
+    bh.iter(|| {
+        for (o, &p) in out.iter_mut().zip(&points) {
+            *o = transform.transform_point(p);
+        }
+        test::black_box(&out);
+    })
+}
+
+#[bench]
+fn _bench_matrix4_transform_points(bh: &mut Bencher) {
+    let mut rng = IsaacRng::from_entropy();
+    bench_transform_points(bh, rng.gen::<Matrix4<f32>>());
+}
+
+#[bench]
+fn _bench_decomposed_transform_points(bh: &mut Bencher) {
+    let mut rng = IsaacRng::from_entropy();
+    bench_transform_points(bh, decomposed(&mut rng));
+}
+
+#[bench]
+fn _bench_matrix3_transform_points(bh: &mut Bencher) {
+    let mut rng = IsaacRng::from_entropy();
+    bench_transform_points(bh, Matrix3::from(rng.gen::<Quaternion<f32>>().normalize()));
+}
+
+#[bench]
+fn _bench_quat_rotate_vectors(bh: &mut Bencher) {
+    let mut rng = IsaacRng::from_entropy();
+
+    let q = rng.gen::<Quaternion<f32>>().normalize();
+    let vectors: Vec<Vector3<f32>> = (0..LEN).map(|_| rng.gen()).collect();
+    let mut out = vec![Vector3::zero(); LEN];
+
+    bh.iter(|| {
+        for (o, &v) in out.iter_mut().zip(&vectors) {
+            *o = q.rotate_vector(v);
+        }
+        test::black_box(&out);
+    })
+}
+
+#[bench]
+fn _bench_matrix4_transformed_iter(bh: &mut Bencher) {
+    let mut rng = IsaacRng::from_entropy();
+
+    let m = rng.gen::<Matrix4<f32>>();
+    let points = points(&mut rng);
+
+    bh.iter(|| {
+        let out: Vec<Point3<f32>> = points.iter().cloned().transformed(&m).collect();
+        test::black_box(out)
+    })
+}
+
+#[bench]
+fn _bench_matrix4_concat_chain(bh: &mut Bencher) {
+    let mut rng = IsaacRng::from_entropy();
+
+    let matrices: Vec<Matrix4<f32>> = (0..LEN).map(|_| rng.gen()).collect();
+
+    bh.iter(|| {
+        let product = matrices.iter().fold(Matrix4::identity(), |acc, m| acc * m);
+        test::black_box(product)
+    })
+}
@@ -0,0 +1,371 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Axis-aligned bounding boxes.
+
+use structure::*;
+
+use approx;
+use matrix::Matrix4;
+use num::{BaseFloat, BaseNum};
+use point::{Point2, Point3};
+use projection::{clip_to_screen, full_screen};
+use vector::{Vector2, Vector3, Vector4};
+
+/// A two-dimensional axis-aligned bounding box.
+///
+/// The box is described by its minimum and maximum corners. Every method
+/// assumes that `min` is component-wise less than or equal to `max`, which is
+/// maintained by all of the constructors.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Aabb2<S> {
+    /// The corner of the box with the smallest coordinates.
+    pub min: Point2<S>,
+    /// The corner of the box with the largest coordinates.
+    pub max: Point2<S>,
+}
+
+/// A three-dimensional axis-aligned bounding box.
+///
+/// The box is described by its minimum and maximum corners. Every method
+/// assumes that `min` is component-wise less than or equal to `max`, which is
+/// maintained by all of the constructors.
+///
+/// ```rust
+/// use cgmath::{Aabb3, Point3};
+///
+/// let vertices = [
+///     Point3::new(1.0, 0.0, 2.0),
+///     Point3::new(-1.0, 4.0, 0.5),
+///     Point3::new(0.0, 2.0, -3.0),
+/// ];
+///
+/// let bounds = Aabb3::from_points(vertices.iter().cloned()).unwrap();
+/// assert_eq!(bounds.min, Point3::new(-1.0, 0.0, -3.0));
+/// assert_eq!(bounds.max, Point3::new(1.0, 4.0, 2.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Aabb3<S> {
+    /// The corner of the box with the smallest coordinates.
+    pub min: Point3<S>,
+    /// The corner of the box with the largest coordinates.
+    pub max: Point3<S>,
+}
+
+#[inline]
+fn partial_min<S: PartialOrd>(a: S, b: S) -> S {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+#[inline]
+fn partial_max<S: PartialOrd>(a: S, b: S) -> S {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+macro_rules! impl_aabb {
+    ($AabbN:ident { $($field:ident),+ }, $PointN:ident, $VectorN:ident) => {
+        impl<S: BaseNum> $AabbN<S> {
+            /// Construct a new bounding box from two opposite corners, given
+            /// in any order.
+            #[inline]
+            pub fn new(p1: $PointN<S>, p2: $PointN<S>) -> $AabbN<S> {
+                $AabbN {
+                    min: p1.zip(p2, partial_min),
+                    max: p1.zip(p2, partial_max),
+                }
+            }
+
+            /// Construct a degenerate bounding box containing a single point.
+            #[inline]
+            pub fn from_point(p: $PointN<S>) -> $AabbN<S> {
+                $AabbN { min: p, max: p }
+            }
+
+            /// Compute the smallest bounding box containing all of the points
+            /// produced by an iterator, or `None` if there are no points.
+            pub fn from_points<I>(points: I) -> Option<$AabbN<S>>
+            where
+                I: IntoIterator<Item = $PointN<S>>,
+            {
+                let mut points = points.into_iter();
+                let first = points.next()?;
+                Some(points.fold($AabbN::from_point(first), |aabb, p| aabb.grow(p)))
+            }
+
+            /// Return the smallest bounding box containing both this box and
+            /// the point `p`.
+            #[inline]
+            pub fn grow(&self, p: $PointN<S>) -> $AabbN<S> {
+                $AabbN {
+                    min: self.min.zip(p, partial_min),
+                    max: self.max.zip(p, partial_max),
+                }
+            }
+
+            /// Return the smallest bounding box containing both this box and
+            /// `other`.
+            #[inline]
+            pub fn union(&self, other: &$AabbN<S>) -> $AabbN<S> {
+                $AabbN {
+                    min: self.min.zip(other.min, partial_min),
+                    max: self.max.zip(other.max, partial_max),
+                }
+            }
+
+            /// The size of the box along each axis.
+            #[inline]
+            pub fn dim(&self) -> $VectorN<S> {
+                self.max - self.min
+            }
+
+            /// The area or volume enclosed by the box.
+            #[inline]
+            pub fn volume(&self) -> S {
+                self.dim().product()
+            }
+
+            /// The point at the center of the box.
+            #[inline]
+            pub fn center(&self) -> $PointN<S> {
+                self.min.midpoint(self.max)
+            }
+
+            /// Test whether the point is inside the box or on its boundary.
+            #[inline]
+            pub fn contains_point(&self, p: $PointN<S>) -> bool {
+                $(self.min.$field <= p.$field && p.$field <= self.max.$field)&&+
+            }
+
+            /// The box where this box and `other` overlap, or `None` if they
+            /// do not. Boxes which only touch overlap on a flat box.
+            ///
+            /// With integer coordinates, this is the intersection of scissor
+            /// rectangles.
+            #[inline]
+            pub fn intersection(&self, other: &$AabbN<S>) -> Option<$AabbN<S>> {
+                let min = self.min.zip(other.min, partial_max);
+                let max = self.max.zip(other.max, partial_min);
+                if $(min.$field <= max.$field)&&+ {
+                    Some($AabbN { min, max })
+                } else {
+                    None
+                }
+            }
+
+            /// This box with its corners moved inside `bounds`, such as a
+            /// rectangle clamped to the surface it is drawn on.
+            ///
+            /// Unlike `intersection`, this always returns a box, which is flat
+            /// on the side of `bounds` nearest to this box if they do not
+            /// overlap.
+            #[inline]
+            pub fn clamped_to(&self, bounds: &$AabbN<S>) -> $AabbN<S> {
+                let clamp = |p: $PointN<S>| {
+                    p.zip(bounds.min, partial_max).zip(bounds.max, partial_min)
+                };
+                $AabbN {
+                    min: clamp(self.min),
+                    max: clamp(self.max),
+                }
+            }
+        }
+
+        impl<S: BaseFloat> approx::AbsDiffEq for $AabbN<S> {
+            type Epsilon = S::Epsilon;
+
+            #[inline]
+            fn default_epsilon() -> S::Epsilon {
+                S::default_epsilon()
+            }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+                $PointN::abs_diff_eq(&self.min, &other.min, epsilon)
+                    && $PointN::abs_diff_eq(&self.max, &other.max, epsilon)
+            }
+        }
+
+        impl<S: BaseFloat> approx::RelativeEq for $AabbN<S> {
+            #[inline]
+            fn default_max_relative() -> S::Epsilon {
+                S::default_max_relative()
+            }
+
+            #[inline]
+            fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+                $PointN::relative_eq(&self.min, &other.min, epsilon, max_relative)
+                    && $PointN::relative_eq(&self.max, &other.max, epsilon, max_relative)
+            }
+        }
+
+        impl<S: BaseFloat> approx::UlpsEq for $AabbN<S> {
+            #[inline]
+            fn default_max_ulps() -> u32 {
+                S::default_max_ulps()
+            }
+
+            #[inline]
+            fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+                $PointN::ulps_eq(&self.min, &other.min, epsilon, max_ulps)
+                    && $PointN::ulps_eq(&self.max, &other.max, epsilon, max_ulps)
+            }
+        }
+    };
+}
+
+impl_aabb!(Aabb2 { x, y }, Point2, Vector2);
+impl_aabb!(Aabb3 { x, y, z }, Point3, Vector3);
+
+/// The twelve edges of a box, given its corners ordered with the `x`, `y`
+/// and `z` coordinates of the corner `i` selected by the bits of `i`.
+pub(crate) fn box_edges<S: Copy>(corners: &[Point3<S>; 8]) -> Vec<(Point3<S>, Point3<S>)> {
+    let mut edges = Vec::with_capacity(12);
+    for i in 0..8 {
+        for &bit in &[1, 2, 4] {
+            if i & bit == 0 {
+                edges.push((corners[i], corners[i | bit]));
+            }
+        }
+    }
+    edges
+}
+
+impl<S: BaseNum> Aabb2<S> {
+    /// The four corners of the box, in counter-clockwise order starting from
+    /// `min`.
+    pub fn corners(&self) -> [Point2<S>; 4] {
+        let (a, b) = (self.min, self.max);
+        [
+            Point2::new(a.x, a.y),
+            Point2::new(b.x, a.y),
+            Point2::new(b.x, b.y),
+            Point2::new(a.x, b.y),
+        ]
+    }
+
+    /// Create line segments along the four edges of the box.
+    pub fn to_wireframe_lines(&self) -> Vec<(Point2<S>, Point2<S>)> {
+        let c = self.corners();
+        (0..4).map(|i| (c[i], c[(i + 1) % 4])).collect()
+    }
+}
+
+impl<S: BaseFloat> Aabb3<S> {
+    /// The eight corners of the box.
+    pub fn corners(&self) -> [Point3<S>; 8] {
+        let (a, b) = (self.min, self.max);
+        [
+            Point3::new(a.x, a.y, a.z),
+            Point3::new(b.x, a.y, a.z),
+            Point3::new(a.x, b.y, a.z),
+            Point3::new(b.x, b.y, a.z),
+            Point3::new(a.x, a.y, b.z),
+            Point3::new(b.x, a.y, b.z),
+            Point3::new(a.x, b.y, b.z),
+            Point3::new(b.x, b.y, b.z),
+        ]
+    }
+
+    /// Create line segments along the twelve edges of the box.
+    pub fn to_wireframe_lines(&self) -> Vec<(Point3<S>, Point3<S>)> {
+        box_edges(&self.corners())
+    }
+
+    /// The smallest box containing this box after an affine transform, such
+    /// as the view space bounds of a box given in world space.
+    ///
+    /// This uses the method from "Transforming Axis-Aligned Bounding Boxes"
+    /// by James Arvo, which is cheaper than transforming every corner.
+    pub fn transformed(&self, m: &Matrix4<S>) -> Aabb3<S> {
+        let mut min = Point3::new(m.w.x, m.w.y, m.w.z);
+        let mut max = min;
+        for i in 0..3 {
+            for j in 0..3 {
+                let a = m[i][j] * self.min[i];
+                let b = m[i][j] * self.max[i];
+                min[j] += a.min(b);
+                max[j] += a.max(b);
+            }
+        }
+        Aabb3 { min, max }
+    }
+
+    /// The area of the box's shadow on a plane perpendicular to
+    /// `direction`, under an orthographic projection.
+    ///
+    /// The direction does not need to be normalized. Averaged over all
+    /// directions, this is a quarter of the box's surface area.
+    ///
+    /// ```rust
+    /// use cgmath::{Aabb3, Point3, Vector3};
+    ///
+    /// let aabb = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0));
+    /// assert_eq!(aabb.projected_area(Vector3::new(0.0, 0.0, -5.0)), 2.0);
+    /// ```
+    pub fn projected_area(&self, direction: Vector3<S>) -> S {
+        let d = direction.normalize();
+        let e = self.dim();
+        d.x.abs() * e.y * e.z + d.y.abs() * e.x * e.z + d.z.abs() * e.x * e.y
+    }
+
+    /// Compute a rectangle that bounds the projection of the box on the
+    /// screen, in normalized device coordinates, given the combined view
+    /// and projection matrix.
+    ///
+    /// Returns `None` if the box is entirely behind the camera or off the
+    /// screen. If the box reaches behind the camera, the whole screen is
+    /// returned.
+    ///
+    /// ```rust
+    /// use cgmath::{perspective, Aabb3, Deg, Matrix4, Point3, Vector3};
+    ///
+    /// let view_projection = perspective(Deg(90.0f64), 1.0, 0.1, 100.0)
+    ///     * Matrix4::from_translation(Vector3::new(0.0, 0.0, -10.0));
+    /// let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+    ///
+    /// let bounds = aabb.screen_bounds(&view_projection).unwrap();
+    /// assert!((bounds.max.x - 1.0 / 9.0).abs() < 1e-12);
+    /// ```
+    pub fn screen_bounds(&self, view_projection: &Matrix4<S>) -> Option<Aabb2<S>> {
+        let clip: Vec<Vector4<S>> = self
+            .corners()
+            .iter()
+            .map(|p| view_projection * p.to_homogeneous())
+            .collect();
+
+        if clip.iter().all(|c| c.w <= S::zero()) {
+            return None;
+        }
+        if clip.iter().any(|c| c.w <= S::zero()) {
+            return Some(full_screen());
+        }
+        let ndc = clip.iter().map(|c| Point2::new(c.x / c.w, c.y / c.w));
+        let bounds = Aabb2::from_points(ndc)?;
+        clip_to_screen(bounds.min, bounds.max)
+    }
+}
+
+impl_precision_conversions!(Aabb2 { min, max }, Aabb2<f32>, Aabb2<f64>);
+impl_precision_conversions!(Aabb3 { min, max }, Aabb3<f32>, Aabb3<f64>);
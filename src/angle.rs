@@ -30,7 +30,27 @@ use rand::{
 use structure::*;
 
 use approx;
-use num::BaseFloat;
+use num::{BaseFloat, ToF32};
+
+/// The trigonometric functions of angles, which are the software ones of
+/// the `strict` module when the `deterministic` feature is enabled, and those
+/// of the platform otherwise.
+#[cfg(feature = "deterministic")]
+use strict as trig;
+
+#[cfg(not(feature = "deterministic"))]
+mod trig {
+    use num::BaseFloat;
+
+    #[inline] pub fn sin<S: BaseFloat>(x: S) -> S { x.sin() }
+    #[inline] pub fn cos<S: BaseFloat>(x: S) -> S { x.cos() }
+    #[inline] pub fn tan<S: BaseFloat>(x: S) -> S { x.tan() }
+    #[inline] pub fn sin_cos<S: BaseFloat>(x: S) -> (S, S) { x.sin_cos() }
+    #[inline] pub fn asin<S: BaseFloat>(x: S) -> S { x.asin() }
+    #[inline] pub fn acos<S: BaseFloat>(x: S) -> S { x.acos() }
+    #[inline] pub fn atan<S: BaseFloat>(x: S) -> S { x.atan() }
+    #[inline] pub fn atan2<S: BaseFloat>(y: S, x: S) -> S { y.atan2(x) }
+}
 
 /// An angle, in radians.
 ///
@@ -78,7 +98,15 @@ macro_rules! impl_angle {
 
             #[inline]
             fn is_zero(&self) -> bool {
-                ulps_eq!(self, &Self::zero())
+                implicit_ulps_eq!(self, &Self::zero())
+            }
+        }
+
+        /// The default angle is zero.
+        impl<S: BaseFloat> Default for $Angle<S> {
+            #[inline]
+            fn default() -> $Angle<S> {
+                $Angle::zero()
             }
         }
 
@@ -101,15 +129,15 @@ macro_rules! impl_angle {
 
             #[inline] fn full_turn() -> $Angle<S> { $Angle(cast($full_turn).unwrap()) }
 
-            #[inline] fn sin(self) -> S { Rad::from(self).0.sin() }
-            #[inline] fn cos(self) -> S { Rad::from(self).0.cos() }
-            #[inline] fn tan(self) -> S { Rad::from(self).0.tan() }
-            #[inline] fn sin_cos(self) -> (S, S) { Rad::from(self).0.sin_cos() }
+            #[inline] fn sin(self) -> S { trig::sin(Rad::from(self).0) }
+            #[inline] fn cos(self) -> S { trig::cos(Rad::from(self).0) }
+            #[inline] fn tan(self) -> S { trig::tan(Rad::from(self).0) }
+            #[inline] fn sin_cos(self) -> (S, S) { trig::sin_cos(Rad::from(self).0) }
 
-            #[inline] fn asin(a: S) -> $Angle<S> { Rad(a.asin()).into() }
-            #[inline] fn acos(a: S) -> $Angle<S> { Rad(a.acos()).into() }
-            #[inline] fn atan(a: S) -> $Angle<S> { Rad(a.atan()).into() }
-            #[inline] fn atan2(a: S, b: S) -> $Angle<S> { Rad(a.atan2(b)).into() }
+            #[inline] fn asin(a: S) -> $Angle<S> { Rad(trig::asin(a)).into() }
+            #[inline] fn acos(a: S) -> $Angle<S> { Rad(trig::acos(a)).into() }
+            #[inline] fn atan(a: S) -> $Angle<S> { Rad(trig::atan(a)).into() }
+            #[inline] fn atan2(a: S, b: S) -> $Angle<S> { Rad(trig::atan2(a, b)).into() }
         }
 
         impl<S: BaseFloat> Neg for $Angle<S> {
@@ -231,3 +259,6 @@ macro_rules! impl_angle {
 
 impl_angle!(Rad, "{:?} rad", f64::consts::PI * 2.0, f64::consts::PI);
 impl_angle!(Deg, "{:?}°", 360, 180);
+
+impl_precision_conversions!(Rad { 0 }, Rad<f32>, Rad<f64>);
+impl_precision_conversions!(Deg { 0 }, Deg<f32>, Deg<f64>);
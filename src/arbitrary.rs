@@ -0,0 +1,280 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Random geometric values for property-based testing.
+//!
+//! Testing geometric code on random inputs finds edge cases that hand
+//! written examples miss, but only if the inputs are unbiased. Generating
+//! uniformly distributed rotations in particular is easy to get wrong:
+//! normalizing a random quaternion or picking random Euler angles favours
+//! some orientations over others. The distributions of this module can be
+//! used with any `rand::Rng` to generate the inputs of such tests.
+//!
+//! ```rust
+//! # extern crate cgmath;
+//! # extern crate rand;
+//! use cgmath::arbitrary::{InCube, UniformRotation};
+//! use cgmath::prelude::*;
+//! use cgmath::{Point3, Quaternion};
+//! use rand::Rng;
+//!
+//! # fn main() {
+//! let mut rng = rand::thread_rng();
+//! for _ in 0..100 {
+//!     let q: Quaternion<f64> = rng.sample(UniformRotation);
+//!     let p: Point3<f64> = rng.sample(InCube::new(10.0));
+//!     let back = q.invert().rotate_point(q.rotate_point(p));
+//!     assert!((back - p).magnitude() < 1.0e-9);
+//! }
+//! # }
+//! ```
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::{Aabb2, Aabb3};
+use angle::Rad;
+use num::BaseFloat;
+use point::{Point2, Point3};
+use quaternion::Quaternion;
+use ray::{Ray2, Ray3};
+use rotation::{Basis2, Basis3, Rotation2};
+use sphere::Sphere;
+use transform::Decomposed;
+use vector::{Vector2, Vector3};
+
+/// A uniformly distributed number in `[-extent, extent)`.
+#[inline]
+fn symmetric<S: BaseFloat, R: Rng + ?Sized>(rng: &mut R, extent: S) -> S
+where
+    Standard: Distribution<S>,
+{
+    let two: S = cast(2).unwrap();
+    (rng.gen::<S>() * two - S::one()) * extent
+}
+
+/// A uniformly distributed angle in `[0, 2 pi)`.
+#[inline]
+fn turn<S: BaseFloat, R: Rng + ?Sized>(rng: &mut R) -> Rad<S>
+where
+    Standard: Distribution<S>,
+{
+    Rad::full_turn() * rng.gen::<S>()
+}
+
+/// Unit vectors uniformly distributed on the circle or the sphere.
+#[derive(Copy, Clone, Debug)]
+pub struct UnitVector;
+
+impl<S: BaseFloat> Distribution<Vector2<S>> for UnitVector
+where
+    Standard: Distribution<S>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2<S> {
+        let (sin, cos) = Rad::sin_cos(turn(rng));
+        Vector2::new(cos, sin)
+    }
+}
+
+impl<S: BaseFloat> Distribution<Vector3<S>> for UnitVector
+where
+    Standard: Distribution<S>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector3<S> {
+        // The height of uniformly distributed points on the sphere is itself
+        // uniformly distributed, by Archimedes' hat-box theorem.
+        let z = symmetric(rng, S::one());
+        let r = (S::one() - z * z).max(S::zero()).sqrt();
+        let (sin, cos) = Rad::sin_cos(turn(rng));
+        Vector3::new(r * cos, r * sin, z)
+    }
+}
+
+/// Rotations uniformly distributed over all orientations.
+///
+/// Quaternions are generated with Shoemake's method, from "Uniform Random
+/// Rotations" in Graphics Gems III.
+#[derive(Copy, Clone, Debug)]
+pub struct UniformRotation;
+
+impl<S: BaseFloat> Distribution<Quaternion<S>> for UniformRotation
+where
+    Standard: Distribution<S>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Quaternion<S> {
+        let u = rng.gen::<S>();
+        let (a, b) = ((S::one() - u).sqrt(), u.sqrt());
+        let (sin1, cos1) = Rad::sin_cos(turn(rng));
+        let (sin2, cos2) = Rad::sin_cos(turn(rng));
+        Quaternion::new(b * cos2, a * sin1, a * cos1, b * sin2)
+    }
+}
+
+impl<S: BaseFloat> Distribution<Basis3<S>> for UniformRotation
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Basis3<S> {
+        let q: Quaternion<S> = self.sample(rng);
+        Basis3::from(q)
+    }
+}
+
+impl<S: BaseFloat> Distribution<Basis2<S>> for UniformRotation
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Basis2<S> {
+        Rotation2::from_angle(turn(rng))
+    }
+}
+
+/// Geometric values from a cube centered on the origin.
+///
+/// Vectors and points are uniformly distributed in the cube, or square in
+/// 2D, going from `-half_extent` to `half_extent` along each axis. Boxes
+/// have their corners in it, rays their origins, and spheres their centers,
+/// with a radius up to `half_extent`. Rays have uniformly distributed unit
+/// directions, and transforms a uniformly distributed rotation, a
+/// displacement in the cube, and a scale between one half and two.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct InCube<S> {
+    pub half_extent: S,
+}
+
+impl<S> InCube<S> {
+    /// Generate values in the cube from `-half_extent` to `half_extent`.
+    #[inline]
+    pub fn new(half_extent: S) -> InCube<S> {
+        InCube { half_extent }
+    }
+}
+
+impl<S: BaseFloat> Distribution<Vector2<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector2<S> {
+        Vector2::new(
+            symmetric(rng, self.half_extent),
+            symmetric(rng, self.half_extent),
+        )
+    }
+}
+
+impl<S: BaseFloat> Distribution<Vector3<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vector3<S> {
+        Vector3::new(
+            symmetric(rng, self.half_extent),
+            symmetric(rng, self.half_extent),
+            symmetric(rng, self.half_extent),
+        )
+    }
+}
+
+impl<S: BaseFloat> Distribution<Point2<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point2<S> {
+        Point2::from_vec(self.sample(rng))
+    }
+}
+
+impl<S: BaseFloat> Distribution<Point3<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Point3<S> {
+        Point3::from_vec(self.sample(rng))
+    }
+}
+
+impl<S: BaseFloat> Distribution<Aabb2<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Aabb2<S> {
+        Aabb2::new(self.sample(rng), self.sample(rng))
+    }
+}
+
+impl<S: BaseFloat> Distribution<Aabb3<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Aabb3<S> {
+        Aabb3::new(self.sample(rng), self.sample(rng))
+    }
+}
+
+impl<S: BaseFloat> Distribution<Ray2<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ray2<S> {
+        Ray2::new(self.sample(rng), UnitVector.sample(rng))
+    }
+}
+
+impl<S: BaseFloat> Distribution<Ray3<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Ray3<S> {
+        Ray3::new(self.sample(rng), UnitVector.sample(rng))
+    }
+}
+
+impl<S: BaseFloat> Distribution<Sphere<S>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Sphere<S> {
+        // Avoid degenerate spheres of zero radius.
+        let radius = (S::one() - rng.gen::<S>()) * self.half_extent;
+        Sphere::new(self.sample(rng), radius)
+    }
+}
+
+impl<S: BaseFloat> Distribution<Decomposed<Vector3<S>, Quaternion<S>>> for InCube<S>
+where
+    Standard: Distribution<S>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Decomposed<Vector3<S>, Quaternion<S>> {
+        // A log-uniform scale, as likely to shrink as to grow.
+        let two: S = cast(2).unwrap();
+        Decomposed {
+            scale: two.powf(symmetric(rng, S::one())),
+            rot: UniformRotation.sample(rng),
+            disp: self.sample(rng),
+        }
+    }
+}
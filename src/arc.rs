@@ -0,0 +1,235 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Circular arcs in the plane.
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use num::BaseFloat;
+use point::Point2;
+use polyline::{segment_distance2, Polyline2};
+use vector::Vector2;
+
+/// A circular arc in the plane from `start` to `end`, or a straight segment
+/// between them.
+///
+/// The arc is described by its bulge, the tangent of a quarter of the angle
+/// it sweeps around its center, as in the polylines of DXF files. The arc
+/// turns counter-clockwise when the bulge is positive, clockwise when it is
+/// negative, and is straight when it is zero. A bulge of one is a half
+/// circle.
+///
+/// ```rust
+/// use cgmath::{Arc2, Point2};
+///
+/// let arc = Arc2::new(Point2::new(1.0, 0.0), Point2::new(-1.0, 0.0), 1.0f64);
+/// assert_eq!(arc.center(), Some(Point2::new(0.0, 0.0)));
+/// assert_eq!(arc.radius(), Some(1.0));
+/// assert!((arc.point_at(0.5) - Point2::new(0.0, 1.0)).x.abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Arc2<S> {
+    /// The point where the arc starts.
+    pub start: Point2<S>,
+    /// The point where the arc ends.
+    pub end: Point2<S>,
+    /// The tangent of a quarter of the signed angle swept by the arc.
+    pub bulge: S,
+}
+
+/// Rotate `v` counter-clockwise by the angle with the given sine and cosine.
+#[inline]
+fn rotate<S: BaseFloat>(v: Vector2<S>, (sin, cos): (S, S)) -> Vector2<S> {
+    Vector2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos)
+}
+
+impl<S: BaseFloat> Arc2<S> {
+    /// Construct an arc from its end points and bulge.
+    #[inline]
+    pub fn new(start: Point2<S>, end: Point2<S>, bulge: S) -> Arc2<S> {
+        Arc2 { start, end, bulge }
+    }
+
+    /// The straight segment from `start` to `end`.
+    #[inline]
+    pub fn line(start: Point2<S>, end: Point2<S>) -> Arc2<S> {
+        Arc2::new(start, end, S::zero())
+    }
+
+    /// The arc leaving `start` in the direction of `tangent` and reaching
+    /// `end`, which is straight when the tangent points at the end.
+    pub fn from_start_tangent(start: Point2<S>, tangent: Vector2<S>, end: Point2<S>) -> Arc2<S> {
+        let chord = end - start;
+        // The tangent turns by half the sweep to follow the chord.
+        let half = Rad::atan2(tangent.perp_dot(chord), tangent.dot(chord));
+        Arc2::new(start, end, (half / cast::<_, S>(2).unwrap()).tan())
+    }
+
+    /// The two arcs from `start` to `end` which are tangent to `start_tangent`
+    /// and `end_tangent` there and meet smoothly, known as a biarc.
+    ///
+    /// The arcs meet where the tangents at both ends, extended by the same
+    /// length, are as far from each other as they are long.
+    pub fn biarc(
+        start: Point2<S>,
+        start_tangent: Vector2<S>,
+        end: Point2<S>,
+        end_tangent: Vector2<S>,
+    ) -> (Arc2<S>, Arc2<S>) {
+        let (t0, t1) = (start_tangent.normalize(), end_tangent.normalize());
+        let v = end - start;
+        let two: S = cast(2).unwrap();
+        let four: S = cast(4).unwrap();
+        let t = t0 + t1;
+        let denominator = two * (S::one() - t0.dot(t1));
+        let epsilon = S::default_epsilon();
+        let joint = if denominator > S::zero()
+            && !implicit_ulps_eq!(denominator, S::zero(), epsilon = epsilon)
+        {
+            let vt = v.dot(t);
+            let d = (-vt + (vt * vt + denominator * v.magnitude2()).sqrt()) / denominator;
+            (start + t0 * d).midpoint(end - t1 * d)
+        } else if !implicit_ulps_eq!(v.dot(t1), S::zero(), epsilon = epsilon * v.magnitude()) {
+            // Parallel tangents.
+            let d = v.magnitude2() / (four * v.dot(t1));
+            (start + t0 * d).midpoint(end - t1 * d)
+        } else {
+            // Parallel tangents across the chord, which two half circles join.
+            start.midpoint(end)
+        };
+        (
+            Arc2::from_start_tangent(start, t0, joint),
+            Arc2::from_start_tangent(end, -t1, joint).reversed(),
+        )
+    }
+
+    /// The same arc, from its end to its start.
+    #[inline]
+    pub fn reversed(&self) -> Arc2<S> {
+        Arc2::new(self.end, self.start, -self.bulge)
+    }
+
+    /// Whether the arc is a straight segment.
+    #[inline]
+    pub fn is_straight(&self) -> bool {
+        self.bulge == S::zero()
+    }
+
+    /// The signed angle swept around the center, positive when
+    /// counter-clockwise.
+    #[inline]
+    pub fn sweep(&self) -> Rad<S> {
+        Rad::atan(self.bulge) * cast(4).unwrap()
+    }
+
+    /// The center of the circle of the arc, or `None` if it is straight.
+    pub fn center(&self) -> Option<Point2<S>> {
+        if self.is_straight() {
+            return None;
+        }
+        let chord = self.end - self.start;
+        let b = self.bulge;
+        let four: S = cast(4).unwrap();
+        let offset = (S::one() - b * b) / (four * b);
+        Some(self.start.midpoint(self.end) + Vector2::new(-chord.y, chord.x) * offset)
+    }
+
+    /// The radius of the circle of the arc, or `None` if it is straight.
+    pub fn radius(&self) -> Option<S> {
+        if self.is_straight() {
+            return None;
+        }
+        let b = self.bulge;
+        let four: S = cast(4).unwrap();
+        Some((self.end - self.start).magnitude() * (S::one() + b * b) / (four * b.abs()))
+    }
+
+    /// The length of the arc.
+    pub fn length(&self) -> S {
+        match self.radius() {
+            Some(radius) => radius * self.sweep().0.abs(),
+            None => self.start.distance(self.end),
+        }
+    }
+
+    /// The point at the fraction `t` of the arc, from its start at zero to
+    /// its end at one.
+    pub fn point_at(&self, t: S) -> Point2<S> {
+        match self.center() {
+            Some(center) => center + rotate(self.start - center, (self.sweep() * t).sin_cos()),
+            None => self.start + (self.end - self.start) * t,
+        }
+    }
+
+    /// The unit direction of the arc at its start.
+    pub fn start_tangent(&self) -> Vector2<S> {
+        let half = self.sweep() / cast::<_, S>(2).unwrap();
+        rotate((self.end - self.start).normalize(), (-half).sin_cos())
+    }
+
+    /// The unit direction of the arc at its end.
+    pub fn end_tangent(&self) -> Vector2<S> {
+        let half = self.sweep() / cast::<_, S>(2).unwrap();
+        rotate((self.end - self.start).normalize(), half.sin_cos())
+    }
+
+    /// The distance from `p` to the closest point of the arc.
+    pub fn distance(&self, p: Point2<S>) -> S {
+        let (center, radius) = match (self.center(), self.radius()) {
+            (Some(center), Some(radius)) => (center, radius),
+            _ => return segment_distance2(self.start, self.end, p).sqrt(),
+        };
+        let (from, to) = (self.start - center, p - center);
+        // The angle from the start to `p`, in the direction of the arc.
+        let mut angle = Rad::atan2(from.perp_dot(to), from.dot(to)).0 * self.bulge.signum();
+        if angle < S::zero() {
+            angle += Rad::<S>::full_turn().0;
+        }
+        if angle <= self.sweep().0.abs() {
+            (to.magnitude() - radius).abs()
+        } else {
+            p.distance(self.start).min(p.distance(self.end))
+        }
+    }
+
+    /// Approximate the arc by a polyline from its start to its end, which
+    /// stays closer than `tolerance` to it, with evenly spaced vertices.
+    pub fn flatten(&self, tolerance: S) -> Polyline2<S> {
+        let radius = match self.radius() {
+            Some(radius) => radius,
+            None => return Polyline2::new(vec![self.start, self.end]),
+        };
+        // The largest angle between vertices whose chord stays within the
+        // tolerance of the circle.
+        let two: S = cast(2).unwrap();
+        let step = Rad::acos((S::one() - tolerance / radius).max(-S::one())).0 * two;
+        let count = (self.sweep().0.abs() / step).ceil().max(S::one());
+        let segments: usize = cast(count).unwrap();
+        let points = (0..=segments)
+            .map(|i| {
+                if i == segments {
+                    self.end
+                } else {
+                    self.point_at(cast::<_, S>(i).unwrap() / count)
+                }
+            })
+            .collect();
+        Polyline2::new(points)
+    }
+}
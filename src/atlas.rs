@@ -0,0 +1,273 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for texture atlases.
+//!
+//! A texture atlas packs many small images, such as glyphs, sprites or
+//! lightmaps, into one large texture.
+//!
+//! The UV transforms are homogeneous `Matrix3`s, which apply to texture
+//! coordinates with `Transform::transform_point`. They take the texture
+//! coordinates of a whole image, from `(0, 0)` to `(1, 1)`, to the texture
+//! coordinates in the atlas.
+
+use structure::*;
+
+use angle::Rad;
+use matrix::Matrix3;
+use num::{BaseFloat, BaseNum};
+use point::Point2;
+use vector::Vector2;
+
+use aabb::Aabb2;
+
+/// The UV transform scaling texture coordinates by `tiling`, then offsetting
+/// them by `offset`, so that the texture repeats `tiling` times over the
+/// surface.
+#[inline]
+pub fn uv_transform<S: BaseFloat>(tiling: Vector2<S>, offset: Vector2<S>) -> Matrix3<S> {
+    Matrix3::new(
+        tiling.x, S::zero(), S::zero(),
+        S::zero(), tiling.y, S::zero(),
+        offset.x, offset.y, S::one(),
+    )
+}
+
+/// The UV transform rotating texture coordinates by `angle` about `pivot`,
+/// such as `(0.5, 0.5)` for the center of the texture.
+pub fn uv_rotation_about<S: BaseFloat, A: Into<Rad<S>>>(
+    pivot: Point2<S>,
+    angle: A,
+) -> Matrix3<S> {
+    let (s, c) = Rad::sin_cos(angle.into());
+    let p = pivot.to_vec();
+    // Translate the pivot to the origin, rotate and translate it back.
+    let t = p - Vector2::new(c * p.x - s * p.y, s * p.x + c * p.y);
+    Matrix3::new(
+        c, s, S::zero(),
+        -s, c, S::zero(),
+        t.x, t.y, S::one(),
+    )
+}
+
+/// The scale and offset taking the texture coordinates of an image to those
+/// of the sub-rectangle `rect` of the atlas covering `atlas`, such as a
+/// placement returned by a `SkylinePacker`.
+///
+/// This is the pair of vectors shaders usually take to sample an atlas, with
+/// `uv * scale + offset`.
+///
+/// ```rust
+/// use cgmath::atlas::atlas_scale_offset;
+/// use cgmath::{Aabb2, Point2, Vector2};
+///
+/// let atlas = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(256.0f32, 128.0));
+/// let rect = Aabb2::new(Point2::new(64.0, 32.0), Point2::new(128.0, 64.0));
+/// let (scale, offset) = atlas_scale_offset(atlas, rect);
+/// assert_eq!(scale, Vector2::new(0.25, 0.25));
+/// assert_eq!(offset, Vector2::new(0.25, 0.25));
+/// ```
+pub fn atlas_scale_offset<S: BaseFloat>(
+    atlas: Aabb2<S>,
+    rect: Aabb2<S>,
+) -> (Vector2<S>, Vector2<S>) {
+    let size = atlas.dim();
+    let relative = |v: Vector2<S>| Vector2::new(v.x / size.x, v.y / size.y);
+    (relative(rect.dim()), relative(rect.min - atlas.min))
+}
+
+/// The UV transform taking the texture coordinates of an image to those of
+/// the sub-rectangle `rect` of the atlas covering `atlas`.
+///
+/// This is the transform of `atlas_scale_offset`, as a matrix.
+pub fn atlas_uv_transform<S: BaseFloat>(atlas: Aabb2<S>, rect: Aabb2<S>) -> Matrix3<S> {
+    let (scale, offset) = atlas_scale_offset(atlas, rect);
+    uv_transform(scale, offset)
+}
+
+/// A horizontal segment of the skyline of a `SkylinePacker`, `width` wide,
+/// starting at `x`, below which everything is already allocated.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct SkylineSegment<S> {
+    x: S,
+    y: S,
+    width: S,
+}
+
+/// A packer of rectangles inside an atlas, using the bottom-left skyline
+/// heuristic.
+///
+/// The packer keeps track of the highest allocated point of each part of the
+/// atlas, and places every new rectangle as low as possible on this skyline.
+/// The space left below the skyline is never reused, which keeps the packer
+/// simple and fast, at the cost of some waste when rectangles of very
+/// different heights are mixed. Sorting the rectangles by decreasing height
+/// before inserting them, as `insert_all` does, limits this waste.
+///
+/// It works with integer coordinates, such as texels, as well as with
+/// floating point ones.
+///
+/// ```rust
+/// use cgmath::atlas::SkylinePacker;
+/// use cgmath::{Aabb2, Point2, Vector2};
+///
+/// let mut packer = SkylinePacker::new(Aabb2::new(Point2::new(0, 0), Point2::new(256, 256)));
+/// let glyph = packer.insert(Vector2::new(12u32, 20)).unwrap();
+/// assert_eq!(glyph, Aabb2::new(Point2::new(0, 0), Point2::new(12, 20)));
+/// assert!(packer.insert(Vector2::new(300, 10)).is_none());
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct SkylinePacker<S> {
+    bounds: Aabb2<S>,
+    skyline: Vec<SkylineSegment<S>>,
+}
+
+impl<S: BaseNum> SkylinePacker<S> {
+    /// Construct an empty packer allocating rectangles inside `bounds`.
+    pub fn new(bounds: Aabb2<S>) -> SkylinePacker<S> {
+        SkylinePacker {
+            bounds,
+            skyline: vec![SkylineSegment {
+                x: bounds.min.x,
+                y: bounds.min.y,
+                width: bounds.max.x - bounds.min.x,
+            }],
+        }
+    }
+
+    /// The area of the atlas.
+    #[inline]
+    pub fn bounds(&self) -> Aabb2<S> {
+        self.bounds
+    }
+
+    /// Free all the allocated rectangles.
+    pub fn clear(&mut self) {
+        *self = SkylinePacker::new(self.bounds);
+    }
+
+    /// Allocate a rectangle of the given size, returning where it was
+    /// placed, or `None` if it does not fit in the remaining space.
+    ///
+    /// Rectangles with a zero or negative side take no room, and are placed
+    /// at the corner of the atlas.
+    pub fn insert(&mut self, size: Vector2<S>) -> Option<Aabb2<S>> {
+        if size.x <= S::zero() || size.y <= S::zero() {
+            let corner = self.bounds.min;
+            return Some(Aabb2::new(corner, corner));
+        }
+
+        // Find the lowest position for the rectangle, then the leftmost
+        // one among them.
+        let mut best: Option<(usize, S)> = None;
+        for i in 0..self.skyline.len() {
+            if let Some(y) = self.fit(i, size) {
+                match best {
+                    Some((_, best_y)) if best_y <= y => {}
+                    _ => best = Some((i, y)),
+                }
+            }
+        }
+
+        let (i, y) = best?;
+        let x = self.skyline[i].x;
+        self.raise(i, x, y + size.y, size.x);
+        Some(Aabb2::new(
+            Point2::new(x, y),
+            Point2::new(x + size.x, y + size.y),
+        ))
+    }
+
+    /// Allocate several rectangles, returning their placements in the same
+    /// order as `sizes`.
+    ///
+    /// The rectangles are inserted by decreasing height, which packs them
+    /// much more tightly than inserting them in an arbitrary order.
+    pub fn insert_all(&mut self, sizes: &[Vector2<S>]) -> Vec<Option<Aabb2<S>>> {
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (sizes[a], sizes[b]);
+            b.y.partial_cmp(&a.y)
+                .unwrap()
+                .then(b.x.partial_cmp(&a.x).unwrap())
+        });
+        let mut placements = vec![None; sizes.len()];
+        for i in order {
+            placements[i] = self.insert(sizes[i]);
+        }
+        placements
+    }
+
+    /// The bottom of a rectangle starting at the left of the `i`-th segment,
+    /// if it fits in the atlas.
+    fn fit(&self, i: usize, size: Vector2<S>) -> Option<S> {
+        let x = self.skyline[i].x;
+        if x + size.x > self.bounds.max.x {
+            return None;
+        }
+        let mut y = self.skyline[i].y;
+        let mut remaining = size.x;
+        for segment in &self.skyline[i..] {
+            if segment.y > y {
+                y = segment.y;
+            }
+            if y + size.y > self.bounds.max.y {
+                return None;
+            }
+            if segment.width >= remaining {
+                return Some(y);
+            }
+            remaining -= segment.width;
+        }
+        None
+    }
+
+    /// Raise the skyline to `y` over `width` from `x`, the left of the
+    /// `i`-th segment.
+    fn raise(&mut self, i: usize, x: S, y: S, width: S) {
+        let end = x + width;
+        self.skyline.insert(i, SkylineSegment { x, y, width });
+
+        // Remove or shorten the segments now below the new one.
+        let j = i + 1;
+        while j < self.skyline.len() {
+            let segment = self.skyline[j];
+            if segment.x + segment.width <= end {
+                self.skyline.remove(j);
+            } else {
+                if segment.x < end {
+                    self.skyline[j] = SkylineSegment {
+                        x: end,
+                        y: segment.y,
+                        width: segment.x + segment.width - end,
+                    };
+                }
+                break;
+            }
+        }
+
+        // Merge the neighbouring segments at the same height.
+        let mut j = 1;
+        while j < self.skyline.len() {
+            if self.skyline[j - 1].y == self.skyline[j].y {
+                let width = self.skyline[j].width;
+                self.skyline[j - 1].width += width;
+                self.skyline.remove(j);
+            } else {
+                j += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,383 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cubic Bézier curves.
+
+use std::cmp::Ordering;
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::{Aabb2, Aabb3};
+use num::BaseFloat;
+use plane::Plane;
+use point::{Point2, Point3};
+use polyline::{segment_distance2, Polyline2, Polyline3};
+use ray::Ray3;
+use vector::{Vector2, Vector3};
+
+/// A two-dimensional cubic Bézier curve, which starts at its first control
+/// point towards the second one, and ends at the last control point coming
+/// from the third one.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CubicBezier2<S> {
+    /// The control points of the curve, in order.
+    pub points: [Point2<S>; 4],
+}
+
+/// A three-dimensional cubic Bézier curve, which starts at its first
+/// control point towards the second one, and ends at the last control point
+/// coming from the third one.
+///
+/// ```rust
+/// use cgmath::{CubicBezier3, Point3, Vector3};
+///
+/// let curve = CubicBezier3::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(2.0, 1.0, 0.0),
+///     Point3::new(2.0, 2.0, 0.0),
+/// );
+/// assert_eq!(curve.point_at(0.5), Point3::new(1.375, 0.625, 0.0));
+/// assert_eq!(curve.derivative_at(0.0), Vector3::new(3.0, 0.0, 0.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CubicBezier3<S> {
+    /// The control points of the curve, in order.
+    pub points: [Point3<S>; 4],
+}
+
+/// The deepest subdivision when flattening a curve, which bounds the number
+/// of segments when the tolerance is too small to be reached.
+const MAX_FLATTEN_DEPTH: usize = 16;
+
+macro_rules! impl_cubic_bezier {
+    ($BezierN:ident, $PointN:ident, $VectorN:ident, $AabbN:ident, $PolylineN:ident) => {
+        impl<S: BaseFloat> $BezierN<S> {
+            /// Construct a curve from its control points.
+            #[inline]
+            pub fn new(
+                p0: $PointN<S>,
+                p1: $PointN<S>,
+                p2: $PointN<S>,
+                p3: $PointN<S>,
+            ) -> $BezierN<S> {
+                $BezierN {
+                    points: [p0, p1, p2, p3],
+                }
+            }
+
+            /// The curve with the same shape as the cubic Hermite curve from
+            /// `p0` to `p1`, with the derivatives `m0` and `m1` there.
+            #[inline]
+            pub fn from_hermite(
+                p0: $PointN<S>,
+                m0: $VectorN<S>,
+                p1: $PointN<S>,
+                m1: $VectorN<S>,
+            ) -> $BezierN<S> {
+                let three: S = cast(3).unwrap();
+                $BezierN::new(p0, p0 + m0 / three, p1 - m1 / three, p1)
+            }
+
+            /// The point of the curve at the parameter `t`, from the start
+            /// at zero to the end at one.
+            pub fn point_at(&self, t: S) -> $PointN<S> {
+                let [p0, p1, p2, p3] = self.points;
+                let three: S = cast(3).unwrap();
+                let s = S::one() - t;
+                let (b0, b1) = (s * s * s, three * s * s * t);
+                let (b2, b3) = (three * s * t * t, t * t * t);
+                $PointN::from_vec(
+                    p0.to_vec() * b0 + p1.to_vec() * b1 + p2.to_vec() * b2 + p3.to_vec() * b3,
+                )
+            }
+
+            /// The derivative of the curve with respect to its parameter at
+            /// `t`, which is tangent to it.
+            pub fn derivative_at(&self, t: S) -> $VectorN<S> {
+                let [p0, p1, p2, p3] = self.points;
+                let three: S = cast(3).unwrap();
+                let s = S::one() - t;
+                ((p1 - p0) * (s * s) + (p2 - p1) * (s * t * cast(2).unwrap()) + (p3 - p2) * (t * t))
+                    * three
+            }
+
+            /// Split the curve at the parameter `t` into the parts before
+            /// and after it, with de Casteljau's algorithm.
+            pub fn split(&self, t: S) -> ($BezierN<S>, $BezierN<S>) {
+                let [p0, p1, p2, p3] = self.points;
+                let lerp = |a: $PointN<S>, b: $PointN<S>| a + (b - a) * t;
+                let (a, b, c) = (lerp(p0, p1), lerp(p1, p2), lerp(p2, p3));
+                let (d, e) = (lerp(a, b), lerp(b, c));
+                let middle = lerp(d, e);
+                (
+                    $BezierN::new(p0, a, d, middle),
+                    $BezierN::new(middle, e, c, p3),
+                )
+            }
+
+            /// The bounding box of the control points, which contains the
+            /// curve but may be larger than it.
+            pub fn control_aabb(&self) -> $AabbN<S> {
+                let [p0, p1, p2, p3] = self.points;
+                $AabbN::new(p0, p3).grow(p1).grow(p2)
+            }
+
+            /// The tightest bounding box of the curve, made of its end
+            /// points and of the points where it turns around along an axis.
+            pub fn aabb(&self) -> $AabbN<S> {
+                let mut aabb = $AabbN::new(self.points[0], self.points[3]);
+                for axis in 0..$PointN::<S>::len() {
+                    let p = &self.points;
+                    let c = [p[0][axis], p[1][axis], p[2][axis], p[3][axis]];
+                    for t in turning_points(c) {
+                        aabb = aabb.grow(self.point_at(t));
+                    }
+                }
+                aabb
+            }
+
+            /// Approximate the curve by a polyline from its start to its end,
+            /// which stays closer than `tolerance` to it.
+            ///
+            /// The curve is halved until the inner control points of each
+            /// part are within the tolerance of the chord, so that the
+            /// segments are shorter where the curve bends more.
+            pub fn flatten(&self, tolerance: S) -> $PolylineN<S> {
+                let tolerance2 = tolerance * tolerance;
+                let two: S = cast(2).unwrap();
+                let mut points = vec![self.points[0]];
+                let mut stack = vec![(*self, 0)];
+                while let Some((curve, depth)) = stack.pop() {
+                    let [p0, p1, p2, p3] = curve.points;
+                    let flat = segment_distance2(p0, p3, p1) <= tolerance2
+                        && segment_distance2(p0, p3, p2) <= tolerance2;
+                    if flat || depth == MAX_FLATTEN_DEPTH {
+                        points.push(p3);
+                    } else {
+                        let (first, second) = curve.split(S::one() / two);
+                        stack.push((second, depth + 1));
+                        stack.push((first, depth + 1));
+                    }
+                }
+                $PolylineN::new(points)
+            }
+        }
+    };
+}
+
+impl_cubic_bezier!(CubicBezier2, Point2, Vector2, Aabb2, Polyline2);
+impl_cubic_bezier!(CubicBezier3, Point3, Vector3, Aabb3, Polyline3);
+
+impl<S: BaseFloat> CubicBezier3<S> {
+    /// The parameters at which the curve crosses or touches the plane, in
+    /// increasing order. A curve lying in the plane has none.
+    ///
+    /// The signed distance to the plane is a cubic polynomial along the
+    /// curve, which is split where it turns around, so that each part has
+    /// at most one root, found with Newton's method kept in its bracket by
+    /// bisection.
+    pub fn intersect_plane(&self, plane: &Plane<S>) -> Vec<S> {
+        let distance = |i: usize| plane.distance(self.points[i]);
+        let d = [distance(0), distance(1), distance(2), distance(3)];
+        let f = |t: S| bernstein(d, t);
+        let df = |t: S| bernstein_derivative(d, t);
+
+        let mut breaks = vec![S::zero()];
+        breaks.extend(turning_points(d));
+        breaks.push(S::one());
+
+        let mut roots = Vec::new();
+        if d.iter().all(|&x| x == S::zero()) {
+            return roots;
+        }
+        for w in breaks.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            let (fa, fb) = (f(a), f(b));
+            if fa == S::zero() {
+                if roots.last() != Some(&a) {
+                    roots.push(a);
+                }
+            } else if fb != S::zero() && (fa < S::zero()) != (fb < S::zero()) {
+                roots.push(refine_root(&f, &df, a, b));
+            }
+        }
+        if f(S::one()) == S::zero() && roots.last() != Some(&S::one()) {
+            roots.push(S::one());
+        }
+        roots
+    }
+
+    /// The parameters of the points of the curve and of the ray that come
+    /// closest to each other, such as the part of a curve under the mouse
+    /// in an editor.
+    ///
+    /// The curve is sampled to find the neighbourhood of the closest
+    /// approach, which is then refined by bisection on the derivative of
+    /// the distance. When several parts of the curve are about as close,
+    /// any of them may be returned.
+    pub fn closest_to_ray(&self, ray: &Ray3<S>) -> (S, S) {
+        const SAMPLES: usize = 32;
+        let length2 = ray.direction.magnitude2();
+        let on_ray = |p: Point3<S>| ((p - ray.origin).dot(ray.direction) / length2).max(S::zero());
+        let distance2 = |t: S| {
+            let p = self.point_at(t);
+            (p - ray.point_at(on_ray(p))).magnitude2()
+        };
+        // Half the derivative of the squared distance, where the offset from
+        // the ray is perpendicular to it, or points to its origin.
+        let slope = |t: S| {
+            let p = self.point_at(t);
+            (p - ray.point_at(on_ray(p))).dot(self.derivative_at(t))
+        };
+        let samples: S = cast(SAMPLES).unwrap();
+        let parameter = |i: usize| cast::<usize, S>(i).unwrap() / samples;
+        let best = (0..=SAMPLES)
+            .min_by(|&i, &j| {
+                distance2(parameter(i))
+                    .partial_cmp(&distance2(parameter(j)))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+
+        let mut candidates = vec![parameter(best)];
+        if best > 0 && slope(parameter(best - 1)) < S::zero() && slope(parameter(best)) > S::zero()
+        {
+            candidates.push(bisect(&slope, parameter(best - 1), parameter(best)));
+        }
+        if best < SAMPLES
+            && slope(parameter(best)) < S::zero()
+            && slope(parameter(best + 1)) > S::zero()
+        {
+            candidates.push(bisect(&slope, parameter(best), parameter(best + 1)));
+        }
+        let t = candidates
+            .into_iter()
+            .min_by(|&a, &b| {
+                distance2(a)
+                    .partial_cmp(&distance2(b))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap();
+        (t, on_ray(self.point_at(t)))
+    }
+}
+
+/// The cubic polynomial with the Bernstein coefficients `c` at `t`.
+fn bernstein<S: BaseFloat>(c: [S; 4], t: S) -> S {
+    let three: S = cast(3).unwrap();
+    let s = S::one() - t;
+    c[0] * s * s * s + c[1] * three * s * s * t + c[2] * three * s * t * t + c[3] * t * t * t
+}
+
+/// The derivative of the cubic polynomial with the Bernstein coefficients
+/// `c` at `t`.
+fn bernstein_derivative<S: BaseFloat>(c: [S; 4], t: S) -> S {
+    let three: S = cast(3).unwrap();
+    let two: S = cast(2).unwrap();
+    let s = S::one() - t;
+    three * ((c[1] - c[0]) * s * s + (c[2] - c[1]) * two * s * t + (c[3] - c[2]) * t * t)
+}
+
+/// The parameters strictly between zero and one where the cubic polynomial
+/// with the Bernstein coefficients `c` turns around, in increasing order.
+fn turning_points<S: BaseFloat>(c: [S; 4]) -> Vec<S> {
+    let (e0, e1, e2) = (c[1] - c[0], c[2] - c[1], c[3] - c[2]);
+    let two: S = cast(2).unwrap();
+    let mut turns = quadratic_roots(e0 - two * e1 + e2, two * (e1 - e0), e0);
+    turns.retain(|&t| t > S::zero() && t < S::one());
+    turns.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    turns
+}
+
+/// The real roots of `a t² + b t + c`.
+fn quadratic_roots<S: BaseFloat>(a: S, b: S, c: S) -> Vec<S> {
+    let scale = a.abs().max(b.abs()).max(c.abs());
+    if scale == S::zero() {
+        return Vec::new();
+    }
+    if implicit_ulps_eq!(a, S::zero(), epsilon = S::default_epsilon() * scale) {
+        return if b == S::zero() {
+            Vec::new()
+        } else {
+            vec![-c / b]
+        };
+    }
+    let two: S = cast(2).unwrap();
+    let four: S = cast(4).unwrap();
+    let discriminant = b * b - four * a * c;
+    if discriminant < S::zero() {
+        return Vec::new();
+    }
+    // Avoid the cancellation of the textbook formula.
+    let q = -(b + b.signum() * discriminant.sqrt()) / two;
+    if q == S::zero() {
+        return vec![S::zero()];
+    }
+    vec![q / a, c / q]
+}
+
+/// The root of `f` between `low` and `high`, where it changes sign, with
+/// Newton's method falling back to bisection when it leaves the bracket.
+fn refine_root<S, F, D>(f: &F, df: &D, mut low: S, mut high: S) -> S
+where
+    S: BaseFloat,
+    F: Fn(S) -> S,
+    D: Fn(S) -> S,
+{
+    let two: S = cast(2).unwrap();
+    let rising = f(high) > f(low);
+    let mut t = (low + high) / two;
+    for _ in 0..64 {
+        let value = f(t);
+        if value == S::zero() {
+            break;
+        }
+        if (value < S::zero()) == rising {
+            low = t;
+        } else {
+            high = t;
+        }
+        let next = t - value / df(t);
+        t = if next > low && next < high {
+            next
+        } else {
+            (low + high) / two
+        };
+        if implicit_ulps_eq!(high - low, S::zero(), epsilon = S::default_epsilon()) {
+            break;
+        }
+    }
+    t
+}
+
+/// The point between `low` and `high` where `f` goes from negative to
+/// positive, by bisection.
+fn bisect<S: BaseFloat, F: Fn(S) -> S>(f: &F, mut low: S, mut high: S) -> S {
+    let two: S = cast(2).unwrap();
+    for _ in 0..64 {
+        let middle = (low + high) / two;
+        if f(middle) < S::zero() {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / two
+}
@@ -0,0 +1,164 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::cell::Cell;
+use std::fmt;
+
+use matrix::{Matrix3, Matrix4};
+use num::BaseFloat;
+use rotation::Rotation3;
+use transform::{Decomposed, Transform};
+use vector::Vector3;
+
+/// A transform which computes its matrix, the inverse of its matrix and its
+/// normal matrix when they are first needed, and keeps them until it is
+/// modified.
+///
+/// Scene graphs typically need these matrices for the same nodes several
+/// times per frame, while only a few nodes move. The matrices are cached in
+/// cells, so they can be computed through a shared reference, and the cache
+/// is cleared whenever the transform is set or borrowed mutably. The cells
+/// make the wrapper `Send` but not `Sync`.
+///
+/// ```rust
+/// use cgmath::{CachedTransform, Decomposed, Quaternion, Rotation3, Vector3, Deg};
+///
+/// let mut node = CachedTransform::new(Decomposed {
+///     scale: 2.0f64,
+///     rot: Quaternion::from_angle_y(Deg(90.0)),
+///     disp: Vector3::new(1.0, 0.0, 0.0),
+/// });
+/// let model = node.matrix();
+/// assert_eq!(node.matrix(), model);
+/// node.transform_mut().disp.y = 5.0;
+/// assert_eq!(node.matrix().w, model.w + Vector3::new(0.0, 5.0, 0.0).extend(0.0));
+/// ```
+#[derive(Clone)]
+pub struct CachedTransform<S: BaseFloat, R> {
+    transform: Decomposed<Vector3<S>, R>,
+    matrix: Cell<Option<Matrix4<S>>>,
+    inverse: Cell<Option<Option<Matrix4<S>>>>,
+    normal: Cell<Option<Option<Matrix3<S>>>>,
+}
+
+impl<S: BaseFloat, R: Rotation3<S>> CachedTransform<S, R> {
+    /// Wrap a transform, with nothing computed yet.
+    pub fn new(transform: Decomposed<Vector3<S>, R>) -> CachedTransform<S, R> {
+        CachedTransform {
+            transform,
+            matrix: Cell::new(None),
+            inverse: Cell::new(None),
+            normal: Cell::new(None),
+        }
+    }
+
+    /// The transform.
+    #[inline]
+    pub fn transform(&self) -> &Decomposed<Vector3<S>, R> {
+        &self.transform
+    }
+
+    /// Borrow the transform mutably, clearing the cached matrices.
+    pub fn transform_mut(&mut self) -> &mut Decomposed<Vector3<S>, R> {
+        self.invalidate();
+        &mut self.transform
+    }
+
+    /// Replace the transform, clearing the cached matrices.
+    pub fn set(&mut self, transform: Decomposed<Vector3<S>, R>) {
+        self.invalidate();
+        self.transform = transform;
+    }
+
+    /// Unwrap the transform.
+    pub fn into_inner(self) -> Decomposed<Vector3<S>, R> {
+        self.transform
+    }
+
+    /// Whether the matrix of the transform is cached.
+    pub fn is_cached(&self) -> bool {
+        self.matrix.get().is_some()
+    }
+
+    fn invalidate(&mut self) {
+        self.matrix.set(None);
+        self.inverse.set(None);
+        self.normal.set(None);
+    }
+
+    /// The matrix of the transform.
+    pub fn matrix(&self) -> Matrix4<S> {
+        if let Some(m) = self.matrix.get() {
+            return m;
+        }
+        let m = self.transform.into();
+        self.matrix.set(Some(m));
+        m
+    }
+
+    /// The matrix of the inverse of the transform, or `None` if its scale
+    /// is zero.
+    ///
+    /// This inverts the decomposed transform, which is cheaper and more
+    /// accurate than inverting its matrix.
+    pub fn inverse_matrix(&self) -> Option<Matrix4<S>> {
+        if let Some(m) = self.inverse.get() {
+            return m;
+        }
+        let m = self.transform.inverse_transform().map(Into::into);
+        self.inverse.set(Some(m));
+        m
+    }
+
+    /// The matrix transforming the normals of the transformed geometry, the
+    /// inverse of the transpose of the linear part of the matrix, or `None`
+    /// if the scale of the transform is zero.
+    ///
+    /// As the scale is uniform, this is the rotation divided by the scale,
+    /// so it keeps normals perpendicular to surfaces but not of unit length
+    /// unless the scale is one.
+    pub fn normal_matrix(&self) -> Option<Matrix3<S>> {
+        if let Some(m) = self.normal.get() {
+            return m;
+        }
+        let m = if self.transform.scale == S::zero() {
+            None
+        } else {
+            let rotation: Matrix3<S> = self.transform.rot.into();
+            Some(rotation / self.transform.scale)
+        };
+        self.normal.set(Some(m));
+        m
+    }
+}
+
+impl<S: BaseFloat, R: Rotation3<S>> From<Decomposed<Vector3<S>, R>> for CachedTransform<S, R> {
+    fn from(transform: Decomposed<Vector3<S>, R>) -> CachedTransform<S, R> {
+        CachedTransform::new(transform)
+    }
+}
+
+impl<S: BaseFloat, R: Rotation3<S> + PartialEq> PartialEq for CachedTransform<S, R> {
+    fn eq(&self, other: &CachedTransform<S, R>) -> bool {
+        self.transform == other.transform
+    }
+}
+
+impl<S: BaseFloat, R: fmt::Debug> fmt::Debug for CachedTransform<S, R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CachedTransform")
+            .field("transform", &self.transform)
+            .finish()
+    }
+}
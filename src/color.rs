@@ -0,0 +1,977 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Color types, with conversions between linear and sRGB encoded values.
+//!
+//! Lighting and blending should be done on the linear `Rgb` and `Rgba`
+//! types, which support component-wise arithmetic. The `Srgb` and `Srgba`
+//! types hold gamma encoded values, as found in images, color pickers and
+//! most color literals, and only provide conversions. `Hsv` and `Hsl` are
+//! cylindrical representations of sRGB encoded colors, convenient for picking
+//! and generating palettes. `Xyz` and `Xyy` are the device independent CIE
+//! 1931 spaces, in which white points are given and adapted.
+//!
+//! High dynamic range colors are brought to the range of a display with
+//! `Rgb::expose` and `Rgb::tonemap`, before being encoded to sRGB. The
+//! curves are the usual ones of shaders, so that previews and bakes on the
+//! CPU match the rendered images.
+//!
+//! ```rust
+//! use cgmath::color::{Rgb, Srgb};
+//!
+//! let orange = Rgb::from(Srgb::new(1.0, 0.5, 0.0));
+//! let dimmed = orange * 0.5;
+//! assert_eq!(Srgb::from(dimmed).to_u8(), [188, 92, 0]);
+//! ```
+
+use num_traits::{cast, clamp};
+use std::ops::*;
+
+use structure::*;
+
+use angle::Deg;
+use approx;
+use matrix::Matrix3;
+use num::BaseFloat;
+use vector::{Vector3, Vector4};
+
+/// A color in linear RGB space.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rgb<S> {
+    pub r: S,
+    pub g: S,
+    pub b: S,
+}
+
+/// A color in linear RGB space, with a linear alpha channel.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rgba<S> {
+    pub r: S,
+    pub g: S,
+    pub b: S,
+    pub a: S,
+}
+
+/// A color encoded with the sRGB transfer function.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Srgb<S> {
+    pub r: S,
+    pub g: S,
+    pub b: S,
+}
+
+/// A color encoded with the sRGB transfer function, with a linear alpha
+/// channel.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Srgba<S> {
+    pub r: S,
+    pub g: S,
+    pub b: S,
+    pub a: S,
+}
+
+/// Convert an sRGB encoded component to linear.
+pub fn srgb_to_linear<S: BaseFloat>(c: S) -> S {
+    let threshold: S = cast(0.04045).unwrap();
+    if c <= threshold {
+        c / cast(12.92).unwrap()
+    } else {
+        let offset: S = cast(0.055).unwrap();
+        ((c + offset) / (S::one() + offset)).powf(cast(2.4).unwrap())
+    }
+}
+
+/// Convert a linear component to sRGB encoding.
+pub fn linear_to_srgb<S: BaseFloat>(c: S) -> S {
+    let threshold: S = cast(0.003_130_8).unwrap();
+    if c <= threshold {
+        c * cast(12.92).unwrap()
+    } else {
+        let offset: S = cast(0.055).unwrap();
+        (S::one() + offset) * c.powf(S::one() / cast(2.4).unwrap()) - offset
+    }
+}
+
+/// The Reinhard tonemapping curve, `x / (1 + x)`, which compresses all
+/// values into `[0, 1)` and never reaches white.
+#[inline]
+pub fn reinhard<S: BaseFloat>(x: S) -> S {
+    x / (S::one() + x)
+}
+
+/// The extended Reinhard tonemapping curve, which maps `white` and brighter
+/// values to one and otherwise follows `reinhard` closely in the shadows.
+#[inline]
+pub fn reinhard_extended<S: BaseFloat>(x: S, white: S) -> S {
+    (x * (S::one() + x / (white * white)) / (S::one() + x)).min(S::one())
+}
+
+/// Krzysztof Narkowicz's fit of the filmic curve of the ACES reference
+/// rendering transform, clamped to `[0, 1]`, as used by many real-time
+/// renderers.
+///
+/// The fit itself has no exposure bias: the original article scales the
+/// color by `0.6` before applying it, to match the brightness of ACES.
+pub fn aces_filmic<S: BaseFloat>(x: S) -> S {
+    let a: S = cast(2.51).unwrap();
+    let b: S = cast(0.03).unwrap();
+    let c: S = cast(2.43).unwrap();
+    let d: S = cast(0.59).unwrap();
+    let e: S = cast(0.14).unwrap();
+    clamp(x * (a * x + b) / (x * (c * x + d) + e), S::zero(), S::one())
+}
+
+/// The exposure value at ISO 100 of a camera with an aperture of f-number
+/// `aperture`, a shutter opened for `shutter_time` seconds and a
+/// sensitivity of `iso`, as a number of stops.
+pub fn ev100<S: BaseFloat>(aperture: S, shutter_time: S, iso: S) -> S {
+    let hundred: S = cast(100).unwrap();
+    (aperture * aperture / shutter_time * hundred / iso).log2()
+}
+
+/// The factor scaling luminance in candelas per square meter to the `[0, 1]`
+/// range of a sensor exposed at `ev100`, following the saturation based
+/// sensitivity of ISO 12232, as in "Moving Frostbite to Physically Based
+/// Rendering" by Sébastien Lagarde and Charles de Rousiers.
+pub fn exposure_from_ev100<S: BaseFloat>(ev100: S) -> S {
+    let two: S = cast(2).unwrap();
+    S::one() / (cast::<f64, S>(1.2).unwrap() * two.powf(ev100))
+}
+
+/// A tonemapping curve, mapping the unbounded components of a linear color
+/// to the `[0, 1]` range of a display.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Tonemap<S> {
+    /// Clamp the components, losing the highlights.
+    Clamp,
+    /// The `reinhard` curve.
+    Reinhard,
+    /// The `reinhard_extended` curve with the given white point.
+    ReinhardExtended(S),
+    /// The `aces_filmic` curve.
+    AcesFilmic,
+}
+
+impl<S: BaseFloat> Tonemap<S> {
+    /// Apply the curve to a component.
+    pub fn apply(self, x: S) -> S {
+        match self {
+            Tonemap::Clamp => clamp(x, S::zero(), S::one()),
+            Tonemap::Reinhard => reinhard(x),
+            Tonemap::ReinhardExtended(white) => reinhard_extended(x, white),
+            Tonemap::AcesFilmic => aces_filmic(x),
+        }
+    }
+}
+
+#[inline]
+fn to_u8<S: BaseFloat>(c: S) -> u8 {
+    if c.is_nan() {
+        return 0;
+    }
+    let max: S = cast(255).unwrap();
+    cast(clamp(c, S::zero(), S::one()) * max + cast(0.5).unwrap()).unwrap()
+}
+
+#[inline]
+fn from_u8<S: BaseFloat>(c: u8) -> S {
+    let max: S = cast(255).unwrap();
+    S::from(c).unwrap() / max
+}
+
+macro_rules! impl_color_common {
+    ($Color:ident { $($field:ident: $vfield:ident),+ }, $VectorN:ident, $n:expr) => {
+        impl<S> $Color<S> {
+            /// Construct a new color from its components.
+            #[inline]
+            pub const fn new($($field: S),+) -> $Color<S> {
+                $Color { $($field),+ }
+            }
+        }
+
+        impl<S: BaseFloat> $Color<S> {
+            /// Clamp every component to the `[0, 1]` range.
+            #[inline]
+            pub fn saturate(self) -> $Color<S> {
+                $Color { $($field: clamp(self.$field, S::zero(), S::one())),+ }
+            }
+
+            /// Quantize the color to 8 bits per component, clamping values
+            /// outside of the `[0, 1]` range and taking NaNs as zero.
+            #[inline]
+            pub fn to_u8(self) -> [u8; $n] {
+                [$(to_u8(self.$field)),+]
+            }
+
+            /// Construct a color from 8 bit components.
+            #[inline]
+            pub fn from_u8(c: [u8; $n]) -> $Color<S> {
+                let [$($field),+] = c;
+                $Color { $($field: from_u8($field)),+ }
+            }
+        }
+
+        impl<S> From<$VectorN<S>> for $Color<S> {
+            #[inline]
+            fn from(v: $VectorN<S>) -> $Color<S> {
+                $Color { $($field: v.$vfield),+ }
+            }
+        }
+
+        impl<S> From<$Color<S>> for $VectorN<S> {
+            #[inline]
+            fn from(c: $Color<S>) -> $VectorN<S> {
+                $VectorN { $($vfield: c.$field),+ }
+            }
+        }
+
+        impl<S> From<[S; $n]> for $Color<S> {
+            #[inline]
+            fn from(c: [S; $n]) -> $Color<S> {
+                let [$($field),+] = c;
+                $Color { $($field),+ }
+            }
+        }
+
+        impl<S> From<$Color<S>> for [S; $n] {
+            #[inline]
+            fn from(c: $Color<S>) -> [S; $n] {
+                [$(c.$field),+]
+            }
+        }
+
+        impl<S: BaseFloat> approx::AbsDiffEq for $Color<S> {
+            type Epsilon = S::Epsilon;
+
+            #[inline]
+            fn default_epsilon() -> S::Epsilon {
+                S::default_epsilon()
+            }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+                $(S::abs_diff_eq(&self.$field, &other.$field, epsilon))&&+
+            }
+        }
+
+        impl<S: BaseFloat> approx::RelativeEq for $Color<S> {
+            #[inline]
+            fn default_max_relative() -> S::Epsilon {
+                S::default_max_relative()
+            }
+
+            #[inline]
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: S::Epsilon,
+                max_relative: S::Epsilon,
+            ) -> bool {
+                $(S::relative_eq(&self.$field, &other.$field, epsilon, max_relative))&&+
+            }
+        }
+
+        impl<S: BaseFloat> approx::UlpsEq for $Color<S> {
+            #[inline]
+            fn default_max_ulps() -> u32 {
+                S::default_max_ulps()
+            }
+
+            #[inline]
+            fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+                $(S::ulps_eq(&self.$field, &other.$field, epsilon, max_ulps))&&+
+            }
+        }
+    };
+}
+
+macro_rules! impl_linear_color {
+    ($Color:ident { $($field:ident),+ }) => {
+        impl<S: BaseFloat> $Color<S> {
+            /// Linearly interpolate between two colors.
+            #[inline]
+            pub fn lerp(self, other: $Color<S>, amount: S) -> $Color<S> {
+                self + (other - self) * amount
+            }
+        }
+
+        impl<S: BaseFloat> Interpolate<S> for $Color<S> {
+            #[inline]
+            fn barycentric(values: ($Color<S>, $Color<S>, $Color<S>), weights: Vector3<S>) -> $Color<S> {
+                values.0 * weights.x + values.1 * weights.y + values.2 * weights.z
+            }
+        }
+
+        impl_operator!(<S: BaseFloat> Add<$Color<S> > for $Color<S> {
+            fn add(lhs, rhs) -> $Color<S> { $Color::new($(lhs.$field + rhs.$field),+) }
+        });
+        impl_assignment_operator!(<S: BaseFloat> AddAssign<$Color<S> > for $Color<S> {
+            fn add_assign(&mut self, other) { $(self.$field += other.$field);+ }
+        });
+
+        impl_operator!(<S: BaseFloat> Sub<$Color<S> > for $Color<S> {
+            fn sub(lhs, rhs) -> $Color<S> { $Color::new($(lhs.$field - rhs.$field),+) }
+        });
+        impl_assignment_operator!(<S: BaseFloat> SubAssign<$Color<S> > for $Color<S> {
+            fn sub_assign(&mut self, other) { $(self.$field -= other.$field);+ }
+        });
+
+        impl_operator!(<S: BaseFloat> Mul<$Color<S> > for $Color<S> {
+            fn mul(lhs, rhs) -> $Color<S> { $Color::new($(lhs.$field * rhs.$field),+) }
+        });
+        impl_assignment_operator!(<S: BaseFloat> MulAssign<$Color<S> > for $Color<S> {
+            fn mul_assign(&mut self, other) { $(self.$field *= other.$field);+ }
+        });
+
+        impl_operator!(<S: BaseFloat> Mul<S> for $Color<S> {
+            fn mul(color, scalar) -> $Color<S> { $Color::new($(color.$field * scalar),+) }
+        });
+        impl_assignment_operator!(<S: BaseFloat> MulAssign<S> for $Color<S> {
+            fn mul_assign(&mut self, scalar) { $(self.$field *= scalar);+ }
+        });
+
+        impl_operator!(<S: BaseFloat> Div<S> for $Color<S> {
+            fn div(color, scalar) -> $Color<S> { $Color::new($(color.$field / scalar),+) }
+        });
+        impl_assignment_operator!(<S: BaseFloat> DivAssign<S> for $Color<S> {
+            fn div_assign(&mut self, scalar) { $(self.$field /= scalar);+ }
+        });
+    };
+}
+
+impl_color_common!(Rgb { r: x, g: y, b: z }, Vector3, 3);
+impl_color_common!(
+    Rgba {
+        r: x,
+        g: y,
+        b: z,
+        a: w
+    },
+    Vector4,
+    4
+);
+impl_color_common!(Srgb { r: x, g: y, b: z }, Vector3, 3);
+impl_color_common!(
+    Srgba {
+        r: x,
+        g: y,
+        b: z,
+        a: w
+    },
+    Vector4,
+    4
+);
+
+impl_linear_color!(Rgb { r, g, b });
+impl_linear_color!(Rgba { r, g, b, a });
+
+impl<S> Rgb<S> {
+    /// Add an alpha channel to the color.
+    #[inline]
+    pub fn extend(self, a: S) -> Rgba<S> {
+        Rgba::new(self.r, self.g, self.b, a)
+    }
+}
+
+impl<S: BaseFloat> Rgb<S> {
+    /// The relative luminance of the color, with the weights of the
+    /// Rec. 709 primaries shared by sRGB.
+    #[inline]
+    pub fn luminance(self) -> S {
+        let r: S = cast(0.2126).unwrap();
+        let g: S = cast(0.7152).unwrap();
+        let b: S = cast(0.0722).unwrap();
+        self.r * r + self.g * g + self.b * b
+    }
+
+    /// Brighten the color by `ev` stops, or darken it if `ev` is negative,
+    /// doubling it for every stop.
+    #[inline]
+    pub fn expose(self, ev: S) -> Rgb<S> {
+        let two: S = cast(2).unwrap();
+        self * two.powf(ev)
+    }
+
+    /// Map every component of the color with a tonemapping curve.
+    #[inline]
+    pub fn tonemap(self, curve: Tonemap<S>) -> Rgb<S> {
+        Rgb::new(
+            curve.apply(self.r),
+            curve.apply(self.g),
+            curve.apply(self.b),
+        )
+    }
+}
+
+impl<S> Rgba<S> {
+    /// Drop the alpha channel of the color.
+    #[inline]
+    pub fn truncate(self) -> Rgb<S> {
+        Rgb::new(self.r, self.g, self.b)
+    }
+}
+
+impl<S> Srgb<S> {
+    /// Add an alpha channel to the color.
+    #[inline]
+    pub fn extend(self, a: S) -> Srgba<S> {
+        Srgba::new(self.r, self.g, self.b, a)
+    }
+}
+
+impl<S> Srgba<S> {
+    /// Drop the alpha channel of the color.
+    #[inline]
+    pub fn truncate(self) -> Srgb<S> {
+        Srgb::new(self.r, self.g, self.b)
+    }
+}
+
+impl<S: BaseFloat> Srgba<S> {
+    /// Pack the color into a `u32`, with the red component in the most
+    /// significant byte and alpha in the least significant byte.
+    #[inline]
+    pub fn to_u32(self) -> u32 {
+        let [r, g, b, a] = self.to_u8();
+        u32::from_be_bytes([r, g, b, a])
+    }
+
+    /// Unpack a color packed by `to_u32`, such as a `0xRRGGBBAA` literal.
+    #[inline]
+    pub fn from_u32(c: u32) -> Srgba<S> {
+        Srgba::from_u8(c.to_be_bytes())
+    }
+}
+
+impl<S: BaseFloat> From<Srgb<S>> for Rgb<S> {
+    #[inline]
+    fn from(c: Srgb<S>) -> Rgb<S> {
+        Rgb::new(
+            srgb_to_linear(c.r),
+            srgb_to_linear(c.g),
+            srgb_to_linear(c.b),
+        )
+    }
+}
+
+impl<S: BaseFloat> From<Rgb<S>> for Srgb<S> {
+    #[inline]
+    fn from(c: Rgb<S>) -> Srgb<S> {
+        Srgb::new(
+            linear_to_srgb(c.r),
+            linear_to_srgb(c.g),
+            linear_to_srgb(c.b),
+        )
+    }
+}
+
+impl<S: BaseFloat> From<Srgba<S>> for Rgba<S> {
+    #[inline]
+    fn from(c: Srgba<S>) -> Rgba<S> {
+        Rgb::from(c.truncate()).extend(c.a)
+    }
+}
+
+impl<S: BaseFloat> From<Rgba<S>> for Srgba<S> {
+    #[inline]
+    fn from(c: Rgba<S>) -> Srgba<S> {
+        Srgb::from(c.truncate()).extend(c.a)
+    }
+}
+
+/// A color given by its hue, saturation and value, as a cylindrical
+/// representation of an sRGB encoded color.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hsv<S> {
+    /// The hue, in the `[0, 360)` range with red at zero.
+    pub hue: Deg<S>,
+    /// The saturation, from grey at zero to the pure hue at one.
+    pub saturation: S,
+    /// The value, from black at zero to the brightest color at one.
+    pub value: S,
+}
+
+/// A color given by its hue, saturation and lightness, as a cylindrical
+/// representation of an sRGB encoded color.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hsl<S> {
+    /// The hue, in the `[0, 360)` range with red at zero.
+    pub hue: Deg<S>,
+    /// The saturation, from grey at zero to the pure hue at one.
+    pub saturation: S,
+    /// The lightness, from black at zero through the pure hue at one half to
+    /// white at one.
+    pub lightness: S,
+}
+
+/// Compute the hue of an RGB color from its components, largest component
+/// and chroma.
+fn rgb_hue<S: BaseFloat>(c: Srgb<S>, max: S, chroma: S) -> Deg<S> {
+    if chroma == S::zero() {
+        return Deg(S::zero());
+    }
+    let sixth: S = cast(60).unwrap();
+    let two: S = cast(2).unwrap();
+    let four: S = cast(4).unwrap();
+    let sector = if max == c.r {
+        (c.g - c.b) / chroma
+    } else if max == c.g {
+        (c.b - c.r) / chroma + two
+    } else {
+        (c.r - c.g) / chroma + four
+    };
+    Deg(sector * sixth).normalize()
+}
+
+/// Compute the RGB color with a given hue and chroma, and its smallest
+/// component at zero.
+fn hue_chroma_rgb<S: BaseFloat>(hue: Deg<S>, chroma: S) -> Srgb<S> {
+    let sector = hue.normalize().0 / cast(60).unwrap();
+    let two: S = cast(2).unwrap();
+    let x = chroma * (S::one() - (sector % two - S::one()).abs());
+    let z = S::zero();
+    match sector.to_u8().unwrap_or(0) {
+        0 => Srgb::new(chroma, x, z),
+        1 => Srgb::new(x, chroma, z),
+        2 => Srgb::new(z, chroma, x),
+        3 => Srgb::new(z, x, chroma),
+        4 => Srgb::new(x, z, chroma),
+        _ => Srgb::new(chroma, z, x),
+    }
+}
+
+/// Interpolate between two hues along the shortest arc.
+#[inline]
+fn lerp_hue<S: BaseFloat>(a: Deg<S>, b: Deg<S>, amount: S) -> Deg<S> {
+    (a + (b - a).normalize_signed() * amount).normalize()
+}
+
+impl<S: BaseFloat> Hsv<S> {
+    /// Construct a new color from its hue, saturation and value.
+    #[inline]
+    pub fn new<A: Into<Deg<S>>>(hue: A, saturation: S, value: S) -> Hsv<S> {
+        Hsv {
+            hue: hue.into(),
+            saturation,
+            value,
+        }
+    }
+
+    /// Interpolate between two colors, taking the shortest way around the
+    /// hue circle.
+    pub fn lerp(self, other: Hsv<S>, amount: S) -> Hsv<S> {
+        Hsv {
+            hue: lerp_hue(self.hue, other.hue, amount),
+            saturation: self.saturation + (other.saturation - self.saturation) * amount,
+            value: self.value + (other.value - self.value) * amount,
+        }
+    }
+}
+
+impl<S: BaseFloat> Hsl<S> {
+    /// Construct a new color from its hue, saturation and lightness.
+    #[inline]
+    pub fn new<A: Into<Deg<S>>>(hue: A, saturation: S, lightness: S) -> Hsl<S> {
+        Hsl {
+            hue: hue.into(),
+            saturation,
+            lightness,
+        }
+    }
+
+    /// Interpolate between two colors, taking the shortest way around the
+    /// hue circle.
+    pub fn lerp(self, other: Hsl<S>, amount: S) -> Hsl<S> {
+        Hsl {
+            hue: lerp_hue(self.hue, other.hue, amount),
+            saturation: self.saturation + (other.saturation - self.saturation) * amount,
+            lightness: self.lightness + (other.lightness - self.lightness) * amount,
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Srgb<S>> for Hsv<S> {
+    fn from(c: Srgb<S>) -> Hsv<S> {
+        let max = c.r.max(c.g).max(c.b);
+        let chroma = max - c.r.min(c.g).min(c.b);
+        let saturation = if max == S::zero() {
+            S::zero()
+        } else {
+            chroma / max
+        };
+        Hsv {
+            hue: rgb_hue(c, max, chroma),
+            saturation,
+            value: max,
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Hsv<S>> for Srgb<S> {
+    fn from(c: Hsv<S>) -> Srgb<S> {
+        let chroma = c.value * c.saturation;
+        let m = c.value - chroma;
+        let rgb = hue_chroma_rgb(c.hue, chroma);
+        Srgb::new(rgb.r + m, rgb.g + m, rgb.b + m)
+    }
+}
+
+impl<S: BaseFloat> From<Srgb<S>> for Hsl<S> {
+    fn from(c: Srgb<S>) -> Hsl<S> {
+        let max = c.r.max(c.g).max(c.b);
+        let min = c.r.min(c.g).min(c.b);
+        let chroma = max - min;
+        let two: S = cast(2).unwrap();
+        let lightness = (max + min) / two;
+        let saturation = if chroma == S::zero() {
+            S::zero()
+        } else {
+            chroma / (S::one() - (two * lightness - S::one()).abs())
+        };
+        Hsl {
+            hue: rgb_hue(c, max, chroma),
+            saturation,
+            lightness,
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Hsl<S>> for Srgb<S> {
+    fn from(c: Hsl<S>) -> Srgb<S> {
+        let two: S = cast(2).unwrap();
+        let chroma = (S::one() - (two * c.lightness - S::one()).abs()) * c.saturation;
+        let m = c.lightness - chroma / two;
+        let rgb = hue_chroma_rgb(c.hue, chroma);
+        Srgb::new(rgb.r + m, rgb.g + m, rgb.b + m)
+    }
+}
+
+impl<S: BaseFloat> From<Hsv<S>> for Hsl<S> {
+    #[inline]
+    fn from(c: Hsv<S>) -> Hsl<S> {
+        Hsl::from(Srgb::from(c))
+    }
+}
+
+impl<S: BaseFloat> From<Hsl<S>> for Hsv<S> {
+    #[inline]
+    fn from(c: Hsl<S>) -> Hsv<S> {
+        Hsv::from(Srgb::from(c))
+    }
+}
+
+/// A color in the CIE 1931 XYZ space, where `y` is the luminance.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Xyz<S> {
+    pub x: S,
+    pub y: S,
+    pub z: S,
+}
+
+/// A color given by its CIE 1931 chromaticity coordinates `x` and `y`, and
+/// its luminance, the `Y` of XYZ.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Xyy<S> {
+    pub x: S,
+    pub y: S,
+    pub luminance: S,
+}
+
+impl<S> Xyz<S> {
+    /// Construct a new color from its components.
+    #[inline]
+    pub const fn new(x: S, y: S, z: S) -> Xyz<S> {
+        Xyz { x, y, z }
+    }
+}
+
+impl<S: BaseFloat> Xyz<S> {
+    /// The white point of the CIE standard illuminant D65, the white of
+    /// sRGB, with a luminance of one.
+    #[inline]
+    pub fn d65() -> Xyz<S> {
+        Xyz::new(cast(0.950_47).unwrap(), S::one(), cast(1.088_83).unwrap())
+    }
+
+    /// The white point of the CIE standard illuminant D50, the white of
+    /// print and of ICC profiles, with a luminance of one.
+    #[inline]
+    pub fn d50() -> Xyz<S> {
+        Xyz::new(cast(0.964_22).unwrap(), S::one(), cast(0.825_21).unwrap())
+    }
+}
+
+impl<S> Xyy<S> {
+    /// Construct a new color from its chromaticity and luminance.
+    #[inline]
+    pub const fn new(x: S, y: S, luminance: S) -> Xyy<S> {
+        Xyy { x, y, luminance }
+    }
+}
+
+impl<S: BaseFloat> Xyy<S> {
+    /// The chromaticity of a black body at `kelvin` degrees, with a
+    /// luminance of one, such as the white point of a light source of that
+    /// color temperature.
+    ///
+    /// This uses the cubic fit of the Planckian locus by Kim et al., and
+    /// the temperature is clamped to the `[1667, 25000]` range of the fit.
+    pub fn from_temperature(kelvin: S) -> Xyy<S> {
+        let c = |v: f64| -> S { cast(v).unwrap() };
+        let t = clamp(kelvin, c(1667.0), c(25000.0));
+        let (t1, t2, t3) = (c(1e3) / t, c(1e6) / (t * t), c(1e9) / (t * t * t));
+        let x = if t <= c(4000.0) {
+            -c(0.266_123_9) * t3 - c(0.234_358_9) * t2 + c(0.877_695_6) * t1 + c(0.179_910)
+        } else {
+            -c(3.025_846_9) * t3 + c(2.107_037_9) * t2 + c(0.222_634_7) * t1 + c(0.240_390)
+        };
+        let (a, b, d, e) = if t <= c(2222.0) {
+            (-1.106_381_4, -1.348_110_20, 2.185_558_32, -0.202_196_83)
+        } else if t <= c(4000.0) {
+            (-0.954_947_6, -1.374_185_93, 2.091_370_15, -0.167_488_67)
+        } else {
+            (3.081_758_0, -5.873_386_70, 3.751_129_97, -0.370_014_83)
+        };
+        let y = ((c(a) * x + c(b)) * x + c(d)) * x + c(e);
+        Xyy::new(x, y, S::one())
+    }
+}
+
+impl<S: BaseFloat> From<Xyz<S>> for Xyy<S> {
+    /// Black has no chromaticity, and is given that of D65.
+    fn from(c: Xyz<S>) -> Xyy<S> {
+        let sum = c.x + c.y + c.z;
+        if sum == S::zero() {
+            let white = Xyz::<S>::d65();
+            let sum = white.x + white.y + white.z;
+            return Xyy::new(white.x / sum, white.y / sum, S::zero());
+        }
+        Xyy::new(c.x / sum, c.y / sum, c.y)
+    }
+}
+
+impl<S: BaseFloat> From<Xyy<S>> for Xyz<S> {
+    fn from(c: Xyy<S>) -> Xyz<S> {
+        if c.y == S::zero() {
+            return Xyz::new(S::zero(), S::zero(), S::zero());
+        }
+        let scale = c.luminance / c.y;
+        Xyz::new(c.x * scale, c.luminance, (S::one() - c.x - c.y) * scale)
+    }
+}
+
+impl<S> From<Vector3<S>> for Xyz<S> {
+    #[inline]
+    fn from(v: Vector3<S>) -> Xyz<S> {
+        Xyz::new(v.x, v.y, v.z)
+    }
+}
+
+impl<S> From<Xyz<S>> for Vector3<S> {
+    #[inline]
+    fn from(c: Xyz<S>) -> Vector3<S> {
+        Vector3::new(c.x, c.y, c.z)
+    }
+}
+
+/// Clamp the negative components of a color outside of the sRGB gamut to
+/// zero.
+#[inline]
+fn drop_negative<S: BaseFloat>(c: Rgb<S>) -> Rgb<S> {
+    Rgb::new(c.r.max(S::zero()), c.g.max(S::zero()), c.b.max(S::zero()))
+}
+
+/// The matrix converting linear sRGB colors to XYZ, with the D65 white point
+/// of sRGB.
+pub fn rgb_to_xyz_matrix<S: BaseFloat>() -> Matrix3<S> {
+    let c = |v: f64| -> S { cast(v).unwrap() };
+    Matrix3::from_cols(
+        Vector3::new(c(0.412_456_4), c(0.212_672_9), c(0.019_333_9)),
+        Vector3::new(c(0.357_576_1), c(0.715_152_2), c(0.119_192_0)),
+        Vector3::new(c(0.180_437_5), c(0.072_175_0), c(0.950_304_1)),
+    )
+}
+
+/// The matrix converting XYZ colors to linear sRGB, the inverse of
+/// `rgb_to_xyz_matrix`.
+pub fn xyz_to_rgb_matrix<S: BaseFloat>() -> Matrix3<S> {
+    let c = |v: f64| -> S { cast(v).unwrap() };
+    Matrix3::from_cols(
+        Vector3::new(c(3.240_454_2), c(-0.969_266_0), c(0.055_643_4)),
+        Vector3::new(c(-1.537_138_5), c(1.876_010_8), c(-0.204_025_9)),
+        Vector3::new(c(-0.498_531_4), c(0.041_556_0), c(1.057_225_2)),
+    )
+}
+
+/// The matrix adapting XYZ colors seen under the `source` white point to
+/// the colors that look the same under the `target` white point, with the
+/// Bradford cone response model.
+///
+/// This is the chromatic adaptation of ICC profiles, and of white balance:
+/// adapting from the white of a light to the white of a display makes
+/// surfaces lit by it look neutral.
+pub fn bradford_adaptation<S: BaseFloat>(source: Xyz<S>, target: Xyz<S>) -> Matrix3<S> {
+    let c = |v: f64| -> S { cast(v).unwrap() };
+    let cone = Matrix3::from_cols(
+        Vector3::new(c(0.8951), c(-0.7502), c(0.0389)),
+        Vector3::new(c(0.2664), c(1.7135), c(-0.0685)),
+        Vector3::new(c(-0.1614), c(0.0367), c(1.0296)),
+    );
+    let from = cone * Vector3::from(source);
+    let to = cone * Vector3::from(target);
+    let scale = Matrix3::from_diagonal(to.div_element_wise(from));
+    cone.invert().unwrap() * scale * cone
+}
+
+/// The matrix white balancing linear sRGB colors, so that the `source`
+/// white, such as the color of the light of a scene, becomes the `target`
+/// white, usually `Xyz::d65()`, with the same luminance.
+pub fn white_balance_matrix<S: BaseFloat>(source: Xyz<S>, target: Xyz<S>) -> Matrix3<S> {
+    xyz_to_rgb_matrix() * bradford_adaptation(source, target) * rgb_to_xyz_matrix()
+}
+
+impl<S: BaseFloat> From<Rgb<S>> for Xyz<S> {
+    #[inline]
+    fn from(c: Rgb<S>) -> Xyz<S> {
+        Xyz::from(rgb_to_xyz_matrix() * Vector3::from(c))
+    }
+}
+
+impl<S: BaseFloat> From<Xyz<S>> for Rgb<S> {
+    #[inline]
+    fn from(c: Xyz<S>) -> Rgb<S> {
+        Rgb::from(xyz_to_rgb_matrix() * Vector3::from(c))
+    }
+}
+
+impl<S: BaseFloat> Rgb<S> {
+    /// The linear sRGB color of a black body at `kelvin` degrees, with a
+    /// luminance of one, as used for the color of lights.
+    ///
+    /// The warmest temperatures are slightly outside of the sRGB gamut, and
+    /// the negative components they would have are clamped to zero.
+    pub fn from_temperature(kelvin: S) -> Rgb<S> {
+        drop_negative(Rgb::from(Xyz::from(Xyy::from_temperature(kelvin))))
+    }
+}
+
+/// A lobe of the fit of the color matching functions, a Gaussian with
+/// different widths on each side of its peak.
+#[inline]
+fn cmf_lobe(wavelength: f64, peak: f64, below: f64, above: f64) -> f64 {
+    let width = if wavelength < peak { below } else { above };
+    let t = (wavelength - peak) / width;
+    (-0.5 * t * t).exp()
+}
+
+/// Planck's law for the spectral radiance of a black body, in watts per
+/// steradian, square meter and nanometer.
+fn planck(wavelength: f64, kelvin: f64) -> f64 {
+    // The first and second radiation constants, for meters.
+    const C1: f64 = 1.191_042_972e-16;
+    const C2: f64 = 1.438_776_877e-2;
+    let meters = wavelength * 1e-9;
+    C1 / (meters.powi(5) * ((C2 / (meters * kelvin)).exp() - 1.0)) * 1e-9
+}
+
+/// The spectral radiance of a black body at `kelvin` degrees, for light of
+/// the given wavelength in nanometers, in watts per steradian, square meter
+/// and nanometer.
+pub fn blackbody_radiance<S: BaseFloat>(wavelength: S, kelvin: S) -> S {
+    cast(planck(
+        wavelength.to_f64().unwrap(),
+        kelvin.to_f64().unwrap(),
+    ))
+    .unwrap()
+}
+
+impl<S: BaseFloat> Xyz<S> {
+    /// The response of the CIE 1931 standard observer to light of the given
+    /// wavelength in nanometers, from the color matching functions, with a
+    /// luminance of one at 555 nanometers.
+    ///
+    /// This uses the multi-lobe fit of "Simple Analytic Approximations to
+    /// the CIE XYZ Color Matching Functions" by Wyman, Sloan and Shirley,
+    /// which is within the variability of the measurements.
+    pub fn from_wavelength(wavelength: S) -> Xyz<S> {
+        let w = wavelength.to_f64().unwrap();
+        let x = 1.056 * cmf_lobe(w, 599.8, 37.9, 31.0) + 0.362 * cmf_lobe(w, 442.0, 16.0, 26.7)
+            - 0.065 * cmf_lobe(w, 501.1, 20.4, 26.2);
+        let y = 0.821 * cmf_lobe(w, 568.8, 46.9, 40.5) + 0.286 * cmf_lobe(w, 530.9, 16.3, 31.1);
+        let z = 1.217 * cmf_lobe(w, 437.0, 11.8, 36.0) + 0.681 * cmf_lobe(w, 459.0, 26.0, 13.8);
+        Xyz::new(cast(x).unwrap(), cast(y).unwrap(), cast(z).unwrap())
+    }
+
+    /// The color of the light emitted by a black body at `kelvin` degrees,
+    /// with its luminance in candelas per square meter, integrating Planck's
+    /// law against the color matching functions over the visible spectrum.
+    pub fn from_blackbody(kelvin: S) -> Xyz<S> {
+        // The luminous efficacy of the peak of the sensitivity of the eye,
+        // in lumens per watt.
+        const EFFICACY: f64 = 683.0;
+        const STEP: f64 = 1.0;
+        let t = kelvin.to_f64().unwrap();
+        let mut sum = Vector3::new(0.0, 0.0, 0.0);
+        let mut wavelength = 360.0;
+        while wavelength <= 830.0 {
+            let response = Vector3::from(Xyz::from_wavelength(wavelength));
+            sum += response * planck(wavelength, t);
+            wavelength += STEP;
+        }
+        let sum = sum * (EFFICACY * STEP);
+        Xyz::new(
+            cast(sum.x).unwrap(),
+            cast(sum.y).unwrap(),
+            cast(sum.z).unwrap(),
+        )
+    }
+}
+
+impl<S: BaseFloat> Rgb<S> {
+    /// The linear sRGB color of light of the given wavelength in
+    /// nanometers, with the luminance of the color matching functions, as
+    /// used for dispersion and iridescence.
+    ///
+    /// Pure spectral colors are outside of the sRGB gamut, and the negative
+    /// components they would have are clamped to zero.
+    pub fn from_wavelength(wavelength: S) -> Rgb<S> {
+        drop_negative(Rgb::from(Xyz::from_wavelength(wavelength)))
+    }
+
+    /// The linear sRGB color of the light emitted by a black body at
+    /// `kelvin` degrees, with its luminance in candelas per square meter,
+    /// from `Xyz::from_blackbody`.
+    ///
+    /// Unlike `from_temperature`, which only gives the color of the light,
+    /// this also gives how bright it is, which grows very quickly with the
+    /// temperature.
+    pub fn from_blackbody(kelvin: S) -> Rgb<S> {
+        drop_negative(Rgb::from(Xyz::from_blackbody(kelvin)))
+    }
+}
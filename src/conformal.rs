@@ -0,0 +1,259 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::*;
+
+use structure::*;
+
+use angle::Rad;
+use approx;
+use matrix::Matrix3;
+use num::BaseFloat;
+use point::Point2;
+use transform::{Transform, Transform2};
+use vector::Vector2;
+
+/// The product of two complex numbers stored as vectors, with the real part
+/// in `x` and the imaginary part in `y`.
+#[inline]
+fn complex_mul<S: BaseFloat>(a: Vector2<S>, b: Vector2<S>) -> Vector2<S> {
+    Vector2::new(a.x * b.x - a.y * b.y, a.x * b.y + a.y * b.x)
+}
+
+/// The quotient of two complex numbers stored as vectors, or `None` if `b`
+/// is zero.
+#[inline]
+fn complex_div<S: BaseFloat>(a: Vector2<S>, b: Vector2<S>) -> Option<Vector2<S>> {
+    let norm = b.magnitude2();
+    if norm == S::zero() {
+        None
+    } else {
+        Some(Vector2::new(a.x * b.x + a.y * b.y, a.y * b.x - a.x * b.y) / norm)
+    }
+}
+
+/// An angle-preserving transform of the plane, made of a rotation, a uniform
+/// scale and a translation.
+///
+/// Treating points as complex numbers `z = x + iy`, the transform maps `z`
+/// to `a * z + b`, where the complex number `a`, stored as a vector with its
+/// real part in `x` and its imaginary part in `y`, holds both the rotation
+/// and the scale. Unlike a `Decomposed` transform with a separate scale and
+/// rotation, composing and inverting such transforms only takes a few
+/// multiplications, and the inverse of a non degenerate transform always
+/// exists.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{Conformal2, Deg, Point2, Vector2};
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// // A map view zoomed in twice, rotated by 90 degrees, and scrolled.
+/// let view = Conformal2::new(2.0, Deg(90.0), Vector2::new(10.0, 0.0));
+/// assert_relative_eq!(view.transform_point(Point2::new(1.0, 0.0)), Point2::new(10.0, 2.0));
+///
+/// let inverse = view.inverse_transform().unwrap();
+/// assert_relative_eq!(inverse.transform_point(Point2::new(10.0, 2.0)), Point2::new(1.0, 0.0));
+/// # }
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Conformal2<S> {
+    /// The rotation and scale, as a complex number.
+    pub a: Vector2<S>,
+    /// The translation.
+    pub b: Vector2<S>,
+}
+
+impl<S: BaseFloat> Conformal2<S> {
+    /// Construct a transform scaling by `scale` and rotating by `angle`
+    /// about the origin, then translating by `disp`.
+    pub fn new<A: Into<Rad<S>>>(scale: S, angle: A, disp: Vector2<S>) -> Conformal2<S> {
+        let (s, c) = Rad::sin_cos(angle.into());
+        Conformal2 {
+            a: Vector2::new(c, s) * scale,
+            b: disp,
+        }
+    }
+
+    /// The transform mapping `p0` to `q0` and `p1` to `q1`, or `None` if
+    /// `p0` and `p1` are the same point.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Conformal2, Point2};
+    /// # #[macro_use] extern crate approx;
+    /// # fn main() {
+    ///
+    /// let (p0, p1) = (Point2::new(0.0, 0.0), Point2::new(1.0, 0.0));
+    /// let (q0, q1) = (Point2::new(5.0, 5.0), Point2::new(5.0, 8.0));
+    /// let t = Conformal2::from_point_pairs(p0, q0, p1, q1).unwrap();
+    /// assert_relative_eq!(t.scale(), 3.0);
+    /// assert_relative_eq!(t.transform_point(Point2::new(2.0, 0.0)), Point2::new(5.0, 11.0));
+    /// # }
+    /// ```
+    pub fn from_point_pairs(
+        p0: Point2<S>,
+        q0: Point2<S>,
+        p1: Point2<S>,
+        q1: Point2<S>,
+    ) -> Option<Conformal2<S>> {
+        let a = complex_div(q1 - q0, p1 - p0)?;
+        Some(Conformal2 {
+            a,
+            b: q0.to_vec() - complex_mul(a, p0.to_vec()),
+        })
+    }
+
+    /// The uniform scale factor of the transform.
+    #[inline]
+    pub fn scale(&self) -> S {
+        self.a.magnitude()
+    }
+
+    /// The angle of the rotation of the transform.
+    #[inline]
+    pub fn angle(&self) -> Rad<S> {
+        Rad::atan2(self.a.y, self.a.x)
+    }
+}
+
+impl<S: BaseFloat> Default for Conformal2<S> {
+    /// The identity transform.
+    #[inline]
+    fn default() -> Conformal2<S> {
+        Transform::one()
+    }
+}
+
+impl<S: BaseFloat> Transform<Point2<S>> for Conformal2<S> {
+    #[inline]
+    fn one() -> Conformal2<S> {
+        Conformal2 {
+            a: Vector2::unit_x(),
+            b: Vector2::zero(),
+        }
+    }
+
+    fn look_at(eye: Point2<S>, center: Point2<S>, _up: Vector2<S>) -> Conformal2<S> {
+        // Like `Decomposed::look_at`, but without the mirroring `up` can
+        // cause, which does not preserve angles.
+        let a = (center - eye).normalize();
+        Conformal2 {
+            a,
+            b: complex_mul(a, Point2::origin() - eye),
+        }
+    }
+
+    #[inline]
+    fn transform_vector(&self, vec: Vector2<S>) -> Vector2<S> {
+        complex_mul(self.a, vec)
+    }
+
+    #[inline]
+    fn inverse_transform_vector(&self, vec: Vector2<S>) -> Option<Vector2<S>> {
+        complex_div(vec, self.a)
+    }
+
+    #[inline]
+    fn transform_point(&self, point: Point2<S>) -> Point2<S> {
+        Point2::from_vec(complex_mul(self.a, point.to_vec()) + self.b)
+    }
+
+    #[inline]
+    fn inverse_transform_point(&self, point: Point2<S>) -> Option<Point2<S>> {
+        complex_div(point.to_vec() - self.b, self.a).map(Point2::from_vec)
+    }
+
+    #[inline]
+    fn concat(&self, other: &Conformal2<S>) -> Conformal2<S> {
+        Conformal2 {
+            a: complex_mul(self.a, other.a),
+            b: complex_mul(self.a, other.b) + self.b,
+        }
+    }
+
+    fn inverse_transform(&self) -> Option<Conformal2<S>> {
+        let a = complex_div(Vector2::unit_x(), self.a)?;
+        Some(Conformal2 {
+            a,
+            b: -complex_mul(a, self.b),
+        })
+    }
+}
+
+impl<S: BaseFloat> Transform2<S> for Conformal2<S> {}
+
+impl<S: BaseFloat> Mul for Conformal2<S> {
+    type Output = Conformal2<S>;
+
+    /// Apply `rhs`, then this transform.
+    #[inline]
+    fn mul(self, rhs: Conformal2<S>) -> Conformal2<S> {
+        self.concat(&rhs)
+    }
+}
+
+impl<S: BaseFloat> From<Conformal2<S>> for Matrix3<S> {
+    fn from(t: Conformal2<S>) -> Matrix3<S> {
+        Matrix3::new(
+            t.a.x, t.a.y, S::zero(),
+            -t.a.y, t.a.x, S::zero(),
+            t.b.x, t.b.y, S::one(),
+        )
+    }
+}
+
+impl<S: BaseFloat> approx::AbsDiffEq for Conformal2<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Vector2::abs_diff_eq(&self.a, &other.a, epsilon)
+            && Vector2::abs_diff_eq(&self.b, &other.b, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for Conformal2<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Vector2::relative_eq(&self.a, &other.a, epsilon, max_relative)
+            && Vector2::relative_eq(&self.b, &other.b, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for Conformal2<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Vector2::ulps_eq(&self.a, &other.a, epsilon, max_ulps)
+            && Vector2::ulps_eq(&self.b, &other.b, epsilon, max_ulps)
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Animation curves, as edited in curve editors.
+
+use std::cmp::Ordering;
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::Aabb2;
+use bezier::CubicBezier2;
+use num::BaseFloat;
+use point::Point2;
+use polyline::Polyline2;
+use vector::Vector2;
+
+/// A key of a `PiecewiseCurve`, with the handles controlling the shape of
+/// the curve around it.
+///
+/// The handles are offsets from the key in time and value, and their
+/// directions are the tangents of the curve at the key. The incoming handle
+/// points back in time and the outgoing one forward. Handles longer than
+/// the interval to the neighbouring key are shortened to it, keeping their
+/// direction, so that the curve never goes back in time.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CurveKey<S> {
+    pub time: S,
+    pub value: S,
+    /// The handle shaping the curve before the key.
+    pub in_handle: Vector2<S>,
+    /// The handle shaping the curve after the key.
+    pub out_handle: Vector2<S>,
+}
+
+impl<S: BaseFloat> CurveKey<S> {
+    /// Construct a key with handles of zero length, so that the curve goes
+    /// straight to the neighbouring keys without handles either.
+    #[inline]
+    pub fn new(time: S, value: S) -> CurveKey<S> {
+        CurveKey {
+            time,
+            value,
+            in_handle: Vector2::zero(),
+            out_handle: Vector2::zero(),
+        }
+    }
+
+    /// Construct a key with its handles, as offsets from the key.
+    #[inline]
+    pub fn with_handles(
+        time: S,
+        value: S,
+        in_handle: Vector2<S>,
+        out_handle: Vector2<S>,
+    ) -> CurveKey<S> {
+        CurveKey {
+            time,
+            value,
+            in_handle,
+            out_handle,
+        }
+    }
+
+    /// The position of the key in the plane of time and value.
+    #[inline]
+    pub fn point(&self) -> Point2<S> {
+        Point2::new(self.time, self.value)
+    }
+}
+
+/// A curve of values over time, made of cubic Bézier segments between keys
+/// with tangent handles, like the curves of animation editors.
+///
+/// Before the first key and after the last one the curve holds their values,
+/// and a curve without keys is zero everywhere.
+///
+/// ```rust
+/// use cgmath::{CurveKey, PiecewiseCurve, Vector2};
+///
+/// // A value rising from 0 to 1 along a straight line.
+/// let third = Vector2::new(1.0, 1.0) / 3.0;
+/// let curve = PiecewiseCurve::new(vec![
+///     CurveKey::with_handles(0.0, 0.0, -third, third),
+///     CurveKey::with_handles(1.0, 1.0f64, -third, third),
+/// ]);
+/// assert!((curve.evaluate(0.25) - 0.25).abs() < 1e-12);
+/// assert_eq!(curve.evaluate(3.0), 1.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PiecewiseCurve<S> {
+    keys: Vec<CurveKey<S>>,
+}
+
+/// Shorten `handle` to span at most `duration` in time.
+fn clamp_handle<S: BaseFloat>(handle: Vector2<S>, duration: S) -> Vector2<S> {
+    let span = handle.x.abs();
+    if span > duration {
+        handle * (duration / span)
+    } else {
+        handle
+    }
+}
+
+impl<S: BaseFloat> PiecewiseCurve<S> {
+    /// Construct a curve from its keys, in any order.
+    pub fn new(mut keys: Vec<CurveKey<S>>) -> PiecewiseCurve<S> {
+        keys.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap_or(Ordering::Equal));
+        PiecewiseCurve { keys }
+    }
+
+    /// The keys of the curve, sorted by time.
+    #[inline]
+    pub fn keys(&self) -> &[CurveKey<S>] {
+        &self.keys
+    }
+
+    /// The index of the first key after `time`.
+    fn upper_bound(&self, time: S) -> usize {
+        self.keys
+            .binary_search_by(|k| {
+                if k.time <= time {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_err()
+    }
+
+    /// Add a key, after the keys at the same time, and return its index.
+    pub fn add_key(&mut self, key: CurveKey<S>) -> usize {
+        let i = self.upper_bound(key.time);
+        self.keys.insert(i, key);
+        i
+    }
+
+    /// Remove the key at index `i` and return it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn remove_key(&mut self, i: usize) -> CurveKey<S> {
+        self.keys.remove(i)
+    }
+
+    /// Move the key at index `i` to a new time and value with its handles,
+    /// as when dragging it, and return its new index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn move_key(&mut self, i: usize, time: S, value: S) -> usize {
+        let mut key = self.keys.remove(i);
+        key.time = time;
+        key.value = value;
+        self.add_key(key)
+    }
+
+    /// Set the handles of every key to follow the curve smoothly through its
+    /// neighbours, with the slope of the Catmull-Rom spline and a third of
+    /// the interval on each side. The handles of the first and last keys are
+    /// flat.
+    pub fn smooth_handles(&mut self) {
+        let three: S = cast(3).unwrap();
+        let n = self.keys.len();
+        for i in 0..n {
+            let slope = if i == 0 || i + 1 == n {
+                S::zero()
+            } else {
+                let (a, b) = (self.keys[i - 1], self.keys[i + 1]);
+                let duration = b.time - a.time;
+                if duration > S::zero() {
+                    (b.value - a.value) / duration
+                } else {
+                    S::zero()
+                }
+            };
+            let before = if i > 0 {
+                (self.keys[i].time - self.keys[i - 1].time) / three
+            } else {
+                S::zero()
+            };
+            let after = if i + 1 < n {
+                (self.keys[i + 1].time - self.keys[i].time) / three
+            } else {
+                S::zero()
+            };
+            let key = &mut self.keys[i];
+            key.in_handle = Vector2::new(-before, -before * slope);
+            key.out_handle = Vector2::new(after, after * slope);
+        }
+    }
+
+    /// The Bézier curve in the plane of time and value from key `i` to the
+    /// next one, with handles shortened to the interval between them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no key after key `i`.
+    pub fn segment(&self, i: usize) -> CubicBezier2<S> {
+        let (a, b) = (self.keys[i], self.keys[i + 1]);
+        let duration = b.time - a.time;
+        let out_handle = clamp_handle(
+            Vector2::new(a.out_handle.x.max(S::zero()), a.out_handle.y),
+            duration,
+        );
+        let in_handle = clamp_handle(
+            Vector2::new(b.in_handle.x.min(S::zero()), b.in_handle.y),
+            duration,
+        );
+        CubicBezier2::new(
+            a.point(),
+            a.point() + out_handle,
+            b.point() + in_handle,
+            b.point(),
+        )
+    }
+
+    /// The value of the curve at `time`.
+    pub fn evaluate(&self, time: S) -> S {
+        let i = self.upper_bound(time);
+        if i == 0 {
+            return self.keys.first().map_or(S::zero(), |k| k.value);
+        }
+        if i == self.keys.len() {
+            return self.keys[i - 1].value;
+        }
+        let segment = self.segment(i - 1);
+        segment.point_at(solve_time(&segment, time)).y
+    }
+
+    /// The bounds of the curve in the plane of time and value, between its
+    /// first and last keys, or `None` if it has no keys.
+    pub fn aabb(&self) -> Option<Aabb2<S>> {
+        let first = Aabb2::from_point(self.keys.first()?.point());
+        Some(
+            (0..self.keys.len() - 1)
+                .map(|i| self.segment(i).aabb())
+                .fold(first, |aabb, segment| aabb.union(&segment)),
+        )
+    }
+
+    /// Approximate the curve between its first and last keys by a polyline
+    /// in the plane of time and value, which stays closer than `tolerance`
+    /// to it, such as to draw it in an editor.
+    pub fn flatten(&self, tolerance: S) -> Polyline2<S> {
+        let mut points: Vec<_> = self.keys.first().map(|k| k.point()).into_iter().collect();
+        for i in 0..self.keys.len().saturating_sub(1) {
+            points.extend(
+                self.segment(i)
+                    .flatten(tolerance)
+                    .points
+                    .into_iter()
+                    .skip(1),
+            );
+        }
+        Polyline2::new(points)
+    }
+}
+
+/// The parameter at which a segment of an animation curve, whose time
+/// grows monotonically along it, reaches `time`.
+///
+/// Newton steps converge quickly away from flat spots, and bisection keeps
+/// the solution bracketed where they would overshoot.
+fn solve_time<S: BaseFloat>(segment: &CubicBezier2<S>, time: S) -> S {
+    let start = segment.points[0].x;
+    let duration = segment.points[3].x - start;
+    let tolerance = S::default_epsilon() * (duration.abs() + start.abs());
+    let (mut low, mut high) = (S::zero(), S::one());
+    let mut t = (time - start) / duration;
+    for _ in 0..64 {
+        let error = segment.point_at(t).x - time;
+        if implicit_ulps_eq!(error, S::zero(), epsilon = tolerance) {
+            break;
+        }
+        if error < S::zero() {
+            low = t;
+        } else {
+            high = t;
+        }
+        let slope = segment.derivative_at(t).x;
+        let next = t - error / slope;
+        t = if slope > S::zero() && next > low && next < high {
+            next
+        } else {
+            (low + high) / cast(2).unwrap()
+        };
+    }
+    t
+}
@@ -0,0 +1,372 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use structure::*;
+
+use angle::Rad;
+use approx;
+use matrix::Matrix3;
+use num::BaseFloat;
+use point::{Point2, Point3};
+use sphere::Sphere;
+use vector::{Vector2, Vector3};
+
+/// A two-dimensional ellipse, such as a selection handle or an orbit path.
+///
+/// The ellipse is described by its center, its radii along each of its
+/// axes, and the counterclockwise angle from the `x` axis to its first
+/// axis. The radii must be positive.
+///
+/// ```rust
+/// use cgmath::{Deg, Ellipse2, Point2, Vector2};
+///
+/// let orbit = Ellipse2::new(Point2::new(0.0, 0.0), Vector2::new(2.0, 1.0), Deg(90.0f64).into());
+/// assert!(orbit.contains_point(Point2::new(0.0, 1.5)));
+/// assert!(!orbit.contains_point(Point2::new(1.5, 0.0)));
+///
+/// let p = orbit.closest_point(Point2::new(0.0, 5.0));
+/// assert!((p.y - 2.0).abs() < 1e-12 && p.x.abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipse2<S> {
+    /// The center of the ellipse.
+    pub center: Point2<S>,
+    /// The radii of the ellipse along each of its axes.
+    pub radii: Vector2<S>,
+    /// The counterclockwise rotation of the ellipse.
+    pub angle: Rad<S>,
+}
+
+impl<S: BaseFloat> Ellipse2<S> {
+    /// Construct an ellipse from its center, its radii along each axis, and
+    /// its rotation.
+    #[inline]
+    pub fn new(center: Point2<S>, radii: Vector2<S>, angle: Rad<S>) -> Ellipse2<S> {
+        Ellipse2 {
+            center,
+            radii,
+            angle,
+        }
+    }
+
+    /// The two unit axes of the ellipse.
+    #[inline]
+    pub fn axes(&self) -> [Vector2<S>; 2] {
+        let (s, c) = Rad::sin_cos(self.angle);
+        [Vector2::new(c, s), Vector2::new(-s, c)]
+    }
+
+    /// The coordinates of a point along the axes of the ellipse, relative to
+    /// its center.
+    #[inline]
+    pub(crate) fn local_point(&self, p: Point2<S>) -> Vector2<S> {
+        self.local_vector(p - self.center)
+    }
+
+    /// The coordinates of a vector along the axes of the ellipse.
+    #[inline]
+    pub(crate) fn local_vector(&self, v: Vector2<S>) -> Vector2<S> {
+        let [u, w] = self.axes();
+        Vector2::new(v.dot(u), v.dot(w))
+    }
+
+    /// Test whether the point is inside the ellipse or on its boundary.
+    #[inline]
+    pub fn contains_point(&self, p: Point2<S>) -> bool {
+        let d = self.local_point(p);
+        let (x, y) = (d.x / self.radii.x, d.y / self.radii.y);
+        x * x + y * y <= S::one()
+    }
+
+    /// The point on the boundary of the ellipse closest to `p`, whether `p`
+    /// is inside or outside the ellipse.
+    ///
+    /// The point is found by bisection, to the precision of `S`, following
+    /// "Distance from a Point to an Ellipse, an Ellipsoid3, or a
+    /// Hyperellipsoid" by David Eberly.
+    pub fn closest_point(&self, p: Point2<S>) -> Point2<S> {
+        let d = self.local_point(p);
+        let mut x = [S::zero(); 2];
+        closest_on_axes(&[self.radii.x, self.radii.y], &[d.x, d.y], &mut x);
+        let [u, w] = self.axes();
+        self.center + u * x[0] + w * x[1]
+    }
+}
+
+/// A three-dimensional ellipsoid, a sphere scaled along three orthogonal
+/// axes.
+///
+/// ```rust
+/// use cgmath::{Ellipsoid3, Matrix3, Point3, SquareMatrix, Vector3};
+///
+/// let ellipsoid = Ellipsoid3::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Vector3::new(1.0, 2.0, 3.0f64),
+///     Matrix3::identity(),
+/// );
+/// assert!(ellipsoid.contains_point(Point3::new(0.0, 0.0, 2.5)));
+/// assert!(!ellipsoid.contains_point(Point3::new(2.5, 0.0, 0.0)));
+///
+/// let p = ellipsoid.closest_point(Point3::new(0.0, 0.0, 5.0));
+/// assert!((p.z - 3.0).abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipsoid3<S> {
+    /// The center of the ellipsoid.
+    pub center: Point3<S>,
+    /// The radii of the ellipsoid along each of its axes.
+    pub radii: Vector3<S>,
+    /// The axes of the ellipsoid, as the columns of a rotation matrix.
+    pub axes: Matrix3<S>,
+}
+
+impl<S: BaseFloat> Ellipsoid3<S> {
+    /// Construct an ellipsoid from its center, its radii along each axis,
+    /// and its axes, which must be orthonormal.
+    #[inline]
+    pub fn new(center: Point3<S>, radii: Vector3<S>, axes: Matrix3<S>) -> Ellipsoid3<S> {
+        Ellipsoid3 {
+            center,
+            radii,
+            axes,
+        }
+    }
+
+    /// The ellipsoid covering the same space as a sphere.
+    #[inline]
+    pub fn from_sphere(sphere: &Sphere<S>) -> Ellipsoid3<S> {
+        Ellipsoid3::new(
+            sphere.center,
+            Vector3::from_value(sphere.radius),
+            Matrix3::identity(),
+        )
+    }
+
+    /// The coordinates of a point along the axes of the ellipsoid, relative
+    /// to its center.
+    #[inline]
+    pub(crate) fn local_point(&self, p: Point3<S>) -> Vector3<S> {
+        self.local_vector(p - self.center)
+    }
+
+    /// The coordinates of a vector along the axes of the ellipsoid.
+    #[inline]
+    pub(crate) fn local_vector(&self, v: Vector3<S>) -> Vector3<S> {
+        Vector3::new(v.dot(self.axes.x), v.dot(self.axes.y), v.dot(self.axes.z))
+    }
+
+    /// Test whether the point is inside the ellipsoid or on its surface.
+    #[inline]
+    pub fn contains_point(&self, p: Point3<S>) -> bool {
+        let d = self.local_point(p).div_element_wise(self.radii);
+        d.magnitude2() <= S::one()
+    }
+
+    /// The point on the surface of the ellipsoid closest to `p`, whether `p`
+    /// is inside or outside the ellipsoid.
+    ///
+    /// This uses the same bisection as `Ellipse2::closest_point`.
+    pub fn closest_point(&self, p: Point3<S>) -> Point3<S> {
+        let d = self.local_point(p);
+        let mut x = [S::zero(); 3];
+        let radii = [self.radii.x, self.radii.y, self.radii.z];
+        closest_on_axes(&radii, &[d.x, d.y, d.z], &mut x);
+        self.center + self.axes * Vector3::new(x[0], x[1], x[2])
+    }
+}
+
+/// Find the point `x` closest to `y` on the axis-aligned ellipse or
+/// ellipsoid with radii `e`, centered on the origin.
+fn closest_on_axes<S: BaseFloat>(e: &[S], y: &[S], x: &mut [S]) {
+    // Work in the positive octant with the axes sorted from the shortest to
+    // the longest, then restore the order and the signs.
+    let mut order = [0, 1, 2];
+    let order = &mut order[..e.len()];
+    order.sort_by(|&i, &j| e[i].partial_cmp(&e[j]).unwrap());
+    let mut se = [S::zero(); 3];
+    let mut sy = [S::zero(); 3];
+    let mut sx = [S::zero(); 3];
+    for (k, &i) in order.iter().enumerate() {
+        se[k] = e[i];
+        sy[k] = y[i].abs();
+    }
+    let n = e.len();
+    closest_on_sorted_axes(&se[..n], &sy[..n], &mut sx[..n]);
+    for (k, &i) in order.iter().enumerate() {
+        x[i] = if y[i] < S::zero() { -sx[k] } else { sx[k] };
+    }
+}
+
+/// `closest_on_axes` for radii sorted in increasing order and coordinates
+/// that are all positive or zero.
+fn closest_on_sorted_axes<S: BaseFloat>(e: &[S], y: &[S], x: &mut [S]) {
+    if e.is_empty() {
+        return;
+    }
+    if y[0] > S::zero() {
+        // The closest point is `e² y / (t + e²)` for the root `t` of
+        // `Σ (e y / (t + e²))² = 1`, which is decreasing past `-e₀²`. Search
+        // for `s = t + e₀²` instead, which keeps the first denominator
+        // exact when the point is close to the plane of the shortest axis.
+        let e0 = e[0] * e[0];
+        let f = |s: S| {
+            e.iter().zip(y).fold(-S::one(), |sum, (&e, &y)| {
+                let r = e * y / (s + (e * e - e0));
+                sum + r * r
+            })
+        };
+        let norm = y.iter().fold(S::zero(), |sum, &y| sum + y * y).sqrt();
+        let mut lo = e[0] * y[0];
+        let mut hi = e[e.len() - 1] * norm + e0;
+        if hi < lo {
+            hi = lo;
+        }
+        let two = S::one() + S::one();
+        for _ in 0..256 {
+            let mid = (lo + hi) / two;
+            if mid <= lo || mid >= hi {
+                break;
+            }
+            if f(mid) > S::zero() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let s = (lo + hi) / two;
+        for ((x, &e), &y) in x.iter_mut().zip(e).zip(y) {
+            *x = e * e * y / (s + (e * e - e0));
+        }
+        return;
+    }
+
+    // On the plane of the shortest axis, the closest point may leave the
+    // plane, at the root `t = -e₀²`, if that lands inside the ellipse.
+    if e.len() > 1 {
+        let e0 = e[0] * e[0];
+        let mut sum = S::zero();
+        for ((x, &e), &y) in x.iter_mut().zip(e).zip(y).skip(1) {
+            *x = S::zero();
+            if y > S::zero() {
+                let denom = e * e - e0;
+                if denom <= S::zero() {
+                    sum = S::infinity();
+                    break;
+                }
+                *x = e * e * y / denom;
+                let r = *x / e;
+                sum += r * r;
+            }
+        }
+        if sum >= S::one() {
+            x[0] = S::zero();
+            closest_on_sorted_axes(&e[1..], &y[1..], &mut x[1..]);
+            return;
+        }
+        x[0] = e[0] * (S::one() - sum).sqrt();
+    } else {
+        x[0] = e[0];
+    }
+}
+
+impl<S: BaseFloat> approx::AbsDiffEq for Ellipse2<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Point2::abs_diff_eq(&self.center, &other.center, epsilon)
+            && Vector2::abs_diff_eq(&self.radii, &other.radii, epsilon)
+            && Rad::abs_diff_eq(&self.angle, &other.angle, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for Ellipse2<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Point2::relative_eq(&self.center, &other.center, epsilon, max_relative)
+            && Vector2::relative_eq(&self.radii, &other.radii, epsilon, max_relative)
+            && Rad::relative_eq(&self.angle, &other.angle, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for Ellipse2<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Point2::ulps_eq(&self.center, &other.center, epsilon, max_ulps)
+            && Vector2::ulps_eq(&self.radii, &other.radii, epsilon, max_ulps)
+            && Rad::ulps_eq(&self.angle, &other.angle, epsilon, max_ulps)
+    }
+}
+
+impl<S: BaseFloat> approx::AbsDiffEq for Ellipsoid3<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Point3::abs_diff_eq(&self.center, &other.center, epsilon)
+            && Vector3::abs_diff_eq(&self.radii, &other.radii, epsilon)
+            && Matrix3::abs_diff_eq(&self.axes, &other.axes, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for Ellipsoid3<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Point3::relative_eq(&self.center, &other.center, epsilon, max_relative)
+            && Vector3::relative_eq(&self.radii, &other.radii, epsilon, max_relative)
+            && Matrix3::relative_eq(&self.axes, &other.axes, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for Ellipsoid3<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Point3::ulps_eq(&self.center, &other.center, epsilon, max_ulps)
+            && Vector3::ulps_eq(&self.radii, &other.radii, epsilon, max_ulps)
+            && Matrix3::ulps_eq(&self.axes, &other.axes, epsilon, max_ulps)
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Geometry of two views of the same scene.
+//!
+//! The functions of this module relate the images of two cameras through
+//! their relative pose, the transform from the view space of the first
+//! camera to the view space of the second one. A point seen at some pixel
+//! in the first image lies on a ray, and its image in the second view lies
+//! on the image of that ray, the epipolar line of the pixel. Matching pixels
+//! in both views then give back the point, by triangulation.
+//!
+//! As `PinholeCamera`, the pose uses the view space of this crate, with the
+//! camera looking down the negative `z` axis and the `y` axis pointing up,
+//! while the essential and fundamental matrices act on image coordinates,
+//! with the `y` axis pointing down, as in computer vision libraries.
+//!
+//! ```rust
+//! use cgmath::prelude::*;
+//! use cgmath::epipolar::{epipolar_line, fundamental_matrix, triangulate_pixels};
+//! use cgmath::{Decomposed, Deg, PinholeCamera, Point3, Quaternion, Vector3};
+//! # #[macro_use] extern crate approx;
+//! # fn main() {
+//!
+//! let camera = PinholeCamera::from_fov(Deg(60.0), 640.0, 480.0);
+//! // The second camera of a stereo rig, 10 cm to the right of the first one.
+//! let relative = Decomposed {
+//!     scale: 1.0,
+//!     rot: Quaternion::one(),
+//!     disp: Vector3::new(-0.1, 0.0, 0.0),
+//! };
+//!
+//! let p = Point3::new(0.3, -0.2, -2.0);
+//! let left = camera.project(p).unwrap();
+//! let right = camera.project(relative.transform_point(p)).unwrap();
+//!
+//! // The match of a pixel lies on its epipolar line, here a horizontal one.
+//! let f = fundamental_matrix(&camera, &camera, &relative).unwrap();
+//! let line = epipolar_line(&f, left).unwrap();
+//! assert_relative_eq!(line.distance(right), 0.0, epsilon = 1e-9);
+//! assert_relative_eq!(line.direction.y, 0.0, epsilon = 1e-12);
+//!
+//! let triangulated = triangulate_pixels(&camera, left, &camera, right, &relative).unwrap();
+//! assert_relative_eq!(triangulated, p, epsilon = 1e-9);
+//! # }
+//! ```
+
+use structure::*;
+
+use line::Line2;
+use matrix::Matrix3;
+use num::BaseFloat;
+use point::{Point2, Point3};
+use projection::PinholeCamera;
+use ray::Ray3;
+use transform::Transform;
+use vector::{Vector2, Vector3};
+
+/// Switch between the view space of this crate and the camera frame of
+/// computer vision, by flipping the `y` and `z` axes.
+#[inline]
+fn flip<S: BaseFloat>(v: Vector3<S>) -> Vector3<S> {
+    Vector3::new(v.x, -v.y, -v.z)
+}
+
+/// The matrix of the cross product with `v`, such that `skew(v) * w` is
+/// `v.cross(w)`.
+#[inline]
+fn skew<S: BaseFloat>(v: Vector3<S>) -> Matrix3<S> {
+    Matrix3::new(
+        S::zero(), v.z, -v.y,
+        -v.z, S::zero(), v.x,
+        v.y, -v.x, S::zero(),
+    )
+}
+
+/// The essential matrix of a relative pose, mapping normalized image
+/// coordinates in the first view to epipolar lines in the second view.
+///
+/// Writing normalized coordinates as `(x, y, 1)`, as returned by
+/// `PinholeCamera::pixel_to_normalized`, the images `a` and `b` of a point
+/// in both views satisfy `b.dot(e * a) == 0`. The relative pose should be a
+/// rigid transform. Its translation only matters up to scale, and a pure
+/// rotation gives the zero matrix.
+pub fn essential_matrix<S: BaseFloat, T: Transform<Point3<S>>>(relative: &T) -> Matrix3<S> {
+    let rotation = Matrix3::from_cols(
+        flip(relative.transform_vector(Vector3::unit_x())),
+        -flip(relative.transform_vector(Vector3::unit_y())),
+        -flip(relative.transform_vector(Vector3::unit_z())),
+    );
+    let translation = flip(relative.transform_point(Point3::origin()).to_vec());
+    skew(translation) * rotation
+}
+
+/// The fundamental matrix of two cameras and their relative pose, mapping
+/// pixels in the first view to epipolar lines in the second view.
+///
+/// Writing pixels as `(u, v, 1)`, the images `a` and `b` of a point in both
+/// views satisfy `b.dot(f * a) == 0`. Returns `None` if the focal length of
+/// either camera is zero.
+pub fn fundamental_matrix<S: BaseFloat, T: Transform<Point3<S>>>(
+    camera_a: &PinholeCamera<S>,
+    camera_b: &PinholeCamera<S>,
+    relative: &T,
+) -> Option<Matrix3<S>> {
+    let k_a = camera_a.to_matrix3().invert()?;
+    let k_b = camera_b.to_matrix3().invert()?;
+    Some(k_b.transpose() * essential_matrix(relative) * k_a)
+}
+
+/// The epipolar line of a point in the first view, on which its match in
+/// the second view lies, given the fundamental matrix of the views.
+///
+/// This also works with the essential matrix and normalized image
+/// coordinates. Returns `None` if the point is the epipole of the first
+/// view, the image of the center of the second camera, whose epipolar line
+/// is not defined.
+pub fn epipolar_line<S: BaseFloat>(f: &Matrix3<S>, point: Point2<S>) -> Option<Line2<S>> {
+    // The line of the points `(x, y)` where `l.x * x + l.y * y + l.z == 0`.
+    let p = Vector3::new(point.x, point.y, S::one());
+    let l = f * p;
+    let normal = l.truncate();
+    let norm2 = normal.magnitude2();
+    // At the epipole, the line vanishes up to the rounding of the product.
+    let size2 = f.x.magnitude2() + f.y.magnitude2() + f.z.magnitude2();
+    let epsilon = S::default_epsilon() * S::default_epsilon() * size2 * p.magnitude2();
+    if implicit_ulps_eq!(norm2, S::zero(), epsilon = epsilon) {
+        return None;
+    }
+    Some(Line2::new(
+        Point2::from_vec(normal * (-l.z / norm2)),
+        Vector2::new(-normal.y, normal.x),
+    ))
+}
+
+/// The point closest to both rays, halfway between their closest points.
+///
+/// Returns `None` if the rays are parallel, or if the closest points are
+/// behind the origin of either ray, as when matching pixels do not come from
+/// the same point.
+pub fn triangulate_rays<S: BaseFloat>(a: &Ray3<S>, b: &Ray3<S>) -> Option<Point3<S>> {
+    let (d1, d2) = (a.direction, b.direction);
+    let r = a.origin - b.origin;
+    let (aa, ab, bb) = (d1.magnitude2(), d1.dot(d2), d2.magnitude2());
+    let (ar, br) = (d1.dot(r), d2.dot(r));
+    let denominator = aa * bb - ab * ab;
+    let epsilon = S::default_epsilon() * aa * bb;
+    if denominator <= S::zero() || implicit_ulps_eq!(denominator, S::zero(), epsilon = epsilon) {
+        return None;
+    }
+    let s = (ab * br - bb * ar) / denominator;
+    let t = (aa * br - ab * ar) / denominator;
+    if s < S::zero() || t < S::zero() {
+        return None;
+    }
+    Some(a.point_at(s).midpoint(b.point_at(t)))
+}
+
+/// The point seen at `pixel_a` by the first camera and at `pixel_b` by the
+/// second one, in the view space of the first camera, given the relative
+/// pose of the cameras.
+///
+/// Returns `None` if the relative pose cannot be inverted, or under the same
+/// conditions as `triangulate_rays`.
+pub fn triangulate_pixels<S: BaseFloat, T: Transform<Point3<S>>>(
+    camera_a: &PinholeCamera<S>,
+    pixel_a: Point2<S>,
+    camera_b: &PinholeCamera<S>,
+    pixel_b: Point2<S>,
+    relative: &T,
+) -> Option<Point3<S>> {
+    let to_a = relative.inverse_transform()?;
+    let ray_a = camera_a.unproject(pixel_a);
+    let ray_b = camera_b.unproject(pixel_b);
+    let ray_b = Ray3::new(
+        to_a.transform_point(ray_b.origin),
+        to_a.transform_vector(ray_b.direction),
+    );
+    triangulate_rays(&ray_a, &ray_b)
+}
@@ -22,7 +22,7 @@ use rand::{
 
 use structure::*;
 
-use angle::Rad;
+use angle::{Deg, Rad};
 use approx;
 #[cfg(feature = "mint")]
 use mint;
@@ -38,6 +38,25 @@ use quaternion::Quaternion;
 /// rotations). Since all three rotation axes are used, the angles are
 /// Tait–Bryan angles rather than proper Euler angles.
 ///
+/// # Conventions
+///
+/// Like the rest of this crate, the conversions treat vectors as column
+/// vectors, multiplied on the right of matrices. Rotating first about the
+/// `x` axis, then about the rotated `y` axis, then about the twice rotated
+/// `z` axis is the same as rotating about the fixed `z`, `y` and `x` axes of
+/// the world, in this order, so the rotation matrix is:
+///
+/// ```
+/// use cgmath::{Deg, Euler, Matrix3};
+///
+/// let (x, y, z) = (Deg(30.0f64), Deg(-20.0), Deg(75.0));
+/// let m = Matrix3::from_angle_x(x) * Matrix3::from_angle_y(y) * Matrix3::from_angle_z(z);
+/// cgmath::assert_relative_eq!(Matrix3::from(Euler::new(x, y, z)), m, epsilon = 1e-12);
+/// ```
+///
+/// All angles are counter-clockwise when looking from the positive end of
+/// their axis towards the origin, following the right-hand rule.
+///
 /// # Ranges
 ///
 /// - x: [-pi, pi]
@@ -103,6 +122,15 @@ impl<A> Euler<A> {
     }
 }
 
+/// The default set of euler angles has all three angles set to zero, which
+/// represents no rotation.
+impl<A: Angle> Default for Euler<A> {
+    #[inline]
+    fn default() -> Euler<A> {
+        Euler::new(A::zero(), A::zero(), A::zero())
+    }
+}
+
 impl<S: BaseFloat> From<Quaternion<S>> for Euler<Rad<S>> {
     fn from(src: Quaternion<S>) -> Euler<Rad<S>> {
         let sig: S = cast(0.499).unwrap();
@@ -224,3 +252,6 @@ impl<S: Clone, A: Angle + Into<S>> Into<MintEuler<S>> for Euler<A> {
         MintEuler::from([self.x.into(), self.y.into(), self.z.into()])
     }
 }
+
+impl_precision_conversions!(Euler { x, y, z }, Euler<Rad<f32>>, Euler<Rad<f64>>);
+impl_precision_conversions!(Euler { x, y, z }, Euler<Deg<f32>>, Euler<Deg<f64>>);
@@ -0,0 +1,207 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fast approximations of trigonometric functions.
+//!
+//! The functions of this module trade accuracy for speed: they are short
+//! polynomials with a cheap range reduction and no slow paths, which the
+//! compiler can inline and vectorize. Their absolute error is below `2e-6`,
+//! about a ten thousandth of a degree for the angles, which suits particle
+//! systems, procedural animation and other visual effects evaluating
+//! millions of them per frame, but not physics or geometry that accumulate
+//! errors.
+//!
+//! `rsqrt`, the reciprocal square root behind `InnerSpace::fast_normalize`,
+//! has a relative error below `0.2%` instead.
+//!
+//! For `sin_cos`, the error bound holds for arguments up to `1e4` in
+//! magnitude. Beyond, the range reduction loses accuracy, as do single
+//! precision arguments themselves.
+//!
+//! ```rust
+//! use cgmath::fast;
+//!
+//! let (sin, cos) = fast::sin_cos(0.5f32);
+//! assert!((sin - 0.5f32.sin()).abs() < 2e-6);
+//! assert!((cos - 0.5f32.cos()).abs() < 2e-6);
+//!
+//! let angle = fast::atan2(1.0f32, -1.0);
+//! assert!((angle - 3.0 * std::f32::consts::FRAC_PI_4).abs() < 2e-6);
+//! ```
+
+use std::f64::consts::{FRAC_2_PI, FRAC_PI_2, PI};
+
+use num_traits::{cast, Float};
+
+use num::BaseFloat;
+
+// pi / 2 split in three parts, the first two having few enough bits for
+// their products with the quadrant number to be exact even in single
+// precision.
+const PIO2_1: f64 = 1.5703125;
+const PIO2_2: f64 = 4.837512969970703e-4;
+const PIO2_3: f64 = 7.549789954891882e-8;
+
+#[inline]
+fn c<S: BaseFloat>(x: f64) -> S {
+    cast(x).unwrap()
+}
+
+/// An approximation of `1 / x.sqrt()` for positive `x`, with a relative
+/// error below `0.2%`.
+///
+/// This is the well known estimate from the bits of a single precision
+/// float, refined by a step of Newton's method. Zero gives a very large
+/// finite value, and arguments outside of the range of `f32` lose the
+/// error bound.
+///
+/// ```rust
+/// use cgmath::fast;
+///
+/// assert!((fast::rsqrt(4.0f32) - 0.5).abs() < 1e-3);
+/// ```
+#[inline]
+pub fn rsqrt<S: BaseFloat>(x: S) -> S {
+    // The magic constant of Chris Lomont, slightly more accurate than the
+    // original one of Quake III.
+    let bits = cast::<S, f32>(x).unwrap_or_else(Float::max_value).to_bits();
+    let y: S = cast(f32::from_bits(0x5f37_5a86u32.wrapping_sub(bits >> 1))).unwrap();
+    y * (c::<S>(1.5) - x * c(0.5) * y * y)
+}
+
+/// The sine and cosine of `x`.
+///
+/// ```rust
+/// use cgmath::fast;
+///
+/// let (sin, cos) = fast::sin_cos(-2.0f64);
+/// assert!((sin - (-2.0f64).sin()).abs() < 2e-6);
+/// assert!((cos - (-2.0f64).cos()).abs() < 2e-6);
+/// ```
+#[inline]
+pub fn sin_cos<S: BaseFloat>(x: S) -> (S, S) {
+    let k = (x * c(FRAC_2_PI)).round();
+    let quadrant = match cast::<S, i64>(k) {
+        Some(quadrant) => quadrant & 3,
+        None => return (S::nan(), S::nan()),
+    };
+    // Minimax polynomials on [-pi / 4, pi / 4].
+    const S1: f64 = 0.9999949976312567;
+    const S3: f64 = -0.16660161976870355;
+    const S5: f64 = 0.008121557204673691;
+    const C0: f64 = 0.9999999725290055;
+    const C2: f64 = -0.49999856831615025;
+    const C4: f64 = 0.041655031221698974;
+    const C6: f64 = -0.001358594801369562;
+    let r = ((x - k * c(PIO2_1)) - k * c(PIO2_2)) - k * c(PIO2_3);
+    let z = r * r;
+    let sin = r * (c::<S>(S1) + z * (c::<S>(S3) + z * c(S5)));
+    let cos = c::<S>(C0) + z * (c::<S>(C2) + z * (c::<S>(C4) + z * c(C6)));
+    match quadrant {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+
+/// Evaluate the polynomial with the given coefficients, from the constant
+/// term up, at `x`.
+#[inline]
+fn polynomial<S: BaseFloat>(coefficients: &[f64], x: S) -> S {
+    coefficients
+        .iter()
+        .rev()
+        .fold(S::zero(), |acc, &a| acc * x + c(a))
+}
+
+/// The arc tangent of `x`, for `0 <= x <= 1`, from a minimax polynomial.
+#[inline]
+fn kernel_atan<S: BaseFloat>(x: S) -> S {
+    const A: [f64; 6] = [
+        0.9999772191463482,
+        -0.33262281987523884,
+        0.19354029876700618,
+        -0.11642624793492877,
+        0.05264706849128876,
+        -0.011719016799993357,
+    ];
+    let z = x * x;
+    x * polynomial(&A, z)
+}
+
+/// The arc tangent of `x`, in radians.
+#[inline]
+pub fn atan<S: BaseFloat>(x: S) -> S {
+    let a = x.abs();
+    let r = if a > S::one() {
+        c::<S>(FRAC_PI_2) - kernel_atan(S::one() / a)
+    } else {
+        kernel_atan(a)
+    };
+    if x < S::zero() {
+        -r
+    } else {
+        r
+    }
+}
+
+/// The four quadrant arc tangent of `y / x`, in radians, between `-pi` and
+/// `pi`.
+///
+/// Unlike `Float::atan2`, the signs of zeros are ignored, and
+/// `atan2(0.0, x)` is zero for every `x` that is not less than zero.
+#[inline]
+pub fn atan2<S: BaseFloat>(y: S, x: S) -> S {
+    let (ax, ay) = (x.abs(), y.abs());
+    let (min, max) = if ax < ay { (ax, ay) } else { (ay, ax) };
+    if max == S::zero() {
+        return S::zero();
+    }
+    let mut r = kernel_atan(min / max);
+    if ay > ax {
+        r = c::<S>(FRAC_PI_2) - r;
+    }
+    if x < S::zero() {
+        r = c::<S>(PI) - r;
+    }
+    if y < S::zero() {
+        -r
+    } else {
+        r
+    }
+}
+
+/// The arc cosine of `x`, in radians, between `0` and `pi`, or NaN if `x`
+/// is not between `-1` and `1`.
+#[inline]
+pub fn acos<S: BaseFloat>(x: S) -> S {
+    let a = x.abs();
+    // A minimax fit of `acos(x) / sqrt(1 - x)` on [0, 1].
+    const A: [f64; 6] = [
+        1.5707952065575732,
+        -0.21451227363177144,
+        0.08787564774011195,
+        -0.04495720212200683,
+        0.019348202146023317,
+        -0.0043371384256957815,
+    ];
+    let r = (S::one() - a).sqrt() * polynomial(&A, a);
+    if x < S::zero() {
+        c::<S>(PI) - r
+    } else {
+        r
+    }
+}
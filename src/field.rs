@@ -0,0 +1,550 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Regular grids of scalar or vector samples, such as velocity fields or
+//! heightmaps, interpolated between the samples.
+//!
+//! The samples of a grid are `spacing` apart along each axis, starting at
+//! `origin`, and are stored with the `x` index varying fastest. Queries
+//! outside of the grid are clamped to its boundary. The differential
+//! operators use central differences between the samples, and one-sided
+//! differences on the boundary.
+//!
+//! Grids of walkable cells also give the distance fields and flow fields
+//! used to steer crowds towards goals.
+//!
+//! ```rust
+//! use cgmath::field::Grid2;
+//! use cgmath::{Point2, Vector2};
+//!
+//! // A wind field blowing faster towards positive y.
+//! let wind = Grid2::from_fn(Point2::new(0.0, 0.0), 10.0f64, 3, 3, |_, j| {
+//!     Vector2::new(j as f64, 0.0)
+//! });
+//! assert_eq!(wind.sample(Point2::new(4.0, 15.0)), Vector2::new(1.5, 0.0));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::ops::{Add, Mul, Sub};
+
+use num_traits::cast;
+
+use structure::*;
+
+use num::BaseFloat;
+use point::{Point2, Point3};
+use vector::{Vector2, Vector3};
+
+/// A two-dimensional regular grid of samples.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Grid2<S, T> {
+    /// The position of the first sample.
+    pub origin: Point2<S>,
+    /// The distance between neighbouring samples.
+    pub spacing: S,
+    width: usize,
+    height: usize,
+    values: Vec<T>,
+}
+
+/// A three-dimensional regular grid of samples.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Grid3<S, T> {
+    /// The position of the first sample.
+    pub origin: Point3<S>,
+    /// The distance between neighbouring samples.
+    pub spacing: S,
+    width: usize,
+    height: usize,
+    depth: usize,
+    values: Vec<T>,
+}
+
+/// Find the samples on either side of the coordinate `x`, in units of the
+/// spacing, along an axis of `n` samples, and the weight of the second one.
+fn bracket<S: BaseFloat>(x: S, n: usize) -> (usize, usize, S) {
+    let last = n - 1;
+    let x = x.max(S::zero()).min(cast(last).unwrap());
+    let i = cast::<_, usize>(x.floor()).unwrap().min(last.saturating_sub(1));
+    (i, (i + 1).min(last), x - cast(i).unwrap())
+}
+
+#[inline]
+fn lerp<S: BaseFloat, T: Copy + Add<T, Output = T> + Mul<S, Output = T>>(a: T, b: T, t: S) -> T {
+    a * (S::one() - t) + b * t
+}
+
+/// The derivative along an axis of `n` samples `spacing` apart at the
+/// sample `i`, where `value` gives the samples along the axis.
+fn derivative<S, T, F>(value: F, i: usize, n: usize, spacing: S) -> T
+where
+    S: BaseFloat,
+    T: Copy + Sub<T, Output = T> + Mul<S, Output = T>,
+    F: Fn(usize) -> T,
+{
+    if n == 1 {
+        return value(i) * S::zero();
+    }
+    let (before, after) = (i.saturating_sub(1), (i + 1).min(n - 1));
+    let distance: S = cast(after - before).unwrap();
+    (value(after) - value(before)) * (S::one() / (distance * spacing))
+}
+
+impl<S: BaseFloat, T: Copy> Grid2<S, T> {
+    /// Construct a grid of `width` by `height` samples from their values,
+    /// with the `x` index varying fastest.
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, or if the number of values is not
+    /// `width * height`.
+    pub fn new(
+        origin: Point2<S>,
+        spacing: S,
+        width: usize,
+        height: usize,
+        values: Vec<T>,
+    ) -> Grid2<S, T> {
+        assert!(width > 0 && height > 0, "the grid must not be empty");
+        assert_eq!(values.len(), width * height, "wrong number of samples");
+        Grid2 {
+            origin,
+            spacing,
+            width,
+            height,
+            values,
+        }
+    }
+
+    /// Construct a grid of `width` by `height` samples, calling `f` with the
+    /// indices of each sample to compute its value.
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(
+        origin: Point2<S>,
+        spacing: S,
+        width: usize,
+        height: usize,
+        mut f: F,
+    ) -> Grid2<S, T> {
+        let values = (0..height)
+            .flat_map(|j| (0..width).map(move |i| (i, j)))
+            .map(|(i, j)| f(i, j))
+            .collect();
+        Grid2::new(origin, spacing, width, height, values)
+    }
+
+    /// The number of samples along `x`.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of samples along `y`.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The values of the samples, with the `x` index varying fastest.
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The values of the samples, with the `x` index varying fastest.
+    #[inline]
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// The value of the sample with the indices `i` along `x` and `j` along
+    /// `y`.
+    #[inline]
+    pub fn get(&self, i: usize, j: usize) -> T {
+        assert!(i < self.width && j < self.height, "sample out of bounds");
+        self.values[j * self.width + i]
+    }
+
+    /// The position of the sample with the indices `i` and `j`.
+    #[inline]
+    pub fn position(&self, i: usize, j: usize) -> Point2<S> {
+        let index = Vector2::new(i, j).map(|n| cast::<_, S>(n).unwrap());
+        self.origin + index * self.spacing
+    }
+}
+
+impl<S: BaseFloat, T: Copy + Add<T, Output = T> + Mul<S, Output = T>> Grid2<S, T> {
+    /// Interpolate the samples bilinearly at `p`.
+    pub fn sample(&self, p: Point2<S>) -> T {
+        let local = (p - self.origin) / self.spacing;
+        let (i0, i1, tx) = bracket(local.x, self.width);
+        let (j0, j1, ty) = bracket(local.y, self.height);
+        let bottom = lerp(self.get(i0, j0), self.get(i1, j0), tx);
+        let top = lerp(self.get(i0, j1), self.get(i1, j1), tx);
+        lerp(bottom, top, ty)
+    }
+}
+
+impl<S: BaseFloat, T: Copy> Grid3<S, T> {
+    /// Construct a grid of `width` by `height` by `depth` samples from their
+    /// values, with the `x` index varying fastest and the `z` index
+    /// slowest.
+    ///
+    /// # Panics
+    ///
+    /// If the grid is empty, or if the number of values is not
+    /// `width * height * depth`.
+    pub fn new(
+        origin: Point3<S>,
+        spacing: S,
+        width: usize,
+        height: usize,
+        depth: usize,
+        values: Vec<T>,
+    ) -> Grid3<S, T> {
+        assert!(width > 0 && height > 0 && depth > 0, "the grid must not be empty");
+        assert_eq!(values.len(), width * height * depth, "wrong number of samples");
+        Grid3 {
+            origin,
+            spacing,
+            width,
+            height,
+            depth,
+            values,
+        }
+    }
+
+    /// Construct a grid of `width` by `height` by `depth` samples, calling
+    /// `f` with the indices of each sample to compute its value.
+    pub fn from_fn<F: FnMut(usize, usize, usize) -> T>(
+        origin: Point3<S>,
+        spacing: S,
+        width: usize,
+        height: usize,
+        depth: usize,
+        mut f: F,
+    ) -> Grid3<S, T> {
+        let values = (0..depth)
+            .flat_map(|k| (0..height).map(move |j| (j, k)))
+            .flat_map(|(j, k)| (0..width).map(move |i| (i, j, k)))
+            .map(|(i, j, k)| f(i, j, k))
+            .collect();
+        Grid3::new(origin, spacing, width, height, depth, values)
+    }
+
+    /// The number of samples along `x`.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of samples along `y`.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The number of samples along `z`.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// The values of the samples, with the `x` index varying fastest and
+    /// the `z` index slowest.
+    #[inline]
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// The values of the samples, with the `x` index varying fastest and
+    /// the `z` index slowest.
+    #[inline]
+    pub fn values_mut(&mut self) -> &mut [T] {
+        &mut self.values
+    }
+
+    /// The value of the sample with the indices `i` along `x`, `j` along
+    /// `y` and `k` along `z`.
+    #[inline]
+    pub fn get(&self, i: usize, j: usize, k: usize) -> T {
+        assert!(
+            i < self.width && j < self.height && k < self.depth,
+            "sample out of bounds"
+        );
+        self.values[(k * self.height + j) * self.width + i]
+    }
+
+    /// The position of the sample with the indices `i`, `j` and `k`.
+    #[inline]
+    pub fn position(&self, i: usize, j: usize, k: usize) -> Point3<S> {
+        let index = Vector3::new(i, j, k).map(|n| cast::<_, S>(n).unwrap());
+        self.origin + index * self.spacing
+    }
+}
+
+impl<S: BaseFloat, T: Copy + Add<T, Output = T> + Mul<S, Output = T>> Grid3<S, T> {
+    /// Interpolate the samples trilinearly at `p`.
+    pub fn sample(&self, p: Point3<S>) -> T {
+        let local = (p - self.origin) / self.spacing;
+        let (i0, i1, tx) = bracket(local.x, self.width);
+        let (j0, j1, ty) = bracket(local.y, self.height);
+        let (k0, k1, tz) = bracket(local.z, self.depth);
+        let layer = |k| {
+            let bottom = lerp(self.get(i0, j0, k), self.get(i1, j0, k), tx);
+            let top = lerp(self.get(i0, j1, k), self.get(i1, j1, k), tx);
+            lerp(bottom, top, ty)
+        };
+        lerp(layer(k0), layer(k1), tz)
+    }
+}
+
+impl<S: BaseFloat> Grid2<S, S> {
+    /// The gradient of the field at each sample.
+    pub fn gradient(&self) -> Grid2<S, Vector2<S>> {
+        let (w, h, d) = (self.width, self.height, self.spacing);
+        Grid2::from_fn(self.origin, d, w, h, |i, j| {
+            Vector2::new(
+                derivative(|i| self.get(i, j), i, w, d),
+                derivative(|j| self.get(i, j), j, h, d),
+            )
+        })
+    }
+}
+
+impl<S: BaseFloat> Grid2<S, Vector2<S>> {
+    /// The divergence of the field at each sample.
+    pub fn divergence(&self) -> Grid2<S, S> {
+        let (w, h, d) = (self.width, self.height, self.spacing);
+        Grid2::from_fn(self.origin, d, w, h, |i, j| {
+            derivative(|i| self.get(i, j).x, i, w, d) + derivative(|j| self.get(i, j).y, j, h, d)
+        })
+    }
+
+    /// The curl of the field at each sample, which is the rate of rotation
+    /// around the `z` axis, counter-clockwise.
+    pub fn curl(&self) -> Grid2<S, S> {
+        let (w, h, d) = (self.width, self.height, self.spacing);
+        Grid2::from_fn(self.origin, d, w, h, |i, j| {
+            derivative(|i| self.get(i, j).y, i, w, d) - derivative(|j| self.get(i, j).x, j, h, d)
+        })
+    }
+}
+
+impl<S: BaseFloat> Grid3<S, S> {
+    /// The gradient of the field at each sample.
+    pub fn gradient(&self) -> Grid3<S, Vector3<S>> {
+        let (w, h, l, d) = (self.width, self.height, self.depth, self.spacing);
+        Grid3::from_fn(self.origin, d, w, h, l, |i, j, k| {
+            Vector3::new(
+                derivative(|i| self.get(i, j, k), i, w, d),
+                derivative(|j| self.get(i, j, k), j, h, d),
+                derivative(|k| self.get(i, j, k), k, l, d),
+            )
+        })
+    }
+}
+
+impl<S: BaseFloat> Grid3<S, Vector3<S>> {
+    /// The divergence of the field at each sample.
+    pub fn divergence(&self) -> Grid3<S, S> {
+        let (w, h, l, d) = (self.width, self.height, self.depth, self.spacing);
+        Grid3::from_fn(self.origin, d, w, h, l, |i, j, k| {
+            derivative(|i| self.get(i, j, k).x, i, w, d)
+                + derivative(|j| self.get(i, j, k).y, j, h, d)
+                + derivative(|k| self.get(i, j, k).z, k, l, d)
+        })
+    }
+
+    /// The curl of the field at each sample.
+    pub fn curl(&self) -> Grid3<S, Vector3<S>> {
+        let (w, h, l, d) = (self.width, self.height, self.depth, self.spacing);
+        Grid3::from_fn(self.origin, d, w, h, l, |i, j, k| {
+            let dx = derivative(|i| self.get(i, j, k), i, w, d);
+            let dy = derivative(|j| self.get(i, j, k), j, h, d);
+            let dz = derivative(|k| self.get(i, j, k), k, l, d);
+            Vector3::new(dy.z - dz.y, dz.x - dx.z, dx.y - dy.x)
+        })
+    }
+}
+
+/// A cell waiting to be visited by the search of `distance_field`, ordered
+/// so that the closest cell is at the top of the heap.
+struct Visit<S> {
+    distance: S,
+    index: usize,
+}
+
+impl<S: BaseFloat> PartialEq for Visit<S> {
+    fn eq(&self, other: &Visit<S>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: BaseFloat> Eq for Visit<S> {}
+
+impl<S: BaseFloat> PartialOrd for Visit<S> {
+    fn partial_cmp(&self, other: &Visit<S>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: BaseFloat> Ord for Visit<S> {
+    fn cmp(&self, other: &Visit<S>) -> Ordering {
+        other
+            .distance
+            .partial_cmp(&self.distance)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// The offsets to the eight neighbours of a cell.
+const NEIGHBOURS: [(isize, isize); 8] = [
+    (1, 0),
+    (-1, 0),
+    (0, 1),
+    (0, -1),
+    (1, 1),
+    (-1, 1),
+    (1, -1),
+    (-1, -1),
+];
+
+impl<S: BaseFloat> Grid2<S, bool> {
+    /// The neighbour of the walkable cell `(i, j)` at `offset`, if it can be
+    /// moved to. Diagonal moves are not allowed to cut corners.
+    fn step(&self, i: usize, j: usize, offset: (isize, isize)) -> Option<(usize, usize)> {
+        let walkable = |di: isize, dj: isize| {
+            let (x, y) = (i as isize + di, j as isize + dj);
+            if x < 0 || y < 0 || x >= self.width as isize || y >= self.height as isize {
+                return None;
+            }
+            let (x, y) = (x as usize, y as usize);
+            if self.get(x, y) {
+                Some((x, y))
+            } else {
+                None
+            }
+        };
+        let (di, dj) = offset;
+        if di != 0 && dj != 0 && (walkable(di, 0).is_none() || walkable(0, dj).is_none()) {
+            return None;
+        }
+        walkable(di, dj)
+    }
+
+    /// The length of the shortest path from each walkable cell to the
+    /// closest of the `goals`, given by their indices.
+    ///
+    /// Paths move between the centers of neighbouring walkable cells,
+    /// including diagonally as long as they do not cut the corner of a
+    /// blocked cell, and are measured in the units of the spacing of the
+    /// grid. Blocked and unreachable cells are infinitely far. This is the
+    /// brushfire expansion of Dijkstra's algorithm from all goals at once.
+    ///
+    /// ```rust
+    /// use cgmath::field::Grid2;
+    /// use cgmath::{Point2, Vector2};
+    ///
+    /// // A wall across the middle, with a gap on the right.
+    /// let walkable = Grid2::from_fn(Point2::new(0.0, 0.0), 1.0f64, 4, 3, |i, j| j != 1 || i == 3);
+    /// let distances = walkable.distance_field(&[(0, 0)]);
+    /// assert_eq!(distances.get(3, 2), 5.0);
+    /// assert!(distances.get(1, 1).is_infinite());
+    ///
+    /// let flow = distances.flow_field();
+    /// assert_eq!(flow.get(3, 1), Vector2::new(0.0, -1.0));
+    /// ```
+    pub fn distance_field(&self, goals: &[(usize, usize)]) -> Grid2<S, S> {
+        let mut distances = vec![S::infinity(); self.values.len()];
+        let mut heap = BinaryHeap::new();
+        for &(i, j) in goals {
+            if self.get(i, j) {
+                let index = j * self.width + i;
+                distances[index] = S::zero();
+                heap.push(Visit {
+                    distance: S::zero(),
+                    index,
+                });
+            }
+        }
+        let diagonal = (S::one() + S::one()).sqrt() * self.spacing;
+        while let Some(Visit { distance, index }) = heap.pop() {
+            if distance > distances[index] {
+                continue;
+            }
+            let (i, j) = (index % self.width, index / self.width);
+            for &offset in &NEIGHBOURS {
+                if let Some((x, y)) = self.step(i, j, offset) {
+                    let length = if offset.0 != 0 && offset.1 != 0 {
+                        diagonal
+                    } else {
+                        self.spacing
+                    };
+                    let next = y * self.width + x;
+                    if distance + length < distances[next] {
+                        distances[next] = distance + length;
+                        heap.push(Visit {
+                            distance: distance + length,
+                            index: next,
+                        });
+                    }
+                }
+            }
+        }
+        Grid2::new(self.origin, self.spacing, self.width, self.height, distances)
+    }
+}
+
+impl<S: BaseFloat> Grid2<S, S> {
+    /// The direction of steepest descent of a distance field created by
+    /// `Grid2::distance_field`, at each cell.
+    ///
+    /// Each cell points towards the neighbour closest to the goals, as a
+    /// unit vector, with the same moves as the distance field. Agents
+    /// following these directions reach the goals along shortest paths. The
+    /// goals, and the cells from which no neighbour is closer, have a zero
+    /// direction.
+    pub fn flow_field(&self) -> Grid2<S, Vector2<S>> {
+        let walkable = Grid2::new(
+            self.origin,
+            self.spacing,
+            self.width,
+            self.height,
+            self.values.iter().map(|d| d.is_finite()).collect(),
+        );
+        Grid2::from_fn(self.origin, self.spacing, self.width, self.height, |i, j| {
+            let mut best = (self.get(i, j), Vector2::zero());
+            if !walkable.get(i, j) {
+                return best.1;
+            }
+            for &offset in &NEIGHBOURS {
+                if let Some((x, y)) = walkable.step(i, j, offset) {
+                    if self.get(x, y) < best.0 {
+                        let (dx, dy) = offset;
+                        best = (self.get(x, y), Vector2::new(cast(dx).unwrap(), cast(dy).unwrap()));
+                    }
+                }
+            }
+            if best.1 == Vector2::zero() {
+                best.1
+            } else {
+                best.1.normalize()
+            }
+        })
+    }
+}
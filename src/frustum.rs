@@ -0,0 +1,324 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use structure::*;
+
+use approx::AbsDiffEq;
+use aabb::{box_edges, Aabb3};
+use matrix::Matrix4;
+use num::BaseFloat;
+use plane::{intersect_planes, Plane};
+use point::Point3;
+use projection::ortho;
+use transform::Transform;
+use vector::{Vector3, Vector4};
+
+/// A view frustum, bounded by six planes whose normals point inside.
+///
+/// ```rust
+/// use cgmath::{perspective, Deg, Frustum, Point3};
+///
+/// let frustum = Frustum::from_matrix4(perspective(Deg(90.0f64), 1.0, 1.0, 10.0)).unwrap();
+/// assert!((frustum.near.distance(Point3::new(0.0, 0.0, -3.0)) - 2.0).abs() < 1e-12);
+/// assert!(frustum.left.distance(Point3::new(-5.0, 0.0, -3.0)) < 0.0);
+///
+/// let corners = frustum.corners();
+/// assert!((corners[7].x - 10.0).abs() < 1e-9);
+/// assert!((corners[7].z + 10.0).abs() < 1e-9);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Frustum<S> {
+    pub left: Plane<S>,
+    pub right: Plane<S>,
+    pub bottom: Plane<S>,
+    pub top: Plane<S>,
+    pub near: Plane<S>,
+    pub far: Plane<S>,
+}
+
+impl<S: BaseFloat> Frustum<S> {
+    /// Extract the frustum of a projection matrix, or of a combined view and
+    /// projection matrix, in which case the planes are in world space.
+    ///
+    /// The matrix is expected to map the frustum to the `[-1, 1]` cube of
+    /// normalized device coordinates, as the projections of this crate do.
+    /// This uses the method from "Fast Extraction of Viewing Frustum Planes
+    /// from the World-View-Projection Matrix" by Gribb and Hartmann, and
+    /// returns `None` if a plane is degenerate.
+    pub fn from_matrix4(m: Matrix4<S>) -> Option<Frustum<S>> {
+        let row = |i: usize| m.row(i);
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        Some(Frustum {
+            left: Plane::from_vector4(w + x)?,
+            right: Plane::from_vector4(w - x)?,
+            bottom: Plane::from_vector4(w + y)?,
+            top: Plane::from_vector4(w - y)?,
+            near: Plane::from_vector4(w + z)?,
+            far: Plane::from_vector4(w - z)?,
+        })
+    }
+
+    /// Construct a frustum from its planes, in the order returned by
+    /// `to_planes`.
+    #[inline]
+    pub fn from_planes(planes: [Plane<S>; 6]) -> Frustum<S> {
+        let [left, right, bottom, top, near, far] = planes;
+        Frustum {
+            left,
+            right,
+            bottom,
+            top,
+            near,
+            far,
+        }
+    }
+
+    /// The planes of the frustum, in the order left, right, bottom, top,
+    /// near and far.
+    #[inline]
+    pub fn to_planes(&self) -> [Plane<S>; 6] {
+        [
+            self.left,
+            self.right,
+            self.bottom,
+            self.top,
+            self.near,
+            self.far,
+        ]
+    }
+
+    /// Construct a frustum from plane equations packed by
+    /// `to_vector4_array`, or `None` if one of them is degenerate.
+    pub fn from_vector4_array(planes: [Vector4<S>; 6]) -> Option<Frustum<S>> {
+        let [left, right, bottom, top, near, far] = planes;
+        Some(Frustum {
+            left: Plane::from_vector4(left)?,
+            right: Plane::from_vector4(right)?,
+            bottom: Plane::from_vector4(bottom)?,
+            top: Plane::from_vector4(top)?,
+            near: Plane::from_vector4(near)?,
+            far: Plane::from_vector4(far)?,
+        })
+    }
+
+    /// The plane equations of the frustum packed as by `Plane::to_vector4`,
+    /// in the order of `to_planes`, ready to be uploaded for culling on the
+    /// GPU.
+    ///
+    /// A point is inside the frustum when its homogeneous coordinates have a
+    /// non-negative dot product with every vector.
+    pub fn to_vector4_array(&self) -> [Vector4<S>; 6] {
+        let planes = self.to_planes();
+        let mut packed = [Vector4::zero(); 6];
+        for (v, plane) in packed.iter_mut().zip(planes.iter()) {
+            *v = plane.to_vector4();
+        }
+        packed
+    }
+
+    /// The eight corners of the frustum, where the bits of the index of a
+    /// corner select the right, top and far planes over the left, bottom and
+    /// near planes.
+    ///
+    /// Corners where the planes do not meet, like those of an infinite far
+    /// plane, are at the origin.
+    pub fn corners(&self) -> [Point3<S>; 8] {
+        let mut corners = [Point3::origin(); 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let x = if i & 1 == 0 { &self.left } else { &self.right };
+            let y = if i & 2 == 0 { &self.bottom } else { &self.top };
+            let z = if i & 4 == 0 { &self.near } else { &self.far };
+            *corner = intersect_planes(x, y, z).unwrap_or_else(Point3::origin);
+        }
+        corners
+    }
+
+    /// Build a matrix mapping the frustum to the `[-1, 1]` cube of
+    /// normalized device coordinates, which is the inverse of
+    /// `from_matrix4`. This returns `None` when the planes do not bound a
+    /// frustum with a finite far plane, with their normals pointing inside.
+    /// Five of the corners of the frustum determine the matrix, and the
+    /// other three must land within `epsilon` of the corners of the cube.
+    ///
+    /// The matrix is only determined up to a positive scale factor. It is
+    /// scaled so that its last row has unit length, which gives back the
+    /// matrices of the projections of this crate.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{perspective, Deg, Frustum};
+    ///
+    /// let projection = perspective(Deg(60.0f64), 1.5, 0.5, 50.0);
+    /// let frustum = Frustum::from_matrix4(projection).unwrap();
+    /// let rebuilt = frustum.to_matrix4(1e-9).unwrap();
+    /// assert!((0..4).all(|i| (projection[i] - rebuilt[i]).magnitude() < 1e-9));
+    /// ```
+    pub fn to_matrix4(&self, epsilon: S) -> Option<Matrix4<S>> {
+        let corners = self.corners();
+        let target = |i: usize| {
+            let sign = |bit: usize| if i & bit == 0 { -S::one() } else { S::one() };
+            Vector4::new(sign(1), sign(2), sign(4), S::one())
+        };
+        let source = |i: usize| corners[i].to_homogeneous();
+
+        // A projective map is determined by the images of five points in
+        // general position. No four of these corners are coplanar.
+        let basis = |p: &dyn Fn(usize) -> Vector4<S>| {
+            let m = Matrix4::from_cols(p(0), p(1), p(2), p(4));
+            let w = m.invert()? * p(7);
+            Some(Matrix4::from_cols(m.x * w.x, m.y * w.y, m.z * w.z, m.w * w.w))
+        };
+        let mut m = basis(&target)? * basis(&source)?.invert()?;
+
+        let centroid = Point3::centroid(&corners);
+        if self.to_planes().iter().any(|plane| plane.distance(centroid) <= S::zero()) {
+            return None;
+        }
+        let mut scale = S::one() / m.row(3).magnitude();
+        if m.row(3).dot(centroid.to_homogeneous()) < S::zero() {
+            scale = -scale;
+        }
+        m *= scale;
+
+        // The other three corners must also land on the cube.
+        for i in 0..8 {
+            let clip = m * source(i);
+            if clip.w <= S::zero() || !(clip / clip.w).abs_diff_eq(&target(i), epsilon) {
+                return None;
+            }
+        }
+        Some(m)
+    }
+
+    /// Test whether the point is inside the frustum or on its boundary.
+    #[inline]
+    pub fn contains_point(&self, p: Point3<S>) -> bool {
+        self.to_planes().iter().all(|plane| plane.distance(p) >= S::zero())
+    }
+
+    /// Create line segments along the twelve edges of the frustum.
+    pub fn to_wireframe_lines(&self) -> Vec<(Point3<S>, Point3<S>)> {
+        box_edges(&self.corners())
+    }
+
+    /// The vertices of the faces of the intersection of the frustum with a
+    /// box, which has every corner of that convex volume among them.
+    ///
+    /// The frustum must have a finite far plane. Returns no points if the
+    /// frustum and the box do not overlap.
+    pub fn clip_to_aabb(&self, aabb: &Aabb3<S>) -> Vec<Point3<S>> {
+        let box_planes = [
+            Plane::from_point_normal(aabb.min, Vector3::unit_x()),
+            Plane::from_point_normal(aabb.max, -Vector3::unit_x()),
+            Plane::from_point_normal(aabb.min, Vector3::unit_y()),
+            Plane::from_point_normal(aabb.max, -Vector3::unit_y()),
+            Plane::from_point_normal(aabb.min, Vector3::unit_z()),
+            Plane::from_point_normal(aabb.max, -Vector3::unit_z()),
+        ];
+        let mut points = Vec::new();
+        for &(corners, planes) in &[
+            (self.corners(), &box_planes),
+            (aabb.corners(), &self.to_planes()),
+        ] {
+            for face in &BOX_FACES {
+                let polygon = face.iter().map(|&i| corners[i]).collect();
+                points.extend(clip_polygon(polygon, planes));
+            }
+        }
+        points
+    }
+
+    /// The bounds in light space of the part of the frustum inside the
+    /// scene bounds, from which a tight orthographic projection for the
+    /// shadow map of a directional light is built.
+    ///
+    /// `light_view` is the view matrix of the light, looking down its `-z`
+    /// axis as the view matrices of this crate do. The bounds are those of
+    /// the intersection of the frustum with `scene` across the direction of
+    /// the light, but reach back towards the light to the end of the scene,
+    /// so that objects outside of the frustum still cast their shadows in
+    /// it. Returns `None` if the frustum and the scene do not overlap.
+    pub fn shadow_bounds(&self, scene: &Aabb3<S>, light_view: &Matrix4<S>) -> Option<Aabb3<S>> {
+        let points = self.clip_to_aabb(scene);
+        let mut bounds = Aabb3::from_points(points.iter().map(|&p| light_view.transform_point(p)))?;
+        bounds.max.z = bounds.max.z.max(scene.transformed(light_view).max.z);
+        Some(bounds)
+    }
+
+    /// The orthographic projection of a directional light tightly covering
+    /// the part of the frustum inside the scene bounds, which together with
+    /// `light_view` is the crop matrix of the shadow map.
+    ///
+    /// See `shadow_bounds` for the area covered. Returns `None` if the
+    /// frustum and the scene do not overlap.
+    ///
+    /// ```rust
+    /// use cgmath::{perspective, Aabb3, Deg, Frustum, Matrix4, Point3, Transform, Vector3};
+    ///
+    /// let eye = Point3::new(0.0, 2.0, 0.0);
+    /// let view = Matrix4::look_at(eye, Point3::new(0.0, 2.0, -1.0), Vector3::unit_y());
+    /// let projection = perspective(Deg(60.0f64), 1.0, 0.1, 50.0);
+    /// let camera = Frustum::from_matrix4(projection * view).unwrap();
+    /// let scene = Aabb3::new(Point3::new(-10.0, 0.0, -10.0), Point3::new(10.0, 5.0, 10.0));
+    /// let sun = Point3::new(0.0, 20.0, 0.0);
+    /// let light_view = Matrix4::look_at(sun, Point3::new(0.0, 0.0, 0.0), -Vector3::unit_z());
+    ///
+    /// let shadow = camera.shadow_projection(&scene, &light_view).unwrap() * light_view;
+    /// // The scene in front of the camera is in the shadow map.
+    /// let p = shadow.transform_point(Point3::new(0.0, 1.0, -5.0));
+    /// assert!(p.x.abs() <= 1.0 && p.y.abs() <= 1.0 && p.z.abs() <= 1.0);
+    /// // The scene behind the camera is not.
+    /// assert!(shadow.transform_point(Point3::new(0.0, 1.0, 5.0)).y < -1.0);
+    /// ```
+    pub fn shadow_projection(
+        &self,
+        scene: &Aabb3<S>,
+        light_view: &Matrix4<S>,
+    ) -> Option<Matrix4<S>> {
+        let b = self.shadow_bounds(scene, light_view)?;
+        Some(ortho(b.min.x, b.max.x, b.min.y, b.max.y, -b.max.z, -b.min.z))
+    }
+}
+
+/// The faces of a box or a frustum, as indices of the corners of each face
+/// in order around it.
+const BOX_FACES: [[usize; 4]; 6] = [
+    [0, 2, 6, 4],
+    [1, 3, 7, 5],
+    [0, 1, 5, 4],
+    [2, 3, 7, 6],
+    [0, 1, 3, 2],
+    [4, 5, 7, 6],
+];
+
+/// Clip a convex polygon to the positive sides of the planes.
+fn clip_polygon<S: BaseFloat>(mut polygon: Vec<Point3<S>>, planes: &[Plane<S>]) -> Vec<Point3<S>> {
+    for plane in planes {
+        let mut clipped = Vec::with_capacity(polygon.len() + 1);
+        for (i, &a) in polygon.iter().enumerate() {
+            let b = polygon[(i + 1) % polygon.len()];
+            let (da, db) = (plane.distance(a), plane.distance(b));
+            if da >= S::zero() {
+                clipped.push(a);
+            }
+            if (da >= S::zero()) != (db >= S::zero()) {
+                clipped.push(a + (b - a) * (da / (da - db)));
+            }
+        }
+        polygon = clipped;
+    }
+    polygon
+}
@@ -0,0 +1,270 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between geodetic coordinates, Earth-centered Earth-fixed
+//! (ECEF) positions and local east-north-up (ENU) tangent frames, and the
+//! position of the sun in the sky.
+//!
+//! ```rust
+//! use cgmath::geo::{Ellipsoid, Geodetic, LocalTangent};
+//! use cgmath::Deg;
+//!
+//! let wgs84 = Ellipsoid::wgs84();
+//! let origin = Geodetic::new(Deg(48.8584f64), Deg(2.2945), 35.0);
+//! let local = LocalTangent::new(&wgs84, origin);
+//!
+//! // A point 100 metres above the origin is straight up in the local frame.
+//! let above = wgs84.to_ecef(Geodetic::new(Deg(48.8584), Deg(2.2945), 135.0));
+//! let enu = local.to_local(above);
+//! assert!(enu.x.abs() < 1e-6 && enu.y.abs() < 1e-6);
+//! assert!((enu.z - 100.0).abs() < 1e-6);
+//! ```
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::{Deg, Rad};
+use matrix::Matrix3;
+use num::BaseFloat;
+use point::Point3;
+use vector::Vector3;
+
+/// A reference ellipsoid approximating the shape of a planet.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ellipsoid<S> {
+    /// The equatorial radius.
+    pub semi_major: S,
+    /// The flattening, `(a - b) / a` where `b` is the polar radius.
+    pub flattening: S,
+}
+
+/// A position given by its latitude, longitude and height above the
+/// ellipsoid.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Geodetic<S> {
+    /// The geodetic latitude, positive towards the north.
+    pub latitude: Rad<S>,
+    /// The longitude, positive towards the east.
+    pub longitude: Rad<S>,
+    /// The height above the surface of the ellipsoid.
+    pub altitude: S,
+}
+
+impl<S: BaseFloat> Geodetic<S> {
+    /// Construct a geodetic position.
+    #[inline]
+    pub fn new<A: Into<Rad<S>>>(latitude: A, longitude: A, altitude: S) -> Geodetic<S> {
+        Geodetic {
+            latitude: latitude.into(),
+            longitude: longitude.into(),
+            altitude,
+        }
+    }
+
+    /// The rotation from the local east-north-up frame at this position to
+    /// the ECEF frame. The columns of the matrix are the east, north and up
+    /// directions.
+    pub fn enu_frame(&self) -> Matrix3<S> {
+        let (sin_lat, cos_lat) = Rad::sin_cos(self.latitude);
+        let (sin_lon, cos_lon) = Rad::sin_cos(self.longitude);
+
+        Matrix3::from_cols(
+            Vector3::new(-sin_lon, cos_lon, S::zero()),
+            Vector3::new(-sin_lat * cos_lon, -sin_lat * sin_lon, cos_lat),
+            Vector3::new(cos_lat * cos_lon, cos_lat * sin_lon, sin_lat),
+        )
+    }
+}
+
+impl<S: BaseFloat> Ellipsoid<S> {
+    /// The WGS84 ellipsoid, used by GPS.
+    pub fn wgs84() -> Ellipsoid<S> {
+        Ellipsoid {
+            semi_major: cast(6_378_137.0).unwrap(),
+            flattening: cast(1.0 / 298.257_223_563).unwrap(),
+        }
+    }
+
+    /// The square of the first eccentricity.
+    #[inline]
+    pub fn eccentricity2(&self) -> S {
+        self.flattening * (S::one() + S::one() - self.flattening)
+    }
+
+    /// The radius of curvature in the prime vertical at a given latitude.
+    #[inline]
+    fn prime_vertical_radius(&self, sin_lat: S) -> S {
+        self.semi_major / (S::one() - self.eccentricity2() * sin_lat * sin_lat).sqrt()
+    }
+
+    /// Convert a geodetic position to an ECEF position.
+    pub fn to_ecef(&self, geodetic: Geodetic<S>) -> Point3<S> {
+        let (sin_lat, cos_lat) = Rad::sin_cos(geodetic.latitude);
+        let (sin_lon, cos_lon) = Rad::sin_cos(geodetic.longitude);
+        let n = self.prime_vertical_radius(sin_lat);
+        let h = geodetic.altitude;
+
+        Point3::new(
+            (n + h) * cos_lat * cos_lon,
+            (n + h) * cos_lat * sin_lon,
+            (n * (S::one() - self.eccentricity2()) + h) * sin_lat,
+        )
+    }
+
+    /// Convert an ECEF position to a geodetic position.
+    ///
+    /// The latitude is refined iteratively until it converges, which takes a
+    /// handful of iterations for points near the surface.
+    pub fn to_geodetic(&self, p: Point3<S>) -> Geodetic<S> {
+        let e2 = self.eccentricity2();
+        let r = (p.x * p.x + p.y * p.y).sqrt();
+        let longitude = Rad::atan2(p.y, p.x);
+
+        let mut latitude = Rad::atan2(p.z, r * (S::one() - e2));
+        let mut altitude = S::zero();
+        for _ in 0..16 {
+            let (sin_lat, cos_lat) = Rad::sin_cos(latitude);
+            let n = self.prime_vertical_radius(sin_lat);
+            altitude = r * cos_lat + (p.z + e2 * n * sin_lat) * sin_lat - n;
+            let next = Rad::atan2(p.z, r * (S::one() - e2 * n / (n + altitude)));
+            let converged = (next - latitude).0.abs() <= S::epsilon();
+            latitude = next;
+            if converged {
+                break;
+            }
+        }
+
+        Geodetic {
+            latitude,
+            longitude,
+            altitude,
+        }
+    }
+}
+
+/// A local east-north-up tangent frame, anchored at a point on or near the
+/// surface of an ellipsoid.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LocalTangent<S> {
+    /// The origin of the frame, in ECEF coordinates.
+    pub origin: Point3<S>,
+    /// The rotation from the local frame to the ECEF frame.
+    pub frame: Matrix3<S>,
+}
+
+impl<S: BaseFloat> LocalTangent<S> {
+    /// Create the tangent frame at a geodetic position.
+    pub fn new(ellipsoid: &Ellipsoid<S>, origin: Geodetic<S>) -> LocalTangent<S> {
+        LocalTangent {
+            origin: ellipsoid.to_ecef(origin),
+            frame: origin.enu_frame(),
+        }
+    }
+
+    /// Convert an ECEF position to local east-north-up coordinates.
+    #[inline]
+    pub fn to_local(&self, p: Point3<S>) -> Point3<S> {
+        Point3::from_vec(self.frame.transpose() * (p - self.origin))
+    }
+
+    /// Convert local east-north-up coordinates to an ECEF position.
+    #[inline]
+    pub fn to_ecef(&self, p: Point3<S>) -> Point3<S> {
+        self.origin + self.frame * p.to_vec()
+    }
+}
+
+/// The position of the sun in the sky, as seen from a place on Earth.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolarPosition<S> {
+    /// The compass direction of the sun, clockwise from the north.
+    pub azimuth: Rad<S>,
+    /// The angle of the sun above the horizon, negative at night.
+    pub elevation: Rad<S>,
+}
+
+impl<S: BaseFloat> SolarPosition<S> {
+    /// The unit vector pointing towards the sun, in the local east-north-up
+    /// frame. The light of the sun travels along the opposite direction.
+    pub fn direction(&self) -> Vector3<S> {
+        let (sin_az, cos_az) = Rad::sin_cos(self.azimuth);
+        let (sin_el, cos_el) = Rad::sin_cos(self.elevation);
+        Vector3::new(sin_az * cos_el, cos_az * cos_el, sin_el)
+    }
+}
+
+/// The position of the sun in the sky at `position`, at the time given in
+/// seconds since the Unix epoch, 1970-01-01 00:00 UTC.
+///
+/// This uses the low precision formulas of the Astronomical Almanac, which
+/// are accurate to about a hundredth of a degree between 1950 and 2050,
+/// ignoring the refraction of the atmosphere, which raises the sun by about
+/// half a degree on the horizon. The altitude of the position is ignored.
+/// The time is in double precision whatever `S`, as single precision can
+/// only resolve Unix times to about two minutes.
+///
+/// ```rust
+/// use cgmath::geo::{sun_position, Geodetic};
+/// use cgmath::Deg;
+///
+/// // Noon on the first day of 2000, at the Greenwich observatory.
+/// let greenwich = Geodetic::new(Deg(51.4769f64), Deg(0.0), 0.0);
+/// let sun = sun_position(946_728_000.0, &greenwich);
+/// assert!((Deg::from(sun.elevation).0 - 15.49).abs() < 0.05);
+/// // The sun is nearly due south, pointing along `-y` in the local frame.
+/// assert!(sun.direction().y < -0.96);
+/// ```
+pub fn sun_position<S: BaseFloat>(unix_seconds: f64, position: &Geodetic<S>) -> SolarPosition<S> {
+    let degrees = |x: f64| Rad::from(Deg(x));
+    // The days since the J2000 epoch, 2000-01-01 12:00.
+    let n = unix_seconds / 86_400.0 - 10_957.5;
+
+    // The ecliptic longitude of the sun, from its mean longitude and mean
+    // anomaly, and the obliquity of the ecliptic.
+    let mean_longitude = 280.460 + 0.985_647_4 * n;
+    let anomaly = degrees(357.528 + 0.985_600_3 * n);
+    let longitude = degrees(
+        mean_longitude + 1.915 * Rad::sin(anomaly) + 0.020 * Rad::sin(anomaly * 2.0),
+    );
+    let obliquity = degrees(23.439 - 0.000_000_4 * n);
+
+    // The equatorial coordinates of the sun.
+    let (sin_lon, cos_lon) = Rad::sin_cos(longitude);
+    let right_ascension = Rad::atan2(Rad::cos(obliquity) * sin_lon, cos_lon);
+    let declination = Rad::asin(Rad::sin(obliquity) * sin_lon);
+
+    // The hour angle, from the Greenwich mean sidereal time in hours.
+    let sidereal = 18.697_374_558 + 24.065_709_824_419_08 * n;
+    let longitude: f64 = cast(position.longitude.0).unwrap();
+    let hour_angle = (degrees(sidereal * 15.0) + Rad(longitude) - right_ascension).normalize();
+
+    let latitude: f64 = cast(position.latitude.0).unwrap();
+    let (sin_lat, cos_lat) = Rad::sin_cos(Rad(latitude));
+    let (sin_dec, cos_dec) = Rad::sin_cos(declination);
+    let (sin_ha, cos_ha) = Rad::sin_cos(hour_angle);
+    let east = -cos_dec * sin_ha;
+    let north = cos_lat * sin_dec - sin_lat * cos_dec * cos_ha;
+    let up = sin_lat * sin_dec + cos_lat * cos_dec * cos_ha;
+
+    SolarPosition {
+        azimuth: Rad(cast(Rad::atan2(east, north).normalize().0).unwrap()),
+        elevation: Rad(cast(Rad::asin(up.max(-1.0).min(1.0)).0).unwrap()),
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+use num_traits::cast;
+
+use structure::*;
+
+use num::BaseFloat;
+use vector::Vector3;
+
+/// A ramp of values keyed by position, blending linearly between its stops,
+/// such as the colors of a particle over its lifetime or the transfer
+/// function of a volume renderer.
+///
+/// The stops are kept sorted by position. Before the first stop and after
+/// the last one the gradient holds their values, and two stops at the same
+/// position make a hard edge.
+///
+/// ```rust
+/// use cgmath::color::Rgb;
+/// use cgmath::Gradient;
+///
+/// let fire = Gradient::new(vec![
+///     (0.0, Rgb::new(1.0, 1.0, 0.5)),
+///     (1.0, Rgb::new(0.2, 0.0, 0.0f32)),
+///     (0.5, Rgb::new(1.0, 0.5, 0.0)),
+/// ]);
+/// assert_eq!(fire.sample(0.25), Rgb::new(1.0, 0.75, 0.25));
+/// assert_eq!(fire.sample(2.0), Rgb::new(0.2, 0.0, 0.0));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Gradient<S, T> {
+    stops: Vec<(S, T)>,
+}
+
+impl<S: BaseFloat, T: Interpolate<S>> Gradient<S, T> {
+    /// Construct a gradient from its stops, as pairs of a position and a
+    /// value, in any order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no stops.
+    pub fn new(mut stops: Vec<(S, T)>) -> Gradient<S, T> {
+        assert!(!stops.is_empty(), "a gradient needs at least one stop");
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+        Gradient { stops }
+    }
+
+    /// The gradient holding `value` everywhere.
+    pub fn constant(value: T) -> Gradient<S, T> {
+        Gradient {
+            stops: vec![(S::zero(), value)],
+        }
+    }
+
+    /// The stops of the gradient, sorted by position.
+    #[inline]
+    pub fn stops(&self) -> &[(S, T)] {
+        &self.stops
+    }
+
+    /// The index of the first stop after `position`.
+    fn upper_bound(&self, position: S) -> usize {
+        self.stops
+            .binary_search_by(|stop| {
+                if stop.0 <= position {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_err()
+    }
+
+    /// Add a stop, after the stops at the same position.
+    pub fn add_stop(&mut self, position: S, value: T) {
+        let i = self.upper_bound(position);
+        self.stops.insert(i, (position, value));
+    }
+
+    /// The value of the gradient at `position`.
+    pub fn sample(&self, position: S) -> T {
+        let i = self.upper_bound(position);
+        if i == 0 {
+            return self.stops[0].1;
+        }
+        if i == self.stops.len() {
+            return self.stops[i - 1].1;
+        }
+        let (a, b) = (self.stops[i - 1], self.stops[i]);
+        let amount = (position - a.0) / (b.0 - a.0);
+        let weights = Vector3::new(S::one() - amount, amount, S::zero());
+        T::barycentric((a.1, b.1, b.1), weights)
+    }
+
+    /// The values of the gradient at `count` evenly spaced positions from
+    /// its first stop to its last one, such as the texels of a lookup
+    /// texture.
+    pub fn bake(&self, count: usize) -> Vec<T> {
+        let first = self.stops[0].0;
+        let last = self.stops[self.stops.len() - 1].0;
+        let steps: S = cast(count.saturating_sub(1).max(1)).unwrap();
+        (0..count)
+            .map(|i| {
+                let amount = cast::<usize, S>(i).unwrap() / steps;
+                self.sample(first + (last - first) * amount)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,374 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Coordinate systems for tile-based boards: hexagonal grids and
+//! isometric tiles.
+//!
+//! The hexagonal grids use the axial and cube coordinates described in
+//! "Hexagonal Grids" by Amit Patel, on Red Blob Games.
+//!
+//! ```rust
+//! use cgmath::grid::{Hex, HexLayout, HexOrientation};
+//! use cgmath::Point2;
+//!
+//! let layout = HexLayout::new(HexOrientation::PointyTop, 1.0f64, Point2::new(0.0, 0.0));
+//! let hex = Hex::new(2, -1);
+//! let center = layout.to_point(hex);
+//! assert_eq!(layout.from_point(center + cgmath::Vector2::new(0.3, 0.2)), hex);
+//! assert_eq!(hex.distance(Hex::new(0, 0)), 2);
+//! ```
+
+use std::ops::*;
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use matrix::Matrix3;
+use num::BaseFloat;
+use point::Point2;
+use vector::{Vector2, Vector3};
+
+/// The axial coordinates of a cell of a hexagonal grid.
+///
+/// The third cube coordinate is `s = -q - r`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// The cube coordinates of a cell of a hexagonal grid, which always sum to
+/// zero.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CubeHex {
+    pub q: i32,
+    pub r: i32,
+    pub s: i32,
+}
+
+/// The six directions from a hexagon to its neighbours, counter-clockwise
+/// from the one of increasing `q`.
+const HEX_DIRECTIONS: [Hex; 6] = [
+    Hex { q: 1, r: 0 },
+    Hex { q: 1, r: -1 },
+    Hex { q: 0, r: -1 },
+    Hex { q: -1, r: 0 },
+    Hex { q: -1, r: 1 },
+    Hex { q: 0, r: 1 },
+];
+
+impl Hex {
+    /// Construct a hexagon from its axial coordinates.
+    #[inline]
+    pub const fn new(q: i32, r: i32) -> Hex {
+        Hex { q, r }
+    }
+
+    /// The third cube coordinate.
+    #[inline]
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+
+    /// The neighbour in the given direction, from `0` to `5`,
+    /// counter-clockwise from the one of increasing `q`.
+    ///
+    /// # Panics
+    ///
+    /// If `direction` is `6` or more.
+    #[inline]
+    pub fn neighbor(self, direction: usize) -> Hex {
+        self + HEX_DIRECTIONS[direction]
+    }
+
+    /// The six neighbours of the hexagon.
+    pub fn neighbors(self) -> impl Iterator<Item = Hex> {
+        HEX_DIRECTIONS.iter().map(move |&d| self + d)
+    }
+
+    /// The number of steps between two hexagons.
+    #[inline]
+    pub fn distance(self, other: Hex) -> i32 {
+        let d = self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// The hexagons on the line from `self` to `other`, including both,
+    /// in order.
+    pub fn line_to(self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![self];
+        }
+        // Nudge the line off the edges between hexagons, so that the cells
+        // it goes through are chosen consistently.
+        let nudge = 1e-6;
+        let (q0, r0) = (f64::from(self.q) + nudge, f64::from(self.r) + nudge);
+        let (q1, r1) = (f64::from(other.q) + nudge, f64::from(other.r) + nudge);
+        (0..=n)
+            .map(|i| {
+                let t = f64::from(i) / f64::from(n);
+                let q = q0 + (q1 - q0) * t;
+                let r = r0 + (r1 - r0) * t;
+                Hex::round(q, r)
+            })
+            .collect()
+    }
+
+    /// The hexagons at exactly `radius` steps from this one, starting in
+    /// the direction `4` and going counter-clockwise.
+    pub fn ring(self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        let mut hex = self + HEX_DIRECTIONS[4] * radius as i32;
+        for &direction in HEX_DIRECTIONS.iter() {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex + direction;
+            }
+        }
+        ring
+    }
+
+    /// The hexagon containing the fractional axial coordinates `(q, r)`.
+    pub fn round<S: BaseFloat>(q: S, r: S) -> Hex {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        // Fix the coordinate that was rounded the most, so that the three of
+        // them still sum to zero.
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(cast(rq).unwrap(), cast(rr).unwrap())
+    }
+}
+
+impl From<CubeHex> for Hex {
+    #[inline]
+    fn from(c: CubeHex) -> Hex {
+        Hex::new(c.q, c.r)
+    }
+}
+
+impl From<Hex> for CubeHex {
+    #[inline]
+    fn from(h: Hex) -> CubeHex {
+        CubeHex {
+            q: h.q,
+            r: h.r,
+            s: h.s(),
+        }
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn neg(self) -> Hex {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Hex;
+
+    #[inline]
+    fn mul(self, k: i32) -> Hex {
+        Hex::new(self.q * k, self.r * k)
+    }
+}
+
+/// Which way the hexagons of a grid are laid out.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum HexOrientation {
+    /// The hexagons have a vertex at the top, and form horizontal rows.
+    PointyTop,
+    /// The hexagons have an edge at the top, and form vertical columns.
+    FlatTop,
+}
+
+/// The placement of a hexagonal grid in the plane.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HexLayout<S> {
+    pub orientation: HexOrientation,
+    /// The distance from the center of a hexagon to its vertices.
+    pub size: S,
+    /// The center of the hexagon at the origin of the grid.
+    pub origin: Point2<S>,
+}
+
+impl<S: BaseFloat> HexLayout<S> {
+    /// Construct a layout.
+    #[inline]
+    pub fn new(orientation: HexOrientation, size: S, origin: Point2<S>) -> HexLayout<S> {
+        HexLayout {
+            orientation,
+            size,
+            origin,
+        }
+    }
+
+    /// The matrix taking the axial coordinates of a hexagon to its center.
+    pub fn to_matrix3(&self) -> Matrix3<S> {
+        let sqrt3 = cast::<_, S>(3).unwrap().sqrt();
+        let half: S = cast(0.5).unwrap();
+        let three_halves: S = cast(1.5).unwrap();
+        let (q, r) = match self.orientation {
+            HexOrientation::PointyTop => (
+                Vector2::new(sqrt3, S::zero()),
+                Vector2::new(sqrt3 * half, three_halves),
+            ),
+            HexOrientation::FlatTop => (
+                Vector2::new(three_halves, sqrt3 * half),
+                Vector2::new(S::zero(), sqrt3),
+            ),
+        };
+        Matrix3::from_cols(
+            (q * self.size).extend(S::zero()),
+            (r * self.size).extend(S::zero()),
+            self.origin.to_vec().extend(S::one()),
+        )
+    }
+
+    /// The center of a hexagon.
+    pub fn to_point(&self, hex: Hex) -> Point2<S> {
+        let q: S = cast(hex.q).unwrap();
+        let r: S = cast(hex.r).unwrap();
+        let p = self.to_matrix3() * Vector3::new(q, r, S::one());
+        Point2::new(p.x, p.y)
+    }
+
+    /// The hexagon containing a point.
+    pub fn from_point(&self, p: Point2<S>) -> Hex {
+        let inverse = self.to_matrix3().invert().unwrap();
+        let axial = inverse * p.to_vec().extend(S::one());
+        Hex::round(axial.x, axial.y)
+    }
+
+    /// The six vertices of a hexagon, counter-clockwise.
+    pub fn corners(&self, hex: Hex) -> [Point2<S>; 6] {
+        let center = self.to_point(hex);
+        let step = Rad::<S>::full_turn() / cast::<_, S>(6).unwrap();
+        let start = match self.orientation {
+            HexOrientation::PointyTop => step / cast::<_, S>(2).unwrap(),
+            HexOrientation::FlatTop => Rad::zero(),
+        };
+        let mut corners = [center; 6];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let angle = start + step * cast::<_, S>(i).unwrap();
+            let (sin, cos) = Rad::sin_cos(angle);
+            *corner = center + Vector2::new(cos, sin) * self.size;
+        }
+        corners
+    }
+}
+
+/// The mapping between the coordinates of isometric tiles and the screen.
+///
+/// Tiles are diamonds `tile_size.x` wide and `tile_size.y` tall on screen.
+/// Going along the tile `x` axis moves right and down the screen, and going
+/// along the tile `y` axis moves left and down, with screen coordinates
+/// going down like those of windows.
+///
+/// ```rust
+/// use cgmath::grid::IsometricLayout;
+/// use cgmath::{Point2, Vector2};
+///
+/// let layout = IsometricLayout::new(Vector2::new(64.0, 32.0f64), Point2::new(400.0, 0.0));
+/// assert_eq!(layout.tile_to_screen(Point2::new(1.0, 0.0)), Point2::new(432.0, 16.0));
+/// assert_eq!(layout.tile_at(Point2::new(432.0, 17.0)), (1, 0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IsometricLayout<S> {
+    /// The width and height of a tile on screen.
+    pub tile_size: Vector2<S>,
+    /// The screen position of the top corner of the tile at the origin.
+    pub origin: Point2<S>,
+}
+
+impl<S: BaseFloat> IsometricLayout<S> {
+    /// Construct a layout.
+    #[inline]
+    pub fn new(tile_size: Vector2<S>, origin: Point2<S>) -> IsometricLayout<S> {
+        IsometricLayout { tile_size, origin }
+    }
+
+    /// The matrix taking tile coordinates to screen coordinates.
+    pub fn to_matrix3(&self) -> Matrix3<S> {
+        let half = self.tile_size / cast(2).unwrap();
+        Matrix3::new(
+            half.x, half.y, S::zero(),
+            -half.x, half.y, S::zero(),
+            self.origin.x, self.origin.y, S::one(),
+        )
+    }
+
+    /// The screen position of a point in tile coordinates. The top corner of
+    /// the tile `(i, j)` is at `(i, j)`, and its center at
+    /// `(i + 0.5, j + 0.5)`.
+    pub fn tile_to_screen(&self, tile: Point2<S>) -> Point2<S> {
+        let half = self.tile_size / cast(2).unwrap();
+        Point2::new(
+            self.origin.x + (tile.x - tile.y) * half.x,
+            self.origin.y + (tile.x + tile.y) * half.y,
+        )
+    }
+
+    /// The position in tile coordinates of a point on the screen.
+    pub fn screen_to_tile(&self, screen: Point2<S>) -> Point2<S> {
+        let half = self.tile_size / cast(2).unwrap();
+        let a = (screen.x - self.origin.x) / half.x;
+        let b = (screen.y - self.origin.y) / half.y;
+        let two: S = cast(2).unwrap();
+        Point2::new((b + a) / two, (b - a) / two)
+    }
+
+    /// The tile under a point on the screen.
+    pub fn tile_at(&self, screen: Point2<S>) -> (i32, i32) {
+        let tile = self.screen_to_tile(screen);
+        (cast(tile.x.floor()).unwrap(), cast(tile.y.floor()).unwrap())
+    }
+}
@@ -0,0 +1,443 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for terrain heightfields.
+//!
+//! A heightfield is a regular grid of height samples over the `xz` plane,
+//! with `y` pointing up, as in the view matrices of this crate. Samples are
+//! indexed by row, then by column, where rows go towards positive `z` and
+//! columns towards positive `x`, so `heights[j][i]` is the height at
+//! `x = i * cell_size` and `z = j * cell_size`. Rays are cast at
+//! heightfields stored in a `Grid2`, whose axes are the `x` and `z` axes of
+//! the terrain, and `terrain_mesh` turns them into triangle meshes.
+
+use std::collections::HashSet;
+
+use num_traits::cast;
+
+use structure::*;
+
+use field::Grid2;
+use mesh::{to_tangent_space, vertex_tangents};
+use num::BaseFloat;
+use point::{Point2, Point3};
+use ray::Ray3;
+use vector::{Vector3, Vector4};
+
+/// The unit normal of a heightfield at the center of a 3x3 neighbourhood of
+/// samples, `cell_size` apart, estimated with the Sobel operator.
+///
+/// The Sobel operator weights the samples next to the center twice as much
+/// as the corners, which smooths the estimate across the slope and gives
+/// better results than central differences on noisy terrain.
+///
+/// ```rust
+/// use cgmath::heightfield::sobel_normal;
+/// use cgmath::prelude::*;
+/// use cgmath::Vector3;
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// // A slope rising by one unit per unit towards positive x.
+/// let heights = [[0.0, 1.0, 2.0], [0.0, 1.0, 2.0], [0.0, 1.0, 2.0]];
+/// let normal = sobel_normal(heights, 1.0f64);
+/// assert_relative_eq!(normal, Vector3::new(-1.0, 1.0, 0.0).normalize());
+/// # }
+/// ```
+pub fn sobel_normal<S: BaseFloat>(heights: [[S; 3]; 3], cell_size: S) -> Vector3<S> {
+    let h = heights;
+    let two: S = cast(2).unwrap();
+    let eight: S = cast(8).unwrap();
+    let dx = (h[0][2] + two * h[1][2] + h[2][2]) - (h[0][0] + two * h[1][0] + h[2][0]);
+    let dz = (h[2][0] + two * h[2][1] + h[2][2]) - (h[0][0] + two * h[0][1] + h[0][2]);
+    let scale = eight * cell_size;
+    Vector3::new(-dx / scale, S::one(), -dz / scale).normalize()
+}
+
+/// The normal of a heightfield stored in a grid at the sample `(i, j)`,
+/// with `sobel_normal`, extrapolating the samples linearly past the edges of
+/// the grid so that planes have the same normal everywhere.
+fn grid_normal<S: BaseFloat>(heights: &Grid2<S, S>, i: usize, j: usize) -> Vector3<S> {
+    fn at<S: BaseFloat>(heights: &Grid2<S, S>, i: isize, j: isize) -> S {
+        let (last_i, last_j) = (heights.width() as isize - 1, heights.height() as isize - 1);
+        let two = S::one() + S::one();
+        if i < 0 && last_i > 0 {
+            two * at(heights, 0, j) - at(heights, 1, j)
+        } else if i > last_i && last_i > 0 {
+            two * at(heights, last_i, j) - at(heights, last_i - 1, j)
+        } else if j < 0 && last_j > 0 {
+            two * at(heights, i, 0) - at(heights, i, 1)
+        } else if j > last_j && last_j > 0 {
+            two * at(heights, i, last_j) - at(heights, i, last_j - 1)
+        } else {
+            heights.get(i.max(0).min(last_i) as usize, j.max(0).min(last_j) as usize)
+        }
+    }
+    let (i, j) = (i as isize, j as isize);
+    let row = |dj: isize| {
+        [
+            at(heights, i - 1, j + dj),
+            at(heights, i, j + dj),
+            at(heights, i + 1, j + dj),
+        ]
+    };
+    sobel_normal([row(-1), row(0), row(1)], heights.spacing)
+}
+
+/// A triangle mesh of a heightfield, as built by `terrain_mesh`.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TerrainMesh<S> {
+    /// The positions of the vertices.
+    pub positions: Vec<Point3<S>>,
+    /// The unit normals of the vertices.
+    pub normals: Vec<Vector3<S>>,
+    /// The tangents of the vertices along the `u` texture coordinate, with
+    /// their handedness in `w`, as computed by `mesh::vertex_tangents`.
+    pub tangents: Vec<Vector4<S>>,
+    /// The texture coordinates of the vertices, going from zero to one
+    /// across the grid.
+    pub uvs: Vec<Point2<S>>,
+    /// The triangles, counterclockwise seen from above.
+    pub indices: Vec<[usize; 3]>,
+}
+
+/// The triangle mesh of a heightfield stored in a grid, with a vertex for
+/// each sample and two triangles for each cell.
+///
+/// The first vertices are the samples of the grid, in the order of its
+/// values. The normals are estimated with `sobel_normal`, and the texture
+/// coordinates are `(0, 0)` at the first sample and `(1, 1)` at the last.
+/// With a `skirt` depth, a vertical strip that deep hangs from the edges of
+/// the mesh, facing outwards, to hide the cracks between neighbouring tiles
+/// of terrain at different levels of detail. The bottom vertices of the
+/// skirt repeat the normals, tangents and texture coordinates of the edge
+/// above them.
+///
+/// ```rust
+/// use cgmath::field::Grid2;
+/// use cgmath::heightfield::terrain_mesh;
+/// use cgmath::{Point2, Point3};
+///
+/// let heights = Grid2::from_fn(Point2::new(0.0, 0.0), 2.0f64, 3, 3, |i, j| (i * j) as f64);
+/// let terrain = terrain_mesh(&heights, None);
+/// assert_eq!(terrain.positions.len(), 9);
+/// assert_eq!(terrain.positions[5], Point3::new(4.0, 2.0, 2.0));
+/// assert_eq!(terrain.uvs[5], Point2::new(1.0, 0.5));
+/// assert_eq!(terrain.indices.len(), 8);
+///
+/// // The skirt adds a vertex under each of the 8 edge samples.
+/// let skirted = terrain_mesh(&heights, Some(1.0));
+/// assert_eq!(skirted.positions.len(), 17);
+/// assert_eq!(skirted.indices.len(), 8 + 2 * 8);
+/// ```
+pub fn terrain_mesh<S: BaseFloat>(heights: &Grid2<S, S>, skirt: Option<S>) -> TerrainMesh<S> {
+    let (width, height) = (heights.width(), heights.height());
+    let mut positions = Vec::with_capacity(width * height);
+    let mut normals = Vec::with_capacity(width * height);
+    let mut uvs = Vec::with_capacity(width * height);
+    let coordinate = |i: usize, n: usize| -> S {
+        if n > 1 {
+            cast::<_, S>(i).unwrap() / cast(n - 1).unwrap()
+        } else {
+            S::zero()
+        }
+    };
+    for j in 0..height {
+        for i in 0..width {
+            let p = heights.position(i, j);
+            positions.push(Point3::new(p.x, heights.get(i, j), p.y));
+            normals.push(grid_normal(heights, i, j));
+            uvs.push(Point2::new(coordinate(i, width), coordinate(j, height)));
+        }
+    }
+    let mut indices = Vec::with_capacity(2 * width.saturating_sub(1) * height.saturating_sub(1));
+    for j in 1..height {
+        for i in 1..width {
+            let (p00, p10) = ((j - 1) * width + i - 1, (j - 1) * width + i);
+            let (p01, p11) = (p00 + width, p10 + width);
+            indices.push([p00, p01, p10]);
+            indices.push([p10, p01, p11]);
+        }
+    }
+    let mut tangents = vertex_tangents(&positions, &normals, &uvs, &indices);
+
+    if let Some(depth) = skirt {
+        let edges: HashSet<_> = indices
+            .iter()
+            .flat_map(|t| vec![(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .collect();
+        let mut below = vec![None; positions.len()];
+        let mut walls = Vec::new();
+        let mut edges: Vec<_> = edges
+            .iter()
+            .filter(|&&(a, b)| !edges.contains(&(b, a)))
+            .collect();
+        edges.sort();
+        for &(a, b) in edges {
+            let mut lower = |v: usize| {
+                *below[v].get_or_insert_with(|| {
+                    positions.push(positions[v] - Vector3::unit_y() * depth);
+                    normals.push(normals[v]);
+                    tangents.push(tangents[v]);
+                    uvs.push(uvs[v]);
+                    positions.len() - 1
+                })
+            };
+            let (a_below, b_below) = (lower(a), lower(b));
+            walls.push([a, a_below, b]);
+            walls.push([b, a_below, b_below]);
+        }
+        indices.extend(walls);
+    }
+    TerrainMesh {
+        positions,
+        normals,
+        tangents,
+        uvs,
+        indices,
+    }
+}
+
+/// Bake a normal map for the mesh of a coarse heightfield from the normals
+/// of a detailed one, returning the tangent space normals at each sample of
+/// the detailed heightfield.
+///
+/// The frames of tangent space are interpolated bilinearly from the
+/// vertices of `terrain_mesh(coarse, None)` at the position of each detailed
+/// sample, so both heightfields should cover the same area. The normals
+/// are unit vectors, with `z` along the normal of the coarse surface, and
+/// are usually stored as colors by mapping `[-1, 1]` to `[0, 1]`.
+///
+/// ```rust
+/// use cgmath::field::Grid2;
+/// use cgmath::heightfield::bake_normal_map;
+/// use cgmath::{InnerSpace, Point2, Vector3};
+///
+/// let origin = Point2::new(0.0, 0.0);
+/// let slope = Grid2::from_fn(origin, 1.0f64, 9, 9, |i, _| i as f64 * 0.5);
+/// let coarse = Grid2::from_fn(origin, 4.0f64, 3, 3, |i, _| i as f64 * 2.0);
+/// // The same surface, so the normal map is flat.
+/// let map = bake_normal_map(&slope, &coarse);
+/// assert!(map.values().iter().all(|n| (n - Vector3::unit_z()).magnitude() < 1e-12));
+/// ```
+pub fn bake_normal_map<S: BaseFloat>(
+    detail: &Grid2<S, S>,
+    coarse: &Grid2<S, S>,
+) -> Grid2<S, Vector3<S>> {
+    let terrain = terrain_mesh(coarse, None);
+    let (origin, spacing) = (coarse.origin, coarse.spacing);
+    let (width, height) = (coarse.width(), coarse.height());
+    let normals = Grid2::new(origin, spacing, width, height, terrain.normals);
+    let tangents = Grid2::new(origin, spacing, width, height, terrain.tangents);
+    Grid2::from_fn(
+        detail.origin,
+        detail.spacing,
+        detail.width(),
+        detail.height(),
+        |i, j| {
+            let p = detail.position(i, j);
+            let normal = normals.sample(p).normalize();
+            let tangent = tangents.sample(p);
+            // Make the interpolated tangent perpendicular to the normal again.
+            let t = tangent.truncate();
+            let t = (t - normal * normal.dot(t)).normalize();
+            to_tangent_space(
+                grid_normal(detail, i, j),
+                normal,
+                t.extend(tangent.w.signum()),
+            )
+        },
+    )
+}
+
+/// The result of casting a ray at a heightfield.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeightfieldHit<S> {
+    /// The parameter of the hit along the ray.
+    pub t: S,
+    /// The point where the ray hits the surface.
+    pub point: Point3<S>,
+    /// The unit normal of the surface at the hit point.
+    pub normal: Vector3<S>,
+}
+
+/// The first point where a ray hits the surface of a heightfield.
+///
+/// The `x` and `y` axes of the grid of heights are the `x` and `z` axes of
+/// the terrain, so that the sample at `(i, j)` is at the height `y` above
+/// the point `(x, z) = heights.position(i, j)`. The surface interpolates the
+/// heights bilinearly, as `Grid2::sample` does, and only spans the grid: the
+/// ray misses if it goes below the terrain outside of it.
+///
+/// The ray walks through the cells it crosses in order, with a digital
+/// differential analyser, and the hit is found exactly within the first
+/// cell where the ray goes below the surface, where the height along the
+/// ray is a quadratic. Rays starting below the surface hit it at once.
+/// Returns `None` if the grid has a single row or column of samples.
+///
+/// ```rust
+/// use cgmath::field::Grid2;
+/// use cgmath::heightfield::raycast;
+/// use cgmath::{InnerSpace, Point2, Point3, Ray3, Vector3};
+///
+/// // A slope rising towards positive x, with one sample per unit.
+/// let terrain = Grid2::from_fn(Point2::new(0.0, 0.0), 1.0f64, 10, 10, |i, _| i as f64);
+/// let ray = Ray3::new(Point3::new(0.0, 8.0, 5.0), Vector3::new(1.0, -1.0, 0.0));
+/// let hit = raycast(&terrain, &ray).unwrap();
+/// assert_eq!(hit.point, Point3::new(4.0, 4.0, 5.0));
+/// assert!((hit.normal - Vector3::new(-1.0, 1.0, 0.0) / 2.0f64.sqrt()).magnitude() < 1e-12);
+/// ```
+pub fn raycast<S: BaseFloat>(heights: &Grid2<S, S>, ray: &Ray3<S>) -> Option<HeightfieldHit<S>> {
+    let (width, height) = (heights.width(), heights.height());
+    if width < 2 || height < 2 {
+        return None;
+    }
+
+    // Work in units of the spacing from the first sample, where the cell
+    // `(i, j)` spans `[i, i + 1] x [j, j + 1]`.
+    let u0 = (ray.origin.x - heights.origin.x) / heights.spacing;
+    let v0 = (ray.origin.z - heights.origin.y) / heights.spacing;
+    let du = ray.direction.x / heights.spacing;
+    let dv = ray.direction.z / heights.spacing;
+    let (last_u, last_v): (S, S) = (cast(width - 1).unwrap(), cast(height - 1).unwrap());
+
+    // Clip the ray to the footprint of the grid.
+    let (mut enter, mut exit) = (S::zero(), S::infinity());
+    for &(origin, direction, last) in &[(u0, du, last_u), (v0, dv, last_v)] {
+        if direction == S::zero() {
+            if origin < S::zero() || origin > last {
+                return None;
+            }
+        } else {
+            let (a, b) = (-origin / direction, (last - origin) / direction);
+            enter = enter.max(a.min(b));
+            exit = exit.min(a.max(b));
+        }
+    }
+    if enter > exit {
+        return None;
+    }
+
+    let cell = |x: S, last: usize| -> isize {
+        cast::<_, isize>(x.floor())
+            .unwrap()
+            .max(0)
+            .min(last as isize - 1)
+    };
+    let (mut i, mut j) = (
+        cell(u0 + du * enter, width - 1),
+        cell(v0 + dv * enter, height - 1),
+    );
+    let step = |direction: S| -> isize {
+        if direction > S::zero() {
+            1
+        } else if direction < S::zero() {
+            -1
+        } else {
+            0
+        }
+    };
+    let (step_i, step_j) = (step(du), step(dv));
+    let next = |index: isize, step: isize, origin: S, direction: S| -> S {
+        if step == 0 {
+            return S::infinity();
+        }
+        let boundary: S = cast(if step > 0 { index + 1 } else { index }).unwrap();
+        (boundary - origin) / direction
+    };
+    let (mut next_u, mut next_v) = (next(i, step_i, u0, du), next(j, step_j, v0, dv));
+    let (delta_u, delta_v) = (S::one() / du.abs(), S::one() / dv.abs());
+
+    let mut start = enter;
+    loop {
+        let end = next_u.min(next_v).min(exit);
+        let (ii, jj) = (i as usize, j as usize);
+        let h00 = heights.get(ii, jj);
+        let b = heights.get(ii + 1, jj) - h00;
+        let c = heights.get(ii, jj + 1) - h00;
+        let d = heights.get(ii + 1, jj + 1) - heights.get(ii + 1, jj) - c;
+
+        // The height of the ray above the surface from `start` is
+        // `qa s² + qb s + qc`, where `s` is the parameter from `start`.
+        let fu = u0 + du * start - cast(i).unwrap();
+        let fv = v0 + dv * start - cast(j).unwrap();
+        let y = ray.origin.y + ray.direction.y * start;
+        let qa = -d * du * dv;
+        let qb = ray.direction.y - (b * du + c * dv + d * (fu * dv + fv * du));
+        let qc = y - (h00 + b * fu + c * fv + d * fu * fv);
+        if let Some(s) = first_root(qa, qb, qc, end - start) {
+            let t = start + s;
+            let (fu, fv) = (fu + du * s, fv + dv * s);
+            let slope_x = (b + d * fv) / heights.spacing;
+            let slope_z = (c + d * fu) / heights.spacing;
+            return Some(HeightfieldHit {
+                t,
+                point: ray.point_at(t),
+                normal: Vector3::new(-slope_x, S::one(), -slope_z).normalize(),
+            });
+        }
+
+        if end >= exit {
+            return None;
+        }
+        if next_u < next_v {
+            i += step_i;
+            start = next_u;
+            next_u += delta_u;
+        } else {
+            j += step_j;
+            start = next_v;
+            next_v += delta_v;
+        }
+        if i < 0 || j < 0 || i as usize >= width - 1 || j as usize >= height - 1 {
+            return None;
+        }
+    }
+}
+
+/// The smallest `s` in `[0, length]` where `a s² + b s + c` is zero, if that
+/// polynomial starts positive, or `0` if it does not.
+fn first_root<S: BaseFloat>(a: S, b: S, c: S, length: S) -> Option<S> {
+    if c <= S::zero() {
+        return Some(S::zero());
+    }
+    let in_range = |s: S| s >= S::zero() && s <= length;
+    if a == S::zero() {
+        return if b < S::zero() {
+            Some(-c / b).filter(|&s| in_range(s))
+        } else {
+            None
+        };
+    }
+    let four: S = cast(4).unwrap();
+    let discriminant = b * b - four * a * c;
+    if discriminant < S::zero() {
+        return None;
+    }
+    // Both roots, without cancellation between b and the square root.
+    let two = S::one() + S::one();
+    let q = -(b + b.signum() * discriminant.sqrt()) / two;
+    let (r0, r1) = (q / a, c / q);
+    let (r0, r1) = if r0 < r1 { (r0, r1) } else { (r1, r0) };
+    if in_range(r0) {
+        Some(r0)
+    } else if in_range(r1) {
+        Some(r1)
+    } else {
+        None
+    }
+}
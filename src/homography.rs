@@ -0,0 +1,271 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::*;
+
+use num_traits::{cast, Float};
+
+use structure::*;
+
+use matrix::Matrix3;
+use num::BaseFloat;
+use point::Point2;
+use vector::{Vector2, Vector3};
+
+/// A projective transform of the plane, mapping straight lines to straight
+/// lines but, unlike an affine transform, not parallel lines to parallel
+/// lines.
+///
+/// The transform is stored as a matrix acting on homogeneous coordinates,
+/// and applying it to a point divides by the resulting `w` coordinate. The
+/// matrix is only defined up to a non zero scale factor, so two homographies
+/// with proportional matrices map points in the same way.
+///
+/// ```rust
+/// use cgmath::{Homography, Point2};
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// // The corners of a photographed sheet of paper, and where they should go.
+/// let photo = [
+///     Point2::new(12.0, 8.0),
+///     Point2::new(95.0, 20.0),
+///     Point2::new(90.0, 110.0),
+///     Point2::new(5.0, 90.0),
+/// ];
+/// let sheet = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(210.0, 0.0),
+///     Point2::new(210.0, 297.0),
+///     Point2::new(0.0, 297.0),
+/// ];
+/// let rectify = Homography::from_point_pairs(&photo, &sheet).unwrap();
+/// assert_relative_eq!(rectify.transform_point(photo[2]), sheet[2], epsilon = 1e-9);
+///
+/// let inverse = rectify.inverse().unwrap();
+/// assert_relative_eq!(inverse.transform_point(sheet[3]), photo[3], epsilon = 1e-9);
+/// # }
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Homography<S> {
+    /// The matrix acting on homogeneous coordinates `(x, y, 1)`.
+    pub m: Matrix3<S>,
+}
+
+impl<S: BaseFloat> Homography<S> {
+    /// Construct a homography from its matrix.
+    #[inline]
+    pub fn new(m: Matrix3<S>) -> Homography<S> {
+        Homography { m }
+    }
+
+    /// The identity transform.
+    #[inline]
+    pub fn identity() -> Homography<S> {
+        Homography::new(Matrix3::identity())
+    }
+
+    /// Estimate the homography mapping each of the `src` points to the `dst`
+    /// point at the same index, with the normalized direct linear transform.
+    ///
+    /// Four pairs determine the homography exactly. With more pairs, the
+    /// algebraic error of the fit is minimized in least squares. The
+    /// resulting matrix is scaled so that its bottom right element is one.
+    ///
+    /// Returns `None` if the slices differ in length, hold fewer than four
+    /// pairs, or if the points are degenerate, such as when three of four
+    /// points are collinear.
+    pub fn from_point_pairs(src: &[Point2<S>], dst: &[Point2<S>]) -> Option<Homography<S>> {
+        if src.len() != dst.len() || src.len() < 4 {
+            return None;
+        }
+        // Moving both point sets around the origin at a unit scale keeps the
+        // linear system well conditioned whatever the input coordinates.
+        let src_norm = normalization(src)?;
+        let dst_norm = normalization(dst)?;
+
+        // Fixing the bottom right element to one, each pair gives two linear
+        // equations in the eight remaining unknowns, solved through the
+        // normal equations.
+        let mut ata = [[S::zero(); 8]; 8];
+        let mut atb = [S::zero(); 8];
+        for (&p, &q) in src.iter().zip(dst) {
+            let p = transform_affine(&src_norm, p);
+            let q = transform_affine(&dst_norm, q);
+            let rows = [
+                (
+                    [p.x, p.y, S::one(), S::zero(), S::zero(), S::zero(), -p.x * q.x, -p.y * q.x],
+                    q.x,
+                ),
+                (
+                    [S::zero(), S::zero(), S::zero(), p.x, p.y, S::one(), -p.x * q.y, -p.y * q.y],
+                    q.y,
+                ),
+            ];
+            for &(row, rhs) in &rows {
+                for i in 0..8 {
+                    for j in 0..8 {
+                        ata[i][j] += row[i] * row[j];
+                    }
+                    atb[i] += row[i] * rhs;
+                }
+            }
+        }
+        let h = solve8(ata, atb)?;
+
+        let normalized = Matrix3::new(
+            h[0], h[3], h[6],
+            h[1], h[4], h[7],
+            h[2], h[5], S::one(),
+        );
+        // Collinear points that do not stay collinear admit no projective
+        // map, and the system then solves to a singular matrix.
+        let entries: &[S; 9] = normalized.as_ref();
+        let size = entries.iter().fold(S::zero(), |acc, &x| acc.max(x.abs()));
+        let epsilon = size * size * size * Float::sqrt(S::epsilon());
+        if implicit_ulps_eq!(normalized.determinant(), S::zero(), epsilon = epsilon) {
+            return None;
+        }
+        let m = dst_norm.invert()? * normalized * src_norm;
+        if m.z.z == S::zero() {
+            Some(Homography::new(m))
+        } else {
+            Some(Homography::new(m / m.z.z))
+        }
+    }
+
+    /// Apply the transform to a point, dividing by the homogeneous `w`
+    /// coordinate.
+    ///
+    /// Points mapped to infinity, on the line sent to the line at infinity,
+    /// have infinite or NaN coordinates.
+    #[inline]
+    pub fn transform_point(&self, point: Point2<S>) -> Point2<S> {
+        let v = self.m * Vector3::new(point.x, point.y, S::one());
+        Point2::new(v.x / v.z, v.y / v.z)
+    }
+
+    /// The inverse transform, or `None` if the matrix is singular.
+    #[inline]
+    pub fn inverse(&self) -> Option<Homography<S>> {
+        self.m.invert().map(Homography::new)
+    }
+
+    /// Apply `other`, then this transform.
+    #[inline]
+    pub fn concat(&self, other: &Homography<S>) -> Homography<S> {
+        Homography::new(self.m * other.m)
+    }
+}
+
+impl<S: BaseFloat> Default for Homography<S> {
+    /// The identity transform.
+    #[inline]
+    fn default() -> Homography<S> {
+        Homography::identity()
+    }
+}
+
+impl<S: BaseFloat> Mul for Homography<S> {
+    type Output = Homography<S>;
+
+    /// Apply `rhs`, then this transform.
+    #[inline]
+    fn mul(self, rhs: Homography<S>) -> Homography<S> {
+        self.concat(&rhs)
+    }
+}
+
+impl<S: BaseFloat> From<Matrix3<S>> for Homography<S> {
+    #[inline]
+    fn from(m: Matrix3<S>) -> Homography<S> {
+        Homography::new(m)
+    }
+}
+
+impl<S: BaseFloat> From<Homography<S>> for Matrix3<S> {
+    #[inline]
+    fn from(h: Homography<S>) -> Matrix3<S> {
+        h.m
+    }
+}
+
+/// The similarity moving the centroid of `points` to the origin and scaling
+/// their mean distance to it to `sqrt(2)`, or `None` if all the points are
+/// the same.
+fn normalization<S: BaseFloat>(points: &[Point2<S>]) -> Option<Matrix3<S>> {
+    let n: S = cast(points.len()).unwrap();
+    let centroid = points
+        .iter()
+        .fold(Vector2::zero(), |acc, p| acc + p.to_vec())
+        / n;
+    let mean_distance = points
+        .iter()
+        .fold(S::zero(), |acc, p| acc + (p.to_vec() - centroid).magnitude())
+        / n;
+    if mean_distance == S::zero() {
+        return None;
+    }
+    let s = cast::<_, S>(2).unwrap().sqrt() / mean_distance;
+    Some(Matrix3::new(
+        s, S::zero(), S::zero(),
+        S::zero(), s, S::zero(),
+        -s * centroid.x, -s * centroid.y, S::one(),
+    ))
+}
+
+#[inline]
+fn transform_affine<S: BaseFloat>(m: &Matrix3<S>, p: Point2<S>) -> Point2<S> {
+    let v = m * Vector3::new(p.x, p.y, S::one());
+    Point2::new(v.x, v.y)
+}
+
+/// Solve the linear system `a * x = b` by Gaussian elimination with partial
+/// pivoting, or return `None` if `a` is singular.
+fn solve8<S: BaseFloat>(mut a: [[S; 8]; 8], mut b: [S; 8]) -> Option<[S; 8]> {
+    // The normal equations of a degenerate configuration are singular up to
+    // rounding, which leaves pivots around the square root of the epsilon
+    // relative to the largest one.
+    let scale = a
+        .iter()
+        .flat_map(|row| row.iter())
+        .fold(S::zero(), |acc, &x| acc.max(x.abs()));
+    let threshold = scale * Float::sqrt(S::epsilon());
+    for col in 0..8 {
+        let pivot = (col..8)
+            .max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).unwrap())
+            .unwrap();
+        if implicit_ulps_eq!(a[pivot][col], S::zero(), epsilon = threshold) {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (pivot_row, pivot_rhs) = (a[col], b[col]);
+        for row in col + 1..8 {
+            let f = a[row][col] / pivot_row[col];
+            for (x, &p) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= f * p;
+            }
+            b[row] -= f * pivot_rhs;
+        }
+    }
+    let mut x = [S::zero(); 8];
+    for row in (0..8).rev() {
+        let sum = (row + 1..8).fold(b[row], |acc, k| acc - a[row][k] * x[k]);
+        x[row] = sum / a[row][row];
+    }
+    Some(x)
+}
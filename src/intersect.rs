@@ -0,0 +1,190 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Intersection tests between geometric primitives.
+
+use aabb::{Aabb2, Aabb3};
+use ellipse::{Ellipse2, Ellipsoid3};
+use frustum::Frustum;
+use num::BaseFloat;
+use plane::Plane;
+use point::{Point2, Point3};
+use ray::{ray_ellipse, ray_ellipsoid, ray_plane, ray_sphere, ray_triangle, Ray2, Ray3};
+use sphere::Sphere;
+use triangle::Triangle3;
+
+/// An intersection test between the primitives of a tuple, such as a ray
+/// and a sphere, returning the kind of result that makes sense for them.
+///
+/// Ray tests return the first point of the ray on the surface of the other
+/// primitive, if any. Rays starting inside a volume hit its boundary on the
+/// way out. Frustum tests return how the volume relates to the frustum,
+/// which is what view frustum culling needs.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{Point3, Ray3, Sphere, Vector3};
+///
+/// // Picking a sphere with a ray from the camera.
+/// let ray = Ray3::new(Point3::new(0.0, 0.0, 5.0), Vector3::new(0.0, 0.0, -1.0f64));
+/// let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 1.0);
+/// assert_eq!((ray, sphere).intersection(), Some(Point3::new(0.0, 0.0, 1.0)));
+/// ```
+pub trait Intersect<Result> {
+    /// Compute the intersection of the primitives.
+    fn intersection(&self) -> Result;
+}
+
+/// How a volume relates to a frustum.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Relation {
+    /// The volume is entirely inside the frustum.
+    In,
+    /// The volume is entirely outside the frustum.
+    Out,
+    /// The volume crosses the boundary of the frustum.
+    ///
+    /// Like most culling tests, this is conservative: volumes close to the
+    /// corners of the frustum may be reported as crossing it while being
+    /// outside.
+    Cross,
+}
+
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Ray3<S>, Plane<S>) {
+    fn intersection(&self) -> Option<Point3<S>> {
+        let (ref ray, ref plane) = *self;
+        ray_plane(ray, plane).map(|t| ray.point_at(t))
+    }
+}
+
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Ray3<S>, Sphere<S>) {
+    fn intersection(&self) -> Option<Point3<S>> {
+        let (ref ray, ref sphere) = *self;
+        ray_sphere(ray, sphere).map(|t| ray.point_at(t))
+    }
+}
+
+impl<S: BaseFloat> Intersect<Option<Point2<S>>> for (Ray2<S>, Ellipse2<S>) {
+    fn intersection(&self) -> Option<Point2<S>> {
+        let (ref ray, ref ellipse) = *self;
+        ray_ellipse(ray, ellipse).map(|t| ray.point_at(t))
+    }
+}
+
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Ray3<S>, Ellipsoid3<S>) {
+    fn intersection(&self) -> Option<Point3<S>> {
+        let (ref ray, ref ellipsoid) = *self;
+        ray_ellipsoid(ray, ellipsoid).map(|t| ray.point_at(t))
+    }
+}
+
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Ray3<S>, Triangle3<S>) {
+    fn intersection(&self) -> Option<Point3<S>> {
+        let (ref ray, ref triangle) = *self;
+        ray_triangle(ray, triangle).map(|(t, _)| ray.point_at(t))
+    }
+}
+
+/// Clip the parameter range `[near, far]` of a ray to the slab between `min`
+/// and `max` along one axis.
+#[inline]
+fn clip_slab<S: BaseFloat>(origin: S, direction: S, min: S, max: S, near: &mut S, far: &mut S) {
+    if direction == S::zero() {
+        if origin < min || origin > max {
+            *near = S::infinity();
+        }
+        return;
+    }
+    let t1 = (min - origin) / direction;
+    let t2 = (max - origin) / direction;
+    *near = near.max(t1.min(t2));
+    *far = far.min(t1.max(t2));
+}
+
+/// The parameter of the first point of the ray inside a box, given the
+/// parameter range left after clipping to its slabs.
+#[inline]
+fn first_hit<S: BaseFloat>(near: S, far: S) -> Option<S> {
+    if near > far || far < S::zero() {
+        None
+    } else if near >= S::zero() {
+        Some(near)
+    } else {
+        // The ray starts inside the box.
+        Some(far)
+    }
+}
+
+impl<S: BaseFloat> Intersect<Option<Point2<S>>> for (Ray2<S>, Aabb2<S>) {
+    fn intersection(&self) -> Option<Point2<S>> {
+        let (ref ray, ref aabb) = *self;
+        let (mut near, mut far) = (S::neg_infinity(), S::infinity());
+        let (o, d) = (ray.origin, ray.direction);
+        clip_slab(o.x, d.x, aabb.min.x, aabb.max.x, &mut near, &mut far);
+        clip_slab(o.y, d.y, aabb.min.y, aabb.max.y, &mut near, &mut far);
+        first_hit(near, far).map(|t| ray.point_at(t))
+    }
+}
+
+impl<S: BaseFloat> Intersect<Option<Point3<S>>> for (Ray3<S>, Aabb3<S>) {
+    fn intersection(&self) -> Option<Point3<S>> {
+        let (ref ray, ref aabb) = *self;
+        let (mut near, mut far) = (S::neg_infinity(), S::infinity());
+        let (o, d) = (ray.origin, ray.direction);
+        clip_slab(o.x, d.x, aabb.min.x, aabb.max.x, &mut near, &mut far);
+        clip_slab(o.y, d.y, aabb.min.y, aabb.max.y, &mut near, &mut far);
+        clip_slab(o.z, d.z, aabb.min.z, aabb.max.z, &mut near, &mut far);
+        first_hit(near, far).map(|t| ray.point_at(t))
+    }
+}
+
+/// Classify a volume against the planes of a frustum, given its center and
+/// its radius along the normal of each plane.
+fn relate<S: BaseFloat, F>(frustum: &Frustum<S>, center: Point3<S>, radius: F) -> Relation
+where
+    F: Fn(&Plane<S>) -> S,
+{
+    let mut relation = Relation::In;
+    for plane in frustum.to_planes().iter() {
+        let distance = plane.distance(center);
+        let r = radius(plane);
+        if distance < -r {
+            return Relation::Out;
+        }
+        if distance < r {
+            relation = Relation::Cross;
+        }
+    }
+    relation
+}
+
+impl<S: BaseFloat> Intersect<Relation> for (Frustum<S>, Sphere<S>) {
+    fn intersection(&self) -> Relation {
+        let (ref frustum, ref sphere) = *self;
+        relate(frustum, sphere.center, |_| sphere.radius)
+    }
+}
+
+impl<S: BaseFloat> Intersect<Relation> for (Frustum<S>, Aabb3<S>) {
+    fn intersection(&self) -> Relation {
+        let (ref frustum, ref aabb) = *self;
+        let half = aabb.dim() / (S::one() + S::one());
+        relate(frustum, aabb.center(), |plane| {
+            let n = plane.normal;
+            n.x.abs() * half.x + n.y.abs() * half.y + n.z.abs() * half.z
+        })
+    }
+}
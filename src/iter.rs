@@ -0,0 +1,216 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Iterator adapters applying transformations and rotations lazily.
+
+use std::borrow::Borrow;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use structure::*;
+
+use num::BaseFloat;
+use rotation::Rotation;
+use transform::Transform;
+
+/// Extension methods for iterators over points and vectors.
+///
+/// The adapters borrow the transformation and apply it to each item as it is
+/// produced, so a pipeline of geometry operations can be built without any
+/// intermediate allocations. The items can be yielded either by value or by
+/// reference, so both `points.iter()` and `points.into_iter()` work.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{Deg, Matrix4, Point3, Quaternion, Vector3};
+///
+/// let points = [Point3::new(1.0, 0.0, 0.0), Point3::new(0.0, 1.0, 0.0)];
+/// let matrix = Matrix4::from_translation(Vector3::new(0.0, 0.0, 1.0));
+/// let moved: Vec<Point3<f64>> = points.iter().transformed(&matrix).collect();
+/// assert_eq!(moved[0], Point3::new(1.0, 0.0, 1.0));
+///
+/// let vectors = [Vector3::unit_x(), Vector3::unit_y()];
+/// let rotation = Quaternion::from_angle_z(Deg(90.0));
+/// let total: Vector3<f64> = vectors.iter().rotated(&rotation).sum();
+/// ```
+pub trait TransformIteratorExt: Iterator + Sized {
+    /// Apply `Transform::transform_point` to each point of the iterator.
+    #[inline]
+    fn transformed<'a, P, T>(self, transform: &'a T) -> Transformed<'a, Self, P, T>
+    where
+        P: EuclideanSpace,
+        T: Transform<P>,
+        Self::Item: Borrow<P>,
+    {
+        Transformed {
+            iter: self,
+            transform,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Apply `Transform::transform_vector` to each vector of the iterator.
+    #[inline]
+    fn transformed_vectors<'a, P, T>(self, transform: &'a T) -> TransformedVectors<'a, Self, P, T>
+    where
+        P: EuclideanSpace,
+        T: Transform<P>,
+        Self::Item: Borrow<P::Diff>,
+    {
+        TransformedVectors {
+            iter: self,
+            transform,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Apply `Rotation::rotate_vector` to each vector of the iterator.
+    #[inline]
+    fn rotated<'a, P, R>(self, rotation: &'a R) -> Rotated<'a, Self, P, R>
+    where
+        P: EuclideanSpace,
+        P::Scalar: BaseFloat,
+        R: Rotation<P>,
+        Self::Item: Borrow<P::Diff>,
+    {
+        Rotated {
+            iter: self,
+            rotation,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<I: Iterator> TransformIteratorExt for I {}
+
+macro_rules! impl_adapter {
+    ($Adapter:ident, $field:ident, $Item:ty, |$t:ident, $x:ident| $body:expr, $Bound:ident $(, $extra:path)*) => {
+        impl<'a, I, P, T> Iterator for $Adapter<'a, I, P, T>
+        where
+            I: Iterator,
+            I::Item: Borrow<$Item>,
+            P: EuclideanSpace,
+            $( $extra: BaseFloat, )*
+            T: $Bound<P>,
+        {
+            type Item = $Item;
+
+            #[inline]
+            fn next(&mut self) -> Option<$Item> {
+                let $t = self.$field;
+                self.iter.next().map(|item| {
+                    let $x = *item.borrow();
+                    $body
+                })
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<'a, I, P, T> DoubleEndedIterator for $Adapter<'a, I, P, T>
+        where
+            I: DoubleEndedIterator,
+            I::Item: Borrow<$Item>,
+            P: EuclideanSpace,
+            $( $extra: BaseFloat, )*
+            T: $Bound<P>,
+        {
+            #[inline]
+            fn next_back(&mut self) -> Option<$Item> {
+                let $t = self.$field;
+                self.iter.next_back().map(|item| {
+                    let $x = *item.borrow();
+                    $body
+                })
+            }
+        }
+
+        impl<'a, I, P, T> ExactSizeIterator for $Adapter<'a, I, P, T>
+        where
+            I: ExactSizeIterator,
+            I::Item: Borrow<$Item>,
+            P: EuclideanSpace,
+            $( $extra: BaseFloat, )*
+            T: $Bound<P>,
+        {
+        }
+
+        impl<'a, I, P, T> FusedIterator for $Adapter<'a, I, P, T>
+        where
+            I: FusedIterator,
+            I::Item: Borrow<$Item>,
+            P: EuclideanSpace,
+            $( $extra: BaseFloat, )*
+            T: $Bound<P>,
+        {
+        }
+    };
+}
+
+/// An iterator that transforms the points of another iterator.
+///
+/// This is created by `TransformIteratorExt::transformed`.
+#[derive(Clone, Debug)]
+pub struct Transformed<'a, I, P, T: 'a> {
+    iter: I,
+    transform: &'a T,
+    phantom: PhantomData<P>,
+}
+
+/// An iterator that transforms the vectors of another iterator.
+///
+/// This is created by `TransformIteratorExt::transformed_vectors`.
+#[derive(Clone, Debug)]
+pub struct TransformedVectors<'a, I, P, T: 'a> {
+    iter: I,
+    transform: &'a T,
+    phantom: PhantomData<P>,
+}
+
+/// An iterator that rotates the vectors of another iterator.
+///
+/// This is created by `TransformIteratorExt::rotated`.
+#[derive(Clone, Debug)]
+pub struct Rotated<'a, I, P, R: 'a> {
+    iter: I,
+    rotation: &'a R,
+    phantom: PhantomData<P>,
+}
+
+impl_adapter!(
+    Transformed,
+    transform,
+    P,
+    |t, p| t.transform_point(p),
+    Transform
+);
+impl_adapter!(
+    TransformedVectors,
+    transform,
+    P::Diff,
+    |t, v| t.transform_vector(v),
+    Transform
+);
+impl_adapter!(
+    Rotated,
+    rotation,
+    P::Diff,
+    |r, v| r.rotate_vector(v),
+    Rotation,
+    P::Scalar
+);
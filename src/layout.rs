@@ -0,0 +1,537 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Layout of two-dimensional quads, such as sprites, text and the widgets
+//! of user interfaces, the scaling of rectangles into each other, rounded
+//! rectangles, and the tracking of the damaged regions of a surface.
+//!
+//! The `y` axis points up, so the bottom-left corner of a rectangle is its
+//! `min` corner. The transforms are homogeneous `Matrix3`s, which apply to
+//! points with `Transform::transform_point`.
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::Aabb2;
+use angle::Rad;
+use matrix::Matrix3;
+use num::{BaseFloat, BaseNum};
+use point::Point2;
+use transform::Transform;
+use vector::Vector2;
+
+/// A point of a rectangle to align it by: one of its corners, the middle
+/// of one of its sides, or its center.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Anchor {
+    /// The top-left corner.
+    TopLeft,
+    /// The middle of the top side.
+    Top,
+    /// The top-right corner.
+    TopRight,
+    /// The middle of the left side.
+    Left,
+    /// The center.
+    Center,
+    /// The middle of the right side.
+    Right,
+    /// The bottom-left corner.
+    BottomLeft,
+    /// The middle of the bottom side.
+    Bottom,
+    /// The bottom-right corner.
+    BottomRight,
+}
+
+impl Anchor {
+    /// The position of the anchor in a rectangle, as a fraction of its size
+    /// from its bottom-left corner, from `(0, 0)` to `(1, 1)`.
+    pub fn fraction<S: BaseFloat>(self) -> Vector2<S> {
+        let (zero, one) = (S::zero(), S::one());
+        let half = one / (one + one);
+        let (x, y) = match self {
+            Anchor::TopLeft => (zero, one),
+            Anchor::Top => (half, one),
+            Anchor::TopRight => (one, one),
+            Anchor::Left => (zero, half),
+            Anchor::Center => (half, half),
+            Anchor::Right => (one, half),
+            Anchor::BottomLeft => (zero, zero),
+            Anchor::Bottom => (half, zero),
+            Anchor::BottomRight => (one, zero),
+        };
+        Vector2::new(x, y)
+    }
+}
+
+/// A rectangle of a given size, aligned by an anchor on a position and
+/// rotated about a pivot.
+///
+/// Before the rotation, the `anchor` of the rectangle is at `position`.
+/// The rectangle then turns counterclockwise by `rotation` about its
+/// `pivot`, which is given as a fraction of its size from its bottom-left
+/// corner, like `Anchor::fraction`, and may lie outside of it.
+///
+/// ```rust
+/// use cgmath::layout::{Anchor, AnchoredQuad};
+/// use cgmath::{Deg, Point2, Vector2};
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// // A label hanging below a point, turned a quarter turn about its center.
+/// let size = Vector2::new(4.0, 2.0f64);
+/// let mut label = AnchoredQuad::new(Point2::new(10.0, 10.0), size, Anchor::Top);
+/// assert_eq!(label.corners()[0], Point2::new(8.0, 8.0));
+///
+/// label.pivot = Anchor::Center.fraction();
+/// label.rotation = Deg(90.0).into();
+/// let corners = label.corners();
+/// assert_relative_eq!(corners[0], Point2::new(11.0, 7.0), epsilon = 1e-12);
+/// assert_relative_eq!(corners[2], Point2::new(9.0, 11.0), epsilon = 1e-12);
+/// # }
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnchoredQuad<S> {
+    /// The position of the anchor, before the rotation.
+    pub position: Point2<S>,
+    /// The width and height of the rectangle.
+    pub size: Vector2<S>,
+    /// The point of the rectangle which is at `position`.
+    pub anchor: Anchor,
+    /// The point the rectangle turns about, as a fraction of its size.
+    pub pivot: Vector2<S>,
+    /// The counterclockwise rotation of the rectangle about its pivot.
+    pub rotation: Rad<S>,
+}
+
+impl<S: BaseFloat> AnchoredQuad<S> {
+    /// Construct an unrotated rectangle, whose pivot is its anchor.
+    pub fn new(position: Point2<S>, size: Vector2<S>, anchor: Anchor) -> AnchoredQuad<S> {
+        AnchoredQuad {
+            position,
+            size,
+            anchor,
+            pivot: anchor.fraction(),
+            rotation: Rad(S::zero()),
+        }
+    }
+
+    /// The point at `fraction` of the size of the rectangle from its
+    /// bottom-left corner, before the rotation.
+    fn unrotated(&self, fraction: Vector2<S>) -> Point2<S> {
+        self.position + (fraction - self.anchor.fraction()).mul_element_wise(self.size)
+    }
+
+    /// The transform taking points given relative to the bottom-left
+    /// corner of the rectangle, in the same units as its size, to their
+    /// positions, such as the vertices of the glyphs of a label.
+    pub fn to_matrix3(&self) -> Matrix3<S> {
+        let pivot = self.unrotated(self.pivot).to_vec();
+        let corner = self.unrotated(Vector2::zero()).to_vec();
+        Matrix3::from_translation(pivot)
+            * Matrix3::from_angle_z(self.rotation)
+            * Matrix3::from_translation(corner - pivot)
+    }
+
+    /// The four corners of the rectangle, counterclockwise from its
+    /// bottom-left corner, which is before the rotation.
+    pub fn corners(&self) -> [Point2<S>; 4] {
+        let pivot = self.unrotated(self.pivot);
+        let (s, c) = Rad::sin_cos(self.rotation);
+        let rotate = |p: Point2<S>| {
+            let v = p - pivot;
+            pivot + Vector2::new(c * v.x - s * v.y, s * v.x + c * v.y)
+        };
+        let (zero, one) = (S::zero(), S::one());
+        [
+            rotate(self.unrotated(Vector2::new(zero, zero))),
+            rotate(self.unrotated(Vector2::new(one, zero))),
+            rotate(self.unrotated(Vector2::new(one, one))),
+            rotate(self.unrotated(Vector2::new(zero, one))),
+        ]
+    }
+}
+
+/// How `fit_scale_offset` scales a rectangle into another one.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Fit {
+    /// Keep the aspect ratio and fit inside the target, leaving bars on two
+    /// of its sides, as letterboxing does.
+    Contain,
+    /// Keep the aspect ratio and cover the target, cropping two of the
+    /// sides of the rectangle.
+    Cover,
+    /// Fill the target exactly, ignoring the aspect ratio.
+    Stretch,
+}
+
+/// The scale and offset mapping the rectangle `content` into `target`,
+/// with `p * scale + offset`.
+///
+/// When the aspect ratio is kept, the space left over or cropped along one
+/// axis is distributed as set by `alignment`, so that the anchor of the
+/// content lands on the anchor of the target: `Anchor::Center` centers the
+/// content.
+///
+/// ```rust
+/// use cgmath::layout::{fit_rect, Anchor, Fit};
+/// use cgmath::{Aabb2, Point2};
+///
+/// // A 4:3 image shown on a 16:9 screen, with bars on the sides.
+/// let image = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(640.0, 480.0f64));
+/// let screen = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(1920.0, 1080.0));
+/// let viewport = fit_rect(image, screen, Fit::Contain, Anchor::Center);
+/// assert_eq!(viewport, Aabb2::new(Point2::new(240.0, 0.0), Point2::new(1680.0, 1080.0)));
+/// ```
+pub fn fit_scale_offset<S: BaseFloat>(
+    content: Aabb2<S>,
+    target: Aabb2<S>,
+    fit: Fit,
+    alignment: Anchor,
+) -> (Vector2<S>, Vector2<S>) {
+    let (size, space) = (content.dim(), target.dim());
+    let ratio = Vector2::new(space.x / size.x, space.y / size.y);
+    let scale = match fit {
+        Fit::Contain => Vector2::from_value(ratio.x.min(ratio.y)),
+        Fit::Cover => Vector2::from_value(ratio.x.max(ratio.y)),
+        Fit::Stretch => ratio,
+    };
+    let anchor = alignment.fraction();
+    let from = content.min.to_vec() + anchor.mul_element_wise(size);
+    let to = target.min.to_vec() + anchor.mul_element_wise(space);
+    (scale, to - from.mul_element_wise(scale))
+}
+
+/// The transform mapping the rectangle `content` into `target`, which is
+/// that of `fit_scale_offset` as a matrix.
+pub fn fit_transform<S: BaseFloat>(
+    content: Aabb2<S>,
+    target: Aabb2<S>,
+    fit: Fit,
+    alignment: Anchor,
+) -> Matrix3<S> {
+    let (scale, offset) = fit_scale_offset(content, target, fit, alignment);
+    Matrix3::from_translation(offset) * Matrix3::from_nonuniform_scale(scale.x, scale.y)
+}
+
+/// The rectangle covered by `content` once mapped into `target`, such as
+/// the viewport of a letterboxed image.
+pub fn fit_rect<S: BaseFloat>(
+    content: Aabb2<S>,
+    target: Aabb2<S>,
+    fit: Fit,
+    alignment: Anchor,
+) -> Aabb2<S> {
+    let (scale, offset) = fit_scale_offset(content, target, fit, alignment);
+    let map = |p: Point2<S>| Point2::from_vec(p.to_vec().mul_element_wise(scale) + offset);
+    Aabb2::new(map(content.min), map(content.max))
+}
+
+/// Split a rectangle into the nine slices of a nine-slice sprite, whose
+/// corners keep their size when it is resized, while its sides stretch
+/// along their length and its center in both directions.
+///
+/// `border_min` holds the widths of the left and bottom borders, and
+/// `border_max` those of the right and top ones. They are scaled down
+/// together along an axis where they do not fit in the rectangle. The
+/// slices are returned row by row from the bottom-left one, so slicing
+/// the source image and the destination rectangle with the same borders
+/// gives matching slices.
+///
+/// ```rust
+/// use cgmath::layout::nine_slice;
+/// use cgmath::{Aabb2, Point2, Vector2};
+///
+/// let button = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(100.0, 40.0f64));
+/// let slices = nine_slice(button, Vector2::new(8.0, 8.0), Vector2::new(8.0, 12.0));
+/// assert_eq!(slices[0], Aabb2::new(Point2::new(0.0, 0.0), Point2::new(8.0, 8.0)));
+/// assert_eq!(slices[4], Aabb2::new(Point2::new(8.0, 8.0), Point2::new(92.0, 28.0)));
+/// ```
+pub fn nine_slice<S: BaseFloat>(
+    rect: Aabb2<S>,
+    border_min: Vector2<S>,
+    border_max: Vector2<S>,
+) -> [Aabb2<S>; 9] {
+    let size = rect.dim();
+    let cuts = |min: S, max: S, lo: S, hi: S, length: S| {
+        let total = lo + hi;
+        let shrink = if total > length && total > S::zero() {
+            length / total
+        } else {
+            S::one()
+        };
+        [min, min + lo * shrink, max - hi * shrink, max]
+    };
+    let xs = cuts(rect.min.x, rect.max.x, border_min.x, border_max.x, size.x);
+    let ys = cuts(rect.min.y, rect.max.y, border_min.y, border_max.y, size.y);
+    let mut slices = [rect; 9];
+    for (i, slice) in slices.iter_mut().enumerate() {
+        let (column, row) = (i % 3, i / 3);
+        *slice = Aabb2 {
+            min: Point2::new(xs[column], ys[row]),
+            max: Point2::new(xs[column + 1], ys[row + 1]),
+        };
+    }
+    slices
+}
+
+/// Adjust a transform to pixel coordinates, such as that of a sprite, so
+/// that it takes `point` to a pixel coordinate whose fractional part is
+/// `offset`.
+///
+/// The transform is moved by less than a pixel along each axis, without
+/// changing its scale or its rotation. With an `offset` of `(0, 0)`, the
+/// corners of an unscaled and unrotated sprite whose origin is `point` land
+/// on the corners of pixels, so that its texels land on the centers of the
+/// pixels and are drawn crisply. An `offset` of `(0.5, 0.5)` puts `point`
+/// itself on the center of a pixel, as one-pixel lines and points need.
+///
+/// ```rust
+/// use cgmath::layout::snap_to_pixels;
+/// use cgmath::{Matrix3, Point2, Transform, Vector2};
+///
+/// let sprite = Matrix3::from_translation(Vector2::new(10.3, 4.8f64));
+/// let snapped = snap_to_pixels(sprite, Point2::new(0.0, 0.0), Vector2::new(0.0, 0.0));
+/// assert_eq!(snapped.transform_point(Point2::new(0.0, 0.0)), Point2::new(10.0, 5.0));
+/// ```
+pub fn snap_to_pixels<S: BaseFloat>(
+    transform: Matrix3<S>,
+    point: Point2<S>,
+    offset: Vector2<S>,
+) -> Matrix3<S> {
+    let p = transform.transform_point(point);
+    let snap = |x: S, offset: S| (x - offset).round() + offset - x;
+    let correction = Vector2::new(snap(p.x, offset.x), snap(p.y, offset.y));
+    Matrix3::from_translation(correction) * transform
+}
+
+/// An accumulator of the damaged regions of a surface, which need to be
+/// drawn again, as a small number of rectangles.
+///
+/// Every rectangle added is merged with the regions it overlaps, or which
+/// are close enough that their common bounding box is no larger than their
+/// areas put together, and those merges repeat until no such pair remains.
+/// When there are more regions than the cap, the pairs of regions whose
+/// common bounding box wastes the least area are merged. The regions stay
+/// a superset of every rectangle added, and work with integer pixels as
+/// well as floating point coordinates.
+///
+/// ```rust
+/// use cgmath::layout::DirtyRects;
+/// use cgmath::{Aabb2, Point2};
+///
+/// let rect = |x0, y0, x1, y1| Aabb2::new(Point2::new(x0, y0), Point2::new(x1, y1));
+/// let mut dirty = DirtyRects::new(4);
+/// dirty.add(rect(0, 0, 10, 10));
+/// dirty.add(rect(5, 5, 15, 15));
+/// dirty.add(rect(100, 100, 110, 110));
+/// assert_eq!(dirty.rects(), &[rect(0, 0, 15, 15), rect(100, 100, 110, 110)]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DirtyRects<S> {
+    rects: Vec<Aabb2<S>>,
+    max_rects: usize,
+}
+
+impl<S: BaseNum> DirtyRects<S> {
+    /// Create an empty accumulator, keeping at most `max_rects` regions,
+    /// and at least one.
+    pub fn new(max_rects: usize) -> DirtyRects<S> {
+        DirtyRects {
+            rects: Vec::new(),
+            max_rects: max_rects.max(1),
+        }
+    }
+
+    /// The damaged regions, which do not overlap each other much.
+    #[inline]
+    pub fn rects(&self) -> &[Aabb2<S>] {
+        &self.rects
+    }
+
+    /// Test whether no region is damaged.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// The bounding box of every damaged region, or `None` if there are
+    /// none.
+    pub fn bounds(&self) -> Option<Aabb2<S>> {
+        let mut rects = self.rects.iter();
+        let first = *rects.next()?;
+        Some(rects.fold(first, |bounds, rect| bounds.union(rect)))
+    }
+
+    /// Forget every damaged region, such as once they have been drawn.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    /// Add a damaged rectangle. Rectangles with no area are ignored.
+    pub fn add(&mut self, rect: Aabb2<S>) {
+        if rect.volume() == S::zero() {
+            return;
+        }
+        self.insert(rect);
+
+        while self.rects.len() > self.max_rects {
+            // Merge the pair of regions whose union wastes the least area,
+            // comparing `union - a - b` without subtracting, which could
+            // overflow unsigned coordinates.
+            let mut best = (0, 1);
+            let mut best_cost = None;
+            for i in 0..self.rects.len() {
+                for j in i + 1..self.rects.len() {
+                    let (a, b) = (self.rects[i], self.rects[j]);
+                    let cost = (a.union(&b).volume(), a.volume() + b.volume());
+                    let better = match best_cost {
+                        None => true,
+                        Some((union, areas)) => cost.0 + areas < union + cost.1,
+                    };
+                    if better {
+                        best = (i, j);
+                        best_cost = Some(cost);
+                    }
+                }
+            }
+            let (i, j) = best;
+            let b = self.rects.swap_remove(j);
+            let a = self.rects.swap_remove(i);
+            self.insert(a.union(&b));
+        }
+    }
+
+    /// Insert a region, merging it with the regions it is worth merging
+    /// with.
+    fn insert(&mut self, mut rect: Aabb2<S>) {
+        let worth_merging = |a: &Aabb2<S>, b: &Aabb2<S>| {
+            let overlap = a.intersection(b).map_or(false, |r| r.volume() > S::zero());
+            overlap || a.union(b).volume() <= a.volume() + b.volume()
+        };
+        while let Some(i) = self
+            .rects
+            .iter()
+            .position(|other| worth_merging(&rect, other))
+        {
+            rect = rect.union(&self.rects.swap_remove(i));
+        }
+        self.rects.push(rect);
+    }
+}
+
+/// A rectangle with rounded corners, such as the shape of a button, for
+/// hit-testing and for building its outline.
+///
+/// The radius is clamped to half the size of the smaller side of the
+/// rectangle, so that a large radius gives a pill or a disc.
+///
+/// ```rust
+/// use cgmath::layout::RoundedRect;
+/// use cgmath::{Aabb2, Point2};
+///
+/// let button = RoundedRect::new(Aabb2::new(Point2::new(0.0, 0.0), Point2::new(10.0, 4.0)), 1.0);
+/// assert!(button.contains_point(Point2::new(5.0, 0.5)));
+/// assert!(!button.contains_point(Point2::new(0.1, 0.1)));
+/// assert_eq!(button.signed_distance(Point2::new(5.0, 6.0)), 2.0);
+/// assert_eq!(button.signed_distance(Point2::new(5.0, 3.0)), -1.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RoundedRect<S> {
+    /// The bounds of the rectangle.
+    pub rect: Aabb2<S>,
+    /// The radius of the corners.
+    pub radius: S,
+}
+
+impl<S: BaseFloat> RoundedRect<S> {
+    /// Construct a rounded rectangle from its bounds and the radius of its
+    /// corners.
+    #[inline]
+    pub fn new(rect: Aabb2<S>, radius: S) -> RoundedRect<S> {
+        RoundedRect { rect, radius }
+    }
+
+    /// The radius of the corners, clamped to fit the rectangle.
+    pub fn effective_radius(&self) -> S {
+        let half = self.rect.dim() / (S::one() + S::one());
+        self.radius.min(half.x).min(half.y).max(S::zero())
+    }
+
+    /// The signed distance from the point to the boundary of the rectangle,
+    /// which is negative inside.
+    pub fn signed_distance(&self, p: Point2<S>) -> S {
+        let r = self.effective_radius();
+        let half = self.rect.dim() / (S::one() + S::one());
+        let d = p - self.rect.center();
+        // The offset past the rectangle the corner circles are centered on.
+        let q = Vector2::new(d.x.abs() - half.x + r, d.y.abs() - half.y + r);
+        let outside = Vector2::new(q.x.max(S::zero()), q.y.max(S::zero())).magnitude();
+        let inside = q.x.max(q.y).min(S::zero());
+        outside + inside - r
+    }
+
+    /// Test whether the point is inside the rectangle or on its boundary.
+    #[inline]
+    pub fn contains_point(&self, p: Point2<S>) -> bool {
+        self.signed_distance(p) <= S::zero()
+    }
+
+    /// Points along the boundary of the rectangle, counterclockwise from
+    /// the start of the arc of its bottom-right corner, approximating each
+    /// corner with `segments` segments, and at least one.
+    ///
+    /// The outline is closed between the last and the first point. Without
+    /// rounding, only the four corners are returned.
+    pub fn boundary_points(&self, segments: usize) -> Vec<Point2<S>> {
+        let r = self.effective_radius();
+        let (min, max) = (self.rect.min, self.rect.max);
+        if r == S::zero() {
+            return vec![
+                Point2::new(max.x, min.y),
+                max,
+                Point2::new(min.x, max.y),
+                min,
+            ];
+        }
+        let segments = segments.max(1);
+        let centers = [
+            Point2::new(max.x - r, min.y + r),
+            Point2::new(max.x - r, max.y - r),
+            Point2::new(min.x + r, max.y - r),
+            Point2::new(min.x + r, min.y + r),
+        ];
+        let quarter = Rad::<S>::turn_div_4();
+        let step = quarter / cast::<_, S>(segments).unwrap();
+        let mut points = Vec::with_capacity(4 * (segments + 1));
+        let mut start = -quarter;
+        for &center in &centers {
+            points.extend((0..=segments).map(|i| {
+                let (s, c) = Rad::sin_cos(start + step * cast::<_, S>(i).unwrap());
+                center + Vector2::new(c, s) * r
+            }));
+            start += quarter;
+        }
+        points
+    }
+}
@@ -84,16 +84,71 @@ pub use euler::Euler;
 pub use point::{point1, point2, point3, Point1, Point2, Point3};
 pub use rotation::*;
 pub use transform::*;
+pub use conformal::Conformal2;
+pub use homography::Homography;
+pub use cached_transform::CachedTransform;
+pub use transform_stack::{MatrixStack, TransformStack};
 
 pub use projection::*;
 
+pub use aabb::{Aabb2, Aabb3};
+pub use arc::Arc2;
+pub use bezier::{CubicBezier2, CubicBezier3};
+pub use curve::{CurveKey, PiecewiseCurve};
+pub use ellipse::{Ellipse2, Ellipsoid3};
+pub use frustum::Frustum;
+pub use gradient::Gradient;
+pub use intersect::{Intersect, Relation};
+pub use line::{Line2, Line3, LineIntersection2};
+pub use obb::{Obb2, Obb3};
+pub use plane::Plane;
+pub use polyline::{CurveFrame, Polyline2, Polyline3};
+pub use pose::{
+    extrapolate, follow, kinematic_step, portal_transform, smooth_damp, FollowParams,
+    KinematicParams, KinematicState, Pose2, Pose3, PoseBuffer, PoseInterpolation, Shake,
+};
+pub use ray::{Ray2, Ray3, RayCone, RayConeHit};
+pub use sphere::Sphere;
+pub use triangle::{Triangle2, Triangle3};
+pub use turtle::{Turtle2, Turtle3};
+pub use iter::{Rotated, TransformIteratorExt, Transformed, TransformedVectors};
+pub use world::{CameraRelative, WorldPoint3};
+pub use xform::Xform;
+
 // Modules
 
+mod macros;
+
+#[cfg(feature = "rand")]
+pub mod arbitrary;
+pub mod atlas;
 pub mod conv;
+pub mod epipolar;
+pub mod fast;
+pub mod field;
+pub mod geo;
+pub mod grid;
+pub mod heightfield;
+pub mod layout;
+pub mod mesh;
+pub mod noise;
+pub mod panorama;
+pub mod physics;
+pub mod polygon;
+pub mod precision;
+pub mod predicates;
+pub mod raster;
+pub mod registration;
+pub mod row;
+pub mod sampling;
+pub mod sensor;
+pub mod steering;
+pub mod strict;
+pub mod triangulation;
+pub mod units;
+pub mod vehicle;
 pub mod prelude;
 
-mod macros;
-
 mod num;
 mod structure;
 
@@ -113,5 +168,34 @@ mod euler;
 mod point;
 mod rotation;
 mod transform;
+mod conformal;
+mod homography;
+mod transform_stack;
+mod cached_transform;
 
 mod projection;
+
+mod aabb;
+mod arc;
+mod bezier;
+mod curve;
+mod ellipse;
+mod frustum;
+mod gradient;
+mod intersect;
+mod iter;
+mod line;
+mod obb;
+mod plane;
+mod polyline;
+mod pose;
+mod ray;
+mod spatial;
+mod sphere;
+mod triangle;
+mod turtle;
+mod world;
+mod xform;
+
+pub mod color;
+pub mod sh;
@@ -0,0 +1,192 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use structure::*;
+
+use num::BaseFloat;
+use point::{Point2, Point3};
+use vector::{Vector2, Vector3};
+
+/// An infinite line in two-dimensional space.
+///
+/// The line is described by a point on it and a direction, which does not
+/// need to be normalized, but must not be zero. The points of the line are
+/// `origin + direction * t` for every `t`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Line2<S> {
+    /// A point on the line.
+    pub origin: Point2<S>,
+    /// The direction of the line.
+    pub direction: Vector2<S>,
+}
+
+/// An infinite line in three-dimensional space.
+///
+/// The line is described by a point on it and a direction, which does not
+/// need to be normalized, but must not be zero. The points of the line are
+/// `origin + direction * t` for every `t`.
+///
+/// ```rust
+/// use cgmath::{Line3, Point3, Vector3};
+///
+/// // Dragging a gizmo along the x axis, with the mouse ray coming from above.
+/// let axis = Line3::new(Point3::new(0.0, 0.0, 0.0), Vector3::unit_x());
+/// let mouse = Line3::new(Point3::new(2.0, 1.0, 5.0), Vector3::new(0.0, 0.0, -1.0));
+/// let (on_axis, on_mouse) = axis.closest_points(&mouse);
+/// assert_eq!(on_axis, Point3::new(2.0, 0.0, 0.0));
+/// assert_eq!(on_mouse, Point3::new(2.0, 1.0, 0.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Line3<S> {
+    /// A point on the line.
+    pub origin: Point3<S>,
+    /// The direction of the line.
+    pub direction: Vector3<S>,
+}
+
+/// How two lines in the plane intersect, as computed by
+/// `Line2::intersection`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum LineIntersection2<S> {
+    /// The lines cross at a single point.
+    Point(Point2<S>),
+    /// The lines are parallel and distinct, so they never meet.
+    Parallel,
+    /// The lines are the same, so they overlap everywhere.
+    Collinear,
+}
+
+macro_rules! impl_line {
+    ($LineN:ident, $PointN:ident, $VectorN:ident) => {
+        impl<S: BaseFloat> $LineN<S> {
+            /// Construct a line from a point on it and its direction.
+            #[inline]
+            pub fn new(origin: $PointN<S>, direction: $VectorN<S>) -> $LineN<S> {
+                $LineN { origin, direction }
+            }
+
+            /// Construct the line through two distinct points, with `a`
+            /// at `t = 0` and `b` at `t = 1`.
+            #[inline]
+            pub fn from_points(a: $PointN<S>, b: $PointN<S>) -> $LineN<S> {
+                $LineN::new(a, b - a)
+            }
+
+            /// The point at parameter `t` along the line.
+            #[inline]
+            pub fn point_at(&self, t: S) -> $PointN<S> {
+                self.origin + self.direction * t
+            }
+
+            /// The parameter of the point of the line closest to `p`.
+            #[inline]
+            pub fn project_point(&self, p: $PointN<S>) -> S {
+                (p - self.origin).dot(self.direction) / self.direction.magnitude2()
+            }
+
+            /// The point of the line closest to `p`.
+            #[inline]
+            pub fn closest_point(&self, p: $PointN<S>) -> $PointN<S> {
+                self.point_at(self.project_point(p))
+            }
+
+            /// The distance from `p` to the line.
+            #[inline]
+            pub fn distance(&self, p: $PointN<S>) -> S {
+                self.closest_point(p).distance(p)
+            }
+        }
+    };
+}
+
+impl_line!(Line2, Point2, Vector2);
+impl_line!(Line3, Point3, Vector3);
+
+impl<S: BaseFloat> Line2<S> {
+    /// Compute where the line crosses `other`.
+    ///
+    /// Lines are considered parallel when the sine of the angle between them
+    /// is within the default epsilon of the scalar type, and collinear when
+    /// they are parallel and the origin of `other` is also within that
+    /// relative tolerance of this line.
+    ///
+    /// ```rust
+    /// use cgmath::{Line2, LineIntersection2, Point2, Vector2};
+    ///
+    /// let a = Line2::new(Point2::new(0.0, 0.0), Vector2::new(1.0, 1.0));
+    /// let b = Line2::new(Point2::new(4.0, 0.0), Vector2::new(-1.0, 1.0));
+    /// assert_eq!(a.intersection(&b), LineIntersection2::Point(Point2::new(2.0, 2.0)));
+    ///
+    /// let c = Line2::new(Point2::new(3.0, 3.0), Vector2::new(-2.0, -2.0));
+    /// assert_eq!(a.intersection(&c), LineIntersection2::Collinear);
+    /// ```
+    pub fn intersection(&self, other: &Line2<S>) -> LineIntersection2<S> {
+        let epsilon = S::default_epsilon();
+        let (d1, d2) = (self.direction, other.direction);
+        let r = other.origin - self.origin;
+        let denominator = d1.perp_dot(d2);
+        let scale = epsilon * d1.magnitude();
+        if implicit_ulps_eq!(denominator, S::zero(), epsilon = scale * d2.magnitude()) {
+            if implicit_ulps_eq!(d1.perp_dot(r), S::zero(), epsilon = scale * r.magnitude()) {
+                LineIntersection2::Collinear
+            } else {
+                LineIntersection2::Parallel
+            }
+        } else {
+            LineIntersection2::Point(self.point_at(r.perp_dot(d2) / denominator))
+        }
+    }
+}
+
+impl<S: BaseFloat> Line3<S> {
+    /// The parameters of the closest points between this line and `other`,
+    /// on this line and on `other` respectively.
+    ///
+    /// If the lines are parallel, every point is as close as any other, and
+    /// the parameters of the origin of this line and its projection on
+    /// `other` are returned.
+    pub fn closest_parameters(&self, other: &Line3<S>) -> (S, S) {
+        let (d1, d2) = (self.direction, other.direction);
+        let r = self.origin - other.origin;
+        let (a, b, c) = (d1.magnitude2(), d1.dot(d2), d2.magnitude2());
+        let (d, e) = (d1.dot(r), d2.dot(r));
+        let denominator = a * c - b * b;
+        let (zero, epsilon) = (S::zero(), S::default_epsilon() * a * c);
+        if denominator <= zero || implicit_ulps_eq!(denominator, zero, epsilon = epsilon) {
+            (S::zero(), e / c)
+        } else {
+            ((b * e - c * d) / denominator, (a * e - b * d) / denominator)
+        }
+    }
+
+    /// The closest points between this line and `other`, on this line and
+    /// on `other` respectively.
+    ///
+    /// If the lines intersect, both points are the intersection. If they are
+    /// parallel, the origin of this line and its projection on `other` are
+    /// returned.
+    pub fn closest_points(&self, other: &Line3<S>) -> (Point3<S>, Point3<S>) {
+        let (s, t) = self.closest_parameters(other);
+        (self.point_at(s), other.point_at(t))
+    }
+
+    /// The shortest distance between this line and `other`.
+    pub fn distance_to_line(&self, other: &Line3<S>) -> S {
+        let (p, q) = self.closest_points(other);
+        p.distance(q)
+    }
+}
@@ -27,6 +27,40 @@ macro_rules! default_fn {
     { $($tt:tt)* } => { fn $( $tt )* };
 }
 
+/// Compare two values with the default tolerance of `ulps_eq!`, or exactly
+/// when the `strict-fp` feature is enabled. This is used wherever the crate
+/// decides on its own whether a value is zero, the identity, or degenerate.
+///
+/// An absolute `epsilon` can be given for comparisons to zero, where it is
+/// usually scaled by the magnitude of the inputs. It is ignored as well
+/// under `strict-fp`.
+#[cfg(not(feature = "strict-fp"))]
+macro_rules! implicit_ulps_eq {
+    ($lhs:expr, $rhs:expr) => {
+        ulps_eq!($lhs, $rhs)
+    };
+    ($lhs:expr, $rhs:expr, epsilon = $epsilon:expr) => {
+        ulps_eq!($lhs, $rhs, epsilon = $epsilon)
+    };
+}
+
+#[cfg(feature = "strict-fp")]
+macro_rules! implicit_ulps_eq {
+    ($lhs:expr, $rhs:expr) => {
+        ulps_eq!($lhs, $rhs, epsilon = ::num_traits::Zero::zero(), max_ulps = 0)
+    };
+    ($lhs:expr, $rhs:expr, epsilon = $epsilon:expr) => {{
+        let _ = $epsilon;
+        implicit_ulps_eq!($lhs, $rhs)
+    }};
+}
+
+macro_rules! implicit_ulps_ne {
+    ($lhs:expr, $rhs:expr) => {
+        !implicit_ulps_eq!($lhs, $rhs)
+    };
+}
+
 /// Generates a binary operator implementation for the permutations of by-ref and by-val
 macro_rules! impl_operator {
     // When it is an unary operator
@@ -260,6 +294,27 @@ macro_rules! impl_tuple_conversions {
     }
 }
 
+/// Generate lossless `f32` to `f64` conversions and lossy `to_f32` methods for a compound type
+macro_rules! impl_precision_conversions {
+    ($Type:ident { $($field:tt),+ }, $Single:ty, $Double:ty) => {
+        impl From<$Single> for $Double {
+            #[inline]
+            fn from(v: $Single) -> $Double {
+                $Type { $($field: v.$field.into()),+ }
+            }
+        }
+
+        impl $Double {
+            /// Convert to single precision, rounding each component to the
+            /// nearest `f32`.
+            #[inline]
+            pub fn to_f32(self) -> $Single {
+                $Type { $($field: self.$field.to_f32()),+ }
+            }
+        }
+    };
+}
+
 /// Generates index operators for a compound type
 macro_rules! impl_index_operators {
     ($VectorN:ident<$S:ident>, $n:expr, $Output:ty, $I:ty) => {
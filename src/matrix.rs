@@ -31,8 +31,10 @@ use angle::Rad;
 use approx;
 use euler::Euler;
 use num::BaseFloat;
+use plane::Plane;
 use point::{Point2, Point3};
 use quaternion::Quaternion;
+use rotation::Rotation3;
 use transform::{Transform, Transform2, Transform3};
 use vector::{Vector2, Vector3, Vector4};
 
@@ -42,6 +44,8 @@ use mint;
 /// A 2 x 2, column major matrix
 ///
 /// This type is marked as `#[repr(C)]`.
+/// The columns are stored one after the other without padding, see
+/// `as_ptr` and `as_col_major_array` for passing the matrix to C APIs.
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -55,6 +59,8 @@ pub struct Matrix2<S> {
 /// A 3 x 3, column major matrix
 ///
 /// This type is marked as `#[repr(C)]`.
+/// The columns are stored one after the other without padding, see
+/// `as_ptr` and `as_col_major_array` for passing the matrix to C APIs.
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -70,6 +76,8 @@ pub struct Matrix3<S> {
 /// A 4 x 4, column major matrix
 ///
 /// This type is marked as `#[repr(C)]`.
+/// The columns are stored one after the other without padding, see
+/// `as_ptr` and `as_col_major_array` for passing the matrix to C APIs.
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -129,6 +137,20 @@ impl<S: BaseFloat> Matrix2<S> {
     pub fn is_finite(&self) -> bool {
         self.x.is_finite() && self.y.is_finite()
     }
+
+    /// Decompose the matrix into the product of a rotation and a symmetric
+    /// stretch, `self == rotation * stretch`.
+    ///
+    /// The rotation is the one closest to the matrix, which makes this the
+    /// preferred way of extracting the rotation from a matrix that has been
+    /// deformed or has accumulated rounding errors. If the determinant of the
+    /// matrix is negative, the stretch contains the reflection.
+    pub fn polar_decomposition(&self) -> (Matrix2<S>, Matrix2<S>) {
+        let angle = Rad::atan2(self.x.y - self.y.x, self.x.x + self.y.y);
+        let rotation = Matrix2::from_angle(angle);
+        let stretch = rotation.transpose_mul(self);
+        (rotation, symmetric_part(stretch))
+    }
 }
 
 impl<S> Matrix3<S> {
@@ -159,6 +181,15 @@ impl<S> Matrix3<S> {
 }
 
 impl<S: BaseFloat> Matrix3<S> {
+    /// Embed the matrix in the top-left corner of a homogeneous
+    /// transformation matrix with no translation.
+    ///
+    /// This is the same as `Matrix4::from(m)`.
+    #[inline]
+    pub fn to_matrix4(&self) -> Matrix4<S> {
+        Matrix4::from(*self)
+    }
+
     /// Create a homogeneous transformation matrix from a translation vector.
     #[inline]
     pub fn from_translation(v: Vector2<S>) -> Matrix3<S> {
@@ -263,6 +294,51 @@ impl<S: BaseFloat> Matrix3<S> {
     pub fn is_finite(&self) -> bool {
         self.x.is_finite() && self.y.is_finite() && self.z.is_finite()
     }
+
+    /// Decompose the matrix into the product of a rotation and a symmetric
+    /// stretch, `self == rotation * stretch`.
+    ///
+    /// The rotation is the one closest to the matrix, which makes this the
+    /// preferred way of extracting the rotation from a matrix that has been
+    /// deformed or has accumulated rounding errors. If the determinant of the
+    /// matrix is negative, the stretch contains the reflection.
+    ///
+    /// The rotation is found iteratively with the method from "A Robust
+    /// Method to Extract the Rotational Part of Deformations" by Müller et
+    /// al., which also copes with singular matrices.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Deg, Matrix3, Vector3};
+    /// # #[macro_use] extern crate approx;
+    /// # fn main() {
+    ///
+    /// let rotation = Matrix3::from_angle_z(Deg(30.0));
+    /// let stretch = Matrix3::from_diagonal(Vector3::new(2.0, 0.5, 1.0));
+    ///
+    /// let (r, s) = (rotation * stretch).polar_decomposition();
+    /// assert_relative_eq!(r, rotation, epsilon = 1e-12);
+    /// assert_relative_eq!(s, stretch, epsilon = 1e-12);
+    /// # }
+    /// ```
+    pub fn polar_decomposition(&self) -> (Matrix3<S>, Matrix3<S>) {
+        let mut q = Quaternion::from_sv(S::one(), Vector3::zero());
+        for _ in 0..64 {
+            let r = Matrix3::from(q);
+            let torque = r.x.cross(self.x) + r.y.cross(self.y) + r.z.cross(self.z);
+            let alignment = r.x.dot(self.x) + r.y.dot(self.y) + r.z.dot(self.z);
+            let omega = torque / (alignment.abs() + S::epsilon());
+            let angle = omega.magnitude();
+            if angle <= S::epsilon() {
+                break;
+            }
+            q = (Quaternion::from_axis_angle(omega / angle, Rad(angle)) * q).normalize();
+        }
+
+        let rotation = Matrix3::from(q);
+        let stretch = rotation.transpose_mul(self);
+        (rotation, symmetric_part(stretch))
+    }
 }
 
 impl<S> Matrix4<S> {
@@ -300,7 +376,133 @@ impl<S> Matrix4<S> {
     }
 }
 
+/// Remove the rounding errors that make a matrix that should be symmetric
+/// slightly asymmetric.
+#[inline]
+fn symmetric_part<M: SquareMatrix>(m: M) -> M
+where
+    M::Scalar: BaseFloat,
+{
+    (m + m.transpose()) / (M::Scalar::one() + M::Scalar::one())
+}
+
+macro_rules! impl_matrix_consts {
+    ($($S:ident),+) => {
+        $(
+            impl Matrix2<$S> {
+                /// The matrix with all elements set to zero.
+                pub const ZERO: Matrix2<$S> = Matrix2::new(0.0, 0.0, 0.0, 0.0);
+                /// The identity matrix.
+                pub const IDENTITY: Matrix2<$S> = Matrix2::new(1.0, 0.0, 0.0, 1.0);
+            }
+
+            impl Matrix3<$S> {
+                /// The matrix with all elements set to zero.
+                #[cfg_attr(rustfmt, rustfmt_skip)]
+                pub const ZERO: Matrix3<$S> = Matrix3::new(
+                    0.0, 0.0, 0.0,
+                    0.0, 0.0, 0.0,
+                    0.0, 0.0, 0.0,
+                );
+                /// The identity matrix.
+                #[cfg_attr(rustfmt, rustfmt_skip)]
+                pub const IDENTITY: Matrix3<$S> = Matrix3::new(
+                    1.0, 0.0, 0.0,
+                    0.0, 1.0, 0.0,
+                    0.0, 0.0, 1.0,
+                );
+            }
+
+            impl Matrix4<$S> {
+                /// The matrix with all elements set to zero.
+                #[cfg_attr(rustfmt, rustfmt_skip)]
+                pub const ZERO: Matrix4<$S> = Matrix4::new(
+                    0.0, 0.0, 0.0, 0.0,
+                    0.0, 0.0, 0.0, 0.0,
+                    0.0, 0.0, 0.0, 0.0,
+                    0.0, 0.0, 0.0, 0.0,
+                );
+                /// The identity matrix.
+                #[cfg_attr(rustfmt, rustfmt_skip)]
+                pub const IDENTITY: Matrix4<$S> = Matrix4::new(
+                    1.0, 0.0, 0.0, 0.0,
+                    0.0, 1.0, 0.0, 0.0,
+                    0.0, 0.0, 1.0, 0.0,
+                    0.0, 0.0, 0.0, 1.0,
+                );
+            }
+        )+
+    };
+}
+
+// `One::one` and `Zero::zero` can't be called in constant contexts, so the
+// identity and zero matrices are also provided for the floating point scalar
+// types.
+impl_matrix_consts!(f32, f64);
+
 impl<S: BaseFloat> Matrix4<S> {
+    /// Create a homogeneous transformation matrix that applies the linear
+    /// transformation `m`, followed by a translation by `t`.
+    #[inline]
+    pub fn from_mat3_translation(m: Matrix3<S>, t: Vector3<S>) -> Matrix4<S> {
+        Matrix4::from_cols(
+            m.x.extend(S::zero()),
+            m.y.extend(S::zero()),
+            m.z.extend(S::zero()),
+            t.extend(S::one()),
+        )
+    }
+
+    /// Create the homogeneous transformation matrix that scales by `scale`,
+    /// then rotates by the unit quaternion `rotation`, then translates by
+    /// `translation`.
+    ///
+    /// This is the usual model matrix of scene graph nodes, equal to
+    /// `from_translation(translation) * from(rotation) * scale`, computed
+    /// directly.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Deg, Matrix4, Point3, Quaternion, Vector3};
+    ///
+    /// let rotation = Quaternion::from_angle_z(Deg(90.0));
+    /// let m = Matrix4::compose(Vector3::new(1.0, 0.0, 0.0), rotation, Vector3::new(2.0, 1.0, 1.0));
+    /// let p = m.transform_point(Point3::new(1.0, 0.0, 0.0));
+    /// assert!((p - Point3::new(1.0, 2.0, 0.0)).magnitude() < 1e-12);
+    /// ```
+    pub fn compose(
+        translation: Vector3<S>,
+        rotation: Quaternion<S>,
+        scale: Vector3<S>,
+    ) -> Matrix4<S> {
+        let r = Matrix3::from(rotation);
+        let m = Matrix3::from_cols(r.x * scale.x, r.y * scale.y, r.z * scale.z);
+        Matrix4::from_mat3_translation(m, translation)
+    }
+
+    /// Create the inverse of `compose(translation, rotation, scale)`,
+    /// computed directly rather than by inverting the matrix.
+    ///
+    /// This is the view matrix of a camera placed by these components. The
+    /// components of `scale` must not be zero.
+    pub fn compose_inverse(
+        translation: Vector3<S>,
+        rotation: Quaternion<S>,
+        scale: Vector3<S>,
+    ) -> Matrix4<S> {
+        // The inverse scales the rows of the transposed rotation.
+        let r = Matrix3::from(rotation);
+        let m = Matrix3::from_cols(r.x / scale.x, r.y / scale.y, r.z / scale.z).transpose();
+        Matrix4::from_mat3_translation(m, -(m * translation))
+    }
+
+    /// The upper-left 3x3 matrix, which is the linear part of an affine
+    /// transformation.
+    #[inline]
+    pub fn truncate(&self) -> Matrix3<S> {
+        Matrix3::from_cols(self.x.truncate(), self.y.truncate(), self.z.truncate())
+    }
+
     /// Create a homogeneous transformation matrix from a translation vector.
     #[inline]
     pub fn from_translation(v: Vector3<S>) -> Matrix4<S> {
@@ -353,6 +555,36 @@ impl<S: BaseFloat> Matrix4<S> {
         Matrix4::look_at_dir(eye, center - eye, up)
     }
 
+    /// Create the matrix flattening geometry onto `plane` along the rays from
+    /// `light`, for planar shadows.
+    ///
+    /// `light` is a homogeneous position: a point light at `p` is
+    /// `p.to_homogeneous()`, and a directional light shining along `dir` is
+    /// `(-dir).extend(0.0)`, pointing towards the light. The result is a
+    /// projective matrix, so transformed points must be divided by `w`.
+    /// Points on the far side of the light from the plane project behind it.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Matrix4, Plane, Point3, Vector3, Vector4};
+    ///
+    /// let ground = Plane::new(Vector3::unit_y(), 0.0);
+    /// let light = Vector4::new(0.0, 10.0, 0.0, 1.0);
+    /// let shadow = Matrix4::shadow(&ground, &light);
+    /// let p = shadow.transform_point(Point3::new(1.0, 5.0, 0.0));
+    /// assert!((p - Point3::new(2.0, 0.0, 0.0)).magnitude() < 1e-12);
+    /// ```
+    pub fn shadow(plane: &Plane<S>, light: &Vector4<S>) -> Matrix4<S> {
+        let p = plane.normal.extend(-plane.d);
+        let dot = p.dot(*light);
+        Matrix4::from_cols(
+            Vector4::unit_x() * dot - *light * p.x,
+            Vector4::unit_y() * dot - *light * p.y,
+            Vector4::unit_z() * dot - *light * p.z,
+            Vector4::unit_w() * dot - *light * p.w,
+        )
+    }
+
     /// Create a homogeneous transformation matrix from a rotation around the `x` axis (pitch).
     pub fn from_angle_x<A: Into<Rad<S>>>(theta: A) -> Matrix4<S> {
         // http://en.wikipedia.org/wiki/Rotation_matrix#Basic_rotations
@@ -441,7 +673,7 @@ impl<S: BaseFloat> Zero for Matrix2<S> {
 
     #[inline]
     fn is_zero(&self) -> bool {
-        ulps_eq!(self, &Self::zero())
+        implicit_ulps_eq!(self, &Self::zero())
     }
 }
 
@@ -458,7 +690,7 @@ impl<S: BaseFloat> Zero for Matrix3<S> {
 
     #[inline]
     fn is_zero(&self) -> bool {
-        ulps_eq!(self, &Self::zero())
+        implicit_ulps_eq!(self, &Self::zero())
     }
 }
 
@@ -476,7 +708,7 @@ impl<S: BaseFloat> Zero for Matrix4<S> {
 
     #[inline]
     fn is_zero(&self) -> bool {
-        ulps_eq!(self, &Self::zero())
+        implicit_ulps_eq!(self, &Self::zero())
     }
 }
 
@@ -501,6 +733,30 @@ impl<S: BaseFloat> One for Matrix4<S> {
     }
 }
 
+/// The default matrix is the identity matrix.
+impl<S: BaseFloat> Default for Matrix2<S> {
+    #[inline]
+    fn default() -> Matrix2<S> {
+        Matrix2::from_value(S::one())
+    }
+}
+
+/// The default matrix is the identity matrix.
+impl<S: BaseFloat> Default for Matrix3<S> {
+    #[inline]
+    fn default() -> Matrix3<S> {
+        Matrix3::from_value(S::one())
+    }
+}
+
+/// The default matrix is the identity matrix.
+impl<S: BaseFloat> Default for Matrix4<S> {
+    #[inline]
+    fn default() -> Matrix4<S> {
+        Matrix4::from_value(S::one())
+    }
+}
+
 impl<S: BaseFloat> VectorSpace for Matrix2<S> {
     type Scalar = S;
 }
@@ -602,12 +858,12 @@ impl<S: BaseFloat> SquareMatrix for Matrix2<S> {
 
     #[inline]
     fn is_diagonal(&self) -> bool {
-        ulps_eq!(self[0][1], &S::zero()) && ulps_eq!(self[1][0], &S::zero())
+        implicit_ulps_eq!(self[0][1], &S::zero()) && implicit_ulps_eq!(self[1][0], &S::zero())
     }
 
     #[inline]
     fn is_symmetric(&self) -> bool {
-        ulps_eq!(self[0][1], &self[1][0]) && ulps_eq!(self[1][0], &self[0][1])
+        implicit_ulps_eq!(self[0][1], &self[1][0]) && implicit_ulps_eq!(self[1][0], &self[0][1])
     }
 }
 
@@ -708,21 +964,21 @@ impl<S: BaseFloat> SquareMatrix for Matrix3<S> {
     }
 
     fn is_diagonal(&self) -> bool {
-        ulps_eq!(self[0][1], &S::zero())
-            && ulps_eq!(self[0][2], &S::zero())
-            && ulps_eq!(self[1][0], &S::zero())
-            && ulps_eq!(self[1][2], &S::zero())
-            && ulps_eq!(self[2][0], &S::zero())
-            && ulps_eq!(self[2][1], &S::zero())
+        implicit_ulps_eq!(self[0][1], &S::zero())
+            && implicit_ulps_eq!(self[0][2], &S::zero())
+            && implicit_ulps_eq!(self[1][0], &S::zero())
+            && implicit_ulps_eq!(self[1][2], &S::zero())
+            && implicit_ulps_eq!(self[2][0], &S::zero())
+            && implicit_ulps_eq!(self[2][1], &S::zero())
     }
 
     fn is_symmetric(&self) -> bool {
-        ulps_eq!(self[0][1], &self[1][0])
-            && ulps_eq!(self[0][2], &self[2][0])
-            && ulps_eq!(self[1][0], &self[0][1])
-            && ulps_eq!(self[1][2], &self[2][1])
-            && ulps_eq!(self[2][0], &self[0][2])
-            && ulps_eq!(self[2][1], &self[1][2])
+        implicit_ulps_eq!(self[0][1], &self[1][0])
+            && implicit_ulps_eq!(self[0][2], &self[2][0])
+            && implicit_ulps_eq!(self[1][0], &self[0][1])
+            && implicit_ulps_eq!(self[1][2], &self[2][1])
+            && implicit_ulps_eq!(self[2][0], &self[0][2])
+            && implicit_ulps_eq!(self[2][1], &self[1][2])
     }
 }
 
@@ -875,33 +1131,33 @@ impl<S: BaseFloat> SquareMatrix for Matrix4<S> {
     }
 
     fn is_diagonal(&self) -> bool {
-        ulps_eq!(self[0][1], &S::zero())
-            && ulps_eq!(self[0][2], &S::zero())
-            && ulps_eq!(self[0][3], &S::zero())
-            && ulps_eq!(self[1][0], &S::zero())
-            && ulps_eq!(self[1][2], &S::zero())
-            && ulps_eq!(self[1][3], &S::zero())
-            && ulps_eq!(self[2][0], &S::zero())
-            && ulps_eq!(self[2][1], &S::zero())
-            && ulps_eq!(self[2][3], &S::zero())
-            && ulps_eq!(self[3][0], &S::zero())
-            && ulps_eq!(self[3][1], &S::zero())
-            && ulps_eq!(self[3][2], &S::zero())
+        implicit_ulps_eq!(self[0][1], &S::zero())
+            && implicit_ulps_eq!(self[0][2], &S::zero())
+            && implicit_ulps_eq!(self[0][3], &S::zero())
+            && implicit_ulps_eq!(self[1][0], &S::zero())
+            && implicit_ulps_eq!(self[1][2], &S::zero())
+            && implicit_ulps_eq!(self[1][3], &S::zero())
+            && implicit_ulps_eq!(self[2][0], &S::zero())
+            && implicit_ulps_eq!(self[2][1], &S::zero())
+            && implicit_ulps_eq!(self[2][3], &S::zero())
+            && implicit_ulps_eq!(self[3][0], &S::zero())
+            && implicit_ulps_eq!(self[3][1], &S::zero())
+            && implicit_ulps_eq!(self[3][2], &S::zero())
     }
 
     fn is_symmetric(&self) -> bool {
-        ulps_eq!(self[0][1], &self[1][0])
-            && ulps_eq!(self[0][2], &self[2][0])
-            && ulps_eq!(self[0][3], &self[3][0])
-            && ulps_eq!(self[1][0], &self[0][1])
-            && ulps_eq!(self[1][2], &self[2][1])
-            && ulps_eq!(self[1][3], &self[3][1])
-            && ulps_eq!(self[2][0], &self[0][2])
-            && ulps_eq!(self[2][1], &self[1][2])
-            && ulps_eq!(self[2][3], &self[3][2])
-            && ulps_eq!(self[3][0], &self[0][3])
-            && ulps_eq!(self[3][1], &self[1][3])
-            && ulps_eq!(self[3][2], &self[2][3])
+        implicit_ulps_eq!(self[0][1], &self[1][0])
+            && implicit_ulps_eq!(self[0][2], &self[2][0])
+            && implicit_ulps_eq!(self[0][3], &self[3][0])
+            && implicit_ulps_eq!(self[1][0], &self[0][1])
+            && implicit_ulps_eq!(self[1][2], &self[2][1])
+            && implicit_ulps_eq!(self[1][3], &self[3][1])
+            && implicit_ulps_eq!(self[2][0], &self[0][2])
+            && implicit_ulps_eq!(self[2][1], &self[1][2])
+            && implicit_ulps_eq!(self[2][3], &self[3][2])
+            && implicit_ulps_eq!(self[3][0], &self[0][3])
+            && implicit_ulps_eq!(self[3][1], &self[1][3])
+            && implicit_ulps_eq!(self[3][2], &self[2][3])
     }
 }
 
@@ -1125,6 +1381,30 @@ impl<S: BaseFloat> Transform3<S> for Matrix4<S> {}
 
 macro_rules! impl_matrix {
     ($MatrixN:ident, $VectorN:ident { $($field:ident : $row_index:expr),+ }) => {
+        impl<S: BaseFloat> $MatrixN<S> {
+            /// Multiply the transpose of this matrix by a vector, without
+            /// computing the transpose. For an orthogonal matrix, such as a
+            /// rotation, this applies the inverse transformation.
+            #[inline]
+            pub fn transpose_mul_vector(&self, v: $VectorN<S>) -> $VectorN<S> {
+                $VectorN::new($(self.$field.dot(v)),+)
+            }
+
+            /// Compute `self.transpose() * other`, without computing the
+            /// transpose.
+            #[inline]
+            pub fn transpose_mul(&self, other: &$MatrixN<S>) -> $MatrixN<S> {
+                $MatrixN { $($field: self.transpose_mul_vector(other.$field)),+ }
+            }
+
+            /// Compute `self * other.transpose()`, without computing the
+            /// transpose.
+            #[inline]
+            pub fn mul_transposed(&self, other: &$MatrixN<S>) -> $MatrixN<S> {
+                $MatrixN { $($field: *self * other.row($row_index)),+ }
+            }
+        }
+
         impl_operator!(<S: BaseFloat> Neg for $MatrixN<S> {
             fn neg(matrix) -> $MatrixN<S> { $MatrixN { $($field: -matrix.$field),+ } }
         });
@@ -1443,6 +1723,50 @@ macro_rules! fixed_array_conversions {
             }
         }
 
+        impl<$S> $MatrixN<$S> {
+            /// Get a pointer to the first element of the matrix, for passing
+            /// to C APIs.
+            ///
+            /// The matrix is stored as consecutive columns, so the pointer
+            /// refers to the elements in column-major order, without any
+            /// padding. This is the layout expected by `glUniformMatrix*fv`
+            /// with `transpose` set to `GL_FALSE`.
+            #[inline]
+            pub fn as_ptr(&self) -> *const $S {
+                self as *const $MatrixN<$S> as *const $S
+            }
+
+            /// Get a mutable pointer to the first element of the matrix. See
+            /// `as_ptr` for the layout of the elements.
+            #[inline]
+            pub fn as_mut_ptr(&mut self) -> *mut $S {
+                self as *mut $MatrixN<$S> as *mut $S
+            }
+
+            /// View the elements of the matrix as a flat array in column-major
+            /// order, so that element `c * N + r` is row `r` of column `c`.
+            #[inline]
+            pub fn as_col_major_array(&self) -> &[$S; ($n * $n)] {
+                self.as_ref()
+            }
+        }
+
+        impl<$S: Copy> $MatrixN<$S> {
+            /// Copy the elements of the matrix into a flat array in row-major
+            /// order, so that element `r * N + c` is column `c` of row `r`.
+            #[inline]
+            pub fn to_row_major_array(&self) -> [$S; ($n * $n)] {
+                let cols: &[[$S; $n]; $n] = self.as_ref();
+                let mut out = [cols[0][0]; ($n * $n)];
+                for (c, col) in cols.iter().enumerate() {
+                    for (r, &value) in col.iter().enumerate() {
+                        out[r * $n + c] = value;
+                    }
+                }
+                out
+            }
+        }
+
         // impl<$S> From<[$S; ($n * $n)]> for $MatrixN<$S> {
         //     #[inline]
         //     fn from(m: [$S; ($n * $n)]) -> $MatrixN<$S> {
@@ -1498,6 +1822,10 @@ mint_conversions!(Matrix3 { x, y, z }, ColumnMatrix3);
 #[cfg(feature = "mint")]
 mint_conversions!(Matrix4 { x, y, z, w }, ColumnMatrix4);
 
+impl_precision_conversions!(Matrix2 { x, y }, Matrix2<f32>, Matrix2<f64>);
+impl_precision_conversions!(Matrix3 { x, y, z }, Matrix3<f32>, Matrix3<f64>);
+impl_precision_conversions!(Matrix4 { x, y, z, w }, Matrix4<f32>, Matrix4<f64>);
+
 impl<S: BaseFloat> From<Matrix2<S>> for Matrix3<S> {
     /// Clone the elements of a 2-dimensional matrix into the top-left corner
     /// of a 3-dimensional identity matrix.
@@ -0,0 +1,947 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities for processing triangle meshes.
+//!
+//! ```rust
+//! use cgmath::mesh::weld;
+//! use cgmath::Point3;
+//!
+//! // Two triangles sharing an edge, stored as a triangle soup.
+//! let soup = [
+//!     Point3::new(0.0, 0.0, 0.0),
+//!     Point3::new(1.0, 0.0, 0.0),
+//!     Point3::new(0.0, 1.0, 0.0),
+//!     Point3::new(1.0, 0.0, 1e-7),
+//!     Point3::new(1.0, 1.0, 0.0),
+//!     Point3::new(0.0, 1.0, 0.0),
+//! ];
+//! let (vertices, remap) = weld(&soup, 1e-5);
+//! assert_eq!(vertices.len(), 4);
+//! assert_eq!(remap, vec![0, 1, 2, 1, 3, 2]);
+//! ```
+
+use std::ops::{Add, AddAssign, Mul};
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::Aabb3;
+use angle::Rad;
+use field::Grid3;
+use matrix::{Matrix3, Matrix4};
+use num::BaseFloat;
+use plane::Plane;
+use point::{Point2, Point3};
+use polyline::{any_perpendicular, Polyline3};
+use predicates::orient3d;
+use spatial::SpatialHash;
+use triangle::Triangle3;
+use vector::{Vector3, Vector4};
+
+/// Merge points that are within `epsilon` of each other.
+///
+/// Returns the merged points, and for each input point the index of the
+/// merged point that replaces it. Each merged point is the first of the
+/// input points that were merged into it, and the merged points keep the
+/// order of the input. Points are only merged if they are within `epsilon`
+/// of that first point, so chains of points that are each close to the next
+/// are not collapsed into one. An `epsilon` of zero only merges identical
+/// points.
+///
+/// This runs in linear time on average, using a spatial hash with cells the
+/// size of `epsilon`.
+pub fn weld<S: BaseFloat>(points: &[Point3<S>], epsilon: S) -> (Vec<Point3<S>>, Vec<usize>) {
+    let cell_size = if epsilon > S::zero() {
+        epsilon
+    } else {
+        S::one()
+    };
+    let epsilon2 = epsilon * epsilon;
+    let mut grid = SpatialHash::new(cell_size);
+    let mut welded: Vec<Point3<S>> = Vec::new();
+    let mut remap = Vec::with_capacity(points.len());
+    for &p in points {
+        let existing = grid
+            .neighbours(p)
+            .filter(|&i| welded[i].distance2(p) <= epsilon2)
+            .min();
+        remap.push(existing.unwrap_or_else(|| {
+            grid.insert(p, welded.len());
+            welded.push(p);
+            welded.len() - 1
+        }));
+    }
+    (welded, remap)
+}
+
+/// The generalized winding number of a set of triangles around a point, the
+/// sum of the signed solid angles they cover as seen from it, in turns.
+///
+/// This is one inside of a closed mesh whose triangles are counterclockwise
+/// seen from the outside, and zero outside of it. Unlike ray casting, it
+/// degrades smoothly on meshes with holes, duplicated or flipped triangles,
+/// where it stays close to one deep inside, following "Robust Inside-Outside
+/// Segmentation using Generalized Winding Numbers" by Jacobson et al. The
+/// solid angles use the formula of Van Oosterom and Strackee. This takes
+/// linear time in the number of triangles.
+pub fn winding_number<S: BaseFloat>(triangles: &[Triangle3<S>], p: Point3<S>) -> S {
+    let two: S = cast(2).unwrap();
+    let total = triangles.iter().fold(S::zero(), |total, t| {
+        let (a, b, c) = (t.a - p, t.b - p, t.c - p);
+        let (la, lb, lc) = (a.magnitude(), b.magnitude(), c.magnitude());
+        let numerator = a.dot(b.cross(c));
+        let denominator = la * lb * lc + a.dot(b) * lc + a.dot(c) * lb + b.dot(c) * la;
+        total + Rad::atan2(numerator, denominator).0 * two
+    });
+    total / (two * Rad::<S>::full_turn().0)
+}
+
+/// Test whether the point is inside a closed mesh, whose winding number
+/// around it is more than one half in magnitude, so that meshes turned
+/// inside out work too.
+///
+/// ```rust
+/// use cgmath::mesh::contains_point;
+/// use cgmath::{Point3, Triangle3};
+///
+/// // A tetrahedron, with its faces counterclockwise from the outside.
+/// let (o, x, y, z) = (
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0f64),
+/// );
+/// let tetrahedron = [
+///     Triangle3::new(o, y, x),
+///     Triangle3::new(o, x, z),
+///     Triangle3::new(o, z, y),
+///     Triangle3::new(x, y, z),
+/// ];
+/// assert!(contains_point(&tetrahedron, Point3::new(0.2, 0.2, 0.2)));
+/// assert!(!contains_point(&tetrahedron, Point3::new(0.5, 0.5, 0.5)));
+/// ```
+pub fn contains_point<S: BaseFloat>(triangles: &[Triangle3<S>], p: Point3<S>) -> bool {
+    winding_number(triangles, p).abs() > cast(0.5).unwrap()
+}
+
+/// Conservatively voxelize a set of triangles into a grid of cubes of side
+/// `cell_size`, marking every cube that a triangle overlaps or touches.
+///
+/// The samples of the grid are the centers of the cubes, and the grid
+/// covers the bounds of the triangles, starting from their minimum corner.
+/// Returns `None` if there are no triangles, or if the cell size is not
+/// positive.
+///
+/// ```rust
+/// use cgmath::mesh::voxelize;
+/// use cgmath::{Point3, Triangle3};
+///
+/// let floor = Triangle3::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(4.0, 0.0, 0.0),
+///     Point3::new(0.0, 0.0, 4.0f64),
+/// );
+/// let grid = voxelize(&[floor], 1.0).unwrap();
+/// assert_eq!((grid.width(), grid.height(), grid.depth()), (5, 1, 5));
+/// assert!(grid.get(0, 0, 0) && grid.get(1, 0, 2) && !grid.get(3, 0, 3));
+/// ```
+pub fn voxelize<S: BaseFloat>(triangles: &[Triangle3<S>], cell_size: S) -> Option<Grid3<S, bool>> {
+    if cell_size.is_nan() || cell_size <= S::zero() {
+        return None;
+    }
+    let mut boxes = triangles.iter().map(Triangle3::to_aabb);
+    let first = boxes.next()?;
+    let bounds = boxes.fold(first, |bounds, b| bounds.union(&b));
+    let cells = |x: S| (x / cell_size).floor().to_usize().unwrap_or(0);
+    let size = bounds.dim();
+    let (width, height, depth) = (cells(size.x) + 1, cells(size.y) + 1, cells(size.z) + 1);
+    let mut voxels = vec![false; width * height * depth];
+
+    let cell = Vector3::from_value(cell_size);
+    for triangle in triangles {
+        let range = triangle.to_aabb();
+        let first = (range.min - bounds.min).map(cells);
+        let last = (range.max - bounds.min).map(cells);
+        for k in first.z..(last.z + 1).min(depth) {
+            for j in first.y..(last.y + 1).min(height) {
+                for i in first.x..(last.x + 1).min(width) {
+                    let index = Vector3::new(i, j, k).map(|n| cast::<_, S>(n).unwrap());
+                    let min = bounds.min + index * cell_size;
+                    let voxel = Aabb3::new(min, min + cell);
+                    if triangle.intersects_aabb(&voxel) {
+                        voxels[(k * height + j) * width + i] = true;
+                    }
+                }
+            }
+        }
+    }
+    let origin = bounds.min + cell / (S::one() + S::one());
+    Some(Grid3::new(origin, cell_size, width, height, depth, voxels))
+}
+
+/// The cross-section of a set of triangles by a plane, as polylines.
+///
+/// The segments where the triangles cross the plane are joined at endpoints
+/// within `epsilon` of each other, as with `weld`. Closed loops repeat their
+/// first point at the end. On a closed mesh whose triangles are
+/// counterclockwise seen from the outside, every polyline is a closed loop,
+/// and outer boundaries are counterclockwise seen from the positive side of
+/// the plane while holes are clockwise. Open polylines remain where the mesh
+/// has boundaries, and at non-manifold edges the segments are joined in an
+/// arbitrary order.
+///
+/// Vertices lying on the plane count as being on its positive side, so that
+/// faces in the plane and edges touching it add no segments.
+///
+/// ```rust
+/// use cgmath::mesh::slice;
+/// use cgmath::{Plane, Point3, Triangle3, Vector3};
+///
+/// // A tetrahedron, cut halfway up.
+/// let [a, b, c, d] = [
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(2.0, 0.0, 0.0),
+///     Point3::new(0.0, 2.0, 0.0),
+///     Point3::new(0.0, 0.0, 2.0),
+/// ];
+/// let tetrahedron = [
+///     Triangle3::new(a, c, b),
+///     Triangle3::new(a, b, d),
+///     Triangle3::new(b, c, d),
+///     Triangle3::new(c, a, d),
+/// ];
+/// let plane = Plane::new(Vector3::unit_z(), 1.0);
+/// let loops = slice(&tetrahedron, &plane, 1e-9);
+/// assert_eq!(loops.len(), 1);
+/// assert_eq!(loops[0].points.len(), 4);
+/// assert_eq!(loops[0].length(), 2.0 + 2.0f64.sqrt());
+/// ```
+pub fn slice<S: BaseFloat>(
+    triangles: &[Triangle3<S>],
+    plane: &Plane<S>,
+    epsilon: S,
+) -> Vec<Polyline3<S>> {
+    // The segments, with each one going from where the triangle boundary
+    // leaves the positive side to where it enters it, so that triangles
+    // sharing an edge give segments that follow each other.
+    let mut ends = Vec::new();
+    for triangle in triangles {
+        let v = [triangle.a, triangle.b, triangle.c];
+        let d = [
+            plane.distance(v[0]),
+            plane.distance(v[1]),
+            plane.distance(v[2]),
+        ];
+        let (mut entering, mut leaving) = (None, None);
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            let (above_i, above_j) = (d[i] >= S::zero(), d[j] >= S::zero());
+            if above_i != above_j {
+                let p = v[i] + (v[j] - v[i]) * (d[i] / (d[i] - d[j]));
+                if above_j {
+                    entering = Some(p);
+                } else {
+                    leaving = Some(p);
+                }
+            }
+        }
+        if let (Some(start), Some(end)) = (leaving, entering) {
+            ends.push(start);
+            ends.push(end);
+        }
+    }
+    let (points, remap) = weld(&ends, epsilon);
+
+    let mut outgoing = vec![Vec::new(); points.len()];
+    let mut incoming = vec![0; points.len()];
+    for pair in remap.chunks(2) {
+        if pair[0] != pair[1] {
+            outgoing[pair[0]].push(pair[1]);
+            incoming[pair[1]] += 1;
+        }
+    }
+    // Open polylines start where no segment ends, then only loops remain.
+    let starts = (0..points.len())
+        .filter(|&i| incoming[i] == 0)
+        .chain(0..points.len())
+        .collect::<Vec<_>>();
+    let mut polylines = Vec::new();
+    for start in starts {
+        let mut current = start;
+        let mut polyline = vec![points[start]];
+        while let Some(next) = outgoing[current].pop() {
+            polyline.push(points[next]);
+            current = next;
+        }
+        if polyline.len() > 1 {
+            polylines.push(Polyline3::new(polyline));
+        }
+    }
+    polylines
+}
+
+/// The candidate with the largest positive key, if any.
+fn farthest<S: BaseFloat, I, F>(candidates: I, key: F) -> Option<usize>
+where
+    I: Iterator<Item = usize>,
+    F: Fn(usize) -> S,
+{
+    let mut best = None;
+    for i in candidates {
+        let k = key(i);
+        match best {
+            Some((_, b)) if b >= k => {}
+            _ if k > S::zero() => best = Some((i, k)),
+            _ => {}
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// A face of a convex hull under construction, with the points that are
+/// outside of it and not yet assigned to another face.
+struct HullFace {
+    vertices: [usize; 3],
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+/// The convex hull of a set of points, as triangles of indices into it.
+///
+/// The triangles are counterclockwise seen from the outside of the hull.
+/// Points inside the hull or in the plane of one of its faces are left out,
+/// but a face may be split into several coplanar triangles. Returns no
+/// triangles if the points are fewer than four, or all in the same plane.
+///
+/// This uses the quickhull algorithm of Barber et al., with the exact
+/// orientation predicate, so that the hull is always convex.
+///
+/// ```rust
+/// use cgmath::mesh::{centroid, convex_hull, volume};
+/// use cgmath::{Point3, Triangle3};
+///
+/// // The corners of a cube, and its center.
+/// let mut points: Vec<_> = (0..8)
+///     .map(|i| Point3::new((i & 1) as f64, (i >> 1 & 1) as f64, (i >> 2) as f64))
+///     .collect();
+/// points.push(Point3::new(0.5, 0.5, 0.5));
+///
+/// let hull = convex_hull(&points);
+/// assert_eq!(hull.len(), 12);
+/// assert!(hull.iter().all(|t| !t.contains(&8)));
+///
+/// let triangles: Vec<_> = hull
+///     .iter()
+///     .map(|t| Triangle3::new(points[t[0]], points[t[1]], points[t[2]]))
+///     .collect();
+/// assert_eq!(volume(&triangles), 1.0);
+/// assert_eq!(centroid(&triangles), Some(Point3::new(0.5, 0.5, 0.5)));
+/// ```
+pub fn convex_hull<S: BaseFloat>(points: &[Point3<S>]) -> Vec<[usize; 3]> {
+    let orient =
+        |f: [usize; 3], p: usize| orient3d(points[f[0]], points[f[1]], points[f[2]], points[p]);
+
+    // The initial tetrahedron, from points that are far apart.
+    let a = match (0..points.len()).min_by(|&i, &j| points[i].x.partial_cmp(&points[j].x).unwrap())
+    {
+        Some(a) => a,
+        None => return Vec::new(),
+    };
+    let b = farthest(0..points.len(), |i| points[i].distance2(points[a]));
+    let c = b.and_then(|b| {
+        let line = points[b] - points[a];
+        farthest(0..points.len(), |i| {
+            (points[i] - points[a]).cross(line).magnitude2()
+        })
+    });
+    let (b, c) = match (b, c) {
+        (Some(b), Some(c)) => (b, c),
+        _ => return Vec::new(),
+    };
+    let d = farthest(0..points.len(), |i| orient([a, b, c], i).abs());
+    let d = match d {
+        Some(d) => d,
+        None => return Vec::new(),
+    };
+    // Orient the base so that the apex is below it.
+    let [a, b, c] = if orient([a, b, c], d) < S::zero() {
+        [a, c, b]
+    } else {
+        [a, b, c]
+    };
+    let mut faces: Vec<HullFace> = [[a, b, c], [b, a, d], [c, b, d], [a, c, d]]
+        .iter()
+        .map(|&vertices| HullFace {
+            vertices,
+            outside: Vec::new(),
+            alive: true,
+        })
+        .collect();
+    let assign = |faces: &mut [HullFace], p: usize| {
+        if let Some(face) = faces.iter_mut().find(|f| orient(f.vertices, p) < S::zero()) {
+            face.outside.push(p);
+        }
+    };
+    for p in 0..points.len() {
+        if p != a && p != b && p != c && p != d {
+            assign(&mut faces, p);
+        }
+    }
+
+    // Add the farthest outside point of a face until no points are left
+    // outside, replacing the faces it sees with a cone from their horizon.
+    while let Some(f) = faces.iter().position(|f| f.alive && !f.outside.is_empty()) {
+        let vertices = faces[f].vertices;
+        let apex = farthest(faces[f].outside.iter().cloned(), |p| -orient(vertices, p)).unwrap();
+        let visible: Vec<usize> = (0..faces.len())
+            .filter(|&i| faces[i].alive && orient(faces[i].vertices, apex) < S::zero())
+            .collect();
+        let mut edges = Vec::new();
+        let mut orphans = Vec::new();
+        for &i in &visible {
+            let v = faces[i].vertices;
+            edges.extend_from_slice(&[(v[0], v[1]), (v[1], v[2]), (v[2], v[0])]);
+            orphans.append(&mut faces[i].outside);
+            faces[i].alive = false;
+        }
+        // The horizon is made of the edges of a single visible face, sorted
+        // so that the hull comes out the same on every run.
+        edges.sort();
+        edges.dedup();
+        let first = faces.len();
+        for &(u, v) in &edges {
+            if edges.binary_search(&(v, u)).is_err() {
+                faces.push(HullFace {
+                    vertices: [u, v, apex],
+                    outside: Vec::new(),
+                    alive: true,
+                });
+            }
+        }
+        for p in orphans {
+            if p != apex {
+                assign(&mut faces[first..], p);
+            }
+        }
+    }
+    faces
+        .into_iter()
+        .filter(|f| f.alive)
+        .map(|f| f.vertices)
+        .collect()
+}
+
+/// The volume enclosed by a closed mesh whose triangles are counterclockwise
+/// seen from the outside, from the divergence theorem.
+///
+/// The volume is negative if the triangles are clockwise instead.
+pub fn volume<S: BaseFloat>(triangles: &[Triangle3<S>]) -> S {
+    let sixth: S = cast(1.0 / 6.0).unwrap();
+    triangles.iter().fold(S::zero(), |sum, t| {
+        sum + t.a.to_vec().dot(t.b.to_vec().cross(t.c.to_vec()))
+    }) * sixth
+}
+
+/// The centroid of the volume enclosed by a closed mesh, or `None` if that
+/// volume is zero.
+///
+/// This sums the centroids of the tetrahedra joining the origin to each
+/// triangle, weighted by their signed volumes.
+pub fn centroid<S: BaseFloat>(triangles: &[Triangle3<S>]) -> Option<Point3<S>> {
+    let (moment, volume) =
+        triangles
+            .iter()
+            .fold((Vector3::zero(), S::zero()), |(moment, volume), t| {
+                let (a, b, c) = (t.a.to_vec(), t.b.to_vec(), t.c.to_vec());
+                let v = a.dot(b.cross(c));
+                (moment + (a + b + c) * v, volume + v)
+            });
+    if volume == S::zero() {
+        return None;
+    }
+    let four: S = cast(4).unwrap();
+    Some(Point3::from_vec(moment / (volume * four)))
+}
+
+/// The mass properties of a solid of unit density, as computed by
+/// `mass_properties`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MassProperties<S> {
+    /// The volume, which is also the mass.
+    pub volume: S,
+    /// The center of mass.
+    pub centroid: Point3<S>,
+    /// The inertia tensor about the center of mass, in the axes of the mesh.
+    pub inertia: Matrix3<S>,
+}
+
+/// The volume, center of mass and inertia tensor of the solid enclosed by a
+/// closed mesh whose triangles are counterclockwise seen from the outside,
+/// for a unit density, or `None` if the volume is zero.
+///
+/// For another density, the inertia tensor and the mass are scaled by it.
+/// This integrates over the tetrahedra joining the origin to each triangle,
+/// from the divergence theorem, with the covariance method of "Computing the
+/// Inertia Tensor of a Tetrahedron" by Tonon.
+///
+/// ```rust
+/// # #[macro_use] extern crate approx;
+/// # extern crate cgmath;
+/// # fn main() {
+/// use cgmath::mesh::{convex_hull, mass_properties};
+/// use cgmath::{Matrix3, Point3, Triangle3};
+///
+/// // A box of two by one by one units.
+/// let points: Vec<_> = (0..8)
+///     .map(|i| Point3::new((i & 1) as f64 * 2.0, (i >> 1 & 1) as f64, (i >> 2) as f64))
+///     .collect();
+/// let triangles: Vec<_> = convex_hull(&points)
+///     .iter()
+///     .map(|t| Triangle3::new(points[t[0]], points[t[1]], points[t[2]]))
+///     .collect();
+///
+/// let properties = mass_properties(&triangles).unwrap();
+/// assert_eq!(properties.volume, 2.0);
+/// assert_eq!(properties.centroid, Point3::new(1.0, 0.5, 0.5));
+/// // The moments of a box, `m (b² + c²) / 12` and so on.
+/// let expected = Matrix3::new(1.0 / 3.0, 0.0, 0.0, 0.0, 5.0 / 6.0, 0.0, 0.0, 0.0, 5.0 / 6.0);
+/// assert_relative_eq!(properties.inertia, expected, epsilon = 1e-12);
+/// # }
+/// ```
+pub fn mass_properties<S: BaseFloat>(triangles: &[Triangle3<S>]) -> Option<MassProperties<S>> {
+    // The covariance of the tetrahedron from the origin to the unit axes,
+    // divided by its determinant.
+    let (diagonal, off): (S, S) = (cast(1.0 / 60.0).unwrap(), cast(1.0 / 120.0).unwrap());
+    let canonical = Matrix3::new(diagonal, off, off, off, diagonal, off, off, off, diagonal);
+    let mut volume = S::zero();
+    let mut moment = Vector3::zero();
+    let mut covariance = Matrix3::zero();
+    for t in triangles {
+        let (a, b, c) = (t.a.to_vec(), t.b.to_vec(), t.c.to_vec());
+        let d = a.dot(b.cross(c));
+        let m = Matrix3::from_cols(a, b, c);
+        volume += d;
+        moment += (a + b + c) * d;
+        covariance += m * canonical * m.transpose() * d;
+    }
+    if volume == S::zero() {
+        return None;
+    }
+    let four: S = cast(4).unwrap();
+    let centroid = moment / (volume * four);
+    volume /= cast(6).unwrap();
+    // Move the covariance to the center of mass, then turn it into the
+    // inertia tensor.
+    let outer = Matrix3::from_cols(
+        centroid * centroid.x,
+        centroid * centroid.y,
+        centroid * centroid.z,
+    );
+    let covariance = covariance - outer * volume;
+    let trace = covariance.trace();
+    Some(MassProperties {
+        volume,
+        centroid: Point3::from_vec(centroid),
+        inertia: Matrix3::from_value(trace) - covariance,
+    })
+}
+
+/// The angles of a triangle at each of its corners, or `None` if one of its
+/// edges has zero length.
+fn corner_angles<S: BaseFloat>(p: [Point3<S>; 3]) -> Option<[S; 3]> {
+    let edges = [p[1] - p[0], p[2] - p[1], p[0] - p[2]];
+    if edges.iter().any(|e| e.magnitude2() == S::zero()) {
+        return None;
+    }
+    let angle = |i: usize| edges[i].angle(-edges[(i + 2) % 3]).0;
+    Some([angle(0), angle(1), angle(2)])
+}
+
+/// The normals at the vertices of an indexed mesh, averaging the normals of
+/// the triangles around each vertex weighted by their angles at it.
+///
+/// Weighting by angles keeps the normals independent of how the faces
+/// around a vertex are split into triangles, as described in "Computing
+/// Vertex Normals from Polygonal Facets" by Thürmer and Wüthrich. The
+/// normals follow the right-hand rule over the vertices of the triangles.
+/// Vertices that are not used by any triangle of non-zero area get a zero
+/// vector.
+///
+/// ```rust
+/// use cgmath::mesh::vertex_normals;
+/// use cgmath::{Point3, Vector3};
+///
+/// // A tent, with its ridge along the x axis.
+/// let positions = [
+///     Point3::new(0.0, -1.0, 0.0),
+///     Point3::new(2.0, -1.0, 0.0),
+///     Point3::new(0.0, 0.0, 1.0),
+///     Point3::new(2.0, 0.0, 1.0),
+///     Point3::new(0.0, 1.0, 0.0),
+///     Point3::new(2.0, 1.0, 0.0f64),
+/// ];
+/// let indices = [[0, 1, 3], [0, 3, 2], [2, 3, 5], [2, 5, 4]];
+/// let normals = vertex_normals(&positions, &indices);
+/// assert_eq!(normals[3], Vector3::unit_z());
+/// ```
+pub fn vertex_normals<S: BaseFloat>(
+    positions: &[Point3<S>],
+    indices: &[[usize; 3]],
+) -> Vec<Vector3<S>> {
+    let mut normals = vec![Vector3::zero(); positions.len()];
+    for t in indices {
+        let p = [positions[t[0]], positions[t[1]], positions[t[2]]];
+        let normal = (p[1] - p[0]).cross(p[2] - p[0]);
+        if let (Some(angles), false) = (corner_angles(p), normal.magnitude2() == S::zero()) {
+            let normal = normal.normalize();
+            for i in 0..3 {
+                normals[t[i]] += normal * angles[i];
+            }
+        }
+    }
+    for n in &mut normals {
+        if n.magnitude2() > S::zero() {
+            *n = n.normalize();
+        }
+    }
+    normals
+}
+
+/// The tangents at the vertices of an indexed mesh, following the texture
+/// coordinates, with the handedness of the tangent frame in `w`.
+///
+/// The tangent and bitangent of each triangle point along increasing `u`
+/// and `v` texture coordinates. As in MikkTSpace, their directions are
+/// averaged at each vertex weighted by the angles of the triangles, then the
+/// tangent is made perpendicular to the normal. The `w` component is `1` if
+/// the bitangent is `normal × tangent`, and `-1` if the texture is mirrored
+/// and it is the opposite. Vertices without usable texture coordinates get
+/// an arbitrary tangent perpendicular to their normal.
+///
+/// Unlike MikkTSpace, vertices are not split where the tangent frames of
+/// their triangles disagree, so vertices along texture seams should already
+/// be duplicated.
+///
+/// ```rust
+/// use cgmath::mesh::{vertex_normals, vertex_tangents};
+/// use cgmath::{Point2, Point3, Vector4};
+///
+/// // A quad in the xy plane, with its texture upright.
+/// let positions = [
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(2.0, 0.0, 0.0),
+///     Point3::new(2.0, 2.0, 0.0),
+///     Point3::new(0.0, 2.0, 0.0f64),
+/// ];
+/// let uvs = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(1.0, 0.0),
+///     Point2::new(1.0, 1.0),
+///     Point2::new(0.0, 1.0),
+/// ];
+/// let indices = [[0, 1, 2], [0, 2, 3]];
+/// let normals = vertex_normals(&positions, &indices);
+/// let tangents = vertex_tangents(&positions, &normals, &uvs, &indices);
+/// assert!(tangents.iter().all(|&t| t == Vector4::new(1.0, 0.0, 0.0, 1.0)));
+/// ```
+pub fn vertex_tangents<S: BaseFloat>(
+    positions: &[Point3<S>],
+    normals: &[Vector3<S>],
+    uvs: &[Point2<S>],
+    indices: &[[usize; 3]],
+) -> Vec<Vector4<S>> {
+    let mut tangents = vec![Vector3::zero(); positions.len()];
+    let mut bitangents = vec![Vector3::zero(); positions.len()];
+    for t in indices {
+        let p = [positions[t[0]], positions[t[1]], positions[t[2]]];
+        let (e1, e2) = (p[1] - p[0], p[2] - p[0]);
+        let (d1, d2) = (uvs[t[1]] - uvs[t[0]], uvs[t[2]] - uvs[t[0]]);
+        let det = d1.x * d2.y - d2.x * d1.y;
+        let angles = match corner_angles(p) {
+            Some(angles) if det != S::zero() => angles,
+            _ => continue,
+        };
+        let tangent = (e1 * d2.y - e2 * d1.y) / det;
+        let bitangent = (e2 * d1.x - e1 * d2.x) / det;
+        for i in 0..3 {
+            tangents[t[i]] += tangent.normalize() * angles[i];
+            bitangents[t[i]] += bitangent.normalize() * angles[i];
+        }
+    }
+    tangents
+        .iter()
+        .zip(&bitangents)
+        .zip(normals)
+        .map(|((&t, &b), &n)| {
+            let t = t - n * n.dot(t);
+            let t = if t.magnitude2() > S::zero() {
+                t.normalize()
+            } else if n.magnitude2() > S::zero() {
+                any_perpendicular(n)
+            } else {
+                Vector3::zero()
+            };
+            let w = if n.cross(t).dot(b) < S::zero() {
+                -S::one()
+            } else {
+                S::one()
+            };
+            t.extend(w)
+        })
+        .collect()
+}
+
+/// A quadric error metric, measuring the sum of the squared distances from a
+/// point to a set of planes, as used for mesh simplification in "Surface
+/// Simplification Using Quadric Error Metrics" by Garland and Heckbert.
+///
+/// Quadrics are added together as the vertices they belong to are merged.
+///
+/// ```rust
+/// use cgmath::mesh::Quadric;
+/// use cgmath::{Plane, Point3, Vector3};
+///
+/// // The corner where three faces of a box meet.
+/// let corner = Quadric::from_plane(&Plane::new(Vector3::unit_x(), 1.0))
+///     + Quadric::from_plane(&Plane::new(Vector3::unit_y(), 2.0))
+///     + Quadric::from_plane(&Plane::new(Vector3::unit_z(), 3.0));
+/// assert_eq!(corner.error(Point3::new(1.0, 2.0, 5.0)), 4.0);
+/// assert_eq!(corner.optimal_point(1e-8), Some(Point3::new(1.0, 2.0, 3.0)));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quadric<S> {
+    /// The symmetric matrix of the quadric, whose quadratic form over the
+    /// homogeneous coordinates of a point is its error.
+    pub matrix: Matrix4<S>,
+}
+
+impl<S: BaseFloat> Quadric<S> {
+    /// The quadric whose error is zero everywhere.
+    #[inline]
+    pub fn zero() -> Quadric<S> {
+        Quadric {
+            matrix: Matrix4::zero(),
+        }
+    }
+
+    /// The quadric of the squared distance to a plane.
+    pub fn from_plane(plane: &Plane<S>) -> Quadric<S> {
+        let v = plane.to_vector4();
+        Quadric {
+            matrix: Matrix4::from_cols(v * v.x, v * v.y, v * v.z, v * v.w),
+        }
+    }
+
+    /// The quadric of the squared distance to the plane of a triangle,
+    /// weighted by its area so that small triangles count less, or zero if
+    /// the triangle is degenerate.
+    pub fn from_triangle(triangle: &Triangle3<S>) -> Quadric<S> {
+        match Plane::from_points(triangle.a, triangle.b, triangle.c) {
+            Some(plane) => Quadric::from_plane(&plane) * triangle.area(),
+            None => Quadric::zero(),
+        }
+    }
+
+    /// The error of a point, the sum of its squared distances to the planes
+    /// of the quadric.
+    #[inline]
+    pub fn error(&self, p: Point3<S>) -> S {
+        let h = p.to_homogeneous();
+        h.dot(self.matrix * h)
+    }
+
+    /// The point with the smallest error, or `None` if it is not unique, as
+    /// when all the planes are parallel or meet along a line, or too close
+    /// to that to be computed accurately. The linear system of the quadric
+    /// is positive semi-definite, and it is taken as singular when its
+    /// determinant is at most `tolerance` times the cube of its trace.
+    ///
+    /// Quadrics of nearly flat regions may still give points far away from
+    /// their planes, so simplification usually falls back to
+    /// `optimal_point_on_segment` when the point is too far from the
+    /// collapsed edge.
+    pub fn optimal_point(&self, tolerance: S) -> Option<Point3<S>> {
+        let m = &self.matrix;
+        let a = Matrix3::from_cols(m.x.truncate(), m.y.truncate(), m.z.truncate());
+        let trace = a.trace();
+        if a.determinant() <= trace * trace * trace * tolerance {
+            return None;
+        }
+        a.invert()
+            .map(|inverse| Point3::from_vec(-(inverse * m.w.truncate())))
+    }
+
+    /// The point of the segment from `a` to `b` with the smallest error.
+    pub fn optimal_point_on_segment(&self, a: Point3<S>, b: Point3<S>) -> Point3<S> {
+        let (h, dh) = (a.to_homogeneous(), (b - a).extend(S::zero()));
+        let (slope, curvature) = (dh.dot(self.matrix * h), dh.dot(self.matrix * dh));
+        let t = if curvature > S::zero() {
+            (-slope / curvature).max(S::zero()).min(S::one())
+        } else {
+            // The error is the same along the whole segment.
+            cast(0.5).unwrap()
+        };
+        a + (b - a) * t
+    }
+}
+
+impl<S: BaseFloat> Zero for Quadric<S> {
+    #[inline]
+    fn zero() -> Quadric<S> {
+        Quadric::zero()
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.matrix.is_zero()
+    }
+}
+
+impl<S: BaseFloat> Add for Quadric<S> {
+    type Output = Quadric<S>;
+
+    #[inline]
+    fn add(self, other: Quadric<S>) -> Quadric<S> {
+        Quadric {
+            matrix: self.matrix + other.matrix,
+        }
+    }
+}
+
+impl<S: BaseFloat> AddAssign for Quadric<S> {
+    #[inline]
+    fn add_assign(&mut self, other: Quadric<S>) {
+        self.matrix += other.matrix;
+    }
+}
+
+impl<S: BaseFloat> Mul<S> for Quadric<S> {
+    type Output = Quadric<S>;
+
+    #[inline]
+    fn mul(self, scalar: S) -> Quadric<S> {
+        Quadric {
+            matrix: self.matrix * scalar,
+        }
+    }
+}
+
+/// The neighbours of each vertex of an indexed mesh, the other vertices it
+/// shares an edge with, in increasing order.
+pub fn vertex_neighbors(vertex_count: usize, indices: &[[usize; 3]]) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); vertex_count];
+    for t in indices {
+        for i in 0..3 {
+            let j = (i + 1) % 3;
+            if t[i] != t[j] {
+                neighbors[t[i]].push(t[j]);
+                neighbors[t[j]].push(t[i]);
+            }
+        }
+    }
+    for n in &mut neighbors {
+        n.sort_unstable();
+        n.dedup();
+    }
+    neighbors
+}
+
+/// Express a direction given in object space in the tangent space of a
+/// vertex with the given unit normal and tangent, as computed by
+/// `vertex_normals` and `vertex_tangents`, such as to bake normal maps.
+///
+/// The axes of tangent space are the tangent, the bitangent and the normal,
+/// so a direction along the normal becomes the `z` axis.
+pub fn to_tangent_space<S: BaseFloat>(
+    v: Vector3<S>,
+    normal: Vector3<S>,
+    tangent: Vector4<S>,
+) -> Vector3<S> {
+    let t = tangent.truncate();
+    let b = normal.cross(t) * tangent.w;
+    Vector3::new(v.dot(t), v.dot(b), v.dot(normal))
+}
+
+/// Move each point by `factor` of the way towards the centroid of its
+/// neighbours, all at once.
+fn smooth_step<S: BaseFloat>(points: &mut [Point3<S>], neighbors: &[Vec<usize>], factor: S) {
+    let original = points.to_vec();
+    for (p, n) in points.iter_mut().zip(neighbors) {
+        if !n.is_empty() {
+            let sum = n
+                .iter()
+                .fold(Vector3::zero(), |sum, &i| sum + original[i].to_vec());
+            let average = Point3::from_vec(sum / cast(n.len()).unwrap());
+            *p += (average - *p) * factor;
+        }
+    }
+}
+
+/// Smooth points by repeatedly moving each one by `lambda` of the way
+/// towards the centroid of its neighbours, with `neighbors` giving the
+/// indices of the neighbours of each point.
+///
+/// A `lambda` between zero and one smooths the points, removing noise but
+/// also shrinking them each iteration. Points without neighbours are left
+/// in place.
+///
+/// ```rust
+/// use cgmath::mesh::laplacian_smooth;
+/// use cgmath::Point3;
+///
+/// // A zigzag whose ends are pinned, having no neighbours.
+/// let mut points = vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 1.0, 0.0),
+///     Point3::new(2.0, -1.0, 0.0),
+///     Point3::new(3.0, 0.0, 0.0f64),
+/// ];
+/// let neighbors = vec![vec![], vec![0, 2], vec![1, 3], vec![]];
+/// laplacian_smooth(&mut points, &neighbors, 0.5, 50);
+/// assert!(points[1].y.abs() < 1e-6 && points[2].y.abs() < 1e-6);
+/// ```
+pub fn laplacian_smooth<S: BaseFloat>(
+    points: &mut [Point3<S>],
+    neighbors: &[Vec<usize>],
+    lambda: S,
+    iterations: usize,
+) {
+    for _ in 0..iterations {
+        smooth_step(points, neighbors, lambda);
+    }
+}
+
+/// Smooth points without shrinking them, alternating Laplacian steps of
+/// `lambda` with inflating steps of `mu`.
+///
+/// This is the method of "A Signal Processing Approach to Fair Surface
+/// Design" by Taubin, which acts as a low-pass filter. `mu` must be negative
+/// and slightly larger in magnitude than `lambda`, as in `0.5` and `-0.53`.
+pub fn taubin_smooth<S: BaseFloat>(
+    points: &mut [Point3<S>],
+    neighbors: &[Vec<usize>],
+    lambda: S,
+    mu: S,
+    iterations: usize,
+) {
+    for _ in 0..iterations {
+        smooth_step(points, neighbors, lambda);
+        smooth_step(points, neighbors, mu);
+    }
+}
@@ -0,0 +1,416 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Gradient noise, fractal Brownian motion, and deterministic per-cell
+//! random values over points.
+//!
+//! ```rust
+//! use cgmath::noise::{Fbm, Perlin};
+//! use cgmath::Point2;
+//!
+//! let perlin = Perlin::new(7);
+//! let height = perlin.sample2(Point2::new(3.2, -1.7));
+//! assert!(height > -1.0 && height < 1.0);
+//!
+//! // The same seed always produces the same noise.
+//! assert_eq!(height, Perlin::new(7).sample2(Point2::new(3.2, -1.7)));
+//!
+//! let terrain = Fbm::<f64>::new(7);
+//! let (height, slope) = terrain.sample2_deriv(Point2::new(3.2, -1.7));
+//! assert_eq!(height, terrain.sample2(Point2::new(3.2, -1.7)));
+//! assert!(slope.x.is_finite() && slope.y.is_finite());
+//! ```
+
+use num_traits::cast;
+
+use structure::*;
+
+use num::BaseFloat;
+use point::{Point2, Point3};
+use vector::{Vector2, Vector3};
+
+/// A fast 32-bit integer hash, based on the output permutation of the PCG
+/// random number generator.
+#[inline]
+pub fn pcg_hash(x: u32) -> u32 {
+    let state = x.wrapping_mul(747_796_405).wrapping_add(2_891_336_453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277_803_737);
+    (word >> 22) ^ word
+}
+
+/// Hash a point of the integer lattice in the plane together with a seed.
+#[inline]
+pub fn hash2(p: Point2<i32>, seed: u32) -> u32 {
+    let h = pcg_hash((p.y as u32).wrapping_add(pcg_hash(seed)));
+    pcg_hash((p.x as u32).wrapping_add(h))
+}
+
+/// Hash a point of the integer lattice in space together with a seed.
+#[inline]
+pub fn hash3(p: Point3<i32>, seed: u32) -> u32 {
+    let h = pcg_hash((p.z as u32).wrapping_add(pcg_hash(seed)));
+    hash2(Point2::new(p.x, p.y), h)
+}
+
+/// Convert a hash to a float uniformly distributed in `[0, 1)`.
+#[inline]
+fn unit_float<S: BaseFloat>(h: u32) -> S {
+    let scale: S = cast(1.0 / 16_777_216.0).unwrap();
+    S::from(h >> 8).unwrap() * scale
+}
+
+/// A deterministic pseudo-random number in `[0, 1)` for a cell of the
+/// integer lattice in the plane.
+///
+/// ```rust
+/// use cgmath::noise::random2;
+/// use cgmath::Point2;
+///
+/// let tile = Point2::new(12, -3);
+/// let r: f32 = random2(tile, 0);
+/// assert!(r >= 0.0 && r < 1.0);
+/// assert_eq!(r, random2(tile, 0));
+/// ```
+#[inline]
+pub fn random2<S: BaseFloat>(p: Point2<i32>, seed: u32) -> S {
+    unit_float(hash2(p, seed))
+}
+
+/// A deterministic pseudo-random number in `[0, 1)` for a cell of the
+/// integer lattice in space.
+#[inline]
+pub fn random3<S: BaseFloat>(p: Point3<i32>, seed: u32) -> S {
+    unit_float(hash3(p, seed))
+}
+
+/// A deterministic pseudo-random vector with components in `[-1, 1)` for a
+/// cell of the integer lattice in the plane.
+pub fn random_vector2<S: BaseFloat>(p: Point2<i32>, seed: u32) -> Vector2<S> {
+    let h0 = hash2(p, seed);
+    let h1 = pcg_hash(h0);
+    let two: S = cast(2).unwrap();
+    Vector2::new(unit_float::<S>(h0), unit_float::<S>(h1)) * two - Vector2::from_value(S::one())
+}
+
+/// A deterministic pseudo-random vector with components in `[-1, 1)` for a
+/// cell of the integer lattice in space.
+pub fn random_vector3<S: BaseFloat>(p: Point3<i32>, seed: u32) -> Vector3<S> {
+    let h0 = hash3(p, seed);
+    let h1 = pcg_hash(h0);
+    let h2 = pcg_hash(h1);
+    let two: S = cast(2).unwrap();
+    let v = Vector3::new(
+        unit_float::<S>(h0),
+        unit_float::<S>(h1),
+        unit_float::<S>(h2),
+    );
+    v * two - Vector3::from_value(S::one())
+}
+
+/// White noise in the plane, constant over each cell of the integer lattice.
+#[inline]
+pub fn white_noise2<S: BaseFloat>(p: Point2<S>, seed: u32) -> S {
+    random2(Point2::new(lattice(p.x).0, lattice(p.y).0), seed)
+}
+
+/// White noise in space, constant over each cell of the integer lattice.
+#[inline]
+pub fn white_noise3<S: BaseFloat>(p: Point3<S>, seed: u32) -> S {
+    let cell = Point3::new(lattice(p.x).0, lattice(p.y).0, lattice(p.z).0);
+    random3(cell, seed)
+}
+
+/// Split a coordinate into its lattice cell and the offset within the cell.
+#[inline]
+fn lattice<S: BaseFloat>(x: S) -> (i32, S) {
+    let floor = x.floor();
+    let cell: i64 = cast(floor).unwrap_or(0);
+    (cell as i32, x - floor)
+}
+
+/// The quintic smoothstep used to blend between lattice points, with its
+/// derivative.
+#[inline]
+fn fade<S: BaseFloat>(t: S) -> (S, S) {
+    let six: S = cast(6).unwrap();
+    let ten: S = cast(10).unwrap();
+    let fifteen: S = cast(15).unwrap();
+    let thirty: S = cast(30).unwrap();
+    let t1 = t - S::one();
+    (
+        t * t * t * (t * (t * six - fifteen) + ten),
+        thirty * t * t * t1 * t1,
+    )
+}
+
+fn gradient2<S: BaseFloat>(h: u32) -> Vector2<S> {
+    let (o, z) = (S::one(), S::zero());
+    match h & 7 {
+        0 => Vector2::new(o, z),
+        1 => Vector2::new(-o, z),
+        2 => Vector2::new(z, o),
+        3 => Vector2::new(z, -o),
+        4 => Vector2::new(o, o),
+        5 => Vector2::new(-o, o),
+        6 => Vector2::new(o, -o),
+        _ => Vector2::new(-o, -o),
+    }
+}
+
+fn gradient3<S: BaseFloat>(h: u32) -> Vector3<S> {
+    let (o, z) = (S::one(), S::zero());
+    match h % 12 {
+        0 => Vector3::new(o, o, z),
+        1 => Vector3::new(-o, o, z),
+        2 => Vector3::new(o, -o, z),
+        3 => Vector3::new(-o, -o, z),
+        4 => Vector3::new(o, z, o),
+        5 => Vector3::new(-o, z, o),
+        6 => Vector3::new(o, z, -o),
+        7 => Vector3::new(-o, z, -o),
+        8 => Vector3::new(z, o, o),
+        9 => Vector3::new(z, -o, o),
+        10 => Vector3::new(z, o, -o),
+        _ => Vector3::new(z, -o, -o),
+    }
+}
+
+/// Perlin gradient noise, with quintic interpolation between the lattice
+/// points.
+///
+/// The noise is zero at every integer lattice point, continuous with
+/// continuous first derivatives, and roughly within the `[-1, 1]` range.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Perlin {
+    /// The seed selecting the pseudo-random gradients.
+    pub seed: u32,
+}
+
+impl Perlin {
+    /// Create a noise generator with the given seed.
+    #[inline]
+    pub fn new(seed: u32) -> Perlin {
+        Perlin { seed }
+    }
+
+    #[inline]
+    fn hash2(&self, x: i32, y: i32) -> u32 {
+        hash2(Point2::new(x, y), self.seed)
+    }
+
+    #[inline]
+    fn hash3(&self, x: i32, y: i32, z: i32) -> u32 {
+        hash3(Point3::new(x, y, z), self.seed)
+    }
+
+    /// Sample the noise at a point in the plane.
+    #[inline]
+    pub fn sample2<S: BaseFloat>(&self, p: Point2<S>) -> S {
+        self.sample2_deriv(p).0
+    }
+
+    /// Sample the noise at a point in space.
+    #[inline]
+    pub fn sample3<S: BaseFloat>(&self, p: Point3<S>) -> S {
+        self.sample3_deriv(p).0
+    }
+
+    /// Sample the noise at a point in the plane, along with its analytic
+    /// gradient.
+    pub fn sample2_deriv<S: BaseFloat>(&self, p: Point2<S>) -> (S, Vector2<S>) {
+        let (ix, fx) = lattice(p.x);
+        let (iy, fy) = lattice(p.y);
+        let (u, du) = fade(fx);
+        let (v, dv) = fade(fy);
+        let o = S::one();
+
+        let g00 = gradient2::<S>(self.hash2(ix, iy));
+        let g10 = gradient2::<S>(self.hash2(ix.wrapping_add(1), iy));
+        let g01 = gradient2::<S>(self.hash2(ix, iy.wrapping_add(1)));
+        let g11 = gradient2::<S>(self.hash2(ix.wrapping_add(1), iy.wrapping_add(1)));
+
+        let n00 = g00.dot(Vector2::new(fx, fy));
+        let n10 = g10.dot(Vector2::new(fx - o, fy));
+        let n01 = g01.dot(Vector2::new(fx, fy - o));
+        let n11 = g11.dot(Vector2::new(fx - o, fy - o));
+
+        let k1 = n10 - n00;
+        let k2 = n01 - n00;
+        let k3 = n00 - n10 - n01 + n11;
+
+        let value = n00 + k1 * u + k2 * v + k3 * u * v;
+        let gradient = g00
+            + (g10 - g00) * u
+            + (g01 - g00) * v
+            + (g00 - g10 - g01 + g11) * (u * v)
+            + Vector2::new(du * (k1 + k3 * v), dv * (k2 + k3 * u));
+        (value, gradient)
+    }
+
+    /// Sample the noise at a point in space, along with its analytic
+    /// gradient.
+    pub fn sample3_deriv<S: BaseFloat>(&self, p: Point3<S>) -> (S, Vector3<S>) {
+        let (ix, fx) = lattice(p.x);
+        let (iy, fy) = lattice(p.y);
+        let (iz, fz) = lattice(p.z);
+        let (u, du) = fade(fx);
+        let (v, dv) = fade(fy);
+        let (w, dw) = fade(fz);
+        let o = S::one();
+        let (jx, jy, jz) = (ix.wrapping_add(1), iy.wrapping_add(1), iz.wrapping_add(1));
+
+        let g000 = gradient3::<S>(self.hash3(ix, iy, iz));
+        let g100 = gradient3::<S>(self.hash3(jx, iy, iz));
+        let g010 = gradient3::<S>(self.hash3(ix, jy, iz));
+        let g110 = gradient3::<S>(self.hash3(jx, jy, iz));
+        let g001 = gradient3::<S>(self.hash3(ix, iy, jz));
+        let g101 = gradient3::<S>(self.hash3(jx, iy, jz));
+        let g011 = gradient3::<S>(self.hash3(ix, jy, jz));
+        let g111 = gradient3::<S>(self.hash3(jx, jy, jz));
+
+        let n000 = g000.dot(Vector3::new(fx, fy, fz));
+        let n100 = g100.dot(Vector3::new(fx - o, fy, fz));
+        let n010 = g010.dot(Vector3::new(fx, fy - o, fz));
+        let n110 = g110.dot(Vector3::new(fx - o, fy - o, fz));
+        let n001 = g001.dot(Vector3::new(fx, fy, fz - o));
+        let n101 = g101.dot(Vector3::new(fx - o, fy, fz - o));
+        let n011 = g011.dot(Vector3::new(fx, fy - o, fz - o));
+        let n111 = g111.dot(Vector3::new(fx - o, fy - o, fz - o));
+
+        let k1 = n100 - n000;
+        let k2 = n010 - n000;
+        let k3 = n001 - n000;
+        let k4 = n000 - n100 - n010 + n110;
+        let k5 = n000 - n010 - n001 + n011;
+        let k6 = n000 - n100 - n001 + n101;
+        let k7 = -n000 + n100 + n010 - n110 + n001 - n101 - n011 + n111;
+
+        let value =
+            n000 + k1 * u + k2 * v + k3 * w + k4 * u * v + k5 * v * w + k6 * w * u + k7 * u * v * w;
+        let gradient = g000
+            + (g100 - g000) * u
+            + (g010 - g000) * v
+            + (g001 - g000) * w
+            + (g000 - g100 - g010 + g110) * (u * v)
+            + (g000 - g010 - g001 + g011) * (v * w)
+            + (g000 - g100 - g001 + g101) * (w * u)
+            + (-g000 + g100 + g010 - g110 + g001 - g101 - g011 + g111) * (u * v * w)
+            + Vector3::new(
+                du * (k1 + k4 * v + k6 * w + k7 * v * w),
+                dv * (k2 + k5 * w + k4 * u + k7 * w * u),
+                dw * (k3 + k6 * u + k5 * v + k7 * u * v),
+            );
+        (value, gradient)
+    }
+}
+
+/// Fractal Brownian motion, summing several octaves of Perlin noise at
+/// increasing frequencies and decreasing amplitudes.
+///
+/// Each octave uses a different seed, so that lattice artifacts do not line
+/// up between octaves.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fbm<S> {
+    /// The seed of the first octave.
+    pub seed: u32,
+    /// The number of octaves to sum.
+    pub octaves: u32,
+    /// The frequency multiplier between successive octaves.
+    pub lacunarity: S,
+    /// The amplitude multiplier between successive octaves.
+    pub gain: S,
+}
+
+impl<S: BaseFloat> Fbm<S> {
+    /// Create a fractal noise generator with six octaves, a lacunarity of two
+    /// and a gain of one half.
+    pub fn new(seed: u32) -> Fbm<S> {
+        Fbm {
+            seed,
+            octaves: 6,
+            lacunarity: cast(2).unwrap(),
+            gain: cast(0.5).unwrap(),
+        }
+    }
+
+    /// Sample the noise at a point in the plane.
+    #[inline]
+    pub fn sample2(&self, p: Point2<S>) -> S {
+        self.sample2_deriv(p).0
+    }
+
+    /// Sample the noise at a point in space.
+    #[inline]
+    pub fn sample3(&self, p: Point3<S>) -> S {
+        self.sample3_deriv(p).0
+    }
+
+    /// Sample the noise at a point in the plane, along with its analytic
+    /// gradient.
+    pub fn sample2_deriv(&self, p: Point2<S>) -> (S, Vector2<S>) {
+        let (mut value, mut gradient) = (S::zero(), Vector2::zero());
+        let (mut frequency, mut amplitude) = (S::one(), S::one());
+        for octave in 0..self.octaves {
+            let noise = Perlin::new(self.seed.wrapping_add(octave));
+            let (n, dn) = noise.sample2_deriv(p * frequency);
+            value += n * amplitude;
+            gradient += dn * (amplitude * frequency);
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        (value, gradient)
+    }
+
+    /// Sample the noise at a point in space, along with its analytic
+    /// gradient.
+    pub fn sample3_deriv(&self, p: Point3<S>) -> (S, Vector3<S>) {
+        let (mut value, mut gradient) = (S::zero(), Vector3::zero());
+        let (mut frequency, mut amplitude) = (S::one(), S::one());
+        for octave in 0..self.octaves {
+            let noise = Perlin::new(self.seed.wrapping_add(octave));
+            let (n, dn) = noise.sample3_deriv(p * frequency);
+            value += n * amplitude;
+            gradient += dn * (amplitude * frequency);
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        (value, gradient)
+    }
+
+    /// The curl of the noise seen as a stream function, at a point in the
+    /// plane, which is a field without divergence swirling around the
+    /// extrema of the noise, as used for turbulence.
+    pub fn curl2(&self, p: Point2<S>) -> Vector2<S> {
+        let (_, gradient) = self.sample2_deriv(p);
+        Vector2::new(gradient.y, -gradient.x)
+    }
+
+    /// The curl of a vector potential made of three independent noises, at a
+    /// point in space, which is a field without divergence, as used for
+    /// turbulence.
+    pub fn curl3(&self, p: Point3<S>) -> Vector3<S> {
+        let potential = |k: u32| {
+            let noise = Fbm {
+                seed: pcg_hash(self.seed.wrapping_add(k)),
+                ..*self
+            };
+            noise.sample3_deriv(p).1
+        };
+        let (a, b, c) = (potential(1), potential(2), potential(3));
+        Vector3::new(c.y - b.z, a.z - c.x, b.x - a.y)
+    }
+}
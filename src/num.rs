@@ -69,3 +69,16 @@ impl<T> BaseFloat for T where
         + approx::UlpsEq<Epsilon = Self>
 {
 }
+
+/// Lossy conversion of double precision scalars to single precision, used by
+/// the `to_f32` methods.
+pub(crate) trait ToF32 {
+    fn to_f32(self) -> f32;
+}
+
+impl ToF32 for f64 {
+    #[inline]
+    fn to_f32(self) -> f32 {
+        self as f32
+    }
+}
@@ -0,0 +1,372 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::mem;
+
+use structure::*;
+
+use aabb::{box_edges, Aabb2, Aabb3};
+use angle::Rad;
+use approx;
+use matrix::{Matrix3, Matrix4};
+use num::BaseFloat;
+use point::{Point2, Point3};
+use triangle::Triangle3;
+use vector::{Vector2, Vector3};
+
+/// A two-dimensional oriented bounding box, such as the bounds of a rotated
+/// sprite.
+///
+/// The box is described by its center, its half size along each of its
+/// axes, and the counterclockwise angle from the `x` axis to its first
+/// axis.
+///
+/// ```rust
+/// use cgmath::{Aabb2, Deg, Obb2, Point2, Vector2};
+///
+/// let sprite = Obb2::new(Point2::new(0.0, 0.0), Vector2::new(2.0, 0.5), Deg(45.0f64).into());
+/// assert!(sprite.contains_point(Point2::new(1.0, 1.0)));
+/// assert!(!sprite.contains_point(Point2::new(1.0, -1.0)));
+///
+/// let wall = Aabb2::new(Point2::new(1.2, 1.5), Point2::new(3.0, 3.0));
+/// assert!(sprite.intersects_aabb(&wall));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Obb2<S> {
+    /// The center of the box.
+    pub center: Point2<S>,
+    /// Half the size of the box along each of its axes.
+    pub half_extents: Vector2<S>,
+    /// The counterclockwise rotation of the box.
+    pub angle: Rad<S>,
+}
+
+impl<S: BaseFloat> Obb2<S> {
+    /// Construct an oriented box from its center, its half size along each
+    /// axis, and its rotation.
+    #[inline]
+    pub fn new(center: Point2<S>, half_extents: Vector2<S>, angle: Rad<S>) -> Obb2<S> {
+        Obb2 {
+            center,
+            half_extents,
+            angle,
+        }
+    }
+
+    /// The oriented box covering the same space as an axis-aligned box.
+    #[inline]
+    pub fn from_aabb(aabb: &Aabb2<S>) -> Obb2<S> {
+        let half = S::one() / (S::one() + S::one());
+        Obb2::new(aabb.center(), aabb.dim() * half, Rad(S::zero()))
+    }
+
+    /// The two unit axes of the box.
+    #[inline]
+    pub fn axes(&self) -> [Vector2<S>; 2] {
+        let (s, c) = Rad::sin_cos(self.angle);
+        [Vector2::new(c, s), Vector2::new(-s, c)]
+    }
+
+    /// The four corners of the box, in counter-clockwise order starting
+    /// from the negative side of both axes.
+    pub fn corners(&self) -> [Point2<S>; 4] {
+        let [u, v] = self.axes();
+        let (u, v) = (u * self.half_extents.x, v * self.half_extents.y);
+        let c = self.center;
+        [c - u - v, c + u - v, c + u + v, c - u + v]
+    }
+
+    /// Test whether the point is inside the box or on its boundary.
+    #[inline]
+    pub fn contains_point(&self, p: Point2<S>) -> bool {
+        let [u, v] = self.axes();
+        let d = p - self.center;
+        d.dot(u).abs() <= self.half_extents.x && d.dot(v).abs() <= self.half_extents.y
+    }
+
+    /// Test whether this box and `other` overlap, or touch, with the
+    /// separating axis theorem.
+    pub fn intersects_obb(&self, other: &Obb2<S>) -> bool {
+        let (a, b) = (self.axes(), other.axes());
+        let d = other.center - self.center;
+        // The half length of the shadow of a box on an axis.
+        let radius = |axes: &[Vector2<S>; 2], e: Vector2<S>, axis: Vector2<S>| {
+            e.x * axes[0].dot(axis).abs() + e.y * axes[1].dot(axis).abs()
+        };
+        a.iter().chain(b.iter()).all(|&axis| {
+            let reach = radius(&a, self.half_extents, axis) + radius(&b, other.half_extents, axis);
+            d.dot(axis).abs() <= reach
+        })
+    }
+
+    /// Test whether this box and an axis-aligned box overlap, or touch.
+    #[inline]
+    pub fn intersects_aabb(&self, aabb: &Aabb2<S>) -> bool {
+        self.intersects_obb(&Obb2::from_aabb(aabb))
+    }
+
+    /// The smallest axis-aligned box containing this box.
+    pub fn to_aabb(&self) -> Aabb2<S> {
+        let [u, v] = self.axes();
+        let e = self.half_extents;
+        let reach = Vector2::new(
+            e.x * u.x.abs() + e.y * v.x.abs(),
+            e.x * u.y.abs() + e.y * v.y.abs(),
+        );
+        Aabb2 {
+            min: self.center - reach,
+            max: self.center + reach,
+        }
+    }
+}
+
+/// A three-dimensional oriented bounding box.
+///
+/// The box is described by its center, its half size along each of its
+/// axes, and a rotation matrix whose columns are its axes.
+///
+/// ```rust
+/// use cgmath::{Deg, Matrix3, Obb3, Point3, Vector3};
+///
+/// let obb = Obb3::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Vector3::new(2.0, 1.0, 1.0),
+///     Matrix3::from_angle_z(Deg(90.0f64)),
+/// );
+/// let corners = obb.corners();
+/// assert!((corners[7].x + 1.0).abs() < 1e-12);
+/// assert!((corners[7].y - 2.0).abs() < 1e-12);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Obb3<S> {
+    /// The center of the box.
+    pub center: Point3<S>,
+    /// Half the size of the box along each of its axes.
+    pub half_extents: Vector3<S>,
+    /// The axes of the box, as the columns of a rotation matrix.
+    pub axes: Matrix3<S>,
+}
+
+impl<S: BaseFloat> Obb3<S> {
+    /// Construct an oriented box from its center, its half size along each
+    /// axis, and its axes, which must be orthonormal.
+    #[inline]
+    pub fn new(center: Point3<S>, half_extents: Vector3<S>, axes: Matrix3<S>) -> Obb3<S> {
+        Obb3 {
+            center,
+            half_extents,
+            axes,
+        }
+    }
+
+    /// The oriented box covering the same space as an axis-aligned box.
+    #[inline]
+    pub fn from_aabb(aabb: &Aabb3<S>) -> Obb3<S> {
+        let half = S::one() / (S::one() + S::one());
+        Obb3::new(aabb.center(), aabb.dim() * half, Matrix3::one())
+    }
+
+    /// The eight corners of the box, where the bits of the index of a corner
+    /// select the positive side along the first, second and third axes.
+    pub fn corners(&self) -> [Point3<S>; 8] {
+        let mut corners = [self.center; 8];
+        for (i, corner) in corners.iter_mut().enumerate() {
+            let sign = |bit: usize, h: S| if i & bit == 0 { -h } else { h };
+            let e = self.half_extents;
+            let offset = Vector3::new(sign(1, e.x), sign(2, e.y), sign(4, e.z));
+            *corner += self.axes * offset;
+        }
+        corners
+    }
+
+    /// Test whether the point is inside the box or on its boundary.
+    #[inline]
+    pub fn contains_point(&self, p: Point3<S>) -> bool {
+        let local = self.axes.transpose() * (p - self.center);
+        let e = self.half_extents;
+        local.x.abs() <= e.x && local.y.abs() <= e.y && local.z.abs() <= e.z
+    }
+
+    /// Create line segments along the twelve edges of the box.
+    pub fn to_wireframe_lines(&self) -> Vec<(Point3<S>, Point3<S>)> {
+        box_edges(&self.corners())
+    }
+
+    /// The matrix taking world positions to the coordinates of the box
+    /// scaled to the unit cube, as used to project decals.
+    ///
+    /// The first two coordinates are the texture coordinates of the decal,
+    /// running from `0` to `1` along the first two axes of the box, and the
+    /// third one is the depth in the box along its third axis, which can be
+    /// used to fade the decal out towards the ends of the projector.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Matrix3, Obb3, Point3, Vector3};
+    ///
+    /// let size = Vector3::new(0.5, 0.5, 1.0);
+    /// let decal = Obb3::new(Point3::new(1.0, 0.0, 0.0), size, Matrix3::identity());
+    /// let m = decal.to_unit_cube_matrix();
+    /// assert_eq!(m.transform_point(Point3::new(1.5, -0.5, 0.0)), Point3::new(1.0, 0.0, 0.5));
+    /// ```
+    pub fn to_unit_cube_matrix(&self) -> Matrix4<S> {
+        let half = S::one() / (S::one() + S::one());
+        let e = self.half_extents;
+        let a = self.axes;
+        let m = Matrix3::from_cols(a.x * (half / e.x), a.y * (half / e.y), a.z * (half / e.z))
+            .transpose();
+        let t = Vector3::from_value(half) - m * self.center.to_vec();
+        Matrix4::from_mat3_translation(m, t)
+    }
+
+    /// Clip a triangle against the box, returning the vertices of the
+    /// convex polygon where they overlap, in the winding order of the
+    /// triangle, or an empty list if they do not overlap.
+    ///
+    /// The polygon has at most nine vertices, and can be triangulated as a
+    /// fan around its first vertex. This is how decals cut the geometry
+    /// they are projected onto.
+    pub fn clip_triangle(&self, triangle: &Triangle3<S>) -> Vec<Point3<S>> {
+        let inverse = self.axes.transpose();
+        let to_local = |p: Point3<S>| inverse * (p - self.center);
+        let mut polygon = vec![to_local(triangle.a), to_local(triangle.b), to_local(triangle.c)];
+        let mut clipped = Vec::with_capacity(9);
+        for axis in 0..3 {
+            for &sign in &[S::one(), -S::one()] {
+                // The signed distance to the face, positive inside.
+                let inside = |p: &Vector3<S>| self.half_extents[axis] - p[axis] * sign;
+                clipped.clear();
+                for (i, &p) in polygon.iter().enumerate() {
+                    let q = polygon[(i + 1) % polygon.len()];
+                    let (dp, dq) = (inside(&p), inside(&q));
+                    if dp >= S::zero() {
+                        clipped.push(p);
+                    }
+                    if (dp >= S::zero()) != (dq >= S::zero()) {
+                        clipped.push(p.lerp(q, dp / (dp - dq)));
+                    }
+                }
+                mem::swap(&mut polygon, &mut clipped);
+                if polygon.is_empty() {
+                    return Vec::new();
+                }
+            }
+        }
+        polygon
+            .into_iter()
+            .map(|p| self.center + self.axes * p)
+            .collect()
+    }
+}
+
+impl<S: BaseFloat> approx::AbsDiffEq for Obb3<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Point3::abs_diff_eq(&self.center, &other.center, epsilon)
+            && Vector3::abs_diff_eq(&self.half_extents, &other.half_extents, epsilon)
+            && Matrix3::abs_diff_eq(&self.axes, &other.axes, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for Obb3<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Point3::relative_eq(&self.center, &other.center, epsilon, max_relative)
+            && Vector3::relative_eq(
+                &self.half_extents,
+                &other.half_extents,
+                epsilon,
+                max_relative,
+            )
+            && Matrix3::relative_eq(&self.axes, &other.axes, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for Obb3<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Point3::ulps_eq(&self.center, &other.center, epsilon, max_ulps)
+            && Vector3::ulps_eq(&self.half_extents, &other.half_extents, epsilon, max_ulps)
+            && Matrix3::ulps_eq(&self.axes, &other.axes, epsilon, max_ulps)
+    }
+}
+
+impl<S: BaseFloat> approx::AbsDiffEq for Obb2<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Point2::abs_diff_eq(&self.center, &other.center, epsilon)
+            && Vector2::abs_diff_eq(&self.half_extents, &other.half_extents, epsilon)
+            && Rad::abs_diff_eq(&self.angle, &other.angle, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for Obb2<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Point2::relative_eq(&self.center, &other.center, epsilon, max_relative)
+            && Vector2::relative_eq(
+                &self.half_extents,
+                &other.half_extents,
+                epsilon,
+                max_relative,
+            )
+            && Rad::relative_eq(&self.angle, &other.angle, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for Obb2<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Point2::ulps_eq(&self.center, &other.center, epsilon, max_ulps)
+            && Vector2::ulps_eq(&self.half_extents, &other.half_extents, epsilon, max_ulps)
+            && Rad::ulps_eq(&self.angle, &other.angle, epsilon, max_ulps)
+    }
+}
@@ -0,0 +1,279 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Conversions between the coordinates used to store environment maps:
+//! directions, cubemap faces, equirectangular panoramas and spherical
+//! angles.
+//!
+//! Cubemap faces follow the OpenGL and Direct3D convention, where the
+//! texture coordinates of each face go right and down as seen from outside
+//! of the cube, so that the faces appear mirrored from its center. Panoramas are laid out with `+y` up, the azimuth
+//! growing from `-z` towards `+x`, and rows going down from the zenith, so
+//! that `v = 0` is straight up and `u = 0.5` is straight ahead.
+//!
+//! Texture coordinates are discontinuous across the seams of a panorama and
+//! the edges of the faces of a cube, so finite differences of neighbouring
+//! coordinates pick the wrong mip level there. The derivative functions
+//! instead differentiate the mapping itself, which is continuous.
+//!
+//! ```rust
+//! use cgmath::panorama::{cube_direction, cube_face_uv, CubeFace};
+//! use cgmath::{InnerSpace, Point2, Vector3};
+//!
+//! let direction = cube_direction(CubeFace::PositiveZ, Point2::new(0.5, 0.5f64));
+//! assert_eq!(direction, Vector3::unit_z());
+//!
+//! let (face, uv) = cube_face_uv(Vector3::new(1.0, -0.5, 0.25f64));
+//! assert_eq!(face, CubeFace::PositiveX);
+//! assert!((cube_direction(face, uv) - Vector3::new(1.0, -0.5, 0.25).normalize()).magnitude() < 1e-12);
+//! ```
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use num::BaseFloat;
+use point::Point2;
+use vector::{Vector2, Vector3};
+
+/// A face of a cubemap, in the order in which graphics APIs store them.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CubeFace {
+    /// The face crossed by the `+x` axis.
+    PositiveX,
+    /// The face crossed by the `-x` axis.
+    NegativeX,
+    /// The face crossed by the `+y` axis.
+    PositiveY,
+    /// The face crossed by the `-y` axis.
+    NegativeY,
+    /// The face crossed by the `+z` axis.
+    PositiveZ,
+    /// The face crossed by the `-z` axis.
+    NegativeZ,
+}
+
+impl CubeFace {
+    /// The faces, in storage order.
+    pub const ALL: [CubeFace; 6] = [
+        CubeFace::PositiveX,
+        CubeFace::NegativeX,
+        CubeFace::PositiveY,
+        CubeFace::NegativeY,
+        CubeFace::PositiveZ,
+        CubeFace::NegativeZ,
+    ];
+
+    /// The position of the face in storage order, as used for layers of
+    /// cubemap textures.
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The face whose center `direction` points to most directly. Ties
+    /// between axes go to `x`, then `y`.
+    pub fn from_direction<S: BaseFloat>(direction: Vector3<S>) -> CubeFace {
+        let (x, y, z) = (direction.x.abs(), direction.y.abs(), direction.z.abs());
+        if x >= y && x >= z {
+            if direction.x >= S::zero() {
+                CubeFace::PositiveX
+            } else {
+                CubeFace::NegativeX
+            }
+        } else if y >= z {
+            if direction.y >= S::zero() {
+                CubeFace::PositiveY
+            } else {
+                CubeFace::NegativeY
+            }
+        } else if direction.z >= S::zero() {
+            CubeFace::PositiveZ
+        } else {
+            CubeFace::NegativeZ
+        }
+    }
+
+    /// The outward normal of the face, followed by the directions in which
+    /// `u` and `v` grow across it.
+    pub fn axes<S: BaseFloat>(self) -> (Vector3<S>, Vector3<S>, Vector3<S>) {
+        let (o, l) = (S::zero(), S::one());
+        let v = Vector3::new;
+        match self {
+            CubeFace::PositiveX => (v(l, o, o), v(o, o, -l), v(o, -l, o)),
+            CubeFace::NegativeX => (v(-l, o, o), v(o, o, l), v(o, -l, o)),
+            CubeFace::PositiveY => (v(o, l, o), v(l, o, o), v(o, o, l)),
+            CubeFace::NegativeY => (v(o, -l, o), v(l, o, o), v(o, o, -l)),
+            CubeFace::PositiveZ => (v(o, o, l), v(l, o, o), v(o, -l, o)),
+            CubeFace::NegativeZ => (v(o, o, -l), v(-l, o, o), v(o, -l, o)),
+        }
+    }
+}
+
+/// The unit direction through the point `uv` of a face of a cubemap, where
+/// both coordinates are in `[0, 1]`.
+pub fn cube_direction<S: BaseFloat>(face: CubeFace, uv: Point2<S>) -> Vector3<S> {
+    let (normal, u, v) = face.axes::<S>();
+    let two: S = cast(2).unwrap();
+    let (s, t) = (uv.x * two - S::one(), uv.y * two - S::one());
+    (normal + u * s + v * t).normalize()
+}
+
+/// The face of a cubemap that `direction` points to, and the texture
+/// coordinates of the point where it crosses that face. The direction does
+/// not need to be normalized, but must not be zero.
+pub fn cube_face_uv<S: BaseFloat>(direction: Vector3<S>) -> (CubeFace, Point2<S>) {
+    let face = CubeFace::from_direction(direction);
+    let (normal, u, v) = face.axes::<S>();
+    let half: S = cast(0.5).unwrap();
+    let major = direction.dot(normal);
+    let uv = Point2::new(
+        (direction.dot(u) / major + S::one()) * half,
+        (direction.dot(v) / major + S::one()) * half,
+    );
+    (face, uv)
+}
+
+/// The rate of change of the texture coordinates on the face of the
+/// cubemap that `direction` points to, when the direction changes by
+/// `dx` and by `dy`, typically between neighbouring pixels of a screen.
+///
+/// The derivatives are taken on the face of `direction` itself, so that
+/// they stay small near the edges of the face, where the coordinates of
+/// neighbouring directions may be on different faces.
+pub fn cube_uv_derivatives<S: BaseFloat>(
+    direction: Vector3<S>,
+    dx: Vector3<S>,
+    dy: Vector3<S>,
+) -> (Vector2<S>, Vector2<S>) {
+    let (normal, u, v) = CubeFace::from_direction(direction).axes::<S>();
+    let two: S = cast(2).unwrap();
+    let major = direction.dot(normal);
+    let (s, t) = (direction.dot(u), direction.dot(v));
+    let scale = S::one() / (two * major * major);
+    let derivative = |d: Vector3<S>| {
+        let dm = d.dot(normal);
+        Vector2::new(
+            (d.dot(u) * major - s * dm) * scale,
+            (d.dot(v) * major - t * dm) * scale,
+        )
+    };
+    (derivative(dx), derivative(dy))
+}
+
+/// A direction given by its angles, with `+y` up.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spherical<S> {
+    /// The angle around the vertical axis, from `-z` towards `+x`, in
+    /// `(-π, π]`.
+    pub azimuth: Rad<S>,
+    /// The angle above the horizontal plane, in `[-π/2, π/2]`.
+    pub elevation: Rad<S>,
+}
+
+impl<S: BaseFloat> Spherical<S> {
+    /// Construct a direction from its angles.
+    #[inline]
+    pub fn new(azimuth: Rad<S>, elevation: Rad<S>) -> Spherical<S> {
+        Spherical { azimuth, elevation }
+    }
+
+    /// The angles of `direction`, which does not need to be normalized.
+    /// The azimuth of the vertical directions is zero.
+    pub fn from_direction(direction: Vector3<S>) -> Spherical<S> {
+        let horizontal = (direction.x * direction.x + direction.z * direction.z).sqrt();
+        let azimuth = if horizontal == S::zero() {
+            Rad::zero()
+        } else {
+            Rad::atan2(direction.x, -direction.z)
+        };
+        Spherical::new(azimuth, Rad::atan2(direction.y, horizontal))
+    }
+
+    /// The unit direction with these angles.
+    pub fn to_direction(self) -> Vector3<S> {
+        let (sin_azimuth, cos_azimuth) = self.azimuth.sin_cos();
+        let (sin_elevation, cos_elevation) = self.elevation.sin_cos();
+        Vector3::new(
+            sin_azimuth * cos_elevation,
+            sin_elevation,
+            -cos_azimuth * cos_elevation,
+        )
+    }
+
+    /// The angles of the point `uv` of an equirectangular panorama, where
+    /// both coordinates are in `[0, 1]`.
+    pub fn from_equirect(uv: Point2<S>) -> Spherical<S> {
+        let half: S = cast(0.5).unwrap();
+        let pi = Rad::<S>::turn_div_2().0;
+        Spherical::new(Rad((uv.x - half) * (pi + pi)), Rad((half - uv.y) * pi))
+    }
+
+    /// The point of an equirectangular panorama with these angles.
+    pub fn to_equirect(self) -> Point2<S> {
+        let half: S = cast(0.5).unwrap();
+        let pi = Rad::<S>::turn_div_2().0;
+        Point2::new(
+            half + self.azimuth.0 / (pi + pi),
+            half - self.elevation.0 / pi,
+        )
+    }
+}
+
+/// The unit direction through the point `uv` of an equirectangular
+/// panorama.
+#[inline]
+pub fn equirect_direction<S: BaseFloat>(uv: Point2<S>) -> Vector3<S> {
+    Spherical::from_equirect(uv).to_direction()
+}
+
+/// The point of an equirectangular panorama that `direction` points to.
+/// The direction does not need to be normalized, but must not be zero.
+#[inline]
+pub fn equirect_uv<S: BaseFloat>(direction: Vector3<S>) -> Point2<S> {
+    Spherical::from_direction(direction).to_equirect()
+}
+
+/// The rate of change of the coordinates of an equirectangular panorama
+/// when `direction` changes by `dx` and by `dy`.
+///
+/// Unlike differences of `equirect_uv`, these do not jump by a whole turn
+/// across the seam behind the viewer. They grow without bound towards the
+/// poles, where a small change of direction sweeps across all azimuths, and
+/// are zero at the poles themselves, where the mapping is singular.
+pub fn equirect_uv_derivatives<S: BaseFloat>(
+    direction: Vector3<S>,
+    dx: Vector3<S>,
+    dy: Vector3<S>,
+) -> (Vector2<S>, Vector2<S>) {
+    let pi = Rad::<S>::turn_div_2().0;
+    let horizontal2 = direction.x * direction.x + direction.z * direction.z;
+    let horizontal = horizontal2.sqrt();
+    let length2 = horizontal2 + direction.y * direction.y;
+    let derivative = |d: Vector3<S>| {
+        if horizontal == S::zero() {
+            return Vector2::zero();
+        }
+        // The derivatives of `atan2(x, -z)` and of `atan2(y, horizontal)`.
+        let azimuth = (d.z * direction.x - d.x * direction.z) / horizontal2;
+        let d_horizontal = (direction.x * d.x + direction.z * d.z) / horizontal;
+        let elevation = (d.y * horizontal - direction.y * d_horizontal) / length2;
+        Vector2::new(azimuth / (pi + pi), -elevation / pi)
+    };
+    (derivative(dx), derivative(dy))
+}
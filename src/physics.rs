@@ -0,0 +1,704 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Building blocks of simple physics simulations, such as ropes and chains
+//! of particles, damped springs and projectiles.
+//!
+//! Particles are simulated with Verlet integration, which stores their
+//! current and previous positions instead of their velocities, and then
+//! moved to satisfy distance constraints between pairs of them, as
+//! described in "Advanced Character Physics" by Thomas Jakobsen. Pinned
+//! particles never move, and hold the rest of the system in place.
+//!
+//! ```rust
+//! use cgmath::physics::VerletSystem;
+//! use cgmath::{Point3, Vector3};
+//!
+//! // A rope of ten segments hanging from its first particle.
+//! let mut rope = VerletSystem::rope(Point3::new(0.0, 0.0, 0.0), Point3::new(5.0, 0.0, 0.0f64), 10);
+//! rope.pinned[0] = true;
+//! rope.damping = 0.99;
+//! for _ in 0..2000 {
+//!     rope.step(Vector3::new(0.0, -9.81, 0.0), 0.01, 20);
+//! }
+//! let end = rope.positions[10];
+//! assert!(end.x.abs() < 0.1 && (end.y + 5.0).abs() < 0.1);
+//! ```
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use noise::Fbm;
+use num::BaseFloat;
+use point::{Point2, Point3};
+use polyline::Polyline3;
+use quaternion::Quaternion;
+use vector::{Vector2, Vector3};
+
+/// A constraint keeping two particles at a given distance.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistanceConstraint<S> {
+    /// The index of the first particle.
+    pub a: usize,
+    /// The index of the second particle.
+    pub b: usize,
+    /// The distance to keep the particles at.
+    pub length: S,
+}
+
+impl<S: BaseFloat> DistanceConstraint<S> {
+    /// Construct a constraint keeping the particles `a` and `b` at
+    /// `length` from each other.
+    #[inline]
+    pub fn new(a: usize, b: usize, length: S) -> DistanceConstraint<S> {
+        DistanceConstraint { a, b, length }
+    }
+
+    /// Construct a constraint keeping the particles `a` and `b` at their
+    /// current distance in `positions`.
+    #[inline]
+    pub fn from_positions(positions: &[Point3<S>], a: usize, b: usize) -> DistanceConstraint<S> {
+        DistanceConstraint::new(a, b, positions[a].distance(positions[b]))
+    }
+}
+
+/// Move the particles at `positions` to satisfy the `constraints`, by
+/// projecting each constraint in turn, `iterations` times.
+///
+/// Each projection moves the two particles of a constraint along the line
+/// between them, by half of the error each, or by the whole error for the
+/// particle which is not pinned if the other one is. More iterations give
+/// stiffer results. `pinned` has one flag per particle.
+pub fn satisfy_constraints<S: BaseFloat>(
+    positions: &mut [Point3<S>],
+    pinned: &[bool],
+    constraints: &[DistanceConstraint<S>],
+    iterations: usize,
+) {
+    assert_eq!(
+        positions.len(),
+        pinned.len(),
+        "one pinned flag per particle"
+    );
+    let half = S::one() / (S::one() + S::one());
+    for _ in 0..iterations {
+        for c in constraints {
+            let delta = positions[c.b] - positions[c.a];
+            let distance = delta.magnitude();
+            if distance == S::zero() {
+                continue;
+            }
+            let correction = delta * ((distance - c.length) / distance);
+            match (pinned[c.a], pinned[c.b]) {
+                (false, false) => {
+                    positions[c.a] += correction * half;
+                    positions[c.b] -= correction * half;
+                }
+                (false, true) => positions[c.a] += correction,
+                (true, false) => positions[c.b] -= correction,
+                (true, true) => {}
+            }
+        }
+    }
+}
+
+/// A system of particles moved by Verlet integration and held together by
+/// distance constraints.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VerletSystem<S> {
+    /// The current positions of the particles.
+    pub positions: Vec<Point3<S>>,
+    /// The positions of the particles at the previous step.
+    pub previous: Vec<Point3<S>>,
+    /// Whether each particle is pinned in place.
+    pub pinned: Vec<bool>,
+    /// The constraints between the particles.
+    pub constraints: Vec<DistanceConstraint<S>>,
+    /// The fraction of the velocity kept at each step, `1` for none of it
+    /// to be lost.
+    pub damping: S,
+}
+
+impl<S: BaseFloat> VerletSystem<S> {
+    /// Construct a system of particles at rest at `positions`, without
+    /// constraints.
+    pub fn new(positions: Vec<Point3<S>>) -> VerletSystem<S> {
+        VerletSystem {
+            previous: positions.clone(),
+            pinned: vec![false; positions.len()],
+            positions,
+            constraints: Vec::new(),
+            damping: S::one(),
+        }
+    }
+
+    /// Construct a rope of `segments` segments from `start` to `end`, as
+    /// particles at rest linked by constraints of the length of the
+    /// segments.
+    pub fn rope(start: Point3<S>, end: Point3<S>, segments: usize) -> VerletSystem<S> {
+        let count: S = cast(segments).unwrap();
+        let positions = (0..=segments)
+            .map(|i| start + (end - start) * (cast::<_, S>(i).unwrap() / count))
+            .collect();
+        let mut system = VerletSystem::new(positions);
+        for i in 0..segments {
+            system.link(i, i + 1);
+        }
+        system
+    }
+
+    /// Add a constraint keeping the particles `a` and `b` at their current
+    /// distance.
+    pub fn link(&mut self, a: usize, b: usize) {
+        let constraint = DistanceConstraint::from_positions(&self.positions, a, b);
+        self.constraints.push(constraint);
+    }
+
+    /// The velocity of the particle `i`, estimated from its last step of
+    /// duration `dt`.
+    pub fn velocity(&self, i: usize, dt: S) -> Vector3<S> {
+        (self.positions[i] - self.previous[i]) / dt
+    }
+
+    /// Advance the system by `dt` under the uniform `acceleration`, then
+    /// satisfy the constraints with `iterations` iterations.
+    ///
+    /// The steps should have a constant duration, since Verlet integration
+    /// infers the velocities from the last step.
+    pub fn step(&mut self, acceleration: Vector3<S>, dt: S, iterations: usize) {
+        let dt2 = dt * dt;
+        for i in 0..self.positions.len() {
+            if self.pinned[i] {
+                self.previous[i] = self.positions[i];
+                continue;
+            }
+            let current = self.positions[i];
+            let inertia = (current - self.previous[i]) * self.damping;
+            self.positions[i] = current + inertia + acceleration * dt2;
+            self.previous[i] = current;
+        }
+        satisfy_constraints(
+            &mut self.positions,
+            &self.pinned,
+            &self.constraints,
+            iterations,
+        );
+    }
+}
+
+/// A damped spring, following Hooke's law.
+///
+/// The spring pulls with a force proportional to its extension, and the
+/// damper resists with a force proportional to the velocity. The same
+/// spring can pull points, or orientations with a torque proportional to
+/// the angle between them, which is how procedural animation makes bones
+/// follow their targets.
+///
+/// ```rust
+/// use cgmath::physics::Spring;
+/// use cgmath::Vector2;
+///
+/// // A critically damped spring pulling a unit mass back to the origin.
+/// let spring = Spring::critically_damped(100.0f64, 1.0);
+/// let (mut x, mut v) = (Vector2::new(1.0, 0.0), Vector2::new(0.0, 0.0));
+/// for _ in 0..1000 {
+///     v += spring.force(x, v) * 0.001;
+///     x += v * 0.001;
+///     // A critically damped spring does not overshoot.
+///     assert!(x.x > 0.0);
+/// }
+/// assert!(x.x < 1e-3);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Spring<S> {
+    /// The force per unit of extension.
+    pub stiffness: S,
+    /// The force per unit of velocity.
+    pub damping: S,
+}
+
+impl<S: BaseFloat> Spring<S> {
+    /// Construct a spring from its stiffness and its damping.
+    #[inline]
+    pub fn new(stiffness: S, damping: S) -> Spring<S> {
+        Spring { stiffness, damping }
+    }
+
+    /// Construct the spring with the given stiffness whose damping brings
+    /// a body of the given mass to rest the fastest without oscillating.
+    #[inline]
+    pub fn critically_damped(stiffness: S, mass: S) -> Spring<S> {
+        let two = S::one() + S::one();
+        Spring::new(stiffness, two * (stiffness * mass).sqrt())
+    }
+
+    /// Construct the spring making a body of the given mass oscillate at
+    /// `frequency` cycles per unit of time, with the given damping ratio.
+    ///
+    /// A damping ratio of `1` is critical damping, lower ratios oscillate,
+    /// and higher ones are sluggish. This is usually easier to tune than
+    /// the stiffness and damping themselves.
+    pub fn from_frequency(frequency: S, damping_ratio: S, mass: S) -> Spring<S> {
+        let two = S::one() + S::one();
+        let omega = Rad::<S>::full_turn().0 * frequency;
+        Spring::new(mass * omega * omega, two * damping_ratio * mass * omega)
+    }
+
+    /// The force of the spring when it is extended by `displacement` from
+    /// its rest position, and moving at `velocity`.
+    #[inline]
+    pub fn force<V: VectorSpace<Scalar = S>>(&self, displacement: V, velocity: V) -> V {
+        displacement * -self.stiffness - velocity * self.damping
+    }
+
+    /// The force of the spring on a body at `a` moving at `velocity_a`,
+    /// when the spring links it to a body at `b` moving at `velocity_b`
+    /// and has the length `rest_length` at rest.
+    ///
+    /// The force on the other body is the opposite. Only the component of
+    /// the relative velocity along the spring is damped.
+    pub fn force_between<P>(
+        &self,
+        a: P,
+        velocity_a: P::Diff,
+        b: P,
+        velocity_b: P::Diff,
+        rest_length: S,
+    ) -> P::Diff
+    where
+        P: EuclideanSpace<Scalar = S>,
+        P::Diff: InnerSpace<Scalar = S>,
+    {
+        let delta = a - b;
+        let length = delta.magnitude();
+        if length == S::zero() {
+            return P::Diff::zero();
+        }
+        let direction = delta / length;
+        let speed = (velocity_a - velocity_b).dot(direction);
+        direction * -(self.stiffness * (length - rest_length) + self.damping * speed)
+    }
+
+    /// The torque of the spring turning a body with the given orientation
+    /// and angular velocity towards `target`, along the shortest path.
+    ///
+    /// The torque is proportional to the rotation vector from the
+    /// orientation to the target, and the angular velocity is in radians
+    /// per unit of time around a world-space axis, as returned by
+    /// `Quaternion::angular_velocity`. The quaternions should be
+    /// normalized.
+    pub fn torque(
+        &self,
+        orientation: Quaternion<S>,
+        angular_velocity: Vector3<S>,
+        target: Quaternion<S>,
+    ) -> Vector3<S> {
+        let error = orientation.angular_velocity(target, S::one());
+        error * self.stiffness - angular_velocity * self.damping
+    }
+}
+
+/// The velocity to fire a projectile at from `shooter`, going at
+/// `projectile_speed`, so that it hits a target at `target` moving at the
+/// constant velocity `target_velocity`.
+///
+/// This aims at the earliest point where the projectile can meet the
+/// target, ignoring gravity. Returns `None` if the projectile can never
+/// catch up with the target, if the speed is not positive, or if the
+/// target is already at the shooter, where there is no direction to aim
+/// at.
+///
+/// ```rust
+/// # #[macro_use] extern crate approx;
+/// # extern crate cgmath;
+/// # fn main() {
+/// use cgmath::physics::intercept;
+/// use cgmath::{InnerSpace, Point3, Vector3};
+///
+/// let shooter = Point3::new(0.0, 0.0, 0.0);
+/// let (target, velocity) = (Point3::new(10.0, 0.0, 0.0), Vector3::new(0.0, 3.0, 0.0f64));
+/// let aim = intercept(shooter, 5.0, target, velocity).unwrap();
+/// assert_relative_eq!(aim.magnitude(), 5.0);
+/// // The projectile and the target meet after 2.5 units of time.
+/// assert_relative_eq!(shooter + aim * 2.5, target + velocity * 2.5);
+///
+/// // A faster target running away can't be caught.
+/// assert_eq!(intercept(shooter, 5.0, target, Vector3::new(6.0, 0.0, 0.0)), None);
+/// # }
+/// ```
+pub fn intercept<S: BaseFloat>(
+    shooter: Point3<S>,
+    projectile_speed: S,
+    target: Point3<S>,
+    target_velocity: Vector3<S>,
+) -> Option<Vector3<S>> {
+    let offset = target - shooter;
+    if projectile_speed.is_nan() || projectile_speed <= S::zero() || offset.is_zero() {
+        return None;
+    }
+
+    // Solve |offset + target_velocity * t| = projectile_speed * t, i.e.
+    // a t² + 2 b t + c = 0, for the smallest positive t.
+    let a = target_velocity.magnitude2() - projectile_speed * projectile_speed;
+    let b = offset.dot(target_velocity);
+    let c = offset.magnitude2();
+    let epsilon = S::default_epsilon() * projectile_speed * projectile_speed;
+    let time = if implicit_ulps_eq!(a, S::zero(), epsilon = epsilon) {
+        // As fast as the target, so the equation is linear and the target
+        // can only be caught if it moves towards the shooter.
+        if b >= S::zero() {
+            return None;
+        }
+        -c / (b + b)
+    } else {
+        let discriminant = b * b - a * c;
+        if discriminant < S::zero() {
+            return None;
+        }
+        // Both roots, without cancellation between b and the square root.
+        let q = -(b + b.signum() * discriminant.sqrt());
+        let (t0, t1) = (q / a, c / q);
+        let (t0, t1) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+        if t0 > S::zero() {
+            t0
+        } else if t1 > S::zero() {
+            t1
+        } else {
+            return None;
+        }
+    };
+    if !time.is_finite() {
+        return None;
+    }
+    Some((offset / time + target_velocity).normalize_to(projectile_speed))
+}
+
+/// The velocity of a body moving at `velocity` after bouncing on a surface
+/// with the given `normal`, which does not need to be normalized.
+///
+/// The velocity along the surface is kept, and the velocity into it is
+/// reversed and scaled by the `restitution`, from `0` for a body which
+/// stops there to `1` for a perfectly elastic bounce.
+pub fn bounce_velocity<S: BaseFloat>(
+    velocity: Vector3<S>,
+    normal: Vector3<S>,
+    restitution: S,
+) -> Vector3<S> {
+    let length2 = normal.magnitude2();
+    if length2 == S::zero() {
+        return velocity;
+    }
+    let into = normal * (velocity.dot(normal) / length2);
+    velocity - into - into * restitution
+}
+
+/// A projectile flying under constant gravity, without drag.
+///
+/// ```rust
+/// use cgmath::physics::Projectile;
+/// use cgmath::{Point3, Vector3};
+///
+/// let ball = Projectile::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Vector3::new(4.0, 5.0, 0.0),
+///     Vector3::new(0.0, -10.0f64, 0.0),
+/// );
+/// // The ball lands after one unit of time, four units away.
+/// assert_eq!(ball.position_at(1.0), Point3::new(4.0, 0.0, 0.0));
+/// let preview = ball.arc(1.0, 8);
+/// assert_eq!(preview.points.len(), 9);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Projectile<S> {
+    /// The position at launch.
+    pub position: Point3<S>,
+    /// The velocity at launch, in units per unit of time.
+    pub velocity: Vector3<S>,
+    /// The acceleration of gravity.
+    pub gravity: Vector3<S>,
+}
+
+/// A point where the trajectory predicted by `Projectile::predict` bounced.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bounce<S> {
+    /// The point of the surface which was hit.
+    pub position: Point3<S>,
+    /// The normal of the surface, as returned by the cast.
+    pub normal: Vector3<S>,
+    /// The time of the hit after launch.
+    pub time: S,
+    /// The velocity after the bounce.
+    pub velocity: Vector3<S>,
+}
+
+impl<S: BaseFloat> Projectile<S> {
+    /// Construct a projectile from its position and velocity at launch.
+    #[inline]
+    pub fn new(position: Point3<S>, velocity: Vector3<S>, gravity: Vector3<S>) -> Projectile<S> {
+        Projectile {
+            position,
+            velocity,
+            gravity,
+        }
+    }
+
+    /// The exact position of the projectile `t` after launch.
+    #[inline]
+    pub fn position_at(&self, t: S) -> Point3<S> {
+        let half: S = cast(0.5).unwrap();
+        self.position + self.velocity * t + self.gravity * (half * t * t)
+    }
+
+    /// The velocity of the projectile `t` after launch.
+    #[inline]
+    pub fn velocity_at(&self, t: S) -> Vector3<S> {
+        self.velocity + self.gravity * t
+    }
+
+    /// Sample the trajectory from launch until `duration` into a polyline of
+    /// `segments` segments of equal durations, such as to preview it.
+    pub fn arc(&self, duration: S, segments: usize) -> Polyline3<S> {
+        let segments = segments.max(1);
+        let count: S = cast(segments).unwrap();
+        Polyline3::new(
+            (0..=segments)
+                .map(|i| self.position_at(duration * cast(i).unwrap() / count))
+                .collect(),
+        )
+    }
+
+    /// Predict the trajectory for `steps` steps of `dt`, bouncing on the
+    /// surfaces it hits with the given `restitution`, and return it as a
+    /// polyline through the sampled positions and the bounces.
+    ///
+    /// `cast` is called with the start and the end of each segment of the
+    /// trajectory, and returns the first point of a surface it hits between
+    /// them with the normal of the surface there, such as by casting a ray
+    /// into the scene. Hits on surfaces the segment leaves are ignored, so
+    /// that the trajectory does not hit again the surface it bounces from.
+    /// The prediction stops after `max_bounces` bounces, at the last one.
+    ///
+    /// ```rust
+    /// use cgmath::physics::Projectile;
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Plane, Point3, Ray3, Vector3};
+    ///
+    /// let ground = Plane::new(Vector3::unit_y(), 0.0);
+    /// let cast = |from: Point3<f64>, to: Point3<f64>| {
+    ///     let hit = (Ray3::new(from, to - from), ground).intersection()?;
+    ///     if (hit - from).magnitude2() <= (to - from).magnitude2() {
+    ///         Some((hit, ground.normal))
+    ///     } else {
+    ///         None
+    ///     }
+    /// };
+    /// let ball = Projectile::new(
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Vector3::new(4.0, 5.0, 0.0),
+    ///     Vector3::new(0.0, -10.0, 0.0),
+    /// );
+    /// let (path, bounces) = ball.predict(0.5, 1.0 / 32.0, 200, 2, cast);
+    /// assert_eq!(bounces.len(), 2);
+    /// assert!((bounces[0].position - Point3::new(4.0, 0.0, 0.0)).magnitude() < 1e-9);
+    /// assert_eq!(*path.points.last().unwrap(), bounces[1].position);
+    /// ```
+    pub fn predict<F>(
+        &self,
+        restitution: S,
+        dt: S,
+        steps: usize,
+        max_bounces: usize,
+        mut cast: F,
+    ) -> (Polyline3<S>, Vec<Bounce<S>>)
+    where
+        F: FnMut(Point3<S>, Point3<S>) -> Option<(Point3<S>, Vector3<S>)>,
+    {
+        let mut points = vec![self.position];
+        let mut bounces = Vec::new();
+        // The current flight, and when it started.
+        let (mut flight, mut start) = (*self, S::zero());
+        let mut time = S::zero();
+        for _ in 0..steps {
+            let from = flight.position_at(time - start);
+            let to = flight.position_at(time - start + dt);
+            let hit = cast(from, to).filter(|&(_, normal)| (to - from).dot(normal) < S::zero());
+            let (position, normal) = match hit {
+                Some(hit) => hit,
+                None => {
+                    points.push(to);
+                    time += dt;
+                    continue;
+                }
+            };
+            // The time of the hit, assuming the speed is about even along
+            // the segment.
+            let travel = (to - from).magnitude();
+            let fraction = if travel > S::zero() {
+                ((position - from).magnitude() / travel).min(S::one())
+            } else {
+                S::zero()
+            };
+            time += dt * fraction;
+            let velocity = bounce_velocity(flight.velocity_at(time - start), normal, restitution);
+            points.push(position);
+            bounces.push(Bounce {
+                position,
+                normal,
+                time,
+                velocity,
+            });
+            if bounces.len() >= max_bounces {
+                break;
+            }
+            flight = Projectile::new(position, velocity, self.gravity);
+            start = time;
+        }
+        (Polyline3::new(points), bounces)
+    }
+}
+
+/// The motion of particles carried by a velocity field, such as smoke or
+/// leaves in the wind.
+///
+/// Particles without drag move with the field, and particles with drag
+/// catch up with its velocity exponentially with the rate of the drag, so
+/// heavier particles have lower drags. The field can be turbulent, with the
+/// curl noise of `Fbm` added to it, which swirls without gathering or
+/// spreading the particles.
+///
+/// ```rust
+/// use cgmath::field::Grid2;
+/// use cgmath::physics::Advection;
+/// use cgmath::{Point2, Vector2};
+///
+/// let wind = Grid2::from_fn(Point2::new(0.0, 0.0), 10.0f64, 3, 3, |_, _| Vector2::new(2.0, 0.0));
+/// let mut positions = vec![Point2::new(0.0, 0.0), Point2::new(5.0, 5.0)];
+/// let mut velocities = vec![Vector2::new(0.0, 0.0); 2];
+/// let smoke = Advection::new();
+/// smoke.advect2(&mut positions, &mut velocities, |p| wind.sample(p), 0.5);
+/// assert_eq!(positions[1], Point2::new(6.0, 5.0));
+/// assert_eq!(velocities[1], Vector2::new(2.0, 0.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Advection<S> {
+    /// The rate at which the particles catch up with the velocity of the
+    /// field, per unit of time, or `None` for particles moving with it.
+    pub drag: Option<S>,
+    /// The speed of the turbulence.
+    pub turbulence: S,
+    /// The spatial frequency of the turbulence, from which its eddies are
+    /// about the inverse in size.
+    pub turbulence_frequency: S,
+    /// The noise of the turbulence.
+    pub noise: Fbm<S>,
+}
+
+macro_rules! impl_advection {
+    ($advance:ident, $advect:ident, $PointN:ident, $VectorN:ident, $curl:ident) => {
+        /// Move a particle at `position` moving at `velocity` for `dt`, in
+        /// a field whose velocity is `wind` at the particle, and return its
+        /// new position and velocity.
+        pub fn $advance(
+            &self,
+            position: $PointN<S>,
+            velocity: $VectorN<S>,
+            wind: $VectorN<S>,
+            dt: S,
+        ) -> ($PointN<S>, $VectorN<S>) {
+            let wind = if self.turbulence == S::zero() {
+                wind
+            } else {
+                let p = $PointN::from_vec(position.to_vec() * self.turbulence_frequency);
+                wind + self.noise.$curl(p) * self.turbulence
+            };
+            let velocity = match self.drag {
+                Some(drag) => velocity + (wind - velocity) * (S::one() - (-drag * dt).exp()),
+                None => wind,
+            };
+            (position + velocity * dt, velocity)
+        }
+
+        /// Move the particles with the given positions and velocities for
+        /// `dt`, in the velocity field sampled by `field`.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the slices do not have the same length.
+        pub fn $advect<F>(
+            &self,
+            positions: &mut [$PointN<S>],
+            velocities: &mut [$VectorN<S>],
+            field: F,
+            dt: S,
+        ) where
+            F: Fn($PointN<S>) -> $VectorN<S>,
+        {
+            assert_eq!(positions.len(), velocities.len());
+            for (position, velocity) in positions.iter_mut().zip(velocities.iter_mut()) {
+                let (p, v) = self.$advance(*position, *velocity, field(*position), dt);
+                *position = p;
+                *velocity = v;
+            }
+        }
+    };
+}
+
+impl<S: BaseFloat> Advection<S> {
+    /// The motion of particles moving with the field, without turbulence.
+    pub fn new() -> Advection<S> {
+        Advection {
+            drag: None,
+            turbulence: S::zero(),
+            turbulence_frequency: S::one(),
+            noise: Fbm::new(0),
+        }
+    }
+
+    /// The same motion, for particles with the given drag.
+    pub fn with_drag(self, drag: S) -> Advection<S> {
+        Advection {
+            drag: Some(drag),
+            ..self
+        }
+    }
+
+    /// The same motion, in a field with turbulence of the given speed and
+    /// spatial frequency, whose noise has the given seed.
+    pub fn with_turbulence(self, speed: S, frequency: S, seed: u32) -> Advection<S> {
+        Advection {
+            turbulence: speed,
+            turbulence_frequency: frequency,
+            noise: Fbm::new(seed),
+            ..self
+        }
+    }
+
+    impl_advection!(advance2, advect2, Point2, Vector2, curl2);
+    impl_advection!(advance3, advect3, Point3, Vector3, curl3);
+}
+
+impl<S: BaseFloat> Default for Advection<S> {
+    fn default() -> Advection<S> {
+        Advection::new()
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use structure::*;
+
+use approx;
+use num::BaseFloat;
+use point::Point3;
+use polyline::any_perpendicular;
+use vector::{Vector3, Vector4};
+
+/// A plane in three-dimensional space.
+///
+/// The plane is the set of points `p` for which `normal.dot(p) == d`, where
+/// the normal is normalized, so `d` is the signed distance from the origin
+/// to the plane along the normal. The normal points towards the positive
+/// half-space, whose points have a positive `distance` to the plane.
+///
+/// ```rust
+/// use cgmath::{Plane, Point3, Vector3};
+///
+/// let ground = Plane::from_point_normal(Point3::new(0.0, 2.0, 0.0), Vector3::unit_y());
+/// assert_eq!(ground.distance(Point3::new(5.0, 7.0, -1.0)), 5.0);
+/// assert_eq!(ground.project_point(Point3::new(5.0, 7.0, -1.0)), Point3::new(5.0, 2.0, -1.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Plane<S> {
+    /// The unit normal of the plane.
+    pub normal: Vector3<S>,
+    /// The signed distance from the origin to the plane, along the normal.
+    pub d: S,
+}
+
+impl<S: BaseFloat> Plane<S> {
+    /// Construct a plane from its unit normal and its signed distance from
+    /// the origin.
+    #[inline]
+    pub fn new(normal: Vector3<S>, d: S) -> Plane<S> {
+        Plane { normal, d }
+    }
+
+    /// Construct the plane through `p` with the given normal, which is
+    /// normalized.
+    #[inline]
+    pub fn from_point_normal(p: Point3<S>, normal: Vector3<S>) -> Plane<S> {
+        let normal = normal.normalize();
+        Plane::new(normal, normal.dot(p.to_vec()))
+    }
+
+    /// Construct the plane through three points, with the normal facing the
+    /// side from which they appear counter-clockwise.
+    ///
+    /// Returns `None` if the points are collinear.
+    pub fn from_points(a: Point3<S>, b: Point3<S>, c: Point3<S>) -> Option<Plane<S>> {
+        let normal = (b - a).cross(c - a);
+        if implicit_ulps_eq!(normal, &Vector3::zero()) {
+            None
+        } else {
+            Some(Plane::from_point_normal(a, normal))
+        }
+    }
+
+    /// Construct the plane of the points `p` for which
+    /// `v.dot(p.to_homogeneous()) == 0`, normalizing its equation.
+    ///
+    /// Returns `None` if the first three components of `v` are zero.
+    pub fn from_vector4(v: Vector4<S>) -> Option<Plane<S>> {
+        let normal = v.truncate();
+        let length = normal.magnitude();
+        if length.is_zero() {
+            None
+        } else {
+            Some(Plane::new(normal / length, -v.w / length))
+        }
+    }
+
+    /// The plane equation packed in a vector, whose dot product with the
+    /// homogeneous coordinates of a point is its signed distance to the
+    /// plane, as used by shaders.
+    #[inline]
+    pub fn to_vector4(&self) -> Vector4<S> {
+        self.normal.extend(-self.d)
+    }
+
+    /// The signed distance from `p` to the plane, positive on the side the
+    /// normal points to.
+    #[inline]
+    pub fn distance(&self, p: Point3<S>) -> S {
+        self.normal.dot(p.to_vec()) - self.d
+    }
+
+    /// The point of the plane closest to `p`.
+    #[inline]
+    pub fn project_point(&self, p: Point3<S>) -> Point3<S> {
+        p - self.normal * self.distance(p)
+    }
+
+    /// The point of the plane closest to the origin.
+    #[inline]
+    pub fn origin(&self) -> Point3<S> {
+        Point3::from_vec(self.normal * self.d)
+    }
+
+    /// Create line segments outlining a square of side `size` on the plane,
+    /// around the point closest to the origin, with a segment of length
+    /// `size / 2` from its center along the normal.
+    pub fn to_wireframe_lines(&self, size: S) -> Vec<(Point3<S>, Point3<S>)> {
+        let half = size / (S::one() + S::one());
+        let u = any_perpendicular(self.normal) * half;
+        let v = self.normal.cross(u);
+        let o = self.origin();
+        let corners = [o - u - v, o + u - v, o + u + v, o - u + v];
+        let mut lines: Vec<_> = (0..4).map(|i| (corners[i], corners[(i + 1) % 4])).collect();
+        lines.push((o, o + self.normal * half));
+        lines
+    }
+}
+
+/// The point where three planes meet, or `None` if two of them are parallel
+/// or they all contain a common line.
+pub(crate) fn intersect_planes<S: BaseFloat>(
+    a: &Plane<S>,
+    b: &Plane<S>,
+    c: &Plane<S>,
+) -> Option<Point3<S>> {
+    let bc = b.normal.cross(c.normal);
+    let denominator = a.normal.dot(bc);
+    if implicit_ulps_eq!(denominator, &S::zero()) {
+        return None;
+    }
+    let v = bc * a.d + c.normal.cross(a.normal) * b.d + a.normal.cross(b.normal) * c.d;
+    Some(Point3::from_vec(v / denominator))
+}
+
+impl<S: BaseFloat> approx::AbsDiffEq for Plane<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Vector3::abs_diff_eq(&self.normal, &other.normal, epsilon)
+            && S::abs_diff_eq(&self.d, &other.d, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for Plane<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Vector3::relative_eq(&self.normal, &other.normal, epsilon, max_relative)
+            && S::relative_eq(&self.d, &other.d, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for Plane<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Vector3::ulps_eq(&self.normal, &other.normal, epsilon, max_ulps)
+            && S::ulps_eq(&self.d, &other.d, epsilon, max_ulps)
+    }
+}
@@ -25,7 +25,7 @@ use std::ops::*;
 use structure::*;
 
 use approx;
-use num::{BaseFloat, BaseNum};
+use num::{BaseFloat, BaseNum, ToF32};
 use vector::{Vector1, Vector2, Vector3, Vector4};
 
 #[cfg(feature = "mint")]
@@ -64,7 +64,37 @@ pub struct Point3<S> {
     pub z: S,
 }
 
+impl<S: BaseNum> Point1<S> {
+    /// Create a `Point2`, using the `x` value from this point, and the
+    /// provided `y`.
+    #[inline]
+    pub fn extend(self, y: S) -> Point2<S> {
+        Point2::new(self.x, y)
+    }
+}
+
+impl<S: BaseNum> Point2<S> {
+    /// Create a `Point3`, using the `x` and `y` values from this point, and
+    /// the provided `z`.
+    #[inline]
+    pub fn extend(self, z: S) -> Point3<S> {
+        Point3::new(self.x, self.y, z)
+    }
+
+    /// Create a `Point1`, dropping the `y` value.
+    #[inline]
+    pub fn truncate(self) -> Point1<S> {
+        Point1::new(self.x)
+    }
+}
+
 impl<S: BaseNum> Point3<S> {
+    /// Create a `Point2`, dropping the `z` value.
+    #[inline]
+    pub fn truncate(self) -> Point2<S> {
+        Point2::new(self.x, self.y)
+    }
+
     #[inline]
     pub fn from_homogeneous(v: Vector4<S>) -> Point3<S> {
         let e = v.truncate() * (S::one() / v.w);
@@ -163,6 +193,14 @@ macro_rules! impl_point {
             }
         }
 
+        /// The default point is the origin.
+        impl<S: BaseNum> Default for $PointN<S> {
+            #[inline]
+            fn default() -> $PointN<S> {
+                $PointN::origin()
+            }
+        }
+
         impl<S: BaseNum> EuclideanSpace for $PointN<S> {
             type Scalar = S;
             type Diff = $VectorN<S>;
@@ -188,6 +226,15 @@ macro_rules! impl_point {
             }
         }
 
+        impl<S: BaseNum> Interpolate<S> for $PointN<S> {
+            #[inline]
+            fn barycentric(values: ($PointN<S>, $PointN<S>, $PointN<S>), weights: Vector3<S>) -> $PointN<S> {
+                $PointN::new($(
+                    values.0.$field * weights.x + values.1.$field * weights.y + values.2.$field * weights.z
+                ),+)
+            }
+        }
+
         impl<S: BaseFloat> approx::AbsDiffEq for $PointN<S> {
             type Epsilon = S::Epsilon;
 
@@ -343,6 +390,29 @@ impl_point!(Point1 { x }, Vector1, 1, point1);
 impl_point!(Point2 { x, y }, Vector2, 2, point2);
 impl_point!(Point3 { x, y, z }, Vector3, 3, point3);
 
+macro_rules! impl_point_consts {
+    ($($S:ident),+) => {
+        $(
+            impl Point1<$S> {
+                /// The origin of the coordinate system.
+                pub const ORIGIN: Point1<$S> = Point1::new(0.0);
+            }
+
+            impl Point2<$S> {
+                /// The origin of the coordinate system.
+                pub const ORIGIN: Point2<$S> = Point2::new(0.0, 0.0);
+            }
+
+            impl Point3<$S> {
+                /// The origin of the coordinate system.
+                pub const ORIGIN: Point3<$S> = Point3::new(0.0, 0.0, 0.0);
+            }
+        )+
+    };
+}
+
+impl_point_consts!(f32, f64);
+
 impl<S: Copy> Point1<S> {
     impl_swizzle_functions!(Point1, Point2, Point3, S, x);
 }
@@ -363,6 +433,24 @@ impl_tuple_conversions!(Point1<S> { x }, (S,));
 impl_tuple_conversions!(Point2<S> { x, y }, (S, S));
 impl_tuple_conversions!(Point3<S> { x, y, z }, (S, S, S));
 
+impl_precision_conversions!(Point1 { x }, Point1<f32>, Point1<f64>);
+impl_precision_conversions!(Point2 { x, y }, Point2<f32>, Point2<f64>);
+impl_precision_conversions!(Point3 { x, y, z }, Point3<f32>, Point3<f64>);
+
+impl Point3<f64> {
+    /// The position of this point relative to `origin`, rounded to single
+    /// precision.
+    ///
+    /// The offset is computed in double precision first, so nearby points keep
+    /// their accuracy even when they are far away from the origin of the
+    /// world. This is the usual way to feed positions to a renderer with the
+    /// camera at the origin.
+    #[inline]
+    pub fn relative_to(self, origin: Point3<f64>) -> Point3<f32> {
+        Point3::from_vec((self - origin).to_f32())
+    }
+}
+
 #[cfg(feature = "mint")]
 impl_mint_conversions!(Point2 { x, y }, Point2);
 #[cfg(feature = "mint")]
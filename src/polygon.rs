@@ -0,0 +1,268 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Simple two-dimensional polygons, given as slices of their vertices in
+//! order, without repeating the first vertex at the end.
+//!
+//! ```rust
+//! use cgmath::polygon::{offset, signed_area, Join};
+//! use cgmath::Point2;
+//!
+//! let square = [
+//!     Point2::new(0.0, 0.0),
+//!     Point2::new(2.0, 0.0),
+//!     Point2::new(2.0, 2.0),
+//!     Point2::new(0.0, 2.0),
+//! ];
+//! assert_eq!(signed_area(&square), 4.0);
+//!
+//! // Shrink the square by half a unit, as for an agent of that radius.
+//! let walkable = offset(&square, -0.5, Join::Miter(2.0));
+//! assert_eq!(signed_area(&walkable), 1.0);
+//! ```
+
+use std::collections::HashMap;
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use num::BaseFloat;
+use point::Point2;
+use triangulation::{circumcenter, delaunay};
+use vector::Vector2;
+
+/// The signed area of a polygon, which is positive if its vertices are in
+/// counterclockwise order.
+pub fn signed_area<S: BaseFloat>(polygon: &[Point2<S>]) -> S {
+    let mut area = S::zero();
+    let mut previous = match polygon.last() {
+        Some(&last) => last,
+        None => return area,
+    };
+    for &p in polygon {
+        area += previous.x * p.y - p.x * previous.y;
+        previous = p;
+    }
+    area / (S::one() + S::one())
+}
+
+/// Test whether the point is inside the polygon, with the even-odd rule.
+/// Points on the boundary may be reported inside or outside.
+pub fn contains_point<S: BaseFloat>(polygon: &[Point2<S>], p: Point2<S>) -> bool {
+    let mut inside = false;
+    let mut previous = match polygon.last() {
+        Some(&last) => last,
+        None => return false,
+    };
+    for &q in polygon {
+        if (q.y > p.y) != (previous.y > p.y) {
+            let x = q.x + (p.y - q.y) * (previous.x - q.x) / (previous.y - q.y);
+            if p.x < x {
+                inside = !inside;
+            }
+        }
+        previous = q;
+    }
+    inside
+}
+
+/// How `offset` joins the offset edges of a polygon around the corners they
+/// move away from.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Join<S> {
+    /// Extend the edges until they meet, and bevel the corners where they
+    /// would meet farther than the given multiple of the distance from the
+    /// original corner.
+    Miter(S),
+    /// Join the edges with arcs around the original corners, made of
+    /// segments spanning at most the given angle.
+    Round(Rad<S>),
+}
+
+/// Offset the edges of a simple polygon by `distance`, outwards if it is
+/// positive and inwards if it is negative, whatever the orientation of the
+/// polygon, which is kept.
+///
+/// Repeated vertices are ignored. The result is not cleaned up: shrinking
+/// a polygon by more than the half width of one of its parts folds that
+/// part over itself, rather than removing it.
+pub fn offset<S: BaseFloat>(polygon: &[Point2<S>], distance: S, join: Join<S>) -> Vec<Point2<S>> {
+    let mut points: Vec<Point2<S>> = Vec::with_capacity(polygon.len());
+    for &p in polygon {
+        if points.last() != Some(&p) {
+            points.push(p);
+        }
+    }
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 3 || distance == S::zero() {
+        return points;
+    }
+
+    // The outward normal of each edge, from each point to the next one.
+    let sign = if signed_area(&points) < S::zero() {
+        -S::one()
+    } else {
+        S::one()
+    };
+    let n = points.len();
+    let normals: Vec<Vector2<S>> = (0..n)
+        .map(|i| {
+            let d = (points[(i + 1) % n] - points[i]).normalize();
+            Vector2::new(d.y, -d.x) * sign
+        })
+        .collect();
+
+    let mut result = Vec::with_capacity(n);
+    for i in 0..n {
+        let p = points[i];
+        let (before, after) = (normals[(i + n - 1) % n], normals[i]);
+        let cos = before.dot(after);
+        let sin = before.perp_dot(after) * sign;
+        // The offset edges meet without a gap on the inner side of the
+        // corner, and on straight edges.
+        let opens = sin * distance > S::zero();
+        let denom = S::one() + cos;
+        if !opens || sin == S::zero() {
+            if denom > S::zero()
+                && !implicit_ulps_eq!(denom, S::zero(), epsilon = S::default_epsilon())
+            {
+                result.push(p + (before + after) * (distance / denom));
+            } else {
+                result.push(p + before * distance);
+                result.push(p + after * distance);
+            }
+            continue;
+        }
+        match join {
+            Join::Miter(limit) => {
+                // The miter is `1 / cos(θ / 2)` times the distance away,
+                // where `1 + cos θ = 2 cos²(θ / 2)`.
+                let two = S::one() + S::one();
+                if denom * limit * limit >= two {
+                    result.push(p + (before + after) * (distance / denom));
+                } else {
+                    result.push(p + before * distance);
+                    result.push(p + after * distance);
+                }
+            }
+            Join::Round(max_angle) => {
+                let from = before * distance;
+                let angle = Rad::atan2(from.perp_dot(after * distance), from.dot(after * distance));
+                let steps = (angle.0.abs() / max_angle.0).ceil().max(S::one());
+                let count = steps.to_usize().unwrap_or(1);
+                let step = angle / steps;
+                result.push(p + from);
+                for k in 1..count {
+                    let (s, c) = Rad::sin_cos(step * cast(k).unwrap());
+                    result.push(p + Vector2::new(c * from.x - s * from.y, s * from.x + c * from.y));
+                }
+                result.push(p + after * distance);
+            }
+        }
+    }
+    result
+}
+
+/// Approximate the medial axis of a simple polygon, the points inside it
+/// with more than one closest point on its boundary, as a set of segments.
+///
+/// The boundary is sampled at most `spacing` apart, and the axis is made of
+/// the edges of the Voronoi diagram of the samples which are inside the
+/// polygon and separate samples more than two spacings apart along the
+/// boundary. The result gets closer to the medial axis as the spacing gets
+/// smaller, but its branches stop about a spacing short of the convex
+/// corners of the polygon. It makes a skeleton for thinning regions or
+/// placing the ridges of roofs.
+///
+/// ```rust
+/// use cgmath::polygon::medial_axis;
+/// use cgmath::Point2;
+///
+/// let corridor = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(10.0, 0.0),
+///     Point2::new(10.0, 2.0),
+///     Point2::new(0.0, 2.0),
+/// ];
+/// let axis = medial_axis(&corridor, 0.25f64);
+///
+/// // The axis runs along the middle of the corridor, then splits towards
+/// // the corners.
+/// let middle = axis.iter().filter(|&&(a, b)| a.y == 1.0 && b.y == 1.0);
+/// assert_eq!(middle.map(|&(a, b)| (a.x - b.x).abs()).sum::<f64>(), 8.0);
+/// ```
+pub fn medial_axis<S: BaseFloat>(polygon: &[Point2<S>], spacing: S) -> Vec<(Point2<S>, Point2<S>)> {
+    // The samples, and their distances along the boundary.
+    let mut samples = Vec::new();
+    let mut along = Vec::new();
+    let mut length = S::zero();
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let edge = b.distance(a);
+        if edge == S::zero() {
+            continue;
+        }
+        let count = (edge / spacing).ceil().max(S::one());
+        let steps = count.to_usize().unwrap_or(1);
+        for k in 0..steps {
+            let t: S = cast::<_, S>(k).unwrap() / count;
+            samples.push(a + (b - a) * t);
+            along.push(length + edge * t);
+        }
+        length += edge;
+    }
+
+    let triangles = delaunay(&samples);
+    let centers: Vec<Option<Point2<S>>> = triangles
+        .iter()
+        .map(|t| {
+            circumcenter(samples[t[0]], samples[t[1]], samples[t[2]])
+                .filter(|&c| contains_point(polygon, c))
+        })
+        .collect();
+
+    // Each edge of the triangulation separates two triangles, whose
+    // circumcenters bound the dual Voronoi edge.
+    let mut edges = HashMap::new();
+    let mut axis = Vec::new();
+    let two = S::one() + S::one();
+    for (i, t) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (t[k], t[(k + 1) % 3]);
+            let j = match edges.remove(&(b, a)) {
+                Some(j) => j,
+                None => {
+                    edges.insert((a, b), i);
+                    continue;
+                }
+            };
+            let gap = (along[a] - along[b]).abs();
+            if gap.min(length - gap) <= two * spacing {
+                continue;
+            }
+            if let (Some(p), Some(q)) = (centers[i], centers[j]) {
+                if p != q {
+                    axis.push((p, q));
+                }
+            }
+        }
+    }
+    axis
+}
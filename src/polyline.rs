@@ -0,0 +1,692 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Open polygonal chains.
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use arc::Arc2;
+use matrix::{Matrix3, Matrix4};
+use num::BaseFloat;
+use point::{Point2, Point3};
+use vector::Vector3;
+
+/// A two-dimensional polyline, made of the segments between consecutive
+/// points.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polyline2<S> {
+    /// The vertices of the polyline, in order.
+    pub points: Vec<Point2<S>>,
+}
+
+/// A three-dimensional polyline, made of the segments between consecutive
+/// points.
+///
+/// ```rust
+/// use cgmath::{Point3, Polyline3};
+///
+/// let path = Polyline3::new(vec![
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(3.0, 0.0, 0.0),
+///     Point3::new(3.0, 4.0, 0.0),
+/// ]);
+/// assert_eq!(path.length(), 7.0);
+///
+/// let even = path.resample(8);
+/// assert_eq!(even.points[3], Point3::new(3.0, 0.0, 0.0));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Polyline3<S> {
+    /// The vertices of the polyline, in order.
+    pub points: Vec<Point3<S>>,
+}
+
+macro_rules! impl_polyline {
+    ($PolylineN:ident, $PointN:ident) => {
+        impl<S: BaseFloat> $PolylineN<S> {
+            /// Construct a polyline from its vertices.
+            #[inline]
+            pub fn new(points: Vec<$PointN<S>>) -> $PolylineN<S> {
+                $PolylineN { points }
+            }
+
+            /// The total length of the segments.
+            pub fn length(&self) -> S {
+                self.points
+                    .windows(2)
+                    .fold(S::zero(), |length, w| length + w[0].distance(w[1]))
+            }
+
+            /// The point at a given distance along the polyline, measured
+            /// from its first vertex. The distance is clamped to the length
+            /// of the polyline, and `None` is returned if it has no vertices.
+            pub fn point_at(&self, distance: S) -> Option<$PointN<S>> {
+                let mut remaining = distance;
+                for w in self.points.windows(2) {
+                    let segment = w[0].distance(w[1]);
+                    if remaining <= segment && segment > S::zero() {
+                        let t = (remaining / segment).max(S::zero());
+                        return Some(w[0] + (w[1] - w[0]) * t);
+                    }
+                    remaining -= segment;
+                }
+                self.points.last().cloned()
+            }
+
+            /// The index of the segment closest to `p`, and the parameter of
+            /// the closest point on it, from `0` at its start to `1` at its
+            /// end. Returns `None` if the polyline has no segments.
+            ///
+            /// The first of the closest segments is returned on ties.
+            pub fn closest_segment(&self, p: $PointN<S>) -> Option<(usize, S)> {
+                let mut closest = None;
+                let mut min_distance2 = S::infinity();
+                for (i, w) in self.points.windows(2).enumerate() {
+                    let t = segment_parameter(w[0], w[1], p);
+                    let distance2 = (w[0] + (w[1] - w[0]) * t).distance2(p);
+                    if distance2 < min_distance2 {
+                        closest = Some((i, t));
+                        min_distance2 = distance2;
+                    }
+                }
+                closest
+            }
+
+            /// Project `p` onto the polyline, returning the closest point and
+            /// its distance along the polyline from the first vertex, which is
+            /// the progress of something at `p` following it as a path.
+            ///
+            /// Returns `None` if the polyline has no vertices.
+            pub fn project(&self, p: $PointN<S>) -> Option<($PointN<S>, S)> {
+                let (i, t) = match self.closest_segment(p) {
+                    Some(closest) => closest,
+                    None => return self.points.first().map(|&first| (first, S::zero())),
+                };
+                let before = self.points[..i + 1]
+                    .windows(2)
+                    .fold(S::zero(), |length, w| length + w[0].distance(w[1]));
+                let (a, b) = (self.points[i], self.points[i + 1]);
+                Some((a + (b - a) * t, before + a.distance(b) * t))
+            }
+
+            /// The point `distance` further along the polyline than the
+            /// projection of `p`, clamped to the end of the polyline.
+            ///
+            /// Steering towards this point, as in pure pursuit, makes a
+            /// vehicle at `p` follow the polyline as a path. Returns `None` if
+            /// the polyline has no vertices.
+            ///
+            /// ```rust
+            /// use cgmath::{Point2, Polyline2};
+            ///
+            /// let path = Polyline2::new(vec![
+            ///     Point2::new(0.0, 0.0),
+            ///     Point2::new(10.0, 0.0),
+            ///     Point2::new(10.0, 10.0f64),
+            /// ]);
+            /// let (closest, progress) = path.project(Point2::new(8.0, 1.0)).unwrap();
+            /// assert_eq!((closest, progress), (Point2::new(8.0, 0.0), 8.0));
+            /// let ahead = path.lookahead(Point2::new(8.0, 1.0), 5.0);
+            /// assert_eq!(ahead, Some(Point2::new(10.0, 3.0)));
+            /// ```
+            pub fn lookahead(&self, p: $PointN<S>, distance: S) -> Option<$PointN<S>> {
+                let (_, progress) = self.project(p)?;
+                self.point_at(progress + distance)
+            }
+
+            /// Resample the polyline into `count` points evenly spaced along
+            /// its length, including both of its ends.
+            ///
+            /// The corners of the original polyline are not preserved unless
+            /// they happen to fall on one of the new points.
+            pub fn resample(&self, count: usize) -> $PolylineN<S> {
+                let first = match self.points.first() {
+                    Some(&first) if count > 1 => first,
+                    Some(&first) if count == 1 => return $PolylineN::new(vec![first]),
+                    _ => return $PolylineN::new(Vec::new()),
+                };
+
+                let spacing = self.length() / cast(count - 1).unwrap();
+                let mut points = Vec::with_capacity(count);
+                points.push(first);
+
+                // Walk along the segments once, carrying the distance left
+                // until the next sample.
+                let mut next = spacing;
+                let mut travelled = S::zero();
+                for w in self.points.windows(2) {
+                    let segment = w[0].distance(w[1]);
+                    while points.len() < count - 1
+                        && segment > S::zero()
+                        && next <= travelled + segment
+                    {
+                        let t = (next - travelled) / segment;
+                        points.push(w[0] + (w[1] - w[0]) * t);
+                        next = spacing * cast(points.len()).unwrap();
+                    }
+                    travelled += segment;
+                }
+                while points.len() < count {
+                    points.push(self.points[self.points.len() - 1]);
+                }
+                $PolylineN::new(points)
+            }
+
+            /// Simplify the polyline with the Douglas–Peucker algorithm,
+            /// removing every vertex that is closer than `tolerance` to the
+            /// simplified line. The first and last vertices are always kept.
+            pub fn simplify(&self, tolerance: S) -> $PolylineN<S> {
+                let n = self.points.len();
+                if n < 3 {
+                    return self.clone();
+                }
+
+                let tolerance2 = tolerance * tolerance;
+                let mut keep = vec![false; n];
+                keep[0] = true;
+                keep[n - 1] = true;
+
+                let mut stack = vec![(0, n - 1)];
+                while let Some((start, end)) = stack.pop() {
+                    let (a, b) = (self.points[start], self.points[end]);
+                    let mut farthest = None;
+                    let mut max_distance2 = tolerance2;
+                    for i in start + 1..end {
+                        let distance2 = segment_distance2(a, b, self.points[i]);
+                        if distance2 > max_distance2 {
+                            farthest = Some(i);
+                            max_distance2 = distance2;
+                        }
+                    }
+                    if let Some(i) = farthest {
+                        keep[i] = true;
+                        stack.push((start, i));
+                        stack.push((i, end));
+                    }
+                }
+
+                let points = self
+                    .points
+                    .iter()
+                    .zip(keep)
+                    .filter(|&(_, keep)| keep)
+                    .map(|(&p, _)| p)
+                    .collect();
+                $PolylineN::new(points)
+            }
+        }
+
+        impl<S> From<Vec<$PointN<S>>> for $PolylineN<S> {
+            #[inline]
+            fn from(points: Vec<$PointN<S>>) -> $PolylineN<S> {
+                $PolylineN { points }
+            }
+        }
+    };
+}
+
+/// The parameter, from `0` to `1`, of the point of the segment between `a`
+/// and `b` which is the closest to `p`.
+fn segment_parameter<S, P>(a: P, b: P, p: P) -> S
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    let ab = b - a;
+    let length2 = ab.magnitude2();
+    if length2 > S::zero() {
+        ((p - a).dot(ab) / length2).max(S::zero()).min(S::one())
+    } else {
+        S::zero()
+    }
+}
+
+/// The squared distance from `p` to the segment between `a` and `b`.
+pub(crate) fn segment_distance2<S, P>(a: P, b: P, p: P) -> S
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    let t = segment_parameter(a, b, p);
+    ((p - a) - (b - a) * t).magnitude2()
+}
+
+impl_polyline!(Polyline2, Point2);
+impl_polyline!(Polyline3, Point3);
+
+/// An orthonormal frame attached to a point of a curve.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CurveFrame<S> {
+    /// The point of the curve.
+    pub position: Point3<S>,
+    /// The direction of the curve.
+    pub tangent: Vector3<S>,
+    /// A direction perpendicular to the tangent.
+    pub normal: Vector3<S>,
+    /// The cross product of the tangent and the normal.
+    pub binormal: Vector3<S>,
+}
+
+impl<S: BaseFloat> CurveFrame<S> {
+    /// The rotation from the local frame to the space of the curve, with the
+    /// normal, binormal and tangent as its columns.
+    #[inline]
+    pub fn basis(&self) -> Matrix3<S> {
+        Matrix3::from_cols(self.normal, self.binormal, self.tangent)
+    }
+
+    /// Place a point of a two-dimensional cross-section in the plane of the
+    /// frame, with its `x` axis along the normal and its `y` axis along the
+    /// binormal.
+    #[inline]
+    pub fn profile_point(&self, p: Point2<S>) -> Point3<S> {
+        self.position + self.normal * p.x + self.binormal * p.y
+    }
+
+    /// The transform from the local frame to the space of the curve, so that
+    /// the local `x`, `y` and `z` axes follow the normal, binormal and
+    /// tangent, and the local origin is at the position.
+    pub fn to_matrix(&self) -> Matrix4<S> {
+        let mut m = Matrix4::from(self.basis());
+        m.w = self.position.to_homogeneous();
+        m
+    }
+}
+
+/// An arbitrary unit vector perpendicular to `v`, which must be a unit
+/// vector itself.
+pub(crate) fn any_perpendicular<S: BaseFloat>(v: Vector3<S>) -> Vector3<S> {
+    let (x, y, z) = (v.x.abs(), v.y.abs(), v.z.abs());
+    let axis = if x <= y && x <= z {
+        Vector3::unit_x()
+    } else if y <= z {
+        Vector3::unit_y()
+    } else {
+        Vector3::unit_z()
+    };
+    (axis - v * v.dot(axis)).normalize()
+}
+
+/// Reflect `v` in the plane perpendicular to `normal`, where `length2` is the
+/// squared length of `normal`.
+#[inline]
+fn reflect<S: BaseFloat>(v: Vector3<S>, normal: Vector3<S>, length2: S) -> Vector3<S> {
+    if length2 > S::zero() {
+        v - normal * ((v.dot(normal) + v.dot(normal)) / length2)
+    } else {
+        v
+    }
+}
+
+impl<S: BaseFloat> Polyline2<S> {
+    /// Revolve a profile around the `y` axis, as on a lathe, generating the
+    /// vertex rings of a surface of revolution.
+    ///
+    /// The `x` coordinate of each profile point is its distance from the
+    /// axis and `y` is its height. Returns `steps + 1` copies of the profile,
+    /// rotated by evenly spaced angles from zero to `sweep` inclusive, so for
+    /// a full turn the last ring coincides with the first one.
+    ///
+    /// ```rust
+    /// use cgmath::{Deg, Point2, Point3, Polyline2};
+    ///
+    /// let profile = Polyline2::new(vec![Point2::new(1.0, 0.0), Point2::new(1.0, 2.0)]);
+    /// let cylinder = profile.lathe(Deg(360.0), 4);
+    /// assert_eq!(cylinder.len(), 5);
+    /// assert_eq!(cylinder[0][1], Point3::new(1.0, 2.0, 0.0));
+    /// ```
+    pub fn lathe<A: Into<Rad<S>>>(&self, sweep: A, steps: usize) -> Vec<Vec<Point3<S>>> {
+        let sweep = sweep.into();
+        let divisions = cast::<_, S>(steps.max(1)).unwrap();
+        (0..=steps)
+            .map(|step| {
+                let angle = sweep * (cast::<_, S>(step).unwrap() / divisions);
+                let (sin, cos) = Rad::sin_cos(angle);
+                self.points
+                    .iter()
+                    .map(|p| Point3::new(p.x * cos, p.y, -p.x * sin))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Fit the polyline with circular arcs that stay closer than `tolerance`
+    /// to its vertices and to the middles of its segments, such as to output
+    /// smooth toolpaths or vector graphics.
+    ///
+    /// Each run of vertices is replaced by a biarc, a pair of arcs meeting
+    /// smoothly, with the tangents estimated at its ends, and runs that
+    /// deviate more than the tolerance are halved. A single segment whose
+    /// biarc bulges more than the tolerance from it is kept straight, so
+    /// sharp corners stay sharp, and elsewhere the arcs follow each other
+    /// tangentially. Sampled curves should be sampled densely enough for
+    /// their segments to be within the tolerance of them.
+    ///
+    /// ```rust
+    /// use cgmath::{Point2, Polyline2};
+    ///
+    /// // Samples of a half circle are fitted by arcs of the same circle.
+    /// let points = (0..=64)
+    ///     .map(|i| {
+    ///         let angle = std::f64::consts::PI * i as f64 / 64.0;
+    ///         Point2::new(angle.cos(), angle.sin())
+    ///     })
+    ///     .collect();
+    /// let arcs = Polyline2::new(points).fit_arcs(1e-3);
+    /// assert_eq!(arcs.len(), 2);
+    /// for arc in arcs {
+    ///     assert!((arc.radius().unwrap() - 1.0).abs() < 1e-3);
+    /// }
+    /// ```
+    pub fn fit_arcs(&self, tolerance: S) -> Vec<Arc2<S>> {
+        let mut points = self.points.clone();
+        points.dedup();
+        let n = points.len();
+        if n < 2 {
+            return Vec::new();
+        }
+
+        let direction = |i: usize| (points[i + 1] - points[i]).normalize();
+        let mut tangents: Vec<_> = (0..n)
+            .map(|i| {
+                if i == 0 || i + 1 == n {
+                    return direction(i.min(n - 2));
+                }
+                let bisector = direction(i - 1) + direction(i);
+                if bisector.magnitude2() > S::zero() {
+                    bisector.normalize()
+                } else {
+                    direction(i - 1)
+                }
+            })
+            .collect();
+        if n > 2 {
+            // Mirror the tangents next to the ends across the first and last
+            // chords, as for a circle through three vertices.
+            let two: S = cast(2).unwrap();
+            let (d, t) = (direction(0), tangents[1]);
+            tangents[0] = d * (two * d.dot(t)) - t;
+            let (d, t) = (direction(n - 2), tangents[n - 2]);
+            tangents[n - 1] = d * (two * d.dot(t)) - t;
+        }
+
+        let mut arcs = Vec::new();
+        let mut stack = vec![(0, n - 1)];
+        while let Some((i, j)) = stack.pop() {
+            let (first, second) = Arc2::biarc(points[i], tangents[i], points[j], tangents[j]);
+            let distance = |p: Point2<S>| first.distance(p).min(second.distance(p));
+            let fits = (i + 1..j).all(|k| distance(points[k]) <= tolerance)
+                && (i..j).all(|k| distance(points[k].midpoint(points[k + 1])) <= tolerance);
+            if fits {
+                arcs.push(first);
+                arcs.push(second);
+            } else if j == i + 1 {
+                arcs.push(Arc2::line(points[i], points[j]));
+            } else {
+                let middle = (i + j) / 2;
+                stack.push((middle, j));
+                stack.push((i, middle));
+            }
+        }
+        arcs
+    }
+}
+
+impl<S: BaseFloat> Polyline3<S> {
+    /// The shortest path from `start` to `goal` through a sequence of
+    /// portals, found with the funnel algorithm, also known as string
+    /// pulling.
+    ///
+    /// The portals are the edges shared by consecutive polygons of a
+    /// navigation mesh along a corridor, given as their `(left, right)`
+    /// endpoints as seen when walking from `start` to `goal` with `up`
+    /// pointing upwards. The path turns only at the endpoints of portals,
+    /// and left and right are decided by projecting along `up`, so the
+    /// portals may be at different heights.
+    ///
+    /// ```rust
+    /// use cgmath::{Point3, Polyline3, Vector3};
+    ///
+    /// // A corridor on the ground, turning left around the corner at (2, 0, -1).
+    /// let portals = [
+    ///     (Point3::new(1.0, 0.0, -1.0), Point3::new(1.0, 0.0, 1.0)),
+    ///     (Point3::new(2.0, 0.0, -1.0), Point3::new(3.0, 0.0, -1.0f64)),
+    /// ];
+    /// let start = Point3::new(0.0, 0.0, 0.0);
+    /// let goal = Point3::new(2.5, 0.0, -4.0);
+    /// let path = Polyline3::string_pull(start, &portals, goal, Vector3::unit_y());
+    /// assert_eq!(path.points, vec![start, Point3::new(2.0, 0.0, -1.0), goal]);
+    /// ```
+    pub fn string_pull(
+        start: Point3<S>,
+        portals: &[(Point3<S>, Point3<S>)],
+        goal: Point3<S>,
+        up: Vector3<S>,
+    ) -> Polyline3<S> {
+        // Positive when `c` is to the left of the line from `a` to `b`.
+        let side = |a: Point3<S>, b: Point3<S>, c: Point3<S>| (b - a).cross(c - a).dot(up);
+        let portal = |i: usize| {
+            if i == 0 {
+                (start, start)
+            } else if i <= portals.len() {
+                portals[i - 1]
+            } else {
+                (goal, goal)
+            }
+        };
+
+        let mut points = vec![start];
+        let (mut apex, mut left, mut right) = (start, start, start);
+        let (mut left_index, mut right_index) = (0, 0);
+        let mut i = 1;
+        while i <= portals.len() + 1 {
+            let (l, r) = portal(i);
+
+            // Narrow the funnel from the right, unless the new right side
+            // crosses the left one, which then becomes a corner of the path.
+            if side(apex, right, r) >= S::zero() {
+                if apex == right || side(apex, left, r) < S::zero() {
+                    right = r;
+                    right_index = i;
+                } else {
+                    apex = left;
+                    points.push(apex);
+                    right = apex;
+                    right_index = left_index;
+                    i = left_index + 1;
+                    continue;
+                }
+            }
+
+            // The same from the left.
+            if side(apex, left, l) <= S::zero() {
+                if apex == left || side(apex, right, l) > S::zero() {
+                    left = l;
+                    left_index = i;
+                } else {
+                    apex = right;
+                    points.push(apex);
+                    left = apex;
+                    left_index = right_index;
+                    i = right_index + 1;
+                    continue;
+                }
+            }
+
+            i += 1;
+        }
+
+        if points.last() != Some(&goal) {
+            points.push(goal);
+        }
+        Polyline3::new(points)
+    }
+
+    /// Sweep a two-dimensional cross-section along the polyline, generating
+    /// one ring of vertices per vertex of the polyline.
+    ///
+    /// The profile is placed in the parallel transport frame at every vertex,
+    /// as computed by `transport_frames`, with its `x` axis along the normal
+    /// and its `y` axis along the binormal. For control over the starting
+    /// orientation, map `CurveFrame::profile_point` over the frames from
+    /// `transport_frames_from` instead.
+    ///
+    /// ```rust
+    /// use cgmath::{Point2, Point3, Polyline3};
+    ///
+    /// let path = Polyline3::new(vec![Point3::new(0.0, 0.0, 0.0), Point3::new(0.0, 0.0, 5.0)]);
+    /// let square = [
+    ///     Point2::new(-1.0, -1.0),
+    ///     Point2::new(1.0, -1.0),
+    ///     Point2::new(1.0, 1.0),
+    ///     Point2::new(-1.0, 1.0),
+    /// ];
+    ///
+    /// let rings = path.extrude(&square);
+    /// assert_eq!(rings.len(), 2);
+    /// assert!(rings[1].iter().all(|p| p.z == 5.0));
+    /// ```
+    pub fn extrude(&self, profile: &[Point2<S>]) -> Vec<Vec<Point3<S>>> {
+        self.transport_frames()
+            .iter()
+            .map(|frame| profile.iter().map(|&p| frame.profile_point(p)).collect())
+            .collect()
+    }
+
+    /// The unit tangents of the polyline at each vertex, estimated from its
+    /// neighbours. Vertices where the tangent is undefined, such as repeated
+    /// points, reuse the previous tangent.
+    fn tangents(&self) -> Option<Vec<Vector3<S>>> {
+        let n = self.points.len();
+        let mut tangents = Vec::with_capacity(n);
+        let mut last = None;
+        for i in 0..n {
+            let (prev, next) = (
+                self.points[i.saturating_sub(1)],
+                self.points[(i + 1).min(n - 1)],
+            );
+            let direction = next - prev;
+            if direction.magnitude2() > S::zero() {
+                last = Some(direction.normalize());
+            }
+            tangents.push(last);
+        }
+
+        // Fill the tangents at the start, before the first defined one.
+        let first = tangents.iter().filter_map(|&t| t).next()?;
+        Some(tangents.into_iter().map(|t| t.unwrap_or(first)).collect())
+    }
+
+    /// Compute twist-free frames at each vertex of the polyline by parallel
+    /// transport, starting from an arbitrary normal.
+    ///
+    /// See `transport_frames_from` for details.
+    pub fn transport_frames(&self) -> Vec<CurveFrame<S>> {
+        match self.tangents() {
+            Some(tangents) => self.transport(tangents[0], any_perpendicular(tangents[0]), tangents),
+            None => Vec::new(),
+        }
+    }
+
+    /// Compute twist-free frames at each vertex of the polyline by parallel
+    /// transport, starting from the component of `normal` that is
+    /// perpendicular to the first tangent.
+    ///
+    /// Unlike Frenet frames, which follow the curvature and flip or spin
+    /// around inflection points and straight sections, parallel transport
+    /// frames rotate as little as possible from one vertex to the next. They
+    /// are computed with the double reflection method of Wang et al. On a
+    /// closed curve the last frame is generally twisted relative to the
+    /// first one.
+    ///
+    /// Returns no frames if every point of the polyline is the same.
+    ///
+    /// ```rust
+    /// use cgmath::{Point3, Polyline3, Vector3};
+    ///
+    /// let path = Polyline3::new(vec![
+    ///     Point3::new(0.0, 0.0, 0.0),
+    ///     Point3::new(0.0, 0.0, 1.0),
+    ///     Point3::new(0.0, 1.0, 2.0),
+    /// ]);
+    ///
+    /// // The path only bends around the x axis, so the normal never turns.
+    /// let frames = path.transport_frames_from(Vector3::unit_x());
+    /// assert_eq!(frames.len(), 3);
+    /// assert!(frames.iter().all(|frame| frame.normal == Vector3::unit_x()));
+    /// ```
+    pub fn transport_frames_from(&self, normal: Vector3<S>) -> Vec<CurveFrame<S>> {
+        let tangents = match self.tangents() {
+            Some(tangents) => tangents,
+            None => return Vec::new(),
+        };
+        let t = tangents[0];
+        let normal = normal - t * normal.dot(t);
+        let normal = if normal.magnitude2() > S::zero() {
+            normal.normalize()
+        } else {
+            any_perpendicular(t)
+        };
+        self.transport(t, normal, tangents)
+    }
+
+    fn transport(
+        &self,
+        tangent: Vector3<S>,
+        normal: Vector3<S>,
+        tangents: Vec<Vector3<S>>,
+    ) -> Vec<CurveFrame<S>> {
+        let mut frames = Vec::with_capacity(tangents.len());
+        let mut frame = CurveFrame {
+            position: self.points[0],
+            tangent,
+            normal,
+            binormal: tangent.cross(normal),
+        };
+        frames.push(frame);
+
+        for (i, &tangent) in tangents.iter().enumerate().skip(1) {
+            let position = self.points[i];
+
+            // Reflect the frame in the bisecting plane of the segment, then
+            // in the plane that takes the reflected tangent onto the new one.
+            let v1 = position - frame.position;
+            let c1 = v1.magnitude2();
+            let normal = reflect(frame.normal, v1, c1);
+            let reflected_tangent = reflect(frame.tangent, v1, c1);
+            let v2 = tangent - reflected_tangent;
+            let normal = reflect(normal, v2, v2.magnitude2());
+
+            // Remove the rounding errors that accumulate along long curves.
+            let normal = (normal - tangent * normal.dot(tangent)).normalize();
+            frame = CurveFrame {
+                position,
+                tangent,
+                normal,
+                binormal: tangent.cross(normal),
+            };
+            frames.push(frame);
+        }
+        frames
+    }
+}
@@ -0,0 +1,827 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rigid poses, with buffers of timestamped poses for snapshot
+//! interpolation and dead reckoning, and the smoothing and shaking of
+//! cameras.
+
+use std::collections::VecDeque;
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use euler::Euler;
+use matrix::Matrix4;
+use noise::Perlin;
+use num::BaseFloat;
+use point::Point2;
+use quaternion::Quaternion;
+use rotation::{Basis2, Rotation, Rotation2, Rotation3};
+use transform::{Decomposed, Transform};
+use vector::{Vector2, Vector3};
+
+/// A rigid pose in two dimensions, with a uniform scale.
+pub type Pose2<S> = Decomposed<Vector2<S>, Basis2<S>>;
+
+impl<S: BaseFloat> Pose2<S> {
+    /// The unscaled pose at `position`, facing `heading` counter-clockwise
+    /// from the `x` axis, like a vehicle seen from above.
+    pub fn from_heading<A: Into<Rad<S>>>(position: Point2<S>, heading: A) -> Pose2<S> {
+        Decomposed {
+            scale: S::one(),
+            rot: Basis2::from_angle(heading),
+            disp: position.to_vec(),
+        }
+    }
+
+    /// The position of the pose.
+    #[inline]
+    pub fn position(&self) -> Point2<S> {
+        Point2::from_vec(self.disp)
+    }
+
+    /// The direction of the local `x` axis of the pose, counter-clockwise
+    /// from the `x` axis, between `-π` and `π`.
+    pub fn heading(&self) -> Rad<S> {
+        let forward = self.rot.rotate_vector(Vector2::unit_x());
+        Rad::atan2(forward.y, forward.x)
+    }
+}
+
+/// A rigid pose in three dimensions, with a uniform scale.
+pub type Pose3<S> = Decomposed<Vector3<S>, Quaternion<S>>;
+
+impl<S: BaseFloat> Pose3<S> {
+    /// This pose expressed in the frame of `other`, such that
+    /// `other.concat(&self.relative_to(other))` is this pose.
+    ///
+    /// This is the measurement an edge of a pose graph holds between two
+    /// nodes, such as the odometry from one keyframe to the next. The
+    /// rotations should be unit quaternions.
+    pub fn relative_to(&self, other: &Pose3<S>) -> Pose3<S> {
+        let inverse = other.rot.conjugate();
+        Decomposed {
+            scale: self.scale / other.scale,
+            rot: inverse * self.rot,
+            disp: inverse.rotate_vector(self.disp - other.disp) / other.scale,
+        }
+    }
+
+    /// The residual between this pose and a `measured` one, as the
+    /// translation followed by the rotation vector of this pose relative to
+    /// the measured one, both in the frame of the measured pose.
+    ///
+    /// The residual is zero when the poses are the same and, for small
+    /// differences, varies linearly with them, which is what least squares
+    /// optimizers of pose graphs minimize. The scales are not compared.
+    ///
+    /// ```rust
+    /// use cgmath::{Deg, Pose3, Quaternion, Rotation3, Vector3};
+    /// # #[macro_use] extern crate approx;
+    /// # fn main() {
+    ///
+    /// let measured = Pose3 {
+    ///     scale: 1.0,
+    ///     rot: Quaternion::from_angle_z(Deg(90.0)),
+    ///     disp: Vector3::new(1.0, 0.0, 0.0),
+    /// };
+    /// let estimate = Pose3 {
+    ///     scale: 1.0,
+    ///     rot: Quaternion::from_angle_z(Deg(91.0)),
+    ///     disp: Vector3::new(1.0, 0.5, 0.0),
+    /// };
+    /// let error = estimate.error(&measured);
+    /// // Half a unit forward in the measured frame, and a degree about z.
+    /// assert_relative_eq!(error[0], 0.5, epsilon = 1e-12);
+    /// assert_relative_eq!(error[5], 1f64.to_radians(), epsilon = 1e-12);
+    /// # }
+    /// ```
+    pub fn error(&self, measured: &Pose3<S>) -> [S; 6] {
+        let relative = self.relative_to(measured);
+        let t = relative.disp;
+        let r = rotation_vector(relative.rot);
+        [t.x, t.y, t.z, r.x, r.y, r.z]
+    }
+}
+
+/// Dead-reckon the pose of an object moving with constant velocities, `dt`
+/// after it was at `pose`.
+///
+/// The displacement moves along `linear_velocity`, in units per unit of
+/// time, and the rotation turns around `angular_velocity`, whose direction
+/// is the world-space axis and whose length is the rate in radians per unit
+/// of time. Both motions are integrated exactly. The time is clamped
+/// between zero and `max_dt`, so that objects whose updates stop arriving
+/// do not drift away indefinitely.
+///
+/// ```rust
+/// use cgmath::{extrapolate, Pose3, Quaternion, Rotation3, Rad, Vector3};
+/// use cgmath::prelude::*;
+///
+/// let pose = Pose3 { scale: 1.0, rot: Quaternion::one(), disp: Vector3::new(0.0, 0.0, 0.0f64) };
+/// let spin = Vector3::new(0.0, 0.0, 1.0);
+/// let moved = extrapolate(&pose, Vector3::new(2.0, 0.0, 0.0), spin, 0.5, 1.0);
+/// assert_eq!(moved.disp, Vector3::new(1.0, 0.0, 0.0));
+/// assert!(moved.rot.approx_eq_rotation(&Quaternion::from_angle_z(Rad(0.5))));
+///
+/// // Past `max_dt`, the object stops.
+/// let stopped = extrapolate(&pose, Vector3::new(2.0, 0.0, 0.0), spin, 10.0, 1.0);
+/// assert_eq!(stopped.disp, Vector3::new(2.0, 0.0, 0.0));
+/// ```
+pub fn extrapolate<S: BaseFloat>(
+    pose: &Pose3<S>,
+    linear_velocity: Vector3<S>,
+    angular_velocity: Vector3<S>,
+    dt: S,
+    max_dt: S,
+) -> Pose3<S> {
+    let dt = dt.max(S::zero()).min(max_dt);
+    let rate = angular_velocity.magnitude();
+    let rot = if rate > S::zero() {
+        let step = Quaternion::from_axis_angle(angular_velocity / rate, Rad(rate * dt));
+        (step * pose.rot).normalize()
+    } else {
+        pose.rot
+    };
+    Decomposed {
+        scale: pose.scale,
+        rot,
+        disp: pose.disp + linear_velocity * dt,
+    }
+}
+
+/// The transformation taking the world seen through `portal_a` to the
+/// world in front of `portal_b`, for rendering linked portals.
+///
+/// The front of a portal faces its local `z` axis, with `y` up. Looking
+/// into the front of `portal_a` shows what lies in front of `portal_b`, so
+/// that scene is rendered from the camera moved by this transformation,
+/// which is applied to the left of the model matrix of the camera. It
+/// includes the half turn about `y` which makes things entering one portal
+/// come out of the front of the other rather than from behind it. Objects
+/// passing through the portals are teleported by the same transformation.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{portal_transform, Deg, Point3, Pose3, Quaternion, Rotation3, Vector3};
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// let a = Pose3::from_translation(Vector3::new(0.0, 0.0, 0.0));
+/// let b = Pose3::from_translation(Vector3::new(10.0, 0.0, 0.0))
+///     .with_rotation(Quaternion::from_angle_y(Deg(90.0)));
+/// let m = portal_transform(&a, &b);
+/// // A camera two units in front of `a` is moved two units behind `b`.
+/// let eye = m.transform_point(Point3::new(0.0, 0.0, 2.0));
+/// assert_relative_eq!(eye, Point3::new(8.0, 0.0, 0.0), epsilon = 1e-12);
+/// # }
+/// ```
+pub fn portal_transform<S: BaseFloat>(portal_a: &Pose3<S>, portal_b: &Pose3<S>) -> Matrix4<S> {
+    let flip = Quaternion::new(S::zero(), S::zero(), S::one(), S::zero());
+    let half_turn = Pose3::from_rotation(flip);
+    let into_a = Pose3::identity().relative_to(portal_a);
+    Matrix4::from(portal_b.concat(&half_turn).concat(&into_a))
+}
+
+/// Move `current` smoothly towards `target`, like a critically damped
+/// spring which takes about `smooth_time` to get there, and return the new
+/// position.
+///
+/// `velocity` is the state of the motion, which is updated, and should
+/// start at zero. The speed never exceeds `max_speed`, and the motion never
+/// overshoots the target. This is the approximation of the exact critically
+/// damped motion from "Critically Damped Ease-In/Ease-Out Smoothing" by
+/// Thomas Lowe, in Game Programming Gems 4, which is stable for every time
+/// step `dt`.
+pub fn smooth_damp<S: BaseFloat, V: InnerSpace<Scalar = S>>(
+    current: V,
+    target: V,
+    velocity: &mut V,
+    smooth_time: S,
+    max_speed: S,
+    dt: S,
+) -> V {
+    let two = S::one() + S::one();
+    let omega = two / smooth_time.max(S::default_epsilon());
+    let x = omega * dt;
+    let (a, b): (S, S) = (cast(0.48).unwrap(), cast(0.235).unwrap());
+    let decay = S::one() / (S::one() + x + a * x * x + b * x * x * x);
+
+    let mut change = current - target;
+    let max_change = max_speed * smooth_time;
+    if change.magnitude2() > max_change * max_change {
+        change = change.normalize_to(max_change);
+    }
+    let temp = (*velocity + change * omega) * dt;
+    *velocity = (*velocity - temp * omega) * decay;
+    let result = current - change + (change + temp) * decay;
+
+    // Stop at the target rather than going past it.
+    if (target - current).dot(result - target) > S::zero() {
+        *velocity = V::zero();
+        return target;
+    }
+    result
+}
+
+/// The parameters of a camera following a target with `follow`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FollowParams<S> {
+    /// About how long the camera takes to catch up with the target.
+    pub smooth_time: S,
+    /// The highest speed of the camera.
+    pub max_speed: S,
+    /// The highest angular speed of the camera.
+    pub max_angular_speed: Rad<S>,
+}
+
+impl<S: BaseFloat> FollowParams<S> {
+    /// Construct the parameters of a camera following a target.
+    #[inline]
+    pub fn new(smooth_time: S, max_speed: S, max_angular_speed: Rad<S>) -> FollowParams<S> {
+        FollowParams {
+            smooth_time,
+            max_speed,
+            max_angular_speed,
+        }
+    }
+}
+
+/// Move a camera smoothly towards a target pose, such as the pose behind a
+/// character of a third-person camera, and return its new pose.
+///
+/// The position follows the target with `smooth_damp`, where `velocity` is
+/// the velocity of the camera, which is updated and should start at zero.
+/// The orientation turns towards the target along the shortest path, by
+/// the fraction `1 - exp(-dt / smooth_time)` of the angle between them
+/// every step, but never faster than the maximum angular speed. The scale
+/// of the camera is kept, and the rotations should be unit quaternions.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{follow, Deg, FollowParams, Pose3, Quaternion, Vector3};
+///
+/// let params = FollowParams::new(0.3f64, 20.0, Deg(90.0).into());
+/// let target = Pose3::from_translation(Vector3::new(10.0, 2.0, 0.0))
+///     .with_rotation(Quaternion::from_angle_y(Deg(120.0)));
+/// let mut camera = Pose3::identity();
+/// let mut velocity = Vector3::zero();
+/// for _ in 0..300 {
+///     camera = follow(&camera, &mut velocity, &target, &params, 1.0 / 60.0);
+/// }
+/// assert!((camera.disp - target.disp).magnitude() < 1e-3);
+/// assert!(camera.rot.dot(target.rot).abs() > 1.0 - 1e-6);
+/// ```
+pub fn follow<S: BaseFloat>(
+    camera: &Pose3<S>,
+    velocity: &mut Vector3<S>,
+    target: &Pose3<S>,
+    params: &FollowParams<S>,
+    dt: S,
+) -> Pose3<S> {
+    let disp = smooth_damp(
+        camera.disp,
+        target.disp,
+        velocity,
+        params.smooth_time,
+        params.max_speed,
+        dt,
+    );
+
+    let goal = if camera.rot.dot(target.rot) < S::zero() {
+        -target.rot
+    } else {
+        target.rot
+    };
+    let two = S::one() + S::one();
+    let angle = Rad::acos(camera.rot.dot(goal).min(S::one())).0 * two;
+    let rot = if angle > S::zero() {
+        let smooth = S::one() - (-dt / params.smooth_time).exp();
+        let limit = (params.max_angular_speed.0 * dt / angle).max(S::zero());
+        camera.rot.slerp(goal, smooth.min(limit))
+    } else {
+        camera.rot
+    };
+
+    Decomposed {
+        scale: camera.scale,
+        rot,
+        disp,
+    }
+}
+
+/// The limits of an agent moved by `kinematic_step`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KinematicParams<S> {
+    /// The highest speed of the agent.
+    pub max_speed: S,
+    /// The highest acceleration of the agent.
+    pub max_acceleration: S,
+    /// The distance to the target within which the agent slows down.
+    pub slowing_radius: S,
+    /// The highest angular speed of the agent.
+    pub max_angular_speed: Rad<S>,
+    /// The highest angular acceleration of the agent, per unit of time.
+    pub max_angular_acceleration: Rad<S>,
+    /// The angle to the target orientation within which the agent slows its
+    /// turning down.
+    pub slowing_angle: Rad<S>,
+    /// About how long the agent takes to reach the velocities it would like
+    /// to have.
+    pub time_to_target: S,
+}
+
+impl<S: BaseFloat> KinematicParams<S> {
+    /// Construct the limits of an agent, which slows down within the
+    /// distance and the angle it takes to stop at full acceleration, and
+    /// reaches the velocities it would like within a tenth of a unit of
+    /// time.
+    pub fn new(
+        max_speed: S,
+        max_acceleration: S,
+        max_angular_speed: Rad<S>,
+        max_angular_acceleration: Rad<S>,
+    ) -> KinematicParams<S> {
+        let two: S = cast(2).unwrap();
+        KinematicParams {
+            max_speed,
+            max_acceleration,
+            slowing_radius: max_speed * max_speed / (two * max_acceleration),
+            max_angular_speed,
+            max_angular_acceleration,
+            slowing_angle: Rad(
+                max_angular_speed.0 * max_angular_speed.0 / (two * max_angular_acceleration.0)
+            ),
+            time_to_target: cast(0.1).unwrap(),
+        }
+    }
+}
+
+/// The pose and velocities of an agent moved by `kinematic_step`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KinematicState<S: BaseFloat> {
+    /// The pose of the agent.
+    pub pose: Pose3<S>,
+    /// The velocity of the agent, in units per unit of time.
+    pub velocity: Vector3<S>,
+    /// The angular velocity of the agent, whose direction is the world-space
+    /// axis and whose length is the rate in radians per unit of time.
+    pub angular_velocity: Vector3<S>,
+}
+
+impl<S: BaseFloat> KinematicState<S> {
+    /// The state of an agent at rest at `pose`.
+    #[inline]
+    pub fn at_rest(pose: Pose3<S>) -> KinematicState<S> {
+        KinematicState {
+            pose,
+            velocity: Vector3::zero(),
+            angular_velocity: Vector3::zero(),
+        }
+    }
+}
+
+/// Move an agent towards the `target` pose, which moves with
+/// `target_velocity`, for `dt`, and return its new state.
+///
+/// The agent arrives at the target position, slowing down within the slowing
+/// radius, while matching the velocity of the target so that it can keep up
+/// with it, and turns towards the target orientation along the shortest
+/// path, slowing its turn down within the slowing angle. Neither motion aims
+/// past the target within one step. Each velocity changes towards the one
+/// the agent would like to have within the time to target, but no faster
+/// than the largest accelerations, and stays below the largest speeds. The
+/// motion is then integrated with the new velocities, as with
+/// `extrapolate`. The scale of the agent is kept, and the rotations should
+/// be unit quaternions.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{kinematic_step, Deg, KinematicParams, KinematicState, Pose3, Quaternion, Vector3};
+///
+/// let params = KinematicParams::new(5.0f64, 10.0, Deg(180.0).into(), Deg(360.0).into());
+/// let target = Pose3::from_translation(Vector3::new(10.0, 0.0, 5.0))
+///     .with_rotation(Quaternion::from_angle_y(Deg(60.0)));
+/// let mut agent = KinematicState::at_rest(Pose3::identity());
+/// for _ in 0..600 {
+///     agent = kinematic_step(&agent, &target, Vector3::zero(), &params, 1.0 / 60.0);
+/// }
+/// assert!((agent.pose.disp - target.disp).magnitude() < 1e-3);
+/// assert!(agent.pose.rot.dot(target.rot).abs() > 1.0 - 1e-6);
+/// ```
+pub fn kinematic_step<S: BaseFloat>(
+    state: &KinematicState<S>,
+    target: &Pose3<S>,
+    target_velocity: Vector3<S>,
+    params: &KinematicParams<S>,
+    dt: S,
+) -> KinematicState<S> {
+    // Reaching the desired velocities within one step at most, so that
+    // long steps do not overshoot them, and the desired speeds cover at
+    // most what is left within the step.
+    let time = params.time_to_target.max(dt);
+
+    let offset = target.disp - state.pose.disp;
+    let distance = offset.magnitude();
+    let speed = if distance < params.slowing_radius {
+        params.max_speed * distance / params.slowing_radius
+    } else {
+        params.max_speed
+    };
+    let speed = if dt > S::zero() {
+        speed.min(distance / dt)
+    } else {
+        speed
+    };
+    let desired = if distance > S::zero() {
+        offset * (speed / distance) + target_velocity
+    } else {
+        target_velocity
+    };
+    let acceleration = limit((desired - state.velocity) / time, params.max_acceleration);
+    let velocity = limit(state.velocity + acceleration * dt, params.max_speed);
+
+    let turn = rotation_vector(target.rot * state.pose.rot.conjugate());
+    let angle = turn.magnitude();
+    let angular_speed = if angle < params.slowing_angle.0 {
+        params.max_angular_speed.0 * angle / params.slowing_angle.0
+    } else {
+        params.max_angular_speed.0
+    };
+    let angular_speed = if dt > S::zero() {
+        angular_speed.min(angle / dt)
+    } else {
+        angular_speed
+    };
+    let desired = if angle > S::zero() {
+        turn * (angular_speed / angle)
+    } else {
+        Vector3::zero()
+    };
+    let angular_acceleration = limit(
+        (desired - state.angular_velocity) / time,
+        params.max_angular_acceleration.0,
+    );
+    let angular_velocity = limit(
+        state.angular_velocity + angular_acceleration * dt,
+        params.max_angular_speed.0,
+    );
+
+    KinematicState {
+        pose: extrapolate(&state.pose, velocity, angular_velocity, dt, dt),
+        velocity,
+        angular_velocity,
+    }
+}
+
+/// Scale `v` down to the length `max` if it is longer.
+fn limit<S: BaseFloat>(v: Vector3<S>, max: S) -> Vector3<S> {
+    let length = v.magnitude();
+    if length > max {
+        v * (max / length)
+    } else {
+        v
+    }
+}
+
+/// A generator of camera shake, producing random offsets of position and
+/// orientation which grow with an amount of trauma that fades over time.
+///
+/// This follows "Math for Game Programmers: Juicing Your Cameras With Math"
+/// by Squirrel Eiserloh: impacts add trauma up to `1`, and the shake is the
+/// square of the trauma, which makes small impacts subtle and large ones
+/// violent. Every axis of the offsets follows its own channel of Perlin
+/// noise, so the camera moves smoothly rather than jittering, and stays
+/// within `max_offset` and `max_angles` at full trauma. The offsets are in
+/// the local frame of the camera, and are applied with
+/// `camera.concat(&offset)`.
+///
+/// ```rust
+/// use cgmath::{Shake, Vector3};
+///
+/// let (max_offset, max_angles) = (Vector3::new(0.2, 0.2, 0.0f64), Vector3::new(0.0, 0.0, 0.1));
+/// let mut shake = Shake::new(3, max_offset, max_angles, 15.0);
+/// shake.add_trauma(0.8);
+/// let offset = shake.update(1.0 / 60.0);
+/// assert!(offset.disp.x.abs() <= 0.2 * 0.8 * 0.8);
+/// for _ in 0..120 {
+///     shake.update(1.0 / 60.0);
+/// }
+/// // The trauma fades by one per second.
+/// assert_eq!(shake.trauma, 0.0);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Shake<S> {
+    /// The current amount of trauma, from `0` to `1`.
+    pub trauma: S,
+    /// The trauma lost per unit of time.
+    pub decay: S,
+    /// The largest offset of the position along each local axis.
+    pub max_offset: Vector3<S>,
+    /// The largest rotations about each local axis, in radians.
+    pub max_angles: Vector3<S>,
+    /// How fast the offsets change, in lattice cells of the noise per unit
+    /// of time.
+    pub frequency: S,
+    /// The time elapsed, which is the position along the noise.
+    pub time: S,
+    /// The noise driving the offsets.
+    pub noise: Perlin,
+}
+
+impl<S: BaseFloat> Shake<S> {
+    /// Create a shake generator without trauma, whose trauma fades by `1`
+    /// per unit of time.
+    pub fn new(
+        seed: u32,
+        max_offset: Vector3<S>,
+        max_angles: Vector3<S>,
+        frequency: S,
+    ) -> Shake<S> {
+        Shake {
+            trauma: S::zero(),
+            decay: S::one(),
+            max_offset,
+            max_angles,
+            frequency,
+            time: S::zero(),
+            noise: Perlin::new(seed),
+        }
+    }
+
+    /// Add trauma, such as from an impact, up to a total of `1`.
+    #[inline]
+    pub fn add_trauma(&mut self, amount: S) {
+        self.trauma = (self.trauma + amount).max(S::zero()).min(S::one());
+    }
+
+    /// The offset of the camera at the current time and trauma.
+    pub fn offset(&self) -> Pose3<S> {
+        let shake = self.trauma * self.trauma;
+        let x = self.time * self.frequency;
+        let half: S = cast(0.5).unwrap();
+        // Sample the noise between the lattice points, where it is zero.
+        let channel = |i: usize| {
+            let y = cast::<_, S>(i).unwrap() + half;
+            self.noise
+                .sample2(Point2::new(x, y))
+                .max(-S::one())
+                .min(S::one())
+                * shake
+        };
+        let disp = Vector3::new(
+            self.max_offset.x * channel(0),
+            self.max_offset.y * channel(1),
+            self.max_offset.z * channel(2),
+        );
+        let angles = Euler::new(
+            Rad(self.max_angles.x * channel(3)),
+            Rad(self.max_angles.y * channel(4)),
+            Rad(self.max_angles.z * channel(5)),
+        );
+        Decomposed {
+            scale: S::one(),
+            rot: Quaternion::from(angles),
+            disp,
+        }
+    }
+
+    /// Advance the shake by `dt`, fading the trauma, and return the offset
+    /// of the camera at the new time.
+    pub fn update(&mut self, dt: S) -> Pose3<S> {
+        self.time += dt;
+        self.trauma = (self.trauma - self.decay * dt).max(S::zero());
+        self.offset()
+    }
+}
+
+/// How a `PoseBuffer` interpolates positions between samples.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PoseInterpolation {
+    /// Move in a straight line between consecutive samples.
+    Linear,
+    /// Follow a cubic Hermite curve through the samples, with velocities
+    /// estimated from the neighbouring samples, which avoids sudden changes
+    /// of direction at the samples.
+    Hermite,
+}
+
+/// A bounded buffer of timestamped poses, which are interpolated and
+/// extrapolated at arbitrary times.
+///
+/// This is what networked games use to render remote objects smoothly
+/// from the snapshots they receive: the positions and scales are
+/// interpolated as set by `interpolation`, and the rotations are slerped
+/// along the shortest path. Past the last sample, the motion between the
+/// last two samples is extrapolated for at most `max_extrapolation`, after
+/// which the pose stops.
+///
+/// ```rust
+/// use cgmath::{Pose3, PoseBuffer, Quaternion, Vector3};
+/// use cgmath::prelude::*;
+///
+/// let pose = |x| Pose3 { scale: 1.0, rot: Quaternion::one(), disp: Vector3::new(x, 0.0, 0.0f64) };
+///
+/// let mut buffer = PoseBuffer::new(32, 0.25);
+/// buffer.push(0.0, pose(0.0));
+/// buffer.push(0.1, pose(1.0));
+///
+/// assert_eq!(buffer.sample(0.05).unwrap().disp.x, 0.5);
+/// assert!((buffer.sample(0.2).unwrap().disp.x - 2.0).abs() < 1e-12);
+/// // The extrapolation stops 0.25 after the last sample.
+/// assert!((buffer.sample(1.0).unwrap().disp.x - 3.5).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PoseBuffer<S: BaseFloat> {
+    samples: VecDeque<(S, Pose3<S>)>,
+    capacity: usize,
+    /// How positions and scales are interpolated between samples.
+    pub interpolation: PoseInterpolation,
+    /// The longest time past the last sample that the motion is
+    /// extrapolated for.
+    pub max_extrapolation: S,
+}
+
+impl<S: BaseFloat> PoseBuffer<S> {
+    /// Create an empty buffer keeping the `capacity` most recent samples,
+    /// and interpolating linearly.
+    ///
+    /// # Panics
+    ///
+    /// If `capacity` is zero.
+    pub fn new(capacity: usize, max_extrapolation: S) -> PoseBuffer<S> {
+        assert!(
+            capacity > 0,
+            "A pose buffer needs room for at least one sample"
+        );
+        PoseBuffer {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+            interpolation: PoseInterpolation::Linear,
+            max_extrapolation,
+        }
+    }
+
+    /// The number of samples in the buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether the buffer has no samples.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Remove every sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// The most recent sample, with its time.
+    #[inline]
+    pub fn latest(&self) -> Option<(S, Pose3<S>)> {
+        self.samples.back().cloned()
+    }
+
+    /// Add the pose sampled at `time`.
+    ///
+    /// Samples arriving out of order are put in their place, and a sample
+    /// at the same time as an existing one replaces it. When the buffer is
+    /// full, the oldest sample is dropped.
+    pub fn push(&mut self, time: S, pose: Pose3<S>) {
+        let index = self.samples.iter().rposition(|&(t, _)| t <= time);
+        match index {
+            Some(i) if self.samples[i].0 == time => self.samples[i].1 = pose,
+            Some(i) => self.samples.insert(i + 1, (time, pose)),
+            None if self.samples.len() == self.capacity => return,
+            None => self.samples.push_front((time, pose)),
+        }
+        if self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    /// The pose at `time`, or `None` if the buffer is empty.
+    ///
+    /// Before the first sample, this is the first pose.
+    pub fn sample(&self, time: S) -> Option<Pose3<S>> {
+        let n = self.samples.len();
+        let &(first_time, first) = self.samples.front()?;
+        if time <= first_time || n == 1 {
+            return Some(first);
+        }
+
+        let (last_time, last) = self.samples[n - 1];
+        if time >= last_time {
+            let (previous_time, previous) = self.samples[n - 2];
+            let elapsed = (time - last_time).min(self.max_extrapolation);
+            return Some(extrapolate_from(
+                &previous,
+                &last,
+                elapsed / (last_time - previous_time),
+            ));
+        }
+
+        // The first sample after `time`, which is neither the first nor
+        // past the last one.
+        let i = self.samples.iter().position(|&(t, _)| t > time).unwrap();
+        let (t0, p0) = self.samples[i - 1];
+        let (t1, p1) = self.samples[i];
+        let dt = t1 - t0;
+        let amount = (time - t0) / dt;
+
+        let disp = match self.interpolation {
+            PoseInterpolation::Linear => p0.disp.lerp(p1.disp, amount),
+            PoseInterpolation::Hermite => {
+                let v0 = self.velocity(i - 1) * dt;
+                let v1 = self.velocity(i) * dt;
+                hermite(p0.disp, v0, p1.disp, v1, amount)
+            }
+        };
+        Some(Decomposed {
+            scale: p0.scale + (p1.scale - p0.scale) * amount,
+            rot: shortest_slerp(p0.rot, p1.rot, amount),
+            disp,
+        })
+    }
+
+    /// The velocity at sample `i`, from the finite difference between its
+    /// neighbours.
+    fn velocity(&self, i: usize) -> Vector3<S> {
+        let before = if i == 0 { 0 } else { i - 1 };
+        let after = (i + 1).min(self.samples.len() - 1);
+        let (t0, p0) = self.samples[before];
+        let (t1, p1) = self.samples[after];
+        (p1.disp - p0.disp) / (t1 - t0)
+    }
+}
+
+/// The rotation vector of a unit quaternion, whose direction is the axis of
+/// the rotation and whose length is its angle in radians, for the shortest
+/// of the two opposite rotations the quaternion and its opposite represent.
+fn rotation_vector<S: BaseFloat>(q: Quaternion<S>) -> Vector3<S> {
+    let q = if q.s < S::zero() { -q } else { q };
+    q.ln().v * (S::one() + S::one())
+}
+
+/// Slerp between two unit quaternions along the shortest path.
+fn shortest_slerp<S: BaseFloat>(a: Quaternion<S>, b: Quaternion<S>, amount: S) -> Quaternion<S> {
+    let b = if a.dot(b) < S::zero() { -b } else { b };
+    a.slerp(b, amount).normalize()
+}
+
+/// Evaluate the cubic Hermite curve from `p0` to `p1` with the tangents
+/// `m0` and `m1`, at `t` between `0` and `1`.
+fn hermite<S: BaseFloat>(
+    p0: Vector3<S>,
+    m0: Vector3<S>,
+    p1: Vector3<S>,
+    m1: Vector3<S>,
+    t: S,
+) -> Vector3<S> {
+    let two = S::one() + S::one();
+    let three = two + S::one();
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h00 = two * t3 - three * t2 + S::one();
+    let h10 = t3 - two * t2 + t;
+    let h01 = three * t2 - two * t3;
+    let h11 = t3 - t2;
+    p0 * h00 + m0 * h10 + p1 * h01 + m1 * h11
+}
+
+/// Continue the motion from `previous` to `last` for `amount` times the
+/// interval between them.
+fn extrapolate_from<S: BaseFloat>(previous: &Pose3<S>, last: &Pose3<S>, amount: S) -> Pose3<S> {
+    let mut step = last.rot * previous.rot.conjugate();
+    if step.s < S::zero() {
+        step = -step;
+    }
+    Decomposed {
+        scale: last.scale + (last.scale - previous.scale) * amount,
+        rot: (step.scaled(amount) * last.rot).normalize(),
+        disp: last.disp + (last.disp - previous.disp) * amount,
+    }
+}
@@ -0,0 +1,253 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Floating point helpers that avoid catastrophic cancellation.
+//!
+//! Subtracting two nearly equal floats cancels their leading digits, leaving
+//! only the rounding errors of how they were computed. The naive quadratic
+//! formula, cross products of the edges of long thin triangles, and dot
+//! products of nearly orthogonal vectors all suffer from this. The functions
+//! here use error-free transformations, computing the rounding error of an
+//! operation exactly, and fused multiply-adds to stay accurate in these
+//! cases.
+//!
+//! ```rust
+//! use cgmath::precision::stable_quadratic;
+//!
+//! // The naive formula computes the small root as the difference of two
+//! // numbers close to 1e8, and loses half of its digits.
+//! let (small, large) = stable_quadratic(1.0f64, -1.0e8, 1.0).unwrap();
+//! assert_eq!(small, 1.0e-8);
+//! assert_eq!(large, 1.0e8);
+//! ```
+
+use std::iter::FromIterator;
+
+use num_traits::cast;
+
+use structure::*;
+
+use num::BaseFloat;
+
+/// Add two floats, returning the rounded sum and its exact rounding error.
+#[inline]
+pub fn two_sum<S: BaseFloat>(a: S, b: S) -> (S, S) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    (x, (a - a_virtual) + (b - b_virtual))
+}
+
+/// Multiply two floats, returning the rounded product and its exact rounding
+/// error.
+#[inline]
+pub fn two_product<S: BaseFloat>(a: S, b: S) -> (S, S) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+/// `a * b - c * d`, accurate to a few ulps even when the two products nearly
+/// cancel, using Kahan's algorithm.
+#[inline]
+pub fn difference_of_products<S: BaseFloat>(a: S, b: S, c: S, d: S) -> S {
+    let cd = c * d;
+    let error = (-c).mul_add(d, cd);
+    a.mul_add(b, -cd) + error
+}
+
+/// The real roots of `a * x^2 + b * x + c`, in increasing order, or `None`
+/// if it has none.
+///
+/// Unlike the textbook formula, this never subtracts nearly equal numbers:
+/// the discriminant is computed with `difference_of_products`, and the root
+/// of smallest magnitude is derived from the other one with Vieta's
+/// formula. A double root is returned twice, and so is the root of a linear
+/// polynomial when `a` is zero.
+pub fn stable_quadratic<S: BaseFloat>(a: S, b: S, c: S) -> Option<(S, S)> {
+    if a == S::zero() {
+        if b == S::zero() {
+            return None;
+        }
+        let x = -c / b;
+        return Some((x, x));
+    }
+    let four: S = S::one() + S::one() + S::one() + S::one();
+    let discriminant = difference_of_products(b, b, four * a, c);
+    if discriminant < S::zero() {
+        return None;
+    }
+    let q = -(b + b.signum() * discriminant.sqrt()) / (S::one() + S::one());
+    if q == S::zero() {
+        // Only when `b` and `c` are both zero.
+        return Some((S::zero(), S::zero()));
+    }
+    let (x0, x1) = (q / a, c / q);
+    Some(if x0 <= x1 { (x0, x1) } else { (x1, x0) })
+}
+
+/// The dot product of two vectors, as accurate as if it was computed with
+/// twice the working precision and then rounded.
+///
+/// This is the `Dot2` algorithm of Ogita, Rump and Oishi, in "Accurate Sum
+/// and Dot Product". It is much more accurate than `dot` for nearly
+/// orthogonal vectors, or vectors with components of very different
+/// magnitudes, at the cost of a few more operations.
+///
+/// ```rust
+/// use cgmath::precision::compensated_dot;
+/// use cgmath::prelude::*;
+/// use cgmath::Vector3;
+///
+/// let a = Vector3::new(1.0e16, 1.0, -1.0e16);
+/// let b = Vector3::new(1.0, 1.0, 1.0);
+/// assert_eq!(a.dot(b), 0.0);
+/// assert_eq!(compensated_dot(a, b), 1.0);
+/// ```
+pub fn compensated_dot<V, S>(a: V, b: V) -> S
+where
+    V: Array<Element = S> + Copy,
+    S: BaseFloat,
+{
+    let (mut sum, mut error) = two_product(a[0], b[0]);
+    for i in 1..V::len() {
+        let (product, product_error) = two_product(a[i], b[i]);
+        let (s, sum_error) = two_sum(sum, product);
+        sum = s;
+        error += product_error + sum_error;
+    }
+    sum + error
+}
+
+/// The length of a vector, computed with `compensated_dot`.
+#[inline]
+pub fn compensated_magnitude<V, S>(v: V) -> S
+where
+    V: Array<Element = S> + Copy,
+    S: BaseFloat,
+{
+    compensated_dot(v, v).sqrt()
+}
+
+/// An accumulator summing vectors with Kahan's compensated summation.
+///
+/// Adding many vectors one by one loses the low bits of each of them once
+/// the running sum is much larger, so the error of a naive sum grows with
+/// the number of terms. Kahan summation keeps track of the rounding error of
+/// every addition and feeds it back into the next one, which keeps the error
+/// independent of the number of terms.
+///
+/// Points can be accumulated through their position vectors, as
+/// `compensated_centroid` does.
+///
+/// ```rust
+/// use cgmath::precision::KahanSum;
+/// use cgmath::Vector2;
+///
+/// let mut sum = KahanSum::new();
+/// for _ in 0..1_000_000 {
+///     sum.add(Vector2::new(0.1f32, 1.0));
+/// }
+/// assert_eq!(sum.sum(), Vector2::new(100000.0, 1000000.0));
+/// assert_eq!(sum.mean(), Some(Vector2::new(0.1, 1.0)));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KahanSum<V> {
+    sum: V,
+    compensation: V,
+    count: usize,
+}
+
+impl<V: VectorSpace> KahanSum<V> {
+    /// An empty sum.
+    #[inline]
+    pub fn new() -> KahanSum<V> {
+        KahanSum {
+            sum: V::zero(),
+            compensation: V::zero(),
+            count: 0,
+        }
+    }
+
+    /// Add a vector to the sum.
+    #[inline]
+    pub fn add(&mut self, v: V) {
+        let y = v - self.compensation;
+        let t = self.sum + y;
+        self.compensation = (t - self.sum) - y;
+        self.sum = t;
+        self.count += 1;
+    }
+
+    /// The sum of the vectors added so far.
+    #[inline]
+    pub fn sum(&self) -> V {
+        self.sum
+    }
+
+    /// The number of vectors added so far.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The mean of the vectors added so far, or `None` if there are none.
+    #[inline]
+    pub fn mean(&self) -> Option<V> {
+        if self.count == 0 {
+            None
+        } else {
+            Some(self.sum / cast(self.count).unwrap())
+        }
+    }
+}
+
+impl<V: VectorSpace> Default for KahanSum<V> {
+    #[inline]
+    fn default() -> KahanSum<V> {
+        KahanSum::new()
+    }
+}
+
+impl<V: VectorSpace> Extend<V> for KahanSum<V> {
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        for v in iter {
+            self.add(v);
+        }
+    }
+}
+
+impl<V: VectorSpace> FromIterator<V> for KahanSum<V> {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> KahanSum<V> {
+        let mut sum = KahanSum::new();
+        sum.extend(iter);
+        sum
+    }
+}
+
+/// The centroid of a set of points, accumulated with `KahanSum`, or `None`
+/// if there are no points.
+pub fn compensated_centroid<P, I>(points: I) -> Option<P>
+where
+    P: EuclideanSpace,
+    I: IntoIterator<Item = P>,
+{
+    points
+        .into_iter()
+        .map(|p| p.to_vec())
+        .collect::<KahanSum<P::Diff>>()
+        .mean()
+        .map(P::from_vec)
+}
@@ -0,0 +1,411 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Robust geometric predicates.
+//!
+//! These are adaptive versions of the classic orientation and in-circle tests
+//! described by Jonathan Shewchuk in "Adaptive Precision Floating-Point
+//! Arithmetic and Fast Robust Geometric Predicates". Each predicate first
+//! evaluates its determinant in ordinary floating point arithmetic together
+//! with a bound on the rounding error. Only when the result is too close to
+//! zero for its sign to be trusted is the determinant evaluated again using
+//! exact arithmetic, so the sign of every result is always correct while the
+//! common case stays fast.
+//!
+//! The magnitude of a result is only an approximation of the determinant,
+//! and the predicates assume that no intermediate value overflows or
+//! underflows.
+//!
+//! ```rust
+//! use cgmath::predicates::orient2d;
+//! use cgmath::Point2;
+//!
+//! let a = Point2::new(12.0, 12.0);
+//! let b = Point2::new(24.0, 24.0);
+//!
+//! // A point a tiny distance above the line through `a` and `b`.
+//! let c = Point2::new(0.5, 0.5 + std::f64::EPSILON);
+//! assert!(orient2d(a, b, c) > 0.0);
+//!
+//! // A point exactly on it.
+//! assert_eq!(orient2d(a, b, Point2::new(0.5, 0.5)), 0.0);
+//! ```
+
+use num_traits::cast;
+
+use num::BaseFloat;
+use point::{Point2, Point3};
+use precision::{two_product, two_sum};
+
+/// Add two floats where `|a| >= |b|`, returning the rounded sum and its exact
+/// rounding error.
+#[inline]
+fn fast_two_sum<S: BaseFloat>(a: S, b: S) -> (S, S) {
+    let x = a + b;
+    (x, b - (x - a))
+}
+
+/// The exact difference of two floats, as an expansion.
+fn difference<S: BaseFloat>(a: S, b: S) -> Vec<S> {
+    let (x, y) = two_sum(a, -b);
+    [y, x].iter().cloned().filter(|h| !h.is_zero()).collect()
+}
+
+/// Add a float to an expansion.
+///
+/// Expansions are sums of non-overlapping components, stored in order of
+/// increasing magnitude with the zero components removed.
+fn grow<S: BaseFloat>(e: &[S], b: S) -> Vec<S> {
+    let mut h = Vec::with_capacity(e.len() + 1);
+    let mut q = b;
+    for &component in e {
+        let (sum, error) = two_sum(q, component);
+        if !error.is_zero() {
+            h.push(error);
+        }
+        q = sum;
+    }
+    if !q.is_zero() {
+        h.push(q);
+    }
+    h
+}
+
+/// The exact sum of two expansions.
+fn sum<S: BaseFloat>(e: &[S], f: &[S]) -> Vec<S> {
+    f.iter()
+        .fold(e.to_vec(), |h, &component| grow(&h, component))
+}
+
+/// The exact difference of two expansions.
+fn sub<S: BaseFloat>(e: &[S], f: &[S]) -> Vec<S> {
+    f.iter()
+        .fold(e.to_vec(), |h, &component| grow(&h, -component))
+}
+
+/// The exact product of an expansion and a float.
+fn scale<S: BaseFloat>(e: &[S], b: S) -> Vec<S> {
+    let mut h = Vec::with_capacity(2 * e.len());
+    let mut components = e.iter();
+    let mut q = match components.next() {
+        Some(&first) => {
+            let (product, error) = two_product(first, b);
+            h.push(error);
+            product
+        }
+        None => return h,
+    };
+    for &component in components {
+        let (product, product_error) = two_product(component, b);
+        let (partial, error) = two_sum(q, product_error);
+        h.push(error);
+        let (next, error) = fast_two_sum(product, partial);
+        h.push(error);
+        q = next;
+    }
+    h.push(q);
+    h.retain(|component| !component.is_zero());
+    h
+}
+
+/// The exact product of two expansions.
+fn mul<S: BaseFloat>(e: &[S], f: &[S]) -> Vec<S> {
+    f.iter()
+        .fold(Vec::new(), |h, &component| sum(&h, &scale(e, component)))
+}
+
+/// An approximation of an expansion with the same sign, which is its most
+/// significant component.
+#[inline]
+fn estimate<S: BaseFloat>(e: &[S]) -> S {
+    e.last().cloned().unwrap_or_else(S::zero)
+}
+
+/// A bound on the relative error of a determinant of the given depth, given
+/// as Shewchuk's `(a + b * eps) * eps` where `eps` is half of the machine
+/// epsilon.
+#[inline]
+fn error_bound<S: BaseFloat>(a: f64, b: f64) -> S {
+    let eps = S::epsilon() * cast(0.5).unwrap();
+    (cast::<_, S>(a).unwrap() + cast::<_, S>(b).unwrap() * eps) * eps
+}
+
+/// The difference of two products `a * b - c * d`, along with the sum of
+/// their magnitudes.
+#[inline]
+fn cross<S: BaseFloat>(a: S, b: S, c: S, d: S) -> (S, S) {
+    let (ab, cd) = (a * b, c * d);
+    (ab - cd, ab.abs() + cd.abs())
+}
+
+/// The exact value of `a * b - c * d`, for expansions.
+#[inline]
+fn exact_cross<S: BaseFloat>(a: &[S], b: &[S], c: &[S], d: &[S]) -> Vec<S> {
+    sub(&mul(a, b), &mul(c, d))
+}
+
+/// The exact value of `x * x + y * y + z * z`, for expansions.
+#[inline]
+fn exact_lift<S: BaseFloat>(v: &[&[S]]) -> Vec<S> {
+    v.iter().fold(Vec::new(), |h, x| sum(&h, &mul(x, x)))
+}
+
+/// Test the orientation of three points.
+///
+/// Returns a positive value if `a`, `b` and `c` are in counterclockwise
+/// order, a negative value if they are in clockwise order, and zero if they
+/// are collinear. The result approximates twice the signed area of the
+/// triangle.
+pub fn orient2d<S: BaseFloat>(a: Point2<S>, b: Point2<S>, c: Point2<S>) -> S {
+    let (det, permanent) = cross(a.x - c.x, b.y - c.y, a.y - c.y, b.x - c.x);
+    if det.abs() > error_bound::<S>(3.0, 16.0) * permanent {
+        return det;
+    }
+
+    let acx = difference(a.x, c.x);
+    let bcx = difference(b.x, c.x);
+    let acy = difference(a.y, c.y);
+    let bcy = difference(b.y, c.y);
+    estimate(&exact_cross(&acx, &bcy, &acy, &bcx))
+}
+
+/// Test whether `d` lies inside the circle passing through `a`, `b` and `c`.
+///
+/// Returns a positive value if `d` is inside the circle, a negative value if
+/// it is outside, and zero if the four points are cocircular. The points `a`,
+/// `b` and `c` must be in counterclockwise order, or the sign of the result
+/// is reversed.
+pub fn incircle<S: BaseFloat>(a: Point2<S>, b: Point2<S>, c: Point2<S>, d: Point2<S>) -> S {
+    let (adx, ady) = (a.x - d.x, a.y - d.y);
+    let (bdx, bdy) = (b.x - d.x, b.y - d.y);
+    let (cdx, cdy) = (c.x - d.x, c.y - d.y);
+
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let (bc, bc_permanent) = cross(bdx, cdy, cdx, bdy);
+    let (ca, ca_permanent) = cross(cdx, ady, adx, cdy);
+    let (ab, ab_permanent) = cross(adx, bdy, bdx, ady);
+
+    let det = alift * bc + blift * ca + clift * ab;
+    let permanent = bc_permanent * alift + ca_permanent * blift + ab_permanent * clift;
+    if det.abs() > error_bound::<S>(10.0, 96.0) * permanent {
+        return det;
+    }
+
+    let (adx, ady) = (difference(a.x, d.x), difference(a.y, d.y));
+    let (bdx, bdy) = (difference(b.x, d.x), difference(b.y, d.y));
+    let (cdx, cdy) = (difference(c.x, d.x), difference(c.y, d.y));
+
+    let alift = exact_lift(&[&adx, &ady]);
+    let blift = exact_lift(&[&bdx, &bdy]);
+    let clift = exact_lift(&[&cdx, &cdy]);
+
+    let bc = exact_cross(&bdx, &cdy, &cdx, &bdy);
+    let ca = exact_cross(&cdx, &ady, &adx, &cdy);
+    let ab = exact_cross(&adx, &bdy, &bdx, &ady);
+
+    let det = sum(
+        &sum(&mul(&alift, &bc), &mul(&blift, &ca)),
+        &mul(&clift, &ab),
+    );
+    estimate(&det)
+}
+
+/// Test the orientation of four points.
+///
+/// Returns a positive value if `d` lies below the plane passing through `a`,
+/// `b` and `c`, where below is the side from which `a`, `b` and `c` appear in
+/// clockwise order. Returns a negative value if `d` lies above the plane, and
+/// zero if the four points are coplanar. The result approximates six times
+/// the signed volume of the tetrahedron.
+pub fn orient3d<S: BaseFloat>(a: Point3<S>, b: Point3<S>, c: Point3<S>, d: Point3<S>) -> S {
+    let (adx, ady, adz) = (a.x - d.x, a.y - d.y, a.z - d.z);
+    let (bdx, bdy, bdz) = (b.x - d.x, b.y - d.y, b.z - d.z);
+    let (cdx, cdy, cdz) = (c.x - d.x, c.y - d.y, c.z - d.z);
+
+    let (bc, bc_permanent) = cross(bdx, cdy, cdx, bdy);
+    let (ca, ca_permanent) = cross(cdx, ady, adx, cdy);
+    let (ab, ab_permanent) = cross(adx, bdy, bdx, ady);
+
+    let det = adz * bc + bdz * ca + cdz * ab;
+    let permanent = bc_permanent * adz.abs() + ca_permanent * bdz.abs() + ab_permanent * cdz.abs();
+    if det.abs() > error_bound::<S>(7.0, 56.0) * permanent {
+        return det;
+    }
+
+    let (adx, ady, adz) = (
+        difference(a.x, d.x),
+        difference(a.y, d.y),
+        difference(a.z, d.z),
+    );
+    let (bdx, bdy, bdz) = (
+        difference(b.x, d.x),
+        difference(b.y, d.y),
+        difference(b.z, d.z),
+    );
+    let (cdx, cdy, cdz) = (
+        difference(c.x, d.x),
+        difference(c.y, d.y),
+        difference(c.z, d.z),
+    );
+
+    let bc = exact_cross(&bdx, &cdy, &cdx, &bdy);
+    let ca = exact_cross(&cdx, &ady, &adx, &cdy);
+    let ab = exact_cross(&adx, &bdy, &bdx, &ady);
+
+    let det = sum(&sum(&mul(&adz, &bc), &mul(&bdz, &ca)), &mul(&cdz, &ab));
+    estimate(&det)
+}
+
+/// Test whether `e` lies inside the sphere passing through `a`, `b`, `c` and
+/// `d`.
+///
+/// Returns a positive value if `e` is inside the sphere, a negative value if
+/// it is outside, and zero if the five points are cospherical. The points
+/// `a`, `b`, `c` and `d` must have a positive orientation according to
+/// `orient3d`, or the sign of the result is reversed.
+pub fn insphere<S: BaseFloat>(
+    a: Point3<S>,
+    b: Point3<S>,
+    c: Point3<S>,
+    d: Point3<S>,
+    e: Point3<S>,
+) -> S {
+    let (aex, aey, aez) = (a.x - e.x, a.y - e.y, a.z - e.z);
+    let (bex, bey, bez) = (b.x - e.x, b.y - e.y, b.z - e.z);
+    let (cex, cey, cez) = (c.x - e.x, c.y - e.y, c.z - e.z);
+    let (dex, dey, dez) = (d.x - e.x, d.y - e.y, d.z - e.z);
+
+    let (ab, ab_permanent) = cross(aex, bey, bex, aey);
+    let (bc, bc_permanent) = cross(bex, cey, cex, bey);
+    let (cd, cd_permanent) = cross(cex, dey, dex, cey);
+    let (da, da_permanent) = cross(dex, aey, aex, dey);
+    let (ac, ac_permanent) = cross(aex, cey, cex, aey);
+    let (bd, bd_permanent) = cross(bex, dey, dex, bey);
+
+    let abc = aez * bc - bez * ac + cez * ab;
+    let bcd = bez * cd - cez * bd + dez * bc;
+    let cda = cez * da + dez * ac + aez * cd;
+    let dab = dez * ab + aez * bd + bez * da;
+
+    let abc_permanent =
+        aez.abs() * bc_permanent + bez.abs() * ac_permanent + cez.abs() * ab_permanent;
+    let bcd_permanent =
+        bez.abs() * cd_permanent + cez.abs() * bd_permanent + dez.abs() * bc_permanent;
+    let cda_permanent =
+        cez.abs() * da_permanent + dez.abs() * ac_permanent + aez.abs() * cd_permanent;
+    let dab_permanent =
+        dez.abs() * ab_permanent + aez.abs() * bd_permanent + bez.abs() * da_permanent;
+
+    let alift = aex * aex + aey * aey + aez * aez;
+    let blift = bex * bex + bey * bey + bez * bez;
+    let clift = cex * cex + cey * cey + cez * cez;
+    let dlift = dex * dex + dey * dey + dez * dez;
+
+    let det = (dlift * abc - clift * dab) + (blift * cda - alift * bcd);
+    let permanent = dlift * abc_permanent
+        + clift * dab_permanent
+        + blift * cda_permanent
+        + alift * bcd_permanent;
+    if det.abs() > error_bound::<S>(16.0, 224.0) * permanent {
+        return det;
+    }
+
+    let (aex, aey, aez) = (
+        difference(a.x, e.x),
+        difference(a.y, e.y),
+        difference(a.z, e.z),
+    );
+    let (bex, bey, bez) = (
+        difference(b.x, e.x),
+        difference(b.y, e.y),
+        difference(b.z, e.z),
+    );
+    let (cex, cey, cez) = (
+        difference(c.x, e.x),
+        difference(c.y, e.y),
+        difference(c.z, e.z),
+    );
+    let (dex, dey, dez) = (
+        difference(d.x, e.x),
+        difference(d.y, e.y),
+        difference(d.z, e.z),
+    );
+
+    let ab = exact_cross(&aex, &bey, &bex, &aey);
+    let bc = exact_cross(&bex, &cey, &cex, &bey);
+    let cd = exact_cross(&cex, &dey, &dex, &cey);
+    let da = exact_cross(&dex, &aey, &aex, &dey);
+    let ac = exact_cross(&aex, &cey, &cex, &aey);
+    let bd = exact_cross(&bex, &dey, &dex, &bey);
+
+    let abc = sum(&sub(&mul(&aez, &bc), &mul(&bez, &ac)), &mul(&cez, &ab));
+    let bcd = sum(&sub(&mul(&bez, &cd), &mul(&cez, &bd)), &mul(&dez, &bc));
+    let cda = sum(&sum(&mul(&cez, &da), &mul(&dez, &ac)), &mul(&aez, &cd));
+    let dab = sum(&sum(&mul(&dez, &ab), &mul(&aez, &bd)), &mul(&bez, &da));
+
+    let alift = exact_lift(&[&aex, &aey, &aez]);
+    let blift = exact_lift(&[&bex, &bey, &bez]);
+    let clift = exact_lift(&[&cex, &cey, &cez]);
+    let dlift = exact_lift(&[&dex, &dey, &dez]);
+
+    let det = sum(
+        &sub(&mul(&dlift, &abc), &mul(&clift, &dab)),
+        &sub(&mul(&blift, &cda), &mul(&alift, &bcd)),
+    );
+    estimate(&det)
+}
+
+/// Returns `true` if `value` is zero or has the same sign as `orientation`.
+#[inline]
+fn same_side<S: BaseFloat>(orientation: S, value: S) -> bool {
+    value.is_zero() || (value > S::zero()) == (orientation > S::zero())
+}
+
+/// Test whether `p` lies inside the triangle `a`, `b`, `c`, or on its
+/// boundary. The triangle may have either orientation, but the test always
+/// returns `false` if it is degenerate.
+pub fn in_triangle<S: BaseFloat>(a: Point2<S>, b: Point2<S>, c: Point2<S>, p: Point2<S>) -> bool {
+    let orientation = orient2d(a, b, c);
+    !orientation.is_zero()
+        && same_side(orientation, orient2d(p, b, c))
+        && same_side(orientation, orient2d(a, p, c))
+        && same_side(orientation, orient2d(a, b, p))
+}
+
+/// Test whether `p` lies inside the tetrahedron `a`, `b`, `c`, `d`, or on its
+/// boundary.
+///
+/// Rather than solving for the barycentric coordinates of `p`, this compares
+/// the orientation of `p` with respect to each face against the orientation
+/// of the tetrahedron itself, so the answer is exact. The tetrahedron may
+/// have either orientation, but the test always returns `false` if it is
+/// degenerate.
+pub fn in_tetrahedron<S: BaseFloat>(
+    a: Point3<S>,
+    b: Point3<S>,
+    c: Point3<S>,
+    d: Point3<S>,
+    p: Point3<S>,
+) -> bool {
+    let orientation = orient3d(a, b, c, d);
+    !orientation.is_zero()
+        && same_side(orientation, orient3d(p, b, c, d))
+        && same_side(orientation, orient3d(a, p, c, d))
+        && same_side(orientation, orient3d(a, b, p, d))
+        && same_side(orientation, orient3d(a, b, c, p))
+}
@@ -11,3 +11,9 @@ pub use rotation::Rotation3;
 pub use transform::Transform;
 pub use transform::Transform2;
 pub use transform::Transform3;
+
+pub use intersect::Intersect;
+
+pub use iter::TransformIteratorExt;
+
+pub use projection::Projection;
@@ -16,11 +16,19 @@
 use num_traits::cast;
 use num_traits::Zero;
 
-use structure::Angle;
+use structure::*;
 
+use aabb::Aabb2;
 use angle::Rad;
-use matrix::Matrix4;
+use frustum::Frustum;
+use matrix::{Matrix2, Matrix3, Matrix4};
 use num::BaseFloat;
+use plane::Plane;
+use point::{Point2, Point3};
+use ray::Ray3;
+use sphere::Sphere;
+use transform::Transform;
+use vector::{Vector2, Vector3, Vector4};
 
 /// Create a perspective projection matrix.
 ///
@@ -76,6 +84,691 @@ pub fn ortho<S: BaseFloat>(left: S, right: S, bottom: S, top: S, near: S, far: S
     .into()
 }
 
+/// The range of depths in normalized device coordinates.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DepthRange {
+    /// Depths go from `-1` at the near plane to `1` at the far plane, as in
+    /// OpenGL and the projections of this crate.
+    NegativeOneToOne,
+    /// Depths go from `0` to `1`, as in Direct3D, Metal and Vulkan.
+    ZeroToOne,
+}
+
+/// Create the matrix mapping normalized device coordinates to texture
+/// coordinates, where `x` and `y` go from `0` to `1` across the screen and
+/// the depth goes from `0` to `1`.
+///
+/// Normalized device coordinates have their depth in `depth`. When `flip_y`
+/// is true, the texture `y` coordinate goes down the screen, for texture
+/// origins in the top-left corner. This is the bias matrix that is applied
+/// after the view-projection matrix of a light for shadow map lookups.
+///
+/// ```rust
+/// use cgmath::{ndc_to_texture, DepthRange, Point3, Transform};
+///
+/// let bias = ndc_to_texture(DepthRange::NegativeOneToOne, false);
+/// let p = bias.transform_point(Point3::new(-1.0, 1.0, 0.0f64));
+/// assert_eq!(p, Point3::new(0.0, 1.0, 0.5));
+///
+/// let bias = ndc_to_texture(DepthRange::ZeroToOne, true);
+/// let p = bias.transform_point(Point3::new(-1.0, 1.0, 0.25f64));
+/// assert_eq!(p, Point3::new(0.0, 0.0, 0.25));
+/// ```
+pub fn ndc_to_texture<S: BaseFloat>(depth: DepthRange, flip_y: bool) -> Matrix4<S> {
+    let half: S = cast(0.5).unwrap();
+    let sy = if flip_y { -half } else { half };
+    let (sz, tz) = match depth {
+        DepthRange::NegativeOneToOne => (half, half),
+        DepthRange::ZeroToOne => (S::one(), S::zero()),
+    };
+    Matrix4::new(
+        half, S::zero(), S::zero(), S::zero(),
+        S::zero(), sy, S::zero(), S::zero(),
+        S::zero(), S::zero(), sz, S::zero(),
+        half, half, tz, S::one(),
+    )
+}
+
+/// Create the matrix mapping texture coordinates back to normalized device
+/// coordinates. This is the inverse of `ndc_to_texture` with the same
+/// arguments.
+pub fn texture_to_ndc<S: BaseFloat>(depth: DepthRange, flip_y: bool) -> Matrix4<S> {
+    let two: S = cast(2).unwrap();
+    let sy = if flip_y { -two } else { two };
+    let (sz, tz) = match depth {
+        DepthRange::NegativeOneToOne => (two, -S::one()),
+        DepthRange::ZeroToOne => (S::one(), S::zero()),
+    };
+    Matrix4::new(
+        two, S::zero(), S::zero(), S::zero(),
+        S::zero(), sy, S::zero(), S::zero(),
+        S::zero(), S::zero(), sz, S::zero(),
+        -S::one(), -sy / two, tz, S::one(),
+    )
+}
+
+/// Offset a projection matrix by `jitter`, in normalized device
+/// coordinates, as temporal anti-aliasing does to sample a different
+/// position within each pixel every frame.
+///
+/// The offset is applied after the perspective divide, so it moves every
+/// point by the same amount on the screen. A jitter of `d` pixels over a
+/// viewport of `size` pixels is `2 * d / size` in normalized device
+/// coordinates.
+pub fn jitter_projection<S: BaseFloat>(projection: Matrix4<S>, jitter: Vector2<S>) -> Matrix4<S> {
+    Matrix4::from_translation(jitter.extend(S::zero())) * projection
+}
+
+/// Create the matrix reprojecting normalized device coordinates of the
+/// current frame to those of the previous frame, for motion vectors and
+/// temporal reprojection.
+///
+/// Both matrices are the view-projection matrices of the frames without
+/// their jitter. Returns `None` if the current one is not invertible.
+pub fn reprojection_matrix<S: BaseFloat>(
+    previous_view_projection: Matrix4<S>,
+    current_view_projection: Matrix4<S>,
+) -> Option<Matrix4<S>> {
+    current_view_projection
+        .invert()
+        .map(|inverse| previous_view_projection * inverse)
+}
+
+/// Reproject a point of the current frame, given by its normalized device
+/// coordinates and depth as read from the jittered render targets, to the
+/// normalized device coordinates it had in the previous frame.
+///
+/// The jitter of the current frame is removed first, and the result has no
+/// jitter either, so that the difference between the positions in the two
+/// frames is the motion of the point alone: the jitter of the previous
+/// frame does not need to be known. `reprojection` is the matrix created
+/// by `reprojection_matrix`. Returns `None` if the point was behind the
+/// previous camera.
+///
+/// ```rust
+/// use cgmath::{jitter_projection, perspective, reproject, reprojection_matrix};
+/// use cgmath::{Deg, Matrix4, Point3, Transform, Vector2, Vector3};
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// let projection = perspective(Deg(60.0f64), 1.0, 0.1, 100.0);
+/// let previous = projection * Matrix4::from_translation(Vector3::new(0.0, 0.0, -5.0));
+/// let current = projection * Matrix4::from_translation(Vector3::new(0.5, 0.0, -5.0));
+/// let jitter = Vector2::new(0.001, -0.002);
+///
+/// // A point rendered with the jittered current projection.
+/// let point = Point3::new(1.0, 0.5, 0.0);
+/// let ndc = (Matrix4::from_translation(jitter.extend(0.0)) * current).transform_point(point);
+/// assert_relative_eq!(ndc, jitter_projection(current, jitter).transform_point(point));
+///
+/// let m = reprojection_matrix(previous, current).unwrap();
+/// let reprojected = reproject(&m, ndc, jitter).unwrap();
+/// assert_relative_eq!(reprojected, previous.transform_point(point), epsilon = 1e-12);
+/// # }
+/// ```
+pub fn reproject<S: BaseFloat>(
+    reprojection: &Matrix4<S>,
+    ndc: Point3<S>,
+    jitter: Vector2<S>,
+) -> Option<Point3<S>> {
+    let unjittered = Point3::new(ndc.x - jitter.x, ndc.y - jitter.y, ndc.z);
+    let clip = *reprojection * unjittered.to_homogeneous();
+    if clip.w <= S::zero() {
+        return None;
+    }
+    Some(Point3::from_homogeneous(clip))
+}
+
+/// The tolerances deciding whether a sample of the previous frame shows the
+/// same surface as a point of the current frame, for `reproject_and_compare`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HistoryThresholds<S> {
+    /// The largest distance from the position of the history sample to the
+    /// tangent plane of the point, relative to the distance of the point from
+    /// the previous camera.
+    pub relative_depth: S,
+    /// The largest angle between the normals of the point and of the
+    /// history sample.
+    pub normal_angle: Rad<S>,
+}
+
+/// Find where a point of the current frame was in the previous frame, and
+/// check that the history sample there shows the same surface, as temporal
+/// techniques do to reject disoccluded history.
+///
+/// `point` and `normal` are the world position and normal of the surface
+/// seen by a pixel of the current frame, and `previous_view` and
+/// `previous_projection` the matrices of the previous frame, without
+/// jitter. `history` is given the normalized device coordinates of the
+/// point in the previous frame, and returns the world position and normal
+/// stored there, or `None` if there is no valid sample. The normalized
+/// device coordinates are returned if the point was on the screen in the
+/// previous frame and the sample passes the `thresholds`.
+///
+/// ```rust
+/// use cgmath::{perspective, reproject_and_compare, Deg, HistoryThresholds, Matrix4, Point3};
+/// use cgmath::Vector3;
+///
+/// let view = Matrix4::from_translation(Vector3::new(0.0, 0.0, -5.0f64));
+/// let projection = perspective(Deg(60.0), 1.0, 0.1, 100.0);
+/// let thresholds = HistoryThresholds { relative_depth: 0.01, normal_angle: Deg(25.0).into() };
+/// let floor = |_| Some((Point3::new(0.2, 0.0, 0.0), Vector3::unit_y()));
+///
+/// let up = Vector3::unit_y();
+/// let hit = Point3::new(0.0, 0.0, 0.0);
+/// assert!(reproject_and_compare(hit, up, view, projection, &thresholds, floor).is_some());
+/// // A point above the floor was hidden by it in the previous frame.
+/// let above = Point3::new(0.0, 0.5, 0.0);
+/// assert!(reproject_and_compare(above, up, view, projection, &thresholds, floor).is_none());
+/// ```
+pub fn reproject_and_compare<S, F>(
+    point: Point3<S>,
+    normal: Vector3<S>,
+    previous_view: Matrix4<S>,
+    previous_projection: Matrix4<S>,
+    thresholds: &HistoryThresholds<S>,
+    history: F,
+) -> Option<Point2<S>>
+where
+    S: BaseFloat,
+    F: FnOnce(Point2<S>) -> Option<(Point3<S>, Vector3<S>)>,
+{
+    let view = previous_view * point.to_homogeneous();
+    let clip = previous_projection * view;
+    if clip.w <= S::zero() {
+        return None;
+    }
+    let ndc = Point2::new(clip.x / clip.w, clip.y / clip.w);
+    if ndc.x.abs() > S::one() || ndc.y.abs() > S::one() {
+        return None;
+    }
+    let (position, history_normal) = history(ndc)?;
+
+    let normal = normal.normalize();
+    let depth = (view.truncate() / view.w).magnitude();
+    if (position - point).dot(normal).abs() > thresholds.relative_depth * depth {
+        return None;
+    }
+    let cos = Rad::cos(thresholds.normal_angle) * history_normal.magnitude();
+    if normal.dot(history_normal) < cos {
+        return None;
+    }
+    Some(ndc)
+}
+
+/// Project a regular grid of points on the screen onto a plane in world
+/// space, as the projected grid technique for rendering water surfaces
+/// does.
+///
+/// The grid has `columns` by `rows` vertices spread evenly over normalized
+/// device coordinates, from the bottom-left corner of the screen to the
+/// top-right one, and the world space points are returned with the column
+/// index varying fastest. Each vertex is the point where the ray through it
+/// crosses the plane between the near and the far planes of the camera,
+/// whose view-projection matrix maps depths from `-1` to `1` as the
+/// projections of this crate do and must have a finite far plane.
+///
+/// The rays that miss the plane, above the horizon, are replaced by their
+/// points on the far plane projected onto the plane, which folds them onto
+/// the horizon and keeps the grid continuous. Cameras below the plane look
+/// at it from the other side in the same way, and the rays starting past
+/// the plane, when the near plane crosses it, are projected from the near
+/// plane. Returns `None` if the matrix is not invertible or if there are
+/// fewer than two rows or columns.
+///
+/// ```rust
+/// use cgmath::{perspective, projected_grid, Deg, Matrix4, Plane, Point3, Vector3};
+///
+/// let projection = perspective(Deg(60.0f64), 1.0, 0.1, 100.0);
+/// let eye = Point3::new(0.0, 10.0, 0.0);
+/// let view = Matrix4::look_at(eye, Point3::new(0.0, 0.0, -20.0), Vector3::unit_y());
+/// let water = Plane::from_point_normal(Point3::new(0.0, 0.0, 0.0), Vector3::unit_y());
+/// let points = projected_grid(projection * view, &water, 16, 9).unwrap();
+/// assert_eq!(points.len(), 16 * 9);
+/// assert!(points.iter().all(|&p| water.distance(p).abs() < 1e-9));
+/// ```
+pub fn projected_grid<S: BaseFloat>(
+    view_projection: Matrix4<S>,
+    plane: &Plane<S>,
+    columns: usize,
+    rows: usize,
+) -> Option<Vec<Point3<S>>> {
+    if columns < 2 || rows < 2 {
+        return None;
+    }
+    let inverse = view_projection.invert()?;
+    let unproject =
+        |x: S, y: S, z: S| Point3::from_homogeneous(inverse * Vector4::new(x, y, z, S::one()));
+
+    // Measure distances on the side of the plane the camera is on.
+    let side = if plane.distance(unproject(S::zero(), S::zero(), -S::one())) < S::zero() {
+        -S::one()
+    } else {
+        S::one()
+    };
+    let two = S::one() + S::one();
+    let step_x = two / cast(columns - 1).unwrap();
+    let step_y = two / cast(rows - 1).unwrap();
+
+    let mut points = Vec::with_capacity(columns * rows);
+    for j in 0..rows {
+        let y = step_y * cast(j).unwrap() - S::one();
+        for i in 0..columns {
+            let x = step_x * cast(i).unwrap() - S::one();
+            let near = unproject(x, y, -S::one());
+            let far = unproject(x, y, S::one());
+            let near_distance = plane.distance(near) * side;
+            let far_distance = plane.distance(far) * side;
+            let point = if near_distance <= S::zero() {
+                plane.project_point(near)
+            } else if far_distance >= S::zero() {
+                plane.project_point(far)
+            } else {
+                near + (far - near) * (near_distance / (near_distance - far_distance))
+            };
+            points.push(point);
+        }
+    }
+    Some(points)
+}
+
+/// Scale the tangent of half of `fov` by `ratio`, which converts a field of
+/// view between two extents of the image whose lengths have that ratio.
+fn scale_fov<S: BaseFloat>(fov: Rad<S>, ratio: S) -> Rad<S> {
+    let two: S = cast(2).unwrap();
+    Rad::atan(Rad::tan(fov / two) * ratio) * two
+}
+
+/// Convert a vertical field of view to the horizontal field of view of an
+/// image with the given `aspect` ratio (width over height).
+///
+/// ```rust
+/// use cgmath::{horizontal_fov, Deg};
+///
+/// let hfov = Deg::from(horizontal_fov(Deg(90.0f64), 1.0));
+/// assert!((hfov.0 - 90.0).abs() < 1e-12);
+/// ```
+pub fn horizontal_fov<S: BaseFloat, A: Into<Rad<S>>>(vfov: A, aspect: S) -> Rad<S> {
+    scale_fov(vfov.into(), aspect)
+}
+
+/// Convert a horizontal field of view to the vertical field of view of an
+/// image with the given `aspect` ratio (width over height).
+pub fn vertical_fov<S: BaseFloat, A: Into<Rad<S>>>(hfov: A, aspect: S) -> Rad<S> {
+    scale_fov(hfov.into(), S::one() / aspect)
+}
+
+/// Convert a vertical field of view to the field of view along the diagonal
+/// of an image with the given `aspect` ratio (width over height).
+pub fn diagonal_fov<S: BaseFloat, A: Into<Rad<S>>>(vfov: A, aspect: S) -> Rad<S> {
+    scale_fov(vfov.into(), (S::one() + aspect * aspect).sqrt())
+}
+
+/// Convert a field of view along the diagonal of an image with the given
+/// `aspect` ratio (width over height) to its vertical field of view.
+pub fn vertical_fov_from_diagonal<S: BaseFloat, A: Into<Rad<S>>>(dfov: A, aspect: S) -> Rad<S> {
+    scale_fov(dfov.into(), S::one() / (S::one() + aspect * aspect).sqrt())
+}
+
+/// The field of view of a lens with the given `focal_length`, across a
+/// sensor extent of length `sensor_size`, both in the same unit.
+pub fn fov_from_focal_length<S: BaseFloat>(focal_length: S, sensor_size: S) -> Rad<S> {
+    let two: S = cast(2).unwrap();
+    Rad::atan(sensor_size / (two * focal_length)) * two
+}
+
+/// The focal length giving the field of view `fov` across a sensor extent
+/// of length `sensor_size`, in the same unit as the sensor size.
+pub fn focal_length_from_fov<S: BaseFloat, A: Into<Rad<S>>>(fov: A, sensor_size: S) -> S {
+    let two: S = cast(2).unwrap();
+    sensor_size / (two * Rad::tan(fov.into() / two))
+}
+
+/// The diagonal of a 36 × 24 mm full-frame sensor, in millimetres.
+fn full_frame_diagonal<S: BaseFloat>() -> S {
+    let width: S = cast(36).unwrap();
+    let height: S = cast(24).unwrap();
+    (width * width + height * height).sqrt()
+}
+
+/// The vertical field of view of an image with the given `aspect` ratio
+/// (width over height), taken with a lens of the given 35mm-equivalent
+/// focal length in millimetres.
+///
+/// As for the crop factors of cameras, the equivalence is defined along the
+/// diagonal, so a 50 mm equivalent lens has the same diagonal field of view
+/// for every aspect ratio.
+///
+/// ```rust
+/// use cgmath::{vertical_fov_from_35mm, Deg};
+///
+/// // On a 3:2 full-frame sensor, the vertical extent is 24 mm.
+/// let vfov = Deg::from(vertical_fov_from_35mm(50.0f64, 1.5));
+/// let expected = 2.0 * (12.0f64 / 50.0).atan().to_degrees();
+/// assert!((vfov.0 - expected).abs() < 1e-10);
+/// ```
+pub fn vertical_fov_from_35mm<S: BaseFloat>(focal_length: S, aspect: S) -> Rad<S> {
+    let dfov = fov_from_focal_length(focal_length, full_frame_diagonal());
+    vertical_fov_from_diagonal(dfov, aspect)
+}
+
+/// The 35mm-equivalent focal length, in millimetres, of a camera with the
+/// vertical field of view `vfov` and the given `aspect` ratio (width over
+/// height). This is the inverse of `vertical_fov_from_35mm`.
+pub fn focal_length_35mm<S: BaseFloat, A: Into<Rad<S>>>(vfov: A, aspect: S) -> S {
+    focal_length_from_fov(diagonal_fov(vfov, aspect), full_frame_diagonal())
+}
+
+/// Clamp a rectangle in normalized device coordinates to the screen, or
+/// return `None` if it is entirely outside of it.
+pub(crate) fn clip_to_screen<S: BaseFloat>(min: Point2<S>, max: Point2<S>) -> Option<Aabb2<S>> {
+    let one = S::one();
+    if max.x < -one || max.y < -one || min.x > one || min.y > one {
+        return None;
+    }
+    Some(Aabb2 {
+        min: Point2::new(min.x.max(-one), min.y.max(-one)),
+        max: Point2::new(max.x.min(one), max.y.min(one)),
+    })
+}
+
+/// The whole screen, in normalized device coordinates.
+pub(crate) fn full_screen<S: BaseFloat>() -> Aabb2<S> {
+    Aabb2 {
+        min: Point2::new(-S::one(), -S::one()),
+        max: Point2::new(S::one(), S::one()),
+    }
+}
+
+/// The range of normalized device coordinates covered by a sphere along one
+/// axis, given the coordinates of its center along that axis and along `z`,
+/// or `None` if it reaches behind the camera.
+fn sphere_axis_bounds<S, F>(a: S, z: S, radius: S, orthographic: bool, project: F) -> Option<(S, S)>
+where
+    S: BaseFloat,
+    F: Fn(S, S) -> S,
+{
+    if orthographic {
+        return Some((project(a - radius, z), project(a + radius, z)));
+    }
+
+    // The tangent points of the lines through the eye that touch the circle
+    // where the sphere crosses the plane of this axis and `z`.
+    let length2 = a * a + z * z;
+    let t = (length2 - radius * radius).sqrt();
+    let scale = t / length2;
+    let (a1, z1) = ((t * a + radius * z) * scale, (t * z - radius * a) * scale);
+    let (a2, z2) = ((t * a - radius * z) * scale, (t * z + radius * a) * scale);
+    if z1 >= S::zero() || z2 >= S::zero() {
+        return None;
+    }
+    let (p1, p2) = (project(a1, z1), project(a2, z2));
+    Some((p1.min(p2), p1.max(p2)))
+}
+
+/// Compute a rectangle that bounds the projection of a sphere on the
+/// screen, in normalized device coordinates.
+///
+/// The center of the sphere is given in view space, with the camera looking
+/// down the negative `z` axis, and `projection` must be a matrix built by
+/// `perspective`, `frustum` or `ortho`. The bounds are tight for a
+/// perspective projection, using the method from "2D Polyhedral Bounds of a
+/// Clipped, Perspective-Projected 3D Sphere" by Mara and McGuire, and are
+/// clamped to the screen.
+///
+/// Returns `None` if the sphere is entirely behind the camera or off the
+/// screen. If the sphere contains the camera or reaches behind it, the whole
+/// screen is returned.
+///
+/// ```rust
+/// use cgmath::{perspective, sphere_screen_bounds, Deg, Point3};
+///
+/// let projection = perspective(Deg(90.0), 1.0, 0.1, 100.0);
+/// let bounds = sphere_screen_bounds(Point3::new(0.0, 0.0, -10.0), 1.0, &projection).unwrap();
+/// assert!(bounds.max.x > 0.1 && bounds.max.x < 0.11);
+///
+/// let behind = sphere_screen_bounds(Point3::new(0.0, 0.0, 10.0), 1.0, &projection);
+/// assert_eq!(behind, None);
+/// ```
+pub fn sphere_screen_bounds<S: BaseFloat>(
+    center: Point3<S>,
+    radius: S,
+    projection: &Matrix4<S>,
+) -> Option<Aabb2<S>> {
+    let m = projection;
+    let orthographic = m.z.w.is_zero();
+    if !orthographic {
+        if center.z - radius >= S::zero() {
+            return None;
+        }
+        if center.z + radius >= S::zero() || center.to_vec().magnitude2() <= radius * radius {
+            return Some(full_screen());
+        }
+    }
+
+    let project_x = |x: S, z: S| (m.x.x * x + m.z.x * z + m.w.x) / (m.x.w * x + m.z.w * z + m.w.w);
+    let project_y = |y: S, z: S| (m.y.y * y + m.z.y * z + m.w.y) / (m.y.w * y + m.z.w * z + m.w.w);
+    match (
+        sphere_axis_bounds(center.x, center.z, radius, orthographic, project_x),
+        sphere_axis_bounds(center.y, center.z, radius, orthographic, project_y),
+    ) {
+        (Some((min_x, max_x)), Some((min_y, max_y))) => {
+            clip_to_screen(Point2::new(min_x, min_y), Point2::new(max_x, max_y))
+        }
+        _ => Some(full_screen()),
+    }
+}
+
+/// The solid angle, in steradians, subtended by a sphere as seen from
+/// `eye`.
+///
+/// This is the area of the sphere's projection on the unit sphere around
+/// the eye, and is `4π` when the eye is inside the sphere.
+///
+/// ```rust
+/// use cgmath::{sphere_solid_angle, Point3};
+///
+/// let near = sphere_solid_angle(Point3::new(0.0f64, 0.0, 0.0), Point3::new(0.0, 0.0, -2.0), 1.0);
+/// let far = sphere_solid_angle(Point3::new(0.0f64, 0.0, 0.0), Point3::new(0.0, 0.0, -20.0), 1.0);
+/// assert!(far < near);
+/// ```
+pub fn sphere_solid_angle<S: BaseFloat>(eye: Point3<S>, center: Point3<S>, radius: S) -> S {
+    let two_pi = Rad::<S>::full_turn().0;
+    let radius2 = radius * radius;
+    let distance2 = (center - eye).magnitude2();
+    if distance2 < radius2 {
+        return two_pi + two_pi;
+    }
+    // `1 - cos(half angle)`, rearranged to stay accurate for small spheres.
+    let distance = distance2.sqrt();
+    two_pi * radius2 / (distance * (distance + (distance2 - radius2).sqrt()))
+}
+
+/// The signed solid angle, in steradians, subtended by a planar polygon as
+/// seen from `eye`.
+///
+/// The result is positive when the vertices wind counter-clockwise as seen
+/// from the eye, and negative otherwise. The polygon is split in a fan of
+/// triangles, each measured with the formula from "The Solid Angle of a
+/// Plane Triangle" by Van Oosterom and Strackee, so concave polygons are
+/// handled as long as they are simple.
+///
+/// ```rust
+/// use cgmath::{polygon_solid_angle, Point3};
+///
+/// // One face of a cube, seen from its centre, covers a sixth of the sphere.
+/// let face = [
+///     Point3::new(-1.0f64, -1.0, -1.0),
+///     Point3::new(1.0, -1.0, -1.0),
+///     Point3::new(1.0, 1.0, -1.0),
+///     Point3::new(-1.0, 1.0, -1.0),
+/// ];
+/// let omega = polygon_solid_angle(Point3::new(0.0, 0.0, 0.0), &face);
+/// assert!((omega - 4.0 * std::f64::consts::PI / 6.0).abs() < 1e-12);
+/// ```
+pub fn polygon_solid_angle<S: BaseFloat>(eye: Point3<S>, polygon: &[Point3<S>]) -> S {
+    if polygon.len() < 3 {
+        return S::zero();
+    }
+    let a = polygon[0] - eye;
+    let la = a.magnitude();
+    let two: S = cast(2).unwrap();
+    polygon[1..]
+        .windows(2)
+        .map(|pair| {
+            let (b, c) = (pair[0] - eye, pair[1] - eye);
+            let (lb, lc) = (b.magnitude(), c.magnitude());
+            let numerator = a.dot(c.cross(b));
+            let denominator = la * lb * lc + a.dot(b) * lc + a.dot(c) * lb + b.dot(c) * la;
+            two * Rad::atan2(numerator, denominator).0
+        })
+        .fold(S::zero(), |sum, omega| sum + omega)
+}
+
+/// The screen-space error, in pixels, of drawing an object whose geometry
+/// deviates by at most `geometric_error` from the exact one, within the
+/// bounding sphere `bounds`.
+///
+/// This is the metric level of detail schemes compare to a threshold, such
+/// as a pixel or two, to select the coarsest version of an object that
+/// looks the same as the exact one. `camera_pose` places the camera in the
+/// world, looking down its negative `z` axis, and `viewport` is the size
+/// of the viewport in pixels.
+///
+/// With a perspective projection, the error is measured at the point of the
+/// sphere closest to the camera, whatever the direction the camera looks
+/// in, so that objects turning into view do not pop. It is infinite when
+/// the camera is inside the sphere. With an orthographic projection, it
+/// does not depend on the distance.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{screen_space_error, Decomposed, Deg, PerspectiveFov};
+/// use cgmath::{Point3, Quaternion, Sphere, Vector2};
+///
+/// let camera: Decomposed<_, Quaternion<_>> = Decomposed::identity();
+/// let projection = PerspectiveFov { fovy: Deg(90.0).into(), aspect: 1.0, near: 0.1, far: 1000.0 };
+/// let viewport = Vector2::new(1000.0f64, 1000.0);
+///
+/// let tile = Sphere::new(Point3::new(0.0, 0.0, -101.0), 1.0);
+/// // An error of 0.1 a hundred units away covers half a pixel.
+/// let error = screen_space_error(&tile, 0.1, &camera, &projection, viewport);
+/// assert!((error - 0.5).abs() < 1e-12);
+/// ```
+pub fn screen_space_error<S, T, P>(
+    bounds: &Sphere<S>,
+    geometric_error: S,
+    camera_pose: &T,
+    projection: &P,
+    viewport: Vector2<S>,
+) -> S
+where
+    S: BaseFloat,
+    T: Transform<Point3<S>>,
+    P: Projection<S>,
+{
+    let m = projection.to_matrix4();
+    let two: S = cast(2).unwrap();
+    // The size in pixels of a unit length across the view at a unit
+    // distance, or at any distance for an orthographic projection.
+    let pixels = (m.x.x.abs() * viewport.x).max(m.y.y.abs() * viewport.y) / two;
+    if !projection.is_perspective() {
+        return geometric_error * pixels;
+    }
+    let eye = camera_pose.transform_point(Point3::origin());
+    let distance = eye.distance(bounds.center) - bounds.radius;
+    if distance <= S::zero() {
+        S::infinity()
+    } else {
+        geometric_error * pixels / distance
+    }
+}
+
+/// A projection from view space to clip space, as produced by the
+/// `PerspectiveFov`, `Perspective` and `Ortho` types.
+///
+/// This lets camera code stay generic over the kind of projection it uses.
+/// View space follows the conventions of the projection matrices of this
+/// crate: the camera looks down the negative z axis, and the near and far
+/// distances are measured along that direction.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{Deg, Ortho, PerspectiveFov, Point3};
+///
+/// fn is_visible<P: Projection<f64>>(projection: &P, p: Point3<f64>) -> bool {
+///     let f = projection.to_frustum();
+///     f.to_planes().iter().all(|plane| plane.distance(p) >= 0.0)
+/// }
+///
+/// let persp = PerspectiveFov { fovy: Deg(90.0).into(), aspect: 1.0, near: 0.1, far: 100.0 };
+/// let ortho = Ortho { left: -1.0, right: 1.0, bottom: -1.0, top: 1.0, near: 0.1, far: 100.0 };
+/// assert!(is_visible(&persp, Point3::new(4.0, 0.0, -5.0)));
+/// assert!(!is_visible(&ortho, Point3::new(4.0, 0.0, -5.0)));
+/// ```
+pub trait Projection<S: BaseFloat>: Copy + Into<Matrix4<S>> {
+    /// The projection matrix.
+    #[inline]
+    fn to_matrix4(&self) -> Matrix4<S> {
+        (*self).into()
+    }
+
+    /// The view-space frustum of the projection, computed directly from its
+    /// parameters rather than extracted from the matrix.
+    fn to_frustum(&self) -> Frustum<S>;
+
+    /// The distance to the near plane.
+    fn near(&self) -> S;
+
+    /// The distance to the far plane.
+    fn far(&self) -> S;
+
+    /// Whether this is a perspective projection, as opposed to an
+    /// orthographic one.
+    fn is_perspective(&self) -> bool;
+
+    /// The same projection restricted to the depths between `near` and
+    /// `far`, which covers exactly the same part of the screen.
+    ///
+    /// This is used to split a scene into depth ranges rendered in separate
+    /// passes, such as foreground and background layers, whose slices fit
+    /// together without gaps.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Deg, PerspectiveFov};
+    ///
+    /// let camera = PerspectiveFov { fovy: Deg(60.0).into(), aspect: 1.5, near: 0.1, far: 1000.0 };
+    /// let foreground = camera.slice(0.1, 5.0);
+    /// let background = camera.slice(5.0, 1000.0);
+    /// assert_eq!(foreground.far(), background.near());
+    /// ```
+    fn slice(&self, near: S, far: S) -> Self;
+
+    /// The view-space frustum of `slice(near, far)`.
+    #[inline]
+    fn slice_frustum(&self, near: S, far: S) -> Frustum<S> {
+        self.slice(near, far).to_frustum()
+    }
+
+    /// The projection matrix of `slice(near, far)`.
+    #[inline]
+    fn slice_matrix4(&self, near: S, far: S) -> Matrix4<S> {
+        self.slice(near, far).to_matrix4()
+    }
+}
+
+/// The near and far planes shared by every projection, with their normals
+/// pointing inside the frustum.
+fn depth_planes<S: BaseFloat>(near: S, far: S) -> (Plane<S>, Plane<S>) {
+    (
+        Plane::new(-Vector3::unit_z(), near),
+        Plane::new(Vector3::unit_z(), -far),
+    )
+}
+
 /// A perspective projection based on a vertical field-of-view angle.
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "rustc-serialize", derive(RustcEncodable, RustcDecodable))]
@@ -282,3 +975,400 @@ impl<S: BaseFloat> From<Ortho<S>> for Matrix4<S> {
         )
     }
 }
+
+impl<S: BaseFloat> Projection<S> for PerspectiveFov<S> {
+    #[inline]
+    fn to_frustum(&self) -> Frustum<S> {
+        self.to_perspective().to_frustum()
+    }
+
+    #[inline]
+    fn near(&self) -> S {
+        self.near
+    }
+
+    #[inline]
+    fn far(&self) -> S {
+        self.far
+    }
+
+    #[inline]
+    fn is_perspective(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn slice(&self, near: S, far: S) -> PerspectiveFov<S> {
+        PerspectiveFov {
+            near,
+            far,
+            ..*self
+        }
+    }
+}
+
+impl<S: BaseFloat> Projection<S> for Perspective<S> {
+    fn to_frustum(&self) -> Frustum<S> {
+        // The side planes go through the eye and the edges of the near
+        // rectangle.
+        let side = |normal: Vector3<S>| Plane::new(normal.normalize(), S::zero());
+        let (near, far) = depth_planes(self.near, self.far);
+        Frustum {
+            left: side(Vector3::new(self.near, S::zero(), self.left)),
+            right: side(Vector3::new(-self.near, S::zero(), -self.right)),
+            bottom: side(Vector3::new(S::zero(), self.near, self.bottom)),
+            top: side(Vector3::new(S::zero(), -self.near, -self.top)),
+            near,
+            far,
+        }
+    }
+
+    #[inline]
+    fn near(&self) -> S {
+        self.near
+    }
+
+    #[inline]
+    fn far(&self) -> S {
+        self.far
+    }
+
+    #[inline]
+    fn is_perspective(&self) -> bool {
+        true
+    }
+
+    fn slice(&self, near: S, far: S) -> Perspective<S> {
+        // The extents are given on the near plane, so they scale with it.
+        let scale = near / self.near;
+        Perspective {
+            left: self.left * scale,
+            right: self.right * scale,
+            bottom: self.bottom * scale,
+            top: self.top * scale,
+            near,
+            far,
+        }
+    }
+}
+
+impl<S: BaseFloat> Projection<S> for Ortho<S> {
+    fn to_frustum(&self) -> Frustum<S> {
+        let (near, far) = depth_planes(self.near, self.far);
+        Frustum {
+            left: Plane::new(Vector3::unit_x(), self.left),
+            right: Plane::new(-Vector3::unit_x(), -self.right),
+            bottom: Plane::new(Vector3::unit_y(), self.bottom),
+            top: Plane::new(-Vector3::unit_y(), -self.top),
+            near,
+            far,
+        }
+    }
+
+    #[inline]
+    fn near(&self) -> S {
+        self.near
+    }
+
+    #[inline]
+    fn far(&self) -> S {
+        self.far
+    }
+
+    #[inline]
+    fn is_perspective(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn slice(&self, near: S, far: S) -> Ortho<S> {
+        Ortho {
+            near,
+            far,
+            ..*self
+        }
+    }
+}
+
+/// The intrinsic parameters of a pinhole camera, as used in computer vision
+/// and camera calibration, mapping view-space points to pixel coordinates.
+///
+/// Points are in the view space of this crate, with the camera at the
+/// origin looking down the negative `z` axis and the `y` axis pointing up.
+/// Pixel coordinates follow the image convention instead, with the origin at
+/// the top left corner of the image and the `y` axis pointing down.
+///
+/// ```rust
+/// use cgmath::{PinholeCamera, Point2, Point3, Vector2};
+///
+/// let camera = PinholeCamera {
+///     focal: Vector2::new(800.0, 800.0),
+///     principal_point: Point2::new(320.0, 240.0),
+///     skew: 0.0,
+/// };
+/// // A point up and to the right of the camera, two units in front of it.
+/// let pixel = camera.project(Point3::new(0.5, 0.25, -2.0)).unwrap();
+/// assert_eq!(pixel, Point2::new(520.0, 140.0));
+///
+/// // The ray through the pixel reaches the point at its depth.
+/// let ray = camera.unproject(pixel);
+/// assert_eq!(ray.point_at(2.0), Point3::new(0.5, 0.25, -2.0));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PinholeCamera<S> {
+    /// The focal lengths along the horizontal and vertical axes of the
+    /// image, in pixels.
+    pub focal: Vector2<S>,
+    /// The pixel the optical axis goes through.
+    pub principal_point: Point2<S>,
+    /// The skew between the axes of the image, zero for rectangular pixels.
+    pub skew: S,
+}
+
+impl<S: BaseFloat> PinholeCamera<S> {
+    /// The camera with square pixels centered on an image of `width` by
+    /// `height` pixels, with the vertical field of view `fovy`.
+    pub fn from_fov<A: Into<Rad<S>>>(fovy: A, width: S, height: S) -> PinholeCamera<S> {
+        let two: S = cast(2).unwrap();
+        let f = focal_length_from_fov(fovy, height);
+        PinholeCamera {
+            focal: Vector2::new(f, f),
+            principal_point: Point2::new(width / two, height / two),
+            skew: S::zero(),
+        }
+    }
+
+    /// The intrinsic matrix, mapping the homogeneous coordinates of a point
+    /// in the camera frame of computer vision, with the `y` axis pointing
+    /// down and the `z` axis pointing forward, to homogeneous pixel
+    /// coordinates.
+    pub fn to_matrix3(&self) -> Matrix3<S> {
+        Matrix3::new(
+            self.focal.x, S::zero(), S::zero(),
+            self.skew, self.focal.y, S::zero(),
+            self.principal_point.x, self.principal_point.y, S::one(),
+        )
+    }
+
+    /// The pixel a view-space point projects to, or `None` if the point is
+    /// not in front of the camera.
+    pub fn project(&self, point: Point3<S>) -> Option<Point2<S>> {
+        if point.z >= S::zero() {
+            return None;
+        }
+        let normalized = Point2::new(point.x / -point.z, point.y / point.z);
+        Some(self.normalized_to_pixel(normalized))
+    }
+
+    /// The ray from the camera through a pixel.
+    ///
+    /// The direction of the ray has a `z` coordinate of `-1`, so the point
+    /// of the ray at `t` is at the depth `t` in front of the camera.
+    pub fn unproject(&self, pixel: Point2<S>) -> Ray3<S> {
+        let n = self.pixel_to_normalized(pixel);
+        Ray3::new(Point3::origin(), Vector3::new(n.x, -n.y, -S::one()))
+    }
+
+    /// The pixel at the given normalized image coordinates, where a point
+    /// at the depth one in the camera frame of computer vision projects, with
+    /// the `y` axis pointing down.
+    ///
+    /// Lens distortion applies to normalized coordinates, between
+    /// projecting a point to them and converting them to pixels.
+    #[inline]
+    pub fn normalized_to_pixel(&self, normalized: Point2<S>) -> Point2<S> {
+        Point2::new(
+            self.principal_point.x + self.focal.x * normalized.x + self.skew * normalized.y,
+            self.principal_point.y + self.focal.y * normalized.y,
+        )
+    }
+
+    /// The normalized image coordinates of a pixel. This is the inverse of
+    /// `normalized_to_pixel`.
+    #[inline]
+    pub fn pixel_to_normalized(&self, pixel: Point2<S>) -> Point2<S> {
+        let y = (pixel.y - self.principal_point.y) / self.focal.y;
+        let x = (pixel.x - self.principal_point.x - self.skew * y) / self.focal.x;
+        Point2::new(x, y)
+    }
+
+    /// The OpenGL-style projection matrix rendering what the camera sees to
+    /// an image of `width` by `height` pixels, between the `near` and `far`
+    /// planes.
+    ///
+    /// The matrix matches `Perspective` for cameras without skew.
+    pub fn to_projection_matrix(&self, width: S, height: S, near: S, far: S) -> Matrix4<S> {
+        let two: S = cast(2).unwrap();
+
+        let c0r0 = two * self.focal.x / width;
+        let c0r1 = S::zero();
+        let c0r2 = S::zero();
+        let c0r3 = S::zero();
+
+        let c1r0 = -two * self.skew / width;
+        let c1r1 = two * self.focal.y / height;
+        let c1r2 = S::zero();
+        let c1r3 = S::zero();
+
+        let c2r0 = S::one() - two * self.principal_point.x / width;
+        let c2r1 = two * self.principal_point.y / height - S::one();
+        let c2r2 = -(far + near) / (far - near);
+        let c2r3 = -S::one();
+
+        let c3r0 = S::zero();
+        let c3r1 = S::zero();
+        let c3r2 = -(two * far * near) / (far - near);
+        let c3r3 = S::zero();
+
+        Matrix4::new(
+            c0r0, c0r1, c0r2, c0r3,
+            c1r0, c1r1, c1r2, c1r3,
+            c2r0, c2r1, c2r2, c2r3,
+            c3r0, c3r1, c3r2, c3r3,
+        )
+    }
+
+    /// The camera seeing what an OpenGL-style perspective projection matrix
+    /// renders to an image of `width` by `height` pixels, or `None` if the
+    /// matrix is not a perspective projection.
+    ///
+    /// This is the inverse of `to_projection_matrix`, up to the near and far
+    /// planes, which the camera does not hold.
+    pub fn from_projection_matrix(m: Matrix4<S>, width: S, height: S) -> Option<PinholeCamera<S>> {
+        // The `w` row of a perspective projection is `(0, 0, -w, 0)`, and
+        // scaling the whole matrix does not change the projection.
+        let w = -m.z.w;
+        if w == S::zero() {
+            return None;
+        }
+        let two: S = cast(2).unwrap();
+        Some(PinholeCamera {
+            focal: Vector2::new(m.x.x * width, m.y.y * height) / (two * w),
+            principal_point: Point2::new(
+                (S::one() - m.z.x / w) * width / two,
+                (S::one() + m.z.y / w) * height / two,
+            ),
+            skew: -m.y.x * width / (two * w),
+        })
+    }
+}
+
+/// The Brown–Conrady model of lens distortion, with three radial and two
+/// tangential coefficients, named as in OpenCV.
+///
+/// The model maps the ideal normalized image coordinates of a pinhole camera
+/// to the distorted ones a real lens produces. Positive values of `k1` give
+/// pincushion distortion and negative values barrel distortion, while the
+/// tangential coefficients `p1` and `p2` account for a lens not quite
+/// parallel to the sensor.
+///
+/// ```rust
+/// use cgmath::{BrownConrady, PinholeCamera, Point2, Point3, Vector2};
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// let camera = PinholeCamera {
+///     focal: Vector2::new(800.0, 800.0),
+///     principal_point: Point2::new(320.0, 240.0),
+///     skew: 0.0,
+/// };
+/// let lens = BrownConrady { k1: -0.2, k2: 0.05, k3: 0.0, p1: 0.001, p2: -0.0005 };
+///
+/// // Where a point appears in the image taken through the lens.
+/// let p = Point3::new(0.5, 0.25, -2.0);
+/// let ideal = camera.pixel_to_normalized(camera.project(p).unwrap());
+/// let pixel = camera.normalized_to_pixel(lens.distort(ideal));
+///
+/// // And back to the ray through the point.
+/// let undistorted = camera.normalized_to_pixel(lens.undistort(camera.pixel_to_normalized(pixel)));
+/// assert_relative_eq!(camera.unproject(undistorted).point_at(2.0), p, epsilon = 1e-9);
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BrownConrady<S> {
+    /// The radial coefficient of the squared distance to the center.
+    pub k1: S,
+    /// The radial coefficient of the fourth power of the distance.
+    pub k2: S,
+    /// The radial coefficient of the sixth power of the distance.
+    pub k3: S,
+    /// The first tangential coefficient.
+    pub p1: S,
+    /// The second tangential coefficient.
+    pub p2: S,
+}
+
+impl<S: BaseFloat> BrownConrady<S> {
+    /// The model of a lens without distortion.
+    pub fn none() -> BrownConrady<S> {
+        BrownConrady {
+            k1: S::zero(),
+            k2: S::zero(),
+            k3: S::zero(),
+            p1: S::zero(),
+            p2: S::zero(),
+        }
+    }
+
+    /// The distorted normalized coordinates of a point.
+    pub fn distort(&self, point: Point2<S>) -> Point2<S> {
+        let two: S = cast(2).unwrap();
+        let (x, y) = (point.x, point.y);
+        let r2 = x * x + y * y;
+        let radial = S::one() + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+        Point2::new(
+            x * radial + two * self.p1 * x * y + self.p2 * (r2 + two * x * x),
+            y * radial + self.p1 * (r2 + two * y * y) + two * self.p2 * x * y,
+        )
+    }
+
+    /// The undistorted normalized coordinates of a point, the inverse of
+    /// `distort`.
+    ///
+    /// The model has no closed-form inverse, so this runs Newton's method
+    /// from the distorted point until it converges to the precision of `S`,
+    /// or for at most 20 iterations. Strong distortion far from the center
+    /// may bend the image back on itself, where the result is the closest
+    /// point found.
+    pub fn undistort(&self, point: Point2<S>) -> Point2<S> {
+        let two: S = cast(2).unwrap();
+        let three: S = cast(3).unwrap();
+        let six: S = cast(6).unwrap();
+        let tolerance = S::epsilon() * (S::one() + point.to_vec().magnitude());
+
+        let mut p = point;
+        for _ in 0..20 {
+            let residual = point - self.distort(p);
+            if residual.magnitude() <= tolerance {
+                break;
+            }
+            let (x, y) = (p.x, p.y);
+            let r2 = x * x + y * y;
+            let radial = S::one() + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+            // The derivative of `radial` with respect to `r2`.
+            let dradial = self.k1 + r2 * (two * self.k2 + r2 * three * self.k3);
+            let dxy = two * x * y * dradial + two * self.p1 * x + two * self.p2 * y;
+            let jacobian = Matrix2::new(
+                radial + two * x * x * dradial + two * self.p1 * y + six * self.p2 * x,
+                dxy,
+                dxy,
+                radial + two * y * y * dradial + six * self.p1 * y + two * self.p2 * x,
+            );
+            match jacobian.invert() {
+                Some(inverse) => p += inverse * residual,
+                None => break,
+            }
+        }
+        p
+    }
+}
+
+impl<S: BaseFloat> Default for BrownConrady<S> {
+    /// The model of a lens without distortion.
+    #[inline]
+    fn default() -> BrownConrady<S> {
+        BrownConrady::none()
+    }
+}
@@ -30,9 +30,9 @@ use angle::Rad;
 use approx;
 use euler::Euler;
 use matrix::{Matrix3, Matrix4};
-use num::BaseFloat;
+use num::{BaseFloat, ToF32};
 use point::Point3;
-use rotation::{Basis3, Rotation, Rotation3};
+use rotation::{AxisAngle, Basis3, Rotation, Rotation3};
 use vector::Vector3;
 
 #[cfg(feature = "mint")]
@@ -67,6 +67,21 @@ impl<S> Quaternion<S> {
     }
 }
 
+macro_rules! impl_quaternion_consts {
+    ($($S:ident),+) => {
+        $(
+            impl Quaternion<$S> {
+                /// The additive identity, with all components set to zero.
+                pub const ZERO: Quaternion<$S> = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+                /// The multiplicative identity, representing no rotation.
+                pub const IDENTITY: Quaternion<$S> = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+            }
+        )+
+    };
+}
+
+impl_quaternion_consts!(f32, f64);
+
 impl<S: BaseFloat> Quaternion<S> {
     /// Construct a new quaternion as a closest arc between two vectors
     ///
@@ -83,12 +98,12 @@ impl<S: BaseFloat> Quaternion<S> {
     ) -> Quaternion<S> {
         let mag_avg = (src.magnitude2() * dst.magnitude2()).sqrt();
         let dot = src.dot(dst);
-        if ulps_eq!(dot, &mag_avg) {
+        if implicit_ulps_eq!(dot, &mag_avg) {
             Quaternion::<S>::one()
-        } else if ulps_eq!(dot, &-mag_avg) {
+        } else if implicit_ulps_eq!(dot, &-mag_avg) {
             let axis = fallback.unwrap_or_else(|| {
                 let mut v = Vector3::unit_x().cross(src);
-                if ulps_eq!(v, &Zero::zero()) {
+                if implicit_ulps_eq!(v, &Zero::zero()) {
                     v = Vector3::unit_y().cross(src);
                 }
                 v.normalize()
@@ -99,6 +114,133 @@ impl<S: BaseFloat> Quaternion<S> {
         }
     }
 
+    /// Construct the rotation about `lock_axis` that turns `forward` as close
+    /// as possible to `dir`.
+    ///
+    /// Both vectors are projected onto the plane perpendicular to the axis,
+    /// and the result is the shortest rotation between the projections, so
+    /// an object spinning on a ground plane can aim at a target above or
+    /// below it without tilting. The identity is returned if either vector
+    /// is parallel to the axis. The axis does not need to be normalized.
+    ///
+    /// ```rust
+    /// use cgmath::{Quaternion, Rotation, Vector3};
+    /// # #[macro_use] extern crate approx;
+    /// # fn main() {
+    ///
+    /// let forward = Vector3::unit_z();
+    /// let target = Vector3::new(5.0, 3.0, 0.0);
+    /// let yaw = Quaternion::face_towards_axis(forward, target, Vector3::unit_y());
+    /// assert_relative_eq!(yaw.rotate_vector(forward), Vector3::unit_x());
+    /// # }
+    /// ```
+    pub fn face_towards_axis(
+        forward: Vector3<S>,
+        dir: Vector3<S>,
+        lock_axis: Vector3<S>,
+    ) -> Quaternion<S> {
+        let axis = lock_axis.normalize();
+        let forward = forward - axis * forward.dot(axis);
+        let dir = dir - axis * dir.dot(axis);
+        if implicit_ulps_eq!(forward, &Zero::zero()) || implicit_ulps_eq!(dir, &Zero::zero()) {
+            return Quaternion::one();
+        }
+        let angle = Rad::atan2(forward.cross(dir).dot(axis), forward.dot(dir));
+        Quaternion::from_axis_angle(axis, angle)
+    }
+
+    /// Encode a tangent frame as a quaternion, as used by compact vertex
+    /// formats.
+    ///
+    /// The columns of `tbn` are the tangent, the bitangent and the normal.
+    /// The frame is orthonormalized around the normal first, and whether it
+    /// is mirrored is stored in the sign of the scalar part, which is kept
+    /// away from zero so that the sign survives quantization to 16 bit
+    /// integers. This is the scheme from "Spherical Skinning with Dual
+    /// Quaternions and QTangents" by Ivo Zoltan Frey.
+    ///
+    /// ```rust
+    /// use cgmath::{Matrix3, Quaternion, Vector3};
+    /// # #[macro_use] extern crate approx;
+    /// # fn main() {
+    ///
+    /// // A mirrored frame, as found on the mirrored half of a symmetric mesh.
+    /// let tbn = Matrix3::from_cols(Vector3::<f64>::unit_x(), -Vector3::unit_y(), Vector3::unit_z());
+    /// let q = Quaternion::from_tangent_frame(tbn);
+    /// assert!(q.s < 0.0);
+    /// assert_relative_eq!(q.to_tangent_frame(), tbn, epsilon = 1e-4);
+    /// # }
+    /// ```
+    pub fn from_tangent_frame(tbn: Matrix3<S>) -> Quaternion<S> {
+        let normal = tbn.z.normalize();
+        let tangent = (tbn.x - normal * normal.dot(tbn.x)).normalize();
+        let bitangent = normal.cross(tangent);
+        let q = Quaternion::from(Matrix3::from_cols(tangent, bitangent, normal)).normalize();
+
+        let mut q = if q.s < S::zero() { -q } else { q };
+        let bias: S = cast(1.0 / 32767.0).unwrap();
+        if q.s < bias {
+            let scale = (S::one() - bias * bias).sqrt() / q.v.magnitude();
+            q = Quaternion::from_sv(bias, q.v * scale);
+        }
+        if tbn.y.dot(bitangent) < S::zero() {
+            -q
+        } else {
+            q
+        }
+    }
+
+    /// Decode a tangent frame encoded with `from_tangent_frame`, returning
+    /// a matrix whose columns are the tangent, the bitangent and the normal.
+    ///
+    /// The quaternion does not need to be normalized, so it can be read
+    /// directly from quantized vertex data.
+    pub fn to_tangent_frame(&self) -> Matrix3<S> {
+        let mut tbn = Matrix3::from(self.normalize());
+        if self.s < S::zero() {
+            tbn.y = -tbn.y;
+        }
+        tbn
+    }
+
+    /// Compress the rotation into 32 bits with the "smallest three"
+    /// encoding: the index of the largest component in the top two bits,
+    /// followed by the three other components with 10 bits each.
+    ///
+    /// The components are ordered `s`, `v.x`, `v.y`, `v.z`. As every
+    /// component but the largest is at most `1/√2` in magnitude, each one
+    /// is quantized over that range, and the largest one is recovered from
+    /// the unit length of the quaternion. The quaternion does not need to be
+    /// normalized.
+    ///
+    /// ```rust
+    /// use cgmath::{Deg, Quaternion, Rotation3, Vector3};
+    ///
+    /// let q = Quaternion::from_axis_angle(Vector3::unit_y(), Deg(30.0f64));
+    /// let decoded = Quaternion::unpack_smallest_three(q.pack_smallest_three());
+    /// assert!(q.angle_between(&decoded).0 < 2e-3);
+    /// ```
+    pub fn pack_smallest_three(&self) -> u32 {
+        pack_smallest_three(*self, 10) as u32
+    }
+
+    /// Decode a rotation compressed with `pack_smallest_three`.
+    pub fn unpack_smallest_three(packed: u32) -> Quaternion<S> {
+        unpack_smallest_three(packed as u64, 10)
+    }
+
+    /// Compress the rotation into the low 48 bits of a `u64`, like
+    /// `pack_smallest_three` but with 15 bits for each component, and the
+    /// top bit of the 48 unused.
+    pub fn pack_smallest_three_48(&self) -> u64 {
+        pack_smallest_three(*self, 15)
+    }
+
+    /// Decode a rotation compressed with `pack_smallest_three_48`.
+    pub fn unpack_smallest_three_48(packed: u64) -> Quaternion<S> {
+        unpack_smallest_three(packed, 15)
+    }
+
     /// The conjugate of the quaternion.
     #[inline]
     pub fn conjugate(self) -> Quaternion<S> {
@@ -156,6 +298,171 @@ impl<S: BaseFloat> Quaternion<S> {
     pub fn is_finite(&self) -> bool {
         self.s.is_finite() && self.v.is_finite()
     }
+
+    /// Returns `true` if the two quaternions represent approximately the same
+    /// rotation.
+    ///
+    /// Unit quaternions form a double cover of the rotation group: `q` and
+    /// `-q` describe the same orientation, even though a component-wise
+    /// comparison of the two would fail. This method accepts either sign,
+    /// using the default relative tolerances of the scalar type.
+    ///
+    /// ```rust
+    /// use cgmath::{Deg, Quaternion, Rotation3, Vector3};
+    ///
+    /// let q = Quaternion::from_axis_angle(Vector3::unit_z(), Deg(90.0f32));
+    /// assert_ne!(q, -q);
+    /// assert!(q.approx_eq_rotation(&-q));
+    /// ```
+    pub fn approx_eq_rotation(&self, other: &Quaternion<S>) -> bool {
+        use approx::RelativeEq;
+
+        let epsilon = S::default_epsilon();
+        let max_relative = S::default_max_relative();
+        self.relative_eq(other, epsilon, max_relative)
+            || self.relative_eq(&-other, epsilon, max_relative)
+    }
+
+    /// The angle of the shortest rotation taking `self` to `other`, in the
+    /// range `[0, π]`.
+    ///
+    /// Both quaternions should be normalized. Antipodal quaternions, which
+    /// represent the same rotation, are `0` radians apart.
+    pub fn angle_between(&self, other: &Quaternion<S>) -> Rad<S> {
+        let two: S = cast(2).unwrap();
+        let diff = self.conjugate() * *other;
+        Rad::atan2(diff.v.magnitude(), diff.s.abs()) * two
+    }
+
+    /// The exponential of the quaternion.
+    ///
+    /// The exponential of a pure quaternion `(0, v)` is the rotation by
+    /// twice the length of `v` around its direction, so this is how a
+    /// rotation vector halved is turned back into a unit quaternion.
+    pub fn exp(self) -> Quaternion<S> {
+        let scale = self.s.exp();
+        let angle = self.v.magnitude();
+        if angle == S::zero() {
+            return Quaternion::from_sv(scale, Vector3::zero());
+        }
+        let (sin, cos) = Rad::sin_cos(Rad(angle));
+        Quaternion::from_sv(scale * cos, self.v * (scale * sin / angle))
+    }
+
+    /// The natural logarithm of the quaternion, the inverse of `exp`.
+    ///
+    /// The logarithm of a unit quaternion is the pure quaternion whose
+    /// vector part is half of the rotation vector, with an angle in
+    /// `[0, π]`. Quaternions with a negative scalar part take the long way
+    /// around, so negate them first for the shortest rotation.
+    ///
+    /// ```rust
+    /// use cgmath::{Deg, Quaternion, Rotation3, Vector3};
+    /// # #[macro_use] extern crate approx;
+    /// # fn main() {
+    ///
+    /// let q = Quaternion::from_axis_angle(Vector3::unit_z(), Deg(60.0f64));
+    /// let log = q.ln();
+    /// assert_relative_eq!(log.v, Vector3::unit_z() * 30f64.to_radians(), epsilon = 1e-12);
+    /// assert_relative_eq!(log.exp(), q, epsilon = 1e-12);
+    /// # }
+    /// ```
+    pub fn ln(self) -> Quaternion<S> {
+        let magnitude = self.magnitude();
+        let sin = self.v.magnitude();
+        if sin == S::zero() {
+            return Quaternion::from_sv(magnitude.ln(), Vector3::zero());
+        }
+        let angle = Rad::atan2(sin, self.s).0;
+        Quaternion::from_sv(magnitude.ln(), self.v * (angle / sin))
+    }
+
+    /// The constant angular velocity turning `self` into `next` in `dt`,
+    /// along the shortest path.
+    ///
+    /// The direction of the result is the axis of rotation in the world
+    /// frame, and its length the rate in radians per unit of time, as
+    /// `2 ln(next * self⁻¹) / dt`. These finite differences between
+    /// keyframes give the tangents of quaternion splines and the rotation
+    /// of motion blur. Both quaternions should be normalized.
+    pub fn angular_velocity(self, next: Quaternion<S>, dt: S) -> Vector3<S> {
+        let delta = next * self.conjugate();
+        let delta = if delta.s < S::zero() { -delta } else { delta };
+        delta.ln().v * ((S::one() + S::one()) / dt)
+    }
+
+    /// Rotate by the constant `angular_velocity` for `dt`, the inverse of
+    /// `angular_velocity`.
+    ///
+    /// ```rust
+    /// use cgmath::{Deg, Quaternion, Rotation3, Vector3};
+    /// # #[macro_use] extern crate approx;
+    /// # fn main() {
+    ///
+    /// let a = Quaternion::from_axis_angle(Vector3::unit_x(), Deg(10.0f64));
+    /// let b = Quaternion::from_axis_angle(Vector3::unit_y(), Deg(50.0)) * a;
+    /// let omega = a.angular_velocity(b, 0.5);
+    /// assert_relative_eq!(omega, Vector3::unit_y() * 100f64.to_radians(), epsilon = 1e-12);
+    /// assert_relative_eq!(a.integrate(omega, 0.5), b, epsilon = 1e-12);
+    /// # }
+    /// ```
+    pub fn integrate(self, angular_velocity: Vector3<S>, dt: S) -> Quaternion<S> {
+        let half_dt = dt / (S::one() + S::one());
+        Quaternion::from_sv(S::zero(), angular_velocity * half_dt).exp() * self
+    }
+}
+
+/// Encode a unit quaternion as the index of its largest component, in two
+/// bits, followed by its other three components with `bits` bits each.
+fn pack_smallest_three<S: BaseFloat>(q: Quaternion<S>, bits: u32) -> u64 {
+    let q = q.normalize();
+    let components = [q.s, q.v.x, q.v.y, q.v.z];
+    let largest = (1..4).fold(0, |largest, i| {
+        if components[i].abs() > components[largest].abs() {
+            i
+        } else {
+            largest
+        }
+    });
+    // `q` and `-q` are the same rotation, so make the largest component
+    // positive, and leave it out.
+    let sign = if components[largest] < S::zero() { -S::one() } else { S::one() };
+    let steps = (1u64 << bits) - 2;
+    let half_steps: S = cast(steps / 2).unwrap();
+    let range = S::one() / cast::<_, S>(2).unwrap().sqrt();
+
+    let mut packed = largest as u64;
+    for (i, &c) in components.iter().enumerate() {
+        if i != largest {
+            let scaled = ((c * sign / range + S::one()) * half_steps).round();
+            let quantized: u64 = cast(scaled.max(S::zero())).unwrap();
+            packed = (packed << bits) | quantized.min(steps);
+        }
+    }
+    packed
+}
+
+/// Decode a quaternion encoded with `pack_smallest_three`.
+fn unpack_smallest_three<S: BaseFloat>(packed: u64, bits: u32) -> Quaternion<S> {
+    let mask = (1u64 << bits) - 1;
+    let steps = (1u64 << bits) - 2;
+    let half_steps: S = cast(steps / 2).unwrap();
+    let range = S::one() / cast::<_, S>(2).unwrap().sqrt();
+
+    let largest = ((packed >> (3 * bits)) & 3) as usize;
+    let mut components = [S::zero(); 4];
+    let mut shift = 3 * bits;
+    let mut sum = S::zero();
+    for (i, c) in components.iter_mut().enumerate() {
+        if i != largest {
+            shift -= bits;
+            let quantized: S = cast(((packed >> shift) & mask).min(steps)).unwrap();
+            *c = (quantized / half_steps - S::one()) * range;
+            sum += *c * *c;
+        }
+    }
+    components[largest] = (S::one() - sum).max(S::zero()).sqrt();
+    Quaternion::new(components[0], components[1], components[2], components[3]).normalize()
 }
 
 impl<S: BaseFloat> Zero for Quaternion<S> {
@@ -166,7 +473,7 @@ impl<S: BaseFloat> Zero for Quaternion<S> {
 
     #[inline]
     fn is_zero(&self) -> bool {
-        ulps_eq!(self, &Quaternion::<S>::zero())
+        implicit_ulps_eq!(self, &Quaternion::<S>::zero())
     }
 }
 
@@ -177,6 +484,14 @@ impl<S: BaseFloat> One for Quaternion<S> {
     }
 }
 
+/// The default quaternion is the identity, representing no rotation.
+impl<S: BaseFloat> Default for Quaternion<S> {
+    #[inline]
+    fn default() -> Quaternion<S> {
+        Quaternion::one()
+    }
+}
+
 impl<S: BaseFloat> iter::Sum<Quaternion<S>> for Quaternion<S> {
     #[inline]
     fn sum<I: Iterator<Item = Quaternion<S>>>(iter: I) -> Quaternion<S> {
@@ -485,16 +800,16 @@ impl<S: BaseFloat> Rotation<Point3<S>> for Quaternion<S> {
         let k_cos_theta = a.dot(b);
 
         // same direction
-        if ulps_eq!(k_cos_theta, S::one()) {
+        if implicit_ulps_eq!(k_cos_theta, S::one()) {
             return Quaternion::<S>::one();
         }
 
         let k = (a.magnitude2() * b.magnitude2()).sqrt();
 
         // opposite direction
-        if ulps_eq!(k_cos_theta / k, -S::one()) {
+        if implicit_ulps_eq!(k_cos_theta / k, -S::one()) {
             let mut orthogonal = a.cross(Vector3::unit_x());
-            if ulps_eq!(orthogonal.magnitude2(), S::zero()) {
+            if implicit_ulps_eq!(orthogonal.magnitude2(), S::zero()) {
                 orthogonal = a.cross(Vector3::unit_y());
             }
             return Quaternion::from_sv(S::zero(), orthogonal.normalize());
@@ -524,6 +839,11 @@ impl<S: BaseFloat> Rotation3<S> for Quaternion<S> {
         let (s, c) = Rad::sin_cos(angle.into() * cast(0.5f64).unwrap());
         Quaternion::from_sv(c, axis * s)
     }
+
+    #[inline]
+    fn scaled(&self, amount: S) -> Quaternion<S> {
+        AxisAngle::from(*self).scaled(amount).into()
+    }
 }
 
 impl<S: BaseFloat> Into<[S; 4]> for Quaternion<S> {
@@ -759,3 +1079,5 @@ mod tests {
         }
     }
 }
+
+impl_precision_conversions!(Quaternion { s, v }, Quaternion<f32>, Quaternion<f64>);
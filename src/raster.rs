@@ -0,0 +1,518 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Software rasterization of triangles onto grids of pixels, such as
+//! occlusion buffers and lightmaps.
+//!
+//! Pixel `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)`, and
+//! is sampled at its center. Rows go downwards, as in images, so that the
+//! fill rules have their usual meaning. Conservative rasterization instead
+//! covers every pixel whose square touches the triangle, as needed by
+//! occlusion culling and voxelization, which must never miss thin shapes.
+//! The resulting depth buffers are tested against with a `DepthPyramid`.
+//!
+//! ```rust
+//! use cgmath::raster::rasterize;
+//! use cgmath::{Point2, Triangle2};
+//!
+//! // Two triangles sharing a diagonal cover each pixel of a square once.
+//! let (a, b, c, d) = (
+//!     Point2::new(0.0, 0.0),
+//!     Point2::new(4.0, 0.0),
+//!     Point2::new(4.0, 4.0),
+//!     Point2::new(0.0, 4.0f64),
+//! );
+//! let mut count = [[0; 4]; 4];
+//! for triangle in &[Triangle2::new(a, b, c), Triangle2::new(a, c, d)] {
+//!     for fragment in rasterize(triangle, 4, 4) {
+//!         count[fragment.y][fragment.x] += 1;
+//!     }
+//! }
+//! assert_eq!(count, [[1; 4]; 4]);
+//! ```
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::{Aabb2, Aabb3};
+use matrix::Matrix4;
+use num::BaseFloat;
+use point::Point2;
+use predicates::orient2d;
+use triangle::Triangle2;
+use vector::{Vector3, Vector4};
+
+/// A pixel covered by a triangle, as yielded by `rasterize` and its
+/// variants.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Fragment<S> {
+    /// The column of the pixel.
+    pub x: usize,
+    /// The row of the pixel.
+    pub y: usize,
+    /// The barycentric coordinates of the center of the pixel with respect
+    /// to the vertices `a`, `b` and `c` of the triangle. With conservative
+    /// rasterization the center may be outside of the triangle, so that
+    /// some coordinates are negative, and for segments these are the
+    /// coordinates of the point of the segment closest to the center.
+    pub barycentric: Vector3<S>,
+}
+
+/// How a `Coverage` decides which pixels are covered.
+#[derive(Copy, Clone, Debug)]
+enum Rule {
+    /// The center is inside, or on an edge marked as inclusive, for each
+    /// edge opposite to the vertex of the same index.
+    TopLeft([bool; 3]),
+    /// The square of the pixel touches the triangle.
+    Conservative,
+    /// The square of the pixel touches the segment between the vertices of
+    /// the given indices.
+    Segment(usize, usize),
+}
+
+/// The pixels covered by a triangle, in rows from top to bottom, as built
+/// by `rasterize` and its variants.
+#[derive(Clone, Debug)]
+pub struct Coverage<S> {
+    /// The vertices, reordered to have a non-negative signed area.
+    vertices: [Point2<S>; 3],
+    /// Whether `b` and `c` were swapped to get `vertices`.
+    flipped: bool,
+    rule: Rule,
+    x_range: (usize, usize),
+    y_end: usize,
+    x: usize,
+    y: usize,
+}
+
+impl<S: BaseFloat> Coverage<S> {
+    /// The coverage of the pixels overlapping the bounds of the vertices
+    /// with the given rule. Without `conservative` bounds, only the pixels
+    /// whose centers are within them are walked.
+    fn new(
+        triangle: &Triangle2<S>,
+        width: usize,
+        height: usize,
+        conservative: bool,
+    ) -> Coverage<S> {
+        let area = orient2d(triangle.a, triangle.b, triangle.c);
+        let flipped = area < S::zero();
+        let vertices = if flipped {
+            [triangle.a, triangle.c, triangle.b]
+        } else {
+            [triangle.a, triangle.b, triangle.c]
+        };
+        // With rows going down, the interior of the triangle is on the right
+        // of each edge. Left edges go up, and top edges go right.
+        let top_left = |from: Point2<S>, to: Point2<S>| {
+            let (dx, dy) = (to.x - from.x, to.y - from.y);
+            dy < S::zero() || (dy == S::zero() && dx > S::zero())
+        };
+        let rule = if !conservative {
+            Rule::TopLeft([
+                top_left(vertices[1], vertices[2]),
+                top_left(vertices[2], vertices[0]),
+                top_left(vertices[0], vertices[1]),
+            ])
+        } else if area == S::zero() {
+            // The segment between the two vertices farthest apart.
+            let d = |i: usize, j: usize| vertices[i].distance2(vertices[j]);
+            let (i, j) = [(0, 1), (1, 2), (2, 0)]
+                .iter()
+                .cloned()
+                .fold((0, 1), |best, (i, j)| {
+                    if d(i, j) > d(best.0, best.1) {
+                        (i, j)
+                    } else {
+                        best
+                    }
+                });
+            Rule::Segment(i, j)
+        } else {
+            Rule::Conservative
+        };
+
+        let half: S = cast(0.5).unwrap();
+        let range = |min: S, max: S, size: usize| -> (usize, usize) {
+            let (first, end) = if conservative {
+                ((min - S::one()).ceil(), max.floor() + S::one())
+            } else {
+                ((min - half).ceil(), (max - half).floor() + S::one())
+            };
+            let size: S = cast(size).unwrap();
+            let clamp = |v: S| v.max(S::zero()).min(size).to_usize().unwrap_or(0);
+            (clamp(first), clamp(end))
+        };
+        let (min, max) = vertices[1..]
+            .iter()
+            .fold((vertices[0], vertices[0]), |(min, max), v| {
+                (
+                    Point2::new(min.x.min(v.x), min.y.min(v.y)),
+                    Point2::new(max.x.max(v.x), max.y.max(v.y)),
+                )
+            });
+        let x_range = range(min.x, max.x, width);
+        let (y, y_end) = if area == S::zero() && !conservative {
+            (0, 0)
+        } else {
+            range(min.y, max.y, height)
+        };
+        Coverage {
+            vertices,
+            flipped,
+            rule,
+            x_range,
+            y_end,
+            x: x_range.0,
+            y,
+        }
+    }
+
+    /// The fragment of the pixel `(x, y)`, if the triangle covers it.
+    fn fragment(&self, x: usize, y: usize) -> Option<Fragment<S>> {
+        let half: S = cast(0.5).unwrap();
+        let (x0, y0): (S, S) = (cast(x).unwrap(), cast(y).unwrap());
+        let p = Point2::new(x0 + half, y0 + half);
+        let v = &self.vertices;
+        let edges = [(v[1], v[2]), (v[2], v[0]), (v[0], v[1])];
+        let e = [
+            orient2d(v[1], v[2], p),
+            orient2d(v[2], v[0], p),
+            orient2d(v[0], v[1], p),
+        ];
+        // The corner of the square of the pixel farthest inside each edge,
+        // whose inward normal is `(-dy, dx)`.
+        let corner = |(from, to): (Point2<S>, Point2<S>)| {
+            let inside_x = from.y > to.y;
+            let inside_y = to.x > from.x;
+            Point2::new(
+                if inside_x { x0 + S::one() } else { x0 },
+                if inside_y { y0 + S::one() } else { y0 },
+            )
+        };
+        let covered = match self.rule {
+            Rule::TopLeft(inclusive) => e
+                .iter()
+                .zip(&inclusive)
+                .all(|(&e, &inclusive)| e > S::zero() || (e == S::zero() && inclusive)),
+            Rule::Conservative => edges
+                .iter()
+                .all(|&(from, to)| orient2d(from, to, corner((from, to))) >= S::zero()),
+            Rule::Segment(i, j) => {
+                let squares = [
+                    orient2d(v[i], v[j], Point2::new(x0, y0)),
+                    orient2d(v[i], v[j], Point2::new(x0 + S::one(), y0)),
+                    orient2d(v[i], v[j], Point2::new(x0, y0 + S::one())),
+                    orient2d(v[i], v[j], Point2::new(x0 + S::one(), y0 + S::one())),
+                ];
+                !squares.iter().all(|&o| o > S::zero()) && !squares.iter().all(|&o| o < S::zero())
+            }
+        };
+        if !covered {
+            return None;
+        }
+        let w = match self.rule {
+            Rule::Segment(i, j) => {
+                let d = v[j] - v[i];
+                let length2 = d.magnitude2();
+                let t = if length2 > S::zero() {
+                    ((p - v[i]).dot(d) / length2).max(S::zero()).min(S::one())
+                } else {
+                    S::zero()
+                };
+                let mut w = [S::zero(); 3];
+                w[i] = S::one() - t;
+                w[j] += t;
+                w
+            }
+            _ => {
+                let sum = e[0] + e[1] + e[2];
+                [e[0] / sum, e[1] / sum, e[2] / sum]
+            }
+        };
+        let barycentric = if self.flipped {
+            Vector3::new(w[0], w[2], w[1])
+        } else {
+            Vector3::new(w[0], w[1], w[2])
+        };
+        Some(Fragment { x, y, barycentric })
+    }
+}
+
+/// The pixels of a `width` by `height` grid whose centers are covered by a
+/// triangle, with the top-left fill rule.
+///
+/// Centers exactly on an edge of the triangle are only covered if it is a
+/// left edge, or a horizontal edge at the top of the triangle. This is the
+/// rule of Direct3D and OpenGL, with which triangles that share an edge
+/// never cover the same pixel twice, nor leave a gap between them. The
+/// edge tests use the exact orientation predicate, so this holds for any
+/// vertices. Triangles of either orientation are covered, and degenerate
+/// ones cover no pixels.
+pub fn rasterize<S: BaseFloat>(
+    triangle: &Triangle2<S>,
+    width: usize,
+    height: usize,
+) -> Coverage<S> {
+    Coverage::new(triangle, width, height, false)
+}
+
+/// The pixels of a `width` by `height` grid whose squares touch a triangle,
+/// which overestimates the coverage of `rasterize`.
+///
+/// Unlike with `rasterize`, thin triangles never fall between pixel
+/// centers, and pixels only touched at a corner or along an edge are
+/// covered too. This is the overestimating conservative rasterization of
+/// graphics hardware, except that it is exact: it covers no pixel that the
+/// triangle does not touch. Degenerate triangles cover the pixels touched by
+/// the segment between their two vertices farthest apart.
+///
+/// ```rust
+/// use cgmath::raster::{rasterize, rasterize_conservative};
+/// use cgmath::{Point2, Triangle2};
+///
+/// // A sliver between the centers of the first two rows.
+/// let sliver = Triangle2::new(
+///     Point2::new(0.2, 0.6),
+///     Point2::new(3.8, 0.7),
+///     Point2::new(0.2, 0.8f64),
+/// );
+/// assert_eq!(rasterize(&sliver, 4, 4).count(), 0);
+/// let pixels: Vec<_> = rasterize_conservative(&sliver, 4, 4).map(|f| (f.x, f.y)).collect();
+/// assert_eq!(pixels, vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+/// ```
+pub fn rasterize_conservative<S: BaseFloat>(
+    triangle: &Triangle2<S>,
+    width: usize,
+    height: usize,
+) -> Coverage<S> {
+    Coverage::new(triangle, width, height, true)
+}
+
+/// The pixels of a `width` by `height` grid whose squares touch the segment
+/// from `a` to `b`, so that the pixels form a connected path even through
+/// corners, also called a supercover line.
+///
+/// The barycentric coordinates of the fragments weight `a` and `b` at the
+/// point of the segment closest to the center of each pixel, with a zero
+/// third coordinate.
+pub fn rasterize_segment<S: BaseFloat>(
+    a: Point2<S>,
+    b: Point2<S>,
+    width: usize,
+    height: usize,
+) -> Coverage<S> {
+    let mut coverage = Coverage::new(&Triangle2::new(a, b, b), width, height, true);
+    coverage.rule = Rule::Segment(0, 1);
+    coverage
+}
+
+impl<S: BaseFloat> Iterator for Coverage<S> {
+    type Item = Fragment<S>;
+
+    fn next(&mut self) -> Option<Fragment<S>> {
+        while self.y < self.y_end {
+            while self.x < self.x_range.1 {
+                let x = self.x;
+                self.x += 1;
+                if let Some(fragment) = self.fragment(x, self.y) {
+                    return Some(fragment);
+                }
+            }
+            self.x = self.x_range.0;
+            self.y += 1;
+        }
+        None
+    }
+}
+
+/// A hierarchical depth buffer, also called a Hi-Z buffer, for occlusion
+/// culling.
+///
+/// Each level halves the size of the previous one, rounding up, and keeps
+/// the farthest depth of the two by two pixels below each of its pixels, so
+/// that testing a rectangle of the screen reads at most four depths. Depths
+/// grow away from the camera, as the window depths from zero to one of the
+/// projections of this crate, with rows going down as in the rest of this
+/// module.
+///
+/// ```rust
+/// use cgmath::raster::DepthPyramid;
+/// use cgmath::{Aabb2, Point2};
+///
+/// // A wall at depth 0.5 covers the left half of the screen.
+/// let depths: Vec<f64> = (0..64 * 64).map(|i| if i % 64 < 32 { 0.5 } else { 1.0 }).collect();
+/// let pyramid = DepthPyramid::new(&depths, 64, 64);
+/// assert_eq!(pyramid.levels(), 7);
+///
+/// let behind_wall = Aabb2::new(Point2::new(4.0, 10.0), Point2::new(28.0, 50.0));
+/// assert!(pyramid.is_occluded(&behind_wall, 0.8));
+/// assert!(!pyramid.is_occluded(&behind_wall, 0.3));
+/// let beside_wall = Aabb2::new(Point2::new(20.0, 10.0), Point2::new(40.0, 50.0));
+/// assert!(!pyramid.is_occluded(&beside_wall, 0.8));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct DepthPyramid<S> {
+    /// The width, height and depths of each level, from the finest.
+    levels: Vec<(usize, usize, Vec<S>)>,
+}
+
+impl<S: BaseFloat> DepthPyramid<S> {
+    /// Build the pyramid of a `width` by `height` depth buffer, whose depths
+    /// are stored in rows from the top.
+    ///
+    /// # Panics
+    ///
+    /// If the buffer is empty, or if the number of depths is not
+    /// `width * height`.
+    pub fn new(depths: &[S], width: usize, height: usize) -> DepthPyramid<S> {
+        assert!(
+            width > 0 && height > 0,
+            "the depth buffer must not be empty"
+        );
+        assert_eq!(depths.len(), width * height, "wrong number of depths");
+        let mut levels = vec![(width, height, depths.to_vec())];
+        loop {
+            let (width, height, ref depths) = levels[levels.len() - 1];
+            if width == 1 && height == 1 {
+                break;
+            }
+            let (next_width, next_height) = ((width + 1) / 2, (height + 1) / 2);
+            let mut next = Vec::with_capacity(next_width * next_height);
+            for y in 0..next_height {
+                for x in 0..next_width {
+                    let (x0, y0) = (2 * x, 2 * y);
+                    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+                    let depth = depths[y0 * width + x0]
+                        .max(depths[y0 * width + x1])
+                        .max(depths[y1 * width + x0])
+                        .max(depths[y1 * width + x1]);
+                    next.push(depth);
+                }
+            }
+            levels.push((next_width, next_height, next));
+        }
+        DepthPyramid { levels }
+    }
+
+    /// The number of levels, including the full resolution buffer.
+    #[inline]
+    pub fn levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// The farthest depth of the pixel `(x, y)` of a level, which covers the
+    /// pixels from `(x << level, y << level)` of the full resolution buffer.
+    #[inline]
+    pub fn depth(&self, level: usize, x: usize, y: usize) -> S {
+        let (width, height, ref depths) = self.levels[level];
+        assert!(x < width && y < height, "pixel out of bounds");
+        depths[y * width + x]
+    }
+
+    /// The farthest depth of the pixels touched by a rectangle of the
+    /// screen, in pixels, or `None` if it is entirely outside of the buffer.
+    pub fn max_depth(&self, rect: &Aabb2<S>) -> Option<S> {
+        let (width, height, _) = self.levels[0];
+        let range = |min: S, max: S, size: usize| -> Option<(usize, usize)> {
+            let size_s: S = cast(size).unwrap();
+            if max < S::zero() || min > size_s || min.is_nan() || max.is_nan() {
+                return None;
+            }
+            let first = min
+                .floor()
+                .max(S::zero())
+                .to_usize()
+                .unwrap_or(0)
+                .min(size - 1);
+            let last = (max.ceil() - S::one())
+                .to_usize()
+                .unwrap_or(0)
+                .min(size - 1);
+            Some((first, last.max(first)))
+        };
+        let (x0, x1) = range(rect.min.x, rect.max.x, width)?;
+        let (y0, y1) = range(rect.min.y, rect.max.y, height)?;
+        // The first level where the rectangle spans at most two pixels along
+        // each axis.
+        let mut level = 0;
+        while (x1 >> level) - (x0 >> level) > 1 || (y1 >> level) - (y0 >> level) > 1 {
+            level += 1;
+        }
+        let mut depth = S::neg_infinity();
+        for y in (y0 >> level)..=(y1 >> level) {
+            for x in (x0 >> level)..=(x1 >> level) {
+                depth = depth.max(self.depth(level, x, y));
+            }
+        }
+        Some(depth)
+    }
+
+    /// Test whether everything within a rectangle of the screen, in pixels,
+    /// is hidden when it is at least `depth` away, such as the screen bounds
+    /// and the nearest depth of an object.
+    ///
+    /// Rectangles entirely outside of the buffer are occluded, as nothing
+    /// of them can be seen.
+    #[inline]
+    pub fn is_occluded(&self, rect: &Aabb2<S>, depth: S) -> bool {
+        match self.max_depth(rect) {
+            Some(max) => depth > max,
+            None => true,
+        }
+    }
+
+    /// Test whether a box is hidden, given the combined view and projection
+    /// matrix that rendered the depth buffer.
+    ///
+    /// The box is tested with its screen bounds, from
+    /// `Aabb3::screen_bounds`, and its nearest window depth. Boxes reaching
+    /// behind the camera are never occluded, and boxes entirely off the
+    /// screen always are.
+    pub fn is_aabb_occluded(&self, aabb: &Aabb3<S>, view_projection: &Matrix4<S>) -> bool {
+        let bounds = match aabb.screen_bounds(view_projection) {
+            Some(bounds) => bounds,
+            None => return true,
+        };
+        let clip: Vec<Vector4<S>> = aabb
+            .corners()
+            .iter()
+            .map(|p| view_projection * p.to_homogeneous())
+            .collect();
+        if clip.iter().any(|c| c.w <= S::zero()) {
+            return false;
+        }
+        let half: S = cast(0.5).unwrap();
+        let nearest = clip
+            .iter()
+            .map(|c| (c.z / c.w + S::one()) * half)
+            .fold(S::infinity(), S::min);
+        // From normalized device coordinates, with `y` up, to pixels.
+        let (width, height, _) = self.levels[0];
+        let (width, height): (S, S) = (cast(width).unwrap(), cast(height).unwrap());
+        let pixel = |p: Point2<S>| {
+            Point2::new(
+                (p.x + S::one()) * half * width,
+                (S::one() - p.y) * half * height,
+            )
+        };
+        let (a, b) = (pixel(bounds.min), pixel(bounds.max));
+        let rect = Aabb2::new(Point2::new(a.x, b.y), Point2::new(b.x, a.y));
+        self.is_occluded(&rect, nearest)
+    }
+}
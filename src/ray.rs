@@ -0,0 +1,319 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use ellipse::{Ellipse2, Ellipsoid3};
+use num::BaseFloat;
+use plane::Plane;
+use point::{Point2, Point3};
+use sphere::Sphere;
+use triangle::Triangle3;
+use vector::{Vector2, Vector3};
+
+/// A half-line in two-dimensional space, starting at an origin and going in
+/// a direction.
+///
+/// The direction does not need to be normalized, but must not be zero. The
+/// points of the ray are `origin + direction * t` for every `t >= 0`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ray2<S> {
+    /// The point the ray starts from.
+    pub origin: Point2<S>,
+    /// The direction of the ray.
+    pub direction: Vector2<S>,
+}
+
+impl<S: BaseFloat> Ray2<S> {
+    /// Construct a ray from its origin and direction.
+    #[inline]
+    pub fn new(origin: Point2<S>, direction: Vector2<S>) -> Ray2<S> {
+        Ray2 { origin, direction }
+    }
+
+    /// The point at parameter `t` along the ray.
+    #[inline]
+    pub fn point_at(&self, t: S) -> Point2<S> {
+        self.origin + self.direction * t
+    }
+}
+
+/// A half-line in three-dimensional space, starting at an origin and going
+/// in a direction.
+///
+/// The direction does not need to be normalized, but must not be zero. The
+/// points of the ray are `origin + direction * t` for every `t >= 0`, so
+/// when the direction is normalized, `t` is the distance along the ray.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ray3<S> {
+    /// The point the ray starts from.
+    pub origin: Point3<S>,
+    /// The direction of the ray.
+    pub direction: Vector3<S>,
+}
+
+impl<S: BaseFloat> Ray3<S> {
+    /// Construct a ray from its origin and direction.
+    #[inline]
+    pub fn new(origin: Point3<S>, direction: Vector3<S>) -> Ray3<S> {
+        Ray3 { origin, direction }
+    }
+
+    /// The point at parameter `t` along the ray.
+    #[inline]
+    pub fn point_at(&self, t: S) -> Point3<S> {
+        self.origin + self.direction * t
+    }
+
+    /// Create a line segment covering the first `length` units of the ray.
+    pub fn to_wireframe_lines(&self, length: S) -> Vec<(Point3<S>, Point3<S>)> {
+        vec![(
+            self.origin,
+            self.origin + self.direction.normalize_to(length),
+        )]
+    }
+}
+
+/// The parameter of the first point of the ray on the plane.
+pub(crate) fn ray_plane<S: BaseFloat>(ray: &Ray3<S>, plane: &Plane<S>) -> Option<S> {
+    let denom = plane.normal.dot(ray.direction);
+    if denom == S::zero() {
+        return None;
+    }
+    let t = (plane.d - plane.normal.dot(ray.origin.to_vec())) / denom;
+    if t >= S::zero() {
+        Some(t)
+    } else {
+        None
+    }
+}
+
+/// The parameter of the first point of the ray on the surface of the
+/// sphere. Rays starting inside the sphere hit it on the way out.
+pub(crate) fn ray_sphere<S: BaseFloat>(ray: &Ray3<S>, sphere: &Sphere<S>) -> Option<S> {
+    let offset = ray.origin - sphere.center;
+    let a = ray.direction.magnitude2();
+    let b = ray.direction.dot(offset);
+    let c = offset.magnitude2() - sphere.radius * sphere.radius;
+    first_root(a, b, c)
+}
+
+/// The parameter of the first point of the ray on the boundary of the
+/// ellipse. Rays starting inside the ellipse hit it on the way out.
+pub(crate) fn ray_ellipse<S: BaseFloat>(ray: &Ray2<S>, ellipse: &Ellipse2<S>) -> Option<S> {
+    // Scale the ellipse to the unit circle, which keeps the parameters.
+    let offset = ellipse
+        .local_point(ray.origin)
+        .div_element_wise(ellipse.radii);
+    let direction = ellipse
+        .local_vector(ray.direction)
+        .div_element_wise(ellipse.radii);
+    let a = direction.magnitude2();
+    first_root(a, direction.dot(offset), offset.magnitude2() - S::one())
+}
+
+/// The parameter of the first point of the ray on the surface of the
+/// ellipsoid. Rays starting inside the ellipsoid hit it on the way out.
+pub(crate) fn ray_ellipsoid<S: BaseFloat>(ray: &Ray3<S>, ellipsoid: &Ellipsoid3<S>) -> Option<S> {
+    let offset = ellipsoid
+        .local_point(ray.origin)
+        .div_element_wise(ellipsoid.radii);
+    let direction = ellipsoid
+        .local_vector(ray.direction)
+        .div_element_wise(ellipsoid.radii);
+    let a = direction.magnitude2();
+    first_root(a, direction.dot(offset), offset.magnitude2() - S::one())
+}
+
+/// The first non-negative root of `a t² + 2 b t + c`, where `a > 0`.
+fn first_root<S: BaseFloat>(a: S, b: S, c: S) -> Option<S> {
+    let discriminant = b * b - a * c;
+    if discriminant < S::zero() {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let near = (-b - root) / a;
+    let far = (-b + root) / a;
+    if near >= S::zero() {
+        Some(near)
+    } else if far >= S::zero() {
+        Some(far)
+    } else {
+        None
+    }
+}
+
+/// The parameter and barycentric coordinates of the point where the ray
+/// crosses the triangle, from either side, using the Möller–Trumbore
+/// algorithm.
+pub(crate) fn ray_triangle<S: BaseFloat>(
+    ray: &Ray3<S>,
+    triangle: &Triangle3<S>,
+) -> Option<(S, Vector3<S>)> {
+    let e1 = triangle.b - triangle.a;
+    let e2 = triangle.c - triangle.a;
+    let p = ray.direction.cross(e2);
+    let det = e1.dot(p);
+    let scale = e1.magnitude() * e2.magnitude() * ray.direction.magnitude();
+    if implicit_ulps_eq!(det, S::zero(), epsilon = S::default_epsilon() * scale) {
+        return None;
+    }
+    let inv_det = S::one() / det;
+    let offset = ray.origin - triangle.a;
+    let u = offset.dot(p) * inv_det;
+    if u < S::zero() || u > S::one() {
+        return None;
+    }
+    let q = offset.cross(e1);
+    let v = ray.direction.dot(q) * inv_det;
+    if v < S::zero() || u + v > S::one() {
+        return None;
+    }
+    let t = e2.dot(q) * inv_det;
+    if t >= S::zero() {
+        Some((t, Vector3::new(S::one() - u - v, u, v)))
+    } else {
+        None
+    }
+}
+
+/// A ray carrying a cone around it, which tracks how wide the pixel or
+/// texel footprint of the ray grows with distance.
+///
+/// This follows the ray cones of "Texture Level of Detail Strategies for
+/// Real-Time Ray Tracing" by Akenine-Möller et al.: at distance `d` along
+/// the ray, the cone is `width + 2 * d * tan(spread_angle / 2)` wide. The
+/// footprint reported at a hit is that width stretched by the obliquity of
+/// the surface, which is what texture level-of-detail selection needs.
+///
+/// ```rust
+/// use cgmath::{Deg, Plane, Point3, Ray3, RayCone, Vector3};
+///
+/// // A primary ray from a camera with a 90° vertical field of view,
+/// // rendering a 1000 pixel tall image.
+/// let ray = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0f64));
+/// let cone = RayCone::from_camera(ray, Deg(90.0).into(), 1000);
+///
+/// let wall = Plane::from_point_normal(Point3::new(0.0, 0.0, -10.0), Vector3::unit_z());
+/// let hit = cone.intersect_plane(&wall).unwrap();
+/// assert!((hit.footprint - 0.02).abs() < 1e-6);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RayCone<S> {
+    /// The central ray of the cone.
+    pub ray: Ray3<S>,
+    /// The width of the cone at the origin of the ray.
+    pub width: S,
+    /// The full opening angle of the cone.
+    pub spread_angle: Rad<S>,
+}
+
+/// The result of intersecting a `RayCone` with a surface.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RayConeHit<S> {
+    /// The parameter of the hit along the central ray.
+    pub t: S,
+    /// The point where the central ray hits the surface.
+    pub point: Point3<S>,
+    /// The unit normal of the surface at the hit point.
+    pub normal: Vector3<S>,
+    /// The width of the cone at the hit point, across the ray.
+    pub width: S,
+    /// The length of the footprint of the cone on the surface, along its
+    /// most stretched direction. This is `width` divided by the cosine of
+    /// the angle between the ray and the normal.
+    pub footprint: S,
+}
+
+impl<S: BaseFloat> RayCone<S> {
+    /// Construct a ray cone from its central ray, its width at the origin
+    /// and its full opening angle.
+    #[inline]
+    pub fn new(ray: Ray3<S>, width: S, spread_angle: Rad<S>) -> RayCone<S> {
+        RayCone {
+            ray,
+            width,
+            spread_angle,
+        }
+    }
+
+    /// Construct the cone of a primary ray from a pinhole camera with the
+    /// vertical field of view `fovy`, rendering an image `height` pixels
+    /// tall, so that the cone covers one pixel.
+    pub fn from_camera(ray: Ray3<S>, fovy: Rad<S>, height: u32) -> RayCone<S> {
+        let two: S = cast(2).unwrap();
+        let height: S = cast(height).unwrap();
+        let spread = Rad::atan(two * (fovy / two).tan() / height);
+        RayCone::new(ray, S::zero(), spread)
+    }
+
+    /// The width of the cone at the parameter `t` along the ray.
+    #[inline]
+    pub fn width_at(&self, t: S) -> S {
+        let two: S = cast(2).unwrap();
+        let distance = t * self.ray.direction.magnitude();
+        self.width + two * distance * (self.spread_angle / two).tan()
+    }
+
+    /// The cone continuing from a hit, for example along a reflected or
+    /// refracted `ray`, with its width taken at the hit and its spread
+    /// widened by `extra_spread` to account for the curvature of the
+    /// surface.
+    #[inline]
+    pub fn propagate(&self, hit: &RayConeHit<S>, ray: Ray3<S>, extra_spread: Rad<S>) -> RayCone<S> {
+        RayCone::new(ray, hit.width, self.spread_angle + extra_spread)
+    }
+
+    fn hit(&self, t: S, normal: Vector3<S>) -> RayConeHit<S> {
+        let width = self.width_at(t);
+        let cos = normal.dot(self.ray.direction.normalize()).abs();
+        RayConeHit {
+            t,
+            point: self.ray.point_at(t),
+            normal,
+            width,
+            footprint: width / cos,
+        }
+    }
+
+    /// Intersect the cone with a plane.
+    pub fn intersect_plane(&self, plane: &Plane<S>) -> Option<RayConeHit<S>> {
+        ray_plane(&self.ray, plane).map(|t| self.hit(t, plane.normal))
+    }
+
+    /// Intersect the cone with the surface of a sphere.
+    pub fn intersect_sphere(&self, sphere: &Sphere<S>) -> Option<RayConeHit<S>> {
+        ray_sphere(&self.ray, sphere).map(|t| {
+            let normal = (self.ray.point_at(t) - sphere.center).normalize();
+            self.hit(t, normal)
+        })
+    }
+
+    /// Intersect the cone with a triangle, also returning the barycentric
+    /// coordinates of the hit point.
+    pub fn intersect_triangle(
+        &self,
+        triangle: &Triangle3<S>,
+    ) -> Option<(RayConeHit<S>, Vector3<S>)> {
+        ray_triangle(&self.ray, triangle).map(|(t, bary)| (self.hit(t, triangle.normal()), bary))
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Estimation of transforms from point correspondences.
+//!
+//! Given points `src[i]` and where they should be mapped to, `dst[i]`, the
+//! functions of this module find the transform of a given kind minimizing
+//! the sum of the squared distances between the transformed `src[i]` and
+//! `dst[i]`. This is how a scanned object is aligned onto its model, or how a
+//! camera is registered against known landmarks.
+//!
+//! Least squares fits are thrown off by outliers, such as wrong matches
+//! between features. With the `rand` feature, `ransac` wraps any of these
+//! fits to ignore them.
+//!
+//! ```rust
+//! use cgmath::prelude::*;
+//! use cgmath::registration::fit_similarity3;
+//! use cgmath::{Decomposed, Deg, Point3, Quaternion, Vector3};
+//! # #[macro_use] extern crate approx;
+//! # fn main() {
+//!
+//! let model = [
+//!     Point3::new(0.0, 0.0, 0.0),
+//!     Point3::new(1.0, 0.0, 0.0),
+//!     Point3::new(0.0, 2.0, 0.0),
+//!     Point3::new(0.0, 0.0, 3.0),
+//! ];
+//! let placement = Decomposed {
+//!     scale: 2.0,
+//!     rot: Quaternion::from_angle_z(Deg(40.0f64)),
+//!     disp: Vector3::new(1.0, -2.0, 0.5),
+//! };
+//! let scan: Vec<_> = model.iter().map(|&p| placement.transform_point(p)).collect();
+//!
+//! let fitted = fit_similarity3(&model, &scan).unwrap();
+//! assert_relative_eq!(fitted.scale, 2.0, epsilon = 1e-9);
+//! assert_relative_eq!(fitted.disp, placement.disp, epsilon = 1e-9);
+//! # }
+//! ```
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use structure::*;
+
+use angle::Rad;
+use conformal::Conformal2;
+use matrix::{Matrix2, Matrix3, Matrix4};
+use num::BaseFloat;
+use point::{Point2, Point3};
+use quaternion::Quaternion;
+use rotation::Rotation;
+use transform::{Decomposed, Transform};
+use vector::{Vector2, Vector3};
+
+/// The centroids of two sets of points of the same length, if they are not
+/// empty.
+fn centroids<P: EuclideanSpace>(src: &[P], dst: &[P]) -> Option<(P, P)>
+where
+    P::Scalar: BaseFloat,
+{
+    if src.len() != dst.len() || src.is_empty() {
+        return None;
+    }
+    Some((P::centroid(src), P::centroid(dst)))
+}
+
+/// The least squares affine transform of the plane mapping `src` to `dst`,
+/// as a homogeneous matrix.
+///
+/// Returns `None` if the slices have different lengths, or if the points of
+/// `src` are all on a line, which takes fewer than three of them.
+pub fn fit_affine2<S: BaseFloat>(src: &[Point2<S>], dst: &[Point2<S>]) -> Option<Matrix3<S>> {
+    let (src_center, dst_center) = centroids(src, dst)?;
+    let mut cross = Matrix2::zero();
+    let mut spread = Matrix2::zero();
+    for (&p, &q) in src.iter().zip(dst) {
+        let (p, q) = (p - src_center, q - dst_center);
+        cross += Matrix2::from_cols(q * p.x, q * p.y);
+        spread += Matrix2::from_cols(p * p.x, p * p.y);
+    }
+    let linear = cross * spread.invert()?;
+    let disp = dst_center.to_vec() - linear * src_center.to_vec();
+    let mut m = Matrix3::from(linear);
+    m.z = disp.extend(S::one());
+    Some(m)
+}
+
+/// The least squares affine transform of space mapping `src` to `dst`, as a
+/// homogeneous matrix.
+///
+/// Returns `None` if the slices have different lengths, or if the points of
+/// `src` are all on a plane, which takes fewer than four of them.
+pub fn fit_affine3<S: BaseFloat>(src: &[Point3<S>], dst: &[Point3<S>]) -> Option<Matrix4<S>> {
+    let (src_center, dst_center) = centroids(src, dst)?;
+    let mut cross = Matrix3::zero();
+    let mut spread = Matrix3::zero();
+    for (&p, &q) in src.iter().zip(dst) {
+        let (p, q) = (p - src_center, q - dst_center);
+        cross += Matrix3::from_cols(q * p.x, q * p.y, q * p.z);
+        spread += Matrix3::from_cols(p * p.x, p * p.y, p * p.z);
+    }
+    let linear = cross * spread.invert()?;
+    let disp = dst_center.to_vec() - linear * src_center.to_vec();
+    Some(Matrix4::from_mat3_translation(linear, disp))
+}
+
+/// The least squares similarity transform of the plane, made of a rotation,
+/// a uniform scale and a translation, mapping `src` to `dst`.
+///
+/// Returns `None` if the slices have different lengths, or if the points of
+/// `src` are all the same.
+pub fn fit_similarity2<S: BaseFloat>(
+    src: &[Point2<S>],
+    dst: &[Point2<S>],
+) -> Option<Conformal2<S>> {
+    let (src_center, dst_center) = centroids(src, dst)?;
+    let mut cross = Vector2::zero();
+    let mut spread = S::zero();
+    for (&p, &q) in src.iter().zip(dst) {
+        let (p, q) = (p - src_center, q - dst_center);
+        // The product of `q` with the complex conjugate of `p`.
+        cross += Vector2::new(p.x * q.x + p.y * q.y, p.x * q.y - p.y * q.x);
+        spread += p.magnitude2();
+    }
+    if spread == S::zero() {
+        return None;
+    }
+    let mut t = Conformal2 {
+        a: cross / spread,
+        b: Vector2::zero(),
+    };
+    t.b = dst_center - t.transform_point(src_center);
+    Some(t)
+}
+
+/// The least squares similarity transform of space, made of a rotation, a
+/// uniform scale and a translation, mapping `src` to `dst`.
+///
+/// This is the method of Umeyama, in "Least-Squares Estimation of
+/// Transformation Parameters Between Two Point Patterns", with the rotation
+/// found by `Matrix3::polar_decomposition`. The rotation is never a
+/// reflection, even if `dst` is a mirror image of `src`.
+///
+/// Returns `None` if the slices have different lengths, or if the points of
+/// `src` are all the same. The rotation is not unique when the points are
+/// all on a line, which takes fewer than three of them.
+pub fn fit_similarity3<S: BaseFloat>(
+    src: &[Point3<S>],
+    dst: &[Point3<S>],
+) -> Option<Decomposed<Vector3<S>, Quaternion<S>>> {
+    let (src_center, dst_center) = centroids(src, dst)?;
+    let mut cross = Matrix3::zero();
+    let mut spread = S::zero();
+    for (&p, &q) in src.iter().zip(dst) {
+        let (p, q) = (p - src_center, q - dst_center);
+        cross += Matrix3::from_cols(q * p.x, q * p.y, q * p.z);
+        spread += p.magnitude2();
+    }
+    if spread == S::zero() {
+        return None;
+    }
+
+    // The polar decomposition converges to the closest rotation from the
+    // identity, unless it starts on a saddle point, as it does when the
+    // best rotation is a half turn about one of the axes. Starting from
+    // half turns about every axis as well avoids them. It also converges
+    // slowly when the points are spread much more along some axes than
+    // others, so it is restarted from its result until it settles.
+    let half_turn = Rad::turn_div_2();
+    let starts = [
+        Matrix3::identity(),
+        Matrix3::from_angle_x(half_turn),
+        Matrix3::from_angle_y(half_turn),
+        Matrix3::from_angle_z(half_turn),
+    ];
+    let closest_rotation = |start: &Matrix3<S>| {
+        let mut r = *start;
+        for _ in 0..16 {
+            let (step, _) = (cross * r.transpose()).polar_decomposition();
+            r = step * r;
+            if implicit_ulps_eq!(step, Matrix3::identity()) {
+                break;
+            }
+        }
+        r
+    };
+    let alignment = |r: &Matrix3<S>| r.x.dot(cross.x) + r.y.dot(cross.y) + r.z.dot(cross.z);
+    let rotation = starts
+        .iter()
+        .map(closest_rotation)
+        .fold(None, |best: Option<Matrix3<S>>, r| match best {
+            Some(b) if alignment(&b) >= alignment(&r) => Some(b),
+            _ => Some(r),
+        })
+        .unwrap();
+
+    let scale = alignment(&rotation) / spread;
+    let rot = Quaternion::from(rotation).normalize();
+    Some(Decomposed {
+        scale,
+        rot,
+        disp: dst_center.to_vec() - rot.rotate_vector(src_center.to_vec()) * scale,
+    })
+}
+
+/// Fit a transform to point correspondences containing outliers, with the
+/// random sample consensus algorithm.
+///
+/// Each of the `iterations` fits a transform with `fit` to `sample_size`
+/// randomly chosen correspondences, and counts the inliers, the
+/// correspondences mapped within `threshold` of their destination. The
+/// transform with the most inliers is then fitted again to all of its
+/// inliers. This returns this transform and the indices of its inliers, or
+/// `None` if no sample could be fitted.
+///
+/// ```rust
+/// # extern crate cgmath;
+/// # extern crate rand;
+/// use cgmath::prelude::*;
+/// use cgmath::registration::{fit_similarity2, ransac};
+/// use cgmath::{Conformal2, Deg, Point2, Vector2};
+///
+/// # fn main() {
+/// let truth = Conformal2::new(1.5, Deg(30.0f64), Vector2::new(4.0, -1.0));
+/// let src: Vec<_> = (0..20).map(|i| Point2::new(i as f64, (i * i % 7) as f64)).collect();
+/// let mut dst: Vec<_> = src.iter().map(|&p| truth.transform_point(p)).collect();
+/// // Two wrong matches.
+/// dst[3] = Point2::new(100.0, 0.0);
+/// dst[11] = Point2::new(-50.0, 20.0);
+///
+/// let mut rng = rand::thread_rng();
+/// let (fitted, inliers) = ransac(&src, &dst, 2, 50, 1e-6, &mut rng, fit_similarity2).unwrap();
+/// assert_eq!(inliers.len(), 18);
+/// assert!((fitted.scale() - 1.5).abs() < 1e-9);
+/// # }
+/// ```
+#[cfg(feature = "rand")]
+pub fn ransac<P, T, F, R>(
+    src: &[P],
+    dst: &[P],
+    sample_size: usize,
+    iterations: usize,
+    threshold: P::Scalar,
+    rng: &mut R,
+    fit: F,
+) -> Option<(T, Vec<usize>)>
+where
+    P: EuclideanSpace + MetricSpace<Metric = <P as EuclideanSpace>::Scalar>,
+    P::Scalar: BaseFloat,
+    T: Transform<P>,
+    F: Fn(&[P], &[P]) -> Option<T>,
+    R: Rng + ?Sized,
+{
+    if src.len() != dst.len() || src.len() < sample_size {
+        return None;
+    }
+    let inliers = |t: &T| -> Vec<usize> {
+        (0..src.len())
+            .filter(|&i| t.transform_point(src[i]).distance(dst[i]) <= threshold)
+            .collect()
+    };
+
+    let mut best: Option<Vec<usize>> = None;
+    let mut indices: Vec<usize> = (0..src.len()).collect();
+    let mut sample_src = Vec::with_capacity(sample_size);
+    let mut sample_dst = Vec::with_capacity(sample_size);
+    for _ in 0..iterations {
+        // A partial Fisher-Yates shuffle picks distinct correspondences.
+        for i in 0..sample_size {
+            let j = rng.gen_range(i, indices.len());
+            indices.swap(i, j);
+        }
+        sample_src.clear();
+        sample_dst.clear();
+        for &i in &indices[..sample_size] {
+            sample_src.push(src[i]);
+            sample_dst.push(dst[i]);
+        }
+        if let Some(t) = fit(&sample_src, &sample_dst) {
+            let candidate = inliers(&t);
+            let better = match best {
+                Some(ref b) => candidate.len() > b.len(),
+                None => true,
+            };
+            if better {
+                best = Some(candidate);
+            }
+        }
+    }
+
+    let best = best?;
+    let inlier_src: Vec<P> = best.iter().map(|&i| src[i]).collect();
+    let inlier_dst: Vec<P> = best.iter().map(|&i| dst[i]).collect();
+    let t = fit(&inlier_src, &inlier_dst)?;
+    let refined = inliers(&t);
+    Some((t, refined))
+}
@@ -68,6 +68,16 @@ pub trait Rotation2<S: BaseFloat>:
     /// Create a rotation by a given angle. Thus is a redundant case of both
     /// from_axis_angle() and from_euler() for 2D space.
     fn from_angle<A: Into<Rad<S>>>(theta: A) -> Self;
+
+    /// Create a rotation by `amount` times the angle of this one.
+    ///
+    /// An amount between zero and one damps the rotation towards the
+    /// identity, and an amount larger than one exaggerates it.
+    #[inline]
+    fn scaled(&self, amount: S) -> Self {
+        let m: Matrix2<S> = (*self).into();
+        Rotation2::from_angle(Rad::atan2(m.x.y, m.x.x) * amount)
+    }
 }
 
 /// A three-dimensional rotation.
@@ -96,6 +106,20 @@ pub trait Rotation3<S: BaseFloat>:
     fn from_angle_z<A: Into<Rad<S>>>(theta: A) -> Self {
         Rotation3::from_axis_angle(Vector3::unit_z(), theta)
     }
+
+    /// Create a rotation around the same axis as this one, by `amount` times
+    /// its angle.
+    ///
+    /// An amount between zero and one damps the rotation towards the
+    /// identity, and an amount larger than one exaggerates it. Rotations
+    /// with several representations are scaled along the shortest arc, so
+    /// the angle of a quaternion is taken in `[0, π]`.
+    #[inline]
+    fn scaled(&self, amount: S) -> Self {
+        let q: Quaternion<S> = (*self).into();
+        let rotation = AxisAngle::from(q);
+        Rotation3::from_axis_angle(rotation.axis, rotation.angle * amount)
+    }
 }
 
 /// A two-dimensional rotation matrix.
@@ -220,6 +244,14 @@ impl<S: BaseFloat> One for Basis2<S> {
     }
 }
 
+/// The default basis is the identity rotation.
+impl<S: BaseFloat> Default for Basis2<S> {
+    #[inline]
+    fn default() -> Basis2<S> {
+        Basis2::one()
+    }
+}
+
 impl_operator!(<S: BaseFloat> Mul<Basis2<S> > for Basis2<S> {
     fn mul(lhs, rhs) -> Basis2<S> { Basis2 { mat: lhs.mat * rhs.mat  } }
 });
@@ -372,6 +404,14 @@ impl<S: BaseFloat> One for Basis3<S> {
     }
 }
 
+/// The default basis is the identity rotation.
+impl<S: BaseFloat> Default for Basis3<S> {
+    #[inline]
+    fn default() -> Basis3<S> {
+        Basis3::one()
+    }
+}
+
 impl_operator!(<S: BaseFloat> Mul<Basis3<S> > for Basis3<S> {
     fn mul(lhs, rhs) -> Basis3<S> { Basis3 { mat: lhs.mat * rhs.mat  } }
 });
@@ -421,6 +461,11 @@ impl<S: BaseFloat> Rotation3<S> for Basis3<S> {
         }
     }
 
+    #[inline]
+    fn scaled(&self, amount: S) -> Basis3<S> {
+        AxisAngle::from(*self).scaled(amount).into()
+    }
+
     fn from_angle_x<A: Into<Rad<S>>>(theta: A) -> Basis3<S> {
         Basis3 {
             mat: Matrix3::from_angle_x(theta),
@@ -458,3 +503,254 @@ impl<S: fmt::Debug> fmt::Debug for Basis3<S> {
         <[[S; 3]; 3] as fmt::Debug>::fmt(self.mat.as_ref(), f)
     }
 }
+
+/// A three-dimensional rotation by an angle around an axis.
+///
+/// This is the representation used by many physics engines and interchange
+/// formats. The axis is expected to be normalized, and is kept as given
+/// otherwise, so that the rotation round-trips exactly. Composition and most
+/// other operations go through quaternions.
+///
+/// ```rust
+/// use cgmath::{AxisAngle, Deg, Quaternion, Rotation, Rotation3, Vector3};
+/// # #[macro_use] extern crate approx;
+/// # fn main() {
+///
+/// let r = AxisAngle::from_axis_angle(Vector3::unit_z(), Deg(90.0f64));
+/// assert_relative_eq!(r.rotate_vector(Vector3::unit_x()), Vector3::unit_y());
+///
+/// let q = Quaternion::from(r);
+/// assert_relative_eq!(AxisAngle::from(q), r);
+/// # }
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AxisAngle<S> {
+    /// The axis of the rotation, which should be normalized.
+    pub axis: Vector3<S>,
+    /// The angle of the rotation, counter-clockwise when looking down the
+    /// axis towards the origin.
+    pub angle: Rad<S>,
+}
+
+impl<S: BaseFloat> AxisAngle<S> {
+    /// Construct a rotation from its axis, which should be normalized, and
+    /// its angle.
+    #[inline]
+    pub fn new<A: Into<Rad<S>>>(axis: Vector3<S>, angle: A) -> AxisAngle<S> {
+        AxisAngle {
+            axis,
+            angle: angle.into(),
+        }
+    }
+}
+
+impl<S: BaseFloat> From<Quaternion<S>> for AxisAngle<S> {
+    /// Convert a quaternion, which does not need to be normalized, to an
+    /// angle in `[0, π]` around an axis.
+    ///
+    /// The axis of the identity rotation is arbitrarily chosen to be `x`.
+    fn from(q: Quaternion<S>) -> AxisAngle<S> {
+        let q = if q.s < S::zero() { -q } else { q };
+        let sin = q.v.magnitude();
+        if implicit_ulps_eq!(sin, &S::zero()) {
+            return AxisAngle::one();
+        }
+        let angle = Rad::atan2(sin, q.s);
+        AxisAngle::new(q.v / sin, angle + angle)
+    }
+}
+
+impl<S: BaseFloat> From<Basis3<S>> for AxisAngle<S> {
+    #[inline]
+    fn from(b: Basis3<S>) -> AxisAngle<S> {
+        Quaternion::from(b).into()
+    }
+}
+
+impl<S: BaseFloat> From<Matrix3<S>> for AxisAngle<S> {
+    /// Convert a rotation matrix, which must be orthonormal.
+    #[inline]
+    fn from(m: Matrix3<S>) -> AxisAngle<S> {
+        Quaternion::from(m).into()
+    }
+}
+
+impl<S: BaseFloat> From<AxisAngle<S>> for Quaternion<S> {
+    #[inline]
+    fn from(r: AxisAngle<S>) -> Quaternion<S> {
+        Quaternion::from_axis_angle(r.axis, r.angle)
+    }
+}
+
+impl<S: BaseFloat> From<AxisAngle<S>> for Basis3<S> {
+    #[inline]
+    fn from(r: AxisAngle<S>) -> Basis3<S> {
+        Basis3::from_axis_angle(r.axis, r.angle)
+    }
+}
+
+impl<S: BaseFloat> From<AxisAngle<S>> for Matrix3<S> {
+    #[inline]
+    fn from(r: AxisAngle<S>) -> Matrix3<S> {
+        Matrix3::from_axis_angle(r.axis, r.angle)
+    }
+}
+
+impl<A: Angle> From<Euler<A>> for AxisAngle<A::Unitless>
+where
+    A: Into<Rad<<A as Angle>::Unitless>>,
+{
+    fn from(src: Euler<A>) -> AxisAngle<A::Unitless> {
+        Quaternion::from(src).into()
+    }
+}
+
+impl<S: BaseFloat> iter::Product<AxisAngle<S>> for AxisAngle<S> {
+    #[inline]
+    fn product<I: Iterator<Item = AxisAngle<S>>>(iter: I) -> AxisAngle<S> {
+        iter.fold(AxisAngle::one(), Mul::mul)
+    }
+}
+
+impl<'a, S: 'a + BaseFloat> iter::Product<&'a AxisAngle<S>> for AxisAngle<S> {
+    #[inline]
+    fn product<I: Iterator<Item = &'a AxisAngle<S>>>(iter: I) -> AxisAngle<S> {
+        iter.fold(AxisAngle::one(), Mul::mul)
+    }
+}
+
+impl<S: BaseFloat> Rotation<Point3<S>> for AxisAngle<S> {
+    #[inline]
+    fn look_at(dir: Vector3<S>, up: Vector3<S>) -> AxisAngle<S> {
+        Matrix3::look_at(dir, up).into()
+    }
+
+    #[inline]
+    fn between_vectors(a: Vector3<S>, b: Vector3<S>) -> AxisAngle<S> {
+        let q: Quaternion<S> = Rotation::between_vectors(a, b);
+        q.into()
+    }
+
+    #[inline]
+    fn rotate_vector(&self, vec: Vector3<S>) -> Vector3<S> {
+        // Rodrigues' rotation formula.
+        let (sin, cos) = self.angle.sin_cos();
+        let k = self.axis;
+        vec * cos + k.cross(vec) * sin + k * (k.dot(vec) * (S::one() - cos))
+    }
+
+    #[inline]
+    fn invert(&self) -> AxisAngle<S> {
+        AxisAngle::new(self.axis, -self.angle)
+    }
+}
+
+impl<S: BaseFloat> One for AxisAngle<S> {
+    #[inline]
+    fn one() -> AxisAngle<S> {
+        AxisAngle::new(Vector3::unit_x(), Rad::zero())
+    }
+}
+
+impl<S: BaseFloat> Default for AxisAngle<S> {
+    #[inline]
+    fn default() -> AxisAngle<S> {
+        AxisAngle::one()
+    }
+}
+
+impl_operator!(<S: BaseFloat> Mul<AxisAngle<S> > for AxisAngle<S> {
+    fn mul(lhs, rhs) -> AxisAngle<S> {{
+        let lhs = Quaternion::from_axis_angle(lhs.axis, lhs.angle);
+        let rhs = Quaternion::from_axis_angle(rhs.axis, rhs.angle);
+        (lhs * rhs).into()
+    }}
+});
+
+impl<S: BaseFloat> approx::AbsDiffEq for AxisAngle<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Vector3::abs_diff_eq(&self.axis, &other.axis, epsilon)
+            && Rad::abs_diff_eq(&self.angle, &other.angle, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for AxisAngle<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Vector3::relative_eq(&self.axis, &other.axis, epsilon, max_relative)
+            && Rad::relative_eq(&self.angle, &other.angle, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for AxisAngle<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Vector3::ulps_eq(&self.axis, &other.axis, epsilon, max_ulps)
+            && Rad::ulps_eq(&self.angle, &other.angle, epsilon, max_ulps)
+    }
+}
+
+impl<S: BaseFloat> Rotation3<S> for AxisAngle<S> {
+    #[inline]
+    fn from_axis_angle<A: Into<Rad<S>>>(axis: Vector3<S>, angle: A) -> AxisAngle<S> {
+        AxisAngle::new(axis, angle)
+    }
+
+    /// Scale the angle as given, which may be outside of `[0, π]`.
+    #[inline]
+    fn scaled(&self, amount: S) -> AxisAngle<S> {
+        AxisAngle::new(self.axis, self.angle * amount)
+    }
+}
+
+/// The geodesic distance between two three-dimensional rotations.
+///
+/// This is the angle of the smallest rotation that takes `a` onto `b`, in the
+/// range `[0, π]`. Any representation that converts into a quaternion can be
+/// compared, including `Quaternion`, `Basis3` and orthonormal `Matrix3`s.
+///
+/// ```rust
+/// use cgmath::{rotation_distance, Basis3, Deg, Matrix3, Quaternion, Rotation3};
+///
+/// let a = Quaternion::from_angle_x(Deg(10.0f64));
+/// let b = Quaternion::from_angle_x(Deg(40.0f64));
+/// let angle = Deg::from(rotation_distance(a, b));
+/// assert!((angle.0 - 30.0).abs() < 1e-10);
+///
+/// let m0 = Matrix3::from_angle_y(Deg(90.0f64));
+/// let m1 = Matrix3::from_angle_y(Deg(-90.0f64));
+/// let angle = Deg::from(rotation_distance(m0, m1));
+/// assert!((angle.0 - 180.0).abs() < 1e-10);
+/// ```
+pub fn rotation_distance<S, R>(a: R, b: R) -> Rad<S>
+where
+    S: BaseFloat,
+    R: Into<Quaternion<S>>,
+{
+    let a: Quaternion<S> = a.into();
+    let b: Quaternion<S> = b.into();
+    a.normalize().angle_between(&b.normalize())
+}
+
+impl_precision_conversions!(Basis2 { mat }, Basis2<f32>, Basis2<f64>);
+impl_precision_conversions!(Basis3 { mat }, Basis3<f32>, Basis3<f64>);
+impl_precision_conversions!(AxisAngle { axis, angle }, AxisAngle<f32>, AxisAngle<f64>);
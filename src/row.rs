@@ -0,0 +1,121 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Matrices for the row vector convention.
+//!
+//! The matrices of this crate multiply column vectors on their right, as in
+//! OpenGL and most mathematics: `m * v` transforms `v`, the translation is in
+//! the last column, and `a * b` applies `b` first. Codebases following
+//! Direct3D or DirectXMath multiply row vectors on the left of matrices
+//! instead: `v * m` transforms `v`, the translation is in the last row, and
+//! `a * b` applies `a` first. A matrix of one convention is the transpose of
+//! the same transformation in the other.
+//!
+//! The functions of this module build and apply matrices of the row vector
+//! convention, so that such matrices can be used as they are, and `to_column`
+//! and `from_column` convert them at the boundary with the rest of the crate.
+//! Only the layout changes: the views are still right-handed and the
+//! projections still map depths to `-1` to `1`, as `DepthRange` describes.
+//! Since matrices are stored by columns, the memory of a matrix of the row
+//! vector convention holds its columns one after the other, and the arrays
+//! of Direct3D, which hold the rows one after the other, have the same
+//! memory as the matrices of the column vector convention.
+//!
+//! ```rust
+//! use cgmath::row;
+//! use cgmath::{Matrix4, Point3, Vector3};
+//!
+//! let view = row::look_at(Point3::new(0.0, 0.0, 5.0), Point3::new(0.0, 0.0, 0.0), Vector3::unit_y());
+//! let model = row::from_column(Matrix4::from_translation(Vector3::new(1.0, 0.0, 0.0)));
+//! // The model matrix applies first.
+//! let p = row::transform_point(Point3::new(0.0, 0.0, 0.0), model * view);
+//! assert_eq!(p, Point3::new(1.0, 0.0, -5.0));
+//! ```
+
+use angle::Rad;
+use matrix::Matrix4;
+use num::BaseFloat;
+use point::Point3;
+use projection;
+use structure::*;
+use vector::{Vector3, Vector4};
+
+/// Convert a matrix of the row vector convention to the same transformation
+/// for column vectors.
+#[inline]
+pub fn to_column<S: BaseFloat>(m: Matrix4<S>) -> Matrix4<S> {
+    m.transpose()
+}
+
+/// Convert a matrix of the column vector convention, like all the other
+/// matrices of this crate, to the same transformation for row vectors.
+#[inline]
+pub fn from_column<S: BaseFloat>(m: Matrix4<S>) -> Matrix4<S> {
+    m.transpose()
+}
+
+/// Multiply the row vector `v` by the matrix `m`, computing `v * m`.
+#[inline]
+pub fn mul<S: BaseFloat>(v: Vector4<S>, m: Matrix4<S>) -> Vector4<S> {
+    m.transpose_mul_vector(v)
+}
+
+/// Transform a point by a matrix of the row vector convention, dividing by
+/// `w` for projections.
+#[inline]
+pub fn transform_point<S: BaseFloat>(p: Point3<S>, m: Matrix4<S>) -> Point3<S> {
+    Point3::from_homogeneous(mul(p.to_homogeneous(), m))
+}
+
+/// Transform a vector by a matrix of the row vector convention, ignoring its
+/// translation.
+#[inline]
+pub fn transform_vector<S: BaseFloat>(v: Vector3<S>, m: Matrix4<S>) -> Vector3<S> {
+    mul(v.extend(S::zero()), m).truncate()
+}
+
+/// Create the view matrix of a camera at `eye` looking along `dir`, for row
+/// vectors. This is the transpose of `Matrix4::look_at_dir`.
+pub fn look_to<S: BaseFloat>(eye: Point3<S>, dir: Vector3<S>, up: Vector3<S>) -> Matrix4<S> {
+    from_column(Matrix4::look_at_dir(eye, dir, up))
+}
+
+/// Create the view matrix of a camera at `eye` looking at `center`, for row
+/// vectors. This is the transpose of `Matrix4::look_at`.
+pub fn look_at<S: BaseFloat>(eye: Point3<S>, center: Point3<S>, up: Vector3<S>) -> Matrix4<S> {
+    look_to(eye, center - eye, up)
+}
+
+/// Create a perspective projection matrix for row vectors. This is the
+/// transpose of `cgmath::perspective`.
+pub fn perspective<S: BaseFloat, A: Into<Rad<S>>>(
+    fovy: A,
+    aspect: S,
+    near: S,
+    far: S,
+) -> Matrix4<S> {
+    from_column(projection::perspective(fovy, aspect, near, far))
+}
+
+/// Create a perspective projection matrix from a view frustum, for row
+/// vectors. This is the transpose of `cgmath::frustum`.
+pub fn frustum<S: BaseFloat>(left: S, right: S, bottom: S, top: S, near: S, far: S) -> Matrix4<S> {
+    from_column(projection::frustum(left, right, bottom, top, near, far))
+}
+
+/// Create an orthographic projection matrix for row vectors. This is the
+/// transpose of `cgmath::ortho`.
+pub fn ortho<S: BaseFloat>(left: S, right: S, bottom: S, top: S, near: S, far: S) -> Matrix4<S> {
+    from_column(projection::ortho(left, right, bottom, top, near, far))
+}
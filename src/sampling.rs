@@ -0,0 +1,699 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sets of random points spread over areas and surfaces, such as the
+//! positions of scattered foliage or blue noise sample patterns.
+//!
+//! Like the `noise` module, the sampling is deterministic: the same seed
+//! always gives the same points.
+//!
+//! The importance sampling functions for reflectance lobes instead map
+//! numbers in `[0, 1)` chosen by the caller, such as those of a
+//! low-discrepancy sequence, to directions, and come with the probability
+//! densities of these directions over the sphere, in inverse steradians.
+//!
+//! ```rust
+//! use cgmath::sampling::poisson_disk_rect;
+//! use cgmath::{Aabb2, MetricSpace, Point2};
+//!
+//! let field = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(20.0, 10.0));
+//! let trees = poisson_disk_rect(&field, 1.5f64, 7);
+//! assert!(trees.len() > 30);
+//! for (i, a) in trees.iter().enumerate() {
+//!     assert!(trees[i + 1..].iter().all(|b| a.distance(*b) >= 1.5));
+//! }
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::Aabb2;
+use angle::Rad;
+use matrix::Matrix4;
+use noise::{random2, random3};
+use num::BaseFloat;
+use point::{Point2, Point3};
+use polyline::any_perpendicular;
+use pose::Pose3;
+use projection::PinholeCamera;
+use ray::Ray3;
+use transform::Transform;
+use triangle::Triangle3;
+use vector::{Vector2, Vector3, Vector4};
+
+/// The number of candidates tried around each point before giving up on
+/// it, as suggested by Bridson.
+const ATTEMPTS: usize = 30;
+
+/// A deterministic stream of pseudo-random numbers in `[0, 1)`.
+struct Stream {
+    seed: u32,
+    index: i32,
+}
+
+impl Stream {
+    fn new(seed: u32) -> Stream {
+        Stream { seed, index: 0 }
+    }
+
+    fn next<S: BaseFloat>(&mut self) -> S {
+        self.index = self.index.wrapping_add(1);
+        random2(Point2::new(self.index, 0), self.seed)
+    }
+}
+
+/// Generate points inside `bounds` for which `inside` holds, no closer than
+/// `radius` to each other, with "Fast Poisson Disk Sampling in Arbitrary
+/// Dimensions" by Robert Bridson.
+fn bridson2<S: BaseFloat, F: Fn(Point2<S>) -> bool>(
+    bounds: &Aabb2<S>,
+    radius: S,
+    seed: u32,
+    inside: F,
+) -> Vec<Point2<S>> {
+    let mut points = Vec::new();
+    let size = bounds.dim();
+    if radius.is_nan() || radius <= S::zero() || size.x < S::zero() || size.y < S::zero() {
+        return points;
+    }
+    let mut random = Stream::new(seed);
+
+    // Each cell of the grid is small enough to hold a single point.
+    let cell = radius / cast(2f64.sqrt()).unwrap();
+    let columns = (size.x / cell).floor().to_usize().unwrap_or(0) + 1;
+    let rows = (size.y / cell).floor().to_usize().unwrap_or(0) + 1;
+    let mut grid: Vec<Option<usize>> = vec![None; columns * rows];
+    let index = |p: Point2<S>| {
+        let d = (p - bounds.min) / cell;
+        let i = d.x.to_usize().unwrap_or(0).min(columns - 1);
+        let j = d.y.to_usize().unwrap_or(0).min(rows - 1);
+        (i, j)
+    };
+
+    let first = (0..ATTEMPTS)
+        .map(|_| bounds.min + Vector2::new(random.next(), random.next()).mul_element_wise(size))
+        .find(|&p| inside(p));
+    let mut active = match first {
+        Some(p) => {
+            let (i, j) = index(p);
+            grid[j * columns + i] = Some(0);
+            points.push(p);
+            vec![0]
+        }
+        None => return points,
+    };
+
+    let r2 = radius * radius;
+    let three: S = cast(3).unwrap();
+    while !active.is_empty() {
+        let k = (random.next::<S>() * cast(active.len()).unwrap())
+            .to_usize()
+            .unwrap_or(0)
+            .min(active.len() - 1);
+        let center = points[active[k]];
+        let mut found = false;
+        for _ in 0..ATTEMPTS {
+            // Uniformly over the annulus between one and two radii.
+            let distance = (S::one() + three * random.next()).sqrt() * radius;
+            let angle = Rad::full_turn() * random.next();
+            let (s, c) = Rad::sin_cos(angle);
+            let p = center + Vector2::new(c, s) * distance;
+            if !bounds.contains_point(p) || !inside(p) {
+                continue;
+            }
+            let (i, j) = index(p);
+            let far = (i.saturating_sub(2)..(i + 3).min(columns)).all(|x| {
+                (j.saturating_sub(2)..(j + 3).min(rows)).all(|y| match grid[y * columns + x] {
+                    Some(other) => (points[other] - p).magnitude2() >= r2,
+                    None => true,
+                })
+            });
+            if far {
+                grid[j * columns + i] = Some(points.len());
+                active.push(points.len());
+                points.push(p);
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            active.swap_remove(k);
+        }
+    }
+    points
+}
+
+/// Generate points inside a rectangle, no closer than `radius` to each
+/// other, and spread so that no more can fit.
+pub fn poisson_disk_rect<S: BaseFloat>(bounds: &Aabb2<S>, radius: S, seed: u32) -> Vec<Point2<S>> {
+    bridson2(bounds, radius, seed, |_| true)
+}
+
+/// Generate points inside a circle, no closer than `radius` to each other,
+/// and spread so that no more can fit.
+pub fn poisson_disk_circle<S: BaseFloat>(
+    center: Point2<S>,
+    circle_radius: S,
+    radius: S,
+    seed: u32,
+) -> Vec<Point2<S>> {
+    let reach = Vector2::from_value(circle_radius);
+    let bounds = Aabb2::new(center - reach, center + reach);
+    let r2 = circle_radius * circle_radius;
+    bridson2(&bounds, radius, seed, |p| (p - center).magnitude2() <= r2)
+}
+
+/// A point on the surface of a set of triangles.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SurfacePoint<S> {
+    /// The position of the point.
+    pub point: Point3<S>,
+    /// The unit normal of the triangle the point is on.
+    pub normal: Vector3<S>,
+    /// The index of the triangle the point is on.
+    pub triangle: usize,
+}
+
+/// Uniformly distributed points on the surface of a set of triangles, such
+/// as the emitters of particles on a mesh, picked with a table of the
+/// cumulative areas of the triangles.
+///
+/// ```rust
+/// use cgmath::sampling::SurfaceSampler;
+/// use cgmath::{Point3, Triangle3};
+///
+/// let small = Triangle3::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(1.0, 0.0, 0.0),
+///     Point3::new(0.0, 1.0, 0.0),
+/// );
+/// let large = Triangle3::new(
+///     Point3::new(0.0, 0.0, 1.0),
+///     Point3::new(0.0, 3.0, 1.0),
+///     Point3::new(3.0, 0.0, 1.0f64),
+/// );
+/// let triangles = [small, large];
+/// let sampler = SurfaceSampler::new(&triangles);
+/// assert_eq!(sampler.area(), 5.0);
+///
+/// // Nine tenths of the points land on the large triangle.
+/// let points = sampler.random_points(1000, 3);
+/// let on_large = points.iter().filter(|p| p.triangle == 1).count();
+/// assert!(on_large > 850 && on_large < 950);
+/// assert!(points.iter().all(|p| p.normal.z.abs() == 1.0));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct SurfaceSampler<'a, S: 'a> {
+    triangles: &'a [Triangle3<S>],
+    cumulative: Vec<S>,
+}
+
+impl<'a, S: BaseFloat> SurfaceSampler<'a, S> {
+    /// Build the table of the cumulative areas of the triangles.
+    pub fn new(triangles: &'a [Triangle3<S>]) -> SurfaceSampler<'a, S> {
+        let mut total = S::zero();
+        let cumulative = triangles
+            .iter()
+            .map(|t| {
+                total += t.area();
+                total
+            })
+            .collect();
+        SurfaceSampler {
+            triangles,
+            cumulative,
+        }
+    }
+
+    /// The total area of the triangles.
+    #[inline]
+    pub fn area(&self) -> S {
+        self.cumulative.last().cloned().unwrap_or_else(S::zero)
+    }
+
+    /// The point picked by three numbers in `[0, 1)`, such as random ones or
+    /// the components of a low-discrepancy sequence. The first one picks the
+    /// triangle, and the other two the point in it.
+    ///
+    /// Returns `None` if the triangles have no area.
+    pub fn sample(&self, u: S, v: S, w: S) -> Option<SurfacePoint<S>> {
+        let area = self.area();
+        if area.is_nan() || area <= S::zero() {
+            return None;
+        }
+        let target = u * area;
+        // The first triangle whose cumulative area is above the target.
+        let i = self
+            .cumulative
+            .binary_search_by(|&a| {
+                if a <= target {
+                    Ordering::Less
+                } else {
+                    Ordering::Greater
+                }
+            })
+            .unwrap_err()
+            .min(self.triangles.len() - 1);
+        let s = v.sqrt();
+        let bary = Vector3::new(S::one() - s, s * (S::one() - w), s * w);
+        let triangle = &self.triangles[i];
+        Some(SurfacePoint {
+            point: triangle.point_at(bary),
+            normal: triangle.normal(),
+            triangle: i,
+        })
+    }
+
+    /// Generate `count` deterministic pseudo-random points, which are the
+    /// same for the same seed.
+    pub fn random_points(&self, count: usize, seed: u32) -> Vec<SurfacePoint<S>> {
+        let mut random = Stream::new(seed);
+        (0..count)
+            .filter_map(|_| self.sample(random.next(), random.next(), random.next()))
+            .collect()
+    }
+}
+
+/// Generate points on the surface of a set of triangles, no closer than
+/// `radius` to each other in space, and spread so that few more could fit.
+///
+/// The triangles do not need to be connected, so rather than growing the
+/// set around the points like `poisson_disk_rect`, this throws darts at the
+/// triangles, weighted by their areas, until they keep missing.
+pub fn poisson_disk_triangles<S: BaseFloat>(
+    triangles: &[Triangle3<S>],
+    radius: S,
+    seed: u32,
+) -> Vec<Point3<S>> {
+    let mut points: Vec<Point3<S>> = Vec::new();
+    let sampler = SurfaceSampler::new(triangles);
+    let area = sampler.area();
+    if area.is_nan() || area <= S::zero() || radius <= S::zero() {
+        return points;
+    }
+    let mut random = Stream::new(seed);
+
+    // Stop once every point has about as many misses as Bridson's attempts,
+    // for the densest packing of disks of the area.
+    let packing: S = cast(3f64.sqrt() / 2.0).unwrap();
+    let most = (area / (packing * radius * radius)).to_usize().unwrap_or(0) + 1;
+    let patience = ATTEMPTS * most;
+
+    let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+    let cell = |p: Point3<S>| {
+        let c = p / radius;
+        let floor = |x: S| x.floor().to_i64().unwrap_or(0);
+        (floor(c.x), floor(c.y), floor(c.z))
+    };
+    let r2 = radius * radius;
+    let mut misses = 0;
+    while misses < patience {
+        let p = match sampler.sample(random.next(), random.next(), random.next()) {
+            Some(sample) => sample.point,
+            None => break,
+        };
+        let (x, y, z) = cell(p);
+        let close = |&other: &usize| (points[other] - p).magnitude2() < r2;
+        let crowded = (x - 1..=x + 1).any(|i| {
+            (y - 1..=y + 1).any(|j| {
+                (z - 1..=z + 1).any(|k| {
+                    grid.get(&(i, j, k))
+                        .map_or(false, |near| near.iter().any(close))
+                })
+            })
+        });
+        if !crowded {
+            grid.entry((x, y, z)).or_default().push(points.len());
+            points.push(p);
+            misses = 0;
+        } else {
+            misses += 1;
+        }
+    }
+    points
+}
+
+/// The direction with coordinates `x`, `y` and `z` in an arbitrary frame
+/// around the unit vector `normal`, which is the `z` axis.
+fn around<S: BaseFloat>(normal: Vector3<S>, x: S, y: S, z: S) -> Vector3<S> {
+    let tangent = any_perpendicular(normal);
+    let bitangent = normal.cross(tangent);
+    tangent * x + bitangent * y + normal * z
+}
+
+/// The point of the unit disk at radius `r` and an angle of `v` turns.
+fn polar<S: BaseFloat>(r: S, v: S) -> (S, S) {
+    let (sin, cos) = (Rad::full_turn() * v).sin_cos();
+    (r * cos, r * sin)
+}
+
+/// The point of the unit disk picked by two numbers in `[0, 1)`, with a
+/// uniform density, as when sampling the aperture of a lens.
+///
+/// This is the concentric mapping of Shirley and Chiu, which maps the
+/// square to the disk with little distortion, so that stratified or
+/// low-discrepancy numbers stay well spread over the disk.
+pub fn concentric_disk<S: BaseFloat>(u: S, v: S) -> Point2<S> {
+    let two: S = cast(2).unwrap();
+    let (a, b) = (u * two - S::one(), v * two - S::one());
+    if a == S::zero() && b == S::zero() {
+        return Point2::origin();
+    }
+    let eighth = Rad::<S>::turn_div_4().0 / two;
+    let (r, angle) = if a.abs() > b.abs() {
+        (a, eighth * (b / a))
+    } else {
+        (b, eighth * (two - a / b))
+    };
+    let (sin, cos) = Rad(angle).sin_cos();
+    Point2::new(r * cos, r * sin)
+}
+
+/// The direction picked by two numbers in `[0, 1)` on the hemisphere
+/// around the unit vector `normal`, with a density proportional to the
+/// cosine of its angle with the normal, like the light reflected by a
+/// diffuse surface.
+pub fn cosine_hemisphere<S: BaseFloat>(normal: Vector3<S>, u: S, v: S) -> Vector3<S> {
+    let (x, y) = polar(u.sqrt(), v);
+    around(normal, x, y, (S::one() - u).max(S::zero()).sqrt())
+}
+
+/// The density of the directions picked by `cosine_hemisphere`, which is
+/// zero below the hemisphere.
+pub fn cosine_hemisphere_pdf<S: BaseFloat>(normal: Vector3<S>, direction: Vector3<S>) -> S {
+    normal.dot(direction).max(S::zero()) / Rad::<S>::turn_div_2().0
+}
+
+/// The GGX, or Trowbridge-Reitz, distribution of the microfacet normals
+/// at `half` of a surface of roughness `alpha`, with the unit vector
+/// `normal`. The roughness `alpha` is commonly the square of the perceptual
+/// roughness of materials, and must be positive.
+pub fn ggx_distribution<S: BaseFloat>(normal: Vector3<S>, alpha: S, half: Vector3<S>) -> S {
+    let cos = normal.dot(half);
+    if cos <= S::zero() {
+        return S::zero();
+    }
+    let alpha2 = alpha * alpha;
+    let d = cos * cos * (alpha2 - S::one()) + S::one();
+    alpha2 / (Rad::<S>::turn_div_2().0 * d * d)
+}
+
+/// The microfacet normal picked by two numbers in `[0, 1)` from the GGX
+/// distribution of a surface of roughness `alpha`, with a density
+/// proportional to the distribution times the cosine of its angle with the
+/// unit vector `normal`.
+pub fn ggx_half_vector<S: BaseFloat>(normal: Vector3<S>, alpha: S, u: S, v: S) -> Vector3<S> {
+    let alpha2 = alpha * alpha;
+    let cos2 = (S::one() - u) / (S::one() + (alpha2 - S::one()) * u);
+    let sin = (S::one() - cos2).max(S::zero()).sqrt();
+    let (x, y) = polar(sin, v);
+    around(normal, x, y, cos2.sqrt())
+}
+
+/// The density of the microfacet normals picked by `ggx_half_vector`.
+pub fn ggx_half_vector_pdf<S: BaseFloat>(normal: Vector3<S>, alpha: S, half: Vector3<S>) -> S {
+    ggx_distribution(normal, alpha, half) * normal.dot(half).max(S::zero())
+}
+
+/// The direction of the light reflected towards the unit vector `view` by
+/// a microfacet normal picked with `ggx_half_vector`. The result may be
+/// below the surface, where it should carry no light. When the microfacet
+/// faces away from the view, the reflection does not exist and its density
+/// given by `ggx_reflection_pdf` is zero, so that the sample should be
+/// discarded.
+pub fn ggx_reflection<S: BaseFloat>(
+    normal: Vector3<S>,
+    alpha: S,
+    view: Vector3<S>,
+    u: S,
+    v: S,
+) -> Vector3<S> {
+    let half = ggx_half_vector(normal, alpha, u, v);
+    let cos = view.dot(half);
+    half * (cos + cos) - view
+}
+
+/// The density of the directions of light picked by `ggx_reflection`,
+/// which is the density of their half vector divided by the Jacobian of the
+/// reflection.
+pub fn ggx_reflection_pdf<S: BaseFloat>(
+    normal: Vector3<S>,
+    alpha: S,
+    view: Vector3<S>,
+    light: Vector3<S>,
+) -> S {
+    let sum = view + light;
+    let length2 = sum.magnitude2();
+    if length2 == S::zero() {
+        return S::zero();
+    }
+    let half = sum / length2.sqrt();
+    let four: S = cast(4).unwrap();
+    let cos = view.dot(half).abs();
+    if cos == S::zero() {
+        return S::zero();
+    }
+    ggx_half_vector_pdf(normal, alpha, half) / (four * cos)
+}
+
+/// The radical inverse of `index` in `base`, which mirrors its digits
+/// around the radix point. Over successive indices, this is the van der
+/// Corput sequence, which fills `[0, 1)` evenly at every length.
+pub fn radical_inverse<S: BaseFloat>(mut index: u32, base: u32) -> S {
+    let b: S = cast(base).unwrap();
+    let mut scale = S::one() / b;
+    let mut result = S::zero();
+    while index > 0 {
+        let digit: S = cast(index % base).unwrap();
+        result += digit * scale;
+        scale /= b;
+        index /= base;
+    }
+    result
+}
+
+/// The point of index `index` of the Halton sequence in bases 2 and 3,
+/// whose points spread over the unit square more evenly than random ones.
+/// The point of index zero is the origin, so sequences usually start at 1.
+pub fn halton2<S: BaseFloat>(index: u32) -> Point2<S> {
+    Point2::new(radical_inverse(index, 2), radical_inverse(index, 3))
+}
+
+/// How `PrimaryRays` places the samples of a pixel, and of the lens of the
+/// camera.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Jitter {
+    /// Every sample goes through the center of the pixel, and through the
+    /// center of the lens, which gives no depth of field.
+    Center,
+    /// The samples follow the Halton sequence from index 1, with the same
+    /// pattern in every pixel.
+    Halton,
+    /// The samples follow the Halton sequence, shifted by a pseudo-random
+    /// offset for each pixel, so that neighbouring pixels do not alias in
+    /// the same way.
+    ScrambledHalton(u32),
+    /// The samples are pseudo-random, from the given seed.
+    Random(u32),
+}
+
+/// The camera which `PrimaryRays` generates rays for.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum RayCamera<S: BaseFloat> {
+    /// A camera with a thin lens of radius `aperture`, zero for a pinhole,
+    /// focused at the depth `focus`.
+    Lens {
+        camera: PinholeCamera<S>,
+        pose: Pose3<S>,
+        aperture: S,
+        focus: S,
+    },
+    Projection(Matrix4<S>, Vector2<S>),
+}
+
+/// A generator of the rays through the pixels of an image, which start the
+/// paths of a path tracer or the rays of a baker.
+///
+/// Pixel `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)` of the
+/// image, with rows going down, and each of its samples goes through a
+/// point of that square chosen by the `Jitter`.
+///
+/// ```rust
+/// use cgmath::sampling::{Jitter, PrimaryRays};
+/// use cgmath::{Deg, One, PinholeCamera, Point3, Pose3, Quaternion, Vector3};
+///
+/// let camera = PinholeCamera::from_fov(Deg(60.0), 640.0, 480.0);
+/// let pose = Pose3 {
+///     scale: 1.0,
+///     rot: Quaternion::one(),
+///     disp: Vector3::new(0.0, 1.0, 5.0f64),
+/// };
+/// let rays = PrimaryRays::from_pinhole(camera, pose, Jitter::Halton);
+/// let ray = rays.ray(320, 240, 0);
+/// assert_eq!(ray.origin, Point3::new(0.0, 1.0, 5.0));
+/// assert!(ray.direction.z < -0.99);
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PrimaryRays<S: BaseFloat> {
+    camera: RayCamera<S>,
+    jitter: Jitter,
+}
+
+impl<S: BaseFloat> PrimaryRays<S> {
+    /// The rays of a pinhole camera placed in the world by `pose`, which
+    /// maps view space to world space. The rays start at the center of the
+    /// camera.
+    pub fn from_pinhole(
+        camera: PinholeCamera<S>,
+        pose: Pose3<S>,
+        jitter: Jitter,
+    ) -> PrimaryRays<S> {
+        PrimaryRays::from_thin_lens(camera, pose, S::zero(), S::one(), jitter)
+    }
+
+    /// The rays of a camera with a thin lens of radius `aperture_radius`,
+    /// focused on the plane at the depth `focus_distance` in front of it,
+    /// which blurs what is nearer or farther than that plane. The rays start
+    /// on the disk of the lens, and the camera model only describes where
+    /// the plane in focus projects. A radius of zero is a pinhole camera.
+    pub fn from_thin_lens(
+        camera: PinholeCamera<S>,
+        pose: Pose3<S>,
+        aperture_radius: S,
+        focus_distance: S,
+        jitter: Jitter,
+    ) -> PrimaryRays<S> {
+        PrimaryRays {
+            camera: RayCamera::Lens {
+                camera,
+                pose,
+                aperture: aperture_radius,
+                focus: focus_distance,
+            },
+            jitter,
+        }
+    }
+
+    /// The rays through the pixels of an image of `width` by `height`
+    /// pixels rendered with an OpenGL-style view-projection matrix, which
+    /// may be orthographic. The rays start on the near plane, like
+    /// rasterization clips what is in front of it.
+    ///
+    /// Returns `None` if the matrix is not invertible.
+    pub fn from_view_projection(
+        view_projection: Matrix4<S>,
+        width: u32,
+        height: u32,
+        jitter: Jitter,
+    ) -> Option<PrimaryRays<S>> {
+        let size = Vector2::new(cast(width).unwrap(), cast(height).unwrap());
+        view_projection
+            .inverse_transform()
+            .map(|inverse| PrimaryRays {
+                camera: RayCamera::Projection(inverse, size),
+                jitter,
+            })
+    }
+
+    /// The position of the sample of index `sample` of pixel `(x, y)`
+    /// within the pixel, in `[0, 1)` along both axes.
+    #[inline]
+    pub fn jitter_offset(&self, x: u32, y: u32, sample: u32) -> Vector2<S> {
+        self.sample_square(x, y, sample, 0)
+    }
+
+    /// The numbers in `[0, 1)` the jitter picks for the sample of index
+    /// `sample` of pixel `(x, y)`, for the pixel in `dimension` zero and
+    /// for the lens in `dimension` one.
+    fn sample_square(&self, x: u32, y: u32, sample: u32, dimension: u32) -> Vector2<S> {
+        let half: S = cast(0.5).unwrap();
+        let (x, y) = (x as i32, y as i32);
+        let halton = |index: u32| {
+            let (a, b) = [(2, 3), (5, 7)][dimension as usize];
+            Vector2::new(radical_inverse(index, a), radical_inverse(index, b))
+        };
+        let axis = (dimension * 2) as i32;
+        match self.jitter {
+            Jitter::Center => Vector2::new(half, half),
+            Jitter::Halton => halton(sample.wrapping_add(1)),
+            Jitter::ScrambledHalton(seed) => {
+                let p: Vector2<S> = halton(sample.wrapping_add(1));
+                let shift = |value: S, axis| {
+                    let shifted = value + random3(Point3::new(x, y, axis), seed);
+                    shifted - shifted.floor()
+                };
+                Vector2::new(shift(p.x, axis), shift(p.y, axis + 1))
+            }
+            Jitter::Random(seed) => {
+                let z = (sample as i32).wrapping_mul(4).wrapping_add(axis);
+                Vector2::new(
+                    random3(Point3::new(x, y, z), seed),
+                    random3(Point3::new(x, y, z.wrapping_add(1)), seed),
+                )
+            }
+        }
+    }
+
+    /// The ray of the sample of index `sample` of pixel `(x, y)`, with a
+    /// unit direction in world space.
+    pub fn ray(&self, x: u32, y: u32, sample: u32) -> Ray3<S> {
+        let offset = self.jitter_offset(x, y, sample);
+        let pixel = Point2::new(
+            cast::<u32, S>(x).unwrap() + offset.x,
+            cast::<u32, S>(y).unwrap() + offset.y,
+        );
+        match self.camera {
+            RayCamera::Lens {
+                ref camera,
+                ref pose,
+                aperture,
+                focus,
+            } => {
+                let mut ray = camera.unproject(pixel);
+                if aperture > S::zero() {
+                    // The direction has a depth of one, so this is where the
+                    // ray crosses the plane in focus.
+                    let target = ray.direction * focus;
+                    let lens = self.sample_square(x, y, sample, 1);
+                    let p = concentric_disk(lens.x, lens.y) * aperture;
+                    ray.origin = Point3::new(p.x, p.y, S::zero());
+                    ray.direction = target - ray.origin.to_vec();
+                }
+                Ray3::new(
+                    pose.transform_point(ray.origin),
+                    pose.transform_vector(ray.direction).normalize(),
+                )
+            }
+            RayCamera::Projection(ref inverse, size) => {
+                let two: S = cast(2).unwrap();
+                let ndc_x = pixel.x / size.x * two - S::one();
+                let ndc_y = S::one() - pixel.y / size.y * two;
+                let unproject = |z: S| {
+                    let p = inverse * Vector4::new(ndc_x, ndc_y, z, S::one());
+                    Point3::from_homogeneous(p)
+                };
+                let near = unproject(-S::one());
+                let far = unproject(S::one());
+                Ray3::new(near, (far - near).normalize())
+            }
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Device orientation from inertial and magnetic sensors, as found on
+//! phones and head-mounted displays.
+//!
+//! Orientations map vectors from the device frame to a world frame whose
+//! axes point east, north and up, matching the local tangent frames of the
+//! [`geo`](../geo/index.html) module. The accelerometer is expected to
+//! report the specific force, which points up when the device is at rest,
+//! and the magnetometer the local magnetic field, which points towards
+//! magnetic north and dips below the horizon away from the equator.
+//!
+//! ```rust
+//! use cgmath::sensor::orientation_from_gravity;
+//! use cgmath::{Rotation, Vector3};
+//! # #[macro_use] extern crate approx;
+//! # fn main() {
+//!
+//! // A device lying flat on a table, its y axis pointing north-east.
+//! let accel = Vector3::new(0.0f64, 0.0, 9.81);
+//! let field = Vector3::new(-0.15, 0.15, -0.4);
+//! let q = orientation_from_gravity(accel, field).unwrap();
+//! let y = q.rotate_vector(Vector3::unit_y());
+//! assert_relative_eq!(y, Vector3::new(1.0, 1.0, 0.0) / 2f64.sqrt(), epsilon = 1e-12);
+//! # }
+//! ```
+
+use structure::*;
+
+use angle::Rad;
+use matrix::Matrix3;
+use num::BaseFloat;
+use quaternion::Quaternion;
+use rotation::Rotation3;
+use vector::Vector3;
+
+/// Compute the orientation of a device from the accelerometer and
+/// magnetometer readings, both given in the device frame.
+///
+/// Neither vector needs to be normalized, and the magnetic field does not
+/// need to be perpendicular to gravity: only its horizontal component is
+/// used, so the magnetic dip does not tilt the result. `None` is returned
+/// if either vector is zero or if they are parallel, as happens near the
+/// magnetic poles.
+pub fn orientation_from_gravity<S: BaseFloat>(
+    accel: Vector3<S>,
+    magnetic: Vector3<S>,
+) -> Option<Quaternion<S>> {
+    let up = accel.normalize();
+    let east = magnetic.cross(up);
+    let length = east.magnitude();
+    let threshold = S::default_epsilon().sqrt() * magnetic.magnitude();
+    if !up.is_finite()
+        || length.is_nan()
+        || implicit_ulps_eq!(length, S::zero(), epsilon = threshold)
+    {
+        return None;
+    }
+    let east = east / length;
+    let north = up.cross(east);
+    // The rows of the device-to-world rotation are the world axes expressed
+    // in the device frame.
+    let m = Matrix3::from_cols(east, north, up).transpose();
+    Some(Quaternion::from(m).normalize())
+}
+
+/// A complementary filter fusing gyroscope, accelerometer and magnetometer
+/// readings into a device orientation.
+///
+/// The gyroscope is integrated at every update, which is smooth but drifts
+/// over time, and the result is pulled towards the orientation measured
+/// from gravity and the magnetic field, which is noisy but drift free. The
+/// time constant sets the cross-over between the two: measurements older
+/// than a few time constants no longer affect the estimate.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ComplementaryFilter<S> {
+    /// The current estimate of the device-to-world rotation.
+    pub orientation: Quaternion<S>,
+    /// The time constant of the correction, in seconds.
+    pub time_constant: S,
+}
+
+impl<S: BaseFloat> ComplementaryFilter<S> {
+    /// Construct a filter starting from the identity orientation.
+    #[inline]
+    pub fn new(time_constant: S) -> ComplementaryFilter<S> {
+        ComplementaryFilter {
+            orientation: Quaternion::one(),
+            time_constant,
+        }
+    }
+
+    /// Construct a filter starting from the orientation measured by the
+    /// accelerometer and magnetometer, or from the identity if it cannot
+    /// be measured.
+    pub fn from_measurement(
+        time_constant: S,
+        accel: Vector3<S>,
+        magnetic: Vector3<S>,
+    ) -> ComplementaryFilter<S> {
+        ComplementaryFilter {
+            orientation: orientation_from_gravity(accel, magnetic).unwrap_or_else(Quaternion::one),
+            time_constant,
+        }
+    }
+
+    /// Advance the filter by `dt` seconds and return the new orientation.
+    ///
+    /// `gyro` is the angular velocity in radians per second around the
+    /// device axes. When `magnetic` is `None`, only the tilt is corrected
+    /// and the heading follows the gyroscope alone. A zero accelerometer
+    /// reading, as measured in free fall, skips the correction.
+    pub fn update(
+        &mut self,
+        gyro: Vector3<S>,
+        accel: Vector3<S>,
+        magnetic: Option<Vector3<S>>,
+        dt: S,
+    ) -> Quaternion<S> {
+        let rate = gyro.magnitude();
+        if rate > S::zero() {
+            let step = Quaternion::from_axis_angle(gyro / rate, Rad(rate * dt));
+            self.orientation = (self.orientation * step).normalize();
+        }
+
+        let amount = dt / (self.time_constant + dt);
+        let measured = match magnetic {
+            Some(magnetic) => orientation_from_gravity(accel, magnetic),
+            None => self.tilt_corrected(accel),
+        };
+        if let Some(measured) = measured {
+            // Blend along the shortest path.
+            let measured = if self.orientation.dot(measured) < S::zero() {
+                -measured
+            } else {
+                measured
+            };
+            self.orientation = self.orientation.slerp(measured, amount).normalize();
+        }
+        self.orientation
+    }
+
+    /// The current orientation rotated so that the measured gravity points
+    /// straight down, keeping the heading unchanged.
+    fn tilt_corrected(&self, accel: Vector3<S>) -> Option<Quaternion<S>> {
+        let up = self.orientation * accel.normalize();
+        if !up.is_finite() {
+            return None;
+        }
+        Some(Quaternion::from_arc(up, Vector3::unit_z(), None) * self.orientation)
+    }
+}
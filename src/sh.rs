@@ -0,0 +1,407 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Real spherical harmonics of low order, for storing and manipulating
+//! smooth functions over the sphere of directions, such as the incoming
+//! light of an irradiance probe.
+//!
+//! `Sh2` holds the first two bands (four coefficients) and `Sh3` the first
+//! three bands (nine coefficients). The basis functions are ordered by band,
+//! then by `m` from `-l` to `l`, and do not include the Condon-Shortley
+//! phase, so the linear band is proportional to `(y, z, x)`.
+//!
+//! ```rust
+//! use cgmath::sh::Sh3;
+//! use cgmath::Vector3;
+//!
+//! // More light coming from above than from below.
+//! let sky = Sh3::project(|d: Vector3<f64>| d.z.max(0.0), 4096);
+//! assert!(sky.evaluate(Vector3::unit_z()) > sky.evaluate(-Vector3::unit_z()));
+//! assert!(sky.dominant_direction().z > 0.99);
+//! ```
+
+use num_traits::cast;
+use std::ops::*;
+
+use structure::*;
+
+use angle::Rad;
+use approx;
+use matrix::Matrix3;
+use num::BaseFloat;
+use quaternion::Quaternion;
+use vector::Vector3;
+
+/// The first two bands of a spherical harmonics expansion.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sh2<S> {
+    pub coefficients: [S; 4],
+}
+
+/// The first three bands of a spherical harmonics expansion.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sh3<S> {
+    pub coefficients: [S; 9],
+}
+
+fn constant<S: BaseFloat>(value: f64) -> S {
+    cast(value).unwrap()
+}
+
+/// `Y(0, 0)`, `Y(1, m) / axis` and `Y(2, m) / xy, yz, 3z² - 1, xz, x² - y²`.
+const BAND_0: f64 = 0.282_094_791_773_878_14;
+const BAND_1: f64 = 0.488_602_511_902_919_9;
+const BAND_2_XY: f64 = 1.092_548_430_592_079_2;
+const BAND_2_ZZ: f64 = 0.315_391_565_252_520_05;
+const BAND_2_XX_YY: f64 = 0.546_274_215_296_039_6;
+
+/// Sample `count` well distributed directions on the unit sphere, using a
+/// spherical Fibonacci lattice.
+fn fibonacci_sphere<S: BaseFloat>(count: usize) -> impl Iterator<Item = Vector3<S>> {
+    let n: S = cast(count).unwrap();
+    let golden_angle: S = constant(::std::f64::consts::PI * (3.0 - 5.0f64.sqrt()));
+    (0..count).map(move |i| {
+        let i: S = cast(i).unwrap();
+        let z = S::one() - (i + i + S::one()) / n;
+        let r = (S::one() - z * z).max(S::zero()).sqrt();
+        let (sin, cos) = Rad(golden_angle * i).sin_cos();
+        Vector3::new(r * cos, r * sin, z)
+    })
+}
+
+/// Rotate the linear band, which transforms like the vector `(x, y, z)`.
+fn rotate_band_1<S: BaseFloat>(rotation: &Matrix3<S>, c: &mut [S]) {
+    let v = rotation * Vector3::new(c[2], c[0], c[1]);
+    c[0] = v.y;
+    c[1] = v.z;
+    c[2] = v.x;
+}
+
+macro_rules! impl_sh {
+    ($Sh:ident, $n:expr) => {
+        impl<S: BaseFloat> $Sh<S> {
+            /// Construct an expansion from its coefficients.
+            #[inline]
+            pub fn new(coefficients: [S; $n]) -> $Sh<S> {
+                $Sh { coefficients }
+            }
+
+            /// The expansion of a function that is zero everywhere.
+            #[inline]
+            pub fn zero() -> $Sh<S> {
+                $Sh::new([S::zero(); $n])
+            }
+
+            /// The expansion of a function that has the same value in every
+            /// direction.
+            #[inline]
+            pub fn constant(value: S) -> $Sh<S> {
+                let mut sh = $Sh::zero();
+                sh.coefficients[0] = value / constant(BAND_0);
+                sh
+            }
+
+            /// The value of the function in a direction, which must be
+            /// normalized.
+            pub fn evaluate(&self, direction: Vector3<S>) -> S {
+                let basis = $Sh::basis(direction);
+                self.dot(&basis)
+            }
+
+            /// The integral over the sphere of the product of the two
+            /// functions.
+            pub fn dot(&self, other: &$Sh<S>) -> S {
+                self.coefficients
+                    .iter()
+                    .zip(other.coefficients.iter())
+                    .fold(S::zero(), |sum, (&a, &b)| sum + a * b)
+            }
+
+            /// Project a set of samples of a function, with directions
+            /// uniformly distributed over the sphere.
+            ///
+            /// Returns zero if there are no samples.
+            pub fn from_samples<I>(samples: I) -> $Sh<S>
+            where
+                I: IntoIterator<Item = (Vector3<S>, S)>,
+            {
+                let mut sum = $Sh::zero();
+                let mut count = 0usize;
+                for (direction, value) in samples {
+                    sum += $Sh::basis(direction) * value;
+                    count += 1;
+                }
+                if count == 0 {
+                    return sum;
+                }
+                let n: S = cast(count).unwrap();
+                let full_turn = Rad::<S>::full_turn().0;
+                sum * ((full_turn + full_turn) / n)
+            }
+
+            /// Project a function over the sphere, by evaluating it in
+            /// `count` well distributed directions.
+            pub fn project<F>(f: F, count: usize) -> $Sh<S>
+            where
+                F: Fn(Vector3<S>) -> S,
+            {
+                $Sh::from_samples(fibonacci_sphere(count).map(|d| (d, f(d))))
+            }
+
+            /// The average direction of the function, weighted by its value,
+            /// which is the direction of its linear band.
+            ///
+            /// For an expansion of incoming light, this is the dominant
+            /// light direction. Returns a zero vector if the linear band is
+            /// zero.
+            pub fn dominant_direction(&self) -> Vector3<S> {
+                let c = &self.coefficients;
+                let v = Vector3::new(c[3], c[1], c[2]);
+                if v.is_zero() {
+                    v
+                } else {
+                    v.normalize()
+                }
+            }
+
+            /// Convolve the function with a clamped cosine lobe.
+            ///
+            /// Applied to an expansion of incoming radiance, this gives the
+            /// irradiance received by a surface as a function of its normal,
+            /// as described in "An Efficient Representation for Irradiance
+            /// Environment Maps" by Ramamoorthi and Hanrahan.
+            pub fn cosine_convolve(&self) -> $Sh<S> {
+                let pi = Rad::<S>::turn_div_2().0;
+                let factors: [S; 3] = [
+                    pi,
+                    pi * constant(2.0 / 3.0),
+                    pi * constant(0.25),
+                ];
+                let mut sh = *self;
+                for (i, c) in sh.coefficients.iter_mut().enumerate() {
+                    let band = match i {
+                        0 => 0,
+                        1..=3 => 1,
+                        _ => 2,
+                    };
+                    *c *= factors[band];
+                }
+                sh
+            }
+        }
+
+        impl<S: BaseFloat> Zero for $Sh<S> {
+            #[inline]
+            fn zero() -> $Sh<S> {
+                $Sh::zero()
+            }
+
+            #[inline]
+            fn is_zero(&self) -> bool {
+                self.coefficients.iter().all(|c| c.is_zero())
+            }
+        }
+
+        impl_operator!(<S: BaseFloat> Add<$Sh<S> > for $Sh<S> {
+            fn add(lhs, rhs) -> $Sh<S> {{
+                let mut sh = lhs.clone();
+                sh += rhs.clone();
+                sh
+            }}
+        });
+        impl_assignment_operator!(<S: BaseFloat> AddAssign<$Sh<S> > for $Sh<S> {
+            fn add_assign(&mut self, other) {
+                for (a, &b) in self.coefficients.iter_mut().zip(other.coefficients.iter()) {
+                    *a += b;
+                }
+            }
+        });
+
+        impl_operator!(<S: BaseFloat> Sub<$Sh<S> > for $Sh<S> {
+            fn sub(lhs, rhs) -> $Sh<S> {{
+                let mut sh = lhs.clone();
+                sh -= rhs.clone();
+                sh
+            }}
+        });
+        impl_assignment_operator!(<S: BaseFloat> SubAssign<$Sh<S> > for $Sh<S> {
+            fn sub_assign(&mut self, other) {
+                for (a, &b) in self.coefficients.iter_mut().zip(other.coefficients.iter()) {
+                    *a -= b;
+                }
+            }
+        });
+
+        impl_operator!(<S: BaseFloat> Mul<S> for $Sh<S> {
+            fn mul(sh, scalar) -> $Sh<S> {{
+                let mut sh = sh.clone();
+                sh *= scalar;
+                sh
+            }}
+        });
+        impl_assignment_operator!(<S: BaseFloat> MulAssign<S> for $Sh<S> {
+            fn mul_assign(&mut self, scalar) {
+                for a in self.coefficients.iter_mut() {
+                    *a *= scalar;
+                }
+            }
+        });
+
+        impl<S: BaseFloat> approx::AbsDiffEq for $Sh<S> {
+            type Epsilon = S::Epsilon;
+
+            #[inline]
+            fn default_epsilon() -> S::Epsilon {
+                S::default_epsilon()
+            }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+                self.coefficients
+                    .iter()
+                    .zip(other.coefficients.iter())
+                    .all(|(a, b)| S::abs_diff_eq(a, b, epsilon))
+            }
+        }
+
+        impl<S: BaseFloat> approx::RelativeEq for $Sh<S> {
+            #[inline]
+            fn default_max_relative() -> S::Epsilon {
+                S::default_max_relative()
+            }
+
+            #[inline]
+            fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+                self.coefficients
+                    .iter()
+                    .zip(other.coefficients.iter())
+                    .all(|(a, b)| S::relative_eq(a, b, epsilon, max_relative))
+            }
+        }
+
+        impl<S: BaseFloat> approx::UlpsEq for $Sh<S> {
+            #[inline]
+            fn default_max_ulps() -> u32 {
+                S::default_max_ulps()
+            }
+
+            #[inline]
+            fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+                self.coefficients
+                    .iter()
+                    .zip(other.coefficients.iter())
+                    .all(|(a, b)| S::ulps_eq(a, b, epsilon, max_ulps))
+            }
+        }
+    };
+}
+
+impl_sh!(Sh2, 4);
+impl_sh!(Sh3, 9);
+
+impl<S: BaseFloat> Sh2<S> {
+    /// The basis functions evaluated in a direction, which must be
+    /// normalized.
+    ///
+    /// This is also the expansion of a function that is concentrated in
+    /// that direction, and can be accumulated to project samples.
+    pub fn basis(direction: Vector3<S>) -> Sh2<S> {
+        let d = direction;
+        let band_1: S = constant(BAND_1);
+        Sh2::new([constant(BAND_0), band_1 * d.y, band_1 * d.z, band_1 * d.x])
+    }
+
+    /// Rotate the function, so that its value in `rotation * d` is the
+    /// original value in `d`.
+    pub fn rotate(&self, rotation: Quaternion<S>) -> Sh2<S> {
+        let mut sh = *self;
+        rotate_band_1(&Matrix3::from(rotation), &mut sh.coefficients[1..4]);
+        sh
+    }
+}
+
+impl<S: BaseFloat> Sh3<S> {
+    /// The basis functions evaluated in a direction, which must be
+    /// normalized.
+    ///
+    /// This is also the expansion of a function that is concentrated in
+    /// that direction, and can be accumulated to project samples.
+    pub fn basis(direction: Vector3<S>) -> Sh3<S> {
+        let d = direction;
+        let band_1: S = constant(BAND_1);
+        let xy: S = constant(BAND_2_XY);
+        let zz: S = constant(BAND_2_ZZ);
+        let xx_yy: S = constant(BAND_2_XX_YY);
+        let three: S = constant(3.0);
+        Sh3::new([
+            constant(BAND_0),
+            band_1 * d.y,
+            band_1 * d.z,
+            band_1 * d.x,
+            xy * d.x * d.y,
+            xy * d.y * d.z,
+            zz * (three * d.z * d.z - S::one()),
+            xy * d.x * d.z,
+            xx_yy * (d.x * d.x - d.y * d.y),
+        ])
+    }
+
+    /// Rotate the function, so that its value in `rotation * d` is the
+    /// original value in `d`.
+    ///
+    /// The quadratic band is rotated as the traceless symmetric matrix `Q`
+    /// for which it equals `dᵀ Q d` on the sphere, which avoids building the
+    /// Wigner rotation matrices.
+    pub fn rotate(&self, rotation: Quaternion<S>) -> Sh3<S> {
+        let r = Matrix3::from(rotation);
+        let mut sh = *self;
+        rotate_band_1(&r, &mut sh.coefficients[1..4]);
+
+        let c = &self.coefficients;
+        let half = constant::<S>(0.5);
+        let xy: S = constant(BAND_2_XY);
+        let zz: S = constant(BAND_2_ZZ);
+        let xx_yy: S = constant(BAND_2_XX_YY);
+        let (q_xy, q_yz, q_xz) = (c[4] * xy * half, c[5] * xy * half, c[7] * xy * half);
+        let q_xx = c[8] * xx_yy - c[6] * zz;
+        let q_yy = -c[8] * xx_yy - c[6] * zz;
+        let q_zz = (c[6] + c[6]) * zz;
+        let q = Matrix3::new(q_xx, q_xy, q_xz, q_xy, q_yy, q_yz, q_xz, q_yz, q_zz);
+        let q = r * q * r.transpose();
+
+        sh.coefficients[4] = (q.y.x + q.y.x) / xy;
+        sh.coefficients[5] = (q.z.y + q.z.y) / xy;
+        sh.coefficients[6] = q.z.z * half / zz;
+        sh.coefficients[7] = (q.z.x + q.z.x) / xy;
+        sh.coefficients[8] = (q.x.x - q.y.y) * half / xx_yy;
+        sh
+    }
+
+    /// The first two bands of the expansion.
+    pub fn truncate(&self) -> Sh2<S> {
+        let c = &self.coefficients;
+        Sh2::new([c[0], c[1], c[2], c[3]])
+    }
+}
+
+impl<S: BaseFloat> From<Sh2<S>> for Sh3<S> {
+    fn from(sh: Sh2<S>) -> Sh3<S> {
+        let mut coefficients = [S::zero(); 9];
+        coefficients[..4].copy_from_slice(&sh.coefficients);
+        Sh3::new(coefficients)
+    }
+}
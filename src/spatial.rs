@@ -0,0 +1,61 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::cast;
+use std::collections::HashMap;
+
+use num::BaseFloat;
+use point::Point3;
+
+/// A uniform grid of cells over three-dimensional space, each holding the
+/// indices of the items whose positions fall inside of it.
+///
+/// Only occupied cells are stored, so the grid is unbounded. Neighbourhood
+/// queries cover the cell of the query point and the 26 cells around it,
+/// which contain every item closer than the cell size.
+pub(crate) struct SpatialHash<S> {
+    cell_size: S,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl<S: BaseFloat> SpatialHash<S> {
+    pub(crate) fn new(cell_size: S) -> SpatialHash<S> {
+        SpatialHash {
+            cell_size,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, p: Point3<S>) -> [i64; 3] {
+        let index = |x: S| cast((x / self.cell_size).floor()).unwrap_or(0);
+        [index(p.x), index(p.y), index(p.z)]
+    }
+
+    pub(crate) fn insert(&mut self, p: Point3<S>, item: usize) {
+        let cell = self.cell(p);
+        self.cells.entry(cell).or_default().push(item);
+    }
+
+    /// The items in the cells around `p`, in no particular order.
+    pub(crate) fn neighbours(&self, p: Point3<S>) -> impl Iterator<Item = usize> + '_ {
+        let [x, y, z] = self.cell(p);
+        (0..27)
+            .filter_map(move |i| {
+                self.cells
+                    .get(&[x + i % 3 - 1, y + i / 3 % 3 - 1, z + i / 9 - 1])
+            })
+            .flat_map(|items| items.iter().cloned())
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use approx;
+use num::BaseFloat;
+use point::Point3;
+use vector::Vector3;
+
+/// A sphere, or ball, in three-dimensional space.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sphere<S> {
+    /// The center of the sphere.
+    pub center: Point3<S>,
+    /// The radius of the sphere.
+    pub radius: S,
+}
+
+impl<S: BaseFloat> Sphere<S> {
+    /// Construct a sphere from its center and radius.
+    #[inline]
+    pub fn new(center: Point3<S>, radius: S) -> Sphere<S> {
+        Sphere { center, radius }
+    }
+
+    /// Test whether the point is inside the sphere or on its surface.
+    #[inline]
+    pub fn contains_point(&self, p: Point3<S>) -> bool {
+        (p - self.center).magnitude2() <= self.radius * self.radius
+    }
+
+    /// Test whether this sphere, as an opaque planet, hides all of `other`
+    /// from `eye`, for horizon culling in planetary renderers.
+    ///
+    /// The test is conservative: it only reports spheres entirely inside the
+    /// cone of the lines from `eye` tangent to the planet, and beyond the
+    /// plane of the horizon, where they are certainly hidden. Spheres that
+    /// are only hidden by the bulge of the planet in front of that plane are
+    /// not reported, and nothing is hidden from an eye inside the planet.
+    ///
+    /// ```rust
+    /// use cgmath::{Point3, Sphere};
+    ///
+    /// let planet = Sphere::new(Point3::new(0.0, 0.0, 0.0), 6371.0f64);
+    /// let eye = Point3::new(0.0, 0.0, 6372.0);
+    ///
+    /// // A mountain on the other side of the planet is culled, but not one
+    /// // near the camera.
+    /// let antipode = Sphere::new(Point3::new(0.0, 0.0, -6371.0), 5.0);
+    /// let nearby = Sphere::new(Point3::new(10.0, 0.0, 6371.0), 5.0);
+    /// assert!(planet.occludes(eye, &antipode));
+    /// assert!(!planet.occludes(eye, &nearby));
+    /// ```
+    pub fn occludes(&self, eye: Point3<S>, other: &Sphere<S>) -> bool {
+        let axis = self.center - eye;
+        let d2 = axis.magnitude2();
+        let r2 = self.radius * self.radius;
+        if d2 <= r2 {
+            return false;
+        }
+        let d = d2.sqrt();
+        let axis = axis / d;
+        let tangent = (d2 - r2).sqrt();
+
+        // The distance of the center of `other` along the axis of the cone,
+        // and from it.
+        let v = other.center - eye;
+        let t = v.dot(axis);
+        let h = (v - axis * t).magnitude();
+
+        // Beyond the plane of the horizon, at `tangent² / d` along the axis,
+        // and farther than `other.radius` from the sides of the cone.
+        let beyond_horizon = t - other.radius >= tangent * tangent / d;
+        let inside_cone = t * self.radius - h * tangent >= other.radius * d;
+        beyond_horizon && inside_cone
+    }
+
+    /// Create line segments approximating the three great circles of the
+    /// sphere perpendicular to the coordinate axes, each with `segments`
+    /// segments.
+    ///
+    /// ```rust
+    /// use cgmath::{Point3, Sphere};
+    ///
+    /// let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 2.0f64);
+    /// let lines = sphere.to_wireframe_lines(16);
+    /// assert_eq!(lines.len(), 48);
+    /// assert!(lines.iter().all(|&(a, _)| (a.x * a.x + a.y * a.y + a.z * a.z - 4.0).abs() < 1e-12));
+    /// ```
+    pub fn to_wireframe_lines(&self, segments: usize) -> Vec<(Point3<S>, Point3<S>)> {
+        let n: S = cast(segments).unwrap();
+        let step = Rad::full_turn() / n;
+        let axes = [
+            (Vector3::unit_x(), Vector3::unit_y()),
+            (Vector3::unit_y(), Vector3::unit_z()),
+            (Vector3::unit_z(), Vector3::unit_x()),
+        ];
+        let mut lines = Vec::with_capacity(segments * 3);
+        for &(u, v) in &axes {
+            let point = |i: usize| {
+                let (sin, cos) = Rad::sin_cos(step * cast(i).unwrap());
+                self.center + (u * cos + v * sin) * self.radius
+            };
+            lines.extend((0..segments).map(|i| (point(i), point(i + 1))));
+        }
+        lines
+    }
+}
+
+impl<S: BaseFloat> approx::AbsDiffEq for Sphere<S> {
+    type Epsilon = S::Epsilon;
+
+    #[inline]
+    fn default_epsilon() -> S::Epsilon {
+        S::default_epsilon()
+    }
+
+    #[inline]
+    fn abs_diff_eq(&self, other: &Self, epsilon: S::Epsilon) -> bool {
+        Point3::abs_diff_eq(&self.center, &other.center, epsilon)
+            && S::abs_diff_eq(&self.radius, &other.radius, epsilon)
+    }
+}
+
+impl<S: BaseFloat> approx::RelativeEq for Sphere<S> {
+    #[inline]
+    fn default_max_relative() -> S::Epsilon {
+        S::default_max_relative()
+    }
+
+    #[inline]
+    fn relative_eq(&self, other: &Self, epsilon: S::Epsilon, max_relative: S::Epsilon) -> bool {
+        Point3::relative_eq(&self.center, &other.center, epsilon, max_relative)
+            && S::relative_eq(&self.radius, &other.radius, epsilon, max_relative)
+    }
+}
+
+impl<S: BaseFloat> approx::UlpsEq for Sphere<S> {
+    #[inline]
+    fn default_max_ulps() -> u32 {
+        S::default_max_ulps()
+    }
+
+    #[inline]
+    fn ulps_eq(&self, other: &Self, epsilon: S::Epsilon, max_ulps: u32) -> bool {
+        Point3::ulps_eq(&self.center, &other.center, epsilon, max_ulps)
+            && S::ulps_eq(&self.radius, &other.radius, epsilon, max_ulps)
+    }
+}
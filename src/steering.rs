@@ -0,0 +1,414 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Steering behaviours for autonomous agents, as described in "Steering
+//! Behaviors For Autonomous Characters" by Craig Reynolds.
+//!
+//! Each behaviour returns a steering force, which is the change of velocity
+//! that the agent would like to make, and are combined by adding them with
+//! weights. The functions work with points and vectors of two or three
+//! dimensions, and `Agent2` and `Agent3` integrate the resulting motion with
+//! limits on the speed and the force. `flock2` and `flock3` compute the
+//! forces of whole flocks of boids at once, and `in_view_cone` and
+//! `in_view_arc` tell what the agents can see.
+//!
+//! ```rust
+//! use cgmath::prelude::*;
+//! use cgmath::steering::{self, Agent2};
+//! use cgmath::Point2;
+//!
+//! let target = Point2::new(10.0, 5.0f64);
+//! let mut agent = Agent2::new(Point2::new(0.0, 0.0), 2.0, 4.0);
+//! for _ in 0..400 {
+//!     let force = steering::arrive(agent.position, agent.velocity, target, agent.max_speed, 3.0);
+//!     agent.update(force, 0.05);
+//! }
+//! assert!((agent.position - target).magnitude() < 0.01);
+//! ```
+
+use num_traits::cast;
+
+use structure::*;
+
+use angle::Rad;
+use num::BaseFloat;
+use point::{Point2, Point3};
+use spatial::SpatialHash;
+use vector::{Vector2, Vector3};
+
+/// Scale `v` down to `max` if it is longer.
+fn limit<S: BaseFloat, V: InnerSpace<Scalar = S>>(v: V, max: S) -> V {
+    let length = v.magnitude();
+    if length > max {
+        v * (max / length)
+    } else {
+        v
+    }
+}
+
+/// The velocity of `max_speed` along `v`, or zero if `v` is zero.
+fn full_speed<S: BaseFloat, V: InnerSpace<Scalar = S>>(v: V, max_speed: S) -> V {
+    let length = v.magnitude();
+    if length > S::zero() {
+        v * (max_speed / length)
+    } else {
+        V::zero()
+    }
+}
+
+/// Steer towards `target` at full speed.
+pub fn seek<S, P>(position: P, velocity: P::Diff, target: P, max_speed: S) -> P::Diff
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    full_speed(target - position, max_speed) - velocity
+}
+
+/// Steer away from `threat` at full speed.
+pub fn flee<S, P>(position: P, velocity: P::Diff, threat: P, max_speed: S) -> P::Diff
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    full_speed(position - threat, max_speed) - velocity
+}
+
+/// Steer towards `target`, slowing down linearly within `slowing_radius`
+/// of it so as to stop there.
+pub fn arrive<S, P>(
+    position: P,
+    velocity: P::Diff,
+    target: P,
+    max_speed: S,
+    slowing_radius: S,
+) -> P::Diff
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    let offset = target - position;
+    let distance = offset.magnitude();
+    let speed = if distance < slowing_radius {
+        max_speed * distance / slowing_radius
+    } else {
+        max_speed
+    };
+    full_speed(offset, speed) - velocity
+}
+
+/// Steer towards a point wandering on a circle, or a sphere, `distance`
+/// ahead of the agent along its velocity.
+///
+/// `displacement` is the offset from the center of the circle to the
+/// wandering point. To wander smoothly, it is kept from one update to the
+/// next, nudged by a small random vector and scaled back to the radius of
+/// the circle each time.
+pub fn wander<S: BaseFloat, V: InnerSpace<Scalar = S>>(velocity: V, distance: S, displacement: V) -> V {
+    full_speed(velocity, distance) + displacement
+}
+
+/// Steer away from the `neighbours` closer than `radius`, more strongly
+/// from the closest ones.
+///
+/// The force away from each neighbour is inversely proportional to its
+/// distance. Neighbours at the position of the agent, which includes the
+/// agent itself, are ignored.
+pub fn separation<S, P>(position: P, neighbours: &[P], radius: S) -> P::Diff
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    let mut force = P::Diff::zero();
+    for &neighbour in neighbours {
+        let away = position - neighbour;
+        let distance2 = away.magnitude2();
+        if distance2 > S::zero() && distance2 < radius * radius {
+            force = force + away / distance2;
+        }
+    }
+    force
+}
+
+/// Whether `target` is within `half_angle` of the direction `forward`, as
+/// seen from `eye`.
+fn in_view<S, P>(eye: P, forward: P::Diff, half_angle: Rad<S>, target: P) -> bool
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    if half_angle >= Rad::turn_div_2() {
+        return true;
+    }
+    let to_target = target - eye;
+    let scale = (forward.magnitude2() * to_target.magnitude2()).sqrt();
+    scale == S::zero() || forward.dot(to_target) >= scale * Rad::cos(half_angle)
+}
+
+/// Whether `target` is inside the cone of vision of an agent at `eye`,
+/// looking along `forward`, which extends `half_angle` around it.
+///
+/// `forward` does not need to be normalized. A target at the eye is always
+/// visible, and so is everything when the half angle is at least half a
+/// turn. Combine this with `view_falloff` to limit the range of vision.
+///
+/// ```rust
+/// use cgmath::steering::in_view_cone;
+/// use cgmath::{Deg, Point3, Vector3};
+///
+/// let eye = Point3::new(0.0, 0.0, 0.0f64);
+/// let forward = Vector3::new(0.0, 0.0, -2.0);
+/// assert!(in_view_cone(eye, forward, Deg(45.0).into(), Point3::new(1.0, 1.0, -5.0)));
+/// assert!(!in_view_cone(eye, forward, Deg(45.0).into(), Point3::new(5.0, 0.0, -1.0)));
+/// ```
+pub fn in_view_cone<S: BaseFloat>(
+    eye: Point3<S>,
+    forward: Vector3<S>,
+    half_angle: Rad<S>,
+    target: Point3<S>,
+) -> bool {
+    in_view(eye, forward, half_angle, target)
+}
+
+/// Whether `target` is inside the arc of vision of a two-dimensional agent
+/// at `eye`, looking along `forward`, which extends `half_angle` on both
+/// sides of it.
+///
+/// This is the two-dimensional `in_view_cone`.
+pub fn in_view_arc<S: BaseFloat>(
+    eye: Point2<S>,
+    forward: Vector2<S>,
+    half_angle: Rad<S>,
+    target: Point2<S>,
+) -> bool {
+    in_view(eye, forward, half_angle, target)
+}
+
+/// How well an agent at `eye` perceives `target`, if it sees as far as
+/// `range`.
+///
+/// This falls linearly from `1` at the eye to `0` at `range` and beyond,
+/// and can weight the reaction of the agent to what is in view.
+pub fn view_falloff<S, P>(eye: P, target: P, range: S) -> S
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+{
+    let distance = (target - eye).magnitude();
+    if distance < range {
+        S::one() - distance / range
+    } else {
+        S::zero()
+    }
+}
+
+/// The neighbourhood radius and the weights of the behaviours of boids,
+/// for `flock2` and `flock3`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Flocking<S> {
+    /// The distance under which boids are neighbours.
+    pub radius: S,
+    /// The weight of the steering towards the average velocity of the
+    /// neighbours.
+    pub alignment: S,
+    /// The weight of the steering towards the center of the neighbours.
+    pub cohesion: S,
+    /// The weight of the steering away from the neighbours, as computed by
+    /// `separation`.
+    pub separation: S,
+}
+
+impl<S: BaseFloat> Flocking<S> {
+    /// Flocking with neighbours closer than `radius`, and all behaviours
+    /// weighted equally.
+    #[inline]
+    pub fn new(radius: S) -> Flocking<S> {
+        Flocking {
+            radius,
+            alignment: S::one(),
+            cohesion: S::one(),
+            separation: S::one(),
+        }
+    }
+}
+
+/// The flocking forces of boids given by their positions and velocities,
+/// with neighbours found in a spatial hash grid embedding the points in
+/// three dimensions.
+fn flock<S, P, F>(
+    positions: &[P],
+    velocities: &[P::Diff],
+    flocking: &Flocking<S>,
+    embed: F,
+) -> Vec<P::Diff>
+where
+    S: BaseFloat,
+    P: EuclideanSpace<Scalar = S>,
+    P::Diff: InnerSpace<Scalar = S>,
+    F: Fn(P) -> Point3<S>,
+{
+    assert_eq!(positions.len(), velocities.len(), "one velocity per boid");
+    let mut grid = SpatialHash::new(flocking.radius);
+    for (i, &p) in positions.iter().enumerate() {
+        grid.insert(embed(p), i);
+    }
+    let radius2 = flocking.radius * flocking.radius;
+    positions
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            let mut count = 0;
+            let mut velocity = P::Diff::zero();
+            let mut center = P::Diff::zero();
+            let mut away = P::Diff::zero();
+            for j in grid.neighbours(embed(p)) {
+                let offset = p - positions[j];
+                let distance2 = offset.magnitude2();
+                if j == i || distance2 >= radius2 {
+                    continue;
+                }
+                count += 1;
+                velocity = velocity + velocities[j];
+                center = center + positions[j].to_vec();
+                if distance2 > S::zero() {
+                    away = away + offset / distance2;
+                }
+            }
+            if count == 0 {
+                return P::Diff::zero();
+            }
+            let n: S = cast(count).unwrap();
+            (velocity / n - velocities[i]) * flocking.alignment
+                + (center / n - p.to_vec()) * flocking.cohesion
+                + away * flocking.separation
+        })
+        .collect()
+}
+
+/// The steering forces of a flock of boids in the plane, given by their
+/// positions and velocities, following the alignment, cohesion and
+/// separation rules of Reynolds.
+///
+/// Each boid only reacts to the others closer than `flocking.radius`,
+/// found with a spatial hash grid, so large flocks are updated in time
+/// proportional to their size when they are not too dense. Boids without
+/// neighbours get a zero force.
+///
+/// ```rust
+/// use cgmath::steering::{flock2, Flocking};
+/// use cgmath::{Point2, Vector2};
+///
+/// let positions = [Point2::new(0.0, 0.0), Point2::new(1.0, 0.0), Point2::new(50.0, 0.0)];
+/// let velocities = [Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0), Vector2::new(0.0, 1.0)];
+/// let forces = flock2(&positions, &velocities, &Flocking::new(2.0f64));
+/// // The sum of the alignment, cohesion and separation of the first boid.
+/// let alignment = Vector2::new(-1.0, 1.0);
+/// let cohesion = Vector2::new(1.0, 0.0);
+/// let separation = Vector2::new(-1.0, 0.0);
+/// assert_eq!(forces[0], alignment + cohesion + separation);
+/// assert_eq!(forces[2], Vector2::new(0.0, 0.0));
+/// ```
+pub fn flock2<S: BaseFloat>(
+    positions: &[Point2<S>],
+    velocities: &[Vector2<S>],
+    flocking: &Flocking<S>,
+) -> Vec<Vector2<S>> {
+    flock(positions, velocities, flocking, |p| Point3::new(p.x, p.y, S::zero()))
+}
+
+/// The steering forces of a flock of boids in space, as computed in the
+/// plane by `flock2`.
+pub fn flock3<S: BaseFloat>(
+    positions: &[Point3<S>],
+    velocities: &[Vector3<S>],
+    flocking: &Flocking<S>,
+) -> Vec<Vector3<S>> {
+    flock(positions, velocities, flocking, |p| p)
+}
+
+macro_rules! impl_agent {
+    ($AgentN:ident, $PointN:ident, $VectorN:ident) => {
+        impl<S: BaseFloat> $AgentN<S> {
+            /// Construct an agent at rest at `position`.
+            #[inline]
+            pub fn new(position: $PointN<S>, max_speed: S, max_force: S) -> $AgentN<S> {
+                $AgentN {
+                    position,
+                    velocity: $VectorN::zero(),
+                    max_speed,
+                    max_force,
+                }
+            }
+
+            /// Apply a steering `force` for `dt`, after limiting it to the
+            /// largest force, then limit the resulting speed and move the
+            /// agent.
+            pub fn update(&mut self, force: $VectorN<S>, dt: S) {
+                let force = limit(force, self.max_force);
+                self.velocity = limit(self.velocity + force * dt, self.max_speed);
+                self.position += self.velocity * dt;
+            }
+
+            /// The direction the agent is moving towards, if it is moving.
+            pub fn heading(&self) -> Option<$VectorN<S>> {
+                if self.velocity.is_zero() {
+                    None
+                } else {
+                    Some(self.velocity.normalize())
+                }
+            }
+        }
+    };
+}
+
+/// A point-like agent moving in the plane.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Agent2<S> {
+    /// The position of the agent.
+    pub position: Point2<S>,
+    /// The velocity of the agent, in units per unit of time.
+    pub velocity: Vector2<S>,
+    /// The largest speed of the agent.
+    pub max_speed: S,
+    /// The largest steering force the agent can apply, which is its
+    /// largest acceleration.
+    pub max_force: S,
+}
+
+/// A point-like agent moving in space.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Agent3<S> {
+    /// The position of the agent.
+    pub position: Point3<S>,
+    /// The velocity of the agent, in units per unit of time.
+    pub velocity: Vector3<S>,
+    /// The largest speed of the agent.
+    pub max_speed: S,
+    /// The largest steering force the agent can apply, which is its
+    /// largest acceleration.
+    pub max_force: S,
+}
+
+impl_agent!(Agent2, Point2, Vector2);
+impl_agent!(Agent3, Point3, Vector3);
@@ -0,0 +1,286 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trigonometric functions giving bit-identical results on every platform.
+//!
+//! The trigonometric functions of the standard library call the C library of
+//! the platform, or compiler intrinsics, which may round their results
+//! differently on different operating systems, architectures or compiler
+//! versions. Programs that must compute exactly the same values on every
+//! machine, like lockstep multiplayer games or replays, cannot use them.
+//!
+//! The functions of this module are implemented in software, adapted from
+//! the FreeBSD math library, using only the basic arithmetic operations and
+//! square roots, which IEEE 754 requires to be correctly rounded and are
+//! therefore the same everywhere. They are computed in double precision and
+//! rounded to the precision of their argument, and are accurate to about an
+//! ulp. The range reduction of `sin`, `cos` and `tan` loses accuracy for
+//! arguments larger than about `1.0e6`, though the results stay
+//! deterministic.
+//!
+//! Enabling the `deterministic` feature makes the `Angle` trait, and so the
+//! rotations, projections and every other function of this crate using
+//! trigonometry, use these functions. Other transcendental functions, like
+//! `exp`, `ln` and `powf`, still come from the platform.
+//!
+//! ```rust
+//! use cgmath::strict;
+//!
+//! let (sin, cos) = strict::sin_cos(0.5f64);
+//! assert!((sin - 0.5f64.sin()).abs() <= 1.0e-16);
+//! assert!((cos - 0.5f64.cos()).abs() <= 1.0e-16);
+//! ```
+
+use std::f64::consts::{FRAC_2_PI, FRAC_PI_2, FRAC_PI_4, PI};
+
+use num_traits::{cast, Float};
+
+use num::BaseFloat;
+
+/// The rounding error of `PI`.
+const PI_LO: f64 = 1.2246467991473532e-16;
+
+// pi / 2 split in three parts of 33 bits, so that their products with the
+// quadrant number are exact.
+const PIO2_1: f64 = 1.5707963267341256;
+const PIO2_2: f64 = 6.077100506303966e-11;
+const PIO2_3: f64 = 2.0222662487111665e-21;
+
+#[inline]
+fn to_f64<S: BaseFloat>(x: S) -> f64 {
+    cast(x).unwrap()
+}
+
+#[inline]
+fn from_f64<S: BaseFloat>(x: f64) -> S {
+    cast(x).unwrap()
+}
+
+/// The magnitude of `x` with the sign of `sign`, as `f64::copysign`, which
+/// is more recent than the oldest compilers the crate supports.
+#[inline]
+fn copysign(x: f64, sign: f64) -> f64 {
+    if sign.is_sign_negative() {
+        -x.abs()
+    } else {
+        x.abs()
+    }
+}
+
+/// The sine of `x` for `|x| <= pi / 4`.
+fn kernel_sin(x: f64) -> f64 {
+    const S1: f64 = -0.16666666666666632;
+    const S2: f64 = 0.00833333333332249;
+    const S3: f64 = -0.0001984126982985795;
+    const S4: f64 = 2.7557313707070068e-06;
+    const S5: f64 = -2.5050760253406863e-08;
+    const S6: f64 = 1.58969099521155e-10;
+    let z = x * x;
+    let v = z * x;
+    let r = S2 + z * (S3 + z * (S4 + z * (S5 + z * S6)));
+    x + v * (S1 + z * r)
+}
+
+/// The cosine of `x` for `|x| <= pi / 4`.
+fn kernel_cos(x: f64) -> f64 {
+    const C1: f64 = 0.0416666666666666;
+    const C2: f64 = -0.001388888888887411;
+    const C3: f64 = 2.480158728947673e-05;
+    const C4: f64 = -2.7557314351390663e-07;
+    const C5: f64 = 2.087572321298175e-09;
+    const C6: f64 = -1.1359647557788195e-11;
+    let z = x * x;
+    let r = z * (C1 + z * (C2 + z * (C3 + z * (C4 + z * (C5 + z * C6)))));
+    let hz = 0.5 * z;
+    let w = 1.0 - hz;
+    w + (((1.0 - w) - hz) + z * r)
+}
+
+/// The sine and cosine of `x`.
+fn sin_cos_f64(x: f64) -> (f64, f64) {
+    if !x.is_finite() {
+        return (Float::nan(), Float::nan());
+    }
+    if x.abs() < 7.450580596923828e-9 {
+        // 2^-27, below which the sine rounds to its argument, keeping the
+        // sign of zero, and the cosine to one.
+        return (x, 1.0);
+    }
+    if x.abs() <= FRAC_PI_4 {
+        return (kernel_sin(x), kernel_cos(x));
+    }
+    let k = (x * FRAC_2_PI).round();
+    let r = ((x - k * PIO2_1) - k * PIO2_2) - k * PIO2_3;
+    let (s, c) = (kernel_sin(r), kernel_cos(r));
+    match (k as i64) & 3 {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    }
+}
+
+/// The arc tangent of `x`.
+fn atan_f64(x: f64) -> f64 {
+    const ATAN_HI: [f64; 4] = [0.4636476090008061, FRAC_PI_4, 0.982793723247329, FRAC_PI_2];
+    const ATAN_LO: [f64; 4] = [
+        2.2698777452961687e-17,
+        3.061616997868383e-17,
+        1.3903311031230998e-17,
+        6.123233995736766e-17,
+    ];
+    const AT: [f64; 11] = [
+        0.3333333333333293,
+        -0.19999999999876483,
+        0.14285714272503466,
+        -0.11111110405462356,
+        0.09090887133436507,
+        -0.0769187620504483,
+        0.06661073137387531,
+        -0.058335701337905735,
+        0.049768779946159324,
+        -0.036531572744216916,
+        0.016285820115365782,
+    ];
+
+    if x.is_nan() {
+        return x;
+    }
+    let a = x.abs();
+    if a >= 7.378697629483821e19 {
+        // 2^66, beyond which the arc tangent rounds to pi / 2.
+        return copysign(ATAN_HI[3] + ATAN_LO[3], x);
+    }
+    if a < 3.725290298461914e-9 {
+        // 2^-28, below which the arc tangent rounds to its argument.
+        return x;
+    }
+    let (id, t) = if a < 0.4375 {
+        (None, x)
+    } else if a < 0.6875 {
+        (Some(0), (2.0 * a - 1.0) / (2.0 + a))
+    } else if a < 1.1875 {
+        (Some(1), (a - 1.0) / (a + 1.0))
+    } else if a < 2.4375 {
+        (Some(2), (a - 1.5) / (1.0 + 1.5 * a))
+    } else {
+        (Some(3), -1.0 / a)
+    };
+    let z = t * t;
+    let w = z * z;
+    let s1 = z * (AT[0] + w * (AT[2] + w * (AT[4] + w * (AT[6] + w * (AT[8] + w * AT[10])))));
+    let s2 = w * (AT[1] + w * (AT[3] + w * (AT[5] + w * (AT[7] + w * AT[9]))));
+    match id {
+        None => t - t * (s1 + s2),
+        Some(i) => copysign(ATAN_HI[i] - ((t * (s1 + s2) - ATAN_LO[i]) - t), x),
+    }
+}
+
+/// The four quadrant arc tangent of `y / x`.
+fn atan2_f64(y: f64, x: f64) -> f64 {
+    if x.is_nan() || y.is_nan() {
+        return Float::nan();
+    }
+    if y == 0.0 {
+        return if x.is_sign_negative() {
+            copysign(PI, y)
+        } else {
+            y
+        };
+    }
+    if x == 0.0 {
+        return copysign(FRAC_PI_2, y);
+    }
+    if x.is_infinite() {
+        let a = match (y.is_infinite(), x > 0.0) {
+            (true, true) => FRAC_PI_4,
+            (true, false) => 3.0 * FRAC_PI_4,
+            (false, true) => 0.0,
+            (false, false) => PI,
+        };
+        return copysign(a, y);
+    }
+    if y.is_infinite() {
+        return copysign(FRAC_PI_2, y);
+    }
+    let a = atan_f64((y / x).abs());
+    if x > 0.0 {
+        copysign(a, y)
+    } else {
+        copysign(PI - (a - PI_LO), y)
+    }
+}
+
+/// The sine of `x`, in radians.
+#[inline]
+pub fn sin<S: BaseFloat>(x: S) -> S {
+    from_f64(sin_cos_f64(to_f64(x)).0)
+}
+
+/// The cosine of `x`, in radians.
+#[inline]
+pub fn cos<S: BaseFloat>(x: S) -> S {
+    from_f64(sin_cos_f64(to_f64(x)).1)
+}
+
+/// The sine and cosine of `x`, in radians.
+#[inline]
+pub fn sin_cos<S: BaseFloat>(x: S) -> (S, S) {
+    let (s, c) = sin_cos_f64(to_f64(x));
+    (from_f64(s), from_f64(c))
+}
+
+/// The tangent of `x`, in radians.
+#[inline]
+pub fn tan<S: BaseFloat>(x: S) -> S {
+    let (s, c) = sin_cos_f64(to_f64(x));
+    from_f64(s / c)
+}
+
+/// The arc sine of `x`, in radians, or `NaN` outside of `[-1, 1]`.
+#[inline]
+pub fn asin<S: BaseFloat>(x: S) -> S {
+    let x = to_f64(x);
+    from_f64(atan2_f64(x, ((1.0 - x) * (1.0 + x)).sqrt()))
+}
+
+/// The arc cosine of `x`, in radians, or `NaN` outside of `[-1, 1]`.
+#[inline]
+pub fn acos<S: BaseFloat>(x: S) -> S {
+    let x = to_f64(x);
+    from_f64(atan2_f64(((1.0 - x) * (1.0 + x)).sqrt(), x))
+}
+
+/// The arc tangent of `x`, in radians.
+#[inline]
+pub fn atan<S: BaseFloat>(x: S) -> S {
+    from_f64(atan_f64(to_f64(x)))
+}
+
+/// The four quadrant arc tangent of `y / x`, in radians, following the
+/// conventions of `f64::atan2`.
+#[inline]
+pub fn atan2<S: BaseFloat>(y: S, x: S) -> S {
+    from_f64(atan2_f64(to_f64(y), to_f64(x)))
+}
+
+/// The square root of `x`.
+///
+/// This is the same as `Float::sqrt`, which IEEE 754 requires to be
+/// correctly rounded, and is only provided here for completeness.
+#[inline]
+pub fn sqrt<S: BaseFloat>(x: S) -> S {
+    x.sqrt()
+}
@@ -23,7 +23,9 @@ use std::ops::*;
 use approx;
 
 use angle::Rad;
+use fast;
 use num::{BaseFloat, BaseNum};
+use vector::Vector3;
 
 pub use num_traits::{Bounded, One, Zero};
 
@@ -190,6 +192,51 @@ where
     }
 }
 
+/// Values that can be blended with barycentric weights, such as the
+/// normals, texture coordinates or colors stored at the vertices of a
+/// triangle.
+///
+/// This is implemented for every `VectorSpace`, for points, for the
+/// linear color types, and for `f32` and `f64` themselves.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{Vector2, Vector3};
+///
+/// let uv = Interpolate::barycentric(
+///     (Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0)),
+///     Vector3::new(0.5, 0.25, 0.25f64),
+/// );
+/// assert_eq!(uv, Vector2::new(0.25, 0.25));
+/// ```
+pub trait Interpolate<S>: Copy {
+    /// Returns `values.0 * weights.x + values.1 * weights.y + values.2 * weights.z`.
+    ///
+    /// The weights are expected to sum to one.
+    fn barycentric(values: (Self, Self, Self), weights: Vector3<S>) -> Self;
+}
+
+impl<V: VectorSpace> Interpolate<V::Scalar> for V {
+    #[inline]
+    fn barycentric(values: (V, V, V), weights: Vector3<V::Scalar>) -> V {
+        values.0 * weights.x + values.1 * weights.y + values.2 * weights.z
+    }
+}
+
+macro_rules! impl_interpolate_scalar {
+    ($S:ident) => {
+        impl Interpolate<$S> for $S {
+            #[inline]
+            fn barycentric(values: ($S, $S, $S), weights: Vector3<$S>) -> $S {
+                values.0 * weights.x + values.1 * weights.y + values.2 * weights.z
+            }
+        }
+    };
+}
+
+impl_interpolate_scalar!(f32);
+impl_interpolate_scalar!(f64);
+
 /// A type with a distance function between values.
 ///
 /// Examples are vectors, points, and quaternions.
@@ -232,7 +279,7 @@ where
     /// Returns `true` if the vector is perpendicular (at right angles) to the
     /// other vector.
     fn is_perpendicular(self, other: Self) -> bool {
-        ulps_eq!(Self::dot(self, other), &Self::Scalar::zero())
+        implicit_ulps_eq!(Self::dot(self, other), &Self::Scalar::zero())
     }
 
     /// Returns the squared magnitude.
@@ -268,6 +315,28 @@ where
         self * (magnitude / self.magnitude())
     }
 
+    /// Returns a vector with the same direction, and a magnitude within
+    /// `0.2%` of `1`.
+    ///
+    /// This skips the square root and division of `normalize`, using an
+    /// estimate of the reciprocal square root refined by a step of Newton's
+    /// method, as computed by `fast::rsqrt`. It is meant for inner loops that
+    /// renormalize often and tolerate the error. The zero vector is left as
+    /// it is.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::Vector3;
+    ///
+    /// let v = Vector3::new(3.0f32, 0.0, 4.0).fast_normalize();
+    /// assert!((v.magnitude() - 1.0).abs() < 2e-3);
+    /// assert!((v.x / v.z - 0.75).abs() < 1e-6);
+    /// ```
+    #[inline]
+    fn fast_normalize(self) -> Self {
+        self * fast::rsqrt(self.magnitude2())
+    }
+
     /// Returns the
     /// [vector projection](https://en.wikipedia.org/wiki/Vector_projection)
     /// of the current inner space projected onto the supplied argument.
@@ -400,6 +469,62 @@ where
         Self::from_vec(total_displacement / cast(points.len()).unwrap())
     }
 
+    /// Returns the average position of all points produced by an iterator,
+    /// or `None` if the iterator is empty.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::Point2;
+    ///
+    /// let points = vec![Point2::new(0.0, 0.0), Point2::new(4.0, 2.0)];
+    /// let centroid = Point2::centroid_of(points.into_iter());
+    /// assert_eq!(centroid, Some(Point2::new(2.0, 1.0)));
+    /// ```
+    fn centroid_of<I: IntoIterator<Item = Self>>(points: I) -> Option<Self> {
+        let (count, total_displacement) = points
+            .into_iter()
+            .fold((0usize, Self::Diff::zero()), |(n, acc), p| {
+                (n + 1, acc + p.to_vec())
+            });
+
+        if count == 0 {
+            None
+        } else {
+            Some(Self::from_vec(total_displacement / cast(count).unwrap()))
+        }
+    }
+
+    /// Returns the interval `(min, max)` covered by the points produced by an
+    /// iterator when projected onto `axis`, or `None` if the iterator is
+    /// empty.
+    ///
+    /// The projections are measured in multiples of the length of `axis`, so
+    /// it should usually be normalized.
+    ///
+    /// ```rust
+    /// use cgmath::prelude::*;
+    /// use cgmath::{Point2, Vector2};
+    ///
+    /// let points = vec![Point2::new(1.0, 5.0), Point2::new(-2.0, 3.0)];
+    /// let extent = Point2::extent_along(points.into_iter(), Vector2::unit_y());
+    /// assert_eq!(extent, Some((3.0, 5.0)));
+    /// ```
+    fn extent_along<I: IntoIterator<Item = Self>>(
+        points: I,
+        axis: Self::Diff,
+    ) -> Option<(Self::Scalar, Self::Scalar)> {
+        let mut points = points.into_iter();
+        let first = points.next()?.dot(axis);
+
+        Some(points.fold((first, first), |(min, max), p| {
+            let d = p.dot(axis);
+            (
+                if d < min { d } else { min },
+                if d > max { d } else { max },
+            )
+        }))
+    }
+
     /// This is a weird one, but its useful for plane calculations.
     fn dot(self, v: Self::Diff) -> Self::Scalar;
 }
@@ -544,14 +669,14 @@ where
     /// Test if this matrix is invertible.
     #[inline]
     fn is_invertible(&self) -> bool {
-        ulps_ne!(self.determinant(), &Self::Scalar::zero())
+        implicit_ulps_ne!(self.determinant(), &Self::Scalar::zero())
     }
 
     /// Test if this matrix is the identity matrix. That is, it is diagonal
     /// and every element in the diagonal is one.
     #[inline]
     fn is_identity(&self) -> bool {
-        ulps_eq!(self, &Self::identity())
+        implicit_ulps_eq!(self, &Self::identity())
     }
 
     /// Test if this is a diagonal matrix. That is, every element outside of
@@ -13,12 +13,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use num_traits::cast;
+
 use structure::*;
 
 use approx;
 use matrix::{Matrix2, Matrix3, Matrix4};
-use num::{BaseFloat, BaseNum};
+use num::{BaseFloat, BaseNum, ToF32};
 use point::{Point2, Point3};
+use quaternion::Quaternion;
 use rotation::*;
 use vector::{Vector2, Vector3};
 
@@ -46,6 +49,11 @@ pub trait Transform<P: EuclideanSpace>: Sized {
     /// Transform a point using this transform.
     fn transform_point(&self, point: P) -> P;
 
+    /// Inverse transform a point using this transform.
+    fn inverse_transform_point(&self, point: P) -> Option<P> {
+        self.inverse_transform().map(|inverse| inverse.transform_point(point))
+    }
+
     /// Combine this transform with another, yielding a new transformation
     /// which has the effects of both.
     fn concat(&self, other: &Self) -> Self;
@@ -58,6 +66,98 @@ pub trait Transform<P: EuclideanSpace>: Sized {
     fn concat_self(&mut self, other: &Self) {
         *self = Self::concat(self, other);
     }
+
+    /// Transform a slice of points in-place.
+    #[inline]
+    fn transform_points_in_place(&self, points: &mut [P]) {
+        for point in points {
+            *point = self.transform_point(*point);
+        }
+    }
+
+    /// Transform a slice of vectors in-place.
+    #[inline]
+    fn transform_vectors_in_place(&self, vectors: &mut [P::Diff]) {
+        for vec in vectors {
+            *vec = self.transform_vector(*vec);
+        }
+    }
+}
+
+/// A unit of work transforming a contiguous chunk of points in-place.
+///
+/// This is produced by `transform_chunks`. It is `Send` whenever the
+/// transform is `Sync` and the points are `Send`, which holds for all of the
+/// transforms and points defined in this crate, so it can be handed off to
+/// another thread.
+#[derive(Debug)]
+pub struct TransformChunk<'a, P: 'a, T: 'a> {
+    transform: &'a T,
+    points: &'a mut [P],
+}
+
+impl<'a, P: EuclideanSpace, T: Transform<P>> TransformChunk<'a, P, T> {
+    /// The points that will be transformed by this chunk.
+    #[inline]
+    pub fn points(&self) -> &[P] {
+        self.points
+    }
+
+    /// Transform the points of this chunk.
+    #[inline]
+    pub fn run(self) {
+        self.transform.transform_points_in_place(self.points);
+    }
+}
+
+/// Split `points` into chunks of at most `chunk_size` points and pass a job
+/// transforming each of them to `f`.
+///
+/// This does not spawn any threads by itself. Instead `f` is free to run the
+/// job immediately, or to hand it to a thread pool, which makes it simple to
+/// spread a large batch of transforms across threads:
+///
+/// ```rust
+/// use std::thread;
+/// use cgmath::{transform_chunks, Matrix4, Point3, Vector3};
+///
+/// let matrix = Matrix4::from_translation(Vector3::new(1.0, 0.0, 0.0));
+/// let points = vec![Point3::new(0.0f32, 0.0, 0.0); 10_000];
+///
+/// let handles: Vec<_> = points
+///     .chunks(1024)
+///     .map(|chunk| {
+///         let mut chunk = chunk.to_vec();
+///         thread::spawn(move || {
+///             transform_chunks(&matrix, &mut chunk, 256, |job| job.run());
+///             chunk
+///         })
+///     })
+///     .collect();
+/// let points: Vec<_> = handles
+///     .into_iter()
+///     .flat_map(|handle| handle.join().unwrap())
+///     .collect();
+///
+/// assert!(points.iter().all(|&p| p == Point3::new(1.0, 0.0, 0.0)));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `chunk_size` is zero.
+pub fn transform_chunks<'a, P, T, F>(
+    transform: &'a T,
+    points: &'a mut [P],
+    chunk_size: usize,
+    mut f: F,
+) where
+    P: EuclideanSpace,
+    T: Transform<P>,
+    F: FnMut(TransformChunk<'a, P, T>),
+{
+    for points in points.chunks_mut(chunk_size) {
+        f(TransformChunk { transform, points });
+    }
 }
 
 /// A generic transformation consisting of a rotation,
@@ -69,6 +169,81 @@ pub struct Decomposed<V: VectorSpace, R> {
     pub disp: V,
 }
 
+/// The default decomposed transform is the identity transform, with a unit
+/// scale, no rotation, and no displacement.
+impl<V: VectorSpace, R: One> Default for Decomposed<V, R> {
+    #[inline]
+    fn default() -> Decomposed<V, R> {
+        Decomposed {
+            scale: V::Scalar::one(),
+            rot: R::one(),
+            disp: V::zero(),
+        }
+    }
+}
+
+impl<V: VectorSpace, R: One> Decomposed<V, R> {
+    /// The identity transform, as `Default::default`, to start building a
+    /// transform from with the `with_` methods.
+    ///
+    /// ```rust
+    /// use cgmath::{Decomposed, Deg, Quaternion, Rotation3, Vector3};
+    ///
+    /// let t = Decomposed::identity()
+    ///     .with_translation(Vector3::new(1.0, 2.0, 3.0))
+    ///     .with_rotation(Quaternion::from_angle_y(Deg(90.0)))
+    ///     .with_scale(2.0);
+    /// assert_eq!(t.disp, Vector3::new(1.0, 2.0, 3.0));
+    /// assert_eq!(t.scale, 2.0);
+    /// ```
+    #[inline]
+    pub fn identity() -> Decomposed<V, R> {
+        Decomposed::default()
+    }
+
+    /// A transform translating by `disp`, without rotation or scale.
+    #[inline]
+    pub fn from_translation(disp: V) -> Decomposed<V, R> {
+        Decomposed::identity().with_translation(disp)
+    }
+
+    /// A transform scaling by `scale`, without rotation or translation.
+    #[inline]
+    pub fn from_scale(scale: V::Scalar) -> Decomposed<V, R> {
+        Decomposed::identity().with_scale(scale)
+    }
+}
+
+impl<V: VectorSpace, R> Decomposed<V, R> {
+    /// A transform rotating by `rot`, without scale or translation.
+    #[inline]
+    pub fn from_rotation(rot: R) -> Decomposed<V, R> {
+        Decomposed {
+            scale: V::Scalar::one(),
+            rot,
+            disp: V::zero(),
+        }
+    }
+
+    /// The same transform with its translation replaced by `disp`.
+    #[inline]
+    pub fn with_translation(self, disp: V) -> Decomposed<V, R> {
+        Decomposed { disp, ..self }
+    }
+
+    /// The same transform with its rotation replaced by `rot`.
+    #[inline]
+    pub fn with_rotation(self, rot: R) -> Decomposed<V, R> {
+        Decomposed { rot, ..self }
+    }
+
+    /// The same transform with its scale replaced by `scale`.
+    #[inline]
+    pub fn with_scale(self, scale: V::Scalar) -> Decomposed<V, R> {
+        Decomposed { scale, ..self }
+    }
+}
+
 impl<P: EuclideanSpace, R: Rotation<P>> Transform<P> for Decomposed<P::Diff, R>
 where
     P::Scalar: BaseFloat,
@@ -102,7 +277,7 @@ where
 
     #[inline]
     fn inverse_transform_vector(&self, vec: P::Diff) -> Option<P::Diff> {
-        if ulps_eq!(self.scale, &P::Scalar::zero()) {
+        if implicit_ulps_eq!(self.scale, &P::Scalar::zero()) {
             None
         } else {
             Some(self.rot.invert().rotate_vector(vec / self.scale))
@@ -114,6 +289,16 @@ where
         self.rot.rotate_point(point * self.scale) + self.disp
     }
 
+    #[inline]
+    fn inverse_transform_point(&self, point: P) -> Option<P> {
+        if implicit_ulps_eq!(self.scale, &P::Scalar::zero()) {
+            None
+        } else {
+            let p = self.rot.invert().rotate_point(point - self.disp);
+            Some(p * (P::Scalar::one() / self.scale))
+        }
+    }
+
     fn concat(&self, other: &Decomposed<P::Diff, R>) -> Decomposed<P::Diff, R> {
         Decomposed {
             scale: self.scale * other.scale,
@@ -123,7 +308,7 @@ where
     }
 
     fn inverse_transform(&self) -> Option<Decomposed<P::Diff, R>> {
-        if ulps_eq!(self.scale, &P::Scalar::zero()) {
+        if implicit_ulps_eq!(self.scale, &P::Scalar::zero()) {
             None
         } else {
             let s = P::Scalar::one() / self.scale;
@@ -159,6 +344,168 @@ impl<S: BaseFloat, R: Rotation3<S>> From<Decomposed<Vector3<S>, R>> for Matrix4<
     }
 }
 
+impl<S: BaseFloat, R: Rotation3<S>> Decomposed<Vector3<S>, R> {
+    /// A combined distance between two poses, suitable for reporting pose
+    /// errors or checking the convergence of an iterative solver.
+    ///
+    /// This is the euclidean distance between the two displacements plus the
+    /// geodesic angle between the two rotations (see `rotation_distance`),
+    /// scaled by `angular_weight`. The weight converts radians into the units
+    /// of the displacement, and is typically chosen as the radius of the
+    /// object being posed. The scale factors are not taken into account.
+    pub fn pose_distance(&self, other: &Decomposed<Vector3<S>, R>, angular_weight: S) -> S {
+        let translation = self.disp.distance(other.disp);
+        let rotation = rotation_distance(self.rot, other.rot);
+        translation + rotation.0 * angular_weight
+    }
+}
+
+/// The change between two poses, as computed by `Decomposed::delta`.
+///
+/// The displacement is the difference between the two displacements, and
+/// the rotation takes the previous orientation to the current one, so both
+/// stay small for poses that are close in time, whatever their distance
+/// from the origin.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PoseDelta<S> {
+    /// The current displacement minus the previous one.
+    pub disp: Vector3<S>,
+    /// The rotation taking the previous orientation to the current one.
+    pub rot: Quaternion<S>,
+}
+
+/// A `PoseDelta` quantized for transmission, as computed by
+/// `PoseDelta::quantize`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuantizedPoseDelta {
+    /// The displacement, in multiples of the quantization step.
+    pub disp: [i32; 3],
+    /// The rotation, packed with `Quaternion::pack_smallest_three_48`.
+    pub rot: u64,
+}
+
+impl<S: BaseFloat> PoseDelta<S> {
+    /// Quantize the delta, rounding its displacement to multiples of `step`
+    /// and packing its rotation into 48 bits.
+    ///
+    /// To keep the quantization errors from accumulating over a stream of
+    /// deltas, the sender should compute each delta from the pose that the
+    /// receiver reconstructed, rather than from its own exact pose.
+    pub fn quantize(&self, step: S) -> QuantizedPoseDelta {
+        let q = |x: S| cast((x / step).round()).unwrap();
+        QuantizedPoseDelta {
+            disp: [q(self.disp.x), q(self.disp.y), q(self.disp.z)],
+            rot: self.rot.pack_smallest_three_48(),
+        }
+    }
+}
+
+impl QuantizedPoseDelta {
+    /// Recover the delta quantized with the same `step`.
+    pub fn dequantize<S: BaseFloat>(&self, step: S) -> PoseDelta<S> {
+        let d = |i: i32| cast::<_, S>(i).unwrap() * step;
+        PoseDelta {
+            disp: Vector3::new(d(self.disp[0]), d(self.disp[1]), d(self.disp[2])),
+            rot: Quaternion::unpack_smallest_three_48(self.rot),
+        }
+    }
+}
+
+impl<S: BaseFloat, R: Rotation3<S> + From<Quaternion<S>>> Decomposed<Vector3<S>, R> {
+    /// Decompose a matrix into a uniform scale, a rotation and a
+    /// displacement, which is the inverse of the conversion of `Decomposed`
+    /// into `Matrix4`.
+    ///
+    /// This returns `None` when the matrix is not such a transform, for
+    /// example when it is projective, mirrors, or scales unevenly along its
+    /// axes. Its bottom row, and the product of its normalized rotation with
+    /// its transpose, must be within `epsilon` of those of a similarity
+    /// transform in every component.
+    ///
+    /// ```rust
+    /// use cgmath::{Decomposed, Deg, Matrix4, Quaternion, Rotation3, Vector3};
+    ///
+    /// let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+    ///     * Matrix4::from_angle_y(Deg(30.0f64))
+    ///     * Matrix4::from_scale(2.0);
+    /// let d: Decomposed<Vector3<f64>, Quaternion<f64>> = Decomposed::from_matrix4(&m, 1e-12).unwrap();
+    /// assert!((d.scale - 2.0).abs() < 1e-12);
+    /// assert_eq!(d.disp, Vector3::new(1.0, 2.0, 3.0));
+    ///
+    /// assert!(Decomposed::<Vector3<f64>, Quaternion<f64>>::from_matrix4(
+    ///     &Matrix4::from_nonuniform_scale(1.0, 2.0, 1.0),
+    ///     1e-12,
+    /// ).is_none());
+    /// ```
+    pub fn from_matrix4(m: &Matrix4<S>, epsilon: S) -> Option<Decomposed<Vector3<S>, R>> {
+        use approx::AbsDiffEq;
+
+        let bottom = Vector3::zero().extend(S::one());
+        if !m.row(3).abs_diff_eq(&bottom, epsilon) {
+            return None;
+        }
+        let linear = Matrix3::from_cols(m.x.truncate(), m.y.truncate(), m.z.truncate());
+        let det = linear.determinant();
+        if det <= S::zero() {
+            return None;
+        }
+        let scale = det.cbrt();
+        let rotation = linear / scale;
+        let orthogonality = rotation.transpose() * rotation;
+        if !orthogonality.abs_diff_eq(&Matrix3::identity(), epsilon) {
+            return None;
+        }
+        Some(Decomposed {
+            scale,
+            rot: R::from(Quaternion::from(rotation).normalize()),
+            disp: m.w.truncate(),
+        })
+    }
+
+    /// The change from `previous` to this pose, which `apply_delta` turns
+    /// back into this pose. The scale is not part of the delta.
+    ///
+    /// ```rust
+    /// use cgmath::{Decomposed, Deg, Quaternion, Rotation3, Vector3};
+    ///
+    /// let previous = Decomposed {
+    ///     scale: 1.0,
+    ///     rot: Quaternion::from_angle_y(Deg(10.0f64)),
+    ///     disp: Vector3::new(100.0, 0.0, -40.0),
+    /// };
+    /// let current = Decomposed {
+    ///     rot: Quaternion::from_angle_y(Deg(12.0)),
+    ///     disp: Vector3::new(100.5, 0.0, -40.25),
+    ///     ..previous
+    /// };
+    ///
+    /// let packet = current.delta(&previous).quantize(1.0 / 1024.0);
+    /// let received = previous.apply_delta(&packet.dequantize(1.0 / 1024.0));
+    /// assert_eq!(received.disp, current.disp);
+    /// assert!(received.rot.angle_between(&current.rot).0 < 1e-4);
+    /// ```
+    pub fn delta(&self, previous: &Decomposed<Vector3<S>, R>) -> PoseDelta<S> {
+        let current: Quaternion<S> = self.rot.into();
+        let previous_rot: Quaternion<S> = previous.rot.into();
+        PoseDelta {
+            disp: self.disp - previous.disp,
+            rot: current * previous_rot.conjugate(),
+        }
+    }
+
+    /// Apply a delta computed by `delta` to this pose, keeping its scale.
+    pub fn apply_delta(&self, delta: &PoseDelta<S>) -> Decomposed<Vector3<S>, R> {
+        let rot: Quaternion<S> = self.rot.into();
+        Decomposed {
+            scale: self.scale,
+            rot: R::from((delta.rot * rot).normalize()),
+            disp: self.disp + delta.disp,
+        }
+    }
+}
+
 impl<S: BaseFloat, R: Rotation2<S>> Transform2<S> for Decomposed<Vector2<S>, R> {}
 
 impl<S: BaseFloat, R: Rotation3<S>> Transform3<S> for Decomposed<Vector3<S>, R> {}
@@ -369,3 +716,19 @@ mod serde_de {
         }
     }
 }
+
+impl_precision_conversions!(
+    Decomposed { scale, rot, disp },
+    Decomposed<Vector2<f32>, Basis2<f32>>,
+    Decomposed<Vector2<f64>, Basis2<f64>>
+);
+impl_precision_conversions!(
+    Decomposed { scale, rot, disp },
+    Decomposed<Vector3<f32>, Basis3<f32>>,
+    Decomposed<Vector3<f64>, Basis3<f64>>
+);
+impl_precision_conversions!(
+    Decomposed { scale, rot, disp },
+    Decomposed<Vector3<f32>, Quaternion<f32>>,
+    Decomposed<Vector3<f64>, Quaternion<f64>>
+);
@@ -0,0 +1,192 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::marker::PhantomData;
+
+use structure::*;
+
+use matrix::{Matrix3, Matrix4};
+use num::BaseFloat;
+use point::Point3;
+use rotation::Rotation3;
+use transform::{Decomposed, Transform};
+use vector::Vector3;
+
+/// A stack of transforms, in the style of the matrix stacks of legacy
+/// OpenGL.
+///
+/// The stack has a current transform, which the convenience methods like
+/// `translate` and `rotate` modify by appending a transform applied before
+/// it, as `glTranslate` and `glRotate` do. `push` saves the current
+/// transform, and `pop` restores the last saved one.
+///
+/// ```rust
+/// use cgmath::{Basis3, Deg, MatrixStack, Point3, Vector3};
+/// use cgmath::prelude::*;
+///
+/// let mut stack = MatrixStack::new();
+/// stack.translate(Vector3::new(10.0, 0.0, 0.0f64));
+/// stack.push();
+/// stack.rotate(Basis3::from_angle_z(Deg(90.0)));
+/// stack.scale(2.0);
+/// let tip = stack.current().transform_point(Point3::new(1.0, 0.0, 0.0));
+/// assert!((tip - Point3::new(10.0, 2.0, 0.0)).magnitude() < 1e-12);
+/// stack.pop();
+/// assert_eq!(stack.current().transform_point(Point3::origin()), Point3::new(10.0, 0.0, 0.0));
+/// ```
+#[derive(Clone, Debug)]
+pub struct TransformStack<P, T> {
+    current: T,
+    saved: Vec<T>,
+    space: PhantomData<P>,
+}
+
+/// A stack of transformation matrices.
+pub type MatrixStack<S> = TransformStack<Point3<S>, Matrix4<S>>;
+
+impl<P: EuclideanSpace, T: Transform<P> + Clone> TransformStack<P, T> {
+    /// Create a stack whose current transform is the identity, with nothing
+    /// saved.
+    pub fn new() -> TransformStack<P, T> {
+        TransformStack::from_transform(T::one())
+    }
+
+    /// Create a stack with the given current transform, with nothing saved.
+    pub fn from_transform(transform: T) -> TransformStack<P, T> {
+        TransformStack {
+            current: transform,
+            saved: Vec::new(),
+            space: PhantomData,
+        }
+    }
+
+    /// The current transform.
+    #[inline]
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// The number of saved transforms.
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.saved.len()
+    }
+
+    /// Save a copy of the current transform.
+    #[inline]
+    pub fn push(&mut self) {
+        self.saved.push(self.current.clone());
+    }
+
+    /// Restore the last saved transform, and return the current one. If no
+    /// transform was saved, this returns `None` and keeps the current
+    /// transform.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let restored = self.saved.pop()?;
+        Some(::std::mem::replace(&mut self.current, restored))
+    }
+
+    /// Run `f` between a `push` and a `pop`, so that the changes it makes
+    /// to the current transform are undone afterwards.
+    pub fn scoped<R, F: FnOnce(&mut TransformStack<P, T>) -> R>(&mut self, f: F) -> R {
+        let depth = self.depth();
+        self.push();
+        let result = f(self);
+        while self.depth() > depth {
+            self.pop();
+        }
+        result
+    }
+
+    /// Replace the current transform.
+    #[inline]
+    pub fn load(&mut self, transform: T) {
+        self.current = transform;
+    }
+
+    /// Replace the current transform with the identity.
+    #[inline]
+    pub fn load_identity(&mut self) {
+        self.current = T::one();
+    }
+
+    /// Append `transform` to the current transform, so that it applies
+    /// first.
+    #[inline]
+    pub fn multiply(&mut self, transform: &T) {
+        self.current = self.current.concat(transform);
+    }
+}
+
+impl<P: EuclideanSpace, T: Transform<P> + Clone> Default for TransformStack<P, T> {
+    fn default() -> TransformStack<P, T> {
+        TransformStack::new()
+    }
+}
+
+impl<S: BaseFloat> TransformStack<Point3<S>, Matrix4<S>> {
+    /// Append a translation to the current transform.
+    pub fn translate(&mut self, v: Vector3<S>) {
+        self.multiply(&Matrix4::from_translation(v));
+    }
+
+    /// Append a rotation to the current transform.
+    pub fn rotate<R: Rotation3<S>>(&mut self, rotation: R) {
+        let m: Matrix3<S> = rotation.into();
+        self.multiply(&m.into());
+    }
+
+    /// Append a uniform scale to the current transform.
+    pub fn scale(&mut self, factor: S) {
+        self.multiply(&Matrix4::from_scale(factor));
+    }
+
+    /// Append a non-uniform scale to the current transform.
+    pub fn scale_nonuniform(&mut self, factors: Vector3<S>) {
+        self.multiply(&Matrix4::from_nonuniform_scale(
+            factors.x, factors.y, factors.z,
+        ));
+    }
+}
+
+impl<S: BaseFloat, R: Rotation3<S>> TransformStack<Point3<S>, Decomposed<Vector3<S>, R>> {
+    /// Append a translation to the current transform.
+    pub fn translate(&mut self, v: Vector3<S>) {
+        self.multiply(&Decomposed {
+            scale: S::one(),
+            rot: R::one(),
+            disp: v,
+        });
+    }
+
+    /// Append a rotation to the current transform.
+    pub fn rotate(&mut self, rotation: R) {
+        self.multiply(&Decomposed {
+            scale: S::one(),
+            rot: rotation,
+            disp: Vector3::zero(),
+        });
+    }
+
+    /// Append a uniform scale to the current transform.
+    pub fn scale(&mut self, factor: S) {
+        self.multiply(&Decomposed {
+            scale: factor,
+            rot: R::one(),
+            disp: Vector3::zero(),
+        });
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use num_traits::cast;
+
+use structure::*;
+
+use aabb::Aabb3;
+use approx::AbsDiffEq;
+use num::BaseFloat;
+use point::{Point2, Point3};
+use vector::Vector3;
+
+/// A triangle in two-dimensional space, given by its three vertices.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangle2<S> {
+    /// The first vertex.
+    pub a: Point2<S>,
+    /// The second vertex.
+    pub b: Point2<S>,
+    /// The third vertex.
+    pub c: Point2<S>,
+}
+
+/// A triangle in three-dimensional space, given by its three vertices.
+///
+/// Barycentric coordinates are stored in a `Vector3`, whose `x`, `y` and `z`
+/// components are the weights of `a`, `b` and `c` respectively.
+///
+/// ```rust
+/// use cgmath::{Point3, Triangle3, Vector2, Vector3};
+///
+/// let triangle = Triangle3::new(
+///     Point3::new(0.0, 0.0, 0.0),
+///     Point3::new(2.0, 0.0, 0.0),
+///     Point3::new(0.0, 2.0, 0.0f64),
+/// );
+///
+/// // Shading a hit point: find its barycentric coordinates, then blend the
+/// // texture coordinates stored at the vertices.
+/// let bary = triangle.barycentric(Point3::new(1.0, 0.5, 0.0)).unwrap();
+/// let uv = triangle.interpolate(
+///     bary,
+///     (Vector2::new(0.0, 0.0), Vector2::new(1.0, 0.0), Vector2::new(0.0, 1.0)),
+/// );
+/// assert_eq!(bary, Vector3::new(0.25, 0.5, 0.25));
+/// assert_eq!(uv, Vector2::new(0.5, 0.25));
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Triangle3<S> {
+    /// The first vertex.
+    pub a: Point3<S>,
+    /// The second vertex.
+    pub b: Point3<S>,
+    /// The third vertex.
+    pub c: Point3<S>,
+}
+
+/// Solve for the barycentric coordinates of `p - a` in the basis `v0`, `v1`
+/// from the Gram matrix of the edges. Returns `None` when the edges are
+/// (nearly) parallel.
+#[inline]
+fn solve_barycentric<V: InnerSpace>(v0: V, v1: V, v2: V) -> Option<Vector3<V::Scalar>>
+where
+    V::Scalar: BaseFloat,
+{
+    let d00 = v0.magnitude2();
+    let d01 = v0.dot(v1);
+    let d11 = v1.magnitude2();
+    let d20 = v2.dot(v0);
+    let d21 = v2.dot(v1);
+    let denom = d00 * d11 - d01 * d01;
+    let (zero, epsilon) = (V::Scalar::zero(), V::Scalar::default_epsilon() * d00 * d11);
+    if denom <= zero || implicit_ulps_eq!(denom, zero, epsilon = epsilon) {
+        return None;
+    }
+    let v = (d11 * d20 - d01 * d21) / denom;
+    let w = (d00 * d21 - d01 * d20) / denom;
+    Some(Vector3::new(V::Scalar::one() - v - w, v, w))
+}
+
+macro_rules! impl_triangle {
+    ($TriangleN:ident, $PointN:ident) => {
+        impl<S: BaseFloat> $TriangleN<S> {
+            /// Construct a triangle from its three vertices.
+            #[inline]
+            pub fn new(a: $PointN<S>, b: $PointN<S>, c: $PointN<S>) -> $TriangleN<S> {
+                $TriangleN { a, b, c }
+            }
+
+            /// The centroid of the triangle.
+            #[inline]
+            pub fn centroid(&self) -> $PointN<S> {
+                let third: S = cast(1.0 / 3.0).unwrap();
+                self.point_at(Vector3::new(third, third, third))
+            }
+
+            /// The point with the barycentric coordinates `bary`.
+            #[inline]
+            pub fn point_at(&self, bary: Vector3<S>) -> $PointN<S> {
+                Interpolate::barycentric((self.a, self.b, self.c), bary)
+            }
+
+            /// Blend the values attached to the vertices `a`, `b` and `c`
+            /// with the barycentric coordinates `bary`, for example to find
+            /// the normal, texture coordinates or color at a hit point.
+            #[inline]
+            pub fn interpolate<T: Interpolate<S>>(&self, bary: Vector3<S>, values: (T, T, T)) -> T {
+                T::barycentric(values, bary)
+            }
+
+            /// The barycentric coordinates of `p` with respect to the
+            /// triangle, or `None` if the triangle is degenerate.
+            ///
+            /// The coordinates may be negative when `p` lies outside the
+            /// triangle.
+            #[inline]
+            pub fn barycentric(&self, p: $PointN<S>) -> Option<Vector3<S>> {
+                solve_barycentric(self.b - self.a, self.c - self.a, p - self.a)
+            }
+
+            /// Test whether the barycentric coordinates `bary` describe a
+            /// point inside the triangle or on its boundary.
+            #[inline]
+            pub fn contains_barycentric(bary: Vector3<S>) -> bool {
+                bary.x >= S::zero() && bary.y >= S::zero() && bary.z >= S::zero()
+            }
+        }
+    };
+}
+
+impl_triangle!(Triangle2, Point2);
+impl_triangle!(Triangle3, Point3);
+
+impl<S: BaseFloat> Triangle2<S> {
+    /// The signed area of the triangle, positive when the vertices are in
+    /// counter-clockwise order.
+    #[inline]
+    pub fn signed_area(&self) -> S {
+        let half: S = cast(0.5).unwrap();
+        (self.b - self.a).perp_dot(self.c - self.a) * half
+    }
+
+    /// The area of the triangle.
+    #[inline]
+    pub fn area(&self) -> S {
+        self.signed_area().abs()
+    }
+}
+
+impl<S: BaseFloat> Triangle3<S> {
+    /// The cross product of the edges `b - a` and `c - a`, which is
+    /// perpendicular to the triangle and twice as long as its area.
+    #[inline]
+    pub fn scaled_normal(&self) -> Vector3<S> {
+        (self.b - self.a).cross(self.c - self.a)
+    }
+
+    /// The unit normal of the triangle, following the right-hand rule over
+    /// `a`, `b`, `c`.
+    #[inline]
+    pub fn normal(&self) -> Vector3<S> {
+        self.scaled_normal().normalize()
+    }
+
+    /// The area of the triangle.
+    #[inline]
+    pub fn area(&self) -> S {
+        let half: S = cast(0.5).unwrap();
+        self.scaled_normal().magnitude() * half
+    }
+
+    /// The smallest axis-aligned box containing the triangle.
+    #[inline]
+    pub fn to_aabb(&self) -> Aabb3<S> {
+        Aabb3::from_point(self.a).grow(self.b).grow(self.c)
+    }
+
+    /// Test whether the triangle and the box overlap, or touch, with the
+    /// separating axis test of "Fast 3D Triangle-Box Overlap Testing" by
+    /// Tomas Akenine-Möller.
+    ///
+    /// ```rust
+    /// use cgmath::{Aabb3, Point3, Triangle3};
+    ///
+    /// let voxel = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+    /// let across = Triangle3::new(
+    ///     Point3::new(-1.0, 0.5, -1.0),
+    ///     Point3::new(2.0, 0.5, -1.0),
+    ///     Point3::new(0.5, 0.5, 2.0f64),
+    /// );
+    /// assert!(across.intersects_aabb(&voxel));
+    ///
+    /// // Its bounds overlap the box, but the triangle passes by a corner.
+    /// let by_corner = Triangle3::new(
+    ///     Point3::new(1.6, 0.0, 0.0),
+    ///     Point3::new(0.0, 1.6, 0.0),
+    ///     Point3::new(0.0, 0.0, 4.0f64),
+    /// );
+    /// assert!(!by_corner.intersects_aabb(&Aabb3::new(
+    ///     Point3::new(1.0, 1.0, 0.0),
+    ///     Point3::new(2.0, 2.0, 1.0),
+    /// )));
+    /// ```
+    pub fn intersects_aabb(&self, aabb: &Aabb3<S>) -> bool {
+        let center = aabb.center();
+        let half = aabb.dim() * cast(0.5).unwrap();
+        let v = [self.a - center, self.b - center, self.c - center];
+        let edges = [v[1] - v[0], v[2] - v[1], v[0] - v[2]];
+        let separates = |axis: Vector3<S>| {
+            let p = [v[0].dot(axis), v[1].dot(axis), v[2].dot(axis)];
+            let r = half.x * axis.x.abs() + half.y * axis.y.abs() + half.z * axis.z.abs();
+            p[0].min(p[1]).min(p[2]) > r || p[0].max(p[1]).max(p[2]) < -r
+        };
+        let units = [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()];
+        // The normals of the box, then of the triangle, then the cross
+        // products of their edges.
+        if units.iter().any(|&u| separates(u)) || separates(edges[0].cross(edges[1])) {
+            return false;
+        }
+        !units
+            .iter()
+            .any(|&u| edges.iter().any(|&e| separates(u.cross(e))))
+    }
+
+    /// The segment along which the triangle crosses another one, with the
+    /// interval method of "A Fast Triangle-Triangle Intersection Test" by
+    /// Tomas Möller, or `None` if they do not meet.
+    ///
+    /// Triangles touching at a single point give a segment of length zero.
+    /// Coplanar triangles, which overlap over an area rather than along a
+    /// segment, also give `None`.
+    ///
+    /// ```rust
+    /// use cgmath::{Point3, Triangle3};
+    ///
+    /// let floor = Triangle3::new(
+    ///     Point3::new(-2.0, -2.0, 0.0),
+    ///     Point3::new(2.0, -2.0, 0.0),
+    ///     Point3::new(0.0, 2.0, 0.0),
+    /// );
+    /// let wall = Triangle3::new(
+    ///     Point3::new(-1.0, 0.0, -1.0),
+    ///     Point3::new(1.0, 0.0, -1.0),
+    ///     Point3::new(0.0, 0.0, 1.0f64),
+    /// );
+    /// let (p, q) = floor.intersection(&wall).unwrap();
+    /// assert_eq!(p, Point3::new(-0.5, 0.0, 0.0));
+    /// assert_eq!(q, Point3::new(0.5, 0.0, 0.0));
+    /// ```
+    pub fn intersection(&self, other: &Triangle3<S>) -> Option<(Point3<S>, Point3<S>)> {
+        let (n1, n2) = (self.scaled_normal(), other.scaled_normal());
+        // Both triangles cross the line where the planes meet, and overlap
+        // where their intervals along that line do.
+        let direction = n1.cross(n2);
+        let first = plane_crossing(self, n2, n2.dot(other.a.to_vec()), direction)?;
+        let second = plane_crossing(other, n1, n1.dot(self.a.to_vec()), direction)?;
+        let t = |p: Point3<S>| direction.dot(p.to_vec());
+        let start = if t(first.0) > t(second.0) {
+            first.0
+        } else {
+            second.0
+        };
+        let end = if t(first.1) < t(second.1) {
+            first.1
+        } else {
+            second.1
+        };
+        if t(start) > t(end) {
+            None
+        } else {
+            Some((start, end))
+        }
+    }
+}
+
+/// The segment where a triangle crosses the plane `normal · p = offset`,
+/// with its endpoints ordered along `direction`, or `None` if the triangle
+/// lies on one side of the plane or in it.
+fn plane_crossing<S: BaseFloat>(
+    triangle: &Triangle3<S>,
+    normal: Vector3<S>,
+    offset: S,
+    direction: Vector3<S>,
+) -> Option<(Point3<S>, Point3<S>)> {
+    let vertices = [triangle.a, triangle.b, triangle.c];
+    let d = [
+        normal.dot(vertices[0].to_vec()) - offset,
+        normal.dot(vertices[1].to_vec()) - offset,
+        normal.dot(vertices[2].to_vec()) - offset,
+    ];
+    let mut points = Vec::with_capacity(3);
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+        if d[i] == S::zero() {
+            points.push(vertices[i]);
+        } else if d[i] * d[j] < S::zero() {
+            let t = d[i] / (d[i] - d[j]);
+            points.push(vertices[i] + (vertices[j] - vertices[i]) * t);
+        }
+    }
+    if points.is_empty() || d.iter().all(|&d| d == S::zero()) {
+        return None;
+    }
+    let t = |p: &Point3<S>| direction.dot(p.to_vec());
+    let mut ends = (points[0], points[0]);
+    for p in &points[1..] {
+        if t(p) < t(&ends.0) {
+            ends.0 = *p;
+        }
+        if t(p) > t(&ends.1) {
+            ends.1 = *p;
+        }
+    }
+    Some(ends)
+}
+
+impl<S: BaseFloat> From<Triangle2<S>> for Triangle3<S> {
+    /// Embed the triangle in the `z = 0` plane.
+    #[inline]
+    fn from(t: Triangle2<S>) -> Triangle3<S> {
+        Triangle3::new(
+            t.a.extend(S::zero()),
+            t.b.extend(S::zero()),
+            t.c.extend(S::zero()),
+        )
+    }
+}
@@ -0,0 +1,687 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Triangulation of two-dimensional point sets and polygons, and the Voronoi
+//! diagrams of point sets.
+//!
+//! ```rust
+//! use cgmath::triangulation::delaunay;
+//! use cgmath::Point2;
+//!
+//! let points = [
+//!     Point2::new(0.0, 0.0),
+//!     Point2::new(2.0, 0.0),
+//!     Point2::new(1.0, 3.0),
+//!     Point2::new(1.0, 1.0),
+//! ];
+//!
+//! // The point in the middle splits the outer triangle in three.
+//! let triangles = delaunay(&points);
+//! assert_eq!(triangles.len(), 3);
+//! assert!(triangles.iter().all(|t| t.contains(&3)));
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use structure::*;
+
+use aabb::Aabb2;
+use num::BaseFloat;
+use point::Point2;
+use polygon::signed_area;
+use predicates::{in_triangle, incircle, orient2d};
+
+/// The vertex at infinity, shared by the triangles on the outside of the
+/// convex hull.
+const GHOST: usize = !0;
+
+#[inline]
+fn lexicographic<S: BaseFloat>(a: Point2<S>, b: Point2<S>) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+/// An incremental Bowyer–Watson triangulation.
+///
+/// Triangles are stored in counterclockwise order. Each edge of the convex
+/// hull is also covered by a ghost triangle joining it to the vertex at
+/// infinity, which is always stored last, so that points outside of the hull
+/// are inserted in exactly the same way as points inside of it.
+struct Delaunay<'a, S: 'a> {
+    points: &'a [Point2<S>],
+    triangles: Vec<[usize; 3]>,
+    alive: Vec<bool>,
+    edges: HashMap<(usize, usize), usize>,
+    last: usize,
+}
+
+impl<'a, S: BaseFloat> Delaunay<'a, S> {
+    fn new(points: &'a [Point2<S>], a: usize, b: usize, c: usize) -> Delaunay<'a, S> {
+        let (b, c) = if orient2d(points[a], points[b], points[c]) > S::zero() {
+            (b, c)
+        } else {
+            (c, b)
+        };
+
+        let mut delaunay = Delaunay {
+            points,
+            triangles: Vec::new(),
+            alive: Vec::new(),
+            edges: HashMap::new(),
+            last: 0,
+        };
+        delaunay.add([a, b, c]);
+        delaunay.add([b, a, GHOST]);
+        delaunay.add([c, b, GHOST]);
+        delaunay.add([a, c, GHOST]);
+        delaunay
+    }
+
+    fn add(&mut self, triangle: [usize; 3]) -> usize {
+        let index = self.triangles.len();
+        for k in 0..3 {
+            self.edges
+                .insert((triangle[k], triangle[(k + 1) % 3]), index);
+        }
+        self.triangles.push(triangle);
+        self.alive.push(true);
+        index
+    }
+
+    fn remove(&mut self, index: usize) {
+        let triangle = self.triangles[index];
+        for k in 0..3 {
+            self.edges.remove(&(triangle[k], triangle[(k + 1) % 3]));
+        }
+        self.alive[index] = false;
+    }
+
+    #[inline]
+    fn neighbor(&self, u: usize, v: usize) -> usize {
+        self.edges[&(v, u)]
+    }
+
+    /// Test whether a point is strictly inside the circumcircle of a
+    /// triangle. For a ghost triangle, this is the open half-plane on the
+    /// outside of its hull edge, together with the inside of the edge itself.
+    fn conflicts(&self, index: usize, p: Point2<S>) -> bool {
+        let [a, b, c] = self.triangles[index];
+        let (a, b) = (self.points[a], self.points[b]);
+        if c == GHOST {
+            let orientation = orient2d(a, b, p);
+            orientation > S::zero()
+                || (orientation.is_zero()
+                    && lexicographic(a, p) != lexicographic(b, p)
+                    && lexicographic(a, p) != Ordering::Equal
+                    && lexicographic(b, p) != Ordering::Equal)
+        } else {
+            incircle(a, b, self.points[c], p) > S::zero()
+        }
+    }
+
+    /// Walk from the last inserted triangle towards a point, returning either
+    /// the real triangle that contains it or a ghost triangle that can see it.
+    fn locate(&self, p: Point2<S>) -> usize {
+        let mut index = self.last;
+        'walk: loop {
+            let triangle = self.triangles[index];
+            if triangle[2] == GHOST {
+                return index;
+            }
+            for k in 0..3 {
+                let (u, v) = (triangle[k], triangle[(k + 1) % 3]);
+                if orient2d(self.points[u], self.points[v], p) < S::zero() {
+                    index = self.neighbor(u, v);
+                    continue 'walk;
+                }
+            }
+            return index;
+        }
+    }
+
+    fn insert(&mut self, vertex: usize) {
+        let p = self.points[vertex];
+        let seed = self.locate(p);
+        if !self.conflicts(seed, p) {
+            // The point duplicates an existing vertex.
+            return;
+        }
+
+        let mut in_cavity = HashMap::new();
+        in_cavity.insert(seed, true);
+        let mut stack = vec![seed];
+        let mut cavity = vec![seed];
+        let mut boundary = Vec::new();
+        while let Some(index) = stack.pop() {
+            let triangle = self.triangles[index];
+            for k in 0..3 {
+                let (u, v) = (triangle[k], triangle[(k + 1) % 3]);
+                let neighbor = self.neighbor(u, v);
+                let conflicts = match in_cavity.get(&neighbor) {
+                    Some(&conflicts) => conflicts,
+                    None => {
+                        let conflicts = self.conflicts(neighbor, p);
+                        in_cavity.insert(neighbor, conflicts);
+                        if conflicts {
+                            stack.push(neighbor);
+                            cavity.push(neighbor);
+                        }
+                        conflicts
+                    }
+                };
+                if !conflicts {
+                    boundary.push((u, v));
+                }
+            }
+        }
+
+        for index in cavity {
+            self.remove(index);
+        }
+        for (u, v) in boundary {
+            if u == GHOST {
+                self.add([v, vertex, GHOST]);
+            } else if v == GHOST {
+                self.add([vertex, u, GHOST]);
+            } else {
+                self.last = self.add([u, v, vertex]);
+            }
+        }
+    }
+
+    fn into_triangles(self) -> Vec<[usize; 3]> {
+        let alive = self.alive;
+        self.triangles
+            .into_iter()
+            .zip(alive)
+            .filter(|&(triangle, alive)| alive && triangle[2] != GHOST)
+            .map(|(triangle, _)| triangle)
+            .collect()
+    }
+}
+
+/// Compute the Delaunay triangulation of a set of points.
+///
+/// Returns the triangles as triples of indices into `points`, each in
+/// counterclockwise order, covering the convex hull of the points. No point
+/// lies strictly inside the circumcircle of any triangle. When four or more
+/// points are cocircular, as on a regular grid, any of the valid
+/// triangulations of them may be returned.
+///
+/// The construction uses the exact predicates from the `predicates` module,
+/// so it is not affected by rounding errors. Duplicate points are only used
+/// once, and if all of the points are collinear there are no triangles.
+pub fn delaunay<S: BaseFloat>(points: &[Point2<S>]) -> Vec<[usize; 3]> {
+    // Inserting the points in sorted order keeps every walk short, since each
+    // new point is next to the last one.
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&i, &j| lexicographic(points[i], points[j]));
+
+    let first = match order.first() {
+        Some(&first) => first,
+        None => return Vec::new(),
+    };
+    let second = match order.iter().position(|&i| points[i] != points[first]) {
+        Some(second) => second,
+        None => return Vec::new(),
+    };
+    let third = match order[second + 1..]
+        .iter()
+        .position(|&i| !orient2d(points[first], points[order[second]], points[i]).is_zero())
+    {
+        Some(third) => second + 1 + third,
+        None => return Vec::new(),
+    };
+
+    let mut delaunay = Delaunay::new(points, first, order[second], order[third]);
+    for (position, &vertex) in order.iter().enumerate() {
+        if position != 0 && position != second && position != third {
+            delaunay.insert(vertex);
+        }
+    }
+    delaunay.into_triangles()
+}
+
+/// Compute the Voronoi diagram of a set of points, clipped to `bounds`.
+///
+/// Returns the cell of each point, the region of `bounds` closer to it
+/// than to any other point, as a convex polygon in counterclockwise order.
+/// The cells are built from the neighbors of each point in the Delaunay
+/// triangulation, so they tile `bounds` without gaps. Duplicate points get
+/// the same cell, and points with no part of their cell inside `bounds`
+/// get an empty one.
+///
+/// ```rust
+/// use cgmath::triangulation::voronoi;
+/// use cgmath::{Aabb2, Point2};
+///
+/// let bounds = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(4.0, 2.0));
+/// let cells = voronoi(&[Point2::new(1.0, 1.0), Point2::new(3.0, 1.0)], &bounds);
+/// assert_eq!(
+///     cells[0],
+///     vec![
+///         Point2::new(0.0, 0.0),
+///         Point2::new(2.0, 0.0),
+///         Point2::new(2.0, 2.0),
+///         Point2::new(0.0, 2.0),
+///     ]
+/// );
+/// ```
+pub fn voronoi<S: BaseFloat>(points: &[Point2<S>], bounds: &Aabb2<S>) -> Vec<Vec<Point2<S>>> {
+    let mut neighbors = vec![Vec::new(); points.len()];
+    let triangles = delaunay(points);
+    for t in &triangles {
+        for k in 0..3 {
+            let (a, b) = (t[k], t[(k + 1) % 3]);
+            // Each inner edge is seen from both sides, but hull edges once.
+            if !neighbors[a].contains(&b) {
+                neighbors[a].push(b);
+                neighbors[b].push(a);
+            }
+        }
+    }
+    // Without triangles, the points are collinear, and each is next to the
+    // previous and next distinct ones along the line.
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&i, &j| lexicographic(points[i], points[j]));
+    if triangles.is_empty() {
+        let mut line = order.clone();
+        line.dedup_by(|&mut i, &mut j| points[i] == points[j]);
+        for pair in line.windows(2) {
+            neighbors[pair[0]].push(pair[1]);
+            neighbors[pair[1]].push(pair[0]);
+        }
+    }
+
+    let mut cells: Vec<Vec<Point2<S>>> = points
+        .iter()
+        .zip(&neighbors)
+        .map(|(&p, neighbors)| {
+            let mut cell = bounds.corners().to_vec();
+            for &q in neighbors {
+                cell = clip_half_plane(&cell, p, points[q]);
+            }
+            cell
+        })
+        .collect();
+    // The duplicates left out are sorted next to the point standing for
+    // them.
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && points[order[end]] == points[order[start]] {
+            end += 1;
+        }
+        let run = &order[start..end];
+        if let Some(&used) = run.iter().find(|&&i| !neighbors[i].is_empty()) {
+            for &i in run {
+                if neighbors[i].is_empty() {
+                    cells[i] = cells[used].clone();
+                }
+            }
+        }
+        start = end;
+    }
+    cells
+}
+
+/// Clip a convex polygon to the points at least as close to `p` as to `q`.
+fn clip_half_plane<S: BaseFloat>(
+    polygon: &[Point2<S>],
+    p: Point2<S>,
+    q: Point2<S>,
+) -> Vec<Point2<S>> {
+    let two = S::one() + S::one();
+    let normal = q - p;
+    let middle = Point2::new((p.x + q.x) / two, (p.y + q.y) / two);
+    let side = |v: Point2<S>| (v - middle).dot(normal);
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        let (sa, sb) = (side(a), side(b));
+        if sa <= S::zero() {
+            clipped.push(a);
+        }
+        if (sa < S::zero() && sb > S::zero()) || (sa > S::zero() && sb < S::zero()) {
+            clipped.push(a + (b - a) * (sa / (sa - sb)));
+        }
+    }
+    clipped
+}
+
+/// The center of the circle through the three points, or `None` if they
+/// are collinear.
+pub(crate) fn circumcenter<S: BaseFloat>(
+    a: Point2<S>,
+    b: Point2<S>,
+    c: Point2<S>,
+) -> Option<Point2<S>> {
+    let (ab, ac) = (b - a, c - a);
+    let d = (ab.x * ac.y - ab.y * ac.x) * (S::one() + S::one());
+    if d == S::zero() {
+        return None;
+    }
+    let (ab2, ac2) = (ab.x * ab.x + ab.y * ab.y, ac.x * ac.x + ac.y * ac.y);
+    Some(Point2::new(
+        a.x + (ac.y * ab2 - ab.y * ac2) / d,
+        a.y + (ab.x * ac2 - ac.x * ab2) / d,
+    ))
+}
+
+/// A polygon with holes, stored as a single circular doubly linked list of
+/// nodes so that vertices can be clipped in constant time.
+///
+/// The outer boundary runs counterclockwise and the holes clockwise. Each
+/// hole is joined to the outer boundary by a pair of coincident bridge edges,
+/// which duplicate the two vertices at their ends.
+struct EarClipper<S> {
+    points: Vec<Point2<S>>,
+    vertex: Vec<usize>,
+    prev: Vec<usize>,
+    next: Vec<usize>,
+}
+
+impl<S: BaseFloat> EarClipper<S> {
+    #[inline]
+    fn position(&self, node: usize) -> Point2<S> {
+        self.points[self.vertex[node]]
+    }
+
+    /// Add a node for a vertex, after `after` or on its own.
+    fn node(&mut self, vertex: usize, after: Option<usize>) -> usize {
+        let node = self.vertex.len();
+        self.vertex.push(vertex);
+        match after {
+            Some(after) => {
+                let next = self.next[after];
+                self.prev.push(after);
+                self.next.push(next);
+                self.next[after] = node;
+                self.prev[next] = node;
+            }
+            None => {
+                self.prev.push(node);
+                self.next.push(node);
+            }
+        }
+        node
+    }
+
+    fn unlink(&mut self, node: usize) {
+        let (prev, next) = (self.prev[node], self.next[node]);
+        self.next[prev] = next;
+        self.prev[next] = prev;
+    }
+
+    /// Append a ring of points, linked in counterclockwise or clockwise order.
+    fn ring(&mut self, ring: &[Point2<S>], counterclockwise: bool) -> Option<usize> {
+        if ring.len() < 3 {
+            return None;
+        }
+        let start = self.points.len();
+        self.points.extend_from_slice(ring);
+
+        let mut vertices: Vec<usize> = (start..start + ring.len()).collect();
+        if (signed_area(ring) > S::zero()) != counterclockwise {
+            vertices.reverse();
+        }
+        let first = self.node(vertices[0], None);
+        vertices[1..]
+            .iter()
+            .fold(first, |last, &vertex| self.node(vertex, Some(last)));
+        Some(first)
+    }
+
+    /// Test whether the direction from `node` towards `p` points into the
+    /// inside of the polygon.
+    fn locally_inside(&self, node: usize, p: Point2<S>) -> bool {
+        let a = self.position(self.prev[node]);
+        let b = self.position(node);
+        let c = self.position(self.next[node]);
+        if orient2d(a, b, c) >= S::zero() {
+            orient2d(b, c, p) > S::zero() && orient2d(a, b, p) > S::zero()
+        } else {
+            orient2d(b, c, p) > S::zero() || orient2d(a, b, p) > S::zero()
+        }
+    }
+
+    /// Find a node on the ring containing `start` that is visible from the
+    /// rightmost vertex of a hole, using the method described by David
+    /// Eberly in "Triangulation by Ear Clipping".
+    fn find_bridge(&self, start: usize, hole: usize) -> Option<usize> {
+        let m = self.position(hole);
+
+        // Cast a ray from the hole towards positive x, and find the nearest
+        // edge that it hits.
+        let mut hit: Option<(S, usize)> = None;
+        let mut node = start;
+        loop {
+            let (a, b) = (self.position(node), self.position(self.next[node]));
+            if a.y != b.y && (a.y <= m.y) == (m.y <= b.y) {
+                let x = a.x + (m.y - a.y) * (b.x - a.x) / (b.y - a.y);
+                if x >= m.x && !hit.iter().any(|&(nearest, _)| nearest <= x) {
+                    hit = Some((x, if a.x > b.x { node } else { self.next[node] }));
+                }
+            }
+            node = self.next[node];
+            if node == start {
+                break;
+            }
+        }
+        let (x, candidate) = hit?;
+        let i = Point2::new(x, m.y);
+        let p = self.position(candidate);
+
+        // The end of the edge is visible unless something inside the triangle
+        // between the hole, the hit and that end blocks it, in which case the
+        // vertex in the triangle closest in angle to the ray is visible.
+        let mut best: Option<(S, usize)> = None;
+        let mut node = start;
+        loop {
+            let q = self.position(node);
+            let eligible = q == p || (q.x > m.x && in_triangle(m, i, p, q));
+            if eligible && (q == m || self.locally_inside(node, m)) {
+                let tangent = if q == m {
+                    S::zero()
+                } else {
+                    (m.y - q.y).abs() / (q.x - m.x)
+                };
+                if !best.iter().any(|&(smallest, _)| smallest <= tangent) {
+                    best = Some((tangent, node));
+                }
+            }
+            node = self.next[node];
+            if node == start {
+                break;
+            }
+        }
+        Some(best.map_or(candidate, |(_, node)| node))
+    }
+
+    /// Join a hole to the ring through the bridge between two nodes.
+    fn splice(&mut self, outer: usize, hole: usize) {
+        let outer_next = self.next[outer];
+        let hole_prev = self.prev[hole];
+        let (outer_vertex, hole_vertex) = (self.vertex[outer], self.vertex[hole]);
+
+        self.next[outer] = hole;
+        self.prev[hole] = outer;
+        self.next[hole_prev] = outer_next;
+        self.prev[outer_next] = hole_prev;
+
+        let hole_copy = self.node(hole_vertex, Some(hole_prev));
+        self.node(outer_vertex, Some(hole_copy));
+    }
+
+    /// Test whether the triangle at a node is an ear, which is convex and
+    /// does not contain any other vertex of the polygon.
+    fn is_ear(&self, node: usize) -> bool {
+        let a = self.position(self.prev[node]);
+        let b = self.position(node);
+        let c = self.position(self.next[node]);
+        if orient2d(a, b, c) <= S::zero() {
+            return false;
+        }
+
+        let mut other = self.next[self.next[node]];
+        while other != self.prev[node] {
+            let p = self.position(other);
+            if p != a && p != b && p != c && in_triangle(a, b, c, p) {
+                return false;
+            }
+            other = self.next[other];
+        }
+        true
+    }
+
+    fn clip(mut self, mut node: usize) -> Vec<[usize; 3]> {
+        let mut triangles = Vec::new();
+        let mut remaining = {
+            let mut count = 1;
+            let mut other = self.next[node];
+            while other != node {
+                count += 1;
+                other = self.next[other];
+            }
+            count
+        };
+
+        let mut stalled = 0;
+        while remaining > 3 {
+            let (prev, next) = (self.prev[node], self.next[node]);
+            let orientation = orient2d(
+                self.position(prev),
+                self.position(node),
+                self.position(next),
+            );
+            if orientation.is_zero() {
+                // A collinear vertex or a zero-width spike adds no area.
+                self.unlink(node);
+                remaining -= 1;
+                node = prev;
+                stalled = 0;
+            } else if self.is_ear(node) || (stalled > remaining && orientation > S::zero()) {
+                // If a whole pass finds no ear, the polygon intersects itself
+                // and the first convex vertex is clipped anyway.
+                triangles.push([self.vertex[prev], self.vertex[node], self.vertex[next]]);
+                self.unlink(node);
+                remaining -= 1;
+                node = next;
+                stalled = 0;
+            } else if stalled > 2 * remaining {
+                // Every remaining vertex is reflex.
+                return triangles;
+            } else {
+                node = next;
+                stalled += 1;
+            }
+        }
+
+        let (prev, next) = (self.prev[node], self.next[node]);
+        if orient2d(
+            self.position(prev),
+            self.position(node),
+            self.position(next),
+        ) > S::zero()
+        {
+            triangles.push([self.vertex[prev], self.vertex[node], self.vertex[next]]);
+        }
+        triangles
+    }
+}
+
+/// Triangulate a simple polygon by ear clipping.
+///
+/// The vertices may be given in either order, and the triangles are returned
+/// as triples of indices into `polygon`, each in counterclockwise order.
+/// Collinear and repeated vertices are allowed. A polygon that intersects
+/// itself is still split into triangles, but they may overlap or leave
+/// parts of it uncovered.
+///
+/// ```rust
+/// use cgmath::triangulation::triangulate;
+/// use cgmath::Point2;
+///
+/// // An L shaped polygon.
+/// let polygon = [
+///     Point2::new(0.0, 0.0),
+///     Point2::new(2.0, 0.0),
+///     Point2::new(2.0, 1.0),
+///     Point2::new(1.0, 1.0),
+///     Point2::new(1.0, 2.0),
+///     Point2::new(0.0, 2.0),
+/// ];
+/// assert_eq!(triangulate(&polygon).len(), 4);
+/// ```
+pub fn triangulate<S: BaseFloat>(polygon: &[Point2<S>]) -> Vec<[usize; 3]> {
+    triangulate_with_holes(polygon, &[])
+}
+
+/// Triangulate a simple polygon with holes by ear clipping.
+///
+/// The holes must be simple polygons inside of the outer one that do not
+/// overlap each other, and as with the outer boundary their vertices may be
+/// given in either order. The triangles are returned as triples of indices
+/// into the concatenation of `polygon` and all of the `holes`, in order.
+pub fn triangulate_with_holes<S: BaseFloat>(
+    polygon: &[Point2<S>],
+    holes: &[&[Point2<S>]],
+) -> Vec<[usize; 3]> {
+    let mut clipper = EarClipper {
+        points: Vec::new(),
+        vertex: Vec::new(),
+        prev: Vec::new(),
+        next: Vec::new(),
+    };
+    let outer = match clipper.ring(polygon, true) {
+        Some(outer) => outer,
+        None => return Vec::new(),
+    };
+
+    // Bridge the holes from right to left, so that each bridge only has to
+    // cross the part of the polygon that is already joined together.
+    let mut rightmost = Vec::new();
+    for hole in holes {
+        let offset = clipper.points.len();
+        if let Some(first) = clipper.ring(hole, false) {
+            let mut best = first;
+            for node in first..first + hole.len() {
+                if clipper.position(node).x > clipper.position(best).x {
+                    best = node;
+                }
+            }
+            rightmost.push(best);
+        } else {
+            clipper.points.extend_from_slice(hole);
+        }
+        debug_assert_eq!(clipper.points.len(), offset + hole.len());
+    }
+    rightmost.sort_by(|&a, &b| {
+        clipper
+            .position(b)
+            .x
+            .partial_cmp(&clipper.position(a).x)
+            .unwrap_or(Ordering::Equal)
+    });
+    for hole in rightmost {
+        if let Some(bridge) = clipper.find_bridge(outer, hole) {
+            clipper.splice(bridge, hole);
+        }
+    }
+
+    clipper.clip(outer)
+}
@@ -0,0 +1,255 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use structure::*;
+
+use angle::Rad;
+use num::BaseFloat;
+use point::{Point2, Point3};
+use quaternion::Quaternion;
+use rotation::{Rotation, Rotation3};
+use vector::{Vector2, Vector3};
+
+/// A turtle drawing in the plane, as used to interpret L-systems.
+///
+/// The turtle has a position and a heading, measured counter-clockwise from
+/// the `x` axis, and records the points it walks through as a set of
+/// polylines. `push` and `pop` save and restore its state, which is how
+/// L-systems draw branches.
+///
+/// ```rust
+/// use cgmath::prelude::*;
+/// use cgmath::{Deg, Point2, Rad, Turtle2};
+///
+/// let mut turtle = Turtle2::new(Point2::new(0.0, 0.0f64), Rad(0.0));
+/// for _ in 0..4 {
+///     turtle.forward(1.0);
+///     turtle.turn(Deg(90.0));
+/// }
+/// let square = &turtle.paths()[0];
+/// assert_eq!(square.len(), 5);
+/// assert!((square[2] - Point2::new(1.0, 1.0)).magnitude() < 1e-12);
+/// assert!((square[4] - square[0]).magnitude() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Turtle2<S> {
+    /// The current position.
+    pub position: Point2<S>,
+    /// The current heading, counter-clockwise from the `x` axis.
+    pub heading: Rad<S>,
+    saved: Vec<(Point2<S>, Rad<S>)>,
+    paths: Vec<Vec<Point2<S>>>,
+}
+
+/// A turtle drawing in space, as used to interpret three-dimensional
+/// L-systems.
+///
+/// The orientation of the turtle maps its local axes to world space: the
+/// heading is its `x` axis, its left is its `y` axis and its up is its `z`
+/// axis. Turning, pitching and rolling rotate the turtle around its own up,
+/// left and heading axes.
+///
+/// ```rust
+/// use cgmath::{Deg, Point3, Quaternion, Turtle3, Vector3};
+/// use cgmath::prelude::*;
+///
+/// let mut turtle = Turtle3::new(Point3::new(0.0, 0.0, 0.0f64), Quaternion::one());
+/// turtle.pitch(Deg(90.0));
+/// turtle.forward(2.0);
+/// assert!((turtle.position - Point3::new(0.0, 0.0, 2.0)).magnitude() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Turtle3<S> {
+    /// The current position.
+    pub position: Point3<S>,
+    /// The rotation from the local axes of the turtle to world space.
+    pub orientation: Quaternion<S>,
+    saved: Vec<(Point3<S>, Quaternion<S>)>,
+    paths: Vec<Vec<Point3<S>>>,
+}
+
+macro_rules! impl_turtle_paths {
+    ($Turtle:ident, $Point:ident, $State:ty) => {
+        impl<S: BaseFloat> $Turtle<S> {
+            /// Move forward by `distance`, drawing a line.
+            pub fn forward(&mut self, distance: S) {
+                self.position += self.direction() * distance;
+                let position = self.position;
+                self.paths.last_mut().unwrap().push(position);
+            }
+
+            /// Move forward by `distance` without drawing, which starts a new
+            /// polyline.
+            pub fn jump(&mut self, distance: S) {
+                self.position += self.direction() * distance;
+                self.start_path();
+            }
+
+            /// Save the current position and orientation.
+            pub fn push(&mut self) {
+                let state = self.state();
+                self.saved.push(state);
+            }
+
+            /// Restore the last saved position and orientation, which starts
+            /// a new polyline. Returns `false`, leaving the turtle as it is,
+            /// if no state was saved.
+            pub fn pop(&mut self) -> bool {
+                match self.saved.pop() {
+                    Some(state) => {
+                        self.set_state(state);
+                        self.start_path();
+                        true
+                    }
+                    None => false,
+                }
+            }
+
+            /// The polylines drawn so far. Each one starts where the turtle
+            /// was put down, and polylines of a single point, where it did not
+            /// draw anything, are included.
+            #[inline]
+            pub fn paths(&self) -> &[Vec<$Point<S>>] {
+                &self.paths
+            }
+
+            /// The polylines drawn, leaving out those of a single point.
+            pub fn into_paths(self) -> Vec<Vec<$Point<S>>> {
+                self.paths
+                    .into_iter()
+                    .filter(|path| path.len() > 1)
+                    .collect()
+            }
+
+            fn start_path(&mut self) {
+                let position = self.position;
+                let last = self.paths.last_mut().unwrap();
+                if last.len() == 1 {
+                    last[0] = position;
+                } else {
+                    self.paths.push(vec![position]);
+                }
+            }
+
+            fn state(&self) -> $State {
+                (self.position, self.orientation_state())
+            }
+        }
+    };
+}
+
+impl<S: BaseFloat> Turtle2<S> {
+    /// Create a turtle at `position`, facing `heading`.
+    pub fn new<A: Into<Rad<S>>>(position: Point2<S>, heading: A) -> Turtle2<S> {
+        Turtle2 {
+            position,
+            heading: heading.into(),
+            saved: Vec::new(),
+            paths: vec![vec![position]],
+        }
+    }
+
+    /// The unit vector the turtle is facing.
+    #[inline]
+    pub fn direction(&self) -> Vector2<S> {
+        let (sin, cos) = Rad::sin_cos(self.heading);
+        Vector2::new(cos, sin)
+    }
+
+    /// Turn counter-clockwise by `angle`.
+    #[inline]
+    pub fn turn<A: Into<Rad<S>>>(&mut self, angle: A) {
+        self.heading += angle.into();
+    }
+
+    #[inline]
+    fn orientation_state(&self) -> Rad<S> {
+        self.heading
+    }
+
+    #[inline]
+    fn set_state(&mut self, (position, heading): (Point2<S>, Rad<S>)) {
+        self.position = position;
+        self.heading = heading;
+    }
+}
+
+impl<S: BaseFloat> Turtle3<S> {
+    /// Create a turtle at `position`, with the given orientation.
+    pub fn new(position: Point3<S>, orientation: Quaternion<S>) -> Turtle3<S> {
+        Turtle3 {
+            position,
+            orientation,
+            saved: Vec::new(),
+            paths: vec![vec![position]],
+        }
+    }
+
+    /// The unit vector the turtle is facing.
+    #[inline]
+    pub fn direction(&self) -> Vector3<S> {
+        self.orientation.rotate_vector(Vector3::unit_x())
+    }
+
+    /// The unit vector on the left of the turtle.
+    #[inline]
+    pub fn left(&self) -> Vector3<S> {
+        self.orientation.rotate_vector(Vector3::unit_y())
+    }
+
+    /// The unit vector above the turtle.
+    #[inline]
+    pub fn up(&self) -> Vector3<S> {
+        self.orientation.rotate_vector(Vector3::unit_z())
+    }
+
+    /// Turn left by `angle`, around the up axis of the turtle.
+    #[inline]
+    pub fn turn<A: Into<Rad<S>>>(&mut self, angle: A) {
+        self.rotate_local(Quaternion::from_angle_z(angle));
+    }
+
+    /// Pitch up by `angle`, around the left axis of the turtle.
+    #[inline]
+    pub fn pitch<A: Into<Rad<S>>>(&mut self, angle: A) {
+        self.rotate_local(Quaternion::from_angle_y(-angle.into()));
+    }
+
+    /// Roll clockwise, as seen from behind the turtle, by `angle` around its
+    /// heading.
+    #[inline]
+    pub fn roll<A: Into<Rad<S>>>(&mut self, angle: A) {
+        self.rotate_local(Quaternion::from_angle_x(angle));
+    }
+
+    #[inline]
+    fn rotate_local(&mut self, rotation: Quaternion<S>) {
+        self.orientation = (self.orientation * rotation).normalize();
+    }
+
+    #[inline]
+    fn orientation_state(&self) -> Quaternion<S> {
+        self.orientation
+    }
+
+    #[inline]
+    fn set_state(&mut self, (position, orientation): (Point3<S>, Quaternion<S>)) {
+        self.position = position;
+        self.orientation = orientation;
+    }
+}
+
+impl_turtle_paths!(Turtle2, Point2, (Point2<S>, Rad<S>));
+impl_turtle_paths!(Turtle3, Point3, (Point3<S>, Quaternion<S>));
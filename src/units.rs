@@ -0,0 +1,288 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Points and vectors tagged with the units they are measured in.
+//!
+//! User interface code juggles logical pixels, which are independent of the
+//! display, physical pixels, which are the actual pixels of the screen, and
+//! world units. Mixing them up is an easy mistake on high DPI displays, where
+//! the two kinds of pixels differ. Tagging 2D positions with their units
+//! makes such mistakes type errors, and the only way to go from one unit to
+//! another is through an explicit `Scale`.
+//!
+//! The tags are only used at compile time, and can be any type, like the
+//! space tags of `Xform`. This module provides the common ones.
+//!
+//! ```rust
+//! use cgmath::units::{Logical, Physical, Scale, TypedPoint2, TypedVector2};
+//!
+//! let hidpi: Scale<f32, Logical, Physical> = Scale::new(2.0);
+//!
+//! let button = TypedPoint2::<f32, Logical>::new(10.0, 20.0);
+//! let cursor = TypedPoint2::<f32, Physical>::new(24.0, 44.0);
+//!
+//! let offset = cursor - hidpi.transform_point(button);
+//! assert_eq!(offset, TypedVector2::new(4.0, 4.0));
+//! assert_eq!(hidpi.inverse().transform_vector(offset), TypedVector2::new(2.0, 2.0));
+//! ```
+//!
+//! Subtracting positions in different units is a type error:
+//!
+//! ```compile_fail
+//! use cgmath::units::{Logical, Physical, TypedPoint2};
+//!
+//! let button = TypedPoint2::<f32, Logical>::new(10.0, 20.0);
+//! let cursor = TypedPoint2::<f32, Physical>::new(24.0, 44.0);
+//! let offset = cursor - button;
+//! ```
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::*;
+
+use num::{BaseFloat, BaseNum};
+use point::Point2;
+use vector::Vector2;
+
+/// Logical pixels, which are independent of the pixel density of the
+/// display.
+pub enum Logical {}
+
+/// Physical pixels, the actual pixels of the display or of a framebuffer.
+pub enum Physical {}
+
+/// The units of the world a user interface shows.
+pub enum World {}
+
+/// A point measured in the units `U`.
+pub struct TypedPoint2<S, U> {
+    /// The untagged point.
+    pub point: Point2<S>,
+    unit: PhantomData<U>,
+}
+
+/// A vector measured in the units `U`.
+pub struct TypedVector2<S, U> {
+    /// The untagged vector.
+    pub vec: Vector2<S>,
+    unit: PhantomData<U>,
+}
+
+/// The scale factor converting positions from the units `Src` to the units
+/// `Dst`, such as the DPI scale factor from `Logical` to `Physical` pixels.
+pub struct Scale<S, Src, Dst> {
+    /// The number of `Dst` units in one `Src` unit.
+    pub factor: S,
+    units: PhantomData<fn(Src) -> Dst>,
+}
+
+macro_rules! impl_typed {
+    ($Typed:ident <$($U:ident),+>, $field:ident: $T:ty, $name:expr) => {
+        impl<S: Copy, $($U),+> Copy for $Typed<S, $($U),+> {}
+
+        impl<S: Clone, $($U),+> Clone for $Typed<S, $($U),+> {
+            #[inline]
+            fn clone(&self) -> $Typed<S, $($U),+> {
+                $Typed {
+                    $field: self.$field.clone(),
+                    ..*self
+                }
+            }
+        }
+
+        impl<S: PartialEq, $($U),+> PartialEq for $Typed<S, $($U),+> {
+            #[inline]
+            fn eq(&self, other: &$Typed<S, $($U),+>) -> bool {
+                self.$field == other.$field
+            }
+        }
+
+        impl<S: fmt::Debug, $($U),+> fmt::Debug for $Typed<S, $($U),+> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, concat!($name, " "))?;
+                self.$field.fmt(f)
+            }
+        }
+    };
+}
+
+impl_typed!(TypedPoint2<U>, point: Point2<S>, "TypedPoint2");
+impl_typed!(TypedVector2<U>, vec: Vector2<S>, "TypedVector2");
+impl_typed!(Scale<Src, Dst>, factor: S, "Scale");
+
+impl<S, U> TypedPoint2<S, U> {
+    /// Construct a point from its coordinates.
+    #[inline]
+    pub fn new(x: S, y: S) -> TypedPoint2<S, U> {
+        TypedPoint2::from_untyped(Point2::new(x, y))
+    }
+
+    /// Tag a point with its units.
+    #[inline]
+    pub fn from_untyped(point: Point2<S>) -> TypedPoint2<S, U> {
+        TypedPoint2 {
+            point,
+            unit: PhantomData,
+        }
+    }
+
+    /// Change the units of the point without converting it, for interfacing
+    /// with code that disagrees on its units.
+    #[inline]
+    pub fn cast_unit<V>(self) -> TypedPoint2<S, V> {
+        TypedPoint2::from_untyped(self.point)
+    }
+}
+
+impl<S, U> TypedVector2<S, U> {
+    /// Construct a vector from its components.
+    #[inline]
+    pub fn new(x: S, y: S) -> TypedVector2<S, U> {
+        TypedVector2::from_untyped(Vector2::new(x, y))
+    }
+
+    /// Tag a vector with its units.
+    #[inline]
+    pub fn from_untyped(vec: Vector2<S>) -> TypedVector2<S, U> {
+        TypedVector2 {
+            vec,
+            unit: PhantomData,
+        }
+    }
+
+    /// Change the units of the vector without converting it, for
+    /// interfacing with code that disagrees on its units.
+    #[inline]
+    pub fn cast_unit<V>(self) -> TypedVector2<S, V> {
+        TypedVector2::from_untyped(self.vec)
+    }
+}
+
+impl<S: BaseNum, U> Add<TypedVector2<S, U>> for TypedPoint2<S, U> {
+    type Output = TypedPoint2<S, U>;
+
+    #[inline]
+    fn add(self, rhs: TypedVector2<S, U>) -> TypedPoint2<S, U> {
+        TypedPoint2::from_untyped(self.point + rhs.vec)
+    }
+}
+
+impl<S: BaseNum, U> Sub<TypedVector2<S, U>> for TypedPoint2<S, U> {
+    type Output = TypedPoint2<S, U>;
+
+    #[inline]
+    fn sub(self, rhs: TypedVector2<S, U>) -> TypedPoint2<S, U> {
+        TypedPoint2::from_untyped(self.point - rhs.vec)
+    }
+}
+
+impl<S: BaseNum, U> Sub for TypedPoint2<S, U> {
+    type Output = TypedVector2<S, U>;
+
+    #[inline]
+    fn sub(self, rhs: TypedPoint2<S, U>) -> TypedVector2<S, U> {
+        TypedVector2::from_untyped(self.point - rhs.point)
+    }
+}
+
+impl<S: BaseNum, U> Add for TypedVector2<S, U> {
+    type Output = TypedVector2<S, U>;
+
+    #[inline]
+    fn add(self, rhs: TypedVector2<S, U>) -> TypedVector2<S, U> {
+        TypedVector2::from_untyped(self.vec + rhs.vec)
+    }
+}
+
+impl<S: BaseNum, U> Sub for TypedVector2<S, U> {
+    type Output = TypedVector2<S, U>;
+
+    #[inline]
+    fn sub(self, rhs: TypedVector2<S, U>) -> TypedVector2<S, U> {
+        TypedVector2::from_untyped(self.vec - rhs.vec)
+    }
+}
+
+impl<S: BaseNum + Neg<Output = S>, U> Neg for TypedVector2<S, U> {
+    type Output = TypedVector2<S, U>;
+
+    #[inline]
+    fn neg(self) -> TypedVector2<S, U> {
+        TypedVector2::from_untyped(-self.vec)
+    }
+}
+
+impl<S: BaseNum, U> Mul<S> for TypedVector2<S, U> {
+    type Output = TypedVector2<S, U>;
+
+    #[inline]
+    fn mul(self, scalar: S) -> TypedVector2<S, U> {
+        TypedVector2::from_untyped(self.vec * scalar)
+    }
+}
+
+impl<S: BaseNum, U> Div<S> for TypedVector2<S, U> {
+    type Output = TypedVector2<S, U>;
+
+    #[inline]
+    fn div(self, scalar: S) -> TypedVector2<S, U> {
+        TypedVector2::from_untyped(self.vec / scalar)
+    }
+}
+
+impl<S, Src, Dst> Scale<S, Src, Dst> {
+    /// Construct a scale from the number of `Dst` units in one `Src` unit.
+    #[inline]
+    pub fn new(factor: S) -> Scale<S, Src, Dst> {
+        Scale {
+            factor,
+            units: PhantomData,
+        }
+    }
+}
+
+impl<S: BaseNum, Src, Dst> Scale<S, Src, Dst> {
+    /// Convert a point to the destination units, scaling it about the
+    /// origin.
+    #[inline]
+    pub fn transform_point(&self, p: TypedPoint2<S, Src>) -> TypedPoint2<S, Dst> {
+        TypedPoint2::from_untyped(p.point * self.factor)
+    }
+
+    /// Convert a vector to the destination units.
+    #[inline]
+    pub fn transform_vector(&self, v: TypedVector2<S, Src>) -> TypedVector2<S, Dst> {
+        TypedVector2::from_untyped(v.vec * self.factor)
+    }
+}
+
+impl<S: BaseFloat, Src, Dst> Scale<S, Src, Dst> {
+    /// The scale converting back from the destination units to the source
+    /// units.
+    #[inline]
+    pub fn inverse(&self) -> Scale<S, Dst, Src> {
+        Scale::new(self.factor.recip())
+    }
+}
+
+impl<S: BaseNum, A, B, C> Mul<Scale<S, A, B>> for Scale<S, B, C> {
+    type Output = Scale<S, A, C>;
+
+    /// Convert with `rhs`, then with this scale.
+    #[inline]
+    fn mul(self, rhs: Scale<S, A, B>) -> Scale<S, A, C> {
+        Scale::new(self.factor * rhs.factor)
+    }
+}
@@ -28,7 +28,7 @@ use structure::*;
 
 use angle::Rad;
 use approx;
-use num::{BaseFloat, BaseNum};
+use num::{BaseFloat, BaseNum, ToF32};
 
 #[cfg(feature = "mint")]
 use mint;
@@ -188,6 +188,14 @@ macro_rules! impl_vector {
             }
         }
 
+        /// The default vector is the zero vector.
+        impl<S: BaseNum> Default for $VectorN<S> {
+            #[inline]
+            fn default() -> $VectorN<S> {
+                $VectorN::zero()
+            }
+        }
+
         impl<S: BaseNum> iter::Sum<$VectorN<S>> for $VectorN<S> {
             #[inline]
             fn sum<I: Iterator<Item=$VectorN<S>>>(iter: I) -> $VectorN<S> {
@@ -386,6 +394,11 @@ impl_tuple_conversions!(Vector2<S> { x, y }, (S, S));
 impl_tuple_conversions!(Vector3<S> { x, y, z }, (S, S, S));
 impl_tuple_conversions!(Vector4<S> { x, y, z, w }, (S, S, S, S));
 
+impl_precision_conversions!(Vector1 { x }, Vector1<f32>, Vector1<f64>);
+impl_precision_conversions!(Vector2 { x, y }, Vector2<f32>, Vector2<f64>);
+impl_precision_conversions!(Vector3 { x, y, z }, Vector3<f32>, Vector3<f64>);
+impl_precision_conversions!(Vector4 { x, y, z, w }, Vector4<f32>, Vector4<f64>);
+
 impl<S: BaseNum> Vector1<S> {
     /// A unit vector in the `x` direction.
     #[inline]
@@ -393,6 +406,13 @@ impl<S: BaseNum> Vector1<S> {
         Vector1::new(S::one())
     }
 
+    /// Create a `Vector2`, using the `x` value from this vector, and the
+    /// provided `y`.
+    #[inline]
+    pub fn extend(self, y: S) -> Vector2<S> {
+        Vector2::new(self.x, y)
+    }
+
     impl_swizzle_functions!(Vector1, Vector2, Vector3, Vector4, S, x);
 }
 
@@ -422,6 +442,12 @@ impl<S: BaseNum> Vector2<S> {
         Vector3::new(self.x, self.y, z)
     }
 
+    /// Create a `Vector1`, dropping the `y` value.
+    #[inline]
+    pub fn truncate(self) -> Vector1<S> {
+        Vector1::new(self.x)
+    }
+
     impl_swizzle_functions!(Vector1, Vector2, Vector3, Vector4, S, xy);
 }
 
@@ -467,6 +493,17 @@ impl<S: BaseNum> Vector3<S> {
         Vector2::new(self.x, self.y)
     }
 
+    /// Create a `Vector2`, dropping the nth element.
+    #[inline]
+    pub fn truncate_n(&self, n: isize) -> Vector2<S> {
+        match n {
+            0 => Vector2::new(self.y, self.z),
+            1 => Vector2::new(self.x, self.z),
+            2 => Vector2::new(self.x, self.y),
+            _ => panic!("{:?} is out of range", n),
+        }
+    }
+
     impl_swizzle_functions!(Vector1, Vector2, Vector3, Vector4, S, xyz);
 }
 
@@ -516,6 +553,57 @@ impl<S: BaseNum> Vector4<S> {
     impl_swizzle_functions!(Vector1, Vector2, Vector3, Vector4, S, xyzw);
 }
 
+macro_rules! impl_vector_consts {
+    ($($S:ident),+) => {
+        $(
+            impl Vector1<$S> {
+                /// The zero vector.
+                pub const ZERO: Vector1<$S> = Vector1::new(0.0);
+                /// A unit vector in the `x` direction.
+                pub const UNIT_X: Vector1<$S> = Vector1::new(1.0);
+            }
+
+            impl Vector2<$S> {
+                /// The zero vector.
+                pub const ZERO: Vector2<$S> = Vector2::new(0.0, 0.0);
+                /// A unit vector in the `x` direction.
+                pub const UNIT_X: Vector2<$S> = Vector2::new(1.0, 0.0);
+                /// A unit vector in the `y` direction.
+                pub const UNIT_Y: Vector2<$S> = Vector2::new(0.0, 1.0);
+            }
+
+            impl Vector3<$S> {
+                /// The zero vector.
+                pub const ZERO: Vector3<$S> = Vector3::new(0.0, 0.0, 0.0);
+                /// A unit vector in the `x` direction.
+                pub const UNIT_X: Vector3<$S> = Vector3::new(1.0, 0.0, 0.0);
+                /// A unit vector in the `y` direction.
+                pub const UNIT_Y: Vector3<$S> = Vector3::new(0.0, 1.0, 0.0);
+                /// A unit vector in the `z` direction.
+                pub const UNIT_Z: Vector3<$S> = Vector3::new(0.0, 0.0, 1.0);
+            }
+
+            impl Vector4<$S> {
+                /// The zero vector.
+                pub const ZERO: Vector4<$S> = Vector4::new(0.0, 0.0, 0.0, 0.0);
+                /// A unit vector in the `x` direction.
+                pub const UNIT_X: Vector4<$S> = Vector4::new(1.0, 0.0, 0.0, 0.0);
+                /// A unit vector in the `y` direction.
+                pub const UNIT_Y: Vector4<$S> = Vector4::new(0.0, 1.0, 0.0, 0.0);
+                /// A unit vector in the `z` direction.
+                pub const UNIT_Z: Vector4<$S> = Vector4::new(0.0, 0.0, 1.0, 0.0);
+                /// A unit vector in the `w` direction.
+                pub const UNIT_W: Vector4<$S> = Vector4::new(0.0, 0.0, 0.0, 1.0);
+            }
+        )+
+    };
+}
+
+// The generic constructors such as `unit_x` go through `num_traits`, which
+// can't be called in constant contexts, so the most common constants are also
+// provided for the floating point scalar types.
+impl_vector_consts!(f32, f64);
+
 /// Dot product of two vectors.
 #[inline]
 pub fn dot<V: InnerSpace>(a: V, b: V) -> V::Scalar
@@ -0,0 +1,758 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Paths of vehicles which turn with a bounded curvature, such as cars,
+//! which cannot turn on the spot.
+//!
+//! A `CurvaturePath` is made of arcs of the turning circle of the vehicle
+//! and of straight lines. `CurvaturePath::dubins` finds the shortest path
+//! between two poses driving forwards, and `CurvaturePath::reeds_shepp` the
+//! shortest one when the vehicle may also reverse.
+//!
+//! `pure_pursuit` and `stanley` compute the steering angle which makes a
+//! vehicle follow a path, and `bicycle_step` moves a vehicle steering with
+//! that angle.
+//!
+//! ```rust
+//! use cgmath::vehicle::CurvaturePath;
+//! use cgmath::{Deg, Point2, Pose2};
+//!
+//! // A U-turn with a turning radius of 2.
+//! let start = Pose2::from_heading(Point2::new(0.0, 0.0), Deg(0.0f64));
+//! let goal = Pose2::from_heading(Point2::new(0.0, 4.0), Deg(180.0));
+//! let path = CurvaturePath::dubins(&start, &goal, 2.0);
+//! assert!((path.length() - 2.0 * std::f64::consts::PI).abs() < 1e-9);
+//! assert!((path.end().position() - goal.position()).x.abs() < 1e-9);
+//! ```
+
+use num_traits::cast;
+
+use structure::*;
+
+use self::Steer::{Left, Right, Straight};
+use angle::Rad;
+use arc::Arc2;
+use num::BaseFloat;
+use point::Point2;
+use polyline::Polyline2;
+use pose::Pose2;
+use vector::Vector2;
+
+/// The way a vehicle steers along a segment of a path.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Steer {
+    /// Turning left as tightly as possible.
+    Left,
+    /// Going straight.
+    Straight,
+    /// Turning right as tightly as possible.
+    Right,
+}
+
+/// A segment of a path, along which the vehicle steers the same way.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PathSegment<S> {
+    /// The way the vehicle steers.
+    pub steer: Steer,
+    /// The distance driven, which is negative when reversing.
+    pub length: S,
+}
+
+/// A path of a vehicle with a minimum turning radius, made of arcs of its
+/// turning circle and of straight lines.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CurvaturePath<S: BaseFloat> {
+    /// The pose where the path starts.
+    pub start: Pose2<S>,
+    /// The turning radius of the vehicle.
+    pub radius: S,
+    /// The segments of the path, in order.
+    pub segments: Vec<PathSegment<S>>,
+}
+
+/// The angle `x` wrapped between `-π` and `π`.
+fn wrap<S: BaseFloat>(x: S) -> S {
+    let pi = Rad::<S>::turn_div_2().0;
+    let v = x % (pi + pi);
+    if v < -pi {
+        v + pi + pi
+    } else if v > pi {
+        v - pi - pi
+    } else {
+        v
+    }
+}
+
+/// The angle `x` wrapped between `0` and `2π`.
+fn wrap_positive<S: BaseFloat>(x: S) -> S {
+    let full = Rad::<S>::full_turn().0;
+    let v = x % full;
+    if v < S::zero() {
+        v + full
+    } else {
+        v
+    }
+}
+
+/// The distance and direction of `(x, y)` from the origin.
+fn polar<S: BaseFloat>(x: S, y: S) -> (S, S) {
+    (x.hypot(y), Rad::atan2(y, x).0)
+}
+
+/// A path of the unit turning circle with the given steering and lengths.
+type Word<S> = (&'static [Steer], Vec<S>);
+
+/// The paths of the unit turning circle from the origin facing `x` to
+/// `(x, y)` facing `phi` that turn left, go straight and turn left, then
+/// turn left, go straight and turn right, then turn left, right and left,
+/// all of them forwards, with the given steering for each.
+///
+/// The turning circles of the start and of the goal are joined by a
+/// tangent, or by a third circle tangent to both.
+fn dubins_words<S: BaseFloat>(x: S, y: S, phi: S, steers: [&'static [Steer]; 3]) -> Vec<Word<S>> {
+    let two: S = cast(2).unwrap();
+    let four: S = cast(4).unwrap();
+    let half_pi = Rad::<S>::turn_div_4().0;
+    let (sin, cos) = Rad(phi).sin_cos();
+    let mut words = Vec::new();
+
+    // Leaving the circle around (0, 1) along the tangent to the left circle
+    // of the goal, parallel to the line between their centers.
+    let (u, t) = polar(x - sin, y + cos - S::one());
+    words.push((steers[0], vec![wrap_positive(t), u, wrap_positive(phi - t)]));
+
+    // Crossing over to the right circle of the goal.
+    let (d, theta) = polar(x + sin, y - cos - S::one());
+    if d >= two {
+        let u = (d * d - four).sqrt();
+        let h = theta + Rad::atan2(two, u).0;
+        words.push((steers[1], vec![wrap_positive(h), u, wrap_positive(h - phi)]));
+    }
+
+    // Rolling on a right circle which touches both left circles.
+    let (d, theta) = polar(x - sin, y + cos - S::one());
+    if d <= four {
+        let gamma = Rad::acos(d / four).0;
+        for &side in &[S::one(), -S::one()] {
+            let towards_middle = theta + gamma * side;
+            let (s, c) = Rad(towards_middle).sin_cos();
+            let middle = Point2::new(S::zero(), S::one()) + Vector2::new(c, s) * two;
+            let goal_center = Point2::new(x - sin, y + cos);
+            let to_goal = goal_center - middle;
+            let t = wrap_positive(towards_middle + half_pi);
+            let p = wrap_positive(t + half_pi - Rad::atan2(to_goal.y, to_goal.x).0);
+            words.push((steers[2], vec![t, p, wrap_positive(phi - t + p)]));
+        }
+    }
+    words
+}
+
+/// The Reeds–Shepp words, as lengths along the unit turning circle, from
+/// the origin facing `x` to `(x, y)` facing `phi`.
+///
+/// These are the formulas of "Optimal paths for a car that goes both
+/// forwards and backwards" by J. A. Reeds and L. A. Shepp, numbered as
+/// there, with the corrections of their implementation in OMPL. Each
+/// formula only covers the paths starting with a left turn forwards, and
+/// the others are found by reversing time, by reflecting across the `x`
+/// axis, or by driving the path backwards from the goal.
+mod reeds_shepp {
+    use num_traits::cast;
+
+    use super::Steer::{Left, Right, Straight};
+    use super::{polar, wrap, Steer, Word};
+    use angle::Rad;
+    use num::BaseFloat;
+    use structure::*;
+
+    /// Whether `x` is positive or zero, up to the rounding of the formulas.
+    fn nonnegative<S: BaseFloat>(x: S) -> bool {
+        let epsilon = S::default_epsilon() * cast(10).unwrap();
+        x >= S::zero() || implicit_ulps_eq!(x, S::zero(), epsilon = epsilon)
+    }
+
+    /// Whether `x` is negative or zero, up to the rounding of the formulas.
+    fn nonpositive<S: BaseFloat>(x: S) -> bool {
+        nonnegative(-x)
+    }
+
+    /// 8.1: left, straight, left.
+    fn lp_sp_lp<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let (sin, cos) = Rad(phi).sin_cos();
+        let (u, t) = polar(x - sin, y - S::one() + cos);
+        let v = wrap(phi - t);
+        if nonnegative(t) && nonnegative(v) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// 8.2: left, straight, right.
+    fn lp_sp_rp<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let four: S = cast(4).unwrap();
+        let (sin, cos) = Rad(phi).sin_cos();
+        let (u1, t1) = polar(x + sin, y - S::one() - cos);
+        let u1 = u1 * u1;
+        if u1 < four {
+            return None;
+        }
+        let u = (u1 - four).sqrt();
+        let t = wrap(t1 + Rad::atan2(cast(2).unwrap(), u).0);
+        let v = wrap(t - phi);
+        if nonnegative(t) && nonnegative(v) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// 8.3: left, then right backwards, then left.
+    fn lp_rm_l<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let (sin, cos) = Rad(phi).sin_cos();
+        let (u1, theta) = polar(x - sin, y - S::one() + cos);
+        if u1 > cast(4).unwrap() {
+            return None;
+        }
+        let half: S = cast(0.5).unwrap();
+        let u = -Rad::asin(u1 / cast(4).unwrap()).0 * cast(2).unwrap();
+        let t = wrap(theta + half * u + Rad::<S>::turn_div_2().0);
+        let v = wrap(phi - t + u);
+        if nonnegative(t) && nonpositive(u) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// The parameters of the last turns of the words with four turns.
+    fn tau_omega<S: BaseFloat>(u: S, v: S, xi: S, eta: S, phi: S) -> (S, S) {
+        let delta = wrap(u - v);
+        let a = Rad(u).sin() - Rad(delta).sin();
+        let b = Rad(u).cos() - Rad(delta).cos() - S::one();
+        let t1 = Rad::atan2(eta * a - xi * b, xi * a + eta * b).0;
+        let two: S = cast(2).unwrap();
+        let t2 = two * (Rad(delta).cos() - Rad(v).cos() - Rad(u).cos()) + cast(3).unwrap();
+        let tau = if t2 < S::zero() {
+            wrap(t1 + Rad::<S>::turn_div_2().0)
+        } else {
+            wrap(t1)
+        };
+        (tau, wrap(tau - u + v - phi))
+    }
+
+    /// 8.7: left, right, then left and right backwards.
+    fn lp_rup_lum_rm<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let (sin, cos) = Rad(phi).sin_cos();
+        let xi = x + sin;
+        let eta = y - S::one() - cos;
+        let rho = (cast::<_, S>(2).unwrap() + xi.hypot(eta)) / cast(4).unwrap();
+        if rho > S::one() {
+            return None;
+        }
+        let u = Rad::acos(rho).0;
+        let (t, v) = tau_omega(u, -u, xi, eta, phi);
+        if nonnegative(t) && nonpositive(v) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// 8.8: left, then right and left backwards, then right.
+    fn lp_rum_lum_rp<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let (sin, cos) = Rad(phi).sin_cos();
+        let xi = x + sin;
+        let eta = y - S::one() - cos;
+        let rho = (cast::<_, S>(20).unwrap() - xi * xi - eta * eta) / cast(16).unwrap();
+        if rho < S::zero() || rho > S::one() {
+            return None;
+        }
+        let u = -Rad::acos(rho).0;
+        if u < -Rad::<S>::turn_div_4().0 {
+            return None;
+        }
+        let (t, v) = tau_omega(u, u, xi, eta, phi);
+        if nonnegative(t) && nonnegative(v) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// 8.9: left, then a quarter turn right and straight backwards, then
+    /// left backwards.
+    fn lp_rm_sm_lm<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let two: S = cast(2).unwrap();
+        let (sin, cos) = Rad(phi).sin_cos();
+        let (rho, theta) = polar(x - sin, y - S::one() + cos);
+        if rho < two {
+            return None;
+        }
+        let r = (rho * rho - cast(4).unwrap()).sqrt();
+        let u = two - r;
+        let t = wrap(theta + Rad::atan2(r, -two).0);
+        let v = wrap(phi - Rad::<S>::turn_div_4().0 - t);
+        if nonnegative(t) && nonpositive(u) && nonpositive(v) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// 8.10: left, then a quarter turn right, straight and right, all
+    /// backwards.
+    fn lp_rm_sm_rm<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let two: S = cast(2).unwrap();
+        let (sin, cos) = Rad(phi).sin_cos();
+        let xi = x + sin;
+        let eta = y - S::one() - cos;
+        let (rho, theta) = polar(-eta, xi);
+        if rho < two {
+            return None;
+        }
+        let t = theta;
+        let u = two - rho;
+        let v = wrap(t + Rad::<S>::turn_div_4().0 - phi);
+        if nonnegative(t) && nonpositive(u) && nonpositive(v) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// 8.11: left, then a quarter turn right, straight and a quarter turn
+    /// left backwards, then right.
+    fn lp_rm_s_lm_rp<S: BaseFloat>(x: S, y: S, phi: S) -> Option<[S; 3]> {
+        let two: S = cast(2).unwrap();
+        let four: S = cast(4).unwrap();
+        let (sin, cos) = Rad(phi).sin_cos();
+        let xi = x + sin;
+        let eta = y - S::one() - cos;
+        let (rho, _) = polar(xi, eta);
+        if rho < two {
+            return None;
+        }
+        let u = four - (rho * rho - four).sqrt();
+        if !nonpositive(u) {
+            return None;
+        }
+        let t = wrap(Rad::atan2((four - u) * xi - two * eta, -two * xi + (u - four) * eta).0);
+        let v = wrap(t - phi);
+        if nonnegative(t) && nonnegative(v) {
+            Some([t, u, v])
+        } else {
+            None
+        }
+    }
+
+    /// Try `formula` as is, with time reversed, reflected across the `x`
+    /// axis, and both, which negate the lengths and swap left and right.
+    fn push_symmetric<S, L>(
+        words: &mut Vec<Word<S>>,
+        formula: fn(S, S, S) -> Option<[S; 3]>,
+        (x, y, phi): (S, S, S),
+        steers: [&'static [Steer]; 2],
+        lengths: L,
+    ) where
+        S: BaseFloat,
+        L: Fn([S; 3]) -> Vec<S>,
+    {
+        let cases = [
+            (x, y, phi, steers[0], S::one()),
+            (-x, y, -phi, steers[0], -S::one()),
+            (x, -y, -phi, steers[1], S::one()),
+            (-x, -y, phi, steers[1], -S::one()),
+        ];
+        for &(x, y, phi, steer, sign) in &cases {
+            if let Some(params) = formula(x, y, phi) {
+                let lengths = lengths(params).into_iter().map(|l| l * sign).collect();
+                words.push((steer, lengths));
+            }
+        }
+    }
+
+    /// The candidate words, the shortest of which is the shortest path.
+    pub fn words<S: BaseFloat>(x: S, y: S, phi: S) -> Vec<Word<S>> {
+        let half_pi = Rad::<S>::turn_div_4().0;
+        let (sin, cos) = Rad(phi).sin_cos();
+        // The start seen from the goal, to drive the words from the goal.
+        let forwards = (x, y, phi);
+        let backwards = (x * cos + y * sin, x * sin - y * cos, phi);
+        let csc = |[t, u, v]: [S; 3]| vec![t, u, v];
+        let reversed = |[t, u, v]: [S; 3]| vec![v, u, t];
+        let ccsc = |[t, u, v]: [S; 3]| vec![t, -half_pi, u, v];
+        let ccsc_reversed = |[t, u, v]: [S; 3]| vec![v, u, -half_pi, t];
+
+        let mut words = Vec::new();
+        let w = &mut words;
+        push_symmetric(
+            w,
+            lp_sp_lp,
+            forwards,
+            [&[Left, Straight, Left], &[Right, Straight, Right]],
+            csc,
+        );
+        push_symmetric(
+            w,
+            lp_sp_rp,
+            forwards,
+            [&[Left, Straight, Right], &[Right, Straight, Left]],
+            csc,
+        );
+
+        let ccc: [&'static [Steer]; 2] = [&[Left, Right, Left], &[Right, Left, Right]];
+        push_symmetric(w, lp_rm_l, forwards, ccc, csc);
+        push_symmetric(w, lp_rm_l, backwards, ccc, reversed);
+
+        let cccc: [&'static [Steer]; 2] =
+            [&[Left, Right, Left, Right], &[Right, Left, Right, Left]];
+        push_symmetric(w, lp_rup_lum_rm, forwards, cccc, |[t, u, v]| {
+            vec![t, u, -u, v]
+        });
+        push_symmetric(w, lp_rum_lum_rp, forwards, cccc, |[t, u, v]| {
+            vec![t, u, u, v]
+        });
+
+        push_symmetric(
+            w,
+            lp_rm_sm_lm,
+            forwards,
+            [
+                &[Left, Right, Straight, Left],
+                &[Right, Left, Straight, Right],
+            ],
+            ccsc,
+        );
+        push_symmetric(
+            w,
+            lp_rm_sm_rm,
+            forwards,
+            [
+                &[Left, Right, Straight, Right],
+                &[Right, Left, Straight, Left],
+            ],
+            ccsc,
+        );
+        push_symmetric(
+            w,
+            lp_rm_sm_lm,
+            backwards,
+            [
+                &[Left, Straight, Right, Left],
+                &[Right, Straight, Left, Right],
+            ],
+            ccsc_reversed,
+        );
+        push_symmetric(
+            w,
+            lp_rm_sm_rm,
+            backwards,
+            [
+                &[Right, Straight, Right, Left],
+                &[Left, Straight, Left, Right],
+            ],
+            ccsc_reversed,
+        );
+
+        push_symmetric(
+            w,
+            lp_rm_s_lm_rp,
+            forwards,
+            [
+                &[Left, Right, Straight, Left, Right],
+                &[Right, Left, Straight, Right, Left],
+            ],
+            |[t, u, v]| vec![t, -half_pi, u, -half_pi, v],
+        );
+        words
+    }
+}
+
+/// The pose reached from `pose` by driving `length`, steering as `steer`
+/// with the turning `radius`.
+fn drive<S: BaseFloat>(pose: &Pose2<S>, steer: Steer, length: S, radius: S) -> Pose2<S> {
+    let (position, heading) = (pose.position(), pose.heading());
+    let forward = |h: Rad<S>| Vector2::new(h.cos(), h.sin());
+    let left = |h: Rad<S>| Vector2::new(-h.sin(), h.cos());
+    let (position, heading) = match steer {
+        Straight => (position + forward(heading) * length, heading),
+        Left => {
+            let center = position + left(heading) * radius;
+            let heading = heading + Rad(length / radius);
+            (center - left(heading) * radius, heading)
+        }
+        Right => {
+            let center = position - left(heading) * radius;
+            let heading = heading - Rad(length / radius);
+            (center + left(heading) * radius, heading)
+        }
+    };
+    Pose2::from_heading(position, heading)
+}
+
+impl<S: BaseFloat> CurvaturePath<S> {
+    /// The path from `start` to `goal` of the lengths along the unit circle
+    /// of the shortest word, scaled to the turning radius.
+    fn shortest(start: &Pose2<S>, radius: S, words: Vec<Word<S>>) -> CurvaturePath<S> {
+        let total = |lengths: &[S]| lengths.iter().fold(S::zero(), |sum, l| sum + l.abs());
+        let (steers, lengths) = words
+            .into_iter()
+            .fold(None, |best: Option<Word<S>>, word| match best {
+                Some(best) if total(&best.1) <= total(&word.1) => Some(best),
+                _ => Some(word),
+            })
+            .expect("a path always exists");
+        CurvaturePath {
+            start: *start,
+            radius,
+            segments: steers
+                .iter()
+                .zip(lengths)
+                .map(|(&steer, length)| PathSegment {
+                    steer,
+                    length: length * radius,
+                })
+                .collect(),
+        }
+    }
+
+    /// The goal seen from the start, in units of the turning radius.
+    fn local_goal(start: &Pose2<S>, goal: &Pose2<S>, radius: S) -> (S, S, S) {
+        let (sin, cos) = Rad::sin_cos(start.heading());
+        let d = (goal.position() - start.position()) / radius;
+        let phi = wrap((goal.heading() - start.heading()).0);
+        (cos * d.x + sin * d.y, cos * d.y - sin * d.x, phi)
+    }
+
+    /// The shortest path from `start` to `goal` driving forwards with the
+    /// turning `radius`, known as a Dubins path.
+    ///
+    /// The path is made of three segments, which turn or go straight
+    /// forwards, some of which may be empty.
+    pub fn dubins(start: &Pose2<S>, goal: &Pose2<S>, radius: S) -> CurvaturePath<S> {
+        let (x, y, phi) = CurvaturePath::local_goal(start, goal, radius);
+        let mut words = dubins_words(
+            x,
+            y,
+            phi,
+            [
+                &[Left, Straight, Left],
+                &[Left, Straight, Right],
+                &[Left, Right, Left],
+            ],
+        );
+        // The paths starting to the right are the mirror images of those
+        // starting to the left.
+        words.extend(dubins_words(
+            x,
+            -y,
+            -phi,
+            [
+                &[Right, Straight, Right],
+                &[Right, Straight, Left],
+                &[Right, Left, Right],
+            ],
+        ));
+        CurvaturePath::shortest(start, radius, words)
+    }
+
+    /// The shortest path from `start` to `goal` with the turning `radius`,
+    /// driving forwards or backwards, known as a Reeds–Shepp path.
+    ///
+    /// The path is made of up to five segments, whose lengths are negative
+    /// where the vehicle reverses.
+    pub fn reeds_shepp(start: &Pose2<S>, goal: &Pose2<S>, radius: S) -> CurvaturePath<S> {
+        let (x, y, phi) = CurvaturePath::local_goal(start, goal, radius);
+        CurvaturePath::shortest(start, radius, reeds_shepp::words(x, y, phi))
+    }
+
+    /// The total distance driven along the path, forwards and backwards.
+    pub fn length(&self) -> S {
+        self.segments
+            .iter()
+            .fold(S::zero(), |sum, s| sum + s.length.abs())
+    }
+
+    /// The pose after driving `distance` along the path, clamped to its
+    /// ends.
+    pub fn pose_at(&self, distance: S) -> Pose2<S> {
+        let mut pose = self.start;
+        let mut remaining = distance.max(S::zero());
+        for segment in &self.segments {
+            let length = segment.length.abs().min(remaining);
+            pose = drive(
+                &pose,
+                segment.steer,
+                length * segment.length.signum(),
+                self.radius,
+            );
+            remaining -= length;
+            if remaining <= S::zero() {
+                break;
+            }
+        }
+        pose
+    }
+
+    /// The pose at the end of the path.
+    pub fn end(&self) -> Pose2<S> {
+        self.segments.iter().fold(self.start, |pose, segment| {
+            drive(&pose, segment.steer, segment.length, self.radius)
+        })
+    }
+
+    /// The segments of the path as arcs and straight lines, in order.
+    pub fn arcs(&self) -> Vec<Arc2<S>> {
+        let mut pose = self.start;
+        self.segments
+            .iter()
+            .map(|segment| {
+                let next = drive(&pose, segment.steer, segment.length, self.radius);
+                let sweep = match segment.steer {
+                    Left => segment.length / self.radius,
+                    Straight => S::zero(),
+                    Right => -segment.length / self.radius,
+                };
+                let arc = Arc2::new(
+                    pose.position(),
+                    next.position(),
+                    Rad(sweep / cast(4).unwrap()).tan(),
+                );
+                pose = next;
+                arc
+            })
+            .collect()
+    }
+
+    /// Approximate the path by a polyline which stays closer than
+    /// `tolerance` to it, such as to follow it or to draw it.
+    pub fn flatten(&self, tolerance: S) -> Polyline2<S> {
+        let mut points = vec![self.start.position()];
+        for arc in self.arcs() {
+            points.extend(arc.flatten(tolerance).points.into_iter().skip(1));
+        }
+        Polyline2::new(points)
+    }
+}
+
+/// The pose of a vehicle after driving `distance` with its front wheels
+/// turned by `steer`, in the kinematic bicycle model.
+///
+/// The pose is the one of the middle of the rear axle, which the vehicle
+/// turns around with the front axle `wheelbase` in front of it. A negative
+/// distance drives backwards.
+pub fn bicycle_step<S, A>(pose: &Pose2<S>, wheelbase: S, steer: A, distance: S) -> Pose2<S>
+where
+    S: BaseFloat,
+    A: Into<Rad<S>>,
+{
+    let tan = Rad::tan(steer.into());
+    if tan == S::zero() {
+        drive(pose, Straight, distance, S::one())
+    } else if tan > S::zero() {
+        drive(pose, Left, distance, wheelbase / tan)
+    } else {
+        drive(pose, Right, distance, -wheelbase / tan)
+    }
+}
+
+/// The steering angle which makes a vehicle at `pose`, with the front axle
+/// `wheelbase` in front of the rear one, follow `path` with the pure pursuit
+/// controller, or `None` if the path has no vertices.
+///
+/// The vehicle steers along the arc from its rear axle to the point of the
+/// path `lookahead` further than the closest one. Longer lookaheads follow
+/// the path more smoothly but cut its corners, and are usually taken in
+/// proportion with the speed. The angle is positive to the left.
+///
+/// ```rust
+/// use cgmath::vehicle::{bicycle_step, pure_pursuit};
+/// use cgmath::{Deg, Point2, Polyline2, Pose2};
+///
+/// // Joining a straight road from its side.
+/// let road = Polyline2::new(vec![Point2::new(0.0, 0.0), Point2::new(100.0, 0.0f64)]);
+/// let mut car = Pose2::from_heading(Point2::new(0.0, 3.0), Deg(0.0));
+/// for _ in 0..200 {
+///     let steer = pure_pursuit(&car, 2.5, &road, 5.0).unwrap();
+///     car = bicycle_step(&car, 2.5, steer, 0.25);
+/// }
+/// assert!(car.position().y.abs() < 0.01);
+/// ```
+pub fn pure_pursuit<S: BaseFloat>(
+    pose: &Pose2<S>,
+    wheelbase: S,
+    path: &Polyline2<S>,
+    lookahead: S,
+) -> Option<Rad<S>> {
+    let position = pose.position();
+    let target = path.lookahead(position, lookahead)?;
+    let to_target = target - position;
+    let distance = to_target.magnitude();
+    if distance == S::zero() {
+        return Some(Rad(S::zero()));
+    }
+    let alpha = Rad::atan2(to_target.y, to_target.x) - pose.heading();
+    let two: S = cast(2).unwrap();
+    Some(Rad::atan(two * wheelbase * Rad::sin(alpha) / distance))
+}
+
+/// The steering angle which makes a vehicle at `pose`, with the front axle
+/// `wheelbase` in front of the rear one, driving at `speed`, follow `path`
+/// with the Stanley controller, or `None` if the path has no segments.
+///
+/// The front wheels are turned parallel to the closest segment of the path
+/// to the front axle, and further towards the path by the arc tangent of the
+/// distance to it times `gain`, divided by the speed plus `softening`, which
+/// keeps the steering gentle at low speeds. The angle is positive to the
+/// left.
+///
+/// ```rust
+/// use cgmath::vehicle::{bicycle_step, stanley};
+/// use cgmath::{Deg, Point2, Polyline2, Pose2};
+///
+/// // Joining a straight road from its side.
+/// let road = Polyline2::new(vec![Point2::new(0.0, 0.0), Point2::new(100.0, 0.0f64)]);
+/// let mut car = Pose2::from_heading(Point2::new(0.0, -3.0), Deg(0.0));
+/// for _ in 0..200 {
+///     let steer = stanley(&car, 2.5, 10.0, &road, 2.0, 1.0).unwrap();
+///     car = bicycle_step(&car, 2.5, steer, 0.25);
+/// }
+/// assert!(car.position().y.abs() < 0.01);
+/// ```
+pub fn stanley<S: BaseFloat>(
+    pose: &Pose2<S>,
+    wheelbase: S,
+    speed: S,
+    path: &Polyline2<S>,
+    gain: S,
+    softening: S,
+) -> Option<Rad<S>> {
+    let heading = pose.heading();
+    let front = pose.position() + Vector2::new(heading.cos(), heading.sin()) * wheelbase;
+    let (i, t) = path.closest_segment(front)?;
+    let (a, b) = (path.points[i], path.points[i + 1]);
+    let tangent = b - a;
+    if tangent.magnitude2() == S::zero() {
+        return Some(Rad(S::zero()));
+    }
+    // Positive when the path is to the left of the front axle.
+    let error = -tangent.normalize().perp_dot(front - (a + tangent * t));
+    let heading_error = (Rad::atan2(tangent.y, tangent.x) - heading).normalize_signed();
+    Some(heading_error + Rad::atan2(gain * error, speed.abs() + softening))
+}
@@ -0,0 +1,272 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Extended precision positions and camera-relative rendering for large worlds.
+
+use std::ops::*;
+
+use structure::*;
+
+use matrix::{Matrix3, Matrix4};
+use point::Point3;
+use vector::{Vector3, Vector4};
+
+/// A position stored as the unevaluated sum of two single precision points,
+/// also known as a double-single or double-float representation.
+///
+/// Single precision coordinates lose sub-millimetre accuracy a few kilometres
+/// away from the origin. Splitting each coordinate into a `high` part and a
+/// `low` correction gives about 48 bits of mantissa while keeping every
+/// component an `f32`, so positions can still be uploaded to the GPU as two
+/// vertex attributes. The difference between two nearby world points,
+/// computed with `relative_to`, is accurate even when both are very far from
+/// the origin.
+///
+/// ```rust
+/// use cgmath::{Point3, Vector3, WorldPoint3};
+///
+/// let camera = WorldPoint3::from(Point3::new(1.0e7, 0.0, -2.0e7));
+/// let object = WorldPoint3::from(Point3::new(1.0e7 + 0.125, 1.5, -2.0e7));
+///
+/// // Converting each point to `f32` first would round the offset away.
+/// assert_eq!(object.relative_to(camera), Vector3::new(0.125, 1.5, 0.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WorldPoint3 {
+    /// The position rounded to single precision.
+    pub high: Point3<f32>,
+    /// The rounding error of `high`, which is always small compared to it.
+    pub low: Vector3<f32>,
+}
+
+/// Add two floats, returning the rounded sum and its exact rounding error.
+#[inline]
+fn two_sum(a: f32, b: f32) -> (f32, f32) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// Renormalize a sum where `|a| >= |b|`, so that the error term does not
+/// overlap the high part.
+#[inline]
+fn quick_two_sum(a: f32, b: f32) -> (f32, f32) {
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+#[inline]
+fn add_component(high: f32, low: f32, v: f32) -> (f32, f32) {
+    let (s, e) = two_sum(high, v);
+    quick_two_sum(s, e + low)
+}
+
+impl WorldPoint3 {
+    /// Construct a world point from a double precision position.
+    #[inline]
+    pub fn new(x: f64, y: f64, z: f64) -> WorldPoint3 {
+        let high = Point3::new(x as f32, y as f32, z as f32);
+        let low = Vector3::new(
+            (x - high.x as f64) as f32,
+            (y - high.y as f64) as f32,
+            (z - high.z as f64) as f32,
+        );
+        WorldPoint3 { high, low }
+    }
+
+    /// The origin of the world.
+    #[inline]
+    pub fn origin() -> WorldPoint3 {
+        WorldPoint3::new(0.0, 0.0, 0.0)
+    }
+
+    /// Convert the position back to double precision.
+    #[inline]
+    pub fn to_f64(self) -> Point3<f64> {
+        Point3::new(
+            self.high.x as f64 + self.low.x as f64,
+            self.high.y as f64 + self.low.y as f64,
+            self.high.z as f64 + self.low.z as f64,
+        )
+    }
+
+    /// The position of this point relative to `origin`, in single precision.
+    ///
+    /// The high and low parts are subtracted separately, so the result is
+    /// accurate as long as the two points are reasonably close to each other,
+    /// however far they are from the world origin.
+    #[inline]
+    pub fn relative_to(self, origin: WorldPoint3) -> Vector3<f32> {
+        (self.high - origin.high) + (self.low - origin.low)
+    }
+}
+
+impl From<Point3<f64>> for WorldPoint3 {
+    #[inline]
+    fn from(p: Point3<f64>) -> WorldPoint3 {
+        WorldPoint3::new(p.x, p.y, p.z)
+    }
+}
+
+impl From<WorldPoint3> for Point3<f64> {
+    #[inline]
+    fn from(p: WorldPoint3) -> Point3<f64> {
+        p.to_f64()
+    }
+}
+
+impl From<Point3<f32>> for WorldPoint3 {
+    #[inline]
+    fn from(p: Point3<f32>) -> WorldPoint3 {
+        WorldPoint3 {
+            high: p,
+            low: Vector3::new(0.0, 0.0, 0.0),
+        }
+    }
+}
+
+impl Add<Vector3<f32>> for WorldPoint3 {
+    type Output = WorldPoint3;
+
+    #[inline]
+    fn add(self, v: Vector3<f32>) -> WorldPoint3 {
+        let (hx, lx) = add_component(self.high.x, self.low.x, v.x);
+        let (hy, ly) = add_component(self.high.y, self.low.y, v.y);
+        let (hz, lz) = add_component(self.high.z, self.low.z, v.z);
+        WorldPoint3 {
+            high: Point3::new(hx, hy, hz),
+            low: Vector3::new(lx, ly, lz),
+        }
+    }
+}
+
+impl Sub<Vector3<f32>> for WorldPoint3 {
+    type Output = WorldPoint3;
+
+    #[inline]
+    fn sub(self, v: Vector3<f32>) -> WorldPoint3 {
+        self + -v
+    }
+}
+
+impl AddAssign<Vector3<f32>> for WorldPoint3 {
+    #[inline]
+    fn add_assign(&mut self, v: Vector3<f32>) {
+        *self = *self + v;
+    }
+}
+
+impl SubAssign<Vector3<f32>> for WorldPoint3 {
+    #[inline]
+    fn sub_assign(&mut self, v: Vector3<f32>) {
+        *self = *self - v;
+    }
+}
+
+impl Sub<WorldPoint3> for WorldPoint3 {
+    type Output = Vector3<f32>;
+
+    #[inline]
+    fn sub(self, other: WorldPoint3) -> Vector3<f32> {
+        self.relative_to(other)
+    }
+}
+
+/// A camera placed in a double precision world, used to build single
+/// precision matrices relative to the camera position.
+///
+/// Rendering far from the origin jitters when the model and view matrices are
+/// rounded to `f32` separately and multiplied on the GPU, because both contain
+/// huge translations that only cancel out after rounding. Composing them in
+/// `f64` first, with the camera translation folded in, leaves only the small
+/// camera-relative offset to round.
+///
+/// ```rust
+/// use cgmath::{CameraRelative, Matrix4, Point3, Vector3};
+///
+/// let eye = Point3::new(4.0e6, 10.0, -7.0e6);
+/// let camera = CameraRelative::look_at(eye, eye + Vector3::unit_z(), Vector3::unit_y());
+///
+/// let model = Matrix4::from_translation(Vector3::new(4.0e6 + 0.25, 10.0, -7.0e6 + 3.0));
+/// let model_view: Matrix4<f32> = camera.model_view(model);
+/// assert_eq!(model_view.w.truncate(), Vector3::new(-0.25, 0.0, -3.0));
+/// ```
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CameraRelative {
+    /// The position of the camera in the world.
+    pub eye: Point3<f64>,
+    /// The view matrix of the camera with its translation removed.
+    pub rotation: Matrix4<f64>,
+}
+
+impl CameraRelative {
+    /// Create a camera from its position and a view orientation, given as a
+    /// view matrix without translation.
+    #[inline]
+    pub fn new(eye: Point3<f64>, rotation: Matrix4<f64>) -> CameraRelative {
+        CameraRelative { eye, rotation }
+    }
+
+    /// Create a camera at `eye` looking at `center`, using `up` for
+    /// orientation, in the same way as `Matrix4::look_at`.
+    pub fn look_at(eye: Point3<f64>, center: Point3<f64>, up: Vector3<f64>) -> CameraRelative {
+        CameraRelative::new(
+            eye,
+            Matrix4::look_at_dir(Point3::origin(), center - eye, up),
+        )
+    }
+
+    /// Split a rigid view matrix into the camera position and orientation.
+    pub fn from_view(view: Matrix4<f64>) -> CameraRelative {
+        let rotation = Matrix3::from_cols(view.x.truncate(), view.y.truncate(), view.z.truncate());
+        let eye = Point3::from_vec(-(rotation.transpose() * view.w.truncate()));
+        let mut rotation = view;
+        rotation.w = Vector4::new(0.0, 0.0, 0.0, 1.0);
+        CameraRelative::new(eye, rotation)
+    }
+
+    /// The full view matrix of the camera, in double precision.
+    #[inline]
+    pub fn view(&self) -> Matrix4<f64> {
+        self.rotation * Matrix4::from_translation(-self.eye.to_vec())
+    }
+
+    /// The view matrix to use with camera-relative positions, which only
+    /// contains the orientation of the camera.
+    #[inline]
+    pub fn relative_view(&self) -> Matrix4<f32> {
+        self.rotation.to_f32()
+    }
+
+    /// The position of a world point relative to the camera.
+    #[inline]
+    pub fn position(&self, p: Point3<f64>) -> Point3<f32> {
+        p.relative_to(self.eye)
+    }
+
+    /// The model matrix of a world transform, translated so that the camera
+    /// sits at the origin. Use it with `relative_view`.
+    pub fn model<T: Into<Matrix4<f64>>>(&self, model: T) -> Matrix4<f32> {
+        (Matrix4::from_translation(-self.eye.to_vec()) * model.into()).to_f32()
+    }
+
+    /// The model-view matrix of a world transform, composed in double
+    /// precision before being rounded.
+    pub fn model_view<T: Into<Matrix4<f64>>>(&self, model: T) -> Matrix4<f32> {
+        (self.view() * model.into()).to_f32()
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::*;
+
+use matrix::{Matrix3, Matrix4};
+use num::BaseFloat;
+use point::{Point2, Point3};
+use rotation::Rotation;
+use transform::{Decomposed, Transform};
+use vector::{Vector2, Vector3};
+
+/// A transform tagged with the coordinate spaces it maps from and to.
+///
+/// The tags are only used at compile time, and can be any type, usually an
+/// empty enum per space. Composing two transforms with `*` only type-checks
+/// when the destination of the right-hand side is the source of the
+/// left-hand side, like `world_from_model * model_from_local`, and inverting
+/// a transform swaps its spaces. The underlying transform, which can be a
+/// `Matrix3`, a `Matrix4` or a `Decomposed`, is available as the public
+/// `transform` field for when the tags get in the way.
+///
+/// ```rust
+/// use cgmath::{Matrix4, Point3, Vector3, Xform};
+///
+/// enum World {}
+/// enum Model {}
+/// enum Camera {}
+///
+/// let world_from_model: Xform<Model, World, _> =
+///     Xform::new(Matrix4::from_translation(Vector3::new(0.0f64, 0.0, -5.0)));
+/// let camera_from_world: Xform<World, Camera, _> = Xform::new(Matrix4::from_scale(2.0));
+///
+/// let camera_from_model = camera_from_world * world_from_model;
+/// let p = camera_from_model.transform_point(Point3::new(1.0, 0.0, 0.0));
+/// assert_eq!(p, Point3::new(2.0, 0.0, -10.0));
+/// ```
+///
+/// Composing in the wrong order is a type error:
+///
+/// ```compile_fail
+/// use cgmath::{Matrix4, Xform};
+///
+/// enum World {}
+/// enum Model {}
+/// enum Camera {}
+///
+/// let world_from_model: Xform<Model, World, _> = Xform::new(Matrix4::from_scale(2.0f64));
+/// let camera_from_world: Xform<World, Camera, _> = Xform::new(Matrix4::from_scale(3.0f64));
+/// let nonsense = world_from_model * camera_from_world;
+/// ```
+pub struct Xform<Src, Dst, T> {
+    /// The untagged transform.
+    pub transform: T,
+    spaces: PhantomData<fn(Src) -> Dst>,
+}
+
+impl<Src, Dst, T> Xform<Src, Dst, T> {
+    /// Tag a transform with its spaces.
+    #[inline]
+    pub fn new(transform: T) -> Xform<Src, Dst, T> {
+        Xform {
+            transform,
+            spaces: PhantomData,
+        }
+    }
+
+    /// Change the tags of the transform, for interfacing with code that
+    /// disagrees on which spaces it maps between.
+    #[inline]
+    pub fn cast_spaces<NewSrc, NewDst>(self) -> Xform<NewSrc, NewDst, T> {
+        Xform::new(self.transform)
+    }
+}
+
+impl<Src, Dst, T: Copy> Copy for Xform<Src, Dst, T> {}
+
+impl<Src, Dst, T: Clone> Clone for Xform<Src, Dst, T> {
+    #[inline]
+    fn clone(&self) -> Xform<Src, Dst, T> {
+        Xform::new(self.transform.clone())
+    }
+}
+
+impl<Src, Dst, T: PartialEq> PartialEq for Xform<Src, Dst, T> {
+    #[inline]
+    fn eq(&self, other: &Xform<Src, Dst, T>) -> bool {
+        self.transform == other.transform
+    }
+}
+
+impl<Src, Dst, T: fmt::Debug> fmt::Debug for Xform<Src, Dst, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Xform ")?;
+        self.transform.fmt(f)
+    }
+}
+
+macro_rules! impl_xform {
+    (<$($param:ident: $Bound:path),+> $T:ty, $P:ty, $V:ty) => {
+        impl<Src, Dst, $($param: $Bound),+> Xform<Src, Dst, $T> {
+            /// Transform a point from the source space to the destination
+            /// space.
+            #[inline]
+            pub fn transform_point(&self, point: $P) -> $P {
+                Transform::<$P>::transform_point(&self.transform, point)
+            }
+
+            /// Transform a vector from the source space to the destination
+            /// space.
+            #[inline]
+            pub fn transform_vector(&self, vec: $V) -> $V {
+                Transform::<$P>::transform_vector(&self.transform, vec)
+            }
+
+            /// The transform from the destination space back to the source
+            /// space, if it exists.
+            #[inline]
+            pub fn inverse(&self) -> Option<Xform<Dst, Src, $T>> {
+                Transform::<$P>::inverse_transform(&self.transform).map(Xform::new)
+            }
+        }
+
+        impl<Space, $($param: $Bound),+> Xform<Space, Space, $T> {
+            /// The identity transform of a space.
+            #[inline]
+            pub fn identity() -> Xform<Space, Space, $T> {
+                Xform::new(Transform::<$P>::one())
+            }
+        }
+
+        impl<A, B, C, $($param: $Bound),+> Mul<Xform<A, B, $T>> for Xform<B, C, $T> {
+            type Output = Xform<A, C, $T>;
+
+            /// Apply `rhs`, then this transform.
+            #[inline]
+            fn mul(self, rhs: Xform<A, B, $T>) -> Xform<A, C, $T> {
+                Xform::new(Transform::<$P>::concat(&self.transform, &rhs.transform))
+            }
+        }
+    };
+}
+
+impl_xform!(<S: BaseFloat> Matrix3<S>, Point2<S>, Vector2<S>);
+impl_xform!(<S: BaseFloat> Matrix4<S>, Point3<S>, Vector3<S>);
+impl_xform!(
+    <S: BaseFloat, R: Rotation<Point2<S>>> Decomposed<Vector2<S>, R>,
+    Point2<S>,
+    Vector2<S>
+);
+impl_xform!(
+    <S: BaseFloat, R: Rotation<Point3<S>>> Decomposed<Vector3<S>, R>,
+    Point3<S>,
+    Vector3<S>
+);
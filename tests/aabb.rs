@@ -0,0 +1,217 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_new_sorts_corners() {
+    let aabb = Aabb2::new(Point2::new(3, -1), Point2::new(-2, 4));
+    assert_eq!(aabb.min, Point2::new(-2, -1));
+    assert_eq!(aabb.max, Point2::new(3, 4));
+}
+
+#[test]
+fn test_from_points_2d() {
+    let points = vec![
+        Point2::new(1.0f32, 1.0),
+        Point2::new(-3.0, 2.0),
+        Point2::new(2.0, -5.0),
+    ];
+    let aabb = Aabb2::from_points(points).unwrap();
+    assert_eq!(aabb.min, Point2::new(-3.0, -5.0));
+    assert_eq!(aabb.max, Point2::new(2.0, 2.0));
+    assert_eq!(aabb.dim(), Vector2::new(5.0, 7.0));
+    assert_eq!(aabb.volume(), 35.0);
+    assert_eq!(aabb.center(), Point2::new(-0.5, -1.5));
+}
+
+#[test]
+fn test_from_points_3d() {
+    let points = [
+        Point3::new(0.0f64, 1.0, 2.0),
+        Point3::new(4.0, -1.0, 0.0),
+        Point3::new(1.0, 0.0, 6.0),
+    ];
+    let aabb = Aabb3::from_points(points.iter().cloned()).unwrap();
+    assert_eq!(aabb, Aabb3::new(Point3::new(0.0, -1.0, 0.0), Point3::new(4.0, 1.0, 6.0)));
+    assert_eq!(aabb.volume(), 48.0);
+}
+
+#[test]
+fn test_from_points_empty() {
+    assert_eq!(Aabb3::<f32>::from_points(Vec::new()), None);
+}
+
+#[test]
+fn test_grow() {
+    let aabb = Aabb3::from_point(Point3::new(1, 1, 1)).grow(Point3::new(0, 2, 1));
+    assert_eq!(aabb.min, Point3::new(0, 1, 1));
+    assert_eq!(aabb.max, Point3::new(1, 2, 1));
+}
+
+#[test]
+fn test_intersection() {
+    let rect = |x0, y0, x1, y1| Aabb2::new(Point2::new(x0, y0), Point2::new(x1, y1));
+    let scissor = rect(10, 10, 50, 40);
+    assert_eq!(
+        scissor.intersection(&rect(0, 20, 30, 100)),
+        Some(rect(10, 20, 30, 40))
+    );
+    assert_eq!(
+        scissor.intersection(&rect(20, 20, 30, 30)),
+        Some(rect(20, 20, 30, 30))
+    );
+    assert_eq!(
+        scissor.intersection(&rect(50, 0, 60, 10)),
+        Some(rect(50, 10, 50, 10))
+    );
+    assert_eq!(scissor.intersection(&rect(51, 0, 60, 10)), None);
+
+    let a = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 2.0));
+    let b = Aabb3::new(Point3::new(1.0, -1.0, 1.5), Point3::new(3.0, 1.0, 4.0));
+    let expected = Aabb3::new(Point3::new(1.0, 0.0, 1.5), Point3::new(2.0, 1.0, 2.0));
+    assert_eq!(a.intersection(&b), Some(expected));
+    assert_eq!(b.intersection(&a), Some(expected));
+}
+
+#[test]
+fn test_clamped_to() {
+    let rect = |x0, y0, x1, y1| Aabb2::new(Point2::new(x0, y0), Point2::new(x1, y1));
+    let surface = rect(0, 0, 640, 480);
+    assert_eq!(
+        rect(-10, 100, 700, 200).clamped_to(&surface),
+        rect(0, 100, 640, 200)
+    );
+    assert_eq!(
+        rect(10, 20, 30, 40).clamped_to(&surface),
+        rect(10, 20, 30, 40)
+    );
+    assert_eq!(
+        rect(700, -50, 800, -10).clamped_to(&surface),
+        rect(640, 0, 640, 0)
+    );
+}
+
+#[test]
+fn test_union() {
+    let a = Aabb2::new(Point2::new(0, 5), Point2::new(10, 6));
+    let b = Aabb2::new(Point2::new(-3, 0), Point2::new(2, 2));
+    let expected = Aabb2::new(Point2::new(-3, 0), Point2::new(10, 6));
+    assert_eq!(a.union(&b), expected);
+    assert_eq!(b.union(&a), expected);
+    assert_eq!(a.union(&a), a);
+}
+
+#[test]
+fn test_centroid_of() {
+    let points = [
+        Point3::new(0.0f64, 0.0, 0.0),
+        Point3::new(3.0, 0.0, 0.0),
+        Point3::new(0.0, 6.0, 3.0),
+    ];
+    let centroid = Point3::centroid_of(points.iter().cloned()).unwrap();
+    assert_ulps_eq!(centroid, Point3::new(1.0, 2.0, 1.0));
+    assert_ulps_eq!(centroid, Point3::centroid(&points));
+    assert_eq!(Point3::<f64>::centroid_of(Vec::new()), None);
+}
+
+#[test]
+fn test_extent_along() {
+    let points = [
+        Point3::new(1.0f64, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(-1.0, -1.0, 0.0),
+    ];
+    let axis = Vector3::new(1.0, 1.0, 0.0).normalize();
+    let (min, max) = Point3::extent_along(points.iter().cloned(), axis).unwrap();
+    assert_ulps_eq!(min, -(2.0f64.sqrt()));
+    assert_ulps_eq!(max, 0.5f64.sqrt());
+    assert_eq!(Point3::extent_along(Vec::new(), axis), None);
+}
+
+#[test]
+fn test_transformed() {
+    let aabb = Aabb3::new(Point3::new(-1.0, 0.0, 2.0), Point3::new(3.0, 1.0, 2.5));
+    let m = Matrix4::from_translation(Vector3::new(1.0, -2.0, 0.5))
+        * Matrix4::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalize(), Deg(35.0))
+        * Matrix4::from_nonuniform_scale(2.0, 1.0, -0.5);
+
+    let expected =
+        Aabb3::from_points(aabb.corners().iter().map(|&p| m.transform_point(p))).unwrap();
+    assert_relative_eq!(aabb.transformed(&m), expected, epsilon = 1e-12);
+}
+
+#[test]
+fn test_screen_bounds() {
+    let view_projection = perspective(Deg(90.0), 2.0, 0.1, 100.0)
+        * Matrix4::look_at(
+            Point3::new(0.0, 0.0, 5.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Vector3::unit_y(),
+        );
+    let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0));
+
+    let bounds = aabb.screen_bounds(&view_projection).unwrap();
+    assert_relative_eq!(bounds.min, Point2::new(-0.125, -0.25), epsilon = 1e-12);
+    assert_relative_eq!(bounds.max, Point2::new(0.125, 0.25), epsilon = 1e-12);
+
+    let screen = Aabb2::new(Point2::new(-1.0, -1.0), Point2::new(1.0, 1.0));
+    let around_eye = Aabb3::new(Point3::new(-1.0, -1.0, 4.0), Point3::new(1.0, 1.0, 6.0));
+    assert_eq!(around_eye.screen_bounds(&view_projection), Some(screen));
+    let behind = Aabb3::new(Point3::new(-1.0, -1.0, 6.0), Point3::new(1.0, 1.0, 8.0));
+    assert_eq!(behind.screen_bounds(&view_projection), None);
+    let aside = Aabb3::new(Point3::new(20.0, -1.0, -1.0), Point3::new(22.0, 1.0, 1.0));
+    assert_eq!(aside.screen_bounds(&view_projection), None);
+}
+
+#[test]
+fn test_projected_area() {
+    let aabb = Aabb3::new(Point3::new(-1.0, 0.0, 2.0), Point3::new(1.0, 3.0, 6.0));
+    assert_eq!(aabb.projected_area(Vector3::unit_x()), 12.0);
+    assert_eq!(aabb.projected_area(-Vector3::unit_y()), 8.0);
+    assert_eq!(aabb.projected_area(Vector3::new(0.0, 0.0, 2.0)), 6.0);
+
+    let diagonal = aabb.projected_area(Vector3::new(1.0, 1.0, 1.0));
+    assert_relative_eq!(diagonal, 26.0 / 3.0f64.sqrt(), epsilon = 1e-12);
+}
+
+#[test]
+fn test_to_wireframe_lines() {
+    let aabb = Aabb2::new(Point2::new(0, 0), Point2::new(2, 1));
+    assert_eq!(
+        aabb.to_wireframe_lines(),
+        vec![
+            (Point2::new(0, 0), Point2::new(2, 0)),
+            (Point2::new(2, 0), Point2::new(2, 1)),
+            (Point2::new(2, 1), Point2::new(0, 1)),
+            (Point2::new(0, 1), Point2::new(0, 0)),
+        ]
+    );
+
+    let aabb = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 3.0));
+    let lines = aabb.to_wireframe_lines();
+    assert_eq!(lines.len(), 12);
+    for &(a, b) in &lines {
+        // Every edge is parallel to an axis.
+        let d = b - a;
+        assert_eq!(
+            (d.x != 0.0) as u8 + (d.y != 0.0) as u8 + (d.z != 0.0) as u8,
+            1
+        );
+    }
+}
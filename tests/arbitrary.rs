@@ -0,0 +1,95 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "rand")]
+
+extern crate cgmath;
+extern crate rand;
+
+use cgmath::arbitrary::*;
+use cgmath::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const SAMPLES: usize = 10_000;
+
+#[test]
+fn test_unit_vectors() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut sum = Vector3::zero();
+    for _ in 0..SAMPLES {
+        let v: Vector3<f64> = rng.sample(UnitVector);
+        assert!((v.magnitude() - 1.0).abs() < 1.0e-12);
+        sum += v;
+    }
+    assert!((sum / SAMPLES as f64).magnitude() < 0.05);
+
+    let v: Vector2<f64> = rng.sample(UnitVector);
+    assert!((v.magnitude() - 1.0).abs() < 1.0e-12);
+}
+
+#[test]
+fn test_uniform_rotations() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut squares = Vector4::zero();
+    let mut axis_sum = Vector3::zero();
+    for _ in 0..SAMPLES {
+        let q: Quaternion<f64> = rng.sample(UniformRotation);
+        assert!((q.magnitude() - 1.0).abs() < 1.0e-12);
+        squares += Vector4::new(q.s * q.s, q.v.x * q.v.x, q.v.y * q.v.y, q.v.z * q.v.z);
+        axis_sum += q.rotate_vector(Vector3::unit_x());
+    }
+    // Every component of a uniformly distributed unit quaternion has the
+    // same variance.
+    let squares = squares / SAMPLES as f64;
+    for i in 0..4 {
+        assert!((squares[i] - 0.25).abs() < 0.02);
+    }
+    assert!((axis_sum / SAMPLES as f64).magnitude() < 0.05);
+
+    let b: Basis3<f64> = rng.sample(UniformRotation);
+    assert!((b.as_ref().determinant() - 1.0).abs() < 1.0e-12);
+    let b: Basis2<f64> = rng.sample(UniformRotation);
+    assert!((b.as_ref().determinant() - 1.0).abs() < 1.0e-12);
+}
+
+#[test]
+fn test_in_cube() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let cube = InCube::new(5.0f64);
+    for _ in 0..1000 {
+        let p: Point3<f64> = rng.sample(cube);
+        assert!(p.x.abs() <= 5.0 && p.y.abs() <= 5.0 && p.z.abs() <= 5.0);
+        let v: Vector2<f64> = rng.sample(cube);
+        assert!(v.x.abs() <= 5.0 && v.y.abs() <= 5.0);
+
+        let aabb: Aabb3<f64> = rng.sample(cube);
+        assert!(aabb.min.x <= aabb.max.x && aabb.min.y <= aabb.max.y && aabb.min.z <= aabb.max.z);
+        let aabb: Aabb2<f64> = rng.sample(cube);
+        assert!(aabb.min.x <= aabb.max.x && aabb.min.y <= aabb.max.y);
+
+        let ray: Ray3<f64> = rng.sample(cube);
+        assert!((ray.direction.magnitude() - 1.0).abs() < 1.0e-12);
+        let ray: Ray2<f64> = rng.sample(cube);
+        assert!((ray.direction.magnitude() - 1.0).abs() < 1.0e-12);
+
+        let sphere: Sphere<f64> = rng.sample(cube);
+        assert!(sphere.radius > 0.0 && sphere.radius <= 5.0);
+
+        let t: Decomposed<Vector3<f64>, Quaternion<f64>> = rng.sample(cube);
+        assert!(t.scale >= 0.5 && t.scale <= 2.0);
+        assert!(t.disp.x.abs() <= 5.0);
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_arc_geometry() {
+    let half = Arc2::new(Point2::new(1.0, 0.0), Point2::new(-1.0, 0.0), 1.0f64);
+    assert_eq!(half.sweep(), Rad(std::f64::consts::PI));
+    assert_relative_eq!(half.length(), std::f64::consts::PI);
+    assert_relative_eq!(
+        half.start_tangent(),
+        Vector2::new(0.0, 1.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(half.end_tangent(), Vector2::new(0.0, -1.0), epsilon = 1e-12);
+
+    // A clockwise quarter circle around (1, 1).
+    let quarter = Arc2::new(
+        Point2::new(0.0, 1.0),
+        Point2::new(1.0, 2.0),
+        (-std::f64::consts::PI / 8.0).tan(),
+    );
+    assert_relative_eq!(
+        quarter.center().unwrap(),
+        Point2::new(1.0, 1.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(quarter.radius().unwrap(), 1.0, epsilon = 1e-12);
+    let middle = quarter.point_at(0.5);
+    let expected = Point2::new(1.0, 1.0) + Vector2::new(-1.0, 1.0).normalize();
+    assert_relative_eq!(middle, expected, epsilon = 1e-12);
+    assert_eq!(quarter.point_at(0.0), quarter.start);
+    assert_relative_eq!(quarter.point_at(1.0), quarter.end, epsilon = 1e-12);
+
+    let reversed = quarter.reversed();
+    assert_relative_eq!(
+        reversed.center().unwrap(),
+        Point2::new(1.0, 1.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        reversed.point_at(0.25),
+        quarter.point_at(0.75),
+        epsilon = 1e-12
+    );
+
+    let line = Arc2::line(Point2::new(0.0, 0.0), Point2::new(3.0, 4.0f64));
+    assert!(line.is_straight());
+    assert_eq!(line.center(), None);
+    assert_eq!(line.length(), 5.0);
+    assert_eq!(line.point_at(0.5), Point2::new(1.5, 2.0));
+}
+
+#[test]
+fn test_arc_from_start_tangent() {
+    let start = Point2::new(0.5, -1.0);
+    for &(tangent, end) in &[
+        (Vector2::new(1.0, 0.0), Point2::new(2.0, 1.0)),
+        (Vector2::new(0.0, 1.0), Point2::new(2.0, 1.0)),
+        (Vector2::new(-1.0, 1.0), Point2::new(-1.0, -3.0f64)),
+    ] {
+        let arc = Arc2::from_start_tangent(start, tangent, end);
+        assert_relative_eq!(arc.start_tangent(), tangent.normalize(), epsilon = 1e-12);
+        let center = arc.center().unwrap();
+        let radius = arc.radius().unwrap();
+        assert_relative_eq!(start.distance(center), radius, epsilon = 1e-12);
+        assert_relative_eq!(end.distance(center), radius, epsilon = 1e-12);
+    }
+    let straight = Arc2::from_start_tangent(start, Vector2::new(2.0, 0.0), Point2::new(3.0, -1.0));
+    assert!(straight.is_straight());
+}
+
+#[test]
+fn test_biarc() {
+    let start = Point2::new(0.0, 0.0);
+    let end = Point2::new(4.0, 1.0f64);
+    for &(t0, t1) in &[
+        (Vector2::new(1.0, 1.0), Vector2::new(1.0, -1.0)),
+        (Vector2::new(1.0, 1.0), Vector2::new(1.0, 1.0)),
+        (Vector2::new(0.0, 1.0), Vector2::new(0.0, -1.0)),
+        (Vector2::new(0.0, 1.0), Vector2::new(0.0, 1.0)),
+    ] {
+        let (first, second) = Arc2::biarc(start, t0, end, t1);
+        assert_eq!(first.start, start);
+        assert_eq!(second.end, end);
+        assert_eq!(first.end, second.start);
+        assert_relative_eq!(first.start_tangent(), t0.normalize(), epsilon = 1e-12);
+        assert_relative_eq!(second.end_tangent(), t1.normalize(), epsilon = 1e-12);
+        assert_relative_eq!(first.end_tangent(), second.start_tangent(), epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_arc_distance_and_flatten() {
+    let arc = Arc2::new(
+        Point2::new(1.0, 0.0),
+        Point2::new(0.0, 1.0),
+        (std::f64::consts::PI / 8.0).tan(),
+    );
+    assert_relative_eq!(
+        arc.distance(Point2::new(2.0, 2.0)),
+        8f64.sqrt() - 1.0,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(arc.distance(Point2::new(0.0, 0.0)), 1.0, epsilon = 1e-12);
+    // Past the ends, the closest point is an end point.
+    assert_relative_eq!(
+        arc.distance(Point2::new(2.0, -1.0)),
+        2f64.sqrt(),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        arc.distance(Point2::new(-1.0, -1.0)),
+        5f64.sqrt(),
+        epsilon = 1e-12
+    );
+
+    for &tolerance in &[0.1, 1e-3] {
+        let polyline = arc.flatten(tolerance);
+        assert_eq!(polyline.points[0], arc.start);
+        assert_eq!(*polyline.points.last().unwrap(), arc.end);
+        for i in 0..=100 {
+            let p = arc.point_at(i as f64 / 100.0);
+            let (closest, _) = polyline.project(p).unwrap();
+            assert!(p.distance(closest) <= tolerance);
+        }
+    }
+    assert_eq!(arc.flatten(1e-3).points.len(), 19);
+}
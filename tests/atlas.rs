@@ -0,0 +1,151 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::atlas::*;
+use cgmath::*;
+
+fn overlaps(a: &Aabb2<u32>, b: &Aabb2<u32>) -> bool {
+    a.min.x < b.max.x && b.min.x < a.max.x && a.min.y < b.max.y && b.min.y < a.max.y
+}
+
+#[test]
+fn test_packs_exactly() {
+    let bounds = Aabb2::new(Point2::new(0u32, 0), Point2::new(4, 4));
+    let mut packer = SkylinePacker::new(bounds);
+    for _ in 0..4 {
+        assert!(packer.insert(Vector2::new(2, 2)).is_some());
+    }
+    assert_eq!(packer.insert(Vector2::new(1, 1)), None);
+    packer.clear();
+    assert_eq!(
+        packer.insert(Vector2::new(4, 4)),
+        Some(Aabb2::new(Point2::new(0, 0), Point2::new(4, 4)))
+    );
+}
+
+#[test]
+fn test_rejects_too_large() {
+    let bounds = Aabb2::new(Point2::new(10u32, 10), Point2::new(20, 20));
+    let mut packer = SkylinePacker::new(bounds);
+    assert_eq!(packer.insert(Vector2::new(11, 1)), None);
+    assert_eq!(packer.insert(Vector2::new(1, 11)), None);
+    assert_eq!(
+        packer.insert(Vector2::new(10, 10)),
+        Some(Aabb2::new(Point2::new(10, 10), Point2::new(20, 20)))
+    );
+}
+
+#[test]
+fn test_fills_lowest_gap() {
+    let bounds = Aabb2::new(Point2::new(0u32, 0), Point2::new(10, 10));
+    let mut packer = SkylinePacker::new(bounds);
+    packer.insert(Vector2::new(4, 6)).unwrap();
+    packer.insert(Vector2::new(3, 2)).unwrap();
+    // The next rectangle goes over the lower one, not over the taller one.
+    let placed = packer.insert(Vector2::new(3, 3)).unwrap();
+    assert_eq!(placed.min, Point2::new(7, 0));
+    let placed = packer.insert(Vector2::new(6, 1)).unwrap();
+    assert_eq!(placed.min, Point2::new(4, 3));
+}
+
+#[test]
+fn test_insert_all_disjoint() {
+    let bounds = Aabb2::new(Point2::new(0u32, 0), Point2::new(64, 64));
+    let mut packer = SkylinePacker::new(bounds);
+    let sizes: Vec<Vector2<u32>> = (0..40)
+        .map(|i| Vector2::new(3 + (i * 7) % 9, 2 + (i * 5) % 11))
+        .collect();
+    let placements = packer.insert_all(&sizes);
+    let placed: Vec<Aabb2<u32>> = placements.iter().map(|p| p.unwrap()).collect();
+    for (rect, size) in placed.iter().zip(&sizes) {
+        assert_eq!(rect.dim(), *size);
+        assert!(rect.max.x <= 64);
+        assert!(rect.max.y <= 64);
+    }
+    for i in 0..placed.len() {
+        for j in i + 1..placed.len() {
+            assert!(!overlaps(&placed[i], &placed[j]));
+        }
+    }
+}
+
+#[test]
+fn test_float_bounds() {
+    let bounds = Aabb2::new(Point2::new(0.0f64, 0.0), Point2::new(1.0, 1.0));
+    let mut packer = SkylinePacker::new(bounds);
+    let a = packer.insert(Vector2::new(0.5, 0.25)).unwrap();
+    let b = packer.insert(Vector2::new(0.5, 0.5)).unwrap();
+    assert_eq!(a.min, Point2::new(0.0, 0.0));
+    assert_eq!(b.min, Point2::new(0.5, 0.0));
+    assert_eq!(
+        packer.insert(Vector2::new(0.0, 0.5)).unwrap().dim(),
+        Vector2::new(0.0, 0.0)
+    );
+}
+
+#[test]
+fn test_uv_transform() {
+    let m = uv_transform(Vector2::new(2.0f64, 3.0), Vector2::new(0.5, -1.0));
+    assert_eq!(
+        m.transform_point(Point2::new(0.0, 0.0)),
+        Point2::new(0.5, -1.0)
+    );
+    assert_eq!(
+        m.transform_point(Point2::new(1.0, 1.0)),
+        Point2::new(2.5, 2.0)
+    );
+    // Directions are tiled but not offset.
+    let v = Transform::<Point2<f64>>::transform_vector(&m, Vector2::new(1.0, 1.0));
+    assert_eq!(v, Vector2::new(2.0, 3.0));
+}
+
+#[test]
+fn test_uv_rotation_about() {
+    let pivot = Point2::new(0.5f64, 0.5);
+    let m = uv_rotation_about(pivot, Deg(90.0));
+    assert_relative_eq!(m.transform_point(pivot), pivot, epsilon = 1e-12);
+    assert_relative_eq!(
+        m.transform_point(Point2::new(1.0, 0.5)),
+        Point2::new(0.5, 1.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        m.transform_point(Point2::new(0.0, 0.0)),
+        Point2::new(1.0, 0.0),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_atlas_uv_transform() {
+    let atlas = Aabb2::new(Point2::new(0.0f64, 0.0), Point2::new(64.0, 64.0));
+    let mut packer = SkylinePacker::new(atlas);
+    packer.insert(Vector2::new(16.0, 32.0)).unwrap();
+    let rect = packer.insert(Vector2::new(32.0, 16.0)).unwrap();
+    let m = atlas_uv_transform(atlas, rect);
+    assert_eq!(
+        m.transform_point(Point2::new(0.0, 0.0)),
+        Point2::new(0.25, 0.0)
+    );
+    assert_eq!(
+        m.transform_point(Point2::new(1.0, 1.0)),
+        Point2::new(0.75, 0.25)
+    );
+    let (scale, offset) = atlas_scale_offset(atlas, rect);
+    assert_eq!(m, uv_transform(scale, offset));
+}
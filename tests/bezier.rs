@@ -0,0 +1,206 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_cubic_bezier() {
+    let curve = CubicBezier2::new(
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 1.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(1.0, 0.0f64),
+    );
+    assert_eq!(curve.point_at(0.0), curve.points[0]);
+    assert_eq!(curve.point_at(1.0), curve.points[3]);
+    assert_eq!(curve.point_at(0.5), Point2::new(0.5, 0.75));
+    assert_eq!(curve.derivative_at(1.0), Vector2::new(0.0, -3.0));
+    let h = 1e-6;
+    for i in 1..10 {
+        let t = i as f64 / 10.0;
+        let difference = (curve.point_at(t + h) - curve.point_at(t - h)) / (2.0 * h);
+        assert_relative_eq!(curve.derivative_at(t), difference, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_from_hermite() {
+    let (p0, m0) = (Point3::new(0.0, 0.0, 0.0), Vector3::new(3.0, 0.0, 0.0));
+    let (p1, m1) = (Point3::new(1.0, 1.0, 0.0), Vector3::new(0.0, 3.0, 3.0f64));
+    let curve = CubicBezier3::from_hermite(p0, m0, p1, m1);
+    assert_eq!(curve.point_at(0.0), p0);
+    assert_eq!(curve.point_at(1.0), p1);
+    assert_eq!(curve.derivative_at(0.0), m0);
+    assert_relative_eq!(curve.derivative_at(1.0), m1, epsilon = 1e-12);
+}
+
+#[test]
+fn test_intersect_plane() {
+    let curve = CubicBezier3::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 2.0, 0.0),
+        Point3::new(2.0, -2.0, 0.0),
+        Point3::new(3.0, 0.0, 0.0f64),
+    );
+    // The curve crosses the plane of `y = 0` at both ends and in the middle.
+    let ground = Plane::new(Vector3::unit_y(), 0.0);
+    let roots = curve.intersect_plane(&ground);
+    assert_eq!(roots.len(), 3);
+    assert_eq!(roots[0], 0.0);
+    assert_relative_eq!(roots[1], 0.5, epsilon = 1e-12);
+    assert_eq!(roots[2], 1.0);
+
+    let tilted = Plane::from_point_normal(Point3::new(1.0, 0.2, 0.0), Vector3::new(1.0, 2.0, 0.5));
+    let roots = curve.intersect_plane(&tilted);
+    assert!(!roots.is_empty());
+    for t in roots {
+        assert_relative_eq!(tilted.distance(curve.point_at(t)), 0.0, epsilon = 1e-12);
+    }
+
+    // Above and in the plane.
+    let above = Plane::new(Vector3::unit_y(), 5.0);
+    assert!(curve.intersect_plane(&above).is_empty());
+    let flat = Plane::new(Vector3::unit_z(), 0.0);
+    assert!(curve.intersect_plane(&flat).is_empty());
+
+    // A curve dipping under the plane crosses it twice.
+    let bowl = CubicBezier3::new(
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(1.0, -0.5, 0.0),
+        Point3::new(2.0, -0.5, 0.0),
+        Point3::new(3.0, 1.0, 0.0f64),
+    );
+    let roots = bowl.intersect_plane(&ground);
+    assert_eq!(roots.len(), 2);
+    assert_relative_eq!(roots[0], 1.0 / 3.0, epsilon = 1e-12);
+    assert_relative_eq!(roots[1], 2.0 / 3.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_closest_to_ray() {
+    let curve = CubicBezier3::new(
+        Point3::new(-2.0, 0.0, 0.0),
+        Point3::new(-1.0, 3.0, 1.0),
+        Point3::new(1.0, -1.0, -1.0),
+        Point3::new(2.0, 1.0, 0.0f64),
+    );
+    let rays = [
+        Ray3::new(Point3::new(0.3, 0.5, 5.0), Vector3::new(0.0, 0.0, -1.0)),
+        Ray3::new(Point3::new(-3.0, 4.0, 2.0), Vector3::new(1.0, -1.0, -0.5)),
+        // The origin of this ray is the closest point of it.
+        Ray3::new(Point3::new(5.0, 1.0, 0.0), Vector3::new(1.0, 0.0, 0.0)),
+    ];
+    for ray in &rays {
+        let (t, s) = curve.closest_to_ray(ray);
+        let distance = curve.point_at(t).distance(ray.point_at(s));
+        let brute = (0..=10000)
+            .map(|i| {
+                let p = curve.point_at(i as f64 / 10000.0);
+                let s = ((p - ray.origin).dot(ray.direction) / ray.direction.magnitude2()).max(0.0);
+                p.distance(ray.point_at(s))
+            })
+            .fold(::std::f64::INFINITY, f64::min);
+        assert!(distance <= brute + 1e-9);
+        assert!(s >= 0.0);
+    }
+    let (t, s) = curve.closest_to_ray(&rays[2]);
+    assert_eq!(s, 0.0);
+    assert_eq!(t, 1.0);
+}
+
+#[test]
+fn test_split() {
+    let curve = CubicBezier2::new(
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 3.0),
+        Point2::new(3.0, -1.0),
+        Point2::new(4.0, 1.0f64),
+    );
+    let (first, second) = curve.split(0.25);
+    assert_eq!(first.points[0], curve.points[0]);
+    assert_eq!(second.points[3], curve.points[3]);
+    for i in 0..=10 {
+        let t = i as f64 / 10.0;
+        assert_relative_eq!(first.point_at(t), curve.point_at(0.25 * t), epsilon = 1e-12);
+        assert_relative_eq!(
+            second.point_at(t),
+            curve.point_at(0.25 + 0.75 * t),
+            epsilon = 1e-12
+        );
+    }
+}
+
+#[test]
+fn test_aabb() {
+    let curve = CubicBezier3::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 3.0, -2.0),
+        Point3::new(3.0, -1.0, 1.0),
+        Point3::new(4.0, 1.0, 0.0f64),
+    );
+    let aabb = curve.aabb();
+    let control = curve.control_aabb();
+    assert_eq!(
+        control,
+        Aabb3::new(Point3::new(0.0, -1.0, -2.0), Point3::new(4.0, 3.0, 1.0))
+    );
+    assert!(control.contains_point(aabb.min) && control.contains_point(aabb.max));
+
+    let sampled =
+        Aabb3::from_points((0..=10000).map(|i| curve.point_at(i as f64 / 10000.0))).unwrap();
+    assert_relative_eq!(aabb.min, sampled.min, epsilon = 1e-6);
+    assert_relative_eq!(aabb.max, sampled.max, epsilon = 1e-6);
+    assert!(
+        aabb.min.x <= sampled.min.x && aabb.min.y <= sampled.min.y && aabb.min.z <= sampled.min.z
+    );
+    assert!(
+        aabb.max.x >= sampled.max.x && aabb.max.y >= sampled.max.y && aabb.max.z >= sampled.max.z
+    );
+}
+
+#[test]
+fn test_flatten() {
+    let curve = CubicBezier2::new(
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 3.0),
+        Point2::new(3.0, -1.0),
+        Point2::new(4.0, 1.0f64),
+    );
+    let mut previous = 0;
+    for &tolerance in &[0.1, 0.01, 0.001] {
+        let polyline = curve.flatten(tolerance);
+        assert_eq!(polyline.points[0], curve.points[0]);
+        assert_eq!(*polyline.points.last().unwrap(), curve.points[3]);
+        assert!(polyline.points.len() > previous);
+        previous = polyline.points.len();
+        for i in 0..=1000 {
+            let p = curve.point_at(i as f64 / 1000.0);
+            let (closest, _) = polyline.project(p).unwrap();
+            assert!(p.distance(closest) <= tolerance);
+        }
+    }
+
+    // A straight curve is a single segment.
+    let straight = CubicBezier3::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 1.0),
+        Point3::new(2.0, 2.0, 2.0),
+        Point3::new(3.0, 3.0, 3.0f64),
+    );
+    assert_eq!(straight.flatten(1e-6).points.len(), 2);
+}
@@ -0,0 +1,326 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::color::*;
+use cgmath::*;
+
+#[test]
+fn test_transfer_functions() {
+    assert_eq!(srgb_to_linear(0.0f64), 0.0);
+    assert_eq!(srgb_to_linear(1.0f64), 1.0);
+    assert_relative_eq!(srgb_to_linear(0.5f64), 0.214_041_140_5, epsilon = 1e-9);
+    assert_relative_eq!(linear_to_srgb(0.214_041_140_5f64), 0.5, epsilon = 1e-9);
+
+    for i in 0..=100 {
+        let c = i as f64 / 100.0;
+        assert_relative_eq!(linear_to_srgb(srgb_to_linear(c)), c, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_srgb_round_trip() {
+    let c = Srgba::new(0.2f32, 0.4, 0.6, 0.5);
+    let linear = Rgba::from(c);
+    assert_eq!(linear.a, 0.5);
+    assert!(linear.r < c.r && linear.g < c.g && linear.b < c.b);
+    assert_relative_eq!(Srgba::from(linear), c, epsilon = 1e-6);
+}
+
+#[test]
+fn test_arithmetic() {
+    let a = Rgb::new(0.5f32, 0.25, 1.0);
+    let b = Rgb::new(0.25f32, 0.5, 0.0);
+    assert_eq!(a + b, Rgb::new(0.75, 0.75, 1.0));
+    assert_eq!(a - b, Rgb::new(0.25, -0.25, 1.0));
+    assert_eq!(a * b, Rgb::new(0.125, 0.125, 0.0));
+    assert_eq!(a * 2.0, Rgb::new(1.0, 0.5, 2.0));
+    assert_eq!(a / 2.0, Rgb::new(0.25, 0.125, 0.5));
+    assert_eq!(a.lerp(b, 0.5), Rgb::new(0.375, 0.375, 0.5));
+
+    let mut c = a;
+    c += b;
+    c *= 0.5;
+    assert_eq!(c, Rgb::new(0.375, 0.375, 0.5));
+    assert_eq!((a * 2.0).saturate(), Rgb::new(1.0, 0.5, 1.0));
+}
+
+#[test]
+fn test_packing() {
+    let c = Srgba::<f32>::from_u32(0xff80_00cc);
+    assert_eq!(c.to_u8(), [255, 128, 0, 204]);
+    assert_eq!(c.to_u32(), 0xff80_00cc);
+    assert_relative_eq!(c.r, 1.0);
+    assert_relative_eq!(c.a, 0.8);
+
+    assert_eq!(Srgb::new(-0.5f64, 0.5, 2.0).to_u8(), [0, 128, 255]);
+    assert_eq!(Srgb::new(::std::f64::NAN, 1.0, 0.0).to_u8(), [0, 255, 0]);
+    assert_eq!(Srgb::<f64>::from_u8([0, 51, 255]).g, 0.2);
+}
+
+#[test]
+fn test_conversions() {
+    let c = Rgba::new(0.1f32, 0.2, 0.3, 0.4);
+    let v: Vector4<f32> = c.into();
+    assert_eq!(v, Vector4::new(0.1, 0.2, 0.3, 0.4));
+    assert_eq!(Rgba::from(v), c);
+
+    let a: [f32; 3] = c.truncate().into();
+    assert_eq!(a, [0.1, 0.2, 0.3]);
+    assert_eq!(Rgb::from(a).extend(0.4), c);
+}
+
+#[test]
+fn test_hsv() {
+    let red = Hsv::from(Srgb::new(1.0f64, 0.0, 0.0));
+    assert_eq!(red, Hsv::new(Deg(0.0), 1.0, 1.0));
+
+    let c = Srgb::new(0.2f64, 0.4, 0.8);
+    let hsv = Hsv::from(c);
+    assert_relative_eq!(hsv.hue, Deg(220.0), epsilon = 1e-12);
+    assert_relative_eq!(hsv.saturation, 0.75, epsilon = 1e-12);
+    assert_relative_eq!(hsv.value, 0.8, epsilon = 1e-12);
+    assert_relative_eq!(Srgb::from(hsv), c, epsilon = 1e-12);
+
+    let grey = Hsv::from(Srgb::new(0.5f64, 0.5, 0.5));
+    assert_eq!(grey.saturation, 0.0);
+    assert_eq!(Srgb::from(grey), Srgb::new(0.5, 0.5, 0.5));
+}
+
+#[test]
+fn test_hsl() {
+    let c = Srgb::new(0.9f64, 0.3, 0.6);
+    let hsl = Hsl::from(c);
+    assert_relative_eq!(hsl.hue, Deg(330.0), epsilon = 1e-12);
+    assert_relative_eq!(hsl.lightness, 0.6, epsilon = 1e-12);
+    assert_relative_eq!(hsl.saturation, 0.75, epsilon = 1e-12);
+    assert_relative_eq!(Srgb::from(hsl), c, epsilon = 1e-12);
+    assert_relative_eq!(Srgb::from(Hsv::from(hsl)), c, epsilon = 1e-12);
+}
+
+#[test]
+fn test_hue_sweep_round_trip() {
+    for i in 0..36 {
+        let hsv = Hsv::new(Deg(i as f64 * 10.0), 0.5, 0.9);
+        let back = Hsv::from(Srgb::from(hsv));
+        assert_relative_eq!(back.hue, hsv.hue, epsilon = 1e-9);
+        assert_relative_eq!(back.saturation, hsv.saturation, epsilon = 1e-12);
+        assert_relative_eq!(back.value, hsv.value, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_hue_lerp_shortest_arc() {
+    let a = Hsv::new(Deg(350.0f64), 1.0, 1.0);
+    let b = Hsv::new(Deg(30.0f64), 0.0, 0.5);
+    let mid = a.lerp(b, 0.5);
+    assert_relative_eq!(mid.hue, Deg(10.0), epsilon = 1e-12);
+    assert_relative_eq!(mid.saturation, 0.5);
+    assert_relative_eq!(mid.value, 0.75);
+
+    let quarter = Hsl::new(Deg(30.0f64), 1.0, 0.5).lerp(Hsl::new(Deg(350.0), 1.0, 0.5), 0.25);
+    assert_relative_eq!(quarter.hue, Deg(20.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_tonemap_curves() {
+    assert_eq!(reinhard(1.0f64), 0.5);
+    assert_eq!(reinhard_extended(4.0f64, 4.0), 1.0);
+    assert_eq!(reinhard_extended(10.0f64, 4.0), 1.0);
+    assert_relative_eq!(
+        reinhard_extended(0.01f64, 4.0),
+        reinhard(0.01),
+        epsilon = 1e-5
+    );
+
+    assert_eq!(aces_filmic(0.0f64), 0.0);
+    assert_eq!(aces_filmic(100.0f64), 1.0);
+    assert_relative_eq!(aces_filmic(0.18f64), 0.266_9, epsilon = 1e-4);
+    let mut previous = 0.0;
+    for i in 1..100 {
+        let x = i as f64 * 0.1;
+        for &curve in &[
+            Tonemap::Reinhard,
+            Tonemap::ReinhardExtended(8.0),
+            Tonemap::AcesFilmic,
+        ] {
+            let y = curve.apply(x);
+            assert!(y > 0.0 && y <= 1.0);
+            assert!(curve.apply(x + 0.05) >= y);
+        }
+        let y = aces_filmic(x);
+        assert!(y >= previous);
+        previous = y;
+    }
+
+    let hdr = Rgb::new(4.0f64, 1.0, 0.0);
+    assert_eq!(hdr.tonemap(Tonemap::Clamp), Rgb::new(1.0, 1.0, 0.0));
+    assert_eq!(hdr.tonemap(Tonemap::Reinhard), Rgb::new(0.8, 0.5, 0.0));
+}
+
+#[test]
+fn test_exposure() {
+    let c = Rgb::new(0.25f64, 0.5, 1.0);
+    assert_eq!(c.expose(1.0), Rgb::new(0.5, 1.0, 2.0));
+    assert_eq!(c.expose(-2.0), c / 4.0);
+    assert_relative_eq!(Rgb::new(1.0f64, 1.0, 1.0).luminance(), 1.0);
+    assert!(Rgb::new(0.0f64, 1.0, 0.0).luminance() > Rgb::new(1.0, 0.0, 1.0).luminance());
+
+    assert_eq!(ev100(1.0f64, 1.0, 100.0), 0.0);
+    // The sunny sixteen rule: f/16 at the reciprocal of the ISO.
+    assert_relative_eq!(ev100(16.0f64, 0.01, 100.0), 25_600f64.log2());
+    assert_eq!(ev100(2.0f64, 1.0, 400.0), 0.0);
+    // Each stop halves the exposure.
+    assert_relative_eq!(exposure_from_ev100(0.0f64), 1.0 / 1.2);
+    assert_relative_eq!(exposure_from_ev100(15.0f64) * 2f64.powi(15) * 1.2, 1.0);
+}
+
+#[test]
+fn test_xyz() {
+    let white = Xyz::from(Rgb::new(1.0f64, 1.0, 1.0));
+    assert_relative_eq!(
+        Vector3::from(white),
+        Vector3::from(Xyz::d65()),
+        epsilon = 1e-4
+    );
+    let m = xyz_to_rgb_matrix::<f64>() * rgb_to_xyz_matrix();
+    assert_relative_eq!(m, Matrix3::identity(), epsilon = 1e-6);
+
+    let c = Rgb::new(0.2f64, 0.7, 0.1);
+    assert_relative_eq!(Rgb::from(Xyz::from(c)), c, epsilon = 1e-6);
+    assert_relative_eq!(Xyz::from(c).y, c.luminance(), epsilon = 1e-4);
+
+    let d65 = Xyy::from(Xyz::<f64>::d65());
+    assert_relative_eq!(d65.x, 0.3127, epsilon = 1e-4);
+    assert_relative_eq!(d65.y, 0.3290, epsilon = 1e-4);
+    assert_eq!(d65.luminance, 1.0);
+    let back = Xyz::from(Xyy {
+        luminance: 0.5,
+        ..d65
+    });
+    assert_relative_eq!(
+        Vector3::from(back),
+        Vector3::from(Xyz::d65()) * 0.5,
+        epsilon = 1e-12
+    );
+
+    let black = Xyy::from(Xyz::new(0.0f64, 0.0, 0.0));
+    assert_eq!(black.luminance, 0.0);
+    assert_relative_eq!(black.x, d65.x);
+    assert_eq!(
+        Vector3::from(Xyz::from(Xyy::new(0.3f64, 0.0, 0.0))),
+        Vector3::zero()
+    );
+}
+
+#[test]
+fn test_bradford_adaptation() {
+    let (d65, d50) = (Xyz::<f64>::d65(), Xyz::<f64>::d50());
+    assert_relative_eq!(
+        bradford_adaptation(d65, d65),
+        Matrix3::identity(),
+        epsilon = 1e-12
+    );
+    let m = bradford_adaptation(d65, d50);
+    assert_relative_eq!(m * Vector3::from(d65), Vector3::from(d50), epsilon = 1e-12);
+    // The reference matrix of Bruce Lindbloom.
+    assert_relative_eq!(
+        m.x,
+        Vector3::new(1.047_811_2, 0.029_542_4, -0.009_234_5),
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(
+        m.z,
+        Vector3::new(-0.050_127_0, -0.017_049_1, 0.752_131_6),
+        epsilon = 1e-6
+    );
+    assert_relative_eq!(
+        bradford_adaptation(d50, d65) * m,
+        Matrix3::identity(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_color_temperature() {
+    // Points of the Planckian locus, and illuminant A.
+    let warm = Xyy::<f64>::from_temperature(2856.0);
+    assert_relative_eq!(warm.x, 0.4476, epsilon = 1e-3);
+    assert_relative_eq!(warm.y, 0.4074, epsilon = 1e-3);
+    let neutral = Xyy::<f64>::from_temperature(6500.0);
+    assert_relative_eq!(neutral.x, 0.3135, epsilon = 1e-3);
+    assert_relative_eq!(neutral.y, 0.3237, epsilon = 1e-3);
+    assert_eq!(
+        Xyy::<f64>::from_temperature(100.0),
+        Xyy::from_temperature(1667.0)
+    );
+
+    let candle = Rgb::<f64>::from_temperature(1900.0);
+    let sky = Rgb::<f64>::from_temperature(12000.0);
+    assert!(candle.r > candle.g && candle.g > candle.b && candle.b >= 0.0);
+    assert!(sky.b > sky.g && sky.g > sky.r);
+    assert_relative_eq!(sky.luminance(), 1.0, epsilon = 1e-3);
+
+    // White balancing the light makes it white.
+    let light = Xyz::from(Xyy::<f64>::from_temperature(3200.0));
+    let balance = white_balance_matrix(light, Xyz::d65());
+    let balanced = balance * Vector3::from(Rgb::from(light));
+    assert_relative_eq!(balanced, Vector3::new(1.0, 1.0, 1.0), epsilon = 1e-3);
+}
+
+#[test]
+fn test_wavelengths() {
+    let peak = Xyz::<f64>::from_wavelength(555.0);
+    assert_relative_eq!(peak.y, 1.0, epsilon = 0.01);
+    assert!(Xyz::<f64>::from_wavelength(700.0).y < 0.01);
+    assert!(Xyz::<f64>::from_wavelength(380.0).y < 0.001);
+
+    let blue = Rgb::<f64>::from_wavelength(450.0);
+    let green = Rgb::<f64>::from_wavelength(530.0);
+    let red = Rgb::<f64>::from_wavelength(650.0);
+    assert!(blue.b > blue.g && blue.b > blue.r);
+    assert!(green.g > green.r && green.g > green.b);
+    assert!(red.r > red.g && red.r > red.b);
+    for i in 0..100 {
+        let c = Rgb::<f64>::from_wavelength(380.0 + i as f64 * 4.0);
+        assert!(c.r >= 0.0 && c.g >= 0.0 && c.b >= 0.0);
+    }
+}
+
+#[test]
+fn test_blackbody() {
+    // Wien's displacement law.
+    let kelvin = 5000.0f64;
+    let peak = 2.897_771_955e6 / kelvin;
+    let b = |w| blackbody_radiance(w, kelvin);
+    assert!(b(peak) > b(peak - 5.0) && b(peak) > b(peak + 5.0));
+    assert_relative_eq!(b(500.0), 1.211e4, max_relative = 1e-3);
+
+    // The chromaticity follows the fit of the Planckian locus.
+    for &t in &[2000.0, 3000.0, 4500.0, 6500.0, 10000.0] {
+        let exact = Xyy::from(Xyz::<f64>::from_blackbody(t));
+        let fit = Xyy::<f64>::from_temperature(t);
+        assert_relative_eq!(exact.x, fit.x, epsilon = 5e-3);
+        assert_relative_eq!(exact.y, fit.y, epsilon = 5e-3);
+    }
+
+    // The surface of the sun is about two billion candelas per square
+    // meter, and hotter bodies are much brighter.
+    let sun = Rgb::<f64>::from_blackbody(5778.0);
+    assert!(sun.luminance() > 1.5e9 && sun.luminance() < 3e9);
+    assert!(Rgb::<f64>::from_blackbody(1000.0).luminance() < sun.luminance() * 1e-6);
+}
@@ -0,0 +1,109 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+fn points() -> Vec<Point2<f64>> {
+    vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 2.0),
+        Point2::new(-3.5, 0.25),
+        Point2::new(10.0, -7.0),
+    ]
+}
+
+#[test]
+fn test_matches_decomposed() {
+    let t = Conformal2::new(1.5, Deg(30.0), Vector2::new(2.0, -1.0));
+    let d = Decomposed {
+        scale: 1.5,
+        rot: Basis2::from_angle(Deg(30.0)),
+        disp: Vector2::new(2.0, -1.0),
+    };
+    for p in points() {
+        assert_relative_eq!(t.transform_point(p), d.transform_point(p), epsilon = 1e-12);
+        let v = p.to_vec();
+        assert_relative_eq!(
+            t.transform_vector(v),
+            d.transform_vector(v),
+            epsilon = 1e-12
+        );
+    }
+    assert_relative_eq!(t.scale(), 1.5, epsilon = 1e-12);
+    assert_relative_eq!(t.angle(), Rad::from(Deg(30.0)), epsilon = 1e-12);
+    assert_relative_eq!(Matrix3::from(t), Matrix3::from(d), epsilon = 1e-12);
+}
+
+#[test]
+fn test_composition() {
+    let t1 = Conformal2::new(2.0, Deg(45.0), Vector2::new(1.0, 0.0));
+    let t2 = Conformal2::new(0.5, Deg(-120.0), Vector2::new(0.0, 3.0));
+    let t = t1 * t2;
+    for p in points() {
+        assert_relative_eq!(
+            t.transform_point(p),
+            t1.transform_point(t2.transform_point(p)),
+            epsilon = 1e-12
+        );
+    }
+    assert_relative_eq!(
+        Matrix3::from(t),
+        Matrix3::from(t1) * Matrix3::from(t2),
+        epsilon = 1e-12
+    );
+    assert_eq!(t1 * Conformal2::one(), t1);
+    assert_eq!(Conformal2::<f64>::default(), Conformal2::one());
+}
+
+#[test]
+fn test_inverse() {
+    let t = Conformal2::new(3.0, Deg(200.0), Vector2::new(-4.0, 5.0));
+    let inverse = t.inverse_transform().unwrap();
+    assert_relative_eq!(t * inverse, Conformal2::one(), epsilon = 1e-12);
+    assert_relative_eq!(inverse * t, Conformal2::one(), epsilon = 1e-12);
+    for p in points() {
+        let q = t.transform_point(p);
+        assert_relative_eq!(t.inverse_transform_point(q).unwrap(), p, epsilon = 1e-12);
+        assert_relative_eq!(inverse.transform_point(q), p, epsilon = 1e-12);
+    }
+    let degenerate = Conformal2::new(0.0, Deg(10.0), Vector2::new(1.0, 1.0));
+    assert_eq!(degenerate.inverse_transform(), None);
+}
+
+#[test]
+fn test_from_point_pairs() {
+    let t = Conformal2::new(0.75, Deg(-65.0), Vector2::new(8.0, 2.0));
+    let (p0, p1) = (Point2::new(1.0, 1.0), Point2::new(-2.0, 4.0));
+    let fitted =
+        Conformal2::from_point_pairs(p0, t.transform_point(p0), p1, t.transform_point(p1)).unwrap();
+    assert_relative_eq!(fitted, t, epsilon = 1e-12);
+    assert_eq!(Conformal2::from_point_pairs(p0, p0, p0, p1), None);
+}
+
+#[test]
+fn test_look_at() {
+    let eye = Point2::new(1.0, 2.0);
+    let t = Conformal2::look_at(eye, Point2::new(1.0, 5.0), Vector2::unit_x());
+    assert_relative_eq!(t.transform_point(eye), Point2::origin(), epsilon = 1e-12);
+    assert_relative_eq!(
+        t.transform_vector(Vector2::unit_x()),
+        Vector2::unit_y(),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(t.scale(), 1.0, epsilon = 1e-12);
+}
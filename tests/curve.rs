@@ -0,0 +1,138 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_curve_holds_outside_of_keys() {
+    assert_eq!(PiecewiseCurve::<f64>::new(vec![]).evaluate(1.0), 0.0);
+    let single = PiecewiseCurve::new(vec![CurveKey::new(2.0, 5.0f64)]);
+    assert_eq!(single.evaluate(-1.0), 5.0);
+    assert_eq!(single.evaluate(4.0), 5.0);
+
+    let curve = PiecewiseCurve::new(vec![CurveKey::new(1.0, 2.0), CurveKey::new(0.0, 1.0f64)]);
+    assert_eq!(curve.keys()[0].time, 0.0);
+    assert_eq!(curve.evaluate(-1.0), 1.0);
+    assert_eq!(curve.evaluate(0.0), 1.0);
+    assert_eq!(curve.evaluate(1.0), 2.0);
+    assert_eq!(curve.evaluate(2.0), 2.0);
+    // Without handles, the segment is straight.
+    assert_relative_eq!(curve.evaluate(0.1), 1.1, epsilon = 1e-12);
+}
+
+#[test]
+fn test_curve_handles() {
+    // Handles along the line between the keys give a straight line, even
+    // when they are uneven.
+    let curve = PiecewiseCurve::new(vec![
+        CurveKey::with_handles(0.0, 0.0, Vector2::zero(), Vector2::new(0.1, 0.2)),
+        CurveKey::with_handles(2.0, 4.0f64, Vector2::new(-1.5, -3.0), Vector2::zero()),
+    ]);
+    for i in 0..=20 {
+        let t = i as f64 / 10.0;
+        assert_relative_eq!(curve.evaluate(t), 2.0 * t, epsilon = 1e-9);
+    }
+
+    // Long handles are shortened to the interval, keeping their slope.
+    let long = PiecewiseCurve::new(vec![
+        CurveKey::with_handles(0.0, 0.0, Vector2::zero(), Vector2::new(10.0, 10.0)),
+        CurveKey::new(1.0, 0.0f64),
+    ]);
+    let segment = long.segment(0);
+    assert_eq!(segment.points[1], Point2::new(1.0, 1.0));
+    let mut previous = -1.0;
+    for i in 0..=100 {
+        let t = i as f64 / 100.0;
+        // Time still grows monotonically along the segment.
+        let x = segment.point_at(t).x;
+        assert!(x >= previous);
+        previous = x;
+        let value = long.evaluate(t);
+        assert!((0.0..=1.0).contains(&value));
+    }
+}
+
+#[test]
+fn test_smooth_handles() {
+    let mut curve = PiecewiseCurve::new(vec![
+        CurveKey::new(0.0, 0.0),
+        CurveKey::new(1.0, 2.0),
+        CurveKey::new(3.0, 1.0),
+        CurveKey::new(4.0, 3.0f64),
+    ]);
+    curve.smooth_handles();
+    for key in curve.keys() {
+        assert_relative_eq!(curve.evaluate(key.time), key.value, epsilon = 1e-12);
+    }
+    // The slope is continuous through the inner keys, and the one of the
+    // Catmull-Rom spline.
+    let h = 1e-5;
+    let slope = |t: f64| (curve.evaluate(t + h) - curve.evaluate(t - h)) / (2.0 * h);
+    let before = (curve.evaluate(1.0) - curve.evaluate(1.0 - h)) / h;
+    let after = (curve.evaluate(1.0 + h) - curve.evaluate(1.0)) / h;
+    assert_relative_eq!(before, after, epsilon = 1e-3);
+    assert_relative_eq!(slope(1.0), 1.0 / 3.0, epsilon = 1e-3);
+    assert_relative_eq!(slope(3.0), 1.0 / 3.0, epsilon = 1e-3);
+    assert_relative_eq!(slope(0.0 + h), 0.0, epsilon = 1e-3);
+}
+
+#[test]
+fn test_editing_keys() {
+    let mut curve = PiecewiseCurve::new(vec![CurveKey::new(0.0, 0.0), CurveKey::new(2.0, 2.0f64)]);
+    assert_eq!(curve.add_key(CurveKey::new(1.0, 5.0)), 1);
+    assert_eq!(curve.evaluate(1.0), 5.0);
+    // Dragging a key past its neighbour reorders the keys.
+    assert_eq!(curve.move_key(1, 3.0, 4.0), 2);
+    assert_eq!(curve.keys()[2], CurveKey::new(3.0, 4.0));
+    assert_eq!(curve.remove_key(0), CurveKey::new(0.0, 0.0));
+    assert_eq!(curve.keys().len(), 2);
+    assert_eq!(curve.evaluate(0.0), 2.0);
+}
+
+#[test]
+fn test_curve_bounds_and_flatten() {
+    let empty = PiecewiseCurve::<f64>::new(vec![]);
+    assert_eq!(empty.aabb(), None);
+    assert!(empty.flatten(0.1).points.is_empty());
+    let single = PiecewiseCurve::new(vec![CurveKey::new(2.0, 5.0f64)]);
+    assert_eq!(single.flatten(0.1).points, vec![Point2::new(2.0, 5.0)]);
+
+    // An overshoot between the keys widens the bounds.
+    let curve = PiecewiseCurve::new(vec![
+        CurveKey::with_handles(0.0, 0.0, Vector2::zero(), Vector2::new(0.5, 2.0)),
+        CurveKey::new(1.0, 1.0),
+        CurveKey::new(2.0, 0.0f64),
+    ]);
+    let aabb = curve.aabb().unwrap();
+    assert_eq!(aabb.min, Point2::new(0.0, 0.0));
+    assert_eq!(aabb.max.x, 2.0);
+    assert!(aabb.max.y > 1.0);
+    assert_relative_eq!(aabb.max.y, curve.segment(0).aabb().max.y);
+
+    let polyline = curve.flatten(0.01);
+    assert_eq!(polyline.points[0], Point2::new(0.0, 0.0));
+    assert_eq!(*polyline.points.last().unwrap(), Point2::new(2.0, 0.0));
+    assert!(polyline.points.windows(2).all(|w| w[0].x <= w[1].x));
+    for i in 0..=200 {
+        let t = i as f64 / 100.0;
+        let p = Point2::new(t, curve.evaluate(t));
+        let (closest, _) = polyline.project(p).unwrap();
+        assert!(p.distance(closest) <= 0.01 + 1e-9);
+    }
+    assert!(polyline.points.contains(&Point2::new(1.0, 1.0)));
+}
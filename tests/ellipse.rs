@@ -0,0 +1,180 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+/// The distance from `p` to the closest of many points around the
+/// boundary of the ellipse.
+fn sampled_distance(ellipse: &Ellipse2<f64>, p: Point2<f64>) -> f64 {
+    let [u, v] = ellipse.axes();
+    (0..20000)
+        .map(|i| {
+            let (s, c) = (i as f64 / 20000.0 * 2.0 * ::std::f64::consts::PI).sin_cos();
+            let q = ellipse.center + u * (ellipse.radii.x * c) + v * (ellipse.radii.y * s);
+            q.distance(p)
+        })
+        .fold(::std::f64::INFINITY, f64::min)
+}
+
+fn on_boundary(ellipse: &Ellipse2<f64>, p: Point2<f64>) -> bool {
+    let [u, v] = ellipse.axes();
+    let d = p - ellipse.center;
+    let (x, y) = (d.dot(u) / ellipse.radii.x, d.dot(v) / ellipse.radii.y);
+    (x * x + y * y - 1.0).abs() < 1e-9
+}
+
+#[test]
+fn test_ellipse2_closest_point() {
+    let ellipse = Ellipse2::new(
+        Point2::new(1.0, -2.0),
+        Vector2::new(3.0, 1.0),
+        Deg(30.0).into(),
+    );
+    let points = [
+        Point2::new(6.0, 1.0),
+        Point2::new(-3.0, -2.5),
+        Point2::new(1.2, -1.9),
+        Point2::new(0.5, -2.0),
+        // The center and points on the axes, where the closest point may
+        // leave the axis.
+        Point2::new(1.0, -2.0),
+        ellipse.center + ellipse.axes()[0] * 0.5,
+        ellipse.center + ellipse.axes()[0] * 2.95,
+        ellipse.center + ellipse.axes()[1] * 0.5,
+        ellipse.center + ellipse.axes()[1] * 4.0,
+    ];
+    for &p in &points {
+        let q = ellipse.closest_point(p);
+        assert!(on_boundary(&ellipse, q), "{:?}", p);
+        assert!(
+            q.distance(p) <= sampled_distance(&ellipse, p) + 1e-6,
+            "{:?}",
+            p
+        );
+    }
+    assert_relative_eq!(
+        ellipse
+            .center
+            .distance(ellipse.closest_point(ellipse.center)),
+        1.0
+    );
+}
+
+#[test]
+fn test_ellipse2_contains_point() {
+    let ellipse = Ellipse2::new(
+        Point2::new(0.0, 0.0),
+        Vector2::new(2.0, 1.0),
+        Deg(45.0).into(),
+    );
+    assert!(ellipse.contains_point(Point2::new(1.0, 1.0)));
+    assert!(!ellipse.contains_point(Point2::new(1.0, -1.0)));
+    assert!(ellipse.contains_point(Point2::new(0.0, 0.0)));
+}
+
+#[test]
+fn test_ellipsoid_closest_point() {
+    let axes = Matrix3::from(Quaternion::from_axis_angle(
+        Vector3::new(1.0, 2.0, 2.0).normalize(),
+        Deg(40.0),
+    ));
+    let ellipsoid = Ellipsoid3::new(
+        Point3::new(1.0, 0.0, -1.0),
+        Vector3::new(2.0, 0.5, 1.0),
+        axes,
+    );
+    let sampled = |p: Point3<f64>| {
+        let mut best = ::std::f64::INFINITY;
+        for i in 0..400 {
+            for j in 0..=200 {
+                let theta = i as f64 / 400.0 * 2.0 * ::std::f64::consts::PI;
+                let phi = j as f64 / 200.0 * ::std::f64::consts::PI;
+                let local = Vector3::new(
+                    ellipsoid.radii.x * phi.sin() * theta.cos(),
+                    ellipsoid.radii.y * phi.sin() * theta.sin(),
+                    ellipsoid.radii.z * phi.cos(),
+                );
+                best = best.min((ellipsoid.center + axes * local).distance(p));
+            }
+        }
+        best
+    };
+    let points = [
+        Point3::new(4.0, 1.0, 2.0),
+        Point3::new(1.2, 0.1, -0.8),
+        Point3::new(1.0, 0.0, -1.0),
+        ellipsoid.center + axes.x * 0.5,
+        ellipsoid.center + axes.z * 0.2,
+        ellipsoid.center + axes.x * 3.0 + axes.z * 0.1,
+    ];
+    for &p in &points {
+        let q = ellipsoid.closest_point(p);
+        let local = axes.transpose() * (q - ellipsoid.center);
+        let local = local.div_element_wise(ellipsoid.radii);
+        assert_relative_eq!(local.magnitude(), 1.0, epsilon = 1e-9);
+        assert!(q.distance(p) <= sampled(p) + 1e-6, "{:?}", p);
+    }
+
+    let sphere = Sphere::new(Point3::new(1.0, 2.0, 3.0), 2.0);
+    let ellipsoid = Ellipsoid3::from_sphere(&sphere);
+    let q = ellipsoid.closest_point(Point3::new(1.0, 2.0, 10.0));
+    assert_relative_eq!(q, Point3::new(1.0, 2.0, 5.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_ray_intersection() {
+    let ellipse = Ellipse2::new(
+        Point2::new(0.0, 0.0),
+        Vector2::new(2.0, 1.0),
+        Deg(90.0).into(),
+    );
+    let ray = Ray2::new(Point2::new(0.0, -5.0), Vector2::new(0.0, 2.0));
+    assert_relative_eq!(
+        (ray, ellipse).intersection().unwrap(),
+        Point2::new(0.0, -2.0)
+    );
+    let inside = Ray2::new(Point2::new(0.0, 0.0), Vector2::new(1.0, 0.0));
+    assert_relative_eq!(
+        (inside, ellipse).intersection().unwrap(),
+        Point2::new(1.0, 0.0)
+    );
+    let miss = Ray2::new(Point2::new(1.5, -5.0), Vector2::new(0.0, 1.0));
+    assert_eq!((miss, ellipse).intersection(), None);
+    let away = Ray2::new(Point2::new(0.0, -5.0), Vector2::new(0.0, -1.0));
+    assert_eq!((away, ellipse).intersection(), None);
+
+    let ellipsoid = Ellipsoid3::new(
+        Point3::new(0.0, 1.0, 0.0),
+        Vector3::new(1.0, 2.0, 3.0),
+        Matrix3::from_angle_x(Deg(90.0)),
+    );
+    let ray = Ray3::new(Point3::new(0.0, 1.0, 10.0), Vector3::new(0.0, 0.0, -1.0));
+    assert_relative_eq!(
+        (ray, ellipsoid).intersection().unwrap(),
+        Point3::new(0.0, 1.0, 2.0),
+        epsilon = 1e-12
+    );
+    let ray = Ray3::new(Point3::new(10.0, 1.0, 0.0), Vector3::new(-1.0, 0.0, 0.0));
+    assert_relative_eq!(
+        (ray, ellipsoid).intersection().unwrap(),
+        Point3::new(1.0, 1.0, 0.0),
+        epsilon = 1e-12
+    );
+    let miss = Ray3::new(Point3::new(10.0, 1.0, 2.5), Vector3::new(-1.0, 0.0, 0.0));
+    assert_eq!((miss, ellipsoid).intersection(), None);
+}
@@ -0,0 +1,160 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::epipolar::*;
+use cgmath::*;
+
+fn cameras() -> (PinholeCamera<f64>, PinholeCamera<f64>) {
+    let a = PinholeCamera::from_fov(Deg(70.0), 640.0, 480.0);
+    let b = PinholeCamera {
+        focal: Vector2::new(900.0, 880.0),
+        principal_point: Point2::new(330.0, 250.0),
+        skew: 1.5,
+    };
+    (a, b)
+}
+
+fn relative() -> Decomposed<Vector3<f64>, Quaternion<f64>> {
+    Decomposed {
+        scale: 1.0,
+        rot: Quaternion::from_axis_angle(Vector3::new(0.2, 1.0, -0.1).normalize(), Deg(12.0)),
+        disp: Vector3::new(-0.5, 0.05, 0.1),
+    }
+}
+
+fn points() -> Vec<Point3<f64>> {
+    vec![
+        Point3::new(0.0, 0.0, -3.0),
+        Point3::new(0.4, -0.3, -2.5),
+        Point3::new(-0.8, 0.6, -6.0),
+        Point3::new(1.2, 0.9, -4.0),
+    ]
+}
+
+fn homogeneous(p: Point2<f64>) -> Vector3<f64> {
+    Vector3::new(p.x, p.y, 1.0)
+}
+
+#[test]
+fn test_essential_matrix() {
+    let (a, b) = cameras();
+    let e = essential_matrix(&relative());
+    for p in points() {
+        let na = a.pixel_to_normalized(a.project(p).unwrap());
+        let nb = b.pixel_to_normalized(b.project(relative().transform_point(p)).unwrap());
+        assert_relative_eq!(
+            homogeneous(nb).dot(e * homogeneous(na)),
+            0.0,
+            epsilon = 1e-12
+        );
+    }
+    // Essential matrices are singular.
+    assert_relative_eq!(e.determinant(), 0.0, epsilon = 1e-12);
+
+    // Without translation, the views do not constrain each other.
+    let rotation = Decomposed {
+        disp: Vector3::zero(),
+        ..relative()
+    };
+    assert_relative_eq!(
+        essential_matrix(&rotation),
+        Matrix3::zero(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_epipolar_lines() {
+    let (a, b) = cameras();
+    let f = fundamental_matrix(&a, &b, &relative()).unwrap();
+    for p in points() {
+        let pa = a.project(p).unwrap();
+        let pb = b.project(relative().transform_point(p)).unwrap();
+        assert_relative_eq!(
+            homogeneous(pb).dot(f * homogeneous(pa)),
+            0.0,
+            epsilon = 1e-9
+        );
+        let line = epipolar_line(&f, pa).unwrap();
+        assert_relative_eq!(line.distance(pb), 0.0, epsilon = 1e-9);
+    }
+
+    // All the epipolar lines go through the image of the first camera, here
+    // behind the second one.
+    let center = relative().transform_point(Point3::origin());
+    assert!(center.z > 0.0);
+    let epipole = b.normalized_to_pixel(Point2::new(center.x / -center.z, center.y / center.z));
+    for p in points() {
+        let line = epipolar_line(&f, a.project(p).unwrap()).unwrap();
+        assert_relative_eq!(line.distance(epipole), 0.0, epsilon = 1e-6);
+    }
+
+    let degenerate = PinholeCamera {
+        focal: Vector2::new(0.0, 880.0),
+        ..b
+    };
+    assert_eq!(fundamental_matrix(&a, &degenerate, &relative()), None);
+}
+
+#[test]
+fn test_epipolar_line_of_epipole() {
+    let (a, _) = cameras();
+    // Moving straight back, the epipole is the principal point.
+    let backward = Decomposed {
+        scale: 1.0,
+        rot: Quaternion::one(),
+        disp: Vector3::new(0.0, 0.0, -1.0),
+    };
+    let f = fundamental_matrix(&a, &a, &backward).unwrap();
+    // The line vanishes up to rounding, which only counts as zero when the
+    // `strict-fp` feature does not ask for exact comparisons.
+    let implicit = cfg!(not(feature = "strict-fp"));
+    assert_eq!(epipolar_line(&f, a.principal_point).is_none(), implicit);
+    assert!(epipolar_line(&f, Point2::new(10.0, 20.0)).is_some());
+}
+
+#[test]
+fn test_triangulate_pixels() {
+    let (a, b) = cameras();
+    for p in points() {
+        let pa = a.project(p).unwrap();
+        let pb = b.project(relative().transform_point(p)).unwrap();
+        let triangulated = triangulate_pixels(&a, pa, &b, pb, &relative()).unwrap();
+        assert_relative_eq!(triangulated, p, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_triangulate_rays() {
+    // Skew rays, one unit apart at their closest.
+    let a = Ray3::new(Point3::new(-1.0, 0.0, 0.0), Vector3::new(1.0, 0.0, -1.0));
+    let b = Ray3::new(Point3::new(1.0, 1.0, 0.0), Vector3::new(-1.0, 0.0, -1.0));
+    assert_relative_eq!(
+        triangulate_rays(&a, &b).unwrap(),
+        Point3::new(0.0, 0.5, -1.0),
+        epsilon = 1e-12
+    );
+
+    // Parallel rays meet nowhere.
+    let c = Ray3::new(Point3::new(1.0, 0.0, 0.0), Vector3::new(2.0, 0.0, -2.0));
+    assert_eq!(triangulate_rays(&a, &c), None);
+
+    // Rays whose lines meet behind them.
+    let d = Ray3::new(Point3::new(1.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 1.0));
+    assert_eq!(triangulate_rays(&a, &d), None);
+}
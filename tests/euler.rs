@@ -0,0 +1,155 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Round trips between Euler angles and every other rotation representation,
+//! over a grid of angles covering all the octants, and checks of the
+//! conventions documented on `Euler`.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+/// Euler angles on a grid of 15 degree steps, staying away from the gimbal
+/// lock at `y = ±90°`, where the `x` and `z` angles are not unique.
+fn grid() -> Vec<Euler<Deg<f64>>> {
+    let mut angles = Vec::new();
+    for x in -12..12 {
+        for y in -5..6 {
+            for z in -12..12 {
+                let step = 15.0;
+                angles.push(Euler::new(
+                    Deg(x as f64 * step + 1.0),
+                    Deg(y as f64 * step + 1.0),
+                    Deg(z as f64 * step + 1.0),
+                ));
+            }
+        }
+    }
+    angles
+}
+
+const VECTORS: [[f64; 3]; 4] = [
+    [1.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0],
+    [0.0, 0.0, 1.0],
+    [0.3, -1.2, 2.5],
+];
+
+/// Check that two rotations, given as functions applying them, agree.
+fn assert_same_rotation<F, G>(f: F, g: G)
+where
+    F: Fn(Vector3<f64>) -> Vector3<f64>,
+    G: Fn(Vector3<f64>) -> Vector3<f64>,
+{
+    for v in VECTORS.iter() {
+        let v = Vector3::from(*v);
+        assert_relative_eq!(f(v), g(v), epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_conventions() {
+    for e in grid() {
+        let m = Matrix3::from(e);
+        // Intrinsic rotations about `x`, then `y`, then `z`, multiplied on
+        // the right with column vectors.
+        let intrinsic =
+            Matrix3::from_angle_x(e.x) * Matrix3::from_angle_y(e.y) * Matrix3::from_angle_z(e.z);
+        assert_relative_eq!(m, intrinsic, epsilon = 1e-12);
+        // Which are extrinsic rotations about `z`, then `y`, then `x`.
+        assert_same_rotation(
+            |v| m * v,
+            |v| {
+                let v = Matrix3::from_angle_z(e.z) * v;
+                let v = Matrix3::from_angle_y(e.y) * v;
+                Matrix3::from_angle_x(e.x) * v
+            },
+        );
+    }
+    // Positive angles follow the right-hand rule.
+    let x = Vector3::unit_x();
+    let y = Vector3::unit_y();
+    let z = Vector3::unit_z();
+    let quarter = |e: Euler<Deg<f64>>| Matrix3::from(e);
+    assert_relative_eq!(
+        quarter(Euler::new(Deg(90.0), Deg(0.0), Deg(0.0))) * y,
+        z,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        quarter(Euler::new(Deg(0.0), Deg(90.0), Deg(0.0))) * z,
+        x,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        quarter(Euler::new(Deg(0.0), Deg(0.0), Deg(90.0))) * x,
+        y,
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_representations_agree() {
+    for e in grid() {
+        let m3 = Matrix3::from(e);
+        let m4 = Matrix4::from(e);
+        let q = Quaternion::from(e);
+        let b3 = Basis3::from(e);
+        let aa = AxisAngle::from(q);
+        let from_aa = Quaternion::from(aa);
+
+        assert_same_rotation(|v| m3 * v, |v| q.rotate_vector(v));
+        assert_same_rotation(|v| m3 * v, |v| b3.rotate_vector(v));
+        assert_same_rotation(|v| m3 * v, |v| (m4 * v.extend(0.0)).truncate());
+        assert_same_rotation(|v| m3 * v, |v| Matrix3::from(q) * v);
+        assert_same_rotation(|v| m3 * v, |v| from_aa.rotate_vector(v));
+        assert_same_rotation(|v| m3 * v, |v| Basis3::from(q).rotate_vector(v));
+    }
+}
+
+#[test]
+fn test_quaternion_round_trip() {
+    for e in grid() {
+        let q = Quaternion::from(e);
+        let back = Euler::from(q);
+        let expected = Euler::new(Rad::from(e.x), Rad::from(e.y), Rad::from(e.z));
+        // Outside of the gimbal lock, the angles themselves come back, up to
+        // a full turn.
+        let wrap = |a: Rad<f64>| a.normalize_signed();
+        assert_relative_eq!(wrap(back.x), wrap(expected.x), epsilon = 1e-9);
+        assert_relative_eq!(back.y, expected.y, epsilon = 1e-9);
+        assert_relative_eq!(wrap(back.z), wrap(expected.z), epsilon = 1e-9);
+        assert_same_rotation(
+            |v| q.rotate_vector(v),
+            |v| Quaternion::from(back).rotate_vector(v),
+        );
+    }
+}
+
+#[test]
+fn test_gimbal_lock_round_trip() {
+    for &y in [-90.0, 90.0].iter() {
+        for x in -6..6 {
+            for z in -6..6 {
+                let e = Euler::new(Deg(x as f64 * 30.0), Deg(y), Deg(z as f64 * 30.0));
+                let q = Quaternion::from(e);
+                // The angles are not unique, but the rotation is preserved.
+                let back = Quaternion::from(Euler::from(q));
+                assert_same_rotation(|v| q.rotate_vector(v), |v| back.rotate_vector(v));
+            }
+        }
+    }
+}
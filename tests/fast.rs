@@ -0,0 +1,116 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+
+use cgmath::fast;
+
+const TOLERANCE: f64 = 2e-6;
+
+fn samples(min: f64, max: f64) -> Vec<f64> {
+    let n = 20000;
+    (0..n + 1)
+        .map(|i| min + (max - min) * i as f64 / n as f64)
+        .collect()
+}
+
+#[test]
+fn test_sin_cos() {
+    for x in samples(-1e4, 1e4).into_iter().chain(samples(-7.0, 7.0)) {
+        let (sin, cos) = fast::sin_cos(x);
+        assert!((sin - x.sin()).abs() < TOLERANCE, "sin({})", x);
+        assert!((cos - x.cos()).abs() < TOLERANCE, "cos({})", x);
+
+        let (sin, cos) = fast::sin_cos(x as f32);
+        let x = x as f32 as f64;
+        assert!((sin as f64 - x.sin()).abs() < TOLERANCE, "sin({}f32)", x);
+        assert!((cos as f64 - x.cos()).abs() < TOLERANCE, "cos({}f32)", x);
+    }
+    assert_eq!(fast::sin_cos(0.0f64).0, 0.0);
+    assert!(fast::sin_cos(std::f64::NAN).0.is_nan());
+    assert!(fast::sin_cos(std::f64::INFINITY).1.is_nan());
+}
+
+#[test]
+fn test_atan() {
+    for x in samples(-50.0, 50.0).into_iter().chain(samples(-2.0, 2.0)) {
+        assert!((fast::atan(x) - x.atan()).abs() < TOLERANCE, "atan({})", x);
+        let y = x as f32;
+        assert!(
+            (fast::atan(y) - y.atan()).abs() < TOLERANCE as f32,
+            "atan({}f32)",
+            y
+        );
+    }
+    assert!((fast::atan(std::f64::INFINITY) - std::f64::consts::FRAC_PI_2).abs() < TOLERANCE);
+}
+
+#[test]
+fn test_atan2() {
+    let pi = std::f64::consts::PI;
+    for angle in samples(-pi, pi) {
+        for &radius in &[1e-3, 1.0, 250.0] {
+            let (y, x) = (radius * angle.sin(), radius * angle.cos());
+            assert!(
+                (fast::atan2(y, x) - y.atan2(x)).abs() < TOLERANCE,
+                "atan2({}, {})",
+                y,
+                x
+            );
+            let (y, x) = (y as f32, x as f32);
+            let exact = (y as f64).atan2(x as f64);
+            assert!(
+                (fast::atan2(y, x) as f64 - exact).abs() < TOLERANCE,
+                "atan2({}f32, {}f32)",
+                y,
+                x
+            );
+        }
+    }
+    assert_eq!(fast::atan2(0.0f64, 0.0), 0.0);
+    assert_eq!(fast::atan2(0.0f64, 1.0), 0.0);
+    assert_eq!(fast::atan2(1.0f64, 0.0), std::f64::consts::FRAC_PI_2);
+    assert!((fast::atan2(0.0f64, -1.0) - std::f64::consts::PI).abs() < TOLERANCE);
+}
+
+#[test]
+fn test_acos() {
+    for x in samples(-1.0, 1.0) {
+        assert!((fast::acos(x) - x.acos()).abs() < TOLERANCE, "acos({})", x);
+        let y = x as f32;
+        assert!(
+            (fast::acos(y) as f64 - (y as f64).acos()).abs() < TOLERANCE,
+            "acos({}f32)",
+            y
+        );
+    }
+    assert_eq!(fast::acos(1.0f64), 0.0);
+    assert!(fast::acos(1.5f64).is_nan());
+}
+
+#[test]
+fn test_rsqrt() {
+    for i in -300..300 {
+        let x = 1.1f64.powi(i);
+        let exact = 1.0 / x.sqrt();
+        assert!((fast::rsqrt(x) / exact - 1.0).abs() < 2e-3, "rsqrt({})", x);
+        assert!(
+            (fast::rsqrt(x as f32) as f64 / exact - 1.0).abs() < 2e-3,
+            "rsqrt({}f32)",
+            x
+        );
+    }
+    assert!(fast::rsqrt(0.0f64).is_finite());
+}
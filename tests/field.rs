@@ -0,0 +1,191 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::field::{Grid2, Grid3};
+
+#[test]
+fn test_bilinear() {
+    // Bilinear interpolation reproduces bilinear functions exactly.
+    let f = |p: Point2<f64>| 2.0 * p.x - p.y + 0.5 * p.x * p.y + 1.0;
+    let grid = Grid2::from_fn(Point2::new(-1.0, 2.0), 0.5, 5, 4, |i, j| {
+        f(Point2::new(-1.0 + 0.5 * i as f64, 2.0 + 0.5 * j as f64))
+    });
+    assert_eq!(grid.width(), 5);
+    assert_eq!(grid.height(), 4);
+    assert_eq!(grid.values().len(), 20);
+    assert_eq!(grid.position(4, 3), Point2::new(1.0, 3.5));
+    assert_eq!(grid.get(4, 3), f(grid.position(4, 3)));
+    for &(x, y) in &[(-1.0, 2.0), (0.3, 2.7), (-0.45, 3.1), (1.0, 3.5)] {
+        let p = Point2::new(x, y);
+        assert_relative_eq!(grid.sample(p), f(p), epsilon = 1e-12);
+    }
+
+    // Queries outside the grid are clamped to its boundary.
+    let corner = grid.position(0, 3);
+    assert_relative_eq!(
+        grid.sample(Point2::new(-5.0, 10.0)),
+        f(corner),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_trilinear() {
+    let f = |p: Point3<f64>| Vector3::new(p.x + p.y * p.z, 2.0 * p.z - p.x * p.y * p.z, p.y);
+    let origin = Point3::new(0.0, -1.0, 1.0);
+    let grid = Grid3::from_fn(origin, 0.25, 3, 4, 5, |i, j, k| {
+        f(origin + Vector3::new(i as f64, j as f64, k as f64) * 0.25)
+    });
+    assert_eq!(grid.depth(), 5);
+    assert_eq!(grid.get(2, 3, 4), f(Point3::new(0.5, -0.25, 2.0)));
+    for &(x, y, z) in &[(0.0, -1.0, 1.0), (0.1, -0.6, 1.3), (0.45, -0.3, 1.95)] {
+        let p = Point3::new(x, y, z);
+        assert_relative_eq!(grid.sample(p), f(p), epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_degenerate_grids() {
+    // A grid with a single row interpolates along it only.
+    let mut line = Grid2::new(Point2::new(0.0, 0.0), 1.0, 3, 1, vec![0.0, 2.0, 4.0]);
+    assert_eq!(line.sample(Point2::new(1.5, 3.0)), 3.0);
+    line.values_mut()[2] = 6.0;
+    assert_eq!(line.sample(Point2::new(1.5, -3.0)), 4.0);
+
+    let point = Grid3::new(Point3::new(1.0, 1.0, 1.0), 1.0, 1, 1, 1, vec![7.0f32]);
+    assert_eq!(point.sample(Point3::new(0.0, 3.0, 1.5)), 7.0);
+}
+
+#[test]
+#[should_panic]
+fn test_wrong_size() {
+    Grid2::new(Point2::new(0.0, 0.0), 1.0, 2, 2, vec![0.0f64; 3]);
+}
+
+#[test]
+fn test_differential_operators_2d() {
+    let at = |i: usize, j: usize| Point2::new(1.0 + 0.5 * i as f64, -2.0 + 0.5 * j as f64);
+    let origin = at(0, 0);
+    let scalar = Grid2::from_fn(origin, 0.5, 4, 5, |i, j| {
+        let p = at(i, j);
+        3.0 * p.x - 2.0 * p.y + 1.0
+    });
+    for v in scalar.gradient().values() {
+        assert_relative_eq!(*v, Vector2::new(3.0, -2.0), epsilon = 1e-12);
+    }
+
+    let spin = Grid2::from_fn(origin, 0.5, 4, 5, |i, j| {
+        let p = at(i, j);
+        Vector2::new(p.x - p.y, p.x + 2.0 * p.y)
+    });
+    for v in spin.divergence().values() {
+        assert_relative_eq!(*v, 3.0, epsilon = 1e-12);
+    }
+    for v in spin.curl().values() {
+        assert_relative_eq!(*v, 2.0, epsilon = 1e-12);
+    }
+
+    // Central differences are exact for quadratics inside the grid.
+    let bowl = Grid2::from_fn(origin, 0.5, 4, 5, |i, j| at(i, j).to_vec().magnitude2());
+    let gradient = bowl.gradient();
+    assert_relative_eq!(gradient.get(1, 2), at(1, 2).to_vec() * 2.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_differential_operators_3d() {
+    let origin = Point3::new(0.0, 1.0, -1.0);
+    let at =
+        |i: usize, j: usize, k: usize| origin + Vector3::new(i as f64, j as f64, k as f64) * 0.25;
+    let field = Grid3::from_fn(origin, 0.25, 3, 4, 5, |i, j, k| {
+        let p = at(i, j, k);
+        Vector3::new(p.y + 2.0 * p.x, -p.x + p.z, 2.0 * p.x - p.z)
+    });
+    for v in field.divergence().values() {
+        assert_relative_eq!(*v, 1.0, epsilon = 1e-12);
+    }
+    for v in field.curl().values() {
+        assert_relative_eq!(*v, Vector3::new(-1.0, -2.0, -2.0), epsilon = 1e-12);
+    }
+
+    let scalar = Grid3::from_fn(origin, 0.25, 3, 4, 5, |i, j, k| {
+        at(i, j, k).dot(Vector3::new(1.0, 2.0, 3.0))
+    });
+    for v in scalar.gradient().values() {
+        assert_relative_eq!(*v, Vector3::new(1.0, 2.0, 3.0), epsilon = 1e-12);
+    }
+
+    // Along an axis with a single sample, the derivative is zero.
+    let flat = Grid3::from_fn(origin, 1.0, 2, 1, 1, |i, _, _| i as f64);
+    assert_eq!(flat.gradient().get(0, 0, 0), Vector3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_distance_field() {
+    // On an open grid, the distances are octile distances.
+    let open = Grid2::from_fn(Point2::new(0.0, 0.0), 2.0f64, 6, 5, |_, _| true);
+    let distances = open.distance_field(&[(1, 1)]);
+    for j in 0..5 {
+        for i in 0..6 {
+            let (dx, dy) = ((i as f64 - 1.0).abs(), (j as f64 - 1.0).abs());
+            let octile = dx.max(dy) + (2f64.sqrt() - 1.0) * dx.min(dy);
+            assert_relative_eq!(distances.get(i, j), 2.0 * octile, epsilon = 1e-12);
+        }
+    }
+
+    // With several goals, each cell goes to the closest one.
+    let distances = open.distance_field(&[(0, 0), (5, 4)]);
+    assert_eq!(distances.get(5, 3), 2.0);
+    assert_eq!(distances.get(1, 0), 2.0);
+}
+
+#[test]
+fn test_flow_field() {
+    // A maze-like grid, where `#` is blocked.
+    let rows = ["..#....", ".##.##.", "...#...", "##...#.", "....#.."];
+    let walkable = Grid2::from_fn(Point2::new(0.0, 0.0), 1.0f64, 7, 5, |i, j| {
+        rows[j].as_bytes()[i] == b'.'
+    });
+    let goal = (6, 4);
+    let distances = walkable.distance_field(&[goal]);
+    let flow = distances.flow_field();
+    assert_eq!(flow.get(goal.0, goal.1), Vector2::zero());
+    assert_eq!(flow.get(2, 0), Vector2::zero());
+    assert!(distances.get(2, 0).is_infinite());
+
+    // Following the flow from any reachable cell reaches the goal along a
+    // path as long as its distance.
+    for j in 0..5 {
+        for i in 0..7 {
+            if !distances.get(i, j).is_finite() {
+                continue;
+            }
+            let (mut x, mut y, mut length) = (i as isize, j as isize, 0.0);
+            while (x as usize, y as usize) != goal {
+                let d = flow.get(x as usize, y as usize);
+                let (dx, dy) = (d.x.round() as isize, d.y.round() as isize);
+                assert!(walkable.get((x + dx) as usize, (y + dy) as usize));
+                length += ((dx * dx + dy * dy) as f64).sqrt();
+                x += dx;
+                y += dy;
+            }
+            assert_relative_eq!(length, distances.get(i, j), epsilon = 1e-12);
+        }
+    }
+}
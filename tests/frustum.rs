@@ -0,0 +1,271 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_from_perspective() {
+    let frustum = Frustum::from_matrix4(perspective(Deg(90.0f64), 2.0, 1.0, 100.0)).unwrap();
+    assert_relative_eq!(
+        frustum.near,
+        Plane::new(-Vector3::unit_z(), 1.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        frustum.far,
+        Plane::new(Vector3::unit_z(), -100.0),
+        epsilon = 1e-9
+    );
+    let s = 0.5f64.sqrt();
+    assert_relative_eq!(
+        frustum.bottom,
+        Plane::new(Vector3::new(0.0, s, -s), 0.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        frustum.top,
+        Plane::new(Vector3::new(0.0, -s, -s), 0.0),
+        epsilon = 1e-12
+    );
+
+    let corners = frustum.corners();
+    assert_relative_eq!(corners[0], Point3::new(-2.0, -1.0, -1.0), epsilon = 1e-9);
+    assert_relative_eq!(corners[3], Point3::new(2.0, 1.0, -1.0), epsilon = 1e-9);
+    assert_relative_eq!(
+        corners[4],
+        Point3::new(-200.0, -100.0, -100.0),
+        epsilon = 1e-6
+    );
+}
+
+#[test]
+fn test_from_view_projection() {
+    let projection = ortho(-1.0f64, 1.0, -1.0, 1.0, 0.0, 10.0);
+    let view = Matrix4::look_at(
+        Point3::new(5.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::unit_y(),
+    );
+    let frustum = Frustum::from_matrix4(projection * view).unwrap();
+    // The camera looks down the negative x axis from x = 5.
+    assert_relative_eq!(
+        frustum.near.distance(Point3::new(4.0, 0.0, 0.0)),
+        1.0,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        frustum.far.distance(Point3::new(4.0, 0.0, 0.0)),
+        9.0,
+        epsilon = 1e-12
+    );
+    for corner in frustum.corners().iter() {
+        assert!(corner.y.abs() > 0.999 && corner.z.abs() > 0.999);
+    }
+
+    assert_eq!(Frustum::from_matrix4(Matrix4::<f64>::zero()), None);
+}
+
+#[test]
+fn test_to_wireframe_lines() {
+    let frustum = Frustum::from_matrix4(ortho(-1.0, 1.0, -2.0, 2.0, 1.0, 3.0)).unwrap();
+    let lines = frustum.to_wireframe_lines();
+    assert_eq!(lines.len(), 12);
+    let total: f64 = lines.iter().map(|&(a, b)| a.distance(b)).sum();
+    assert_relative_eq!(total, 4.0 * (2.0 + 4.0 + 2.0), epsilon = 1e-9);
+}
+
+#[test]
+fn test_packed_planes() {
+    let view_projection = perspective(Deg(60.0f64), 1.5, 0.1, 50.0)
+        * Matrix4::look_at(
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(0.0, 0.0, 0.0),
+            Vector3::unit_y(),
+        );
+    let frustum = Frustum::from_matrix4(view_projection).unwrap();
+
+    let planes = frustum.to_planes();
+    assert_eq!(planes[0], frustum.left);
+    assert_eq!(planes[5], frustum.far);
+    assert_eq!(Frustum::from_planes(planes), frustum);
+
+    let packed = frustum.to_vector4_array();
+    let p = Point3::new(0.5, -0.2, 0.1);
+    for (v, plane) in packed.iter().zip(planes.iter()) {
+        assert_relative_eq!(
+            v.dot(p.to_homogeneous()),
+            plane.distance(p),
+            epsilon = 1e-12
+        );
+    }
+    let unpacked = Frustum::from_vector4_array(packed).unwrap();
+    assert_relative_eq!(unpacked.near, frustum.near, epsilon = 1e-12);
+    assert_relative_eq!(unpacked.top, frustum.top, epsilon = 1e-12);
+
+    // Scaled equations are normalized back.
+    let mut scaled = packed;
+    scaled[2] *= 4.0;
+    let rescaled = Frustum::from_vector4_array(scaled).unwrap();
+    assert_relative_eq!(rescaled.bottom, frustum.bottom, epsilon = 1e-12);
+    scaled[3] = Vector4::unit_w();
+    assert_eq!(Frustum::from_vector4_array(scaled), None);
+}
+
+#[test]
+fn test_to_matrix4_inverts_extraction() {
+    let matrices = [
+        perspective(Deg(75.0f64), 1.6, 0.1, 500.0),
+        frustum(-0.3, 0.5, -0.2, 0.4, 0.5, 20.0),
+        ortho(-4.0, 2.0, -1.0, 3.0, 0.5, 30.0),
+    ];
+    for m in matrices.iter() {
+        let rebuilt = Frustum::from_matrix4(*m).unwrap().to_matrix4(1e-9).unwrap();
+        assert_relative_eq!(rebuilt, *m, epsilon = 1e-9, max_relative = 1e-9);
+    }
+
+    // Exact frusta from projections give the same matrices.
+    let projection = Ortho {
+        left: -1.0,
+        right: 1.0,
+        bottom: -2.0,
+        top: 2.0,
+        near: 1.0,
+        far: 10.0f64,
+    };
+    assert_relative_eq!(
+        projection.to_frustum().to_matrix4(1e-9).unwrap(),
+        projection.to_matrix4(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_to_matrix4_of_world_space_frustum() {
+    let view = Matrix4::look_at(
+        Point3::new(3.0, 4.0, 5.0),
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::unit_y(),
+    );
+    let view_projection = perspective(Deg(60.0f64), 1.0, 1.0, 50.0) * view;
+    let frustum = Frustum::from_matrix4(view_projection).unwrap();
+    let m = frustum.to_matrix4(1e-9).unwrap();
+    // The rebuilt matrix is the original one up to a positive scale.
+    let scale = m.w.w / view_projection.w.w;
+    assert!(scale > 0.0);
+    assert_relative_eq!(m, view_projection * scale, epsilon = 1e-9);
+}
+
+#[test]
+fn test_to_matrix4_rejects_invalid_planes() {
+    let valid = Frustum::from_matrix4(perspective(Deg(60.0f64), 1.0, 1.0, 50.0)).unwrap();
+
+    // Normals pointing outside.
+    let mut planes = valid.to_planes();
+    for plane in planes.iter_mut() {
+        *plane = Plane::new(-plane.normal, -plane.d);
+    }
+    assert_eq!(Frustum::from_planes(planes).to_matrix4(1e-9), None);
+
+    // A tilted far plane does not bound a projective image of a cube.
+    let mut tilted = valid;
+    tilted.far = Plane::from_point_normal(
+        Point3::new(0.0, 0.0, -50.0),
+        Vector3::new(0.3, 0.0, 1.0).normalize(),
+    );
+    assert_eq!(tilted.to_matrix4(1e-9), None);
+
+    // Parallel side planes meeting nowhere.
+    let mut open = valid;
+    open.left = open.right;
+    assert_eq!(open.to_matrix4(1e-9), None);
+}
+
+#[test]
+fn test_clip_to_aabb() {
+    // A box-shaped frustum spanning [-1, 1] x [-1, 1] x [-10, -1].
+    let frustum = Frustum::from_matrix4(ortho(-1.0f64, 1.0, -1.0, 1.0, 1.0, 10.0)).unwrap();
+    let bounds = |aabb: Aabb3<f64>| Aabb3::from_points(frustum.clip_to_aabb(&aabb));
+
+    let overlap = Aabb3::new(Point3::new(0.0, -5.0, -3.0), Point3::new(5.0, 0.5, 3.0));
+    let expected = Aabb3::new(Point3::new(0.0, -1.0, -3.0), Point3::new(1.0, 0.5, -1.0));
+    let clipped = bounds(overlap).unwrap();
+    assert_relative_eq!(clipped.min, expected.min, epsilon = 1e-12);
+    assert_relative_eq!(clipped.max, expected.max, epsilon = 1e-12);
+
+    let inside = Aabb3::new(Point3::new(-0.5, -0.5, -5.0), Point3::new(0.5, 0.5, -4.0));
+    assert_eq!(bounds(inside), Some(inside));
+
+    let around = Aabb3::new(Point3::new(-5.0, -5.0, -20.0), Point3::new(5.0, 5.0, 20.0));
+    let clipped = bounds(around).unwrap();
+    assert_relative_eq!(clipped.min, Point3::new(-1.0, -1.0, -10.0), epsilon = 1e-12);
+    assert_relative_eq!(clipped.max, Point3::new(1.0, 1.0, -1.0), epsilon = 1e-12);
+
+    let outside = Aabb3::new(Point3::new(2.0, 2.0, -5.0), Point3::new(3.0, 3.0, -4.0));
+    assert!(frustum.clip_to_aabb(&outside).is_empty());
+}
+
+#[test]
+fn test_clip_to_aabb_perspective() {
+    let projection = perspective(Deg(90.0f64), 1.0, 1.0, 10.0);
+    let frustum = Frustum::from_matrix4(projection).unwrap();
+    // A slab cutting the frustum between depths 2 and 4, which is 8 wide
+    // at its far side.
+    let slab = Aabb3::new(
+        Point3::new(-100.0, -100.0, -4.0),
+        Point3::new(100.0, 100.0, -2.0),
+    );
+    let points = frustum.clip_to_aabb(&slab);
+    let planes = frustum.to_planes();
+    for &p in &points {
+        assert!(planes.iter().all(|plane| plane.distance(p) > -1e-9));
+        assert!(slab.contains_point(p));
+    }
+    let clipped = Aabb3::from_points(points).unwrap();
+    assert_relative_eq!(clipped.min, Point3::new(-4.0, -4.0, -4.0), epsilon = 1e-9);
+    assert_relative_eq!(clipped.max, Point3::new(4.0, 4.0, -2.0), epsilon = 1e-9);
+}
+
+#[test]
+fn test_shadow_projection() {
+    let frustum = Frustum::from_matrix4(ortho(-1.0f64, 1.0, -1.0, 1.0, 1.0, 10.0)).unwrap();
+    let scene = Aabb3::new(Point3::new(0.0, -5.0, -3.0), Point3::new(5.0, 0.5, 3.0));
+    // A light shining down the z axis, from z = 10.
+    let light_view = Matrix4::from_translation(Vector3::new(0.0, 0.0, -10.0));
+    let bounds = frustum.shadow_bounds(&scene, &light_view).unwrap();
+    // Across the light, the bounds are those of the clipped frustum, but
+    // they reach the end of the scene towards the light.
+    assert_relative_eq!(bounds.min, Point3::new(0.0, -1.0, -13.0), epsilon = 1e-12);
+    assert_relative_eq!(bounds.max, Point3::new(1.0, 0.5, -7.0), epsilon = 1e-12);
+
+    let projection = frustum.shadow_projection(&scene, &light_view).unwrap();
+    let crop = projection * light_view;
+    assert_relative_eq!(
+        crop.transform_point(Point3::new(0.0, -1.0, -3.0)),
+        Point3::new(-1.0, -1.0, 1.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        crop.transform_point(Point3::new(1.0, 0.5, 3.0)),
+        Point3::new(1.0, 1.0, -1.0),
+        epsilon = 1e-12
+    );
+
+    let elsewhere = Aabb3::new(Point3::new(2.0, 2.0, -5.0), Point3::new(3.0, 3.0, -4.0));
+    assert_eq!(frustum.shadow_bounds(&elsewhere, &light_view), None);
+    assert_eq!(frustum.shadow_projection(&elsewhere, &light_view), None);
+}
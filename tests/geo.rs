@@ -0,0 +1,123 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::geo::*;
+use cgmath::*;
+
+#[test]
+fn test_to_ecef() {
+    let wgs84 = Ellipsoid::wgs84();
+
+    let equator = wgs84.to_ecef(Geodetic::new(Deg(0.0), Deg(0.0), 0.0));
+    assert_relative_eq!(equator, Point3::new(6378137.0, 0.0, 0.0), epsilon = 1e-6);
+
+    let pole = wgs84.to_ecef(Geodetic::new(Deg(90.0), Deg(0.0), 0.0));
+    assert_relative_eq!(pole, Point3::new(0.0, 0.0, 6356752.314245), epsilon = 1e-5);
+
+    let east = wgs84.to_ecef(Geodetic::new(Deg(0.0), Deg(90.0), 100.0));
+    assert_relative_eq!(east, Point3::new(0.0, 6378237.0, 0.0), epsilon = 1e-6);
+}
+
+#[test]
+fn test_round_trip() {
+    let wgs84 = Ellipsoid::wgs84();
+    for &(lat, lon, alt) in &[
+        (45.0, 7.5, 300.0),
+        (-33.9, 151.2, -20.0),
+        (89.9, -120.0, 10000.0),
+        (-12.0, -77.0, 0.0),
+    ] {
+        let g = Geodetic::new(Deg(lat), Deg(lon), alt);
+        let back = wgs84.to_geodetic(wgs84.to_ecef(g));
+        assert_relative_eq!(Deg::from(back.latitude), Deg(lat), epsilon = 1e-9);
+        assert_relative_eq!(Deg::from(back.longitude), Deg(lon), epsilon = 1e-9);
+        assert_relative_eq!(back.altitude, alt, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_enu_frame() {
+    let g = Geodetic::new(Deg(30.0), Deg(60.0), 0.0);
+    let frame = g.enu_frame();
+    assert_relative_eq!(
+        frame.transpose() * frame,
+        Matrix3::identity(),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(frame.determinant(), 1.0, epsilon = 1e-12);
+
+    // At the equator and prime meridian, east is +y and up is +x.
+    let frame = Geodetic::new(Deg(0.0), Deg(0.0), 0.0).enu_frame();
+    assert_relative_eq!(frame.x, Vector3::unit_y());
+    assert_relative_eq!(frame.y, Vector3::unit_z());
+    assert_relative_eq!(frame.z, Vector3::unit_x());
+}
+
+#[test]
+fn test_local_tangent() {
+    let wgs84 = Ellipsoid::wgs84();
+    let origin = Geodetic::new(Deg(51.4779f64), Deg(-0.0015), 45.0);
+    let local = LocalTangent::new(&wgs84, origin);
+
+    assert_relative_eq!(
+        local.to_local(local.origin),
+        Point3::origin(),
+        epsilon = 1e-9
+    );
+
+    let north = wgs84.to_ecef(Geodetic::new(Deg(51.4879), Deg(-0.0015), 45.0));
+    let enu = local.to_local(north);
+    assert!(enu.x.abs() < 1e-6);
+    assert!(enu.y > 1100.0 && enu.y < 1120.0);
+
+    let p = Point3::new(10.0, -20.0, 5.0);
+    assert_relative_eq!(local.to_local(local.to_ecef(p)), p, epsilon = 1e-6);
+}
+
+#[test]
+fn test_sun_position() {
+    // Noon on the first day of 2000 at Greenwich, when the declination of
+    // the sun is -23.03 degrees and the equation of time -3.3 minutes.
+    let greenwich = Geodetic::new(Deg(51.4769f64), Deg(0.0), 0.0);
+    let sun = sun_position(946_728_000.0, &greenwich);
+    assert_relative_eq!(
+        Deg::from(sun.elevation).0,
+        90.0 - 51.4769 - 23.03,
+        epsilon = 0.02
+    );
+    assert_relative_eq!(Deg::from(sun.azimuth).0, 179.2, epsilon = 0.05);
+
+    // At midnight, the sun is below the horizon, to the north.
+    let night = sun_position(946_728_000.0 - 43_200.0, &greenwich);
+    assert!(night.elevation < Rad(0.0));
+    assert!(night.direction().y > 0.0);
+
+    // At noon UTC near the June solstice of 2021, the sun is overhead on the
+    // tropic of Cancer, 1.6 minutes of time east of Greenwich.
+    let tropic = Geodetic::new(Deg(23.44f32), Deg(0.4), 0.0);
+    let sun = sun_position(1_624_276_800.0, &tropic);
+    assert!(Deg::from(sun.elevation).0 > 89.8);
+    let v = sun.direction();
+    assert_relative_eq!(v.magnitude(), 1.0, epsilon = 1e-6);
+
+    // The sun rises in the east at the March equinox, at the equator.
+    let equator = Geodetic::new(Deg(0.0f64), Deg(0.0), 0.0);
+    let sunrise = sun_position(1_584_662_400.0 + 6.0 * 3600.0 + 7.0 * 60.0, &equator);
+    assert_relative_eq!(Deg::from(sunrise.elevation).0, 0.0, epsilon = 0.3);
+    assert_relative_eq!(Deg::from(sunrise.azimuth).0, 90.0, epsilon = 0.3);
+}
@@ -0,0 +1,71 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::color::Rgb;
+
+#[test]
+fn test_scalar_gradient() {
+    let g = Gradient::new(vec![(1.0, 10.0), (0.0, 0.0f64), (3.0, 20.0)]);
+    assert_eq!(g.stops()[0], (0.0, 0.0));
+    assert_eq!(g.sample(-1.0), 0.0);
+    assert_eq!(g.sample(0.5), 5.0);
+    assert_eq!(g.sample(1.0), 10.0);
+    assert_eq!(g.sample(2.0), 15.0);
+    assert_eq!(g.sample(4.0), 20.0);
+    assert_eq!(g.bake(4), vec![0.0, 10.0, 15.0, 20.0]);
+    assert_eq!(Gradient::constant(3.0f64).sample(-7.0), 3.0);
+    assert_eq!(Gradient::constant(3.0f64).bake(2), vec![3.0, 3.0]);
+}
+
+#[test]
+fn test_hard_edges() {
+    let mut g = Gradient::new(vec![(0.0, 0.0f32), (1.0, 1.0)]);
+    g.add_stop(0.5, 0.5);
+    g.add_stop(0.5, 0.0);
+    assert_eq!(g.stops().len(), 4);
+    assert_eq!(g.sample(0.25), 0.25);
+    // The gradient jumps at the edge, and takes the value after it there.
+    assert_eq!(g.sample(0.499), 0.499);
+    assert_eq!(g.sample(0.5), 0.0);
+    assert_eq!(g.sample(0.75), 0.5);
+}
+
+#[test]
+fn test_vector_and_color_gradients() {
+    let path = Gradient::new(vec![
+        (0.0, Vector3::new(0.0, 0.0, 0.0)),
+        (2.0, Vector3::new(2.0, 4.0, -2.0f64)),
+    ]);
+    assert_eq!(path.sample(0.5), Vector3::new(0.5, 1.0, -0.5));
+
+    let points = Gradient::new(vec![
+        (0.0, Point2::new(1.0, 1.0)),
+        (1.0, Point2::new(3.0, 1.0f64)),
+    ]);
+    assert_eq!(points.sample(0.25), Point2::new(1.5, 1.0));
+
+    let heat = Gradient::new(vec![
+        (0.0, Rgb::new(0.0, 0.0, 1.0f32)),
+        (1.0, Rgb::new(1.0, 0.0, 0.0)),
+    ]);
+    let colors = heat.bake(3);
+    assert_eq!(colors[1], Rgb::new(0.5, 0.0, 0.5));
+    assert_eq!(colors[2], Rgb::new(1.0, 0.0, 0.0));
+}
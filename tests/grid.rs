@@ -0,0 +1,123 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::grid::*;
+use cgmath::*;
+
+#[test]
+fn test_hex_cube_conversion() {
+    let hex = Hex::new(3, -5);
+    let cube = CubeHex::from(hex);
+    assert_eq!(cube, CubeHex { q: 3, r: -5, s: 2 });
+    assert_eq!(Hex::from(cube), hex);
+}
+
+#[test]
+fn test_hex_neighbors() {
+    let hex = Hex::new(1, 2);
+    let neighbors: Vec<Hex> = hex.neighbors().collect();
+    assert_eq!(neighbors.len(), 6);
+    for (i, &n) in neighbors.iter().enumerate() {
+        assert_eq!(hex.distance(n), 1);
+        assert_eq!(hex.neighbor(i), n);
+    }
+}
+
+#[test]
+fn test_hex_distance() {
+    assert_eq!(Hex::new(0, 0).distance(Hex::new(3, -1)), 3);
+    assert_eq!(Hex::new(-2, 2).distance(Hex::new(2, -2)), 4);
+    assert_eq!(Hex::new(1, 1).distance(Hex::new(1, 1)), 0);
+}
+
+#[test]
+fn test_hex_line() {
+    let from = Hex::new(0, 0);
+    let to = Hex::new(4, -2);
+    let line = from.line_to(to);
+    assert_eq!(line.len(), 5);
+    assert_eq!(line[0], from);
+    assert_eq!(line[4], to);
+    for pair in line.windows(2) {
+        assert_eq!(pair[0].distance(pair[1]), 1);
+    }
+    assert_eq!(from.line_to(from), vec![from]);
+}
+
+#[test]
+fn test_hex_ring() {
+    let center = Hex::new(2, -1);
+    assert_eq!(center.ring(0), vec![center]);
+    let ring = center.ring(2);
+    assert_eq!(ring.len(), 12);
+    for &hex in &ring {
+        assert_eq!(center.distance(hex), 2);
+    }
+}
+
+#[test]
+fn test_hex_layout_round_trip() {
+    for &orientation in &[HexOrientation::PointyTop, HexOrientation::FlatTop] {
+        let layout = HexLayout::new(orientation, 2.0f64, Point2::new(10.0, -5.0));
+        for q in -3..4 {
+            for r in -3..4 {
+                let hex = Hex::new(q, r);
+                let center = layout.to_point(hex);
+                assert_eq!(layout.from_point(center), hex);
+                for corner in layout.corners(hex).iter() {
+                    assert_relative_eq!(corner.distance(center), 2.0, epsilon = 1e-12);
+                    // Points slightly inside the corners are still in the hexagon.
+                    assert_eq!(layout.from_point(center + (*corner - center) * 0.95), hex);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_hex_layout_neighbor_spacing() {
+    let layout = HexLayout::new(HexOrientation::FlatTop, 1.0f64, Point2::new(0.0, 0.0));
+    let center = layout.to_point(Hex::new(0, 0));
+    for n in Hex::new(0, 0).neighbors() {
+        assert_relative_eq!(
+            layout.to_point(n).distance(center),
+            3.0f64.sqrt(),
+            epsilon = 1e-12
+        );
+    }
+}
+
+#[test]
+fn test_isometric_round_trip() {
+    let layout = IsometricLayout::new(Vector2::new(64.0, 32.0f64), Point2::new(320.0, 16.0));
+    let tile = Point2::new(2.5, -1.25);
+    let screen = layout.tile_to_screen(tile);
+    assert_relative_eq!(layout.screen_to_tile(screen), tile, epsilon = 1e-12);
+    let m = layout.to_matrix3();
+    let h = m * Vector3::new(tile.x, tile.y, 1.0);
+    assert_relative_eq!(Point2::new(h.x, h.y), screen, epsilon = 1e-12);
+}
+
+#[test]
+fn test_isometric_tile_at() {
+    let layout = IsometricLayout::new(Vector2::new(64.0, 32.0f64), Point2::new(0.0, 0.0));
+    let center = layout.tile_to_screen(Point2::new(3.5, 1.5));
+    assert_eq!(layout.tile_at(center), (3, 1));
+    assert_eq!(layout.tile_at(Point2::new(0.0, 1.0)), (0, 0));
+    assert_eq!(layout.tile_at(Point2::new(0.0, -1.0)), (-1, -1));
+}
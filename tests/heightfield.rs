@@ -0,0 +1,192 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::field::Grid2;
+use cgmath::heightfield::{bake_normal_map, raycast, sobel_normal, terrain_mesh};
+use cgmath::*;
+
+fn sample<F: Fn(f64, f64) -> f64>(f: F, x: f64, z: f64, cell_size: f64) -> [[f64; 3]; 3] {
+    let mut heights = [[0.0; 3]; 3];
+    for (j, row) in heights.iter_mut().enumerate() {
+        for (i, h) in row.iter_mut().enumerate() {
+            *h = f(
+                x + (i as f64 - 1.0) * cell_size,
+                z + (j as f64 - 1.0) * cell_size,
+            );
+        }
+    }
+    heights
+}
+
+#[test]
+fn test_flat() {
+    let normal = sobel_normal([[3.0; 3]; 3], 0.5);
+    assert_eq!(normal, Vector3::unit_y());
+}
+
+#[test]
+fn test_planes() {
+    // Planar terrain gives the exact plane normal, whatever the cell size.
+    let f = |x: f64, z: f64| 0.5 * x - 2.0 * z + 7.0;
+    let expected = Vector3::new(-0.5, 1.0, 2.0).normalize();
+    for &cell_size in &[0.1, 1.0, 4.0] {
+        let normal = sobel_normal(sample(f, 3.0, -1.0, cell_size), cell_size);
+        assert_relative_eq!(normal, expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_curved() {
+    // On a smooth surface, the estimate converges to the analytic normal.
+    let f = |x: f64, z: f64| x.sin() * z.cos();
+    let (x, z) = (0.3f64, 0.8f64);
+    let expected = Vector3::new(-x.cos() * z.cos(), 1.0, x.sin() * z.sin()).normalize();
+    let normal = sobel_normal(sample(f, x, z, 1e-3), 1e-3);
+    assert_relative_eq!(normal, expected, epsilon = 1e-6);
+}
+
+#[test]
+fn test_raycast_saddle() {
+    // The bilinear interpolation of `x * z` is exact, so the ray hits the
+    // saddle where it crosses it.
+    let terrain = Grid2::from_fn(Point2::new(-4.0, -4.0), 0.5, 17, 17, |i, j| {
+        (i as f64 * 0.5 - 4.0) * (j as f64 * 0.5 - 4.0)
+    });
+    let ray = Ray3::new(Point3::new(0.0, 2.0, 0.0), Vector3::new(1.0, 0.0, 1.0));
+    let hit = raycast(&terrain, &ray).unwrap();
+    let x = 2.0f64.sqrt();
+    assert_relative_eq!(hit.t, x, epsilon = 1e-12);
+    assert_relative_eq!(hit.point, Point3::new(x, 2.0, x), epsilon = 1e-12);
+    let normal = Vector3::new(-x, 1.0, -x).normalize();
+    assert_relative_eq!(hit.normal, normal, epsilon = 1e-12);
+
+    // From the other side, across many cells.
+    let ray = Ray3::new(Point3::new(-4.0, 2.0, 3.0), Vector3::new(0.0, 0.0, -1.0));
+    let hit = raycast(&terrain, &ray).unwrap();
+    assert_relative_eq!(hit.point, Point3::new(-4.0, 2.0, -0.5), epsilon = 1e-12);
+}
+
+#[test]
+fn test_raycast_vertical() {
+    let terrain = Grid2::from_fn(Point2::new(10.0, 20.0), 2.0, 4, 3, |i, j| (i + j) as f64);
+    let ray = Ray3::new(Point3::new(13.0, 100.0, 21.0), Vector3::new(0.0, -3.0, 0.0));
+    let hit = raycast(&terrain, &ray).unwrap();
+    assert_relative_eq!(hit.point, Point3::new(13.0, 2.0, 21.0), epsilon = 1e-12);
+    assert_relative_eq!(hit.t, 98.0 / 3.0, epsilon = 1e-12);
+    let normal = Vector3::new(-0.5, 1.0, -0.5).normalize();
+    assert_relative_eq!(hit.normal, normal, epsilon = 1e-12);
+}
+
+#[test]
+fn test_raycast_miss() {
+    let terrain = Grid2::from_fn(Point2::new(0.0, 0.0), 1.0, 5, 5, |_, _| 1.0f64);
+    let up = Ray3::new(Point3::new(2.0, 3.0, 2.0), Vector3::new(0.0, 1.0, 0.0));
+    assert_eq!(raycast(&terrain, &up), None);
+    // Going below the height outside of the grid does not hit it.
+    let past = Ray3::new(Point3::new(2.0, 3.0, 2.0), Vector3::new(1.0, -0.1, 0.0));
+    assert_eq!(raycast(&terrain, &past), None);
+    let away = Ray3::new(Point3::new(-1.0, 0.0, 2.0), Vector3::new(-1.0, 0.0, 0.0));
+    assert_eq!(raycast(&terrain, &away), None);
+    let beside = Ray3::new(Point3::new(-1.0, 3.0, 7.0), Vector3::new(1.0, -1.0, 0.0));
+    assert_eq!(raycast(&terrain, &beside), None);
+
+    // Rays entering from the side below the surface hit it at once.
+    let side = Ray3::new(Point3::new(-1.0, 0.0, 2.0), Vector3::new(1.0, 0.0, 0.0));
+    assert_eq!(
+        raycast(&terrain, &side).unwrap().point,
+        Point3::new(0.0, 0.0, 2.0)
+    );
+
+    let line = Grid2::from_fn(Point2::new(0.0, 0.0), 1.0, 5, 1, |_, _| 1.0f64);
+    let down = Ray3::new(Point3::new(2.0, 3.0, 0.0), Vector3::new(0.0, -1.0, 0.0));
+    assert_eq!(raycast(&line, &down), None);
+}
+
+#[test]
+fn test_terrain_mesh() {
+    let f = |x: f64, z: f64| (x * 0.3).sin() + (z * 0.2).cos();
+    let heights = Grid2::from_fn(Point2::new(-2.0, 1.0), 0.5, 6, 4, |i, j| {
+        f(-2.0 + i as f64 * 0.5, 1.0 + j as f64 * 0.5)
+    });
+    let terrain = terrain_mesh(&heights, Some(2.0));
+    let triangle = |t: &[usize; 3]| {
+        let p = &terrain.positions;
+        Triangle3::new(p[t[0]], p[t[1]], p[t[2]])
+    };
+    // The surface faces up, then the skirt faces outwards and down to the
+    // given depth.
+    let (surface, skirt) = terrain.indices.split_at(2 * 5 * 3);
+    assert!(surface.iter().all(|t| triangle(t).normal().y > 0.0));
+    let center = Point3::new(-0.75, 0.0, 1.75);
+    for t in skirt {
+        let triangle = triangle(t);
+        let outwards = triangle.centroid() - center;
+        assert!(
+            triangle
+                .normal()
+                .dot(Vector3::new(outwards.x, 0.0, outwards.z))
+                > 0.0
+        );
+        assert_abs_diff_eq!(triangle.normal().y, 0.0, epsilon = 1e-12);
+    }
+    assert_eq!(skirt.len(), 2 * 2 * (5 + 3));
+    assert_eq!(terrain.positions.len(), 6 * 4 + 2 * (5 + 3));
+    for (i, p) in terrain.positions.iter().enumerate().skip(24) {
+        let above = terrain.positions[..24]
+            .iter()
+            .position(|q| q.x == p.x && q.z == p.z)
+            .unwrap();
+        assert_eq!(*p, terrain.positions[above] - Vector3::new(0.0, 2.0, 0.0));
+        assert_eq!(terrain.uvs[i], terrain.uvs[above]);
+        assert_eq!(terrain.normals[i], terrain.normals[above]);
+    }
+
+    // The frames follow the surface and the texture, which is not mirrored.
+    for (n, t) in terrain.normals.iter().zip(&terrain.tangents) {
+        assert_abs_diff_eq!(t.truncate().dot(*n), 0.0, epsilon = 1e-12);
+        assert!(t.x > 0.9 && t.w == -1.0);
+    }
+    assert_eq!(terrain.uvs[0], Point2::new(0.0, 0.0));
+    assert_eq!(terrain.uvs[23], Point2::new(1.0, 1.0));
+}
+
+#[test]
+fn test_bake_normal_map() {
+    // Bumps over a flat coarse surface, whose tangent space has its `x` and
+    // `y` axes along `x` and `z`.
+    let origin = Point2::new(0.0, 0.0);
+    let bumps = Grid2::from_fn(origin, 0.25, 17, 17, |i, j| {
+        (i as f64 * 0.7).sin() * (j as f64 * 0.4).cos() * 0.1
+    });
+    let flat = Grid2::from_fn(origin, 2.0, 3, 3, |_, _| 0.0);
+    let map = bake_normal_map(&bumps, &flat);
+    for j in 0..17 {
+        for i in 0..17 {
+            let object = {
+                let terrain = terrain_mesh(&bumps, None);
+                terrain.normals[j * 17 + i]
+            };
+            let baked = map.get(i, j);
+            assert_relative_eq!(
+                baked,
+                Vector3::new(object.x, object.z, object.y),
+                epsilon = 1e-12
+            );
+        }
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+
+fn perspective() -> Homography<f64> {
+    Homography::new(Matrix3::new(
+        1.2, 0.1, 0.002,
+        -0.3, 0.9, -0.001,
+        15.0, -4.0, 1.0,
+    ))
+}
+
+fn grid() -> Vec<Point2<f64>> {
+    let mut points = Vec::new();
+    for i in 0..4 {
+        for j in 0..3 {
+            points.push(Point2::new(i as f64 * 40.0 - 60.0, j as f64 * 25.0 + 5.0));
+        }
+    }
+    points
+}
+
+#[test]
+fn test_transform_point_divides_by_w() {
+    let h = Homography::new(Matrix3::<f64>::new(
+        2.0, 0.0, 0.0,
+        0.0, 2.0, 1.0,
+        0.0, 0.0, 1.0,
+    ));
+    // w = y + 1
+    assert_eq!(h.transform_point(Point2::new(3.0, 0.0)), Point2::new(6.0, 0.0));
+    assert_eq!(h.transform_point(Point2::new(3.0, 1.0)), Point2::new(3.0, 1.0));
+    assert!(!h.transform_point(Point2::new(3.0, -1.0)).x.is_finite());
+}
+
+#[test]
+fn test_identity() {
+    let p = Point2::new(3.0, -7.5);
+    assert_eq!(Homography::identity().transform_point(p), p);
+    assert_eq!(Homography::<f64>::default(), Homography::identity());
+}
+
+#[test]
+fn test_matches_matrix3_for_affine_maps() {
+    let m = Matrix3::from_translation(Vector2::new(2.0, 3.0)) * Matrix3::from_angle_z(Deg(30.0));
+    let h = Homography::from(m);
+    for p in grid() {
+        assert_relative_eq!(
+            h.transform_point(p),
+            Transform::<Point2<f64>>::transform_point(&m, p),
+            epsilon = 1e-12
+        );
+    }
+    assert_eq!(Matrix3::from(h), m);
+}
+
+#[test]
+fn test_inverse() {
+    let h = perspective();
+    let inverse = h.inverse().unwrap();
+    for p in grid() {
+        assert_relative_eq!(inverse.transform_point(h.transform_point(p)), p, epsilon = 1e-9);
+    }
+    assert!(Homography::new(Matrix3::<f64>::zero()).inverse().is_none());
+}
+
+#[test]
+fn test_concat() {
+    let a = perspective();
+    let b = Homography::new(Matrix3::from_nonuniform_scale(2.0, 0.5));
+    for p in grid() {
+        assert_relative_eq!(
+            (a * b).transform_point(p),
+            a.transform_point(b.transform_point(p)),
+            epsilon = 1e-9
+        );
+    }
+}
+
+#[test]
+fn test_from_four_point_pairs() {
+    let h = perspective();
+    let src = [
+        Point2::new(0.0, 0.0),
+        Point2::new(100.0, 0.0),
+        Point2::new(100.0, 50.0),
+        Point2::new(0.0, 50.0),
+    ];
+    let dst: Vec<_> = src.iter().map(|&p| h.transform_point(p)).collect();
+    let fitted = Homography::from_point_pairs(&src, &dst).unwrap();
+    assert_relative_eq!(fitted.m, h.m, epsilon = 1e-9);
+    for p in grid() {
+        assert_relative_eq!(fitted.transform_point(p), h.transform_point(p), epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_from_point_pairs_is_scale_invariant() {
+    let src = grid();
+    let dst: Vec<_> = src.iter().map(|&p| perspective().transform_point(p)).collect();
+    let scaled = Homography::new(perspective().m * 3.0);
+    let fitted = Homography::from_point_pairs(&src, &dst).unwrap();
+    for p in &src {
+        assert_relative_eq!(fitted.transform_point(*p), scaled.transform_point(*p), epsilon = 1e-9);
+    }
+    assert_relative_eq!(fitted.m.z.z, 1.0);
+}
+
+#[test]
+fn test_from_point_pairs_least_squares() {
+    // Small perturbations of the targets, alternating in sign, leave the fit
+    // close to the exact homography.
+    let h = perspective();
+    let src = grid();
+    let dst: Vec<_> = src
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| {
+            let e = if i % 2 == 0 { 1e-3 } else { -1e-3 };
+            h.transform_point(p) + Vector2::new(e, -e)
+        })
+        .collect();
+    let fitted = Homography::from_point_pairs(&src, &dst).unwrap();
+    for p in &src {
+        assert!((fitted.transform_point(*p) - h.transform_point(*p)).magnitude() < 1e-2);
+    }
+}
+
+#[test]
+fn test_from_point_pairs_degenerate() {
+    let square = [
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(0.0, 1.0),
+    ];
+    // Too few or mismatched pairs.
+    assert!(Homography::from_point_pairs(&square[..3], &square[..3]).is_none());
+    assert!(Homography::from_point_pairs(&square, &square[..3]).is_none());
+    // Three collinear source points, which make the system singular up to
+    // rounding, unless the `strict-fp` feature asks for exact comparisons.
+    let collinear = [
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(2.0, 0.0),
+        Point2::new(0.0, 1.0),
+    ];
+    let implicit = cfg!(not(feature = "strict-fp"));
+    assert_eq!(Homography::from_point_pairs(&collinear, &square).is_none(), implicit);
+    // All the points are the same.
+    let same = [Point2::new(1.0, 1.0); 4];
+    assert!(Homography::from_point_pairs(&same, &square).is_none());
+}
@@ -0,0 +1,180 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_ray_plane() {
+    let plane = Plane::from_point_normal(Point3::new(0.0, 1.0, 0.0), Vector3::unit_y());
+    let ray = Ray3::new(Point3::new(2.0, 5.0, 0.0), Vector3::new(0.0, -2.0, 1.0f64));
+    assert_eq!(
+        (ray, plane).intersection(),
+        Some(Point3::new(2.0, 1.0, 2.0))
+    );
+
+    let parallel = Ray3::new(ray.origin, Vector3::unit_x());
+    assert_eq!((parallel, plane).intersection(), None);
+    let away = Ray3::new(ray.origin, Vector3::unit_y());
+    assert_eq!((away, plane).intersection(), None);
+}
+
+#[test]
+fn test_ray_sphere() {
+    let sphere = Sphere::new(Point3::new(0.0, 0.0, 0.0), 2.0f64);
+    let ray = Ray3::new(Point3::new(-5.0, 0.0, 0.0), Vector3::unit_x());
+    assert_eq!(
+        (ray, sphere).intersection(),
+        Some(Point3::new(-2.0, 0.0, 0.0))
+    );
+
+    let inside = Ray3::new(Point3::new(1.0, 0.0, 0.0), Vector3::unit_x());
+    assert_eq!(
+        (inside, sphere).intersection(),
+        Some(Point3::new(2.0, 0.0, 0.0))
+    );
+
+    let behind = Ray3::new(Point3::new(5.0, 0.0, 0.0), Vector3::unit_x());
+    assert_eq!((behind, sphere).intersection(), None);
+    let miss = Ray3::new(Point3::new(-5.0, 2.5, 0.0), Vector3::unit_x());
+    assert_eq!((miss, sphere).intersection(), None);
+}
+
+#[test]
+fn test_ray_triangle() {
+    let triangle = Triangle3::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0f64),
+    );
+    let down = Vector3::new(0.0, 0.0, -1.0);
+    let ray = Ray3::new(Point3::new(0.25, 0.25, 1.0), down);
+    assert_eq!(
+        (ray, triangle).intersection(),
+        Some(Point3::new(0.25, 0.25, 0.0))
+    );
+    // Both sides of the triangle are hit.
+    let below = Ray3::new(Point3::new(0.25, 0.25, -1.0), -down);
+    assert_eq!(
+        (below, triangle).intersection(),
+        Some(Point3::new(0.25, 0.25, 0.0))
+    );
+    let outside = Ray3::new(Point3::new(0.75, 0.75, 1.0), down);
+    assert_eq!((outside, triangle).intersection(), None);
+}
+
+#[test]
+fn test_ray_aabb() {
+    let aabb = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 2.0, 3.0f64));
+    let ray = Ray3::new(Point3::new(-4.0, 0.0, 0.0), Vector3::new(1.0, 0.5, 0.0));
+    assert_relative_eq!(
+        (ray, aabb).intersection().unwrap(),
+        Point3::new(-1.0, 1.5, 0.0)
+    );
+
+    let inside = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vector3::unit_z());
+    assert_eq!(
+        (inside, aabb).intersection(),
+        Some(Point3::new(0.0, 0.0, 3.0))
+    );
+
+    // Rays parallel to a slab either stay inside it or miss.
+    let grazing = Ray3::new(Point3::new(-4.0, 2.0, 0.0), Vector3::unit_x());
+    assert_eq!(
+        (grazing, aabb).intersection(),
+        Some(Point3::new(-1.0, 2.0, 0.0))
+    );
+    let outside = Ray3::new(Point3::new(-4.0, 2.5, 0.0), Vector3::unit_x());
+    assert_eq!((outside, aabb).intersection(), None);
+    let away = Ray3::new(Point3::new(-4.0, 0.0, 0.0), -Vector3::unit_x());
+    assert_eq!((away, aabb).intersection(), None);
+
+    let aabb2 = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(2.0, 1.0f64));
+    let ray2 = Ray2::new(Point2::new(3.0, 3.0), Vector2::new(-1.0, -1.0));
+    assert_eq!((ray2, aabb2).intersection(), Some(Point2::new(1.0, 1.0)));
+    assert_eq!(ray2.point_at(2.0), Point2::new(1.0, 1.0));
+    let miss2 = Ray2::new(Point2::new(3.0, 3.0), Vector2::new(1.0, -1.0));
+    assert_eq!((miss2, aabb2).intersection(), None);
+}
+
+fn frustum() -> Frustum<f64> {
+    Frustum::from_matrix4(perspective(Deg(90.0), 1.0, 1.0, 100.0)).unwrap()
+}
+
+#[test]
+fn test_frustum_sphere() {
+    let f = frustum();
+    let relation = |x, y, z, r| (f, Sphere::new(Point3::new(x, y, z), r)).intersection();
+    assert_eq!(relation(0.0, 0.0, -10.0, 1.0), Relation::In);
+    assert_eq!(relation(0.0, 0.0, 5.0, 1.0), Relation::Out);
+    assert_eq!(relation(0.0, 0.0, -1.0, 0.5), Relation::Cross);
+    assert_eq!(relation(12.0, 0.0, -10.0, 1.0), Relation::Out);
+    assert_eq!(relation(10.0, 0.0, -10.0, 1.0), Relation::Cross);
+}
+
+#[test]
+fn test_frustum_aabb() {
+    let f = frustum();
+    let relation = |min: Point3<f64>, max: Point3<f64>| (f, Aabb3::new(min, max)).intersection();
+    assert_eq!(
+        relation(Point3::new(-1.0, -1.0, -11.0), Point3::new(1.0, 1.0, -9.0)),
+        Relation::In
+    );
+    assert_eq!(
+        relation(Point3::new(-1.0, -1.0, 1.0), Point3::new(1.0, 1.0, 3.0)),
+        Relation::Out
+    );
+    assert_eq!(
+        relation(
+            Point3::new(-1.0, -1.0, -200.0),
+            Point3::new(1.0, 1.0, -50.0)
+        ),
+        Relation::Cross
+    );
+    assert_eq!(
+        relation(Point3::new(20.0, -1.0, -11.0), Point3::new(22.0, 1.0, -9.0)),
+        Relation::Out
+    );
+}
+
+#[test]
+fn test_contains_point() {
+    let aabb = Aabb3::new(Point3::new(0, 0, 0), Point3::new(2, 3, 4));
+    assert!(aabb.contains_point(Point3::new(2, 0, 1)));
+    assert!(!aabb.contains_point(Point3::new(2, -1, 1)));
+    let aabb2 = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(1.0, 1.0f64));
+    assert!(aabb2.contains_point(Point2::new(0.5, 1.0)));
+    assert!(!aabb2.contains_point(Point2::new(1.5, 0.5)));
+
+    let sphere = Sphere::new(Point3::new(1.0, 1.0, 1.0), 1.0f64);
+    assert!(sphere.contains_point(Point3::new(1.0, 2.0, 1.0)));
+    assert!(!sphere.contains_point(Point3::new(1.8, 1.8, 1.0)));
+
+    let f = frustum();
+    assert!(f.contains_point(Point3::new(0.0, 0.0, -50.0)));
+    assert!(f.contains_point(Point3::new(4.9, 0.0, -5.0)));
+    assert!(!f.contains_point(Point3::new(5.1, 0.0, -5.0)));
+    assert!(!f.contains_point(Point3::new(0.0, 0.0, -0.5)));
+
+    let obb = Obb3::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::new(2.0, 0.5, 0.5),
+        Matrix3::from_angle_z(Deg(90.0f64)),
+    );
+    assert!(obb.contains_point(Point3::new(0.0, 1.9, 0.0)));
+    assert!(!obb.contains_point(Point3::new(1.9, 0.0, 0.0)));
+}
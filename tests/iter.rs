@@ -0,0 +1,88 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_transformed_by_ref() {
+    let points = [Point3::new(1.0f64, 2.0, 3.0), Point3::new(-1.0, 0.0, 4.0)];
+    let matrix = Matrix4::from_translation(vec3(1.0, 1.0, 1.0)) * Matrix4::from_scale(2.0);
+
+    let transformed: Vec<_> = points.iter().transformed(&matrix).collect();
+    let expected: Vec<_> = points.iter().map(|&p| matrix.transform_point(p)).collect();
+    assert_eq!(transformed, expected);
+}
+
+#[test]
+fn test_transformed_by_value() {
+    let points = vec![Point2::new(1.0f32, 0.0), Point2::new(0.0, 1.0)];
+    let matrix = Matrix3::from_translation(vec2(2.0, 3.0));
+
+    let transformed: Vec<_> = points.into_iter().transformed(&matrix).collect();
+    assert_eq!(
+        transformed,
+        vec![Point2::new(3.0, 3.0), Point2::new(2.0, 4.0)]
+    );
+}
+
+#[test]
+fn test_transformed_decomposed() {
+    let transform = Decomposed {
+        scale: 2.0f64,
+        rot: Quaternion::from_angle_z(Deg(90.0)),
+        disp: vec3(0.0, 0.0, 1.0),
+    };
+    let points = [Point3::new(1.0, 0.0, 0.0)];
+
+    let mut iter = points.iter().transformed(&transform);
+    assert_eq!(iter.len(), 1);
+    assert_relative_eq!(
+        iter.next().unwrap(),
+        Point3::new(0.0, 2.0, 1.0),
+        epsilon = 1e-12
+    );
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn test_transformed_vectors_ignores_translation() {
+    let vectors = [vec3(1.0f64, 0.0, 0.0)];
+    let matrix = Matrix4::from_translation(vec3(5.0, 5.0, 5.0));
+
+    let transformed: Vec<_> = vectors.iter().transformed_vectors(&matrix).collect();
+    assert_eq!(transformed, vec![vec3(1.0, 0.0, 0.0)]);
+}
+
+#[test]
+fn test_rotated() {
+    let vectors = [vec3(1.0f64, 0.0, 0.0), vec3(0.0, 1.0, 0.0)];
+    let rotation = Quaternion::from_angle_z(Deg(90.0));
+
+    let rotated: Vec<_> = vectors.iter().rotated(&rotation).rev().collect();
+    assert_relative_eq!(rotated[0], vec3(-1.0, 0.0, 0.0), epsilon = 1e-12);
+    assert_relative_eq!(rotated[1], vec3(0.0, 1.0, 0.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_rotated_basis2() {
+    let vectors = [vec2(1.0f64, 0.0)];
+    let rotation: Basis2<_> = Rotation2::from_angle(Deg(90.0));
+
+    let rotated: Vec<_> = vectors.iter().rotated(&rotation).collect();
+    assert_relative_eq!(rotated[0], vec2(0.0, 1.0), epsilon = 1e-12);
+}
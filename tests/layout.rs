@@ -0,0 +1,270 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::layout::{
+    fit_rect, fit_scale_offset, fit_transform, nine_slice, snap_to_pixels, Anchor, AnchoredQuad,
+    DirtyRects, Fit, RoundedRect,
+};
+
+#[test]
+fn test_anchors() {
+    let position = Point2::new(1.0, 2.0f64);
+    let size = Vector2::new(4.0, 6.0);
+    let bottom_left = |anchor| AnchoredQuad::new(position, size, anchor).corners()[0];
+    assert_eq!(bottom_left(Anchor::BottomLeft), Point2::new(1.0, 2.0));
+    assert_eq!(bottom_left(Anchor::TopRight), Point2::new(-3.0, -4.0));
+    assert_eq!(bottom_left(Anchor::Center), Point2::new(-1.0, -1.0));
+    assert_eq!(bottom_left(Anchor::Right), Point2::new(-3.0, -1.0));
+    assert_eq!(bottom_left(Anchor::Bottom), Point2::new(-1.0, 2.0));
+}
+
+#[test]
+fn test_matrix_matches_corners() {
+    let mut quad = AnchoredQuad::new(
+        Point2::new(3.0, -1.0f64),
+        Vector2::new(5.0, 2.0),
+        Anchor::Left,
+    );
+    quad.pivot = Vector2::new(1.5, -0.5);
+    quad.rotation = Rad(0.7);
+    let m = quad.to_matrix3();
+    let corners = quad.corners();
+    let local = [
+        Point2::new(0.0, 0.0),
+        Point2::new(5.0, 0.0),
+        Point2::new(5.0, 2.0),
+        Point2::new(0.0, 2.0),
+    ];
+    for (&corner, &p) in corners.iter().zip(&local) {
+        assert_relative_eq!(m.transform_point(p), corner, epsilon = 1e-12);
+    }
+
+    // The rotation keeps the shape and turns about the pivot.
+    let pivot = Point2::new(3.0, -1.0) + Vector2::new(1.5 * 5.0, -1.0 - 0.5 * 2.0);
+    assert_relative_eq!(
+        m.transform_point(Point2::new(7.5, -1.0)),
+        pivot,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(corners[0].distance(corners[1]), 5.0, epsilon = 1e-12);
+    assert_relative_eq!(corners[1].distance(corners[2]), 2.0, epsilon = 1e-12);
+    let edge = corners[1] - corners[0];
+    assert_relative_eq!(edge.y.atan2(edge.x), 0.7, epsilon = 1e-12);
+}
+
+fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> Aabb2<f64> {
+    Aabb2::new(Point2::new(x0, y0), Point2::new(x1, y1))
+}
+
+#[test]
+fn test_fit() {
+    let content = rect(10.0, 10.0, 30.0, 20.0);
+    let target = rect(0.0, 0.0, 100.0, 100.0);
+    assert_eq!(
+        fit_rect(content, target, Fit::Contain, Anchor::Center),
+        rect(0.0, 25.0, 100.0, 75.0)
+    );
+    assert_eq!(
+        fit_rect(content, target, Fit::Contain, Anchor::TopLeft),
+        rect(0.0, 50.0, 100.0, 100.0)
+    );
+    assert_eq!(
+        fit_rect(content, target, Fit::Cover, Anchor::Center),
+        rect(-50.0, 0.0, 150.0, 100.0)
+    );
+    assert_eq!(
+        fit_rect(content, target, Fit::Cover, Anchor::Right),
+        rect(-100.0, 0.0, 100.0, 100.0)
+    );
+    assert_eq!(
+        fit_rect(content, target, Fit::Stretch, Anchor::Center),
+        target
+    );
+
+    let (scale, offset) = fit_scale_offset(content, target, Fit::Contain, Anchor::Center);
+    assert_eq!(scale, Vector2::new(5.0, 5.0));
+    let m = fit_transform(content, target, Fit::Contain, Anchor::Center);
+    let p = Point2::new(15.0, 12.0);
+    let mapped = Point2::from_vec(p.to_vec().mul_element_wise(scale) + offset);
+    assert_eq!(m.transform_point(p), mapped);
+    assert_eq!(mapped, Point2::new(25.0, 35.0));
+}
+
+#[test]
+fn test_nine_slice() {
+    let slices = nine_slice(
+        rect(0.0, 0.0, 10.0, 6.0),
+        Vector2::new(2.0, 1.0),
+        Vector2::new(3.0, 2.0),
+    );
+    assert_eq!(slices[0], rect(0.0, 0.0, 2.0, 1.0));
+    assert_eq!(slices[1], rect(2.0, 0.0, 7.0, 1.0));
+    assert_eq!(slices[2], rect(7.0, 0.0, 10.0, 1.0));
+    assert_eq!(slices[4], rect(2.0, 1.0, 7.0, 4.0));
+    assert_eq!(slices[8], rect(7.0, 4.0, 10.0, 6.0));
+    let area: f64 = slices.iter().map(|s| s.volume()).sum();
+    assert_eq!(area, 60.0);
+
+    // Borders wider than the rectangle are scaled down to fit.
+    let slices = nine_slice(
+        rect(0.0, 0.0, 4.0, 10.0),
+        Vector2::new(3.0, 1.0),
+        Vector2::new(5.0, 1.0),
+    );
+    assert_eq!(slices[0], rect(0.0, 0.0, 1.5, 1.0));
+    assert_eq!(slices[1], rect(1.5, 0.0, 1.5, 1.0));
+    assert_eq!(slices[2], rect(1.5, 0.0, 4.0, 1.0));
+}
+
+#[test]
+fn test_snap_to_pixels() {
+    let sprite = Matrix3::from_translation(Vector2::new(3.7, -2.2))
+        * Matrix3::from_angle_z(Rad(0.3))
+        * Matrix3::from_scale(2.0f64);
+    let origin = Point2::new(1.0, 1.0);
+    let centered = snap_to_pixels(sprite, origin, Vector2::new(0.5, 0.5));
+    let p = centered.transform_point(origin);
+    assert_relative_eq!(p.x - p.x.floor(), 0.5, epsilon = 1e-12);
+    assert_relative_eq!(p.y - p.y.floor(), 0.5, epsilon = 1e-12);
+    // Only the translation changes, by less than a pixel.
+    let shift = p - sprite.transform_point(origin);
+    assert!(shift.x.abs() <= 0.5 && shift.y.abs() <= 0.5);
+    let other = Point2::new(-4.0, 2.5);
+    assert_relative_eq!(
+        centered.transform_point(other),
+        sprite.transform_point(other) + shift,
+        epsilon = 1e-12
+    );
+
+    let cornered = snap_to_pixels(sprite, origin, Vector2::new(0.0, 0.0));
+    let p = cornered.transform_point(origin);
+    assert_relative_eq!(p.x, p.x.round(), epsilon = 1e-12);
+    assert_relative_eq!(p.y, p.y.round(), epsilon = 1e-12);
+}
+
+#[test]
+fn test_dirty_rects_merging() {
+    let rect = |x0, y0, x1, y1| Aabb2::new(Point2::new(x0, y0), Point2::new(x1, y1));
+    let mut dirty = DirtyRects::<u32>::new(8);
+    assert!(dirty.is_empty());
+    assert_eq!(dirty.bounds(), None);
+
+    // Side by side rectangles merge without wasting area.
+    dirty.add(rect(0, 0, 10, 10));
+    dirty.add(rect(10, 0, 20, 10));
+    assert_eq!(dirty.rects(), &[rect(0, 0, 20, 10)]);
+    // Far apart ones do not, and empty ones are ignored.
+    dirty.add(rect(0, 50, 10, 60));
+    dirty.add(rect(30, 30, 30, 40));
+    assert_eq!(dirty.rects().len(), 2);
+    // A rectangle bridging two regions merges them all.
+    dirty.add(rect(0, 5, 10, 55));
+    assert_eq!(dirty.rects(), &[rect(0, 0, 20, 60)]);
+    assert_eq!(dirty.bounds(), Some(rect(0, 0, 20, 60)));
+
+    dirty.clear();
+    assert!(dirty.is_empty());
+}
+
+#[test]
+fn test_dirty_rects_cap() {
+    let mut dirty = DirtyRects::new(3);
+    let mut added = Vec::new();
+    for i in 0..10 {
+        let x = (i * 37 % 100) as f64;
+        let y = (i * 61 % 100) as f64;
+        let r = Aabb2::new(Point2::new(x, y), Point2::new(x + 3.0, y + 2.0));
+        dirty.add(r);
+        added.push(r);
+        assert!(dirty.rects().len() <= 3);
+    }
+    // Every rectangle added is still covered.
+    for r in &added {
+        assert!(dirty
+            .rects()
+            .iter()
+            .any(|d| d.contains_point(r.min) && d.contains_point(r.max)));
+    }
+    // The regions do not overlap.
+    let rects = dirty.rects();
+    for i in 0..rects.len() {
+        for j in i + 1..rects.len() {
+            let overlap = rects[i].intersection(&rects[j]).map_or(0.0, |r| r.volume());
+            assert_eq!(overlap, 0.0);
+        }
+    }
+}
+
+#[test]
+fn test_rounded_rect_signed_distance() {
+    let rect = Aabb2::new(Point2::new(-2.0, -1.0), Point2::new(4.0, 3.0));
+    let rounded = RoundedRect::new(rect, 1.0);
+    assert_eq!(rounded.signed_distance(Point2::new(1.0, 1.0)), -2.0);
+    assert_eq!(rounded.signed_distance(Point2::new(6.0, 1.0)), 2.0);
+    assert_eq!(rounded.signed_distance(Point2::new(1.0, -1.0)), 0.0);
+    // Diagonally out of the corner, the distance is to the arc.
+    let corner = Point2::new(3.0, 2.0) + Vector2::new(1.0, 1.0).normalize_to(3.0);
+    assert_relative_eq!(rounded.signed_distance(corner), 2.0, epsilon = 1e-12);
+    assert!(!rounded.contains_point(Point2::new(3.9, 2.9)));
+    assert!(rounded.contains_point(Point2::new(3.6, 2.6)));
+
+    // Without rounding, this is the distance to a box.
+    let square = RoundedRect::new(rect, 0.0);
+    assert_relative_eq!(square.signed_distance(Point2::new(7.0, 7.0)), 5.0);
+    assert!(square.contains_point(Point2::new(4.0, 3.0)));
+
+    // The radius is clamped to a pill.
+    let pill = RoundedRect::new(rect, 10.0);
+    assert_eq!(pill.effective_radius(), 2.0);
+    assert_relative_eq!(pill.signed_distance(Point2::new(1.0, 1.0)), -2.0);
+    assert_relative_eq!(pill.signed_distance(Point2::new(-3.0, 1.0)), 1.0);
+}
+
+#[test]
+fn test_rounded_rect_boundary_points() {
+    let rect = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(10.0, 4.0));
+    let rounded = RoundedRect::new(rect, 1.5);
+    let points = rounded.boundary_points(6);
+    assert_eq!(points.len(), 28);
+    assert_relative_eq!(points[0], Point2::new(8.5, 0.0), epsilon = 1e-12);
+    assert_relative_eq!(points[6], Point2::new(10.0, 1.5), epsilon = 1e-12);
+    assert_relative_eq!(points[20], Point2::new(0.0, 2.5), epsilon = 1e-12);
+    assert_relative_eq!(points[27], Point2::new(1.5, 0.0), epsilon = 1e-12);
+    for &p in &points {
+        assert_relative_eq!(rounded.signed_distance(p), 0.0, epsilon = 1e-12);
+    }
+    // The outline is counterclockwise.
+    let area = (0..points.len()).fold(0.0, |sum, i| {
+        let (a, b) = (points[i], points[(i + 1) % points.len()]);
+        sum + (a.x * b.y - b.x * a.y) / 2.0
+    });
+    assert!(area > 0.0 && area < 40.0);
+
+    let corners = RoundedRect::new(rect, 0.0).boundary_points(6);
+    assert_eq!(
+        corners,
+        vec![
+            Point2::new(10.0, 0.0),
+            Point2::new(10.0, 4.0),
+            Point2::new(0.0, 4.0),
+            Point2::new(0.0, 0.0),
+        ]
+    );
+}
@@ -0,0 +1,106 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_closest_point() {
+    let line = Line2::from_points(Point2::new(1.0, 1.0), Point2::new(3.0, 1.0));
+    assert_eq!(line.point_at(0.5), Point2::new(2.0, 1.0));
+    assert_eq!(line.project_point(Point2::new(5.0, 4.0)), 2.0);
+    assert_eq!(
+        line.closest_point(Point2::new(5.0, 4.0)),
+        Point2::new(5.0, 1.0)
+    );
+    assert_eq!(line.distance(Point2::new(-7.0, -2.0)), 3.0);
+
+    let line = Line3::new(Point3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 2.0, 0.0));
+    assert_eq!(
+        line.closest_point(Point3::new(3.0, -4.0, 1.0)),
+        Point3::new(0.0, -4.0, 1.0)
+    );
+    assert_eq!(line.distance(Point3::new(3.0, -4.0, 5.0)), 5.0);
+}
+
+#[test]
+fn test_intersection() {
+    let a = Line2::new(Point2::new(1.0, 0.0), Vector2::new(0.0, 3.0));
+    let b = Line2::from_points(Point2::new(-1.0, -1.0), Point2::new(3.0, 1.0));
+    match a.intersection(&b) {
+        LineIntersection2::Point(p) => assert_relative_eq!(p, Point2::new(1.0, 0.0)),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert_eq!(b.intersection(&a), a.intersection(&b));
+
+    let parallel = Line2::new(Point2::new(0.0, 1.0), Vector2::new(2.0, 1.0));
+    assert_eq!(b.intersection(&parallel), LineIntersection2::Parallel);
+    let same = Line2::new(Point2::new(7.0, 3.0), Vector2::new(-4.0, -2.0));
+    assert_eq!(b.intersection(&same), LineIntersection2::Collinear);
+    assert_eq!(same.intersection(&same), LineIntersection2::Collinear);
+}
+
+#[test]
+fn test_intersection_tolerance() {
+    // Nearly parallel lines are parallel up to rounding, unless the
+    // `strict-fp` feature asks for exact comparisons.
+    let a = Line2::new(Point2::new(0.0, 0.0), Vector2::new(1.0f64, 0.0));
+    let b = Line2::new(Point2::new(0.0, 1.0), Vector2::new(1.0, 1e-20));
+    match a.intersection(&b) {
+        LineIntersection2::Parallel => assert!(cfg!(not(feature = "strict-fp"))),
+        LineIntersection2::Point(p) => {
+            assert!(cfg!(feature = "strict-fp"));
+            assert_eq!(p, Point2::new(-1e20, 0.0));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_closest_points() {
+    let a = Line3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 0.0));
+    let b = Line3::new(Point3::new(1.0, -3.0, 4.0), Vector3::new(0.0, 1.0, 0.0));
+    let (p, q) = a.closest_points(&b);
+    assert_relative_eq!(p, Point3::new(1.0, 0.0, 0.0));
+    assert_relative_eq!(q, Point3::new(1.0, 0.0, 4.0));
+    assert_relative_eq!(a.distance_to_line(&b), 4.0);
+    let (s, t) = a.closest_parameters(&b);
+    assert_relative_eq!(s, 0.5);
+    assert_relative_eq!(t, 3.0);
+
+    // Skew lines in general position: the connecting segment is
+    // perpendicular to both.
+    let c = Line3::new(Point3::new(1.0, 2.0, 3.0), Vector3::new(1.0, -1.0, 0.5));
+    let d = Line3::new(Point3::new(-2.0, 0.0, 1.0), Vector3::new(0.3, 0.2, -1.0));
+    let (p, q) = c.closest_points(&d);
+    assert_relative_eq!((q - p).dot(c.direction), 0.0, epsilon = 1e-12);
+    assert_relative_eq!((q - p).dot(d.direction), 0.0, epsilon = 1e-12);
+
+    // Intersecting lines meet at a single point.
+    let e = Line3::from_points(Point3::new(1.0, 1.0, 1.0), Point3::new(2.0, 3.0, 4.0));
+    let f = Line3::from_points(Point3::new(2.0, 3.0, 4.0), Point3::new(0.0, 3.0, 4.0));
+    let (p, q) = e.closest_points(&f);
+    assert_relative_eq!(p, Point3::new(2.0, 3.0, 4.0));
+    assert_relative_eq!(q, Point3::new(2.0, 3.0, 4.0));
+
+    // Parallel lines fall back to the origin of the first line.
+    let g = Line3::new(Point3::new(0.0, 1.0, 0.0), Vector3::new(-4.0, 0.0, 0.0));
+    let (p, q) = a.closest_points(&g);
+    assert_eq!(p, a.origin);
+    assert_relative_eq!(q, Point3::new(0.0, 1.0, 0.0));
+    assert_relative_eq!(a.distance_to_line(&g), 1.0);
+}
@@ -58,6 +58,72 @@ pub mod matrix2 {
     };
     const F: f64 = 0.5;
 
+    #[test]
+    fn test_consts() {
+        const IDENTITY: Matrix2<f64> = Matrix2::<f64>::IDENTITY;
+        assert_eq!(IDENTITY, Matrix2::identity());
+        assert_eq!(Matrix2::<f32>::ZERO, Matrix2::zero());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Matrix2::<f64>::default(), Matrix2::identity());
+    }
+
+    #[test]
+    fn test_raw_layout() {
+        // Elements are given column by column.
+        let m = Matrix2::<f32>::new(0.0, 1.0, 2.0, 3.0);
+        assert_eq!(
+            std::mem::size_of::<Matrix2<f32>>(),
+            4 * std::mem::size_of::<f32>()
+        );
+
+        let flat = m.as_col_major_array();
+        let raw = unsafe { std::slice::from_raw_parts(m.as_ptr(), 4) };
+        for c in 0..2 {
+            for r in 0..2 {
+                assert_eq!(flat[c * 2 + r], m[c][r]);
+                assert_eq!(raw[c * 2 + r], m[c][r]);
+                assert_eq!(m.to_row_major_array()[r * 2 + c], m[c][r]);
+            }
+        }
+        assert_eq!(flat[1], m.x.y);
+        assert_eq!(m.to_row_major_array(), *m.transpose().as_col_major_array());
+
+        let mut n = m;
+        unsafe { *n.as_mut_ptr().offset(2) = -1.0 };
+        assert_eq!(n.y.x, -1.0);
+    }
+
+    #[test]
+    fn test_fused_transpose_products() {
+        let a: Matrix2<f64> = Matrix2::from_angle(Rad(0.7));
+        let b = a * 2.0 + Matrix2::identity();
+        let v = Vector2::new(1.0, -2.0);
+        assert_ulps_eq!(a.transpose_mul_vector(v), a.transpose() * v);
+        assert_ulps_eq!(a.transpose_mul(&b), a.transpose() * b);
+        assert_ulps_eq!(a.mul_transposed(&b), a * b.transpose());
+    }
+
+    #[test]
+    fn test_polar_decomposition() {
+        let rotation = Matrix2::from_angle(Deg(-130.0));
+        let stretch = Matrix2::new(2.0, 0.5, 0.5, 1.0f64);
+        let (r, s) = (rotation * stretch).polar_decomposition();
+        assert_relative_eq!(r, rotation, epsilon = 1e-12);
+        assert_relative_eq!(s, stretch, epsilon = 1e-12);
+
+        // With a reflection, the rotation is still proper and the stretch
+        // takes the negative determinant.
+        let m = rotation * Matrix2::new(1.0, 0.0, 0.0, -3.0);
+        let (r, s) = m.polar_decomposition();
+        assert_relative_eq!(r.determinant(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(r.transpose_mul(&r), Matrix2::identity(), epsilon = 1e-12);
+        assert!(s.is_symmetric());
+        assert_relative_eq!(r * s, m, epsilon = 1e-12);
+    }
+
     #[test]
     fn test_neg() {
         assert_eq!(-A, Matrix2::new(-1.0f64, -3.0f64, -2.0f64, -4.0f64));
@@ -174,6 +240,18 @@ pub mod matrix2 {
         assert!(Matrix2::from_value(6.0f64).is_diagonal());
     }
 
+    #[test]
+    fn test_predicates_tolerance() {
+        // The predicates allow a few ulps of rounding error, unless the
+        // `strict-fp` feature asks for exact comparisons.
+        let nearly = Matrix2::new(1.0, 0.0, f64::EPSILON, 1.0 + f64::EPSILON);
+        let implicit = cfg!(not(feature = "strict-fp"));
+        assert_eq!(nearly.is_identity(), implicit);
+        assert_eq!(nearly.is_symmetric(), implicit);
+        assert_eq!(nearly.is_diagonal(), implicit);
+        assert!(Matrix2::<f64>::identity().is_identity());
+    }
+
     #[test]
     fn test_from_angle() {
         // Rotate the vector (1, 0) by π/2 radians to the vector (0, 1)
@@ -285,6 +363,86 @@ pub mod matrix3 {
     };
     const F: f64 = 0.5;
 
+    #[test]
+    fn test_consts() {
+        const IDENTITY: Matrix3<f64> = Matrix3::<f64>::IDENTITY;
+        assert_eq!(IDENTITY, Matrix3::identity());
+        assert_eq!(Matrix3::<f32>::ZERO, Matrix3::zero());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Matrix3::<f64>::default(), Matrix3::identity());
+    }
+
+    #[test]
+    fn test_raw_layout() {
+        // Elements are given column by column.
+        let m = Matrix3::<f32>::new(0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0);
+        assert_eq!(
+            std::mem::size_of::<Matrix3<f32>>(),
+            9 * std::mem::size_of::<f32>()
+        );
+
+        let flat = m.as_col_major_array();
+        let raw = unsafe { std::slice::from_raw_parts(m.as_ptr(), 9) };
+        for c in 0..3 {
+            for r in 0..3 {
+                assert_eq!(flat[c * 3 + r], m[c][r]);
+                assert_eq!(raw[c * 3 + r], m[c][r]);
+                assert_eq!(m.to_row_major_array()[r * 3 + c], m[c][r]);
+            }
+        }
+        assert_eq!(flat[1], m.x.y);
+        assert_eq!(m.to_row_major_array(), *m.transpose().as_col_major_array());
+
+        let mut n = m;
+        unsafe { *n.as_mut_ptr().offset(3) = -1.0 };
+        assert_eq!(n.y.x, -1.0);
+    }
+
+    #[test]
+    fn test_fused_transpose_products() {
+        let a: Matrix3<f64> = Matrix3::from_angle_y(Rad(0.7));
+        let b = a * 2.0 + Matrix3::identity();
+        let v = Vector3::new(1.0, -2.0, 0.5);
+        assert_ulps_eq!(a.transpose_mul_vector(v), a.transpose() * v);
+        assert_ulps_eq!(a.transpose_mul(&b), a.transpose() * b);
+        assert_ulps_eq!(a.mul_transposed(&b), a * b.transpose());
+    }
+
+    #[test]
+    fn test_polar_decomposition() {
+        let rotation =
+            Matrix3::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalize(), Deg(160.0));
+        let frame = Matrix3::from_angle_x(Deg(25.0)) * Matrix3::from_angle_y(Deg(-70.0));
+        let stretch =
+            frame.transpose() * Matrix3::from_diagonal(Vector3::new(3.0, 0.2, 1.5f64)) * frame;
+        let (r, s) = (rotation * stretch).polar_decomposition();
+        assert_relative_eq!(r, rotation, epsilon = 1e-10);
+        assert_relative_eq!(s, stretch, epsilon = 1e-10);
+
+        // A pure rotation with rounding errors snaps back to a rotation.
+        let noisy = rotation + Matrix3::from_value(1e-6);
+        let (r, _) = noisy.polar_decomposition();
+        assert_relative_eq!(r, rotation, epsilon = 1e-5);
+        assert_relative_eq!(r.transpose_mul(&r), Matrix3::identity(), epsilon = 1e-12);
+
+        // Reflected and singular matrices still give a proper rotation.
+        for &diagonal in &[Vector3::new(1.0, 2.0, -1.0), Vector3::new(1.0, 0.0, 2.0)] {
+            let m = rotation * Matrix3::from_diagonal(diagonal);
+            let (r, s) = m.polar_decomposition();
+            assert_relative_eq!(r.determinant(), 1.0, epsilon = 1e-12);
+            assert_relative_eq!(r.transpose_mul(&r), Matrix3::identity(), epsilon = 1e-12);
+            assert!(s.is_symmetric());
+            assert_relative_eq!(r * s, m, epsilon = 1e-9);
+        }
+
+        let (r, s) = Matrix3::<f64>::zero().polar_decomposition();
+        assert_eq!(r, Matrix3::identity());
+        assert_eq!(s, Matrix3::zero());
+    }
+
     #[test]
     fn test_neg() {
         assert_eq!(
@@ -844,6 +1002,112 @@ pub mod matrix4 {
     };
     const F: f64 = 0.5;
 
+    #[test]
+    fn test_consts() {
+        const IDENTITY: Matrix4<f64> = Matrix4::<f64>::IDENTITY;
+        assert_eq!(IDENTITY, Matrix4::identity());
+        assert_eq!(Matrix4::<f32>::ZERO, Matrix4::zero());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Matrix4::<f64>::default(), Matrix4::identity());
+    }
+
+    #[test]
+    fn test_raw_layout() {
+        // Elements are given column by column.
+        let m = Matrix4::<f32>::new(
+            0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0,
+        );
+        assert_eq!(
+            std::mem::size_of::<Matrix4<f32>>(),
+            16 * std::mem::size_of::<f32>()
+        );
+
+        let flat = m.as_col_major_array();
+        let raw = unsafe { std::slice::from_raw_parts(m.as_ptr(), 16) };
+        for c in 0..4 {
+            for r in 0..4 {
+                assert_eq!(flat[c * 4 + r], m[c][r]);
+                assert_eq!(raw[c * 4 + r], m[c][r]);
+                assert_eq!(m.to_row_major_array()[r * 4 + c], m[c][r]);
+            }
+        }
+        assert_eq!(flat[1], m.x.y);
+        assert_eq!(m.to_row_major_array(), *m.transpose().as_col_major_array());
+
+        let mut n = m;
+        unsafe { *n.as_mut_ptr().offset(4) = -1.0 };
+        assert_eq!(n.y.x, -1.0);
+    }
+
+    #[test]
+    fn test_fused_transpose_products() {
+        let a: Matrix4<f64> = Matrix4::from_angle_z(Rad(0.7))
+            * Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+        let b = a * 2.0 + Matrix4::identity();
+        let v = Vector4::new(1.0, -2.0, 0.5, 3.0);
+        assert_ulps_eq!(a.transpose_mul_vector(v), a.transpose() * v);
+        assert_ulps_eq!(a.transpose_mul(&b), a.transpose() * b);
+        assert_ulps_eq!(a.mul_transposed(&b), a * b.transpose());
+    }
+
+    #[test]
+    fn test_mat3_embedding() {
+        let m = Matrix3::from_angle_x(Rad(0.3f64)) * 2.0;
+        let t = Vector3::new(1.0, 2.0, 3.0);
+        assert_eq!(m.to_matrix4(), Matrix4::from(m));
+        assert_eq!(m.to_matrix4().truncate(), m);
+
+        let affine = Matrix4::from_mat3_translation(m, t);
+        assert_eq!(affine, Matrix4::from_translation(t) * m.to_matrix4());
+        assert_eq!(affine.truncate(), m);
+        assert_eq!(affine.w.truncate(), t);
+        assert_eq!(A.truncate(), Matrix3::new(1.0, 5.0, 9.0, 2.0, 6.0, 10.0, 3.0, 7.0, 11.0));
+    }
+
+    #[test]
+    fn test_compose() {
+        let t = Vector3::new(1.0, -2.0, 3.0);
+        let r = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 2.0) / 3.0, Deg(70.0f64));
+        let s = Vector3::new(2.0, 0.5, 3.0);
+        let m = Matrix4::compose(t, r, s);
+        assert_ulps_eq!(
+            m,
+            Matrix4::from_translation(t)
+                * Matrix4::from(r)
+                * Matrix4::from_nonuniform_scale(s.x, s.y, s.z)
+        );
+
+        let inverse = Matrix4::compose_inverse(t, r, s);
+        assert_relative_eq!(inverse, m.invert().unwrap(), epsilon = 1e-12);
+        assert_relative_eq!(inverse * m, Matrix4::identity(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_shadow() {
+        let normal = Vector3::new(1.0, 3.0, 1.0);
+        let plane = Plane::from_point_normal(Point3::new(0.0, 1.0, 2.0), normal);
+        let p = Point3::new(0.5, 4.0, -1.0);
+
+        let light = Point3::new(-1.0, 9.0, 2.0);
+        let shadow = Matrix4::shadow(&plane, &light.to_homogeneous());
+        let q = shadow.transform_point(p);
+        assert_relative_eq!(plane.distance(q), 0.0, epsilon = 1e-12);
+        assert_relative_eq!((q - light).normalize(), (p - light).normalize(), epsilon = 1e-12);
+
+        let dir = Vector3::new(0.2, -1.0, 0.4);
+        let shadow = Matrix4::shadow(&plane, &(-dir).extend(0.0));
+        let q = shadow.transform_point(p);
+        assert_relative_eq!(plane.distance(q), 0.0, epsilon = 1e-12);
+        assert_relative_eq!((q - p).normalize(), dir.normalize(), epsilon = 1e-12);
+
+        // Points on the plane are left in place.
+        let on_plane = plane.project_point(p);
+        assert_relative_eq!(shadow.transform_point(on_plane), on_plane, epsilon = 1e-12);
+    }
+
     #[test]
     fn test_neg() {
         assert_eq!(
@@ -1011,7 +1275,7 @@ pub mod matrix4 {
             -1.550973f64,
             1.0f64,
         );
-        assert!((mat_c.invert().unwrap() * mat_c).is_identity());
+        assert_ulps_eq!(mat_c.invert().unwrap() * mat_c, Matrix4::identity(), epsilon = 1e-12);
 
         let mat_d = Matrix4::new(
             0.065455f64,
@@ -1031,7 +1295,7 @@ pub mod matrix4 {
             -1.443098f64,
             1.0f64,
         );
-        assert!((mat_d.invert().unwrap() * mat_d).is_identity());
+        assert_ulps_eq!(mat_d.invert().unwrap() * mat_d, Matrix4::identity(), epsilon = 1e-12);
 
         let mat_e = Matrix4::new(
             0.409936f64,
@@ -1051,7 +1315,7 @@ pub mod matrix4 {
             -1.499401f64,
             1.0f64,
         );
-        assert!((mat_e.invert().unwrap() * mat_e).is_identity());
+        assert_ulps_eq!(mat_e.invert().unwrap() * mat_e, Matrix4::identity(), epsilon = 1e-12);
 
         let mat_f = Matrix4::new(
             -0.160691f64,
@@ -1071,7 +1335,7 @@ pub mod matrix4 {
             -1.565561f64,
             1.0f64,
         );
-        assert!((mat_f.invert().unwrap() * mat_f).is_identity());
+        assert_ulps_eq!(mat_f.invert().unwrap() * mat_f, Matrix4::identity(), epsilon = 1e-12);
     }
 
     #[test]
@@ -0,0 +1,553 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::mesh::{
+    centroid, contains_point, convex_hull, laplacian_smooth, mass_properties, slice, taubin_smooth,
+    vertex_neighbors, vertex_normals, vertex_tangents, volume, voxelize, weld, winding_number,
+    Quadric,
+};
+use cgmath::*;
+
+#[test]
+fn test_weld_across_cells() {
+    // Pairs that straddle cell boundaries, including around the origin.
+    let points = [
+        Point3::new(-0.0001, 0.0, 0.0),
+        Point3::new(0.0001, 0.0, 0.0),
+        Point3::new(0.2999, -0.5, 7.0),
+        Point3::new(0.3001, -0.5001, 7.0001),
+        Point3::new(5.0, 5.0, 5.0),
+    ];
+    let (welded, remap) = weld(&points, 0.001);
+    assert_eq!(welded, vec![points[0], points[2], points[4]]);
+    assert_eq!(remap, vec![0, 0, 1, 1, 2]);
+}
+
+#[test]
+fn test_weld_chain() {
+    // Every point is close to the next, but not to the first.
+    let points: Vec<_> = (0..5)
+        .map(|i| Point3::new(i as f64 * 0.6, 0.0, 0.0))
+        .collect();
+    let (welded, remap) = weld(&points, 1.0);
+    assert_eq!(welded, vec![points[0], points[2], points[4]]);
+    assert_eq!(remap, vec![0, 0, 1, 1, 2]);
+}
+
+#[test]
+fn test_weld_exact() {
+    let points = [
+        Point3::new(1.0, 2.0, 3.0),
+        Point3::new(1.0, 2.0, 3.0 + 1e-12),
+        Point3::new(1.0, 2.0, 3.0),
+    ];
+    let (welded, remap) = weld(&points, 0.0);
+    assert_eq!(welded.len(), 2);
+    assert_eq!(remap, vec![0, 1, 0]);
+
+    let (welded, remap) = weld::<f64>(&[], 0.1);
+    assert!(welded.is_empty() && remap.is_empty());
+}
+
+#[test]
+fn test_weld_matches_brute_force() {
+    let epsilon = 0.05;
+    let points: Vec<_> = (0..2000)
+        .map(|i| {
+            // Snap to a coarse lattice so that many points nearly coincide.
+            let p = noise::random_vector3::<f64>(Point3::new(i, 0, 0), 7) * 8.0;
+            let jitter = noise::random_vector3(Point3::new(i, 1, 0), 7) * 0.01;
+            Point3::new(p.x.round(), p.y.round(), p.z.round()) / 8.0 + jitter
+        })
+        .collect();
+    let (welded, remap) = weld(&points, epsilon);
+
+    let mut expected_welded = Vec::new();
+    let mut expected_remap = Vec::new();
+    for &p in &points {
+        match expected_welded
+            .iter()
+            .position(|&q: &Point3<f64>| q.distance(p) <= epsilon)
+        {
+            Some(i) => expected_remap.push(i),
+            None => {
+                expected_remap.push(expected_welded.len());
+                expected_welded.push(p);
+            }
+        }
+    }
+    assert_eq!(welded, expected_welded);
+    assert_eq!(remap, expected_remap);
+    assert!(welded.len() < points.len());
+}
+
+/// The twelve triangles of the unit cube, counterclockwise from outside.
+fn cube() -> Vec<Triangle3<f64>> {
+    let corner = |i: usize| Point3::new((i & 1) as f64, (i >> 1 & 1) as f64, (i >> 2) as f64);
+    let faces = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+    let mut triangles = Vec::new();
+    for f in &faces {
+        triangles.push(Triangle3::new(corner(f[0]), corner(f[1]), corner(f[2])));
+        triangles.push(Triangle3::new(corner(f[0]), corner(f[2]), corner(f[3])));
+    }
+    triangles
+}
+
+#[test]
+fn test_winding_number() {
+    let cube = cube();
+    for &p in &[
+        Point3::new(0.5, 0.5, 0.5),
+        Point3::new(0.01, 0.99, 0.5),
+        Point3::new(0.9, 0.1, 0.05),
+    ] {
+        assert_relative_eq!(winding_number(&cube, p), 1.0, epsilon = 1e-9);
+        assert!(contains_point(&cube, p));
+    }
+    for &p in &[
+        Point3::new(1.5, 0.5, 0.5),
+        Point3::new(-3.0, 4.0, 10.0),
+        Point3::new(0.5, 0.5, 1.01),
+    ] {
+        assert_relative_eq!(winding_number(&cube, p), 0.0, epsilon = 1e-9);
+        assert!(!contains_point(&cube, p));
+    }
+
+    // Turned inside out.
+    let flipped: Vec<_> = cube.iter().map(|t| Triangle3::new(t.a, t.c, t.b)).collect();
+    assert_relative_eq!(
+        winding_number(&flipped, Point3::new(0.5, 0.5, 0.5)),
+        -1.0,
+        epsilon = 1e-9
+    );
+    assert!(contains_point(&flipped, Point3::new(0.5, 0.5, 0.5)));
+
+    // With a missing triangle, the center is still classified correctly.
+    let open = &cube[1..];
+    assert!(contains_point(open, Point3::new(0.5, 0.5, 0.5)));
+    assert!(!contains_point(open, Point3::new(0.5, 0.5, -2.0)));
+}
+
+#[test]
+fn test_triangle_intersects_aabb() {
+    let unit = Aabb3::new(Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 1.0, 1.0));
+    let triangle = |a: [f64; 3], b: [f64; 3], c: [f64; 3]| {
+        Triangle3::new(Point3::from(a), Point3::from(b), Point3::from(c))
+    };
+    // Inside, touching a face, and crossing without any vertex inside.
+    assert!(triangle([0.2, 0.2, 0.2], [0.8, 0.2, 0.2], [0.2, 0.8, 0.2]).intersects_aabb(&unit));
+    assert!(triangle([1.0, 0.0, 0.0], [2.0, 0.0, 0.0], [1.0, 1.0, 0.0]).intersects_aabb(&unit));
+    assert!(triangle([-5.0, -5.0, 0.5], [5.0, -5.0, 0.5], [0.0, 5.0, 0.5]).intersects_aabb(&unit));
+    // Separated by a face of the box, by the plane of the triangle, and by
+    // the cross product of edges.
+    assert!(!triangle([1.1, 0.0, 0.0], [2.0, 0.0, 0.0], [1.5, 1.0, 0.0]).intersects_aabb(&unit));
+    assert!(!triangle([3.1, 0.0, 0.0], [0.0, 3.1, 0.0], [0.0, 0.0, 3.1]).intersects_aabb(&unit));
+    assert!(!triangle([3.0, 0.0, 0.5], [0.0, 3.0, 0.5], [3.0, 3.0, 0.5]).intersects_aabb(&unit));
+
+    // Compare with dense sampling of the triangles against the box.
+    let sampled = |t: &Triangle3<f64>| {
+        (0..=60).any(|i| {
+            (0..=60 - i).any(|j| {
+                let (u, v) = (i as f64 / 60.0, j as f64 / 60.0);
+                unit.contains_point(t.point_at(Vector3::new(1.0 - u - v, u, v)))
+            })
+        })
+    };
+    let mut hits = 0;
+    for n in 0..200 {
+        let p = |k: i32| {
+            let r =
+                |d: i32| cgmath::noise::random2::<f64>(Point2::new(n, 3 * k + d), 1) * 3.0 - 1.0;
+            Point3::new(r(0), r(1), r(2))
+        };
+        let t = Triangle3::new(p(0), p(1), p(2));
+        if sampled(&t) {
+            assert!(t.intersects_aabb(&unit));
+            hits += 1;
+        }
+    }
+    assert!(hits > 20);
+}
+
+#[test]
+fn test_voxelize() {
+    // A slanted quad crossing a 4 × 4 × 4 block.
+    let (a, b, c, d) = (
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(4.0, 0.0, 0.0),
+        Point3::new(4.0, 4.0, 4.0),
+        Point3::new(0.0, 4.0, 4.0),
+    );
+    let triangles = [Triangle3::new(a, b, c), Triangle3::new(a, c, d)];
+    let grid = voxelize(&triangles, 1.0).unwrap();
+    assert_eq!((grid.width(), grid.height(), grid.depth()), (5, 5, 5));
+    assert_eq!(grid.position(0, 0, 0), Point3::new(0.5, 0.5, 0.5));
+    for k in 0..5 {
+        for j in 0..5 {
+            for i in 0..5 {
+                // The plane `y = z` touches the cubes with `|j - k| <= 1`.
+                let expected = j.max(k) - j.min(k) <= 1;
+                assert_eq!(grid.get(i, j, k), expected, "{} {} {}", i, j, k);
+            }
+        }
+    }
+    assert!(voxelize::<f64>(&[], 1.0).is_none());
+    assert!(voxelize(&triangles, 0.0).is_none());
+}
+
+#[test]
+fn test_slice() {
+    let area = |polyline: &Polyline3<f64>| {
+        let points: Vec<_> = polyline.points[1..]
+            .iter()
+            .map(|p| Point2::new(p.x, p.y))
+            .collect();
+        polygon::signed_area(&points)
+    };
+    let plane = Plane::new(Vector3::unit_z(), 0.5);
+    let loops = slice(&cube(), &plane, 1e-9);
+    assert_eq!(loops.len(), 1);
+    // Each side of the cube is split in two triangles, so the section has a
+    // point in the middle of each side.
+    let points = &loops[0].points;
+    assert_eq!(points.len(), 9);
+    assert_eq!(points[0], points[8]);
+    assert!(points.iter().all(|p| p.z == 0.5));
+    assert_ulps_eq!(loops[0].length(), 4.0);
+    assert_ulps_eq!(area(&loops[0]), 1.0);
+
+    // A hollow cube, whose inner surface faces inwards, gives a hole.
+    let mut hollow = cube();
+    hollow.extend(cube().iter().map(|t| {
+        let inner = |p: Point3<f64>| Point3::new(0.25, 0.25, 0.25) + p.to_vec() * 0.5;
+        Triangle3::new(inner(t.a), inner(t.c), inner(t.b))
+    }));
+    let mut areas: Vec<_> = slice(&hollow, &plane, 1e-9).iter().map(area).collect();
+    areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(areas.len(), 2);
+    assert_ulps_eq!(areas[0], -0.25);
+    assert_ulps_eq!(areas[1], 1.0);
+
+    // The bottom face lies in the plane, and counts as above it.
+    assert!(slice(&cube(), &Plane::new(Vector3::unit_z(), 0.0), 1e-9).is_empty());
+
+    // An open surface gives open polylines.
+    let open = slice(&cube()[..2], &Plane::new(Vector3::unit_x(), 0.5), 1e-9);
+    assert_eq!(open.len(), 1);
+    assert_eq!(
+        open[0].points,
+        vec![
+            Point3::new(0.5, 0.0, 0.0),
+            Point3::new(0.5, 0.5, 0.0),
+            Point3::new(0.5, 1.0, 0.0)
+        ]
+    );
+}
+
+#[test]
+fn test_convex_hull() {
+    let random = |n: i32| {
+        let r = |d: i32| noise::random2::<f64>(Point2::new(n, d), 7) * 2.0 - 1.0;
+        Point3::new(r(0), r(1), r(2))
+    };
+    let mut points: Vec<_> = (0..300).map(random).collect();
+    // Duplicated points may be on the hull, but only once.
+    points.push(points[0]);
+    points.push(points[10]);
+    let hull = convex_hull(&points);
+    // The faces come out in the same order every time.
+    assert_eq!(convex_hull(&points), hull);
+
+    // No point is outside of any face.
+    for t in &hull {
+        for &p in &points {
+            assert!(predicates::orient3d(points[t[0]], points[t[1]], points[t[2]], p) >= 0.0);
+        }
+    }
+    // Each edge is shared with exactly one other face, in the other direction.
+    let mut edges = std::collections::HashSet::new();
+    for t in &hull {
+        for &(u, v) in &[(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+            assert!(edges.insert((u, v)));
+        }
+    }
+    assert!(edges.iter().all(|&(u, v)| edges.contains(&(v, u))));
+    // The surface is a sphere, by the Euler characteristic.
+    let vertices: std::collections::HashSet<_> = hull.iter().flat_map(|t| t.to_vec()).collect();
+    assert_eq!(vertices.len() + hull.len(), edges.len() / 2 + 2);
+
+    let triangles: Vec<_> = hull
+        .iter()
+        .map(|t| Triangle3::new(points[t[0]], points[t[1]], points[t[2]]))
+        .collect();
+    let v = volume(&triangles);
+    assert!(v > 6.0 && v < 8.0);
+    assert!(centroid(&triangles).unwrap().to_vec().magnitude() < 0.1);
+
+    // Degenerate inputs.
+    assert!(convex_hull::<f64>(&[]).is_empty());
+    assert!(convex_hull(&points[..3]).is_empty());
+    let flat: Vec<_> = points.iter().map(|p| Point3::new(p.x, p.y, 0.0)).collect();
+    assert!(convex_hull(&flat).is_empty());
+    let line: Vec<_> = (0..5).map(|i| Point3::new(i as f64, 0.0, 0.0)).collect();
+    assert!(convex_hull(&line).is_empty());
+}
+
+#[test]
+fn test_volume_and_centroid() {
+    let shifted: Vec<_> = cube()
+        .iter()
+        .map(|t| {
+            let f = |p: Point3<f64>| Point3::new(p.x * 2.0 + 1.0, p.y + 3.0, p.z * 0.5);
+            Triangle3::new(f(t.a), f(t.b), f(t.c))
+        })
+        .collect();
+    assert_ulps_eq!(volume(&shifted), 1.0);
+    assert_ulps_eq!(centroid(&shifted).unwrap(), Point3::new(2.0, 3.5, 0.25));
+
+    // Clockwise triangles have a negative volume, but the same centroid.
+    let flipped: Vec<_> = shifted
+        .iter()
+        .map(|t| Triangle3::new(t.a, t.c, t.b))
+        .collect();
+    assert_ulps_eq!(volume(&flipped), -1.0);
+    assert_ulps_eq!(centroid(&flipped).unwrap(), Point3::new(2.0, 3.5, 0.25));
+    assert_eq!(centroid::<f64>(&[]), None);
+}
+
+#[test]
+fn test_mass_properties() {
+    // A box of 2 by 1 by 0.5, rotated and moved away from the origin.
+    let rotation = Matrix3::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalize(), Deg(40.0));
+    let offset = Vector3::new(3.0, -2.0, 5.0);
+    let place = |p: Point3<f64>| {
+        let local = Vector3::new((p.x - 0.5) * 2.0, p.y - 0.5, (p.z - 0.5) * 0.5);
+        Point3::from_vec(rotation * local + offset)
+    };
+    let triangles: Vec<_> = cube()
+        .iter()
+        .map(|t| Triangle3::new(place(t.a), place(t.b), place(t.c)))
+        .collect();
+    let properties = mass_properties(&triangles).unwrap();
+    assert_relative_eq!(properties.volume, 1.0, epsilon = 1e-12);
+    assert_relative_eq!(properties.volume, volume(&triangles), epsilon = 1e-12);
+    assert_relative_eq!(
+        properties.centroid,
+        Point3::from_vec(offset),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        properties.centroid,
+        centroid(&triangles).unwrap(),
+        epsilon = 1e-12
+    );
+
+    let (x, y, z) = (2.0f64, 1.0f64, 0.5f64);
+    let diagonal = Matrix3::new(
+        (y * y + z * z) / 12.0,
+        0.0,
+        0.0,
+        0.0,
+        (x * x + z * z) / 12.0,
+        0.0,
+        0.0,
+        0.0,
+        (x * x + y * y) / 12.0,
+    );
+    let expected = rotation * diagonal * rotation.transpose();
+    assert_relative_eq!(properties.inertia, expected, epsilon = 1e-12);
+
+    assert_eq!(mass_properties::<f64>(&[]), None);
+}
+
+#[test]
+fn test_vertex_normals() {
+    let soup: Vec<_> = cube().iter().flat_map(|t| vec![t.a, t.b, t.c]).collect();
+    let (positions, remap) = weld(&soup, 0.0);
+    let indices: Vec<_> = remap.chunks(3).map(|t| [t[0], t[1], t[2]]).collect();
+    let normals = vertex_normals(&positions, &indices);
+    // Every corner sees three faces at right angles, however they are split.
+    for (p, n) in positions.iter().zip(&normals) {
+        let outwards = (p - Point3::new(0.5, 0.5, 0.5)).normalize();
+        assert_relative_eq!(*n, outwards, epsilon = 1e-12);
+    }
+
+    // Unused vertices, and degenerate triangles, give no normal.
+    let mut positions = positions;
+    positions.push(Point3::new(5.0, 5.0, 5.0));
+    let mut indices = indices;
+    indices.push([0, 0, 1]);
+    let normals = vertex_normals(&positions, &indices);
+    assert_eq!(normals[8], Vector3::zero());
+    assert_relative_eq!(
+        normals[0],
+        -Vector3::new(1.0, 1.0, 1.0).normalize(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_vertex_tangents() {
+    // A bumpy grid, with the texture along x and y.
+    let size = 5;
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    for j in 0..size {
+        for i in 0..size {
+            let (x, y) = (i as f64, j as f64);
+            positions.push(Point3::new(x, y, (x * 0.7).sin() * (y * 0.5).cos()));
+            uvs.push(Point2::new(x / 4.0, y / 4.0));
+        }
+    }
+    let mut indices = Vec::new();
+    for j in 0..size - 1 {
+        for i in 0..size - 1 {
+            let k = j * size + i;
+            indices.push([k, k + 1, k + size + 1]);
+            indices.push([k, k + size + 1, k + size]);
+        }
+    }
+    let normals = vertex_normals(&positions, &indices);
+    let tangents = vertex_tangents(&positions, &normals, &uvs, &indices);
+    for (n, t) in normals.iter().zip(&tangents) {
+        assert_relative_eq!(t.truncate().magnitude(), 1.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(t.truncate().dot(*n), 0.0, epsilon = 1e-12);
+        assert!(t.x > 0.5);
+        assert_eq!(t.w, 1.0);
+    }
+
+    // Mirroring the texture flips the handedness.
+    let mirrored: Vec<_> = uvs.iter().map(|p| Point2::new(-p.x, p.y)).collect();
+    let tangents = vertex_tangents(&positions, &normals, &mirrored, &indices);
+    assert!(tangents.iter().all(|t| t.x < -0.5 && t.w == -1.0));
+
+    // Without texture coordinates, the tangents are still perpendicular.
+    let flat = vec![Point2::new(0.0, 0.0); uvs.len()];
+    let tangents = vertex_tangents(&positions, &normals, &flat, &indices);
+    for (n, t) in normals.iter().zip(&tangents) {
+        assert_abs_diff_eq!(t.truncate().dot(*n), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(t.truncate().magnitude(), 1.0, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_quadric() {
+    let triangle = Triangle3::new(
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(2.0, 0.0, 1.0),
+        Point3::new(0.0, 3.0, 1.0),
+    );
+    let quadric = Quadric::from_triangle(&triangle);
+    // The squared distance to the plane, weighted by the area.
+    assert_relative_eq!(
+        quadric.error(Point3::new(7.0, -4.0, 3.0)),
+        12.0,
+        epsilon = 1e-12
+    );
+    assert_eq!(quadric.error(Point3::new(7.0, -4.0, 1.0)), 0.0);
+    let degenerate = Triangle3::new(triangle.a, triangle.b, triangle.a);
+    assert_eq!(Quadric::from_triangle(&degenerate), Quadric::zero());
+
+    // Around a vertex of a tetrahedron, the vertex is the optimal point.
+    let apex = Point3::new(0.3, 0.2, 2.0);
+    let sides = [
+        Triangle3::new(triangle.a, triangle.b, apex),
+        Triangle3::new(triangle.b, triangle.c, apex),
+        Triangle3::new(triangle.c, triangle.a, apex),
+    ];
+    let mut around = Quadric::zero();
+    for side in &sides {
+        around += Quadric::from_triangle(side);
+    }
+    assert_relative_eq!(around.optimal_point(1e-8).unwrap(), apex, epsilon = 1e-12);
+    assert_abs_diff_eq!(around.error(apex), 0.0, epsilon = 1e-12);
+
+    // Flat regions and ridges have no unique optimum, but the best point of
+    // a segment is still found.
+    assert_eq!(quadric.optimal_point(1e-8), None);
+    let ridge = Quadric::from_triangle(&sides[0]) + Quadric::from_triangle(&sides[1]);
+    assert_eq!(ridge.optimal_point(1e-8), None);
+    let (a, b) = (Point3::new(1.0, 1.0, 0.0), Point3::new(1.0, 1.0, 4.0));
+    assert_relative_eq!(
+        quadric.optimal_point_on_segment(a, b),
+        Point3::new(1.0, 1.0, 1.0)
+    );
+    let (a, b) = (Point3::new(1.0, 1.0, 3.0), Point3::new(1.0, 1.0, 4.0));
+    assert_eq!(quadric.optimal_point_on_segment(a, b), a);
+    let (a, b) = (Point3::new(0.0, 0.0, 1.0), Point3::new(4.0, 2.0, 1.0));
+    assert_eq!(
+        quadric.optimal_point_on_segment(a, b),
+        Point3::new(2.0, 1.0, 1.0)
+    );
+}
+
+#[test]
+fn test_vertex_neighbors() {
+    let neighbors = vertex_neighbors(5, &[[0, 1, 2], [2, 1, 3], [3, 3, 1]]);
+    assert_eq!(
+        neighbors,
+        vec![vec![1, 2], vec![0, 2, 3], vec![0, 1, 3], vec![1, 2], vec![]]
+    );
+}
+
+#[test]
+fn test_smoothing() {
+    // A noisy circle, each point linked to the next and the previous.
+    let count = 64;
+    let noisy: Vec<_> = (0..count)
+        .map(|i| {
+            let angle = i as f64 / count as f64 * std::f64::consts::PI * 2.0;
+            let radius = 1.0 + if i % 2 == 0 { 0.05 } else { -0.05 };
+            Point3::new(angle.cos() * radius, angle.sin() * radius, 0.0)
+        })
+        .collect();
+    let neighbors: Vec<_> = (0..count)
+        .map(|i| vec![(i + count - 1) % count, (i + 1) % count])
+        .collect();
+    let radii = |points: &[Point3<f64>]| {
+        let r: Vec<_> = points.iter().map(|p| p.to_vec().magnitude()).collect();
+        let mean = r.iter().sum::<f64>() / r.len() as f64;
+        let spread = r.iter().fold(0.0f64, |m, r| m.max((r - mean).abs()));
+        (mean, spread)
+    };
+
+    let mut laplacian = noisy.clone();
+    laplacian_smooth(&mut laplacian, &neighbors, 0.5, 20);
+    let (mean, spread) = radii(&laplacian);
+    assert!(spread < 1e-3);
+    assert!(mean < 0.96);
+
+    let mut taubin = noisy.clone();
+    taubin_smooth(&mut taubin, &neighbors, 0.5, -0.53, 20);
+    let (mean, spread) = radii(&taubin);
+    assert!(spread < 1e-3);
+    assert!(mean > 0.99 && mean < 1.01);
+
+    // Nothing moves without iterations or without neighbours.
+    let mut unchanged = noisy.clone();
+    laplacian_smooth(&mut unchanged, &neighbors, 0.5, 0);
+    laplacian_smooth(&mut unchanged, &vec![vec![]; count], 0.5, 10);
+    assert_eq!(unchanged, noisy);
+}
@@ -0,0 +1,204 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::noise::*;
+use cgmath::*;
+
+fn samples2() -> Vec<Point2<f64>> {
+    (0..200)
+        .map(|i| {
+            let t = i as f64;
+            Point2::new(t * 0.37 - 20.0, (t * 1.31).sin() * 15.0)
+        })
+        .collect()
+}
+
+fn samples3() -> Vec<Point3<f64>> {
+    samples2()
+        .into_iter()
+        .enumerate()
+        .map(|(i, p)| Point3::new(p.x, p.y, i as f64 * -0.23))
+        .collect()
+}
+
+#[test]
+fn test_zero_on_lattice() {
+    let perlin = Perlin::new(3);
+    for x in -3..3 {
+        for y in -3..3 {
+            assert_eq!(perlin.sample2(Point2::new(x as f64, y as f64)), 0.0);
+            assert_eq!(perlin.sample3(Point3::new(x as f64, y as f64, 2.0)), 0.0);
+        }
+    }
+}
+
+#[test]
+fn test_range_and_variation() {
+    let perlin = Perlin::new(11);
+    let values: Vec<f64> = samples3().into_iter().map(|p| perlin.sample3(p)).collect();
+    assert!(values.iter().all(|v| v.abs() <= 1.5));
+    assert!(values.iter().any(|&v| v > 0.1));
+    assert!(values.iter().any(|&v| v < -0.1));
+
+    let other = Perlin::new(12);
+    assert!(samples3()
+        .into_iter()
+        .any(|p| perlin.sample3(p) != other.sample3(p)));
+}
+
+fn check_gradient2<F, D>(f: F, df: D, p: Point2<f64>)
+where
+    F: Fn(Point2<f64>) -> f64,
+    D: Fn(Point2<f64>) -> (f64, Vector2<f64>),
+{
+    let h = 1e-6;
+    let (value, gradient) = df(p);
+    assert_eq!(value, f(p));
+    let dx = (f(p + Vector2::unit_x() * h) - f(p - Vector2::unit_x() * h)) / (2.0 * h);
+    let dy = (f(p + Vector2::unit_y() * h) - f(p - Vector2::unit_y() * h)) / (2.0 * h);
+    assert_relative_eq!(gradient, Vector2::new(dx, dy), epsilon = 1e-4);
+}
+
+#[test]
+fn test_derivatives_2d() {
+    let perlin = Perlin::new(5);
+    let fbm = Fbm::new(5);
+    for p in samples2() {
+        check_gradient2(|p| perlin.sample2(p), |p| perlin.sample2_deriv(p), p);
+        check_gradient2(|p| fbm.sample2(p), |p| fbm.sample2_deriv(p), p);
+    }
+}
+
+#[test]
+fn test_derivatives_3d() {
+    let perlin = Perlin::new(9);
+    let fbm = Fbm::new(9);
+    let h = 1e-6;
+    for p in samples3() {
+        let (_, gradient) = perlin.sample3_deriv(p);
+        let (_, fbm_gradient) = fbm.sample3_deriv(p);
+        let mut numeric = Vector3::zero();
+        let mut fbm_numeric = Vector3::zero();
+        for i in 0..3 {
+            let mut e = Vector3::zero();
+            e[i] = h;
+            numeric[i] = (perlin.sample3(p + e) - perlin.sample3(p - e)) / (2.0 * h);
+            fbm_numeric[i] = (fbm.sample3(p + e) - fbm.sample3(p - e)) / (2.0 * h);
+        }
+        assert_relative_eq!(gradient, numeric, epsilon = 1e-4);
+        assert_relative_eq!(fbm_gradient, fbm_numeric, epsilon = 1e-3);
+    }
+}
+
+#[test]
+fn test_fbm_single_octave() {
+    let mut fbm = Fbm::new(21);
+    fbm.octaves = 1;
+    let perlin = Perlin::new(21);
+    for p in samples2() {
+        assert_eq!(fbm.sample2(p), perlin.sample2(p));
+    }
+}
+
+#[test]
+fn test_curl_divergence_free() {
+    let fbm = Fbm::new(13);
+    let h = 1e-5;
+    for p in samples2() {
+        let dx = Vector2::new(h, 0.0);
+        let dy = Vector2::new(0.0, h);
+        let divergence = (fbm.curl2(p + dx).x - fbm.curl2(p - dx).x + fbm.curl2(p + dy).y
+            - fbm.curl2(p - dy).y)
+            / (2.0 * h);
+        assert!(divergence.abs() < 1e-3, "{}", divergence);
+    }
+    for p in samples3() {
+        let mut divergence = 0.0;
+        for i in 0..3 {
+            let mut e = Vector3::zero();
+            e[i] = h;
+            divergence += (fbm.curl3(p + e)[i] - fbm.curl3(p - e)[i]) / (2.0 * h);
+        }
+        assert!(divergence.abs() < 1e-3, "{}", divergence);
+    }
+    assert!(fbm.curl3(Point3::new(0.3, 0.6, 0.2)).magnitude() > 0.0);
+}
+
+#[test]
+fn test_f32() {
+    let perlin = Perlin::new(1);
+    let a = perlin.sample3(Point3::new(0.25f32, 0.5, 0.75));
+    let b = perlin.sample3(Point3::new(0.25f64, 0.5, 0.75));
+    assert_relative_eq!(a as f64, b, epsilon = 1e-6);
+}
+
+#[test]
+fn test_hash_deterministic() {
+    assert_eq!(hash2(Point2::new(3, -7), 1), hash2(Point2::new(3, -7), 1));
+    assert_ne!(hash2(Point2::new(3, -7), 1), hash2(Point2::new(3, -7), 2));
+    assert_ne!(hash2(Point2::new(3, -7), 1), hash2(Point2::new(-7, 3), 1));
+    assert_ne!(
+        hash3(Point3::new(0, 0, 1), 0),
+        hash3(Point3::new(0, 0, 0), 1)
+    );
+    assert_ne!(pcg_hash(0), pcg_hash(1));
+}
+
+#[test]
+fn test_random_distribution() {
+    let mut sum = 0.0;
+    let mut buckets = [0; 10];
+    for x in -50..50 {
+        for y in -50..50 {
+            let r: f64 = random2(Point2::new(x, y), 42);
+            assert!((0.0..1.0).contains(&r));
+            sum += r;
+            buckets[(r * 10.0) as usize] += 1;
+        }
+    }
+    assert_relative_eq!(sum / 10_000.0, 0.5, epsilon = 0.02);
+    assert!(buckets.iter().all(|&n| n > 850 && n < 1150));
+
+    let r: f32 = random3(Point3::new(1, 2, 3), 0);
+    assert!((0.0..1.0).contains(&r));
+}
+
+#[test]
+fn test_random_vectors() {
+    for i in 0..100 {
+        let v: Vector2<f32> = random_vector2(Point2::new(i, -i), 5);
+        assert!(v.x >= -1.0 && v.x < 1.0 && v.y >= -1.0 && v.y < 1.0);
+        assert_ne!(v.x, v.y);
+
+        let w: Vector3<f64> = random_vector3(Point3::new(i, 0, i * 2), 5);
+        assert!(w.x >= -1.0 && w.x < 1.0);
+        assert!(w.y >= -1.0 && w.y < 1.0);
+        assert!(w.z >= -1.0 && w.z < 1.0);
+    }
+}
+
+#[test]
+fn test_white_noise_constant_per_cell() {
+    let a = white_noise2(Point2::new(2.1f64, -0.5), 9);
+    assert_eq!(a, white_noise2(Point2::new(2.9, -0.01), 9));
+    assert_eq!(a, random2(Point2::new(2, -1), 9));
+    assert_eq!(
+        white_noise3(Point3::new(-0.5f32, 0.5, 1.5), 9),
+        random3(Point3::new(-1, 0, 1), 9)
+    );
+}
@@ -0,0 +1,180 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_from_aabb() {
+    let aabb = Aabb3::new(Point3::new(-1.0, 0.0, 2.0), Point3::new(3.0, 1.0, 4.0));
+    let obb = Obb3::from_aabb(&aabb);
+    assert_eq!(obb.center, Point3::new(1.0, 0.5, 3.0));
+    assert_eq!(obb.half_extents, Vector3::new(2.0, 0.5, 1.0));
+    assert_eq!(obb.corners(), aabb.corners());
+    assert_eq!(obb.to_wireframe_lines(), aabb.to_wireframe_lines());
+}
+
+#[test]
+fn test_corners() {
+    let axes = Matrix3::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalize(), Deg(40.0));
+    let obb = Obb3::new(
+        Point3::new(1.0, 2.0, 3.0),
+        Vector3::new(1.0, 2.0, 3.0),
+        axes,
+    );
+    let corners = obb.corners();
+    for (i, &corner) in corners.iter().enumerate() {
+        let local = axes.transpose() * (corner - obb.center);
+        let expected = Vector3::new(
+            if i & 1 == 0 { -1.0 } else { 1.0 },
+            if i & 2 == 0 { -2.0 } else { 2.0 },
+            if i & 4 == 0 { -3.0 } else { 3.0 },
+        );
+        assert_relative_eq!(local, expected, epsilon = 1e-12);
+    }
+
+    let lines = obb.to_wireframe_lines();
+    assert_eq!(lines.len(), 12);
+    let total: f64 = lines.iter().map(|&(a, b)| a.distance(b)).sum();
+    assert_relative_eq!(total, 4.0 * (2.0 + 4.0 + 6.0), epsilon = 1e-12);
+}
+
+fn rotated_box() -> Obb3<f64> {
+    let axes = Matrix3::from_axis_angle(Vector3::new(-2.0, 1.0, 3.0).normalize(), Deg(25.0));
+    Obb3::new(
+        Point3::new(1.0, -2.0, 0.5),
+        Vector3::new(1.0, 2.0, 0.5),
+        axes,
+    )
+}
+
+#[test]
+fn test_to_unit_cube_matrix() {
+    let obb = rotated_box();
+    let m = obb.to_unit_cube_matrix();
+    for (i, &corner) in obb.corners().iter().enumerate() {
+        let expected = Point3::new((i & 1) as f64, (i >> 1 & 1) as f64, (i >> 2) as f64);
+        assert_relative_eq!(m.transform_point(corner), expected, epsilon = 1e-12);
+    }
+    let center = m.transform_point(obb.center);
+    assert_relative_eq!(center, Point3::new(0.5, 0.5, 0.5), epsilon = 1e-12);
+}
+
+#[test]
+fn test_clip_triangle() {
+    let obb = rotated_box();
+    let to_world = |x: f64, y: f64, z: f64| obb.center + obb.axes * Vector3::new(x, y, z);
+
+    // A triangle inside the box is left unchanged.
+    let inside = Triangle3::new(
+        to_world(0.5, 0.0, 0.0),
+        to_world(0.0, 1.0, 0.2),
+        to_world(-0.5, -1.0, -0.2),
+    );
+    let polygon = obb.clip_triangle(&inside);
+    assert_eq!(polygon.len(), 3);
+    assert_relative_eq!(polygon[0], inside.a, epsilon = 1e-12);
+    assert_relative_eq!(polygon[1], inside.b, epsilon = 1e-12);
+    assert_relative_eq!(polygon[2], inside.c, epsilon = 1e-12);
+
+    // A triangle far away is clipped away entirely.
+    let outside = Triangle3::new(
+        to_world(3.0, 0.0, 0.0),
+        to_world(4.0, 1.0, 0.0),
+        to_world(3.0, 1.0, 0.0),
+    );
+    assert!(obb.clip_triangle(&outside).is_empty());
+
+    // A large triangle through the middle of the box is cut to the cross
+    // section of the box.
+    let large = Triangle3::new(
+        to_world(-10.0, -10.0, 0.1),
+        to_world(30.0, -10.0, 0.1),
+        to_world(-10.0, 30.0, 0.1),
+    );
+    let polygon = obb.clip_triangle(&large);
+    assert_eq!(polygon.len(), 4);
+    let area: f64 = (1..polygon.len() - 1)
+        .map(|i| Triangle3::new(polygon[0], polygon[i], polygon[i + 1]).area())
+        .sum();
+    assert_relative_eq!(area, 8.0, epsilon = 1e-9);
+    let normal = Triangle3::new(polygon[0], polygon[1], polygon[2]).normal();
+    assert_relative_eq!(normal, large.normal(), epsilon = 1e-9);
+}
+
+#[test]
+fn test_obb2_corners() {
+    let obb = Obb2::new(
+        Point2::new(1.0, 2.0),
+        Vector2::new(2.0, 1.0),
+        Deg(90.0f64).into(),
+    );
+    let corners = obb.corners();
+    assert_relative_eq!(corners[0], Point2::new(2.0, 0.0), epsilon = 1e-12);
+    assert_relative_eq!(corners[1], Point2::new(2.0, 4.0), epsilon = 1e-12);
+    assert_relative_eq!(corners[2], Point2::new(0.0, 4.0), epsilon = 1e-12);
+    assert_relative_eq!(corners[3], Point2::new(0.0, 0.0), epsilon = 1e-12);
+    assert_relative_eq!(
+        obb.to_aabb(),
+        Aabb2::new(Point2::new(0.0, 0.0), Point2::new(2.0, 4.0)),
+        epsilon = 1e-12
+    );
+    assert!(obb.contains_point(Point2::new(1.9, 3.9)));
+    assert!(!obb.contains_point(Point2::new(2.5, 2.0)));
+
+    let aabb = Aabb2::new(Point2::new(-1.0, 0.0), Point2::new(3.0, 1.0));
+    let from_aabb = Obb2::from_aabb(&aabb);
+    assert_eq!(from_aabb.corners(), aabb.corners());
+}
+
+#[test]
+fn test_obb2_intersection() {
+    let diamond = Obb2::new(
+        Point2::new(0.0, 0.0),
+        Vector2::new(1.0, 1.0),
+        Deg(45.0f64).into(),
+    );
+    let reach = 2.0f64.sqrt();
+    let at = |x: f64, y: f64, angle: f64| {
+        Obb2::new(Point2::new(x, y), Vector2::new(0.5, 0.5), Deg(angle).into())
+    };
+    // The square next to the tip of the diamond, along the x axis.
+    assert!(diamond.intersects_obb(&at(reach + 0.49, 0.0, 0.0)));
+    assert!(!diamond.intersects_obb(&at(reach + 0.51, 0.0, 0.0)));
+    // Near the corner of the bounds of the diamond, only the axes of the
+    // diamond separate them.
+    assert!(!diamond.intersects_obb(&at(1.25, 1.25, 0.0)));
+    assert!(diamond.intersects_obb(&at(1.0, 1.0, 0.0)));
+    assert!(diamond.intersects_obb(&at(0.0, 0.0, 10.0)));
+    // The overlap test is symmetric.
+    for &(x, y, angle) in &[
+        (1.25, 1.25, 0.0),
+        (1.0, 1.0, 30.0),
+        (reach + 0.6, 0.0, 45.0),
+    ] {
+        let other = at(x, y, angle);
+        assert_eq!(
+            diamond.intersects_obb(&other),
+            other.intersects_obb(&diamond)
+        );
+    }
+
+    let corner = Aabb2::new(Point2::new(1.0, 1.0), Point2::new(2.0, 2.0));
+    assert!(!diamond.intersects_aabb(&corner));
+    let side = Aabb2::new(Point2::new(0.5, -0.1), Point2::new(2.0, 0.1));
+    assert!(diamond.intersects_aabb(&side));
+}
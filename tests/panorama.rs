@@ -0,0 +1,194 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::noise::random2;
+use cgmath::panorama::*;
+
+/// Deterministic pseudo-random unit directions.
+fn directions(count: usize) -> Vec<Vector3<f64>> {
+    (0..count as i32)
+        .map(|n| {
+            let u = random2::<f64>(Point2::new(n, 0), 7) * 2.0 - 1.0;
+            let v = random2::<f64>(Point2::new(n, 1), 7) * 2.0 - 1.0;
+            let w = random2::<f64>(Point2::new(n, 2), 7) * 2.0 - 1.0;
+            Vector3::new(u, v, w).normalize()
+        })
+        .collect()
+}
+
+#[test]
+fn test_cube_face_centers() {
+    for &face in &CubeFace::ALL {
+        let (normal, u, v) = face.axes::<f64>();
+        assert_eq!(cube_direction(face, Point2::new(0.5, 0.5)), normal);
+        assert_eq!(CubeFace::from_direction(normal), face);
+        // Seen from outside of the cube, `u` goes right and `v` goes down.
+        assert_eq!(v.cross(u), normal);
+    }
+    assert_eq!(CubeFace::ALL[3].index(), 3);
+    assert_eq!(
+        cube_direction(CubeFace::PositiveX, Point2::new(0.0, 0.0)),
+        Vector3::new(1.0, 1.0, 1.0).normalize()
+    );
+}
+
+#[test]
+fn test_cube_round_trip() {
+    for direction in directions(200) {
+        let (face, uv) = cube_face_uv(direction);
+        assert!(uv.x >= 0.0 && uv.x <= 1.0 && uv.y >= 0.0 && uv.y <= 1.0);
+        assert_relative_eq!(cube_direction(face, uv), direction, epsilon = 1e-12);
+        let (again, other) = cube_face_uv(direction * 3.0);
+        assert_eq!(again, face);
+        assert_relative_eq!(other, uv, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_cube_edges_are_shared() {
+    // Points on the common edge of two faces map to the same direction.
+    let right = cube_direction(CubeFace::PositiveZ, Point2::new(1.0, 0.25));
+    let left = cube_direction(CubeFace::PositiveX, Point2::new(0.0, 0.25));
+    assert_relative_eq!(right, left, epsilon = 1e-12);
+    let top = cube_direction(CubeFace::PositiveZ, Point2::new(0.75, 0.0));
+    let bottom = cube_direction(CubeFace::PositiveY, Point2::new(0.75, 1.0));
+    assert_relative_eq!(top, bottom, epsilon = 1e-12);
+}
+
+#[test]
+fn test_equirect_layout() {
+    let ahead = equirect_uv(-Vector3::<f64>::unit_z());
+    assert_relative_eq!(ahead, Point2::new(0.5, 0.5));
+    assert_relative_eq!(
+        equirect_uv(Vector3::<f64>::unit_x()),
+        Point2::new(0.75, 0.5)
+    );
+    assert_relative_eq!(equirect_uv(Vector3::<f64>::unit_y()).y, 0.0);
+    assert_relative_eq!(equirect_uv(-Vector3::<f64>::unit_y()).y, 1.0);
+    assert_relative_eq!(
+        equirect_direction(Point2::new(0.0, 0.5)),
+        Vector3::<f64>::unit_z(),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        equirect_direction(Point2::new(1.0, 0.5)),
+        Vector3::<f64>::unit_z(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_equirect_round_trip() {
+    for direction in directions(200) {
+        let uv = equirect_uv(direction);
+        assert_relative_eq!(equirect_direction(uv), direction, epsilon = 1e-12);
+
+        let angles = Spherical::from_direction(direction);
+        assert_relative_eq!(angles.to_direction(), direction, epsilon = 1e-12);
+        assert_relative_eq!(angles.to_equirect(), uv, epsilon = 1e-12);
+        let back = Spherical::from_equirect(uv);
+        assert_relative_eq!(back.azimuth, angles.azimuth, epsilon = 1e-12);
+        assert_relative_eq!(back.elevation, angles.elevation, epsilon = 1e-12);
+
+        // A cubemap can be converted to a panorama through directions.
+        let (face, cube_uv) = cube_face_uv(direction);
+        assert_relative_eq!(
+            equirect_uv(cube_direction(face, cube_uv)),
+            uv,
+            epsilon = 1e-9
+        );
+    }
+    let up = Spherical::from_direction(Vector3::new(0.0, 2.0, 0.0f64));
+    assert_eq!(up.azimuth, Rad(0.0));
+    assert_relative_eq!(up.elevation, Rad::turn_div_4());
+}
+
+/// Finite differences of a mapping, which are only meaningful away from
+/// its seams.
+fn finite_difference<F: Fn(Vector3<f64>) -> Point2<f64>>(
+    map: F,
+    direction: Vector3<f64>,
+    d: Vector3<f64>,
+) -> Vector2<f64> {
+    let h = 1e-6;
+    (map(direction + d * h) - map(direction - d * h)) / (2.0 * h)
+}
+
+#[test]
+fn test_equirect_derivatives() {
+    let (dx, dy) = (Vector3::new(0.3, -0.2, 0.1), Vector3::new(-0.1, 0.4, 0.2));
+    for direction in directions(100) {
+        if direction.y.abs() > 0.95 || (direction.x.abs() < 0.05 && direction.z > 0.0) {
+            continue;
+        }
+        let (du, dv) = equirect_uv_derivatives(direction, dx, dy);
+        assert_relative_eq!(
+            du,
+            finite_difference(equirect_uv, direction, dx),
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(
+            dv,
+            finite_difference(equirect_uv, direction, dy),
+            epsilon = 1e-6
+        );
+    }
+
+    // Across the seam behind the viewer, the coordinates jump by a whole
+    // turn while the derivatives stay continuous.
+    let (left, right) = (Vector3::new(-1e-9, 0.0, 1.0), Vector3::new(1e-9, 0.0, 1.0));
+    assert!((equirect_uv::<f64>(left).x - equirect_uv(right).x).abs() > 0.99);
+    let d = Vector3::unit_x();
+    let (a, _) = equirect_uv_derivatives(left, d, d);
+    let (b, _) = equirect_uv_derivatives(right, d, d);
+    assert_relative_eq!(a, b, epsilon = 1e-9);
+    assert_relative_eq!(a.x, -1.0 / (2.0 * std::f64::consts::PI), epsilon = 1e-9);
+
+    let (pole, _) = equirect_uv_derivatives(Vector3::unit_y(), d, d);
+    assert_eq!(pole, Vector2::zero());
+}
+
+#[test]
+fn test_cube_derivatives() {
+    let (dx, dy) = (Vector3::new(0.3, -0.2, 0.1), Vector3::new(-0.1, 0.4, 0.2));
+    let uv = |direction| cube_face_uv(direction).1;
+    for direction in directions(100) {
+        let (face, coords) = cube_face_uv(direction);
+        let margin = coords
+            .x
+            .min(coords.y)
+            .min(1.0 - coords.x)
+            .min(1.0 - coords.y);
+        if margin < 0.01 {
+            continue;
+        }
+        let (du, dv) = cube_uv_derivatives(direction, dx, dy);
+        assert_relative_eq!(du, finite_difference(uv, direction, dx), epsilon = 1e-6);
+        assert_relative_eq!(dv, finite_difference(uv, direction, dy), epsilon = 1e-6);
+        assert_eq!(CubeFace::from_direction(direction), face);
+    }
+
+    // Along the edge between two faces, both faces change in the same way.
+    let d = Vector3::unit_y();
+    let (a, _) = cube_uv_derivatives(Vector3::new(1.0 + 1e-9, 0.2, 1.0), d, d);
+    let (b, _) = cube_uv_derivatives(Vector3::new(1.0, 0.2, 1.0 + 1e-9), d, d);
+    assert_relative_eq!(a, b, epsilon = 1e-6);
+    assert_relative_eq!(a, Vector2::new(0.0, -0.5), epsilon = 1e-6);
+}
@@ -0,0 +1,391 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::physics::{
+    bounce_velocity, intercept, satisfy_constraints, Advection, DistanceConstraint, Projectile,
+    Spring, VerletSystem,
+};
+
+#[test]
+fn test_satisfy_constraints() {
+    let mut positions = vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(3.0, 0.0, 0.0),
+        Point3::new(3.0, 4.0, 1.0),
+    ];
+    let pinned = [true, false, false];
+    let constraints = [
+        DistanceConstraint::new(0, 1, 1.0),
+        DistanceConstraint::new(1, 2, 2.0f64),
+    ];
+    satisfy_constraints(&mut positions, &pinned, &constraints, 100);
+    assert_eq!(positions[0], Point3::new(0.0, 0.0, 0.0));
+    assert_relative_eq!(positions[0].distance(positions[1]), 1.0, epsilon = 1e-9);
+    assert_relative_eq!(positions[1].distance(positions[2]), 2.0, epsilon = 1e-9);
+
+    // Two free particles move symmetrically.
+    let mut pair = vec![Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0)];
+    let constraint = DistanceConstraint::new(0, 1, 1.0);
+    satisfy_constraints(&mut pair, &[false, false], &[constraint], 1);
+    assert_eq!(
+        pair,
+        vec![Point3::new(-0.5, 0.0, 0.0), Point3::new(0.5, 0.0, 0.0)]
+    );
+
+    // Pinned on both ends, nothing moves.
+    satisfy_constraints(
+        &mut pair,
+        &[true, true],
+        &[DistanceConstraint::new(0, 1, 5.0)],
+        1,
+    );
+    assert_eq!(
+        pair,
+        vec![Point3::new(-0.5, 0.0, 0.0), Point3::new(0.5, 0.0, 0.0)]
+    );
+}
+
+#[test]
+fn test_verlet_free_fall() {
+    let mut system = VerletSystem::new(vec![
+        Point3::new(0.0, 10.0, 0.0),
+        Point3::new(1.0, 10.0, 0.0),
+    ]);
+    system.pinned[1] = true;
+    let gravity = Vector3::new(0.0, -10.0, 0.0);
+    let dt = 0.1;
+    for _ in 0..10 {
+        system.step(gravity, dt, 4);
+    }
+    // The particle starts at rest, and gains `a * dt` of velocity per step.
+    let fallen = 10.0 * 0.01 * (10.0 * 11.0) / 2.0;
+    assert_relative_eq!(
+        system.positions[0],
+        Point3::new(0.0, 10.0 - fallen, 0.0),
+        epsilon = 1e-9
+    );
+    assert_relative_eq!(
+        system.velocity(0, dt),
+        Vector3::new(0.0, -10.0, 0.0),
+        epsilon = 1e-9
+    );
+    assert_eq!(system.positions[1], Point3::new(1.0, 10.0, 0.0));
+    assert_eq!(system.velocity(1, dt), Vector3::zero());
+}
+
+#[test]
+fn test_rope() {
+    let start = Point3::new(0.0, 0.0, 0.0);
+    let mut rope = VerletSystem::rope(start, Point3::new(0.0, 0.0, 2.0f64), 8);
+    assert_eq!(rope.positions.len(), 9);
+    assert_eq!(rope.constraints.len(), 8);
+    assert_relative_eq!(rope.constraints[3].length, 0.25);
+
+    // Pulling the last particle away stretches the segments a little,
+    // except the last one to be projected, and keeps the rope straight
+    // between its pinned ends.
+    rope.pinned[0] = true;
+    rope.pinned[8] = true;
+    rope.positions[8] = Point3::new(0.0, 0.0, 2.2);
+    for _ in 0..50 {
+        rope.step(Vector3::zero(), 0.01, 50);
+    }
+    assert_eq!(rope.positions[0], start);
+    assert_eq!(rope.positions[8], Point3::new(0.0, 0.0, 2.2));
+    for c in &rope.constraints {
+        let segment = rope.positions[c.b] - rope.positions[c.a];
+        assert_relative_eq!(segment.truncate(), Vector2::zero(), epsilon = 1e-12);
+        assert!(segment.z > 0.25 - 1e-12 && segment.z < 0.3);
+    }
+}
+
+#[test]
+fn test_spring_frequency() {
+    let spring = Spring::from_frequency(2.0f64, 0.0, 3.0);
+    let omega = 4.0 * ::std::f64::consts::PI;
+    assert_relative_eq!(spring.stiffness, 3.0 * omega * omega, max_relative = 1e-12);
+    assert_eq!(spring.damping, 0.0);
+    let critical = Spring::from_frequency(2.0f64, 1.0, 3.0);
+    assert_relative_eq!(
+        critical.damping,
+        Spring::critically_damped(critical.stiffness, 3.0).damping,
+        max_relative = 1e-12
+    );
+}
+
+#[test]
+fn test_spring_between() {
+    let spring = Spring::new(10.0f64, 2.0);
+    let a = Point3::new(3.0, 0.0, 0.0);
+    let b = Point3::new(0.0, 0.0, 0.0);
+    let still = Vector3::zero();
+    // Stretched by one unit, the spring pulls `a` towards `b`.
+    let force = spring.force_between(a, still, b, still, 2.0);
+    assert_relative_eq!(force, Vector3::new(-10.0, 0.0, 0.0));
+    // Only the velocity along the spring is damped.
+    let sliding = Vector3::new(0.0, 5.0, 0.0);
+    assert_relative_eq!(spring.force_between(a, sliding, b, still, 3.0), still);
+    let closing = Vector3::new(-1.0, 0.0, 0.0);
+    let force = spring.force_between(a, closing, b, still, 3.0);
+    assert_relative_eq!(force, Vector3::new(2.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_spring_torque() {
+    let spring = Spring::critically_damped(50.0f64, 1.0);
+    let target = Quaternion::from_axis_angle(Vector3::unit_z(), Rad(1.0));
+    let torque = spring.torque(Quaternion::one(), Vector3::zero(), target);
+    assert_relative_eq!(torque, Vector3::new(0.0, 0.0, 50.0), epsilon = 1e-12);
+
+    // The same rotation by its negated quaternion takes the shortest path.
+    let torque = spring.torque(Quaternion::one(), Vector3::zero(), -target);
+    assert_relative_eq!(torque, Vector3::new(0.0, 0.0, 50.0), epsilon = 1e-12);
+
+    // Integrating the torque brings the orientation to the target.
+    let (mut q, mut omega) = (Quaternion::<f64>::one(), Vector3::zero());
+    for _ in 0..2000 {
+        omega += spring.torque(q, omega, target) * 0.001;
+        q = q.integrate(omega, 0.001);
+    }
+    assert!(q.normalize().dot(target).abs() > 1.0 - 1e-9);
+}
+
+#[test]
+fn test_intercept() {
+    let shooter = Point3::new(1.0, 2.0, 3.0f64);
+    let target = Point3::new(-4.0, 6.0, 0.0);
+    let velocity = Vector3::new(1.0, -0.5, 2.0);
+    let aim = intercept(shooter, 4.0, target, velocity).unwrap();
+    assert_relative_eq!(aim.magnitude(), 4.0, epsilon = 1e-12);
+    // The projectile and the target are on the same ray from the shooter at
+    // the same time, so their relative velocity points at the target.
+    let relative = velocity - aim;
+    assert_relative_eq!(
+        relative.normalize(),
+        (shooter - target).normalize(),
+        epsilon = 1e-12
+    );
+
+    // A still target is aimed at directly.
+    let aim = intercept(shooter, 2.0, target, Vector3::zero()).unwrap();
+    assert_relative_eq!(aim, (target - shooter).normalize_to(2.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_intercept_degenerate() {
+    let shooter = Point3::new(0.0, 0.0, 0.0f64);
+    let target = Point3::new(10.0, 0.0, 0.0);
+
+    // A target as fast as the projectile can only be caught head on.
+    let aim = intercept(shooter, 2.0, target, Vector3::new(-2.0, 0.0, 0.0)).unwrap();
+    assert_relative_eq!(aim, Vector3::new(2.0, 0.0, 0.0), epsilon = 1e-12);
+    let aim = intercept(shooter, 2.0, target, Vector3::new(-1.2, -1.6, 0.0)).unwrap();
+    assert_relative_eq!(aim.magnitude(), 2.0, epsilon = 1e-12);
+    assert_eq!(
+        intercept(shooter, 2.0, target, Vector3::new(0.0, 2.0, 0.0)),
+        None
+    );
+    assert_eq!(
+        intercept(shooter, 2.0, target, Vector3::new(2.0, 0.0, 0.0)),
+        None
+    );
+
+    // A faster target moving towards the shooter is hit the first time.
+    let aim = intercept(shooter, 1.0, target, Vector3::new(-3.0, 0.0, 0.0)).unwrap();
+    assert_relative_eq!(aim, Vector3::new(1.0, 0.0, 0.0), epsilon = 1e-12);
+    // A faster target passing by is out of reach.
+    assert_eq!(
+        intercept(shooter, 1.0, target, Vector3::new(0.0, 3.0, 0.0)),
+        None
+    );
+
+    assert_eq!(intercept(shooter, 0.0, target, Vector3::zero()), None);
+    assert_eq!(intercept(shooter, 1.0, shooter, Vector3::zero()), None);
+}
+
+#[test]
+fn test_bounce_velocity() {
+    let v = Vector3::new(3.0, -4.0, 1.0f64);
+    assert_eq!(
+        bounce_velocity(v, Vector3::unit_y(), 1.0),
+        Vector3::new(3.0, 4.0, 1.0)
+    );
+    assert_eq!(
+        bounce_velocity(v, Vector3::new(0.0, 2.0, 0.0), 0.5),
+        Vector3::new(3.0, 2.0, 1.0)
+    );
+    assert_eq!(
+        bounce_velocity(v, Vector3::unit_y(), 0.0),
+        Vector3::new(3.0, 0.0, 1.0)
+    );
+    assert_eq!(bounce_velocity(v, Vector3::zero(), 0.5), v);
+}
+
+#[test]
+fn test_projectile_arc() {
+    let ball = Projectile::new(
+        Point3::new(1.0, 2.0, 0.0),
+        Vector3::new(2.0, 3.0, -1.0),
+        Vector3::new(0.0, -9.81, 0.0f64),
+    );
+    let arc = ball.arc(2.0, 10);
+    assert_eq!(arc.points.len(), 11);
+    for (i, &p) in arc.points.iter().enumerate() {
+        assert_relative_eq!(p, ball.position_at(i as f64 / 5.0), epsilon = 1e-12);
+    }
+    // The apex is where the vertical velocity vanishes.
+    let apex = 3.0 / 9.81;
+    assert_relative_eq!(ball.velocity_at(apex).y, 0.0, epsilon = 1e-12);
+    assert_relative_eq!(
+        ball.position_at(apex).y,
+        2.0 + 9.0 / (2.0 * 9.81),
+        epsilon = 1e-12
+    );
+}
+
+fn ground_cast(from: Point3<f64>, to: Point3<f64>) -> Option<(Point3<f64>, Vector3<f64>)> {
+    let ground = Plane::new(Vector3::unit_y(), 0.0);
+    let hit = (Ray3::new(from, to - from), ground).intersection()?;
+    if (hit - from).magnitude2() <= (to - from).magnitude2() {
+        Some((hit, ground.normal))
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_projectile_bounces() {
+    let ball = Projectile::new(
+        Point3::new(0.0, 5.0, 0.0),
+        Vector3::new(1.0, 0.0, 0.0),
+        Vector3::new(0.0, -10.0f64, 0.0),
+    );
+    let (path, bounces) = ball.predict(0.5, 1e-3, 5000, 3, ground_cast);
+    assert_eq!(bounces.len(), 3);
+    assert_eq!(*path.points.last().unwrap(), bounces[2].position);
+    assert!(path.points.iter().all(|p| p.y >= -1e-12));
+
+    // Falling from a height of 5 takes a unit of time.
+    assert_relative_eq!(bounces[0].time, 1.0, epsilon = 1e-3);
+    assert_relative_eq!(
+        bounces[0].position,
+        Point3::new(1.0, 0.0, 0.0),
+        epsilon = 1e-3
+    );
+    assert_relative_eq!(
+        bounces[0].velocity,
+        Vector3::new(1.0, 5.0, 0.0),
+        epsilon = 1e-2
+    );
+    // Each bounce halves the vertical speed, and the flight time with it.
+    for w in bounces.windows(2) {
+        assert_relative_eq!(w[1].velocity.y, w[0].velocity.y * 0.5, epsilon = 1e-2);
+    }
+    assert_relative_eq!(bounces[1].time - bounces[0].time, 1.0, epsilon = 1e-2);
+    assert_relative_eq!(bounces[2].time - bounces[1].time, 0.5, epsilon = 1e-2);
+
+    // Without surfaces, the prediction samples the flight.
+    let (path, bounces) = ball.predict(0.5, 0.1, 20, 3, |_, _| None);
+    assert!(bounces.is_empty());
+    assert_eq!(path.points.len(), 21);
+    assert_relative_eq!(path.points[20], ball.position_at(2.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_projectile_leaving_surface() {
+    // Launched from the ground, the ball does not hit it at once.
+    let ball = Projectile::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::new(4.0, 5.0, 0.0),
+        Vector3::new(0.0, -10.0f64, 0.0),
+    );
+    let (_, bounces) = ball.predict(1.0, 1.0 / 64.0, 100, 1, ground_cast);
+    assert_eq!(bounces.len(), 1);
+    assert_relative_eq!(bounces[0].time, 1.0, epsilon = 1e-9);
+    assert_relative_eq!(
+        bounces[0].velocity,
+        Vector3::new(4.0, 5.0, 0.0),
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn test_advection_without_drag() {
+    let smoke = Advection::new();
+    let (p, v) = smoke.advance3(
+        Point3::new(1.0, 2.0, 3.0),
+        Vector3::new(5.0, 0.0, 0.0),
+        Vector3::new(0.0, 1.0, 0.0),
+        2.0,
+    );
+    assert_eq!(v, Vector3::new(0.0, 1.0, 0.0));
+    assert_eq!(p, Point3::new(1.0, 4.0, 3.0));
+}
+
+#[test]
+fn test_advection_drag() {
+    let leaf = Advection::new().with_drag(2.0);
+    let wind = Vector2::new(3.0, 0.0);
+    let (_, v) = leaf.advance2(Point2::new(0.0, 0.0), Vector2::zero(), wind, 0.5);
+    assert_relative_eq!(v, wind * (1.0 - (-1.0f64).exp()));
+    // Stepping once or in many steps gives the same velocity.
+    let mut velocity = Vector2::zero();
+    for _ in 0..10 {
+        velocity = leaf.advance2(Point2::new(0.0, 0.0), velocity, wind, 0.05).1;
+    }
+    assert_relative_eq!(velocity, v, epsilon = 1e-12);
+    // Without wind, the particle slows down.
+    let (_, v) = leaf.advance2(Point2::new(0.0, 0.0), wind, Vector2::zero(), 1.0);
+    assert!(v.x > 0.0 && v.x < wind.x);
+}
+
+#[test]
+fn test_advection_turbulence() {
+    let smoke = Advection::new().with_turbulence(1.0, 0.5, 7);
+    let mut positions: Vec<Point3<f64>> = (0..16)
+        .map(|i| Point3::new(i as f64 * 0.37, 0.1, -0.2))
+        .collect();
+    let mut velocities = vec![Vector3::zero(); 16];
+    let start = positions.clone();
+    smoke.advect3(
+        &mut positions,
+        &mut velocities,
+        |_| Vector3::new(0.0, 0.0, 1.0),
+        0.1,
+    );
+    let mut swirled = false;
+    for ((p, q), v) in positions.iter().zip(&start).zip(&velocities) {
+        assert_relative_eq!(*p, q + v * 0.1);
+        swirled |= (v - Vector3::new(0.0, 0.0, 1.0)).magnitude() > 1e-3;
+    }
+    assert!(swirled);
+    // The same seed gives the same motion.
+    let again = Advection::new().with_turbulence(1.0, 0.5, 7);
+    let (_, v) = again.advance3(start[3], Vector3::zero(), Vector3::new(0.0, 0.0, 1.0), 0.1);
+    assert_eq!(v, velocities[3]);
+}
+
+#[test]
+#[should_panic]
+fn test_advection_mismatched_slices() {
+    let mut positions = vec![Point2::new(0.0, 0.0); 2];
+    let mut velocities = vec![Vector2::zero(); 3];
+    Advection::new().advect2(&mut positions, &mut velocities, |_| Vector2::zero(), 0.1);
+}
@@ -0,0 +1,67 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_construction() {
+    let a = Point3::new(1.0, 0.0, 0.0);
+    let b = Point3::new(0.0, 1.0, 0.0);
+    let c = Point3::new(0.0, 0.0, 1.0);
+    let plane = Plane::from_points(a, b, c).unwrap();
+    let n = Vector3::new(1.0, 1.0, 1.0).normalize();
+    assert_relative_eq!(plane, Plane::new(n, 1.0 / 3.0f64.sqrt()), epsilon = 1e-12);
+    assert_relative_eq!(plane, Plane::from_point_normal(b, n * 3.0), epsilon = 1e-12);
+    assert_eq!(Plane::from_points(a, b, a + (b - a) * 2.0), None);
+
+    assert_relative_eq!(plane.distance(Point3::new(0.0, 0.0, 0.0)), -plane.d);
+    assert_relative_eq!(plane.distance(plane.origin()), 0.0);
+    let p = Point3::new(3.0, -1.0, 2.0);
+    assert_relative_eq!(plane.distance(plane.project_point(p)), 0.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_to_wireframe_lines() {
+    let plane = Plane::new(Vector3::unit_z(), 2.0f64);
+    let lines = plane.to_wireframe_lines(4.0);
+    assert_eq!(lines.len(), 5);
+    for &(a, b) in &lines[..4] {
+        assert_relative_eq!(a.z, 2.0);
+        assert_relative_eq!(a.distance(b), 4.0, epsilon = 1e-12);
+        assert_relative_eq!(a.x.abs(), 2.0, epsilon = 1e-12);
+        assert_relative_eq!(a.y.abs(), 2.0, epsilon = 1e-12);
+    }
+    assert_eq!(
+        lines[4],
+        (Point3::new(0.0, 0.0, 2.0), Point3::new(0.0, 0.0, 4.0))
+    );
+}
+
+#[test]
+fn test_vector4() {
+    let plane = Plane::from_point_normal(Point3::new(0.0, 0.0, 5.0), Vector3::new(0.0, 0.0, -2.0));
+    assert_eq!(plane.to_vector4(), Vector4::new(0.0, 0.0, -1.0, 5.0));
+    assert_eq!(
+        Plane::from_vector4(Vector4::new(0.0, 0.0, -3.0, 15.0)),
+        Some(plane)
+    );
+    assert_eq!(
+        Plane::from_vector4(Vector4::new(0.0, 0.0, 0.0, 1.0f64)),
+        None
+    );
+}
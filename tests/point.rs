@@ -63,6 +63,14 @@ fn test_homogeneous() {
     assert_ulps_eq!(&p, &Point3::from_homogeneous(p.to_homogeneous()));
 }
 
+#[test]
+fn test_extend_truncate() {
+    assert_eq!(Point1::new(1).extend(2), Point2::new(1, 2));
+    assert_eq!(Point2::new(1, 2).extend(3), Point3::new(1, 2, 3));
+    assert_eq!(Point2::new(1, 2).truncate(), Point1::new(1));
+    assert_eq!(Point3::new(1, 2, 3).truncate(), Point2::new(1, 2));
+}
+
 #[test]
 fn test_mul() {
     impl_test_mul!(Point3 { x, y, z }, 2.0f32, Point3::new(2.0f32, 4.0, 6.0));
@@ -0,0 +1,164 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::polygon::{contains_point, medial_axis, offset, signed_area, Join};
+
+fn square() -> Vec<Point2<f64>> {
+    vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(2.0, 0.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(0.0, 2.0),
+    ]
+}
+
+#[test]
+fn test_signed_area() {
+    let mut polygon = square();
+    assert_eq!(signed_area(&polygon), 4.0);
+    polygon.reverse();
+    assert_eq!(signed_area(&polygon), -4.0);
+    assert_eq!(signed_area::<f64>(&[]), 0.0);
+}
+
+#[test]
+fn test_offset_joins() {
+    let miter = offset(&square(), 0.5, Join::Miter(2.0));
+    assert_eq!(
+        miter,
+        vec![
+            Point2::new(-0.5, -0.5),
+            Point2::new(2.5, -0.5),
+            Point2::new(2.5, 2.5),
+            Point2::new(-0.5, 2.5),
+        ]
+    );
+
+    // A right angle needs a limit of at least √2.
+    let bevel = offset(&square(), 0.5, Join::Miter(1.4));
+    assert_eq!(bevel.len(), 8);
+    assert_relative_eq!(signed_area(&bevel), 9.0 - 4.0 * 0.125, epsilon = 1e-12);
+
+    let round = offset(&square(), 0.5, Join::Round(Deg(10.0).into()));
+    assert_eq!(round.len(), 4 * 10);
+    for p in &round {
+        let nearest = Point2::new(p.x.max(0.0).min(2.0), p.y.max(0.0).min(2.0));
+        assert_relative_eq!(p.distance(nearest), 0.5, epsilon = 1e-12);
+    }
+    let exact = 4.0 + 4.0 * 2.0 * 0.5 + ::std::f64::consts::PI * 0.25;
+    assert!(signed_area(&round) < exact && signed_area(&round) > exact - 0.01);
+}
+
+#[test]
+fn test_offset_orientation_and_concave_corners() {
+    // An L shape, clockwise, with a repeated vertex.
+    let l = vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 4.0),
+        Point2::new(2.0, 4.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(4.0, 2.0),
+        Point2::new(4.0, 0.0),
+    ];
+    let shrunk = offset(&l, -0.5, Join::Miter(2.0));
+    assert_eq!(
+        shrunk,
+        vec![
+            Point2::new(0.5, 0.5),
+            Point2::new(0.5, 3.5),
+            Point2::new(1.5, 3.5),
+            Point2::new(1.5, 1.5),
+            Point2::new(3.5, 1.5),
+            Point2::new(3.5, 0.5),
+        ]
+    );
+    assert!(signed_area(&shrunk) < 0.0);
+    // Shrinking rounds the concave corner only.
+    let round = offset(&l, -0.5, Join::Round(Deg(10.0).into()));
+    assert_eq!(round.len(), 5 + 10);
+    assert_relative_eq!(round[3], Point2::new(1.5, 2.0), epsilon = 1e-12);
+    assert_relative_eq!(round[12], Point2::new(2.0, 1.5), epsilon = 1e-12);
+
+    // Growing it rounds the five convex corners, and moves the concave one
+    // diagonally.
+    let grown = offset(&l, 0.5, Join::Round(Deg(45.0).into()));
+    assert_eq!(grown.len(), 5 * 3 + 1);
+    assert!(grown.contains(&Point2::new(2.5, 2.5)));
+    assert!(signed_area(&grown) < 0.0);
+}
+
+#[test]
+fn test_contains_point() {
+    let l = [
+        Point2::new(0.0, 0.0),
+        Point2::new(4.0, 0.0),
+        Point2::new(4.0, 2.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(2.0, 4.0),
+        Point2::new(0.0, 4.0),
+    ];
+    assert!(contains_point(&l, Point2::new(1.0, 3.0)));
+    assert!(contains_point(&l, Point2::new(3.0, 1.0)));
+    assert!(!contains_point(&l, Point2::new(3.0, 3.0)));
+    assert!(!contains_point(&l, Point2::new(-1.0, 1.0)));
+    assert!(!contains_point::<f64>(&[], Point2::new(0.0, 0.0)));
+}
+
+fn segment_distance(p: Point2<f64>, a: Point2<f64>, b: Point2<f64>) -> f64 {
+    let t = ((p - a).dot(b - a) / (b - a).magnitude2())
+        .max(0.0)
+        .min(1.0);
+    p.distance(a + (b - a) * t)
+}
+
+#[test]
+fn test_medial_axis() {
+    let l = [
+        Point2::new(0.0, 0.0),
+        Point2::new(6.0, 0.0),
+        Point2::new(6.0, 2.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(2.0, 6.0),
+        Point2::new(0.0, 6.0),
+    ];
+    let spacing = 0.1;
+    let axis = medial_axis(&l, spacing);
+    assert!(axis.len() > 100);
+    for &(a, b) in &axis {
+        for &p in &[a, b] {
+            assert!(contains_point(&l, p));
+            // The points of the axis are about as close to two edges.
+            let mut distances: Vec<f64> = (0..l.len())
+                .map(|i| segment_distance(p, l[i], l[(i + 1) % l.len()]))
+                .collect();
+            distances.sort_by(|x, y| x.partial_cmp(y).unwrap());
+            assert!(distances[1] - distances[0] < spacing, "{:?}", p);
+        }
+    }
+    // The axis follows the middle of both arms.
+    assert!(axis
+        .iter()
+        .any(|&(a, _)| a.distance(Point2::new(4.5, 1.0)) < spacing));
+    assert!(axis
+        .iter()
+        .any(|&(a, _)| a.distance(Point2::new(1.0, 4.5)) < spacing));
+    assert!(medial_axis::<f64>(&[], spacing).is_empty());
+}
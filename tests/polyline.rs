@@ -0,0 +1,396 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+fn zigzag() -> Polyline2<f64> {
+    Polyline2::new(vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(3.0, 1.0),
+    ])
+}
+
+#[test]
+fn test_length() {
+    assert_eq!(zigzag().length(), 4.0);
+    assert_eq!(Polyline3::<f32>::new(Vec::new()).length(), 0.0);
+}
+
+#[test]
+fn test_point_at() {
+    let line = zigzag();
+    assert_eq!(line.point_at(-1.0), Some(Point2::new(0.0, 0.0)));
+    assert_eq!(line.point_at(0.5), Some(Point2::new(0.5, 0.0)));
+    assert_eq!(line.point_at(1.5), Some(Point2::new(1.0, 0.5)));
+    assert_eq!(line.point_at(3.0), Some(Point2::new(2.0, 1.0)));
+    assert_eq!(line.point_at(10.0), Some(Point2::new(3.0, 1.0)));
+    assert_eq!(Polyline2::<f64>::new(Vec::new()).point_at(1.0), None);
+}
+
+#[test]
+fn test_closest_segment() {
+    let line = zigzag();
+    assert_eq!(
+        line.closest_segment(Point2::new(0.25, -1.0)),
+        Some((0, 0.25))
+    );
+    assert_eq!(line.closest_segment(Point2::new(1.2, 0.5)), Some((1, 0.5)));
+    // The degenerate segment between the repeated vertices is never closer.
+    assert_eq!(line.closest_segment(Point2::new(2.0, 2.0)), Some((3, 0.5)));
+    assert_eq!(line.closest_segment(Point2::new(5.0, 1.0)), Some((3, 1.0)));
+    let single = Polyline2::new(vec![Point2::new(1.0, 2.0f64)]);
+    assert_eq!(single.closest_segment(Point2::new(0.0, 0.0)), None);
+}
+
+#[test]
+fn test_project() {
+    let line = zigzag();
+    assert_eq!(
+        line.project(Point2::new(1.2, 0.5)),
+        Some((Point2::new(1.0, 0.5), 1.5))
+    );
+    assert_eq!(
+        line.project(Point2::new(2.5, 3.0)),
+        Some((Point2::new(2.5, 1.0), 3.5))
+    );
+    assert_eq!(
+        line.project(Point2::new(-1.0, -1.0)),
+        Some((Point2::new(0.0, 0.0), 0.0))
+    );
+    let single = Polyline2::new(vec![Point2::new(1.0, 2.0f64)]);
+    assert_eq!(
+        single.project(Point2::new(0.0, 0.0)),
+        Some((Point2::new(1.0, 2.0), 0.0))
+    );
+    assert_eq!(
+        Polyline2::<f64>::new(Vec::new()).project(Point2::new(0.0, 0.0)),
+        None
+    );
+}
+
+#[test]
+fn test_lookahead() {
+    let line = zigzag();
+    assert_eq!(
+        line.lookahead(Point2::new(0.5, 0.5), 1.0),
+        Some(Point2::new(1.0, 0.5))
+    );
+    assert_eq!(
+        line.lookahead(Point2::new(0.5, 0.5), 10.0),
+        Some(Point2::new(3.0, 1.0))
+    );
+    let path = Polyline3::new(vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(0.0, 0.0, 4.0f64),
+    ]);
+    assert_eq!(
+        path.lookahead(Point3::new(1.0, 1.0, 1.0), 2.0),
+        Some(Point3::new(0.0, 0.0, 3.0))
+    );
+}
+
+#[test]
+fn test_resample() {
+    let line = zigzag();
+    let resampled = line.resample(9);
+    assert_eq!(resampled.points.len(), 9);
+    for (i, &p) in resampled.points.iter().enumerate() {
+        assert_ulps_eq!(p, line.point_at(i as f64 * 0.5).unwrap());
+    }
+
+    assert_eq!(line.resample(1).points, vec![Point2::new(0.0, 0.0)]);
+    assert!(line.resample(0).points.is_empty());
+
+    let single = Polyline3::new(vec![Point3::new(1.0, 2.0, 3.0)]);
+    assert_eq!(
+        single.resample(3).points,
+        vec![Point3::new(1.0, 2.0, 3.0); 3]
+    );
+}
+
+#[test]
+fn test_simplify() {
+    let points: Vec<_> = (0..=20)
+        .map(|i| {
+            let x = i as f64 / 10.0;
+            let noise = if i % 2 == 0 { 0.01 } else { -0.01 };
+            Point3::new(x, (1.0 - x).abs() + noise, 0.0)
+        })
+        .collect();
+    let line = Polyline3::new(points);
+
+    let simplified = line.simplify(0.05);
+    assert_eq!(simplified.points.len(), 3);
+    assert_eq!(simplified.points[0], line.points[0]);
+    assert_eq!(simplified.points[1], line.points[10]);
+    assert_eq!(simplified.points[2], line.points[20]);
+
+    assert_eq!(line.simplify(0.0).points.len(), line.points.len());
+}
+
+fn helix(count: usize) -> Polyline3<f64> {
+    Polyline3::new(
+        (0..count)
+            .map(|i| {
+                let angle = i as f64 * 0.1;
+                Point3::new(angle.cos(), angle.sin(), 0.2 * angle)
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn test_transport_frames_orthonormal() {
+    let path = helix(200);
+    let frames = path.transport_frames();
+    assert_eq!(frames.len(), path.points.len());
+    for (frame, &p) in frames.iter().zip(&path.points) {
+        assert_eq!(frame.position, p);
+        assert_relative_eq!(frame.tangent.magnitude(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(frame.normal.magnitude(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(frame.tangent.dot(frame.normal), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(frame.binormal, frame.tangent.cross(frame.normal));
+        assert_relative_eq!(frame.basis().determinant(), 1.0, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_transport_frames_minimal_twist() {
+    // Between consecutive frames, the normal only turns as much as needed to
+    // stay perpendicular to the tangent, so it never gains a component along
+    // the previous binormal beyond what the tangent change requires.
+    let path = helix(100);
+    let frames = path.transport_frames_from(Vector3::unit_z());
+    for w in frames.windows(2) {
+        let twist = w[1].normal.dot(w[0].binormal);
+        let bend = w[1].tangent.dot(w[0].binormal);
+        assert!(twist.abs() <= bend.abs() + 1e-9);
+    }
+
+    // A straight line keeps its starting normal, projected to be
+    // perpendicular to the line.
+    let line = Polyline3::new(vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(3.0, 3.0, 0.0),
+    ]);
+    let frames = line.transport_frames_from(Vector3::new(1.0, 0.0, 1.0));
+    for frame in frames {
+        assert_relative_eq!(
+            frame.normal,
+            Vector3::new(0.5, -0.5, 1.0).normalize(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            frame.tangent,
+            Vector3::new(1.0, 1.0, 0.0).normalize(),
+            epsilon = 1e-12
+        );
+    }
+}
+
+#[test]
+fn test_transport_frames_degenerate() {
+    assert!(Polyline3::<f64>::new(Vec::new())
+        .transport_frames()
+        .is_empty());
+    let point = Polyline3::new(vec![Point3::new(1.0, 2.0, 3.0); 4]);
+    assert!(point.transport_frames().is_empty());
+}
+
+#[test]
+fn test_curve_frame_matrix() {
+    let frame = CurveFrame {
+        position: Point3::new(1.0, 2.0, 3.0),
+        tangent: Vector3::unit_x(),
+        normal: Vector3::unit_y(),
+        binormal: Vector3::unit_z(),
+    };
+    let m = frame.to_matrix();
+    assert_eq!(
+        m.transform_point(Point3::new(0.0, 0.0, 1.0)),
+        Point3::new(2.0, 2.0, 3.0)
+    );
+    assert_eq!(
+        m.transform_point(Point3::new(1.0, 0.0, 0.0)),
+        Point3::new(1.0, 3.0, 3.0)
+    );
+}
+
+#[test]
+fn test_lathe() {
+    let profile = Polyline2::new(vec![
+        Point2::new(1.0, 0.0),
+        Point2::new(2.0, 1.0),
+        Point2::new(0.0, 2.0),
+    ]);
+    let rings = profile.lathe(Deg(90.0), 3);
+    assert_eq!(rings.len(), 4);
+    for (step, ring) in rings.iter().enumerate() {
+        let rotation = Matrix3::from_angle_y(Deg(30.0 * step as f64));
+        for (&p, &q) in profile.points.iter().zip(ring) {
+            assert_relative_eq!(q, rotation.transform_point(Point3::new(p.x, p.y, 0.0)));
+        }
+    }
+    assert_relative_eq!(rings[3][1], Point3::new(0.0, 1.0, -2.0));
+    assert_eq!(profile.lathe(Rad(1.0), 0).len(), 1);
+}
+
+#[test]
+fn test_extrude() {
+    let path = helix(50);
+    let profile = [
+        Point2::new(0.1, 0.0),
+        Point2::new(0.0, 0.1),
+        Point2::new(-0.1, 0.0),
+    ];
+    let rings = path.extrude(&profile);
+    let frames = path.transport_frames();
+    assert_eq!(rings.len(), frames.len());
+    for (ring, frame) in rings.iter().zip(&frames) {
+        assert_eq!(ring.len(), 3);
+        for &p in ring {
+            // Every ring lies in the plane of its frame, at the profile radius.
+            assert_relative_eq!(
+                (p - frame.position).dot(frame.tangent),
+                0.0,
+                epsilon = 1e-12
+            );
+            assert_relative_eq!(p.distance(frame.position), 0.1, epsilon = 1e-12);
+        }
+        assert_relative_eq!(ring[0], frame.position + frame.normal * 0.1);
+        assert_relative_eq!(ring[1], frame.position + frame.binormal * 0.1);
+    }
+}
+
+#[test]
+fn test_string_pull_straight() {
+    let up = Vector3::unit_z();
+    let start = Point3::new(0.0, 0.0, 0.0f64);
+    let goal = Point3::new(10.0, 0.5, 0.0);
+    let portals: Vec<_> = (1..10)
+        .map(|x| {
+            let x = x as f64;
+            (Point3::new(x, 1.0, 0.0), Point3::new(x, -1.0, 0.0))
+        })
+        .collect();
+    let path = Polyline3::string_pull(start, &portals, goal, up);
+    assert_eq!(path.points, vec![start, goal]);
+    let path = Polyline3::string_pull(start, &[], goal, up);
+    assert_eq!(path.points, vec![start, goal]);
+}
+
+#[test]
+fn test_string_pull_corners() {
+    let up = Vector3::unit_z();
+    // A corridor going right, then up around the corner at (9, 1), then
+    // right again around the corner at (11, 8).
+    let portals = [
+        (Point3::new(5.0, 1.0, 0.0), Point3::new(5.0, -1.0, 0.0)),
+        (Point3::new(9.0, 1.0, 0.0), Point3::new(9.0, -1.0, 0.0)),
+        (Point3::new(9.0, 1.0, 0.0), Point3::new(11.0, 1.0, 0.0)),
+        (Point3::new(9.0, 5.0, 0.0), Point3::new(11.0, 5.0, 0.0)),
+        (Point3::new(9.0, 8.0, 0.0), Point3::new(11.0, 8.0, 0.0)),
+        (Point3::new(11.0, 10.0, 0.0), Point3::new(11.0, 8.0, 0.0)),
+        (Point3::new(15.0, 10.0, 0.0), Point3::new(15.0, 8.0, 0.0)),
+    ];
+    let start = Point3::new(1.0, 0.0, 0.0f64);
+    let goal = Point3::new(20.0, 9.0, 0.0);
+    let path = Polyline3::string_pull(start, &portals, goal, up);
+    assert_eq!(
+        path.points,
+        vec![
+            start,
+            Point3::new(9.0, 1.0, 0.0),
+            Point3::new(11.0, 8.0, 0.0),
+            goal
+        ]
+    );
+
+    // The way back turns around the same corners.
+    let back: Vec<_> = portals.iter().rev().map(|&(l, r)| (r, l)).collect();
+    let path = Polyline3::string_pull(goal, &back, start, up);
+    assert_eq!(
+        path.points,
+        vec![
+            goal,
+            Point3::new(11.0, 8.0, 0.0),
+            Point3::new(9.0, 1.0, 0.0),
+            start
+        ]
+    );
+}
+
+#[test]
+fn test_string_pull_heights() {
+    // Heights along `up` do not change which side the portals are on.
+    let portals = [
+        (Point3::new(1.0, 3.0, 1.0), Point3::new(1.0, -1.0, -1.0)),
+        (Point3::new(2.0, 5.0, 1.0), Point3::new(2.0, 2.0, 2.0)),
+    ];
+    let start = Point3::new(0.0, 0.0, 0.0f64);
+    let goal = Point3::new(3.0, 0.0, 3.0);
+    let path = Polyline3::string_pull(start, &portals, goal, Vector3::unit_z());
+    assert_eq!(path.points, vec![start, Point3::new(2.0, 2.0, 2.0), goal]);
+}
+
+#[test]
+fn test_fit_arcs_smooth() {
+    let points = (0..=64)
+        .map(|i| {
+            let x = i as f64 / 8.0;
+            Point2::new(x, x.sin())
+        })
+        .collect();
+    let polyline = Polyline2::new(points);
+    let arcs = polyline.fit_arcs(1e-2);
+    assert!(arcs.len() < 64);
+    assert_eq!(arcs[0].start, polyline.points[0]);
+    assert_eq!(arcs.last().unwrap().end, polyline.points[64]);
+    for w in arcs.windows(2) {
+        assert_eq!(w[0].end, w[1].start);
+        assert_relative_eq!(w[0].end_tangent(), w[1].start_tangent(), epsilon = 1e-6);
+    }
+    for &p in &polyline.points {
+        let distance = arcs
+            .iter()
+            .map(|arc| arc.distance(p))
+            .fold(::std::f64::INFINITY, f64::min);
+        assert!(distance <= 1e-2);
+    }
+}
+
+#[test]
+fn test_fit_arcs_corners() {
+    assert!(Polyline2::<f64>::new(vec![]).fit_arcs(0.1).is_empty());
+    let square = Polyline2::new(vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(0.0, 1.0f64),
+    ]);
+    let arcs = square.fit_arcs(0.01);
+    assert_eq!(arcs.len(), 3);
+    for (arc, w) in arcs.iter().zip(square.points.windows(2)) {
+        assert_eq!(*arc, Arc2::line(w[0], w[1]));
+    }
+}
@@ -0,0 +1,437 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+fn pose(x: f64, angle: f64) -> Pose3<f64> {
+    Pose3 {
+        scale: 1.0,
+        rot: Quaternion::from_angle_z(Deg(angle)),
+        disp: Vector3::new(x, x * x, 0.0),
+    }
+}
+
+#[test]
+fn test_empty_and_single() {
+    let mut buffer = PoseBuffer::new(4, 1.0);
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.sample(0.0), None);
+    buffer.push(1.0, pose(2.0, 10.0));
+    assert_eq!(buffer.len(), 1);
+    assert_eq!(buffer.sample(0.0), Some(pose(2.0, 10.0)));
+    assert_eq!(buffer.sample(5.0), Some(pose(2.0, 10.0)));
+    buffer.clear();
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_linear_interpolation() {
+    let mut buffer = PoseBuffer::new(8, 0.0);
+    buffer.push(0.0, pose(0.0, 0.0));
+    buffer.push(1.0, pose(1.0, 90.0));
+    buffer.push(2.0, pose(2.0, 0.0));
+
+    let p = buffer.sample(0.5).unwrap();
+    assert_relative_eq!(p.disp, Vector3::new(0.5, 0.5, 0.0), epsilon = 1e-12);
+    assert_relative_eq!(p.rot, Quaternion::from_angle_z(Deg(45.0)), epsilon = 1e-12);
+
+    let p = buffer.sample(1.0).unwrap();
+    assert_relative_eq!(p, pose(1.0, 90.0), epsilon = 1e-12);
+
+    assert_eq!(buffer.sample(-1.0), Some(pose(0.0, 0.0)));
+    // Without extrapolation, the pose stops at the last sample.
+    assert_relative_eq!(buffer.sample(3.0).unwrap(), pose(2.0, 0.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_shortest_rotation() {
+    let mut buffer = PoseBuffer::new(8, 0.0);
+    buffer.push(0.0, pose(0.0, 170.0));
+    let mut flipped = pose(1.0, -170.0);
+    flipped.rot = -flipped.rot;
+    buffer.push(1.0, flipped);
+    let p = buffer.sample(0.5).unwrap();
+    assert!(p
+        .rot
+        .approx_eq_rotation(&Quaternion::from_angle_z(Deg(180.0))));
+}
+
+#[test]
+fn test_hermite_interpolation() {
+    let mut buffer = PoseBuffer::new(8, 0.0);
+    buffer.interpolation = PoseInterpolation::Hermite;
+    for i in 0..5 {
+        let t = i as f64;
+        buffer.push(t, pose(t, 0.0));
+    }
+    // Hermite curves with central differences reproduce the inner parts of
+    // quadratic motion closely, unlike linear interpolation.
+    let p = buffer.sample(2.5).unwrap();
+    assert_relative_eq!(p.disp.x, 2.5, epsilon = 1e-12);
+    assert_relative_eq!(p.disp.y, 6.25, epsilon = 1e-12);
+    // It still goes through the samples.
+    assert_relative_eq!(buffer.sample(3.0).unwrap().disp, pose(3.0, 0.0).disp);
+}
+
+#[test]
+fn test_extrapolation() {
+    let mut buffer = PoseBuffer::new(8, 0.5);
+    buffer.push(0.0, pose(0.0, 0.0));
+    buffer.push(1.0, pose(1.0, 20.0));
+
+    let p = buffer.sample(1.25).unwrap();
+    assert_relative_eq!(p.disp, Vector3::new(1.25, 1.25, 0.0), epsilon = 1e-12);
+    assert_relative_eq!(p.rot, Quaternion::from_angle_z(Deg(25.0)), epsilon = 1e-12);
+
+    let clamped = buffer.sample(10.0).unwrap();
+    assert_relative_eq!(clamped, buffer.sample(1.5).unwrap(), epsilon = 1e-12);
+    assert_relative_eq!(
+        clamped.rot,
+        Quaternion::from_angle_z(Deg(30.0)),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_out_of_order_and_capacity() {
+    let mut buffer = PoseBuffer::new(3, 0.0);
+    buffer.push(2.0, pose(2.0, 0.0));
+    buffer.push(0.0, pose(0.0, 0.0));
+    buffer.push(1.0, pose(1.0, 0.0));
+    assert_relative_eq!(buffer.sample(0.5).unwrap().disp.x, 0.5);
+
+    // Replacing a sample keeps the length.
+    buffer.push(1.0, pose(5.0, 0.0));
+    assert_eq!(buffer.len(), 3);
+
+    // The oldest sample is dropped, and older samples are ignored.
+    buffer.push(3.0, pose(3.0, 0.0));
+    assert_eq!(buffer.len(), 3);
+    assert_eq!(buffer.sample(0.0), Some(pose(5.0, 0.0)));
+    buffer.push(-1.0, pose(-1.0, 0.0));
+    assert_eq!(buffer.sample(-1.0), Some(pose(5.0, 0.0)));
+    assert_eq!(buffer.latest(), Some((3.0, pose(3.0, 0.0))));
+}
+
+#[test]
+fn test_dead_reckoning() {
+    let start = Pose3 {
+        scale: 2.0,
+        rot: Quaternion::from_angle_x(Deg(30.0f64)),
+        disp: Vector3::new(1.0, 2.0, 3.0),
+    };
+    let linear = Vector3::new(0.5, -1.0, 2.0);
+    let angular = Vector3::new(0.0, 0.3, 0.4);
+
+    let p = extrapolate(&start, linear, angular, 2.0, 5.0);
+    assert_eq!(p.scale, 2.0);
+    assert_relative_eq!(p.disp, Vector3::new(2.0, 0.0, 7.0), epsilon = 1e-12);
+    let expected = Quaternion::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), Rad(1.0)) * start.rot;
+    assert_relative_eq!(p.rot, expected, epsilon = 1e-12);
+
+    // Integrating in two steps gives the same pose as in one.
+    let half = extrapolate(&start, linear, angular, 1.0, 5.0);
+    let twice = extrapolate(&half, linear, angular, 1.0, 5.0);
+    assert_relative_eq!(twice, p, epsilon = 1e-12);
+
+    // The time is clamped to the allowed range.
+    assert_eq!(extrapolate(&start, linear, angular, -1.0, 5.0), start);
+    assert_eq!(
+        extrapolate(&start, linear, angular, 9.0, 2.0),
+        extrapolate(&start, linear, angular, 2.0, 2.0)
+    );
+    assert_eq!(
+        extrapolate(&start, linear, Vector3::zero(), 0.0, 1.0),
+        start
+    );
+}
+
+fn rigid(axis: Vector3<f64>, angle: f64, disp: Vector3<f64>) -> Pose3<f64> {
+    Pose3 {
+        scale: 1.0,
+        rot: Quaternion::from_axis_angle(axis.normalize(), Deg(angle)),
+        disp,
+    }
+}
+
+#[test]
+fn test_relative_to() {
+    let a = rigid(
+        Vector3::new(1.0, 2.0, 3.0),
+        40.0,
+        Vector3::new(1.0, -2.0, 0.5),
+    );
+    let b = rigid(
+        Vector3::new(-1.0, 0.0, 1.0),
+        130.0,
+        Vector3::new(0.0, 4.0, 2.0),
+    );
+    let relative = a.relative_to(&b);
+    assert_relative_eq!(b.concat(&relative), a, epsilon = 1e-12);
+    assert_relative_eq!(a.relative_to(&a), Pose3::one(), epsilon = 1e-12);
+
+    // The relative pose maps points from the frame of `a` to that of `b`.
+    let p = Point3::new(0.3, 0.2, -1.0);
+    assert_relative_eq!(
+        relative.transform_point(p),
+        b.inverse_transform_point(a.transform_point(p)).unwrap(),
+        epsilon = 1e-12
+    );
+
+    let scaled = Pose3 { scale: 2.0, ..b };
+    assert_relative_eq!(scaled.concat(&a.relative_to(&scaled)), a, epsilon = 1e-12);
+}
+
+#[test]
+fn test_error() {
+    let measured = rigid(
+        Vector3::new(0.0, 1.0, 1.0),
+        70.0,
+        Vector3::new(3.0, 1.0, -2.0),
+    );
+    assert_eq!(measured.error(&measured), [0.0; 6]);
+
+    // A small local perturbation of the measured pose comes back as the
+    // residual.
+    let axis = Vector3::new(1.0, -2.0, 0.5).normalize();
+    let step = rigid(axis, 3.0, Vector3::new(0.1, -0.2, 0.05));
+    let error = measured.concat(&step).error(&measured);
+    let rotation = axis * 3f64.to_radians();
+    let expected = [0.1, -0.2, 0.05, rotation.x, rotation.y, rotation.z];
+    for (e, x) in error.iter().zip(&expected) {
+        assert_relative_eq!(e, x, epsilon = 1e-12);
+    }
+
+    // The opposite quaternion gives the same rotation, and the same error.
+    let flipped = Pose3 {
+        rot: -measured.concat(&step).rot,
+        ..measured.concat(&step)
+    };
+    for (e, x) in flipped.error(&measured).iter().zip(&expected) {
+        assert_relative_eq!(e, x, epsilon = 1e-12);
+    }
+
+    // Past a half turn, the shorter rotation the other way is used.
+    let far = measured.concat(&rigid(Vector3::unit_z(), 200.0, Vector3::zero()));
+    let error = far.error(&measured);
+    assert_relative_eq!(error[5], (-160f64).to_radians(), epsilon = 1e-12);
+}
+
+#[test]
+fn test_portal_transform() {
+    let a = Pose3::from_translation(Vector3::new(1.0, 2.0, 3.0)).with_rotation(
+        Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalize(), Deg(30.0)),
+    );
+    let b = Pose3::from_translation(Vector3::new(-4.0, 0.0, 5.0))
+        .with_rotation(Quaternion::from_angle_y(Deg(-120.0)));
+    let m = portal_transform(&a, &b);
+
+    // A point in front of one portal ends up behind the other, mirrored
+    // left to right, and the way back undoes the transformation.
+    let local = Vector3::new(0.5, 0.25, 2.0);
+    let p = m.transform_point(a.transform_point(Point3::from_vec(local)));
+    let expected = b.transform_point(Point3::new(-0.5, 0.25, -2.0));
+    assert_relative_eq!(p, expected, epsilon = 1e-12);
+    assert_relative_eq!(
+        portal_transform(&b, &a) * m,
+        Matrix4::identity(),
+        epsilon = 1e-12
+    );
+
+    // The transformation is rigid, so it does not flip the handedness.
+    assert_relative_eq!(m.determinant(), 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_smooth_damp() {
+    let target = Vector2::new(10.0, 0.0f64);
+    let mut position = Vector2::new(0.0, 0.0);
+    let mut velocity = Vector2::new(0.0, 0.0);
+    let mut previous = position;
+    for _ in 0..200 {
+        position = smooth_damp(position, target, &mut velocity, 0.5, 100.0, 0.02);
+        // The motion is monotonic, and never overshoots.
+        assert!(position.x >= previous.x && position.x <= target.x);
+        previous = position;
+    }
+    assert_relative_eq!(position, target, epsilon = 1e-3);
+
+    // The speed is limited.
+    let mut position = Vector2::new(0.0, 0.0);
+    let mut velocity = Vector2::new(0.0, 0.0);
+    for _ in 0..10 {
+        let next = smooth_damp(position, target, &mut velocity, 0.5, 2.0, 0.1);
+        assert!((next - position).magnitude() <= 0.2 + 1e-12);
+        position = next;
+    }
+
+    // Large time steps land on the target rather than past it.
+    let mut velocity = Vector2::new(0.0, 0.0);
+    let next = smooth_damp(
+        Vector2::new(0.0, 0.0),
+        target,
+        &mut velocity,
+        0.1,
+        1e3,
+        10.0,
+    );
+    assert!(next.x <= target.x);
+}
+
+#[test]
+fn test_follow_angular_speed() {
+    let params = FollowParams::new(0.1f64, 10.0, Deg(30.0).into());
+    let target = pose(0.0, 135.0);
+    let mut camera = pose(0.0, 0.0);
+    let mut velocity = Vector3::zero();
+    let dt = 0.1;
+    for _ in 0..30 {
+        let next = follow(&camera, &mut velocity, &target, &params, dt);
+        let turned = Rad::acos(next.rot.dot(camera.rot).abs().min(1.0)) * 2.0;
+        assert_relative_eq!(Deg::from(turned).0, 3.0, epsilon = 1e-6);
+        camera = next;
+    }
+    // 30 steps of 3 degrees, towards the target.
+    let remaining = Rad::acos(camera.rot.dot(target.rot).abs()) * 2.0;
+    assert_relative_eq!(Deg::from(remaining).0, 45.0, epsilon = 1e-6);
+    assert_eq!(camera.scale, 1.0);
+}
+
+#[test]
+fn test_follow_shortest_path() {
+    let params = FollowParams::new(0.2f64, 10.0, Deg(360.0).into());
+    let target = pose(3.0, 170.0);
+    let flipped = Pose3 {
+        rot: -target.rot,
+        ..target
+    };
+    let mut camera = pose(0.0, 0.0);
+    let mut velocity = Vector3::zero();
+    let mut other = camera;
+    let mut other_velocity = velocity;
+    for _ in 0..10 {
+        camera = follow(&camera, &mut velocity, &target, &params, 0.05);
+        other = follow(&other, &mut other_velocity, &flipped, &params, 0.05);
+        assert!(camera.rot.approx_eq_rotation(&other.rot));
+    }
+}
+
+#[test]
+fn test_shake() {
+    let max_offset = Vector3::new(0.5, 0.25, 0.1);
+    let max_angles = Vector3::new(0.1, 0.2, 0.3);
+    let mut shake = Shake::new(11, max_offset, max_angles, 10.0f64);
+
+    // Without trauma, there is no shake.
+    assert_eq!(shake.update(0.1), Pose3::identity());
+
+    shake.add_trauma(0.5);
+    shake.add_trauma(0.75);
+    assert_eq!(shake.trauma, 1.0);
+    let mut moved = false;
+    let mut previous = shake.offset();
+    for _ in 0..100 {
+        let offset = shake.update(0.005);
+        let limit = shake.trauma * shake.trauma;
+        let angles = Euler::from(offset.rot);
+        assert!(offset.disp.x.abs() <= max_offset.x * limit);
+        assert!(offset.disp.y.abs() <= max_offset.y * limit);
+        assert!(offset.disp.z.abs() <= max_offset.z * limit);
+        assert!(angles.x.0.abs() <= max_angles.x * limit + 1e-12);
+        assert!(angles.y.0.abs() <= max_angles.y * limit + 1e-12);
+        assert!(angles.z.0.abs() <= max_angles.z * limit + 1e-12);
+        // The offsets change smoothly.
+        assert!((offset.disp - previous.disp).magnitude() < 0.1);
+        moved |= offset.disp.magnitude() > 0.01;
+        previous = offset;
+    }
+    assert!(moved);
+    assert_relative_eq!(shake.trauma, 0.5, epsilon = 1e-12);
+
+    // The same seed shakes the same way.
+    let mut other = Shake::new(11, max_offset, max_angles, 10.0f64);
+    other.trauma = shake.trauma;
+    other.time = shake.time;
+    assert_eq!(other.offset(), shake.offset());
+}
+
+#[test]
+fn test_pose2_heading() {
+    let pose = Pose2::from_heading(Point2::new(1.0, 2.0), Deg(135.0f64));
+    assert_eq!(pose.position(), Point2::new(1.0, 2.0));
+    assert_relative_eq!(pose.heading(), Deg(135.0).into(), epsilon = 1e-12);
+    let ahead = pose.transform_point(Point2::new(2f64.sqrt(), 0.0));
+    assert_relative_eq!(ahead, Point2::new(0.0, 3.0), epsilon = 1e-12);
+    assert_relative_eq!(
+        Pose2::from_heading(Point2::new(0.0, 0.0), Deg(270.0f64)).heading(),
+        Rad(-std::f64::consts::FRAC_PI_2),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_kinematic_step_limits() {
+    let params = KinematicParams::new(4.0, 8.0, Deg(90.0).into(), Deg(180.0).into());
+    assert_relative_eq!(params.slowing_radius, 1.0);
+    assert_relative_eq!(params.slowing_angle, Rad(std::f64::consts::PI / 8.0));
+
+    let target = Pose3::from_translation(Vector3::new(20.0, 0.0, 0.0))
+        .with_rotation(Quaternion::from_angle_z(Deg(170.0)));
+    let mut agent = KinematicState::at_rest(Pose3::identity());
+    let dt = 1.0 / 30.0;
+    for _ in 0..300 {
+        let next = kinematic_step(&agent, &target, Vector3::zero(), &params, dt);
+        assert!(next.velocity.magnitude() <= 4.0 + 1e-12);
+        assert!((next.velocity - agent.velocity).magnitude() <= 8.0 * dt + 1e-12);
+        assert!(next.angular_velocity.magnitude() <= std::f64::consts::FRAC_PI_2 + 1e-12);
+        assert!(
+            (next.angular_velocity - agent.angular_velocity).magnitude()
+                <= std::f64::consts::PI * dt + 1e-12
+        );
+        agent = next;
+    }
+    assert_relative_eq!(agent.pose.disp, target.disp, epsilon = 1e-3);
+    assert!(agent.velocity.magnitude() < 1e-3);
+    assert!(agent.pose.rot.dot(target.rot).abs() > 1.0 - 1e-6);
+    assert!(agent.angular_velocity.magnitude() < 1e-3);
+}
+
+#[test]
+fn test_kinematic_step_matches_velocity() {
+    let params = KinematicParams::new(10.0, 20.0, Deg(180.0).into(), Deg(360.0).into());
+    let velocity = Vector3::new(3.0, 0.0, -1.0);
+    let mut target = Pose3::from_translation(Vector3::new(5.0, 2.0, 0.0));
+    let mut agent = KinematicState::at_rest(Pose3::identity());
+    let dt = 1.0 / 60.0;
+    for _ in 0..600 {
+        agent = kinematic_step(&agent, &target, velocity, &params, dt);
+        target.disp += velocity * dt;
+    }
+    // The agent keeps up with the moving target.
+    assert_relative_eq!(agent.velocity, velocity, epsilon = 1e-3);
+    assert!((agent.pose.disp - target.disp).magnitude() < 0.1);
+
+    // Long steps do not overshoot.
+    let mut agent = KinematicState::at_rest(Pose3::identity());
+    let target = Pose3::from_translation(Vector3::new(1.0, 0.0, 0.0));
+    for _ in 0..20 {
+        agent = kinematic_step(&agent, &target, Vector3::zero(), &params, 0.5);
+        assert!(agent.pose.disp.x <= 1.0 + 1e-12);
+    }
+    assert_relative_eq!(agent.pose.disp, target.disp, epsilon = 1e-3);
+}
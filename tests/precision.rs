@@ -0,0 +1,112 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+
+use cgmath::precision::*;
+use cgmath::*;
+
+#[test]
+fn test_two_sum_is_exact() {
+    let (s, e) = two_sum(1.0e16f64, 1.0);
+    assert_eq!(s, 1.0e16);
+    assert_eq!(e, 1.0);
+    let (p, e) = two_product(1.0 + std::f64::EPSILON, 1.0 - std::f64::EPSILON);
+    assert_eq!(p, 1.0);
+    assert_eq!(e, -std::f64::EPSILON * std::f64::EPSILON);
+}
+
+#[test]
+fn test_difference_of_products() {
+    let a = 1.0 + std::f64::EPSILON;
+    // `a * a - 1` rounds `a * a` first and loses the `EPSILON^2` term.
+    assert_eq!(
+        difference_of_products(a, a, 1.0, 1.0),
+        2.0 * std::f64::EPSILON + std::f64::EPSILON * std::f64::EPSILON
+    );
+}
+
+#[test]
+fn test_stable_quadratic() {
+    assert_eq!(stable_quadratic(1.0f64, -3.0, 2.0), Some((1.0, 2.0)));
+    assert_eq!(stable_quadratic(-1.0f64, 3.0, -2.0), Some((1.0, 2.0)));
+    assert_eq!(stable_quadratic(1.0f64, 0.0, 1.0), None);
+    assert_eq!(stable_quadratic(1.0f64, 2.0, 1.0), Some((-1.0, -1.0)));
+    assert_eq!(stable_quadratic(1.0f64, 0.0, 0.0), Some((0.0, 0.0)));
+    assert_eq!(stable_quadratic(0.0f64, 2.0, -1.0), Some((0.5, 0.5)));
+    assert_eq!(stable_quadratic(0.0f64, 0.0, 1.0), None);
+}
+
+#[test]
+fn test_stable_quadratic_cancellation() {
+    // The naive formula rounds the root close to zero to `0.0`.
+    let (large, small) = stable_quadratic(1.0f32, 1.0e5, 1.0).unwrap();
+    assert!((small + 1.0e-5).abs() < 1.0e-11);
+    assert!((large + 1.0e5).abs() < 1.0e-2);
+}
+
+#[test]
+fn test_compensated_dot() {
+    let a = Vector4::new(1.0e20f64, 3.0, -1.0e20, 0.5);
+    let b = Vector4::new(1.0, 1.0, 1.0, 2.0);
+    assert_eq!(compensated_dot(a, b), 4.0);
+    assert_eq!(
+        compensated_dot(Vector2::new(3.0f32, 4.0), Vector2::new(3.0, 4.0)),
+        25.0
+    );
+}
+
+#[test]
+fn test_compensated_magnitude() {
+    assert_eq!(compensated_magnitude(Vector3::new(2.0f64, 3.0, 6.0)), 7.0);
+}
+
+#[test]
+fn test_kahan_sum_drift() {
+    let mut kahan = KahanSum::new();
+    let mut naive = Vector3::new(0.0f32, 0.0, 0.0);
+    let v = Vector3::new(0.1f32, 0.01, 1.0e-3);
+    for _ in 0..100_000 {
+        kahan.add(v);
+        naive += v;
+    }
+    let exact = Vector3::new(10000.0f64, 1000.0, 100.0);
+    let error = |s: Vector3<f32>| (s.cast::<f64>().unwrap() - exact).magnitude();
+    assert!(error(kahan.sum()) < 1.0e-3);
+    assert!(error(naive) > 1.0e-1);
+    assert_eq!(kahan.count(), 100_000);
+}
+
+#[test]
+fn test_kahan_sum_collect() {
+    let sum: KahanSum<Vector2<f64>> = (1..5).map(|i| Vector2::new(i as f64, 1.0)).collect();
+    assert_eq!(sum.sum(), Vector2::new(10.0, 4.0));
+    assert_eq!(sum.mean(), Some(Vector2::new(2.5, 1.0)));
+    assert_eq!(KahanSum::<Vector2<f64>>::default().mean(), None);
+}
+
+#[test]
+fn test_compensated_centroid() {
+    let points = vec![
+        Point3::new(1.0e8f64, 0.0, 1.0),
+        Point3::new(1.0e8 + 2.0, 4.0, 1.0),
+        Point3::new(1.0e8 + 4.0, 2.0, 1.0),
+    ];
+    assert_eq!(
+        compensated_centroid(points),
+        Some(Point3::new(1.0e8 + 2.0, 2.0, 1.0))
+    );
+    assert_eq!(compensated_centroid(Vec::<Point2<f32>>::new()), None);
+}
@@ -0,0 +1,160 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+
+use cgmath::predicates::*;
+use cgmath::{Point2, Point3};
+
+fn next_up(x: f64) -> f64 {
+    f64::from_bits(x.to_bits() + 1)
+}
+
+fn next_down(x: f64) -> f64 {
+    f64::from_bits(x.to_bits() - 1)
+}
+
+fn sign(x: f64) -> i32 {
+    if x > 0.0 {
+        1
+    } else if x < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+#[test]
+fn test_orient2d() {
+    let a = Point2::new(0.0, 0.0);
+    let b = Point2::new(1.0, 0.0);
+    let c = Point2::new(0.0, 1.0);
+    assert_eq!(orient2d(a, b, c), 1.0);
+    assert_eq!(orient2d(a, c, b), -1.0);
+    assert_eq!(orient2d(a, b, Point2::new(2.0, 0.0)), 0.0);
+}
+
+#[test]
+fn test_orient2d_near_degenerate() {
+    // The sign of the determinant is the sign of `j - i`, which plain floating
+    // point evaluation gets wrong for many of these points.
+    let b = Point2::new(12.0, 12.0);
+    let c = Point2::new(24.0, 24.0);
+    let eps = 0.5 * std::f64::EPSILON;
+    for i in 0..64 {
+        for j in 0..64 {
+            let a = Point2::new(0.5 + i as f64 * eps, 0.5 + j as f64 * eps);
+            assert_eq!(sign(orient2d(a, b, c)), sign((j - i) as f64), "{} {}", i, j);
+        }
+    }
+}
+
+#[test]
+fn test_incircle() {
+    let o = 1.0e6;
+    let a = Point2::new(o + 1.0, o);
+    let b = Point2::new(o, o + 1.0);
+    let c = Point2::new(o - 1.0, o);
+    assert_eq!(incircle(a, b, c, Point2::new(o, o - 1.0)), 0.0);
+    assert!(incircle(a, b, c, Point2::new(o, next_up(o - 1.0))) > 0.0);
+    assert!(incircle(a, b, c, Point2::new(o, next_down(o - 1.0))) < 0.0);
+    assert!(incircle(a, b, c, Point2::new(o, o)) > 0.0);
+    assert!(incircle(a, c, b, Point2::new(o, o)) < 0.0);
+}
+
+#[test]
+fn test_orient3d() {
+    let a = Point3::new(0.0, 0.0, 0.0);
+    let b = Point3::new(1.0, 0.0, 0.0);
+    let c = Point3::new(0.0, 1.0, 0.0);
+    assert_eq!(orient3d(a, b, c, Point3::new(0.0, 0.0, -1.0)), 1.0);
+    assert_eq!(orient3d(a, b, c, Point3::new(0.0, 0.0, 1.0)), -1.0);
+    assert_eq!(orient3d(a, b, c, Point3::new(5.0, -3.0, 0.0)), 0.0);
+}
+
+#[test]
+fn test_orient3d_near_degenerate() {
+    // These points are close to the plane `x = y`, and the sign of the
+    // determinant is the sign of `j - i`.
+    let a = Point3::new(12.0, 12.0, 0.0);
+    let b = Point3::new(24.0, 24.0, 0.0);
+    let c = Point3::new(0.0, 0.0, 1.0);
+    let eps = 0.5 * std::f64::EPSILON;
+    for i in 0..32 {
+        for j in 0..32 {
+            let d = Point3::new(0.5 + i as f64 * eps, 0.5 + j as f64 * eps, 0.25);
+            assert_eq!(
+                sign(orient3d(a, b, c, d)),
+                sign((j - i) as f64),
+                "{} {}",
+                i,
+                j
+            );
+        }
+    }
+}
+
+#[test]
+fn test_insphere() {
+    let o = 1.0e6;
+    let a = Point3::new(o + 1.0, o, o);
+    let b = Point3::new(o, o + 1.0, o);
+    let c = Point3::new(o, o, o + 1.0);
+    let d = Point3::new(o - 1.0, o, o);
+    let (a, b) = if orient3d(a, b, c, d) > 0.0 {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    assert!(orient3d(a, b, c, d) > 0.0);
+
+    assert_eq!(insphere(a, b, c, d, Point3::new(o, o - 1.0, o)), 0.0);
+    assert!(insphere(a, b, c, d, Point3::new(o, next_up(o - 1.0), o)) > 0.0);
+    assert!(insphere(a, b, c, d, Point3::new(o, next_down(o - 1.0), o)) < 0.0);
+    assert!(insphere(a, b, c, d, Point3::new(o, o, o)) > 0.0);
+    assert!(insphere(b, a, c, d, Point3::new(o, o, o)) < 0.0);
+}
+
+#[test]
+fn test_in_triangle() {
+    let a = Point2::new(0.0, 0.0f32);
+    let b = Point2::new(4.0, 0.0);
+    let c = Point2::new(0.0, 4.0);
+    assert!(in_triangle(a, b, c, Point2::new(1.0, 1.0)));
+    assert!(in_triangle(c, b, a, Point2::new(1.0, 1.0)));
+    assert!(in_triangle(a, b, c, Point2::new(2.0, 2.0)));
+    assert!(in_triangle(a, b, c, a));
+    assert!(!in_triangle(a, b, c, Point2::new(2.5, 2.0)));
+    assert!(!in_triangle(
+        a,
+        b,
+        Point2::new(8.0, 0.0),
+        Point2::new(1.0, 0.0)
+    ));
+}
+
+#[test]
+fn test_in_tetrahedron() {
+    let a = Point3::new(0.0, 0.0, 0.0);
+    let b = Point3::new(1.0, 0.0, 0.0);
+    let c = Point3::new(0.0, 1.0, 0.0);
+    let d = Point3::new(0.0, 0.0, 1.0);
+    assert!(in_tetrahedron(a, b, c, d, Point3::new(0.25, 0.25, 0.25)));
+    assert!(in_tetrahedron(b, a, c, d, Point3::new(0.25, 0.25, 0.25)));
+    assert!(in_tetrahedron(a, b, c, d, Point3::new(0.5, 0.5, 0.0)));
+    assert!(in_tetrahedron(a, b, c, d, d));
+    assert!(!in_tetrahedron(a, b, c, d, Point3::new(0.5, 0.5, 0.1)));
+    assert!(!in_tetrahedron(a, b, c, Point3::new(1.0, 1.0, 0.0), a));
+}
@@ -15,7 +15,7 @@
 
 extern crate cgmath;
 
-use cgmath::{ortho, Matrix4, Vector4};
+use cgmath::*;
 
 #[test]
 fn test_ortho_scale() {
@@ -66,3 +66,702 @@ fn test_ortho_translate() {
     let orig = o * vec_orig;
     assert_eq!(orig, Vector4::new(1., 1., 1., 1.));
 }
+
+fn sphere_samples(center: Point3<f64>, radius: f64) -> Vec<Point3<f64>> {
+    let mut samples = Vec::new();
+    for i in 0..=64 {
+        let theta = i as f64 * std::f64::consts::PI / 64.0;
+        for j in 0..128 {
+            let phi = j as f64 * std::f64::consts::PI / 64.0;
+            let v = Vector3::new(
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            );
+            samples.push(center + v * radius);
+        }
+    }
+    samples
+}
+
+#[test]
+fn test_sphere_screen_bounds_perspective() {
+    let projection = perspective(Deg(70.0), 1.5, 0.1, 100.0);
+    for &(center, radius) in &[
+        (Point3::new(0.0, 0.0, -10.0), 1.0),
+        (Point3::new(3.0, -2.0, -6.0), 0.5),
+        (Point3::new(-1.0, 1.5, -2.0), 1.2),
+    ] {
+        let bounds = sphere_screen_bounds(center, radius, &projection).unwrap();
+        let projected: Vec<_> = sphere_samples(center, radius)
+            .iter()
+            .map(|&p| {
+                let c = projection * p.to_homogeneous();
+                let clamp = |x: f64| x.max(-1.0).min(1.0);
+                Point2::new(clamp(c.x / c.w), clamp(c.y / c.w))
+            })
+            .collect();
+        let expected = Aabb2::from_points(projected).unwrap();
+        assert!(bounds.min.x <= expected.min.x + 1e-12 && bounds.max.x >= expected.max.x - 1e-12);
+        assert!(bounds.min.y <= expected.min.y + 1e-12 && bounds.max.y >= expected.max.y - 1e-12);
+        assert!((bounds.dim() - expected.dim()).magnitude() < 1e-3);
+    }
+}
+
+#[test]
+fn test_sphere_screen_bounds_orthographic() {
+    let projection = ortho(-4.0, 4.0, -2.0, 2.0, 0.1, 10.0);
+    let bounds = sphere_screen_bounds(Point3::new(1.0, 0.0, -5.0), 1.0, &projection).unwrap();
+    assert_eq!(bounds.min, Point2::new(0.0, -0.5));
+    assert_eq!(bounds.max, Point2::new(0.5, 0.5));
+}
+
+#[test]
+fn test_sphere_screen_bounds_clipping() {
+    let projection = perspective(Deg(90.0), 1.0, 0.1, 100.0);
+    let screen = Aabb2::new(Point2::new(-1.0, -1.0), Point2::new(1.0, 1.0));
+
+    // Behind the camera, containing it, or reaching behind it.
+    assert_eq!(
+        sphere_screen_bounds(Point3::new(0.0, 0.0, 2.0), 1.0, &projection),
+        None
+    );
+    assert_eq!(
+        sphere_screen_bounds(Point3::new(0.0, 0.0, -0.5), 1.0, &projection),
+        Some(screen)
+    );
+    assert_eq!(
+        sphere_screen_bounds(Point3::new(5.0, 0.0, -0.5), 1.0, &projection),
+        Some(screen)
+    );
+
+    // Off the side of the screen, and partially on it.
+    assert_eq!(
+        sphere_screen_bounds(Point3::new(30.0, 0.0, -10.0), 1.0, &projection),
+        None
+    );
+    let partial = sphere_screen_bounds(Point3::new(10.0, 0.0, -10.0), 1.0, &projection).unwrap();
+    assert_eq!(partial.max.x, 1.0);
+    assert!(partial.min.x > 0.8 && partial.min.x < 1.0);
+}
+
+#[test]
+fn test_sphere_solid_angle() {
+    let eye = Point3::new(1.0, 2.0, 3.0);
+    let pi = std::f64::consts::PI;
+
+    assert_eq!(sphere_solid_angle(eye, eye, 1.0), 4.0 * pi);
+    assert_eq!(
+        sphere_solid_angle(eye, eye + Vector3::new(0.5, 0.0, 0.0), 1.0),
+        4.0 * pi
+    );
+
+    // A sphere seen from its surface covers half of the view.
+    let touching = sphere_solid_angle(eye, eye + Vector3::new(0.0, 1.0, 0.0), 1.0);
+    assert_relative_eq!(touching, 2.0 * pi, epsilon = 1e-12);
+
+    // Far away, the sphere looks like a disc of area pi r^2 at distance d.
+    let far = sphere_solid_angle(eye, eye + Vector3::new(0.0, 0.0, -1000.0), 2.0);
+    assert_relative_eq!(far, pi * 4.0 / 1.0e6, max_relative = 1e-5);
+}
+
+#[test]
+fn test_polygon_solid_angle() {
+    let pi = std::f64::consts::PI;
+    let corners = Aabb3::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0)).corners();
+    // The faces of the cube, wound counter-clockwise as seen from outside.
+    let faces = [
+        [0, 2, 3, 1],
+        [4, 5, 7, 6],
+        [0, 1, 5, 4],
+        [2, 6, 7, 3],
+        [0, 4, 6, 2],
+        [1, 3, 7, 5],
+    ];
+    let inside = Point3::new(0.2, -0.3, 0.1);
+    let outside = Point3::new(3.0, 2.0, -5.0);
+    let mut total_inside = 0.0;
+    let mut total_outside = 0.0;
+    for face in faces.iter() {
+        let polygon: Vec<_> = face.iter().map(|&i| corners[i]).collect();
+        total_inside += polygon_solid_angle(inside, &polygon);
+        total_outside += polygon_solid_angle(outside, &polygon);
+    }
+    assert_relative_eq!(total_inside, -4.0 * pi, epsilon = 1e-12);
+    assert_relative_eq!(total_outside, 0.0, epsilon = 1e-12);
+
+    // Reversing the winding flips the sign, and degenerate input is empty.
+    let eye = Point3::new(0.0, 0.0, 0.0);
+    let triangle = [
+        Point3::new(0.0, 0.0, -1.0),
+        Point3::new(1.0, 0.0, -1.0),
+        Point3::new(0.0, 1.0, -1.0),
+    ];
+    let reversed = [triangle[2], triangle[1], triangle[0]];
+    let omega = polygon_solid_angle(eye, &triangle);
+    assert!(omega > 0.0);
+    assert_relative_eq!(polygon_solid_angle(eye, &reversed), -omega, epsilon = 1e-12);
+    assert_eq!(polygon_solid_angle(eye, &triangle[..2]), 0.0);
+
+    // A concave polygon measures the same as its convex pieces.
+    let l_shape = [
+        Point3::new(-1.0, -1.0, -1.0),
+        Point3::new(2.0, -1.0, -1.0),
+        Point3::new(2.0, 0.0, -1.0),
+        Point3::new(0.0, 0.0, -1.0),
+        Point3::new(0.0, 2.0, -1.0),
+        Point3::new(-1.0, 2.0, -1.0),
+    ];
+    let bottom = [
+        l_shape[0],
+        l_shape[1],
+        l_shape[2],
+        Point3::new(-1.0, 0.0, -1.0),
+    ];
+    let left = [
+        Point3::new(-1.0, 0.0, -1.0),
+        l_shape[3],
+        l_shape[4],
+        l_shape[5],
+    ];
+    let eye = Point3::new(1.5, 1.5, 0.0);
+    assert_relative_eq!(
+        polygon_solid_angle(eye, &l_shape),
+        polygon_solid_angle(eye, &bottom) + polygon_solid_angle(eye, &left),
+        epsilon = 1e-12
+    );
+}
+
+fn assert_frustum_matches_matrix<P: Projection<f64>>(projection: P) {
+    let exact = projection.to_frustum().to_planes();
+    let extracted = Frustum::from_matrix4(projection.to_matrix4())
+        .unwrap()
+        .to_planes();
+    for (a, b) in exact.iter().zip(extracted.iter()) {
+        assert_relative_eq!(a, b, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_projection_frusta() {
+    let fov = PerspectiveFov {
+        fovy: Deg(70.0).into(),
+        aspect: 16.0 / 9.0,
+        near: 0.5,
+        far: 200.0,
+    };
+    let persp = Perspective {
+        left: -0.2,
+        right: 0.6,
+        bottom: -0.3,
+        top: 0.1,
+        near: 0.25,
+        far: 40.0,
+    };
+    let ortho = Ortho {
+        left: -3.0,
+        right: 5.0,
+        bottom: -1.0,
+        top: 2.0,
+        near: 1.0,
+        far: 9.0,
+    };
+    assert_frustum_matches_matrix(fov);
+    assert_frustum_matches_matrix(persp);
+    assert_frustum_matches_matrix(ortho);
+
+    assert!(fov.is_perspective() && persp.is_perspective());
+    assert!(!ortho.is_perspective());
+    assert_eq!((fov.near(), fov.far()), (0.5, 200.0));
+    assert_eq!((persp.near(), persp.far()), (0.25, 40.0));
+    assert_eq!((ortho.near(), ortho.far()), (1.0, 9.0));
+    assert_eq!(ortho.to_matrix4(), Matrix4::from(ortho));
+}
+
+#[test]
+fn test_fov_conversions() {
+    let vfov = Rad::from(Deg(60.0f64));
+    let aspect = 16.0 / 9.0;
+
+    let hfov = horizontal_fov(vfov, aspect);
+    assert_relative_eq!(
+        Rad::tan(hfov / 2.0),
+        Rad::tan(vfov / 2.0) * aspect,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(vertical_fov(hfov, aspect), vfov, epsilon = 1e-12);
+
+    let dfov = diagonal_fov(vfov, aspect);
+    assert!(dfov > hfov);
+    assert_relative_eq!(
+        vertical_fov_from_diagonal(dfov, aspect),
+        vfov,
+        epsilon = 1e-12
+    );
+
+    // A square image has the same horizontal and vertical fields of view.
+    assert_relative_eq!(
+        horizontal_fov(Deg(75.0), 1.0),
+        Deg(75.0f64).into(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_focal_length_conversions() {
+    // A 50 mm lens across a 36 mm wide sensor.
+    let hfov = fov_from_focal_length(50.0f64, 36.0);
+    assert_relative_eq!(hfov, Rad(2.0 * (18.0f64 / 50.0).atan()), epsilon = 1e-12);
+    assert_relative_eq!(focal_length_from_fov(hfov, 36.0), 50.0, epsilon = 1e-12);
+
+    // On a full-frame sensor, the 35mm equivalent is the focal length itself.
+    let vfov = vertical_fov_from_35mm(35.0f64, 1.5);
+    assert_relative_eq!(vfov, fov_from_focal_length(35.0, 24.0), epsilon = 1e-12);
+    assert_relative_eq!(
+        horizontal_fov(vfov, 1.5),
+        fov_from_focal_length(35.0, 36.0),
+        epsilon = 1e-12
+    );
+
+    for &aspect in &[1.0, 4.0 / 3.0, 16.0 / 9.0] {
+        let vfov = vertical_fov_from_35mm(24.0, aspect);
+        assert_relative_eq!(focal_length_35mm(vfov, aspect), 24.0, epsilon = 1e-10);
+    }
+}
+
+fn assert_slice_shares_sides<P: Projection<f64>>(projection: P) {
+    let whole = projection.to_frustum();
+    let slice = projection.slice_frustum(2.0, 6.0);
+    assert_relative_eq!(whole.left, slice.left, epsilon = 1e-12);
+    assert_relative_eq!(whole.right, slice.right, epsilon = 1e-12);
+    assert_relative_eq!(whole.bottom, slice.bottom, epsilon = 1e-12);
+    assert_relative_eq!(whole.top, slice.top, epsilon = 1e-12);
+    assert_relative_eq!(slice.near.distance(Point3::new(0.0, 0.0, -2.0)), 0.0);
+    assert_relative_eq!(slice.far.distance(Point3::new(0.0, 0.0, -6.0)), 0.0);
+
+    let m = projection.slice_matrix4(2.0, 6.0);
+    assert_eq!(m, projection.slice(2.0, 6.0).to_matrix4());
+    let far = m * Vector4::new(0.0, 0.0, -6.0, 1.0);
+    assert_relative_eq!(far.z / far.w, 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_projection_slices() {
+    assert_slice_shares_sides(PerspectiveFov {
+        fovy: Deg(50.0).into(),
+        aspect: 1.25,
+        near: 0.5,
+        far: 100.0,
+    });
+    assert_slice_shares_sides(Perspective {
+        left: -0.2,
+        right: 0.6,
+        bottom: -0.3,
+        top: 0.1,
+        near: 0.25,
+        far: 40.0,
+    });
+    assert_slice_shares_sides(Ortho {
+        left: -3.0,
+        right: 5.0,
+        bottom: -1.0,
+        top: 2.0,
+        near: 1.0,
+        far: 9.0,
+    });
+}
+
+#[test]
+fn test_texture_bias_matrices() {
+    for &depth in &[DepthRange::NegativeOneToOne, DepthRange::ZeroToOne] {
+        for &flip_y in &[false, true] {
+            let forward: Matrix4<f64> = ndc_to_texture(depth, flip_y);
+            let back = texture_to_ndc(depth, flip_y);
+            assert_ulps_eq!(forward * back, Matrix4::identity());
+            assert_ulps_eq!(forward.invert().unwrap(), back);
+        }
+    }
+
+    // A shadow map lookup: project a point with the light, then bias it.
+    let light = perspective(Deg(90.0), 1.0, 1.0, 10.0);
+    let bias = ndc_to_texture(DepthRange::NegativeOneToOne, false);
+    let p = (bias * light).transform_point(Point3::new(0.0, 0.0, -10.0f64));
+    assert_relative_eq!(p, Point3::new(0.5, 0.5, 1.0), epsilon = 1e-12);
+    let p = (bias * light).transform_point(Point3::new(-1.0, 1.0, -1.0f64));
+    assert_relative_eq!(p, Point3::new(0.0, 1.0, 0.0), epsilon = 1e-12);
+
+    let flipped = ndc_to_texture(DepthRange::NegativeOneToOne, true);
+    let p = (flipped * light).transform_point(Point3::new(-1.0, 1.0, -1.0f64));
+    assert_relative_eq!(p, Point3::new(0.0, 0.0, 0.0), epsilon = 1e-12);
+}
+
+fn skewed_camera() -> PinholeCamera<f64> {
+    PinholeCamera {
+        focal: Vector2::new(1200.0, 1100.0),
+        principal_point: Point2::new(650.0, 370.0),
+        skew: 3.5,
+    }
+}
+
+#[test]
+fn test_pinhole_project_unproject() {
+    let camera = skewed_camera();
+    for &p in &[
+        Point3::new(0.0, 0.0, -1.0),
+        Point3::new(1.5, -0.5, -4.0),
+        Point3::new(-2.0, 3.0, -10.0),
+    ] {
+        let pixel = camera.project(p).unwrap();
+        let ray = camera.unproject(pixel);
+        assert_relative_eq!(ray.point_at(-p.z), p, epsilon = 1e-12);
+    }
+    // The optical axis goes through the principal point, and up in view
+    // space is up in the image.
+    assert_eq!(
+        camera.project(Point3::new(0.0, 0.0, -3.0)),
+        Some(camera.principal_point)
+    );
+    assert!(camera.project(Point3::new(0.0, 1.0, -3.0)).unwrap().y < 370.0);
+    // Points behind the camera do not project.
+    assert_eq!(camera.project(Point3::new(0.0, 0.0, 1.0)), None);
+    assert_eq!(camera.project(Point3::new(1.0, 0.0, 0.0)), None);
+}
+
+#[test]
+fn test_pinhole_intrinsic_matrix() {
+    let camera = skewed_camera();
+    let k = camera.to_matrix3();
+    let p = Point3::new(1.5, -0.5, -4.0);
+    // The computer vision camera frame flips the `y` and `z` axes.
+    let h = k * Vector3::new(p.x, -p.y, -p.z);
+    assert_relative_eq!(
+        Point2::new(h.x / h.z, h.y / h.z),
+        camera.project(p).unwrap(),
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn test_pinhole_projection_matrix() {
+    let (width, height) = (1280.0, 720.0);
+    let camera = skewed_camera();
+    let m = camera.to_projection_matrix(width, height, 0.1, 100.0);
+    for &p in &[Point3::new(1.5, -0.5, -4.0), Point3::new(-2.0, 3.0, -10.0)] {
+        let clip = m * p.to_homogeneous();
+        let ndc = clip.truncate() / clip.w;
+        let pixel = Point2::new((ndc.x + 1.0) * width / 2.0, (1.0 - ndc.y) * height / 2.0);
+        assert_relative_eq!(pixel, camera.project(p).unwrap(), epsilon = 1e-9);
+    }
+    // The near and far planes map to the ends of the depth range.
+    let near = m * Point3::new(0.0, 0.0, -0.1).to_homogeneous();
+    assert_relative_eq!(near.z / near.w, -1.0, epsilon = 1e-12);
+
+    let back = PinholeCamera::from_projection_matrix(m, width, height).unwrap();
+    assert_relative_eq!(back.focal, camera.focal, epsilon = 1e-9);
+    assert_relative_eq!(back.principal_point, camera.principal_point, epsilon = 1e-9);
+    assert_relative_eq!(back.skew, camera.skew, epsilon = 1e-9);
+    assert_eq!(
+        PinholeCamera::from_projection_matrix(
+            ortho(-1.0, 1.0, -1.0, 1.0, 0.1, 10.0),
+            width,
+            height
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_pinhole_matches_perspective() {
+    let camera = PinholeCamera::from_fov(Deg(60.0), 800.0, 600.0);
+    assert_eq!(camera.principal_point, Point2::new(400.0, 300.0));
+    assert_relative_eq!(
+        camera.to_projection_matrix(800.0, 600.0, 0.5, 50.0),
+        perspective(Deg(60.0), 800.0 / 600.0, 0.5, 50.0),
+        epsilon = 1e-12
+    );
+    // An off-center camera matches an asymmetric frustum.
+    let camera = PinholeCamera {
+        principal_point: Point2::new(300.0, 200.0),
+        ..camera
+    };
+    let at_near = |pixel: Point2<f64>| camera.unproject(pixel).point_at(0.5);
+    let bottom_left = at_near(Point2::new(0.0, 600.0));
+    let top_right = at_near(Point2::new(800.0, 0.0));
+    assert_relative_eq!(
+        camera.to_projection_matrix(800.0, 600.0, 0.5, 50.0),
+        frustum(
+            bottom_left.x,
+            top_right.x,
+            bottom_left.y,
+            top_right.y,
+            0.5,
+            50.0
+        ),
+        epsilon = 1e-12
+    );
+}
+
+fn lenses() -> Vec<BrownConrady<f64>> {
+    vec![
+        BrownConrady::none(),
+        BrownConrady {
+            k1: -0.28,
+            k2: 0.07,
+            k3: -0.01,
+            ..BrownConrady::none()
+        },
+        BrownConrady {
+            k1: 0.15,
+            k2: 0.02,
+            k3: 0.0,
+            p1: 0.002,
+            p2: -0.001,
+        },
+    ]
+}
+
+#[test]
+fn test_brown_conrady_distort() {
+    let p = Point2::new(0.3, -0.2);
+    assert_eq!(BrownConrady::none().distort(p), p);
+    assert_eq!(BrownConrady::<f64>::default(), BrownConrady::none());
+
+    // Radial distortion scales points away from the center.
+    let barrel = BrownConrady {
+        k1: -0.25,
+        ..BrownConrady::none()
+    };
+    let r2 = 0.3 * 0.3 + 0.2 * 0.2;
+    assert_relative_eq!(barrel.distort(p), p * (1.0 - 0.25 * r2), epsilon = 1e-15);
+    assert_eq!(barrel.distort(Point2::new(0.0, 0.0)), Point2::new(0.0, 0.0));
+
+    // On the `y` axis, the first tangential coefficient moves points by
+    // `p1 * 3 * y²`.
+    let tilted = BrownConrady {
+        p1: 0.01,
+        ..BrownConrady::none()
+    };
+    assert_relative_eq!(
+        tilted.distort(Point2::new(0.0, 0.5)),
+        Point2::new(0.0, 0.5 + 0.01 * 3.0 * 0.25),
+        epsilon = 1e-15
+    );
+}
+
+#[test]
+fn test_brown_conrady_undistort() {
+    for lens in lenses() {
+        for i in -4..5 {
+            for j in -3..4 {
+                let p = Point2::new(i as f64 * 0.1, j as f64 * 0.1);
+                let distorted = lens.distort(p);
+                assert_relative_eq!(lens.undistort(distorted), p, epsilon = 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_brown_conrady_pixel_pipeline() {
+    let camera = skewed_camera();
+    let lens = lenses()[2];
+    let p = Point3::new(-1.0, 0.75, -5.0);
+    let normalized = camera.pixel_to_normalized(camera.project(p).unwrap());
+    assert_relative_eq!(normalized, Point2::new(-0.2, -0.15), epsilon = 1e-12);
+
+    let pixel = camera.normalized_to_pixel(lens.distort(normalized));
+    let ideal = camera.normalized_to_pixel(lens.undistort(camera.pixel_to_normalized(pixel)));
+    assert_relative_eq!(camera.unproject(ideal).point_at(5.0), p, epsilon = 1e-9);
+}
+
+#[test]
+fn test_screen_space_error() {
+    let projection = PerspectiveFov {
+        fovy: Deg(60.0).into(),
+        aspect: 1.5,
+        near: 0.1,
+        far: 1000.0,
+    };
+    let viewport = Vector2::new(1500.0, 1000.0);
+    let camera = Decomposed {
+        scale: 1.0,
+        rot: Quaternion::from_angle_y(Deg(30.0)),
+        disp: Vector3::new(5.0, 2.0, -1.0),
+    };
+    let tile = Sphere::new(Point3::new(5.0, 2.0, -51.0), 10.0);
+
+    // The error covers the same part of the screen as an object of that
+    // size at the closest distance.
+    let pixels_per_unit = 500.0 / Rad::tan(Rad::from(Deg(30.0)));
+    let error = screen_space_error(&tile, 0.5, &camera, &projection, viewport);
+    assert_relative_eq!(error, 0.5 * pixels_per_unit / 40.0, epsilon = 1e-12);
+
+    // Farther objects have smaller errors, and the error grows without
+    // bound as the camera gets close.
+    let far = Sphere::new(Point3::new(5.0, 2.0, -201.0), 10.0);
+    assert!(screen_space_error(&far, 0.5, &camera, &projection, viewport) < error);
+    let inside = Sphere::new(Point3::new(5.0, 2.0, -5.0), 10.0);
+    assert_eq!(
+        screen_space_error(&inside, 0.5, &camera, &projection, viewport),
+        std::f64::INFINITY
+    );
+
+    // Orthographic projections do not shrink things with distance.
+    let ortho = Ortho {
+        left: -15.0,
+        right: 15.0,
+        bottom: -10.0,
+        top: 10.0,
+        near: 0.1,
+        far: 1000.0,
+    };
+    let near_error = screen_space_error(&tile, 0.5, &camera, &ortho, viewport);
+    assert_relative_eq!(near_error, 0.5 * 50.0, epsilon = 1e-12);
+    assert_eq!(
+        screen_space_error(&far, 0.5, &camera, &ortho, viewport),
+        near_error
+    );
+}
+
+#[test]
+fn test_reprojection() {
+    let projection = perspective(Deg(70.0f64), 1.5, 0.1, 100.0);
+    let view = |x: f64, angle: f64| {
+        Matrix4::from_angle_y(Deg(angle)) * Matrix4::from_translation(Vector3::new(-x, 0.0, 0.0))
+    };
+    let previous = projection * view(0.0, 0.0);
+    let current = projection * view(1.0, 10.0);
+
+    // The jitter moves every point by the same offset on the screen.
+    let jitter = Vector2::new(0.004, -0.003);
+    let jittered = jitter_projection(current, jitter);
+    let points = [
+        Point3::new(0.5, 0.5, -4.0),
+        Point3::new(-2.0, 1.0, -10.0),
+        Point3::new(3.0, -1.0, -20.0),
+    ];
+    for &p in &points {
+        let offset = jittered.transform_point(p) - current.transform_point(p);
+        assert_relative_eq!(offset, jitter.extend(0.0), epsilon = 1e-12);
+    }
+
+    let m = reprojection_matrix(previous, current).unwrap();
+    for &p in &points {
+        let ndc = jittered.transform_point(p);
+        let reprojected = reproject(&m, ndc, jitter).unwrap();
+        assert_relative_eq!(reprojected, previous.transform_point(p), epsilon = 1e-9);
+    }
+
+    // A point behind the previous camera cannot be reprojected.
+    let behind = Point3::new(1.0, 0.0, -5.0);
+    let previous = projection * Matrix4::from_angle_y(Deg(180.0));
+    let m = reprojection_matrix(previous, current).unwrap();
+    let ndc = current.transform_point(behind);
+    assert_eq!(reproject(&m, ndc, Vector2::zero()), None);
+}
+
+#[test]
+fn test_reproject_and_compare() {
+    let view = Matrix4::look_at(
+        Point3::new(0.0, 3.0, 6.0),
+        Point3::new(0.0, 0.0, 0.0),
+        Vector3::unit_y(),
+    );
+    let projection = perspective(Deg(60.0f64), 1.0, 0.1, 100.0);
+    let thresholds = HistoryThresholds {
+        relative_depth: 0.01,
+        normal_angle: Deg(20.0).into(),
+    };
+    let point = Point3::new(0.5, 0.0, -0.5);
+    let up = Vector3::unit_y();
+    let viewed = (projection * view).transform_point(point);
+    let expected = Point2::new(viewed.x, viewed.y);
+
+    // The history is given the position of the point in the previous frame.
+    let same = |ndc: Point2<f64>| {
+        assert_relative_eq!(ndc, expected, epsilon = 1e-12);
+        Some((point + Vector3::new(0.1, 0.0, 0.0), up * 2.0))
+    };
+    let ndc = reproject_and_compare(point, up, view, projection, &thresholds, same);
+    assert_relative_eq!(ndc.unwrap(), expected, epsilon = 1e-12);
+
+    // Samples on a different plane, with a different normal, or missing are
+    // rejected.
+    let closer = |_| Some((point + Vector3::new(0.0, 0.1, 0.0), up));
+    assert_eq!(
+        reproject_and_compare(point, up, view, projection, &thresholds, closer),
+        None
+    );
+    let tilted = Vector3::new(0.5, 1.0, 0.0);
+    let wall = |_| Some((point, tilted));
+    assert_eq!(
+        reproject_and_compare(point, up, view, projection, &thresholds, wall),
+        None
+    );
+    let missing = |_| None;
+    assert_eq!(
+        reproject_and_compare(point, up, view, projection, &thresholds, missing),
+        None
+    );
+
+    // Points outside the previous view are never looked up.
+    let outside = |_: Point2<f64>| -> Option<(Point3<f64>, Vector3<f64>)> { unreachable!() };
+    let behind = Point3::new(0.0, 3.0, 10.0);
+    assert_eq!(
+        reproject_and_compare(behind, up, view, projection, &thresholds, outside),
+        None
+    );
+    let aside = Point3::new(50.0, 0.0, 0.0);
+    assert_eq!(
+        reproject_and_compare(aside, up, view, projection, &thresholds, outside),
+        None
+    );
+}
+
+#[test]
+fn test_projected_grid() {
+    let projection = perspective(Deg(60.0f64), 1.5, 0.1, 100.0);
+    let water = Plane::from_point_normal(Point3::new(0.0, 2.0, 0.0), Vector3::unit_y());
+    let (columns, rows) = (7, 5);
+    let check = |eye: Point3<f64>, center: Point3<f64>| {
+        let view_projection = projection * Matrix4::look_at(eye, center, Vector3::unit_y());
+        let inverse = view_projection.invert().unwrap();
+        let points = projected_grid(view_projection, &water, columns, rows).unwrap();
+        assert_eq!(points.len(), columns * rows);
+        let mut horizon = 0;
+        for j in 0..rows {
+            for i in 0..columns {
+                let ndc = Point2::new(i as f64 / 3.0 - 1.0, j as f64 / 2.0 - 1.0);
+                let p = points[j * columns + i];
+                assert_relative_eq!(water.distance(p), 0.0, epsilon = 1e-9);
+                let far = inverse.transform_point(Point3::new(ndc.x, ndc.y, 1.0));
+                if water.distance(far).signum() == water.distance(eye).signum() {
+                    // Beyond the horizon, the far point is folded onto the plane.
+                    assert_relative_eq!(p, water.project_point(far), epsilon = 1e-9);
+                    horizon += 1;
+                } else {
+                    let projected = view_projection.transform_point(p);
+                    assert_relative_eq!(projected.x, ndc.x, epsilon = 1e-9);
+                    assert_relative_eq!(projected.y, ndc.y, epsilon = 1e-9);
+                }
+            }
+        }
+        horizon
+    };
+
+    // From above, looking slightly down at the horizon.
+    let horizon = check(Point3::new(1.0, 12.0, 3.0), Point3::new(1.0, 10.0, -20.0));
+    assert!(horizon > 0 && horizon < columns * rows);
+    // From below, looking slightly up.
+    let horizon = check(Point3::new(0.0, -5.0, 0.0), Point3::new(5.0, -3.0, -20.0));
+    assert!(horizon > 0 && horizon < columns * rows);
+}
+
+#[test]
+fn test_projected_grid_invalid() {
+    let water = Plane::from_point_normal(Point3::new(0.0, 0.0, 0.0), Vector3::unit_y());
+    let view_projection = perspective(Deg(60.0f64), 1.0, 0.1, 100.0);
+    assert_eq!(projected_grid(view_projection, &water, 1, 4), None);
+    assert_eq!(projected_grid(view_projection, &water, 4, 0), None);
+    assert_eq!(projected_grid(Matrix4::zero(), &water, 4, 4), None);
+}
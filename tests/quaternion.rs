@@ -288,6 +288,53 @@ mod arc {
     }
 }
 
+mod face_towards_axis {
+    use cgmath::*;
+
+    #[test]
+    fn test_yaw_only() {
+        let forward = Vector3::new(0.0, 0.0, 1.0f64);
+        let up = Vector3::unit_y();
+        for &target in &[
+            vec3(1.0, 0.0, 0.0),
+            vec3(-2.0, 5.0, -1.0),
+            vec3(0.0, -3.0, -4.0),
+            vec3(3.0, 1.0, 0.001),
+        ] {
+            let q = Quaternion::face_towards_axis(forward, target, up * 2.0);
+            let aimed = q.rotate_vector(forward);
+            assert_relative_eq!(q.rotate_vector(up), up, epsilon = 1e-12);
+            assert_relative_eq!(aimed.y, 0.0, epsilon = 1e-12);
+            assert_relative_eq!(
+                aimed,
+                vec3(target.x, 0.0, target.z).normalize(),
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_tilted_forward() {
+        // Only the horizontal part of the forward vector turns.
+        let forward = vec3(0.0, 1.0, 1.0f64);
+        let q = Quaternion::face_towards_axis(forward, vec3(-1.0, 0.0, 0.0), Vector3::unit_y());
+        assert_relative_eq!(
+            q.rotate_vector(forward),
+            vec3(-1.0, 1.0, 0.0),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_parallel_to_axis() {
+        let up = Vector3::unit_y();
+        let q = Quaternion::face_towards_axis(Vector3::unit_z(), up * 3.0, up);
+        assert_eq!(q, Quaternion::one());
+        let q = Quaternion::face_towards_axis(-up, Vector3::unit_x(), up);
+        assert_eq!(q, Quaternion::<f32>::one());
+    }
+}
+
 mod rotate_from_euler {
     use cgmath::*;
 
@@ -469,3 +516,227 @@ mod cast {
         );
     }
 }
+
+mod double_cover {
+    use cgmath::*;
+
+    #[test]
+    fn test_approx_eq_rotation_antipodal() {
+        let q = Quaternion::from_axis_angle(vec3(1.0, 2.0, 3.0).normalize(), Deg(75.0f32));
+        assert!(q.approx_eq_rotation(&q));
+        assert!(q.approx_eq_rotation(&-q));
+        assert!((-q).approx_eq_rotation(&q));
+    }
+
+    #[test]
+    fn test_approx_eq_rotation_different() {
+        let a = Quaternion::from_angle_x(Deg(30.0f32));
+        let b = Quaternion::from_angle_x(Deg(31.0f32));
+        assert!(!a.approx_eq_rotation(&b));
+        assert!(!a.approx_eq_rotation(&-b));
+    }
+
+    #[test]
+    fn test_angle_between() {
+        let a = Quaternion::from_angle_y(Deg(10.0f64));
+        let b = Quaternion::from_angle_y(Deg(55.0f64));
+        assert_ulps_eq!(Deg::from(a.angle_between(&b)), Deg(45.0));
+        assert_ulps_eq!(Deg::from(b.angle_between(&a)), Deg(45.0));
+    }
+
+    #[test]
+    fn test_angle_between_antipodal() {
+        let q = Quaternion::from_angle_z(Deg(120.0f64));
+        assert_ulps_eq!(q.angle_between(&-q), Rad(0.0));
+    }
+
+    #[test]
+    fn test_angle_between_takes_shortest_arc() {
+        let a = Quaternion::from_angle_z(Deg(-170.0f64));
+        let b = Quaternion::from_angle_z(Deg(170.0f64));
+        assert_relative_eq!(Deg::from(a.angle_between(&b)), Deg(20.0), epsilon = 1e-10);
+    }
+}
+
+mod consts {
+    use cgmath::*;
+
+    #[test]
+    fn test_consts() {
+        const IDENTITY: Quaternion<f32> = Quaternion::<f32>::IDENTITY;
+        assert_eq!(IDENTITY, Quaternion::one());
+        assert_eq!(Quaternion::<f64>::ZERO, Quaternion::zero());
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(Quaternion::<f64>::default(), Quaternion::one());
+        assert_eq!(
+            Euler::<Rad<f64>>::default(),
+            Euler::new(Rad(0.0), Rad(0.0), Rad(0.0))
+        );
+        assert_eq!(Deg::<f32>::default(), Deg(0.0));
+    }
+}
+
+mod precision {
+    use cgmath::*;
+
+    #[test]
+    fn test_precision_conversions() {
+        let q = Quaternion::from_angle_x(Deg(30.0f32));
+        let q64: Quaternion<f64> = q.into();
+        assert_eq!(q64.to_f32(), q);
+
+        let m = Matrix4::from(Quaternion::from_angle_y(Rad(0.5f64)));
+        let single: Matrix4<f32> = m.to_f32();
+        assert_relative_eq!(Matrix4::<f64>::from(single), m, epsilon = 1e-6);
+
+        let e = Euler::new(Deg(10.0f64), Deg(20.0), Deg(30.0));
+        assert_eq!(e.to_f32(), Euler::new(Deg(10.0f32), Deg(20.0), Deg(30.0)));
+    }
+}
+
+mod tangent_frame {
+    use cgmath::*;
+
+    fn frames() -> Vec<Matrix3<f64>> {
+        let mut frames = Vec::new();
+        for &(axis, angle) in [
+            (Vector3::unit_x(), 0.0),
+            (Vector3::unit_y(), 180.0),
+            (Vector3::new(1.0, 2.0, 3.0), 77.0),
+            (Vector3::new(-1.0, 0.5, 0.0), 200.0),
+        ]
+        .iter()
+        {
+            let r = Matrix3::from_axis_angle(axis.normalize(), Deg(angle));
+            frames.push(r);
+            frames.push(Matrix3::from_cols(r.x, -r.y, r.z));
+        }
+        frames
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for tbn in frames() {
+            let q = Quaternion::from_tangent_frame(tbn);
+            assert_ulps_eq!(q.magnitude(), 1.0);
+            assert_eq!(q.s < 0.0, tbn.determinant() < 0.0);
+            assert!(q.s.abs() >= 1.0 / 32767.0 - 1e-12);
+            assert_relative_eq!(q.to_tangent_frame(), tbn, epsilon = 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_quantized() {
+        for tbn in frames() {
+            let q = Quaternion::from_tangent_frame(tbn);
+            let quantize = |x: f64| (x * 32767.0).round() / 32767.0;
+            let q = Quaternion::new(
+                quantize(q.s),
+                quantize(q.v.x),
+                quantize(q.v.y),
+                quantize(q.v.z),
+            );
+            assert_relative_eq!(q.to_tangent_frame(), tbn, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_orthonormalizes() {
+        let tbn = Matrix3::from_cols(
+            Vector3::new(2.0, 0.0, 0.3),
+            Vector3::new(0.1, -3.0, 0.0),
+            Vector3::new(0.0, 0.0, 0.5),
+        );
+        let decoded = Quaternion::from_tangent_frame(tbn).to_tangent_frame();
+        assert_relative_eq!(decoded.z, Vector3::unit_z(), epsilon = 1e-4);
+        assert_relative_eq!(decoded.x, Vector3::unit_x(), epsilon = 1e-4);
+        assert_relative_eq!(decoded.y, -Vector3::unit_y(), epsilon = 1e-4);
+    }
+}
+
+mod smallest_three {
+    use cgmath::*;
+
+    fn rotations() -> Vec<Quaternion<f64>> {
+        let mut rotations = vec![Quaternion::one(), -Quaternion::one()];
+        for i in 0..200 {
+            let t = i as f64;
+            let axis = Vector3::new((t * 0.7).sin(), (t * 1.3).cos(), (t * 0.2).sin() + 0.1);
+            rotations.push(Quaternion::from_axis_angle(axis.normalize(), Rad(t * 0.37)));
+        }
+        rotations
+    }
+
+    #[test]
+    fn test_round_trip_32() {
+        for q in rotations() {
+            let decoded = Quaternion::unpack_smallest_three(q.pack_smallest_three());
+            assert_ulps_eq!(decoded.magnitude(), 1.0);
+            assert!(q.angle_between(&decoded).0 < 3e-3);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_48() {
+        for q in rotations() {
+            let packed = q.pack_smallest_three_48();
+            assert_eq!(packed >> 47, 0);
+            let decoded = Quaternion::unpack_smallest_three_48(packed);
+            assert!(q.angle_between(&decoded).0 < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_identity_is_exact() {
+        let one = Quaternion::<f64>::one();
+        let decoded = Quaternion::unpack_smallest_three(one.pack_smallest_three());
+        assert_eq!(decoded, one);
+        let decoded = Quaternion::unpack_smallest_three_48((-one).pack_smallest_three_48());
+        assert_eq!(decoded, one);
+    }
+
+    #[test]
+    fn test_unnormalized_input() {
+        let q = Quaternion::from_axis_angle(Vector3::unit_z(), Deg(120.0f64));
+        assert_eq!((q * 3.0).pack_smallest_three(), q.pack_smallest_three());
+        // The largest component is `v.z`, stored in the top two bits.
+        assert_eq!(q.pack_smallest_three() >> 30, 3);
+    }
+}
+
+mod exp_ln {
+    use cgmath::*;
+
+    #[test]
+    fn test_exp_ln() {
+        let q = Quaternion::new(0.5f64, -1.0, 2.0, 0.25);
+        assert_relative_eq!(q.ln().exp(), q, epsilon = 1e-12);
+        assert_relative_eq!(q.exp().ln(), q, epsilon = 1e-12);
+        assert_relative_eq!(Quaternion::<f64>::one().ln(), Quaternion::zero());
+        assert_relative_eq!(Quaternion::<f64>::zero().exp(), Quaternion::one());
+
+        // The exponential of a real quaternion is real.
+        let real = Quaternion::from_sv(2.0f64, Vector3::zero());
+        assert_relative_eq!(real.exp(), Quaternion::from_sv(2f64.exp(), Vector3::zero()));
+    }
+
+    #[test]
+    fn test_angular_velocity() {
+        let a = Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 2.0) / 3.0, Deg(40.0f64));
+        let turn = Quaternion::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), Deg(-30.0));
+        let b = turn * a;
+        let omega = a.angular_velocity(b, 0.25);
+        let expected = Vector3::new(0.0, 0.6, 0.8) * (-120f64).to_radians();
+        assert_relative_eq!(omega, expected, epsilon = 1e-12);
+
+        // The opposite quaternion is the same rotation, and the shortest
+        // path is taken to it.
+        assert_relative_eq!(a.angular_velocity(-b, 0.25), expected, epsilon = 1e-12);
+        assert_relative_eq!(a.integrate(omega, 0.25), b, epsilon = 1e-12);
+        assert_relative_eq!(b.integrate(-omega, 0.25), a, epsilon = 1e-12);
+        assert_relative_eq!(a.angular_velocity(a, 1.0), Vector3::zero());
+    }
+}
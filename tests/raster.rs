@@ -0,0 +1,320 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::raster::{rasterize, rasterize_conservative, rasterize_segment, DepthPyramid};
+use cgmath::triangulation::delaunay;
+use cgmath::*;
+
+/// How many times each pixel of a grid is covered by a set of triangles.
+fn coverage_counts(triangles: &[Triangle2<f64>], size: usize) -> Vec<Vec<u32>> {
+    let mut counts = vec![vec![0; size]; size];
+    for triangle in triangles {
+        for fragment in rasterize(triangle, size, size) {
+            counts[fragment.y][fragment.x] += 1;
+        }
+    }
+    counts
+}
+
+#[test]
+fn test_shared_edges_through_centers() {
+    // Vertices at pixel centers, so that many centers are on edges, and
+    // every other triangle flipped.
+    let mut points = Vec::new();
+    for i in 0..40 {
+        let r = |d: i32| noise::random2::<f64>(Point2::new(i, d), 3);
+        points.push(Point2::new(
+            (r(0) * 7.0).floor() + 0.5,
+            (r(1) * 7.0).floor() + 0.5,
+        ));
+    }
+    for &(x, y) in &[(0.5, 0.5), (7.5, 0.5), (0.5, 7.5), (7.5, 7.5)] {
+        points.push(Point2::new(x, y));
+    }
+    points.sort_by(|a, b| (a.x, a.y).partial_cmp(&(b.x, b.y)).unwrap());
+    points.dedup();
+    let triangles: Vec<_> = delaunay(&points)
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let (a, b, c) = (points[t[0]], points[t[1]], points[t[2]]);
+            if i % 2 == 0 {
+                Triangle2::new(a, b, c)
+            } else {
+                Triangle2::new(a, c, b)
+            }
+        })
+        .collect();
+    // The left and top sides of the square are included, but not the
+    // right and bottom ones.
+    let counts = coverage_counts(&triangles, 10);
+    for (y, row) in counts.iter().enumerate() {
+        for (x, &count) in row.iter().enumerate() {
+            assert_eq!(
+                count,
+                if x < 7 && y < 7 { 1 } else { 0 },
+                "pixel {}, {}",
+                x,
+                y
+            );
+        }
+    }
+}
+
+#[test]
+fn test_barycentric() {
+    let triangle = Triangle2::new(
+        Point2::new(1.0, 1.0),
+        Point2::new(2.0, 9.0),
+        Point2::new(9.0, 3.0),
+    );
+    let fragments: Vec<_> = rasterize(&triangle, 10, 10).collect();
+    assert!(fragments.len() > 20);
+    for f in &fragments {
+        let center = Point2::new(f.x as f64 + 0.5, f.y as f64 + 0.5);
+        assert_relative_eq!(triangle.point_at(f.barycentric), center, epsilon = 1e-12);
+        assert!(f.barycentric.x >= 0.0 && f.barycentric.y >= 0.0 && f.barycentric.z >= 0.0);
+    }
+    // The same pixels and barycentrics for either orientation, in rows.
+    let flipped = Triangle2::new(triangle.a, triangle.c, triangle.b);
+    for (f, g) in fragments.iter().zip(rasterize(&flipped, 10, 10)) {
+        assert_eq!((f.x, f.y), (g.x, g.y));
+        assert_relative_eq!(
+            f.barycentric,
+            Vector3::new(g.barycentric.x, g.barycentric.z, g.barycentric.y)
+        );
+    }
+    assert!(fragments
+        .windows(2)
+        .all(|w| (w[0].y, w[0].x) < (w[1].y, w[1].x)));
+}
+
+#[test]
+fn test_clipping_and_degenerate() {
+    // A triangle larger than the grid covers all of it.
+    let huge = Triangle2::new(
+        Point2::new(-100.0, -100.0),
+        Point2::new(100.0, -100.0),
+        Point2::new(0.0, 100.0),
+    );
+    assert_eq!(rasterize(&huge, 3, 2).count(), 6);
+    let outside = Triangle2::new(
+        Point2::new(-5.0, -5.0),
+        Point2::new(-1.0, -5.0),
+        Point2::new(-1.0, -1.0),
+    );
+    assert_eq!(rasterize(&outside, 3, 2).count(), 0);
+    let line = Triangle2::new(
+        Point2::new(0.5, 0.5),
+        Point2::new(2.5, 0.5),
+        Point2::new(1.5, 0.5),
+    );
+    assert_eq!(rasterize(&line, 3, 2).count(), 0);
+    // Thin triangles between pixel centers cover nothing.
+    let thin = Triangle2::new(
+        Point2::new(0.0, 0.6),
+        Point2::new(3.0, 0.6),
+        Point2::new(3.0, 0.9),
+    );
+    assert_eq!(rasterize(&thin, 3, 2).count(), 0);
+}
+
+/// Whether the square of a pixel touches a triangle, by embedding both in
+/// three dimensions.
+fn touches(triangle: &Triangle2<f64>, x: usize, y: usize) -> bool {
+    let (x, y) = (x as f64, y as f64);
+    let pixel = Aabb3::new(Point3::new(x, y, -1.0), Point3::new(x + 1.0, y + 1.0, 1.0));
+    Triangle3::from(*triangle).intersects_aabb(&pixel)
+}
+
+#[test]
+fn test_conservative() {
+    for n in 0..200 {
+        let r = |d: i32| noise::random2::<f64>(Point2::new(n, d), 11) * 12.0 - 2.0;
+        let triangle = Triangle2::new(
+            Point2::new(r(0), r(1)),
+            Point2::new(r(2), r(3)),
+            Point2::new(r(4), r(5)),
+        );
+        let pixels: Vec<_> = rasterize_conservative(&triangle, 8, 8)
+            .map(|f| (f.x, f.y))
+            .collect();
+        let expected: Vec<_> = (0..8)
+            .flat_map(|y| (0..8).map(move |x| (x, y)))
+            .filter(|&(x, y)| touches(&triangle, x, y))
+            .collect();
+        assert_eq!(pixels, expected);
+        // Overestimating the standard coverage.
+        assert!(rasterize(&triangle, 8, 8).all(|f| pixels.contains(&(f.x, f.y))));
+    }
+
+    // Touching at a corner of a pixel counts, and the barycentrics extend
+    // past the triangle.
+    let corner = Triangle2::new(
+        Point2::new(2.0, 2.0),
+        Point2::new(4.0, 2.0),
+        Point2::new(2.0, 4.0),
+    );
+    let fragments: Vec<_> = rasterize_conservative(&corner, 8, 8).collect();
+    assert_eq!(fragments[0].x, 1);
+    assert_eq!(fragments[0].y, 1);
+    assert_relative_eq!(
+        corner.point_at(fragments[0].barycentric),
+        Point2::new(1.5, 1.5)
+    );
+
+    // Degenerate triangles cover their longest side.
+    let flat = Triangle2::new(
+        Point2::new(0.5, 0.5),
+        Point2::new(3.5, 0.5),
+        Point2::new(1.0, 0.5),
+    );
+    let pixels: Vec<_> = rasterize_conservative(&flat, 8, 8)
+        .map(|f| (f.x, f.y))
+        .collect();
+    assert_eq!(pixels, vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+}
+
+#[test]
+fn test_segment() {
+    let (a, b) = (Point2::new(0.5, 0.5), Point2::new(6.5, 3.5));
+    let fragments: Vec<_> = rasterize_segment(a, b, 8, 8).collect();
+    let pixels: Vec<_> = fragments.iter().map(|f| (f.x, f.y)).collect();
+    // The pixels touched by the segment, as a degenerate triangle.
+    let segment = Triangle2::new(a, b, b);
+    let expected: Vec<_> = (0..8)
+        .flat_map(|y| (0..8).map(move |x| (x, y)))
+        .filter(|&(x, y)| touches(&segment, x, y))
+        .collect();
+    assert_eq!(pixels, expected);
+    assert!(pixels.contains(&(0, 0)) && pixels.contains(&(6, 3)));
+    for f in &fragments {
+        assert_eq!(f.barycentric.z, 0.0);
+        assert_relative_eq!(f.barycentric.x + f.barycentric.y, 1.0);
+    }
+    assert_eq!(fragments[0].barycentric, Vector3::new(1.0, 0.0, 0.0));
+
+    // A point covers the pixels it touches.
+    let point = Point2::new(2.0, 3.5);
+    let pixels: Vec<_> = rasterize_segment(point, point, 8, 8)
+        .map(|f| (f.x, f.y))
+        .collect();
+    assert_eq!(pixels, vec![(1, 3), (2, 3)]);
+}
+
+#[test]
+fn test_depth_pyramid_max_depth() {
+    let (width, height) = (37, 23);
+    let depths: Vec<f64> = (0..width * height)
+        .map(|i| noise::random2(Point2::new(i as i32, 0), 5))
+        .collect();
+    let pyramid = DepthPyramid::new(&depths, width, height);
+    assert_eq!(pyramid.levels(), 7);
+    assert_eq!(
+        pyramid.depth(6, 0, 0),
+        depths.iter().cloned().fold(0.0, f64::max)
+    );
+
+    for n in 0..200 {
+        let r = |d: i32| noise::random2::<f64>(Point2::new(n, d), 6) * 50.0 - 5.0;
+        let (x0, x1, y0, y1) = (r(0), r(1), r(2), r(3));
+        let rect = Aabb2::new(
+            Point2::new(x0.min(x1), y0.min(y1)),
+            Point2::new(x0.max(x1), y0.max(y1)),
+        );
+        let mut expected = None;
+        for y in 0..height {
+            for x in 0..width {
+                let (xf, yf) = (x as f64, y as f64);
+                let touches = xf + 1.0 > rect.min.x
+                    && xf <= rect.max.x
+                    && yf + 1.0 > rect.min.y
+                    && yf <= rect.max.y;
+                if touches {
+                    let d = depths[y * width + x];
+                    expected = Some(expected.map_or(d, |e: f64| e.max(d)));
+                }
+            }
+        }
+        // The pyramid may read a few more pixels, but never fewer.
+        match (pyramid.max_depth(&rect), expected) {
+            (Some(max), Some(expected)) => assert!(max >= expected),
+            (None, None) => {}
+            (Some(_), None) => assert!(rect.max.x <= 0.0 || rect.max.y <= 0.0),
+            (None, Some(_)) => panic!("missed pixels of {:?}", rect),
+        }
+    }
+
+    // A single pixel reads exactly its own depth.
+    let pixel = Aabb2::new(Point2::new(12.2, 7.1), Point2::new(12.8, 7.9));
+    assert_eq!(pyramid.max_depth(&pixel), Some(depths[7 * width + 12]));
+    let outside = Aabb2::new(Point2::new(40.0, 0.0), Point2::new(45.0, 5.0));
+    assert_eq!(pyramid.max_depth(&outside), None);
+    assert!(pyramid.is_occluded(&outside, 0.0));
+}
+
+#[test]
+fn test_depth_pyramid_occlusion() {
+    // Render a wall five units in front of the camera into a depth buffer.
+    let size = 64;
+    let view_projection = perspective(Deg(90.0), 1.0, 0.1, 100.0);
+    let window = |p: Point3<f64>| {
+        let clip = view_projection * p.to_homogeneous();
+        let ndc = clip.truncate() / clip.w;
+        let pixel = Point2::new(
+            (ndc.x + 1.0) * 0.5 * size as f64,
+            (1.0 - ndc.y) * 0.5 * size as f64,
+        );
+        (pixel, (ndc.z + 1.0) * 0.5)
+    };
+    let corners = [
+        Point3::new(-3.0, -3.0, -5.0),
+        Point3::new(3.0, -3.0, -5.0),
+        Point3::new(3.0, 3.0, -5.0),
+        Point3::new(-3.0, 3.0, -5.0),
+    ];
+    let mut depths = vec![1.0f64; size * size];
+    for t in &[[0, 1, 2], [0, 2, 3]] {
+        let (a, b, c) = (
+            window(corners[t[0]]),
+            window(corners[t[1]]),
+            window(corners[t[2]]),
+        );
+        for f in rasterize(&Triangle2::new(a.0, b.0, c.0), size, size) {
+            let depth = f.barycentric.dot(Vector3::new(a.1, b.1, c.1));
+            let d = &mut depths[f.y * size + f.x];
+            *d = d.min(depth);
+        }
+    }
+    let pyramid = DepthPyramid::new(&depths, size, size);
+
+    let cube = |center: Point3<f64>, half: f64| {
+        Aabb3::new(
+            center - Vector3::new(half, half, half),
+            center + Vector3::new(half, half, half),
+        )
+    };
+    assert!(pyramid.is_aabb_occluded(&cube(Point3::new(0.0, 0.0, -10.0), 1.0), &view_projection));
+    assert!(pyramid.is_aabb_occluded(&cube(Point3::new(1.0, -1.0, -20.0), 3.0), &view_projection));
+    // In front of the wall, reaching past its side, or behind the camera.
+    assert!(!pyramid.is_aabb_occluded(&cube(Point3::new(0.0, 0.0, -3.0), 0.5), &view_projection));
+    assert!(!pyramid.is_aabb_occluded(&cube(Point3::new(5.0, 0.0, -10.0), 1.0), &view_projection));
+    assert!(!pyramid.is_aabb_occluded(&cube(Point3::new(0.0, 0.0, 0.0), 1.0), &view_projection));
+    // Off the screen, nothing is visible.
+    assert!(pyramid.is_aabb_occluded(&cube(Point3::new(0.0, 0.0, 10.0), 1.0), &view_projection));
+}
@@ -0,0 +1,111 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_ray() {
+    let ray = Ray3::new(Point3::new(1.0, 2.0, 3.0), Vector3::new(0.0, 0.0, -2.0));
+    assert_eq!(ray.point_at(1.5), Point3::new(1.0, 2.0, 0.0));
+    assert_eq!(
+        ray.to_wireframe_lines(10.0),
+        vec![(Point3::new(1.0, 2.0, 3.0), Point3::new(1.0, 2.0, -7.0))]
+    );
+}
+
+#[test]
+fn test_ray_cone_width() {
+    let ray = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -2.0f64));
+    let cone = RayCone::new(ray, 0.5, Rad(0.2));
+    assert_ulps_eq!(cone.width_at(0.0), 0.5);
+    // The parameter is scaled by the length of the direction.
+    assert_ulps_eq!(cone.width_at(2.5), 0.5 + 10.0 * Rad(0.1f64).tan());
+}
+
+#[test]
+fn test_ray_cone_oblique_plane() {
+    let ray = Ray3::new(Point3::new(0.0, 5.0, 0.0), Vector3::new(1.0, -1.0, 0.0f64));
+    let cone = RayCone::new(ray, 0.0, Rad(0.01));
+    let ground = Plane::from_point_normal(Point3::new(0.0, 0.0, 0.0), Vector3::unit_y());
+    let hit = cone.intersect_plane(&ground).unwrap();
+    assert_ulps_eq!(hit.t, 5.0);
+    assert_ulps_eq!(hit.point, Point3::new(5.0, 0.0, 0.0));
+    assert_ulps_eq!(hit.width, cone.width_at(5.0));
+    assert_relative_eq!(hit.footprint, hit.width * 2.0f64.sqrt(), epsilon = 1e-12);
+
+    let away = RayCone::new(Ray3::new(ray.origin, Vector3::unit_y()), 0.0, Rad(0.01));
+    assert_eq!(away.intersect_plane(&ground), None);
+}
+
+#[test]
+fn test_ray_cone_sphere() {
+    let sphere = Sphere::new(Point3::new(0.0, 0.0, -10.0), 2.0f64);
+    let ray = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+    let cone = RayCone::new(ray, 0.0, Rad(0.01));
+    let hit = cone.intersect_sphere(&sphere).unwrap();
+    assert_ulps_eq!(hit.t, 8.0);
+    assert_ulps_eq!(hit.normal, Vector3::unit_z());
+    assert_ulps_eq!(hit.footprint, hit.width);
+
+    // From inside the sphere the ray hits the far side.
+    let inside = RayCone::new(Ray3::new(sphere.center, Vector3::unit_x()), 0.0, Rad(0.01));
+    assert_ulps_eq!(inside.intersect_sphere(&sphere).unwrap().t, 2.0);
+
+    let miss = RayCone::new(
+        Ray3::new(Point3::new(3.0, 0.0, 0.0), ray.direction),
+        0.0,
+        Rad(0.01),
+    );
+    assert_eq!(miss.intersect_sphere(&sphere), None);
+}
+
+#[test]
+fn test_ray_cone_triangle() {
+    let triangle = Triangle3::new(
+        Point3::new(-1.0, -1.0, -4.0),
+        Point3::new(3.0, -1.0, -4.0),
+        Point3::new(-1.0, 3.0, -4.0f64),
+    );
+    let ray = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0));
+    let cone = RayCone::from_camera(ray, Deg(60.0).into(), 720);
+    let (hit, bary) = cone.intersect_triangle(&triangle).unwrap();
+    assert_ulps_eq!(hit.t, 4.0);
+    assert_ulps_eq!(bary, Vector3::new(0.5, 0.25, 0.25));
+    assert_ulps_eq!(triangle.point_at(bary), hit.point);
+
+    let pixel = 2.0 * Rad::from(Deg(30.0f64)).tan() / 720.0;
+    assert_relative_eq!(hit.footprint, 4.0 * pixel, max_relative = 1e-6);
+
+    let miss = RayCone::new(
+        Ray3::new(Point3::new(2.0, 2.0, 0.0), ray.direction),
+        0.0,
+        Rad(0.0),
+    );
+    assert_eq!(miss.intersect_triangle(&triangle), None);
+}
+
+#[test]
+fn test_ray_cone_propagate() {
+    let ray = Ray3::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0f64));
+    let cone = RayCone::new(ray, 0.0, Rad(0.01));
+    let mirror = Plane::from_point_normal(Point3::new(0.0, 0.0, -3.0), Vector3::unit_z());
+    let hit = cone.intersect_plane(&mirror).unwrap();
+    let reflected = cone.propagate(&hit, Ray3::new(hit.point, Vector3::unit_z()), Rad(0.02));
+    assert_ulps_eq!(reflected.width, hit.width);
+    assert_ulps_eq!(reflected.spread_angle, Rad(0.03));
+}
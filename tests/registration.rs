@@ -0,0 +1,158 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+#[cfg(feature = "rand")]
+extern crate rand;
+
+use cgmath::registration::*;
+use cgmath::*;
+
+fn points2() -> Vec<Point2<f64>> {
+    vec![
+        Point2::new(0.0, 0.0),
+        Point2::new(2.0, 0.5),
+        Point2::new(-1.0, 3.0),
+        Point2::new(4.0, -2.0),
+        Point2::new(1.5, 1.5),
+    ]
+}
+
+fn points3() -> Vec<Point3<f64>> {
+    vec![
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(2.0, 0.5, -1.0),
+        Point3::new(-1.0, 3.0, 0.5),
+        Point3::new(4.0, -2.0, 2.0),
+        Point3::new(1.5, 1.5, 1.5),
+        Point3::new(-3.0, -1.0, 4.0),
+    ]
+}
+
+#[test]
+fn test_fit_affine2() {
+    let m = Matrix3::new(1.5, 0.5, 0.0, -0.25, 2.0, 0.0, 3.0, -4.0, 1.0);
+    let src = points2();
+    let dst: Vec<_> = src.iter().map(|&p| m.transform_point(p)).collect();
+    assert_relative_eq!(fit_affine2(&src, &dst).unwrap(), m, epsilon = 1e-9);
+    // Collinear points do not determine the transform.
+    let line = [
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(2.0, 2.0),
+    ];
+    assert_eq!(fit_affine2(&line, &line), None);
+    assert_eq!(fit_affine2(&src, &dst[1..]), None);
+    assert_eq!(fit_affine2::<f64>(&[], &[]), None);
+}
+
+#[test]
+fn test_fit_affine3() {
+    let m = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+        * Matrix4::from_nonuniform_scale(2.0, 0.5, 1.5)
+        * Matrix4::from_angle_y(Deg(35.0));
+    let src = points3();
+    let dst: Vec<_> = src.iter().map(|&p| m.transform_point(p)).collect();
+    assert_relative_eq!(fit_affine3(&src, &dst).unwrap(), m, epsilon = 1e-9);
+    assert_eq!(fit_affine3(&src[..3], &dst[..3]), None);
+}
+
+#[test]
+fn test_fit_similarity2() {
+    let t = Conformal2::new(0.5, Deg(-110.0), Vector2::new(-2.0, 7.0));
+    let src = points2();
+    let dst: Vec<_> = src.iter().map(|&p| t.transform_point(p)).collect();
+    assert_relative_eq!(fit_similarity2(&src, &dst).unwrap(), t, epsilon = 1e-9);
+    let same = [Point2::new(1.0, 1.0), Point2::new(1.0, 1.0)];
+    assert_eq!(fit_similarity2(&same, &same), None);
+}
+
+#[test]
+fn test_fit_similarity2_least_squares() {
+    // Fitting a scaled square to a rectangle averages the two scales.
+    let src = [
+        Point2::new(-1.0, -1.0),
+        Point2::new(1.0, -1.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(-1.0, 1.0),
+    ];
+    let dst: Vec<_> = src.iter().map(|p| Point2::new(p.x * 3.0, p.y)).collect();
+    let t = fit_similarity2(&src, &dst).unwrap();
+    assert_relative_eq!(t.scale(), 2.0, epsilon = 1e-12);
+    assert_relative_eq!(t.angle(), Rad(0.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_fit_similarity3() {
+    let src = points3();
+    let axes = [
+        Vector3::unit_x(),
+        Vector3::unit_y(),
+        Vector3::unit_z(),
+        Vector3::new(1.0, -2.0, 0.5).normalize(),
+    ];
+    for &axis in axes.iter() {
+        for &angle in [0.0, 30.0, 95.0, 180.0].iter() {
+            let t = Decomposed {
+                scale: 1.75,
+                rot: Quaternion::from_axis_angle(axis, Deg(angle)),
+                disp: Vector3::new(5.0, -1.0, 2.0),
+            };
+            let dst: Vec<_> = src.iter().map(|&p| t.transform_point(p)).collect();
+            let fitted = fit_similarity3(&src, &dst).unwrap();
+            assert_relative_eq!(fitted.scale, t.scale, epsilon = 1e-9);
+            assert_relative_eq!(fitted.disp, t.disp, epsilon = 1e-9);
+            for &p in &src {
+                assert_relative_eq!(
+                    fitted.transform_point(p),
+                    t.transform_point(p),
+                    epsilon = 1e-9
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fit_similarity3_rejects_degenerate() {
+    let p = [Point3::new(1.0, 2.0, 3.0); 4];
+    assert!(fit_similarity3(&p, &p).is_none());
+    assert!(fit_similarity3(&p[..2], &p[..3]).is_none());
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn test_ransac() {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let t = Matrix4::from_translation(Vector3::new(1.0, 0.0, -1.0)) * Matrix4::from_scale(2.0);
+    let src: Vec<_> = (0..30)
+        .map(|i| Point3::new(i as f64, (i * 7 % 5) as f64, (i * 3 % 11) as f64))
+        .collect();
+    let mut dst: Vec<_> = src.iter().map(|&p| t.transform_point(p)).collect();
+    for &i in [2, 9, 17, 25].iter() {
+        dst[i] = Point3::new(-100.0, i as f64, 3.0);
+    }
+
+    let mut rng = StdRng::seed_from_u64(4);
+    let (fitted, inliers) = ransac(&src, &dst, 4, 100, 1e-6, &mut rng, fit_affine3).unwrap();
+    assert_relative_eq!(fitted, t, epsilon = 1e-9);
+    assert_eq!(inliers.len(), 26);
+    assert!(!inliers.contains(&9));
+
+    assert!(ransac(&src[..3], &dst[..3], 4, 10, 1e-6, &mut rng, fit_affine3).is_none());
+}
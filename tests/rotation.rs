@@ -13,6 +13,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate approx;
 extern crate cgmath;
 
 use cgmath::*;
@@ -43,5 +44,112 @@ fn test_invert_basis3() {
     let a: Basis3<_> = rotation::a3();
     let a = a * a.invert();
     let a: &Matrix3<_> = a.as_ref();
-    assert!(a.is_identity());
+    assert_ulps_eq!(*a, Matrix3::identity(), epsilon = 1e-12);
+}
+
+#[test]
+fn test_rotation_distance() {
+    let q0 = Quaternion::from_angle_x(Deg(20.0f64));
+    let q1 = Quaternion::from_angle_x(Deg(-25.0f64));
+    let expected = Rad::from(Deg(45.0));
+
+    assert_relative_eq!(rotation_distance(q0, q1), expected, epsilon = 1e-10);
+    assert_relative_eq!(rotation_distance(q0, -q1), expected, epsilon = 1e-10);
+
+    let b0: Basis3<_> = q0.into();
+    let b1: Basis3<_> = q1.into();
+    assert_relative_eq!(rotation_distance(b0, b1), expected, epsilon = 1e-10);
+
+    let m0: Matrix3<_> = q0.into();
+    let m1: Matrix3<_> = q1.into();
+    assert_relative_eq!(rotation_distance(m0, m1), expected, epsilon = 1e-10);
+}
+
+#[test]
+fn test_rotation_distance_identical() {
+    let b: Basis3<f64> = rotation::a3();
+    assert_ulps_eq!(rotation_distance(b, b), Rad(0.0));
+}
+
+#[test]
+fn test_invert_axis_angle() {
+    let a: AxisAngle<f64> = rotation::a3();
+    let v = Vector3::new(1.0, -2.0, 3.0);
+    assert_relative_eq!(
+        a.invert().rotate_vector(a.rotate_vector(v)),
+        v,
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(
+        Quaternion::from(a * a.invert()),
+        Quaternion::one(),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_axis_angle_conversions() {
+    let a: AxisAngle<f64> = rotation::a3();
+    let q: Quaternion<f64> = rotation::a3();
+    let b: Basis3<f64> = rotation::a3();
+    let m = Matrix3::from(b);
+
+    assert_relative_eq!(Quaternion::from(a), q, epsilon = 1e-12);
+    assert_relative_eq!(Basis3::from(a), b, epsilon = 1e-12);
+    assert_relative_eq!(Matrix3::from(a), m, epsilon = 1e-12);
+    assert_relative_eq!(AxisAngle::from(q), a, epsilon = 1e-12);
+    assert_relative_eq!(AxisAngle::from(b), a, epsilon = 1e-12);
+    assert_relative_eq!(AxisAngle::from(m), a, epsilon = 1e-12);
+
+    let v = Vector3::new(0.5, 4.0, -1.0);
+    assert_relative_eq!(a.rotate_vector(v), q.rotate_vector(v), epsilon = 1e-12);
+
+    // Angles come back in [0, pi], flipping the axis if needed.
+    let large = AxisAngle::new(Vector3::unit_y(), Deg(270.0));
+    let converted = AxisAngle::from(Quaternion::from(large));
+    assert_relative_eq!(
+        converted,
+        AxisAngle::new(-Vector3::unit_y(), Deg(90.0)),
+        epsilon = 1e-12
+    );
+    assert_eq!(AxisAngle::from(Quaternion::<f64>::one()), AxisAngle::one());
+
+    let euler = Euler::new(Deg(10.0), Deg(-20.0), Deg(30.0));
+    assert_relative_eq!(
+        Quaternion::from(AxisAngle::from(euler)),
+        Quaternion::from(euler),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_scaled() {
+    let v = Vector3::new(1.0, 2.0, -0.5);
+    let full: Quaternion<f64> = rotation::a3();
+    let half = Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalize(), Deg(15.0));
+    let double = Quaternion::from_axis_angle(Vector3::new(1.0, 1.0, 0.0).normalize(), Deg(60.0));
+
+    assert_relative_eq!(full.scaled(0.5), half, epsilon = 1e-12);
+    assert_relative_eq!(full.scaled(2.0), double, epsilon = 1e-12);
+    assert_relative_eq!(full.scaled(0.0), Quaternion::one(), epsilon = 1e-12);
+    assert_relative_eq!(full.scaled(-1.0), full.invert(), epsilon = 1e-12);
+    // The negated quaternion is the same rotation, and scales the same way.
+    assert_relative_eq!((-full).scaled(0.5), half, epsilon = 1e-12);
+
+    let b: Basis3<f64> = rotation::a3();
+    assert_relative_eq!(
+        b.scaled(0.5).rotate_vector(v),
+        half.rotate_vector(v),
+        epsilon = 1e-12
+    );
+    let a: AxisAngle<f64> = rotation::a3();
+    assert_relative_eq!(
+        a.scaled(2.0).rotate_vector(v),
+        double.rotate_vector(v),
+        epsilon = 1e-12
+    );
+
+    let b2: Basis2<f64> = rotation::a2();
+    let expected: Basis2<f64> = Rotation2::from_angle(Deg(-45.0));
+    assert_relative_eq!(b2.scaled(-1.5), expected, epsilon = 1e-12);
 }
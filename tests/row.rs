@@ -0,0 +1,96 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_matches_column_convention() {
+    let eye = Point3::new(1.0, 2.0, 3.0);
+    let center = Point3::new(-1.0, 0.5, 0.0);
+    let column =
+        perspective(Deg(60.0), 1.5, 0.1, 100.0) * Matrix4::look_at(eye, center, Vector3::unit_y());
+    let row =
+        row::look_at(eye, center, Vector3::unit_y()) * row::perspective(Deg(60.0), 1.5, 0.1, 100.0);
+    assert_eq!(row::to_column(row), column);
+    assert_eq!(row::from_column(column), row);
+
+    let p = Point3::new(0.3, -0.2, 1.0);
+    assert_relative_eq!(
+        row::transform_point(p, row),
+        column.transform_point(p),
+        epsilon = 1e-12
+    );
+    let v = Vector3::new(0.5, 1.0, -2.0);
+    assert_relative_eq!(
+        row::transform_vector(v, row),
+        column.transform_vector(v),
+        epsilon = 1e-12
+    );
+    let h = Vector4::new(1.0, 2.0, 3.0, 4.0);
+    assert_relative_eq!(row::mul(h, row), column * h, epsilon = 1e-12);
+}
+
+#[test]
+fn test_layout() {
+    let m = row::from_column(Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0)));
+    // The translation is in the last row.
+    assert_eq!(m.row(3), Vector4::new(1.0, 2.0, 3.0, 1.0));
+    // Storing the rows one after the other gives the memory of the matrix of
+    // the column vector convention.
+    let rows: Vec<f64> = (0..4)
+        .flat_map(|i| {
+            let r = m.row(i);
+            vec![r.x, r.y, r.z, r.w]
+        })
+        .collect();
+    let column = row::to_column(m);
+    let memory: &[f64; 16] = column.as_ref();
+    assert_eq!(&rows[..], &memory[..]);
+    assert_eq!(
+        row::mul(Vector4::new(0.0, 0.0, 0.0, 1.0), m),
+        Vector4::new(1.0, 2.0, 3.0, 1.0)
+    );
+    assert_eq!(
+        row::transform_vector(Vector3::unit_x(), m),
+        Vector3::unit_x()
+    );
+}
+
+#[test]
+fn test_projections() {
+    assert_eq!(
+        row::to_column(row::ortho(-1.0, 2.0, -3.0, 4.0, 0.5, 10.0)),
+        ortho(-1.0, 2.0, -3.0, 4.0, 0.5, 10.0)
+    );
+    assert_eq!(
+        row::to_column(row::frustum(-1.0, 2.0, -3.0, 4.0, 0.5, 10.0)),
+        frustum(-1.0, 2.0, -3.0, 4.0, 0.5, 10.0)
+    );
+    let dir = Vector3::new(0.0, -1.0, -1.0);
+    let eye = Point3::new(0.0, 4.0, 4.0);
+    assert_eq!(
+        row::to_column(row::look_to(eye, dir, Vector3::unit_y())),
+        Matrix4::look_at_dir(eye, dir, Vector3::unit_y())
+    );
+    // The near plane still maps to a depth of -1.
+    let p = row::transform_point(
+        Point3::new(0.0, 0.0, -0.5),
+        row::frustum(-1.0, 1.0, -1.0, 1.0, 0.5, 10.0),
+    );
+    assert_relative_eq!(p.z, -1.0, epsilon = 1e-12);
+}
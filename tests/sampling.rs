@@ -0,0 +1,417 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::sampling::*;
+
+fn check_spacing<P: MetricSpace<Metric = f64> + Copy>(points: &[P], radius: f64) {
+    for (i, &a) in points.iter().enumerate() {
+        for &b in &points[i + 1..] {
+            assert!(a.distance(b) >= radius);
+        }
+    }
+}
+
+#[test]
+fn test_poisson_disk_rect() {
+    let bounds = Aabb2::new(Point2::new(-5.0, 0.0), Point2::new(5.0, 4.0));
+    let points = poisson_disk_rect(&bounds, 0.5, 3);
+    check_spacing(&points, 0.5);
+    assert!(points.iter().all(|&p| bounds.contains_point(p)));
+    // No room is left for another disk.
+    for i in 0..=40 {
+        for j in 0..=16 {
+            let probe = Point2::new(-5.0 + i as f64 * 0.25, j as f64 * 0.25);
+            assert!(points.iter().any(|&p| p.distance(probe) < 1.0));
+        }
+    }
+    assert_eq!(points, poisson_disk_rect(&bounds, 0.5, 3));
+    assert_ne!(points, poisson_disk_rect(&bounds, 0.5, 4));
+    assert!(poisson_disk_rect(&bounds, 0.0, 3).is_empty());
+}
+
+#[test]
+fn test_poisson_disk_circle() {
+    let center = Point2::new(2.0, -1.0);
+    let points = poisson_disk_circle(center, 3.0, 0.4, 11);
+    check_spacing(&points, 0.4);
+    assert!(points.iter().all(|&p| p.distance(center) <= 3.0));
+    let densest = (3.0f64 / 0.4).powi(2) * ::std::f64::consts::PI;
+    assert!(points.len() as f64 > densest * 0.15 && (points.len() as f64) < densest);
+}
+
+#[test]
+fn test_poisson_disk_triangles() {
+    // Two faces of a unit cube, as separate triangles.
+    let (o, x, y, z) = (
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+        Point3::new(0.0, 0.0, 1.0),
+    );
+    let xy = Point3::new(1.0, 1.0, 0.0);
+    let yz = Point3::new(0.0, 1.0, 1.0);
+    let triangles = [
+        Triangle3::new(o, x, xy),
+        Triangle3::new(o, xy, y),
+        Triangle3::new(o, y, yz),
+        Triangle3::new(o, yz, z),
+    ];
+    let points = poisson_disk_triangles(&triangles, 0.1, 5);
+    check_spacing(&points, 0.1);
+    assert!(points
+        .iter()
+        .all(|p| p.x.abs() < 1e-12 || p.z.abs() < 1e-12));
+    // Both faces are covered.
+    let bottom = points.iter().filter(|p| p.z.abs() < 1e-12).count();
+    assert!(bottom > 40 && points.len() - bottom > 40);
+    assert_eq!(points, poisson_disk_triangles(&triangles, 0.1, 5));
+    assert!(poisson_disk_triangles::<f64>(&[], 0.1, 5).is_empty());
+}
+
+#[test]
+fn test_surface_sampler() {
+    // A unit square in two triangles, with a degenerate one between them.
+    let (a, b, c, d) = (
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 0.0),
+        Point3::new(0.0, 1.0, 0.0),
+    );
+    let triangles = [
+        Triangle3::new(a, b, c),
+        Triangle3::new(a, a, c),
+        Triangle3::new(a, c, d),
+    ];
+    let sampler = SurfaceSampler::new(&triangles);
+    assert_relative_eq!(sampler.area(), 1.0);
+
+    assert_eq!(sampler.sample(0.0, 0.0, 0.0).unwrap().triangle, 0);
+    assert_eq!(sampler.sample(0.5, 0.0, 0.0).unwrap().triangle, 2);
+    assert_eq!(sampler.sample(0.999, 0.5, 0.5).unwrap().triangle, 2);
+    let corner = sampler.sample(0.25, 1.0, 0.0).unwrap();
+    assert_eq!(corner.point, b);
+    assert_eq!(corner.normal, Vector3::unit_z());
+
+    // The quadrants of the square get about the same number of points.
+    let points = sampler.random_points(4000, 9);
+    assert_eq!(points.len(), 4000);
+    let mut quadrants = [0; 4];
+    for p in &points {
+        assert!(p.triangle != 1);
+        let i = (p.point.x >= 0.5) as usize + 2 * (p.point.y >= 0.5) as usize;
+        quadrants[i] += 1;
+    }
+    assert!(
+        quadrants.iter().all(|&n| n > 900 && n < 1100),
+        "{:?}",
+        quadrants
+    );
+    assert_eq!(points, sampler.random_points(4000, 9));
+
+    let empty: [Triangle3<f64>; 0] = [];
+    assert_eq!(SurfaceSampler::new(&empty).sample(0.5, 0.5, 0.5), None);
+    assert!(SurfaceSampler::new(&triangles[1..2])
+        .random_points(5, 0)
+        .is_empty());
+}
+
+/// Pairs of deterministic pseudo-random numbers in `[0, 1)`.
+fn uniform_pairs(count: i32) -> Vec<(f64, f64)> {
+    (0..count)
+        .map(|n| {
+            let u = noise::random2(Point2::new(n, 0), 11);
+            (u, noise::random2(Point2::new(n, 1), 11))
+        })
+        .collect()
+}
+
+#[test]
+fn test_cosine_hemisphere() {
+    let normal = Vector3::new(1.0, 2.0, -2.0f64).normalize();
+    let pairs = uniform_pairs(20000);
+    let mut mean_cos = 0.0;
+    for &(u, v) in &pairs {
+        let direction = cosine_hemisphere(normal, u, v);
+        assert_relative_eq!(direction.magnitude(), 1.0, epsilon = 1e-12);
+        let cos = direction.dot(normal);
+        assert!(cos >= 0.0);
+        // The density is exactly the integrand of irradiance.
+        assert_relative_eq!(
+            cosine_hemisphere_pdf(normal, direction),
+            cos / std::f64::consts::PI
+        );
+        mean_cos += cos / pairs.len() as f64;
+    }
+    assert_relative_eq!(mean_cos, 2.0 / 3.0, epsilon = 0.01);
+    assert_eq!(cosine_hemisphere_pdf(normal, -normal), 0.0);
+}
+
+#[test]
+fn test_ggx_distribution_is_normalized() {
+    // The projected area of the microfacets is that of the surface.
+    let normal = Vector3::unit_z();
+    for &alpha in &[0.05, 0.3, 1.0] {
+        let steps = 20000;
+        let step = std::f64::consts::FRAC_PI_2 / steps as f64;
+        let total: f64 = (0..steps)
+            .map(|i| {
+                let theta = (i as f64 + 0.5) * step;
+                let half = Vector3::new(theta.sin(), 0.0, theta.cos());
+                ggx_half_vector_pdf(normal, alpha, half) * theta.sin() * step
+            })
+            .sum::<f64>()
+            * 2.0
+            * std::f64::consts::PI;
+        assert_relative_eq!(total, 1.0, epsilon = 1e-4);
+    }
+    assert_eq!(ggx_distribution(normal, 0.5, -normal), 0.0);
+}
+
+#[test]
+fn test_ggx_half_vector() {
+    let normal = Vector3::new(-3.0, 0.0, 4.0f64).normalize();
+    let alpha = 0.4;
+    let pairs = uniform_pairs(20000);
+    // Estimating the projected area of the hemisphere with the samples.
+    let mut estimate = 0.0;
+    for &(u, v) in &pairs {
+        let half = ggx_half_vector(normal, alpha, u, v);
+        assert_relative_eq!(half.magnitude(), 1.0, epsilon = 1e-12);
+        assert!(half.dot(normal) >= 0.0);
+        estimate += half.dot(normal) / ggx_half_vector_pdf(normal, alpha, half);
+    }
+    estimate /= pairs.len() as f64;
+    assert_relative_eq!(estimate, std::f64::consts::PI, max_relative = 0.03);
+
+    // Smoother surfaces concentrate the normals.
+    let spread = |alpha| {
+        pairs
+            .iter()
+            .map(|&(u, v)| ggx_half_vector(normal, alpha, u, v).dot(normal))
+            .sum::<f64>()
+    };
+    assert!(spread(0.1) > spread(0.5));
+}
+
+#[test]
+fn test_ggx_reflection() {
+    let normal = Vector3::unit_y();
+    let view = Vector3::new(0.6, 0.8, 0.0f64);
+    let alpha = 0.5;
+    let pairs = uniform_pairs(20000);
+    let mut estimate = 0.0;
+    let mut rejected = 0;
+    for &(u, v) in &pairs {
+        let light = ggx_reflection(normal, alpha, view, u, v);
+        assert_relative_eq!(light.magnitude(), 1.0, epsilon = 1e-12);
+        let pdf = ggx_reflection_pdf(normal, alpha, view, light);
+        if pdf == 0.0 {
+            rejected += 1;
+            continue;
+        }
+        estimate += light.dot(normal).max(0.0) / pdf;
+    }
+    estimate /= pairs.len() as f64;
+    assert_relative_eq!(estimate, std::f64::consts::PI, max_relative = 0.03);
+    assert!(rejected > 0 && rejected < pairs.len() / 10);
+
+    // A very smooth surface reflects like a mirror.
+    let mirror = ggx_reflection(normal, 1e-4, view, 0.3, 0.7);
+    assert_relative_eq!(mirror, Vector3::new(-0.6, 0.8, 0.0), epsilon = 1e-3);
+    assert_eq!(ggx_reflection_pdf(normal, alpha, view, -view), 0.0);
+}
+
+#[test]
+fn test_halton() {
+    assert_eq!(radical_inverse::<f64>(0, 2), 0.0);
+    assert_eq!(radical_inverse::<f64>(6, 2), 0.375);
+    assert_relative_eq!(radical_inverse::<f64>(5, 3), 7.0 / 9.0);
+    assert_eq!(halton2::<f64>(1), Point2::new(0.5, 1.0 / 3.0));
+    // Each of the first 2^k points is in a different interval of 2^-k.
+    let mut seen = [false; 16];
+    for i in 0..16 {
+        let p = halton2::<f64>(i);
+        let slot = (p.x * 16.0) as usize;
+        assert!(!seen[slot]);
+        seen[slot] = true;
+    }
+}
+
+#[test]
+fn test_jitter_offsets() {
+    let camera = PinholeCamera::from_fov(Deg(60.0), 8.0, 8.0);
+    let pose = Pose3 {
+        scale: 1.0,
+        rot: Quaternion::one(),
+        disp: Vector3::zero(),
+    };
+    let rays = |jitter| PrimaryRays::<f64>::from_pinhole(camera, pose, jitter);
+    assert_eq!(
+        rays(Jitter::Center).jitter_offset(3, 4, 7),
+        Vector2::new(0.5, 0.5)
+    );
+    assert_eq!(
+        rays(Jitter::Halton).jitter_offset(3, 4, 0),
+        Vector2::new(0.5, 1.0 / 3.0)
+    );
+
+    for &jitter in &[
+        Jitter::Halton,
+        Jitter::ScrambledHalton(5),
+        Jitter::Random(5),
+    ] {
+        let rays = rays(jitter);
+        let mut mean = Vector2::zero();
+        for sample in 0..256 {
+            let offset = rays.jitter_offset(3, 4, sample);
+            assert!(offset.x >= 0.0 && offset.x < 1.0 && offset.y >= 0.0 && offset.y < 1.0);
+            mean += offset / 256.0;
+        }
+        assert_relative_eq!(mean, Vector2::new(0.5, 0.5), epsilon = 0.05);
+    }
+
+    // Scrambling gives neighbouring pixels different patterns.
+    let scrambled = rays(Jitter::ScrambledHalton(5));
+    assert_ne!(
+        scrambled.jitter_offset(3, 4, 0),
+        scrambled.jitter_offset(4, 4, 0)
+    );
+    // It is deterministic.
+    assert_eq!(
+        scrambled.jitter_offset(3, 4, 9),
+        scrambled.jitter_offset(3, 4, 9)
+    );
+}
+
+#[test]
+fn test_primary_rays() {
+    let (width, height) = (64, 48);
+    let camera = PinholeCamera::from_fov(Deg(50.0), width as f64, height as f64);
+    let pose = Pose3 {
+        scale: 1.0,
+        rot: Quaternion::from_angle_y(Deg(30.0)),
+        disp: Vector3::new(1.0, 2.0, 3.0),
+    };
+    let pinhole = PrimaryRays::from_pinhole(camera, pose, Jitter::Random(1));
+
+    // The same camera given by its view-projection matrix.
+    let view: Matrix4<f64> = pose.inverse_transform().unwrap().into();
+    let projection = camera.to_projection_matrix(width as f64, height as f64, 0.1, 100.0);
+    let matrix =
+        PrimaryRays::from_view_projection(projection * view, width, height, Jitter::Random(1))
+            .unwrap();
+
+    for &(x, y, sample) in &[(0, 0, 0), (63, 47, 3), (20, 30, 17)] {
+        let a = pinhole.ray(x, y, sample);
+        let b = matrix.ray(x, y, sample);
+        assert_eq!(a.origin, Point3::new(1.0, 2.0, 3.0));
+        assert_relative_eq!(a.direction.magnitude(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(a.direction, b.direction, epsilon = 1e-9);
+        // The matrix rays start on the near plane, along the same line.
+        let depth = (b.origin - a.origin).dot(pose.transform_vector(-Vector3::unit_z()));
+        assert_relative_eq!(depth, 0.1, epsilon = 1e-9);
+        assert_relative_eq!(
+            (b.origin - a.origin).normalize(),
+            a.direction,
+            epsilon = 1e-6
+        );
+
+        // Projecting the ray back lands in the pixel.
+        let view_point = pose
+            .inverse_transform()
+            .unwrap()
+            .transform_point(a.point_at(2.0));
+        let pixel = camera.project(view_point).unwrap();
+        assert!(pixel.x >= x as f64 && pixel.x < x as f64 + 1.0);
+        assert!(pixel.y >= y as f64 && pixel.y < y as f64 + 1.0);
+    }
+    assert!(
+        PrimaryRays::<f64>::from_view_projection(Matrix4::zero(), 4, 4, Jitter::Center).is_none()
+    );
+}
+
+#[test]
+fn test_concentric_disk() {
+    assert_eq!(concentric_disk(0.5, 0.5), Point2::origin());
+    assert_relative_eq!(concentric_disk(1.0, 0.5), Point2::new(1.0, 0.0));
+    assert_relative_eq!(
+        concentric_disk(0.5, 0.0),
+        Point2::new(0.0, -1.0),
+        epsilon = 1e-12
+    );
+    // The mapping preserves areas: a quarter of a grid over the square
+    // lands within half of the radius.
+    let n = 200;
+    let mut inner = 0;
+    for i in 0..n {
+        for j in 0..n {
+            let u = (i as f64 + 0.5) / n as f64;
+            let v = (j as f64 + 0.5) / n as f64;
+            let p = concentric_disk(u, v);
+            assert!(p.to_vec().magnitude() <= 1.0 + 1e-12);
+            if p.to_vec().magnitude() < 0.5 {
+                inner += 1;
+            }
+        }
+    }
+    assert_relative_eq!(inner as f64 / (n * n) as f64, 0.25, epsilon = 0.01);
+}
+
+#[test]
+fn test_thin_lens_rays() {
+    let camera = PinholeCamera::from_fov(Deg(40.0), 32.0, 32.0);
+    let pose = Pose3 {
+        scale: 1.0,
+        rot: Quaternion::from_angle_x(Deg(-20.0)),
+        disp: Vector3::new(0.0, 3.0, 4.0),
+    };
+    let forward = pose.transform_vector(-Vector3::unit_z());
+    let (aperture, focus) = (0.2, 5.0);
+    let lens = PrimaryRays::from_thin_lens(camera, pose, aperture, focus, Jitter::Center);
+    let pinhole = PrimaryRays::from_pinhole(camera, pose, Jitter::Center);
+    let (a, b) = (lens.ray(10, 20, 3), pinhole.ray(10, 20, 3));
+    assert_eq!(a.origin, b.origin);
+    assert_relative_eq!(a.direction, b.direction, epsilon = 1e-12);
+
+    for &jitter in &[
+        Jitter::Halton,
+        Jitter::ScrambledHalton(2),
+        Jitter::Random(2),
+    ] {
+        let lens = PrimaryRays::from_thin_lens(camera, pose, aperture, focus, jitter);
+        let pinhole = PrimaryRays::from_pinhole(camera, pose, jitter);
+        let mut spread = 0.0f64;
+        for sample in 0..64 {
+            let ray = lens.ray(10, 20, sample);
+            let reference = pinhole.ray(10, 20, sample);
+            assert_relative_eq!(ray.direction.magnitude(), 1.0, epsilon = 1e-12);
+            // The rays start on the lens, and meet the rays of the pinhole
+            // camera on the plane in focus.
+            let offset = ray.origin - reference.origin;
+            assert_relative_eq!(offset.dot(forward), 0.0, epsilon = 1e-12);
+            assert!(offset.magnitude() <= aperture + 1e-12);
+            spread = spread.max(offset.magnitude());
+            let t = focus / ray.direction.dot(forward);
+            let s = focus / reference.direction.dot(forward);
+            assert_relative_eq!(ray.point_at(t), reference.point_at(s), epsilon = 1e-9);
+        }
+        assert!(spread > aperture * 0.8);
+    }
+}
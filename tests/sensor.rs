@@ -0,0 +1,102 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::sensor::{orientation_from_gravity, ComplementaryFilter};
+
+const GRAVITY: Vector3<f64> = Vector3 {
+    x: 0.0,
+    y: 0.0,
+    z: 9.81,
+};
+const FIELD: Vector3<f64> = Vector3 {
+    x: 0.0,
+    y: 0.2,
+    z: -0.4,
+};
+
+fn readings(q: Quaternion<f64>) -> (Vector3<f64>, Vector3<f64>) {
+    let inverse = q.invert();
+    (inverse.rotate_vector(GRAVITY), inverse.rotate_vector(FIELD))
+}
+
+#[test]
+fn test_orientation_from_gravity() {
+    let q = orientation_from_gravity(GRAVITY, FIELD).unwrap();
+    assert_relative_eq!(q, Quaternion::one(), epsilon = 1e-12);
+
+    let axis = Vector3::new(0.3, -1.0, 0.5).normalize();
+    let truth = Quaternion::from_axis_angle(axis, Deg(130.0));
+    let (accel, magnetic) = readings(truth);
+    let q = orientation_from_gravity(accel, magnetic).unwrap();
+    assert!(q.angle_between(&truth) < Rad(1e-12));
+
+    assert_eq!(orientation_from_gravity(Vector3::zero(), FIELD), None);
+    assert_eq!(orientation_from_gravity(GRAVITY, GRAVITY * 0.01), None);
+}
+
+#[test]
+fn test_complementary_filter_converges() {
+    let truth = Quaternion::from_axis_angle(Vector3::unit_x(), Deg(20.0))
+        * Quaternion::from_axis_angle(Vector3::unit_z(), Deg(-70.0));
+    let (accel, magnetic) = readings(truth);
+    let mut filter = ComplementaryFilter::new(0.5);
+    for _ in 0..1000 {
+        filter.update(Vector3::zero(), accel, Some(magnetic), 0.01);
+    }
+    assert!(filter.orientation.angle_between(&truth) < Rad(1e-3));
+}
+
+#[test]
+fn test_complementary_filter_follows_gyro() {
+    // A device spinning around the vertical at one radian per second, with
+    // a gyroscope that reads 2% too high.
+    let mut filter = ComplementaryFilter::from_measurement(1.0, GRAVITY, FIELD);
+    let dt = 0.005;
+    for i in 1..=2000 {
+        let truth = Quaternion::from_axis_angle(Vector3::unit_z(), Rad(i as f64 * dt));
+        let (accel, magnetic) = readings(truth);
+        filter.update(Vector3::new(0.0, 0.0, 1.02), accel, Some(magnetic), dt);
+        assert!(filter.orientation.angle_between(&truth) < Rad(0.03));
+    }
+}
+
+#[test]
+fn test_complementary_filter_tilt_only() {
+    // Without a magnetometer the heading is kept while the tilt converges.
+    let heading = Quaternion::from_axis_angle(Vector3::unit_z(), Deg(40.0));
+    let tilt = Quaternion::from_axis_angle(Vector3::unit_y(), Deg(15.0));
+    let (accel, _) = readings(heading * tilt);
+    let mut filter = ComplementaryFilter {
+        orientation: heading,
+        time_constant: 0.2,
+    };
+    for _ in 0..1000 {
+        filter.update(Vector3::zero(), accel, None, 0.01);
+    }
+    let up = filter.orientation.rotate_vector(accel.normalize());
+    assert_relative_eq!(up, Vector3::unit_z(), epsilon = 1e-6);
+    let forward = filter.orientation.rotate_vector(Vector3::unit_x());
+    let expected = (heading * tilt).rotate_vector(Vector3::unit_x());
+    assert_relative_eq!(
+        forward.truncate().normalize(),
+        expected.truncate().normalize(),
+        epsilon = 1e-6
+    );
+}
@@ -0,0 +1,121 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::sh::{Sh2, Sh3};
+use cgmath::*;
+
+fn directions() -> Vec<Vector3<f64>> {
+    vec![
+        Vector3::unit_x(),
+        -Vector3::unit_y(),
+        Vector3::unit_z(),
+        Vector3::new(1.0, 2.0, -3.0).normalize(),
+        Vector3::new(-0.3, 0.4, 0.5).normalize(),
+        Vector3::new(0.9, -0.1, 0.2).normalize(),
+    ]
+}
+
+#[test]
+fn test_basis_is_orthonormal() {
+    for i in 0..9 {
+        let f = |d: Vector3<f64>| Sh3::basis(d).coefficients[i];
+        let projected = Sh3::project(f, 20000);
+        let mut expected = [0.0; 9];
+        expected[i] = 1.0;
+        assert_abs_diff_eq!(projected, Sh3::new(expected), epsilon = 1e-3);
+    }
+}
+
+#[test]
+fn test_evaluate_quadratic() {
+    // Quadratic polynomials are exactly represented by three bands.
+    let f = |d: Vector3<f64>| 2.0 + d.x - 3.0 * d.y * d.z + d.z * d.z;
+    let sh = Sh3::project(f, 20000);
+    for d in directions() {
+        assert_abs_diff_eq!(sh.evaluate(d), f(d), epsilon = 1e-3);
+    }
+
+    let constant = Sh2::constant(1.5);
+    for d in directions() {
+        assert_relative_eq!(constant.evaluate(d), 1.5, epsilon = 1e-12);
+    }
+    assert_eq!(Sh2::<f64>::from_samples(vec![]), Sh2::zero());
+}
+
+#[test]
+fn test_rotate() {
+    let sh = Sh3::new([0.5, -0.2, 0.7, 0.1, 0.3, -0.6, 0.25, 0.8, -0.4]);
+    let rotation = Quaternion::from_axis_angle(Vector3::new(1.0, -2.0, 0.5).normalize(), Deg(70.0));
+    let rotated = sh.rotate(rotation);
+    let rotated2 = sh.truncate().rotate(rotation);
+    for d in directions() {
+        assert_relative_eq!(
+            rotated.evaluate(rotation * d),
+            sh.evaluate(d),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            rotated2.evaluate(rotation * d),
+            sh.truncate().evaluate(d),
+            epsilon = 1e-12
+        );
+    }
+    assert_relative_eq!(rotated.rotate(rotation.invert()), sh, epsilon = 1e-12);
+    assert_relative_eq!(
+        Sh3::from(rotated2),
+        {
+            let mut c = rotated.coefficients;
+            for x in c[4..].iter_mut() {
+                *x = 0.0;
+            }
+            Sh3::new(c)
+        },
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_irradiance() {
+    // Uniform radiance gives an irradiance of pi times the radiance.
+    let irradiance = Sh3::constant(2.0).cosine_convolve();
+    assert_relative_eq!(
+        irradiance.evaluate(Vector3::unit_y()),
+        2.0 * std::f64::consts::PI,
+        epsilon = 1e-12
+    );
+
+    // Light from above reaches surfaces facing up more than sideways.
+    let sky = Sh3::project(|d: Vector3<f64>| if d.z > 0.9 { 1.0 } else { 0.0 }, 20000);
+    let irradiance = sky.cosine_convolve();
+    let up = irradiance.evaluate(Vector3::unit_z());
+    let side = irradiance.evaluate(Vector3::unit_x());
+    assert!(up > side && side > irradiance.evaluate(-Vector3::unit_z()));
+    assert_relative_eq!(sky.dominant_direction(), Vector3::unit_z(), epsilon = 1e-3);
+    assert_eq!(Sh3::constant(1.0).dominant_direction(), Vector3::zero());
+}
+
+#[test]
+fn test_arithmetic() {
+    let a = Sh2::new([1.0, 2.0, 3.0, 4.0]);
+    let b = Sh2::new([0.5, -1.0, 0.0, 2.0]);
+    assert_eq!(a + b, Sh2::new([1.5, 1.0, 3.0, 6.0]));
+    assert_eq!(a - b, Sh2::new([0.5, 3.0, 3.0, 2.0]));
+    assert_eq!(a * 2.0, Sh2::new([2.0, 4.0, 6.0, 8.0]));
+    assert_eq!(a.dot(&b), 6.5);
+    assert!(Sh2::<f64>::zero().is_zero());
+}
@@ -0,0 +1,84 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_to_wireframe_lines() {
+    let sphere = Sphere::new(Point3::new(1.0, -2.0, 3.0), 0.5);
+    let lines = sphere.to_wireframe_lines(8);
+    assert_eq!(lines.len(), 24);
+    for &(a, b) in &lines {
+        assert_relative_eq!(a.distance(sphere.center), 0.5, epsilon = 1e-12);
+        assert_relative_eq!(b.distance(sphere.center), 0.5, epsilon = 1e-12);
+    }
+    // Each circle is closed.
+    for circle in lines.chunks(8) {
+        assert_relative_eq!(circle[7].1, circle[0].0, epsilon = 1e-12);
+        for pair in circle.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+    }
+}
+
+/// Whether the segment from `eye` to `p` goes through the planet.
+fn is_hidden(planet: &Sphere<f64>, eye: Point3<f64>, p: Point3<f64>) -> bool {
+    let d = p - eye;
+    let m = eye - planet.center;
+    let (a, b, c) = (d.dot(d), m.dot(d), m.dot(m) - planet.radius * planet.radius);
+    let discriminant = b * b - a * c;
+    discriminant > 0.0 && (-b - discriminant.sqrt()) / a < 1.0
+}
+
+#[test]
+fn test_occludes() {
+    let planet = Sphere::new(Point3::new(1.0, 2.0, 3.0), 10.0);
+    let eye = Point3::new(1.0, 2.0, 3.0) + Vector3::new(3.0, 4.0, 12.0) * 1.5;
+
+    let mut culled = 0;
+    for i in -6..7 {
+        for j in -6..7 {
+            for k in -6..7 {
+                let center = planet.center + Vector3::new(i as f64, j as f64, k as f64) * 3.0;
+                let other = Sphere::new(center, 1.5);
+                if !planet.occludes(eye, &other) {
+                    continue;
+                }
+                culled += 1;
+                // Every point of a culled sphere is hidden.
+                for &(a, b) in &other.to_wireframe_lines(12) {
+                    assert!(is_hidden(&planet, eye, a), "{:?}", other);
+                    assert!(is_hidden(&planet, eye, a.midpoint(b)), "{:?}", other);
+                }
+            }
+        }
+    }
+    assert!(culled > 100);
+
+    // The point opposite the eye is hidden, but not the planet's near side.
+    let far_side = planet.center - (eye - planet.center).normalize_to(11.0);
+    let near_side = planet.center + (eye - planet.center).normalize_to(11.0);
+    assert!(planet.occludes(eye, &Sphere::new(far_side, 0.5)));
+    assert!(!planet.occludes(eye, &Sphere::new(near_side, 0.5)));
+
+    // A sphere too large to fit in the shadow of the planet.
+    assert!(!planet.occludes(eye, &Sphere::new(far_side, 20.0)));
+
+    // Nothing is hidden from inside the planet.
+    assert!(!planet.occludes(planet.center, &Sphere::new(far_side, 0.5)));
+}
@@ -0,0 +1,203 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::steering::{
+    self, flock2, flock3, in_view_arc, in_view_cone, view_falloff, Agent2, Agent3, Flocking,
+};
+
+#[test]
+fn test_seek_and_flee() {
+    let position = Point2::new(1.0, 1.0);
+    let velocity = Vector2::new(0.0, 1.0);
+    let target = Point2::new(4.0, 5.0);
+    let seek = steering::seek(position, velocity, target, 10.0f64);
+    assert_relative_eq!(seek, Vector2::new(6.0, 8.0) - velocity, epsilon = 1e-12);
+    let flee = steering::flee(position, velocity, target, 10.0f64);
+    assert_relative_eq!(flee, Vector2::new(-6.0, -8.0) - velocity, epsilon = 1e-12);
+
+    // At the target there is no direction to seek, so the agent brakes.
+    assert_eq!(steering::seek(target, velocity, target, 10.0), -velocity);
+}
+
+#[test]
+fn test_arrive() {
+    let position = Point3::new(0.0, 0.0, 0.0);
+    let target = Point3::new(0.0, 3.0, 4.0);
+    let far = steering::arrive(position, Vector3::zero(), target, 2.0f64, 2.0);
+    assert_relative_eq!(far, Vector3::new(0.0, 1.2, 1.6), epsilon = 1e-12);
+    let near = steering::arrive(position, Vector3::zero(), target, 2.0f64, 10.0);
+    assert_relative_eq!(near, Vector3::new(0.0, 0.6, 0.8), epsilon = 1e-12);
+
+    let mut agent = Agent3::new(Point3::new(0.0, 0.0, 0.0), 3.0, 5.0);
+    for _ in 0..1000 {
+        let force = steering::arrive(agent.position, agent.velocity, target, 3.0, 2.0);
+        agent.update(force, 0.02);
+        assert!(agent.velocity.magnitude() <= 3.0 + 1e-12);
+    }
+    assert_relative_eq!(agent.position, target, epsilon = 1e-3);
+    assert!(agent.velocity.magnitude() < 1e-3);
+}
+
+#[test]
+fn test_wander() {
+    let velocity = Vector2::new(0.0, 3.0f64);
+    let displacement = Vector2::new(0.5, 0.0);
+    let force = steering::wander(velocity, 2.0, displacement);
+    assert_relative_eq!(force, Vector2::new(0.5, 2.0), epsilon = 1e-12);
+    assert_eq!(
+        steering::wander(Vector2::zero(), 2.0, displacement),
+        displacement
+    );
+}
+
+#[test]
+fn test_separation() {
+    let position = Point2::new(0.0, 0.0f64);
+    let neighbours = [
+        position,
+        Point2::new(1.0, 0.0),
+        Point2::new(0.0, -2.0),
+        Point2::new(10.0, 10.0),
+    ];
+    let force = steering::separation(position, &neighbours, 3.0);
+    assert_relative_eq!(force, Vector2::new(-1.0, 0.5), epsilon = 1e-12);
+}
+
+#[test]
+fn test_agent_limits() {
+    let mut agent = Agent2::new(Point2::new(0.0, 0.0), 2.0f64, 1.0);
+    assert_eq!(agent.heading(), None);
+
+    // The force is limited, then the speed.
+    agent.update(Vector2::new(100.0, 0.0), 1.0);
+    assert_relative_eq!(agent.velocity, Vector2::new(1.0, 0.0));
+    assert_relative_eq!(agent.position, Point2::new(1.0, 0.0));
+    agent.update(Vector2::new(0.0, 100.0), 2.0);
+    assert_relative_eq!(agent.velocity.magnitude(), 2.0, epsilon = 1e-12);
+    assert_relative_eq!(agent.heading().unwrap(), Vector2::new(1.0, 2.0).normalize());
+}
+
+#[test]
+fn test_flock() {
+    // A deterministic cloud of boids, compared with a brute force search.
+    let mut state = 12345u32;
+    let mut random = || {
+        state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+        (state >> 8) as f64 / (1u32 << 24) as f64
+    };
+    let mut positions = Vec::new();
+    let mut velocities = Vec::new();
+    for _ in 0..200 {
+        positions.push(Point3::new(random(), random(), random()) * 10.0);
+        velocities.push(Vector3::new(random(), random(), random()) - Vector3::from_value(0.5));
+    }
+    let flocking = Flocking {
+        radius: 1.5,
+        alignment: 0.5,
+        cohesion: 0.25,
+        separation: 2.0,
+    };
+    let forces = flock3(&positions, &velocities, &flocking);
+    for (i, &p) in positions.iter().enumerate() {
+        let neighbours: Vec<usize> = (0..positions.len())
+            .filter(|&j| j != i && p.distance(positions[j]) < 1.5)
+            .collect();
+        if neighbours.is_empty() {
+            assert_eq!(forces[i], Vector3::zero());
+            continue;
+        }
+        let n = neighbours.len() as f64;
+        let velocity = neighbours
+            .iter()
+            .map(|&j| velocities[j])
+            .sum::<Vector3<f64>>()
+            / n;
+        let center = neighbours
+            .iter()
+            .map(|&j| positions[j].to_vec())
+            .sum::<Vector3<f64>>()
+            / n;
+        let others: Vec<Point3<f64>> = neighbours.iter().map(|&j| positions[j]).collect();
+        let expected = (velocity - velocities[i]) * 0.5
+            + (center - p.to_vec()) * 0.25
+            + steering::separation(p, &others, 1.5) * 2.0;
+        assert_relative_eq!(forces[i], expected, epsilon = 1e-9);
+    }
+
+    // Boids in the plane behave like boids in space at `z = 0`.
+    let flat: Vec<Point2<f64>> = positions.iter().map(|p| Point2::new(p.x, p.y)).collect();
+    let flat_velocities: Vec<Vector2<f64>> = velocities.iter().map(|v| v.truncate()).collect();
+    let forces = flock2(&flat, &flat_velocities, &flocking);
+    let lifted: Vec<Point3<f64>> = flat.iter().map(|p| Point3::new(p.x, p.y, 0.0)).collect();
+    let lifted_velocities: Vec<Vector3<f64>> =
+        flat_velocities.iter().map(|v| v.extend(0.0)).collect();
+    let expected = flock3(&lifted, &lifted_velocities, &flocking);
+    for (force, expected) in forces.iter().zip(&expected) {
+        assert_relative_eq!(force.extend(0.0), *expected, epsilon = 1e-12);
+    }
+}
+
+#[test]
+fn test_in_view_cone() {
+    let eye = Point3::new(1.0, 2.0, 3.0f64);
+    let forward = Vector3::new(1.0, 0.0, 0.0);
+    let half_angle = Rad::from(Deg(30.0));
+    let at = |angle: f64| eye + Vector3::new(angle.cos(), angle.sin(), 0.0) * 4.0;
+    assert!(in_view_cone(eye, forward, half_angle, at(0.5)));
+    assert!(in_view_cone(eye, forward, half_angle, at(-0.5)));
+    assert!(!in_view_cone(eye, forward, half_angle, at(0.55)));
+    assert!(!in_view_cone(eye, forward, half_angle, eye - forward));
+    assert!(in_view_cone(eye, forward, half_angle, eye));
+    assert!(in_view_cone(eye, forward, Rad::turn_div_2(), eye - forward));
+}
+
+#[test]
+fn test_in_view_arc() {
+    let eye = Point2::new(0.0, 0.0f64);
+    let forward = Vector2::new(0.0, 3.0);
+    let half_angle = Rad::from(Deg(100.0));
+    assert!(in_view_arc(
+        eye,
+        forward,
+        half_angle,
+        Point2::new(5.0, -0.5)
+    ));
+    assert!(in_view_arc(
+        eye,
+        forward,
+        half_angle,
+        Point2::new(-5.0, -0.5)
+    ));
+    assert!(!in_view_arc(
+        eye,
+        forward,
+        half_angle,
+        Point2::new(1.0, -5.0)
+    ));
+}
+
+#[test]
+fn test_view_falloff() {
+    let eye = Point2::new(1.0, 1.0f64);
+    assert_eq!(view_falloff(eye, eye, 10.0), 1.0);
+    assert_relative_eq!(view_falloff(eye, Point2::new(4.0, 5.0), 10.0), 0.5);
+    assert_eq!(view_falloff(eye, Point2::new(11.0, 1.0), 10.0), 0.0);
+    assert_eq!(view_falloff(eye, Point2::new(20.0, 1.0), 10.0), 0.0);
+}
@@ -0,0 +1,95 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+
+use cgmath::strict;
+use std::f64::consts::PI;
+
+/// The machine epsilon of `f64`.
+const EPSILON: f64 = 2.220446049250313e-16;
+
+fn assert_close(a: f64, b: f64) {
+    assert!(
+        (a - b).abs() <= 4.0 * EPSILON * b.abs().max(1.0e-300),
+        "{} != {}",
+        a,
+        b
+    );
+}
+
+#[test]
+fn test_sin_cos_match_platform() {
+    for i in -2000..2000 {
+        let x = i as f64 * 0.0123 + 0.001;
+        let (s, c) = strict::sin_cos(x);
+        assert_close(s, x.sin());
+        assert_close(c, x.cos());
+        assert_eq!(strict::sin(x), s);
+        assert_eq!(strict::cos(x), c);
+        assert_close(strict::tan(x), x.tan());
+    }
+}
+
+#[test]
+fn test_sin_cos_special_values() {
+    assert_eq!(strict::sin(0.0f64), 0.0);
+    assert_eq!(strict::cos(0.0f64), 1.0);
+    assert!(strict::sin(-0.0f64).is_sign_negative());
+    assert!(strict::sin(std::f64::INFINITY).is_nan());
+    assert!(strict::cos(std::f64::NAN).is_nan());
+    assert_close(strict::sin(PI / 6.0), 0.5);
+    assert_close(strict::cos(PI / 3.0), 0.5);
+}
+
+#[test]
+fn test_inverse_functions_match_platform() {
+    for i in -1000..=1000 {
+        let x = i as f64 / 1000.0;
+        assert_close(strict::asin(x), x.asin());
+        assert_close(strict::acos(x), x.acos());
+        let y = x * 50.0;
+        assert_close(strict::atan(y), y.atan());
+    }
+    assert!(strict::asin(1.5f64).is_nan());
+    assert!(strict::acos(-1.5f64).is_nan());
+}
+
+#[test]
+fn test_atan2_quadrants() {
+    for i in 0..64 {
+        let angle = -PI + (i as f64 + 0.5) * PI / 32.0;
+        let (y, x) = (3.0 * angle.sin(), 3.0 * angle.cos());
+        assert_close(strict::atan2(y, x), y.atan2(x));
+    }
+    assert_eq!(strict::atan2(0.0f64, 1.0), 0.0);
+    assert_eq!(strict::atan2(0.0f64, -1.0), PI);
+    assert_eq!(strict::atan2(-0.0f64, -1.0), -PI);
+    assert_eq!(strict::atan2(1.0f64, 0.0), PI / 2.0);
+    assert_eq!(strict::atan2(-1.0f64, 0.0), -PI / 2.0);
+    assert_eq!(
+        strict::atan2(std::f64::INFINITY, std::f64::NEG_INFINITY),
+        3.0 * PI / 4.0
+    );
+}
+
+#[test]
+fn test_single_precision() {
+    for i in -100..100 {
+        let x = i as f32 * 0.1;
+        assert!((strict::sin(x) - x.sin()).abs() <= 2.0 * std::f32::EPSILON);
+        assert!((strict::cos(x) - x.cos()).abs() <= 2.0 * std::f32::EPSILON);
+    }
+}
@@ -20,6 +20,7 @@ extern crate cgmath;
 extern crate serde_json;
 
 use cgmath::*;
+use std::f64;
 
 #[test]
 fn test_invert() {
@@ -61,6 +62,264 @@ fn test_look_at() {
     assert_ulps_eq!(&t.transform_point(point), &view_point);
 }
 
+#[test]
+fn test_pose_distance() {
+    let a = Decomposed {
+        scale: 1.0f64,
+        rot: Quaternion::from_angle_z(Deg(30.0)),
+        disp: Vector3::new(1.0, 2.0, 3.0),
+    };
+    let b = Decomposed {
+        scale: 2.0f64,
+        rot: Quaternion::from_angle_z(Deg(-60.0)),
+        disp: Vector3::new(1.0, 5.0, 7.0),
+    };
+    assert_ulps_eq!(a.pose_distance(&a, 1.0), 0.0);
+    assert_ulps_eq!(a.pose_distance(&b, 0.0), 5.0);
+    assert_relative_eq!(
+        a.pose_distance(&b, 2.0),
+        5.0 + f64::consts::PI,
+        epsilon = 1e-10
+    );
+}
+
+#[test]
+fn test_default() {
+    let t: Decomposed<Vector3<f64>, Quaternion<f64>> = Default::default();
+    assert_eq!(t, Transform::<Point3<f64>>::one());
+
+    let b: Basis2<f64> = Default::default();
+    assert_eq!(b, Basis2::one());
+}
+
+#[test]
+fn test_builder() {
+    let rot = Quaternion::from_angle_x(Deg(30.0f64));
+    let disp = Vector3::new(1.0, -2.0, 0.5);
+    let built = Decomposed::identity()
+        .with_scale(3.0)
+        .with_rotation(rot)
+        .with_translation(disp);
+    assert_eq!(
+        built,
+        Decomposed {
+            scale: 3.0,
+            rot,
+            disp,
+        }
+    );
+    assert_eq!(built.with_scale(1.0).scale, 1.0);
+
+    let identity: Decomposed<Vector3<f64>, Quaternion<f64>> = Decomposed::identity();
+    assert_eq!(identity, Default::default());
+
+    let p = Point3::new(4.0, 5.0, 6.0);
+    let translation: Decomposed<_, Quaternion<_>> = Decomposed::from_translation(disp);
+    assert_eq!(translation.transform_point(p), p + disp);
+    let scale: Decomposed<_, Quaternion<_>> = Decomposed::from_scale(2.0);
+    assert_eq!(scale.transform_point(p), Point3::new(8.0, 10.0, 12.0));
+    let rotation: Decomposed<Vector3<f64>, _> = Decomposed::from_rotation(rot);
+    assert_ulps_eq!(rotation.transform_point(p), rot.rotate_point(p));
+
+    // The shortcuts compose into the same transform.
+    let composed = translation
+        .concat(&rotation)
+        .concat(&Decomposed::from_scale(3.0));
+    assert_ulps_eq!(composed, built);
+}
+
+#[test]
+fn test_precision_conversions() {
+    let t = Decomposed {
+        scale: 2.0f64,
+        rot: Quaternion::from_angle_z(Deg(45.0)),
+        disp: Vector3::new(1.0e6, 0.5, -3.0),
+    };
+    let single: Decomposed<Vector3<f32>, Quaternion<f32>> = t.to_f32();
+    assert_eq!(single.scale, 2.0f32);
+    assert_eq!(single.disp, Vector3::new(1.0e6f32, 0.5, -3.0));
+
+    let double: Decomposed<Vector3<f64>, Quaternion<f64>> = single.into();
+    assert_relative_eq!(double.rot, t.rot, epsilon = 1e-6);
+}
+
+#[test]
+fn test_inverse_transform_point() {
+    let t = Decomposed {
+        scale: 3.0f64,
+        rot: Quaternion::from_angle_y(Deg(40.0)),
+        disp: Vector3::new(-4.0, 2.0, 1.5),
+    };
+    let p = Point3::new(0.5, -7.0, 2.0);
+    let q = t.transform_point(p);
+    assert_relative_eq!(t.inverse_transform_point(q).unwrap(), p, epsilon = 1e-12);
+    assert_relative_eq!(
+        t.inverse_transform_point(q).unwrap(),
+        t.inverse_transform().unwrap().transform_point(q),
+        epsilon = 1e-12
+    );
+
+    let m = Matrix4::from(t);
+    assert_relative_eq!(m.inverse_transform_point(q).unwrap(), p, epsilon = 1e-12);
+
+    let degenerate = Decomposed { scale: 0.0, ..t };
+    assert_eq!(degenerate.inverse_transform_point(q), None);
+}
+
+#[test]
+fn test_transform_points_in_place() {
+    let t = Decomposed {
+        scale: 2.0f64,
+        rot: Quaternion::from_angle_x(Deg(30.0)),
+        disp: Vector3::new(1.0, -2.0, 3.0),
+    };
+    let original = [Point3::new(1.0, 2.0, 3.0), Point3::new(-4.0, 0.5, 0.0)];
+    let mut points = original;
+    t.transform_points_in_place(&mut points);
+    for (p, &o) in points.iter().zip(original.iter()) {
+        assert_ulps_eq!(*p, t.transform_point(o));
+    }
+
+    let mut vectors = [Vector3::new(1.0, 0.0, 0.0)];
+    t.transform_vectors_in_place(&mut vectors);
+    assert_ulps_eq!(vectors[0], t.transform_vector(Vector3::new(1.0, 0.0, 0.0)));
+}
+
+#[test]
+fn test_transform_chunks() {
+    let m = Matrix4::from_translation(Vector3::new(0.0f32, 1.0, 0.0));
+    let mut points: Vec<_> = (0..10).map(|i| Point3::new(i as f32, 0.0, 0.0)).collect();
+
+    let mut sizes = Vec::new();
+    transform_chunks(&m, &mut points, 4, |chunk| {
+        sizes.push(chunk.points().len());
+        chunk.run();
+    });
+
+    assert_eq!(sizes, vec![4, 4, 2]);
+    for (i, p) in points.iter().enumerate() {
+        assert_eq!(*p, Point3::new(i as f32, 1.0, 0.0));
+    }
+}
+
+#[test]
+fn test_transform_chunks_across_threads() {
+    let t = Decomposed {
+        scale: 1.0f64,
+        rot: Quaternion::from_angle_z(Deg(90.0)),
+        disp: Vector3::new(0.0, 0.0, 1.0),
+    };
+    let original: Vec<_> = (0..100).map(|i| Point3::new(i as f64, 0.0, 0.0)).collect();
+
+    let handles: Vec<_> = original
+        .chunks(16)
+        .map(|chunk| {
+            let mut chunk = chunk.to_vec();
+            std::thread::spawn(move || {
+                transform_chunks(&t, &mut chunk, 4, |job| job.run());
+                chunk
+            })
+        })
+        .collect();
+    let points: Vec<_> = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+
+    for (p, &o) in points.iter().zip(original.iter()) {
+        assert_ulps_eq!(*p, t.transform_point(o));
+    }
+}
+
+#[test]
+fn test_pose_delta_round_trip() {
+    let previous = Decomposed {
+        scale: 2.0f64,
+        rot: Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 2.0) / 3.0, Deg(40.0)),
+        disp: Vector3::new(1000.0, -20.0, 3.0),
+    };
+    let current = Decomposed {
+        scale: 3.0,
+        rot: Quaternion::from_axis_angle(Vector3::new(0.0, 0.6, 0.8), Deg(43.0)),
+        disp: Vector3::new(1000.3, -20.1, 3.2),
+    };
+    let delta = current.delta(&previous);
+    assert_relative_eq!(delta.disp, Vector3::new(0.3, -0.1, 0.2), epsilon = 1e-10);
+    let applied = previous.apply_delta(&delta);
+    assert_eq!(applied.scale, previous.scale);
+    assert_relative_eq!(applied.disp, current.disp, epsilon = 1e-12);
+    assert_relative_eq!(applied.rot, current.rot, epsilon = 1e-12);
+
+    // Deltas also work for the other kinds of rotations.
+    let previous_basis = Decomposed {
+        scale: 1.0,
+        rot: Basis3::from(previous.rot),
+        disp: previous.disp,
+    };
+    let current_basis = Decomposed {
+        scale: 1.0,
+        rot: Basis3::from(current.rot),
+        disp: current.disp,
+    };
+    let applied = previous_basis.apply_delta(&current_basis.delta(&previous_basis));
+    assert_relative_eq!(applied.rot, current_basis.rot, epsilon = 1e-12);
+}
+
+#[test]
+fn test_quantized_pose_delta() {
+    let step = 0.001;
+    let mut sent = Decomposed {
+        scale: 1.0,
+        rot: Quaternion::<f64>::one(),
+        disp: Vector3::zero(),
+    };
+    let mut received = sent;
+    for i in 1..100 {
+        let t = i as f64 * 0.1;
+        let exact = Decomposed {
+            scale: 1.0,
+            rot: Quaternion::from_angle_y(Rad(t)) * Quaternion::from_angle_x(Rad(t * 0.3)),
+            disp: Vector3::new(t.sin() * 10.0, t, -t * t),
+        };
+        // The sender tracks the reconstructed pose, so errors do not build up.
+        let packet = exact.delta(&sent).quantize(step);
+        received = received.apply_delta(&packet.dequantize(step));
+        sent = sent.apply_delta(&packet.dequantize(step));
+        assert_eq!(sent, received);
+        assert!(received.disp.distance(exact.disp) <= step);
+        assert!(received.rot.angle_between(&exact.rot).0 < 1e-4);
+    }
+}
+
+#[test]
+fn test_decompose_matrix4() {
+    let t = Decomposed {
+        scale: 0.75f64,
+        rot: Quaternion::from_axis_angle(Vector3::new(2.0, -1.0, 2.0) / 3.0, Deg(130.0)),
+        disp: Vector3::new(-4.0, 5.0, 6.0),
+    };
+    let m = Matrix4::from(t);
+    let d: Decomposed<Vector3<f64>, Quaternion<f64>> = Decomposed::from_matrix4(&m, 1e-12).unwrap();
+    assert_relative_eq!(d.scale, t.scale, epsilon = 1e-12);
+    assert!(d.rot.approx_eq_rotation(&t.rot));
+    assert_eq!(d.disp, t.disp);
+
+    let b: Decomposed<Vector3<f64>, Basis3<f64>> = Decomposed::from_matrix4(&m, 1e-12).unwrap();
+    assert_relative_eq!(Matrix4::from(b), m, epsilon = 1e-12);
+
+    let decompose =
+        |m: Matrix4<f64>| Decomposed::<Vector3<f64>, Quaternion<f64>>::from_matrix4(&m, 1e-12);
+    assert!(decompose(Matrix4::from_nonuniform_scale(-1.0, 1.0, 1.0)).is_none());
+    assert!(decompose(Matrix4::from_scale(0.0)).is_none());
+    assert!(decompose(perspective(Deg(60.0), 1.0, 0.1, 10.0)).is_none());
+    assert!(decompose(Matrix4::identity()).is_some());
+
+    let mut sheared = Matrix4::<f64>::identity();
+    sheared.y.x = 1e-9;
+    assert!(decompose(sheared).is_none());
+    assert!(Decomposed::<Vector3<f64>, Quaternion<f64>>::from_matrix4(&sheared, 1e-6).is_some());
+}
+
 #[cfg(feature = "serde")]
 #[test]
 fn test_serialize() {
@@ -76,3 +335,51 @@ fn test_serialize() {
 
     assert_ulps_eq!(&t, &deserialized);
 }
+
+#[test]
+fn test_cached_transform() {
+    let t = Decomposed {
+        scale: 2.0f64,
+        rot: Quaternion::from_axis_angle(Vector3::new(1.0, 2.0, 3.0).normalize(), Deg(40.0)),
+        disp: Vector3::new(1.0, -2.0, 0.5),
+    };
+    let mut cached = CachedTransform::new(t);
+    assert!(!cached.is_cached());
+    let m: Matrix4<f64> = t.into();
+    assert_eq!(cached.matrix(), m);
+    assert!(cached.is_cached());
+    assert_eq!(cached.matrix(), m);
+    assert_relative_eq!(
+        cached.inverse_matrix().unwrap(),
+        m.invert().unwrap(),
+        epsilon = 1e-12
+    );
+    let linear = Matrix3::from_cols(m.x.truncate(), m.y.truncate(), m.z.truncate());
+    assert_relative_eq!(
+        cached.normal_matrix().unwrap(),
+        linear.invert().unwrap().transpose(),
+        epsilon = 1e-12
+    );
+
+    // Mutations clear the cache.
+    cached.transform_mut().scale = 0.5;
+    assert!(!cached.is_cached());
+    let t = *cached.transform();
+    assert_eq!(cached.matrix(), Matrix4::from(t));
+    cached.set(Decomposed::one());
+    assert_eq!(cached.matrix(), Matrix4::identity());
+    assert_eq!(cached.inverse_matrix(), Some(Matrix4::identity()));
+    assert_eq!(cached.normal_matrix(), Some(Matrix3::identity()));
+
+    cached.transform_mut().scale = 0.0;
+    assert_eq!(cached.inverse_matrix(), None);
+    assert_eq!(cached.normal_matrix(), None);
+
+    // A clone keeps the cache but is independent.
+    let mut copy = cached.clone();
+    assert_eq!(copy, cached);
+    copy.set(t);
+    assert_eq!(copy.matrix(), Matrix4::from(t));
+    assert!(copy != cached);
+    assert_eq!(cached.into_inner().scale, 0.0);
+}
@@ -0,0 +1,100 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_push_pop() {
+    let mut stack = MatrixStack::<f64>::new();
+    assert_eq!(stack.depth(), 0);
+    assert_eq!(stack.pop(), None);
+    assert_eq!(*stack.current(), Matrix4::identity());
+
+    stack.translate(Vector3::new(1.0, 2.0, 3.0));
+    stack.push();
+    stack.scale(2.0);
+    assert_eq!(stack.depth(), 1);
+    let scaled = *stack.current();
+    assert_eq!(stack.pop(), Some(scaled));
+    assert_eq!(
+        *stack.current(),
+        Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+    );
+
+    stack.load_identity();
+    assert_eq!(*stack.current(), Matrix4::identity());
+}
+
+#[test]
+fn test_operations_apply_first() {
+    let mut stack = MatrixStack::new();
+    stack.translate(Vector3::new(1.0, 0.0, 0.0f64));
+    stack.rotate(Quaternion::from_angle_z(Deg(90.0)));
+    stack.scale_nonuniform(Vector3::new(2.0, 3.0, 4.0));
+    let expected = Matrix4::from_translation(Vector3::new(1.0, 0.0, 0.0))
+        * Matrix4::from_angle_z(Deg(90.0))
+        * Matrix4::from_nonuniform_scale(2.0, 3.0, 4.0);
+    assert_ulps_eq!(*stack.current(), expected);
+    assert_relative_eq!(
+        stack.current().transform_point(Point3::new(1.0, 1.0, 1.0)),
+        Point3::new(-2.0, 2.0, 4.0),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_scoped() {
+    let mut stack = MatrixStack::new();
+    stack.scale(3.0f64);
+    let before = *stack.current();
+    let inner = stack.scoped(|stack| {
+        stack.translate(Vector3::unit_x());
+        // Unbalanced pushes are undone too.
+        stack.push();
+        stack.scale(5.0);
+        *stack.current()
+    });
+    assert_eq!(stack.depth(), 0);
+    assert_eq!(*stack.current(), before);
+    assert_ulps_eq!(
+        inner,
+        before * Matrix4::from_translation(Vector3::unit_x()) * Matrix4::from_scale(5.0)
+    );
+}
+
+#[test]
+fn test_decomposed_stack() {
+    let mut stack: TransformStack<Point3<f64>, Decomposed<Vector3<f64>, Quaternion<f64>>> =
+        TransformStack::new();
+    let mut matrices = MatrixStack::new();
+    let rotation = Quaternion::from_axis_angle(Vector3::new(0.6, 0.0, 0.8), Deg(40.0));
+
+    stack.translate(Vector3::new(1.0, -2.0, 0.5));
+    matrices.translate(Vector3::new(1.0, -2.0, 0.5));
+    stack.rotate(rotation);
+    matrices.rotate(rotation);
+    stack.scale(1.5);
+    matrices.scale(1.5);
+
+    let m: Matrix4<f64> = (*stack.current()).into();
+    assert_relative_eq!(m, *matrices.current(), epsilon = 1e-12);
+
+    stack.multiply(&Decomposed::one());
+    stack.load(Decomposed::one());
+    assert_eq!(*stack.current(), Decomposed::one());
+}
@@ -0,0 +1,176 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+fn triangle3() -> Triangle3<f64> {
+    Triangle3::new(
+        Point3::new(1.0, 0.0, 2.0),
+        Point3::new(4.0, 1.0, 2.0),
+        Point3::new(2.0, 3.0, 5.0),
+    )
+}
+
+#[test]
+fn test_barycentric_of_vertices() {
+    let t = triangle3();
+    assert_ulps_eq!(t.barycentric(t.a).unwrap(), Vector3::unit_x());
+    assert_ulps_eq!(t.barycentric(t.b).unwrap(), Vector3::unit_y());
+    assert_ulps_eq!(t.barycentric(t.c).unwrap(), Vector3::unit_z());
+}
+
+#[test]
+fn test_barycentric_roundtrip() {
+    let t = triangle3();
+    let bary = Vector3::new(0.2, -0.5, 1.3);
+    let p = t.point_at(bary);
+    assert_relative_eq!(t.barycentric(p).unwrap(), bary, epsilon = 1e-12);
+    assert!(!Triangle3::contains_barycentric(bary));
+    assert!(Triangle3::<f64>::contains_barycentric(Vector3::new(
+        0.2, 0.3, 0.5
+    )));
+}
+
+#[test]
+fn test_barycentric_projects_off_plane_points() {
+    let t = triangle3();
+    let bary = Vector3::new(0.25, 0.25, 0.5);
+    let p = t.point_at(bary) + t.normal() * 3.0;
+    assert_relative_eq!(t.barycentric(p).unwrap(), bary, epsilon = 1e-12);
+}
+
+#[test]
+fn test_barycentric_degenerate() {
+    let t = Triangle3::new(
+        Point3::new(0.0, 0.0, 0.0),
+        Point3::new(1.0, 1.0, 1.0),
+        Point3::new(2.0, 2.0, 2.0f64),
+    );
+    assert_eq!(t.barycentric(Point3::new(0.5, 0.5, 0.5)), None);
+    let t = Triangle2::new(
+        Point2::new(1.0, 1.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(3.0, 0.0f64),
+    );
+    assert_eq!(t.barycentric(Point2::new(0.0, 0.0)), None);
+}
+
+#[test]
+fn test_interpolate_attributes() {
+    let t = triangle3();
+    let bary = Vector3::new(0.5, 0.3, 0.2);
+    let p = t.point_at(bary);
+
+    let normal = t.interpolate(
+        bary,
+        (Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()),
+    );
+    assert_ulps_eq!(normal, Vector3::new(0.5, 0.3, 0.2));
+
+    let uv = t.interpolate(
+        t.barycentric(p).unwrap(),
+        (
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 1.0),
+        ),
+    );
+    assert_relative_eq!(uv, Vector2::new(0.3, 0.2), epsilon = 1e-12);
+
+    let position = t.interpolate(bary, (t.a, t.b, t.c));
+    assert_ulps_eq!(position, p);
+
+    let color = t.interpolate(
+        bary,
+        (
+            color::Rgb::new(1.0, 0.0, 0.0),
+            color::Rgb::new(0.0, 1.0, 0.0),
+            color::Rgb::new(0.0, 0.0, 1.0),
+        ),
+    );
+    assert_ulps_eq!(color, color::Rgb::new(0.5, 0.3, 0.2));
+}
+
+#[test]
+fn test_triangle3_normal_and_area() {
+    let t = Triangle3::new(
+        Point3::new(0.0, 0.0, 1.0),
+        Point3::new(2.0, 0.0, 1.0),
+        Point3::new(0.0, 3.0, 1.0f64),
+    );
+    assert_ulps_eq!(t.normal(), Vector3::unit_z());
+    assert_ulps_eq!(t.area(), 3.0);
+    assert_ulps_eq!(t.centroid(), Point3::new(2.0 / 3.0, 1.0, 1.0));
+}
+
+#[test]
+fn test_triangle2() {
+    let t = Triangle2::new(
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 2.0),
+        Point2::new(2.0, 0.0f64),
+    );
+    assert_ulps_eq!(t.signed_area(), -2.0);
+    assert_ulps_eq!(t.area(), 2.0);
+    let bary = t.barycentric(Point2::new(0.5, 1.0)).unwrap();
+    assert_ulps_eq!(bary, Vector3::new(0.25, 0.5, 0.25));
+    assert_ulps_eq!(
+        Triangle3::from(t).point_at(bary),
+        Point3::new(0.5, 1.0, 0.0)
+    );
+}
+
+#[test]
+fn test_triangle3_intersection() {
+    let triangle = |a: [f64; 3], b: [f64; 3], c: [f64; 3]| {
+        Triangle3::new(Point3::from(a), Point3::from(b), Point3::from(c))
+    };
+    let floor = triangle([0.0, 0.0, 0.0], [4.0, 0.0, 0.0], [0.0, 4.0, 0.0]);
+
+    // A spike through the interior, where the segment ends inside the floor.
+    let spike = triangle([1.0, 1.0, -1.0], [2.0, 1.0, -1.0], [1.0, 1.0, 1.0]);
+    let (p, q) = floor.intersection(&spike).unwrap();
+    assert_ulps_eq!(p, Point3::new(1.0, 1.0, 0.0));
+    assert_ulps_eq!(q, Point3::new(1.5, 1.0, 0.0));
+    // The same segment in the other order, reversed or not.
+    let (r, s) = spike.intersection(&floor).unwrap();
+    assert!((r == p && s == q) || (r == q && s == p));
+
+    // A wall crossing the whole floor is clipped to it.
+    let wall = triangle([-5.0, 1.0, -5.0], [10.0, 1.0, -5.0], [-5.0, 1.0, 10.0]);
+    let (p, q) = floor.intersection(&wall).unwrap();
+    let (p, q) = if p.x < q.x { (p, q) } else { (q, p) };
+    assert_ulps_eq!(p, Point3::new(0.0, 1.0, 0.0));
+    assert_ulps_eq!(q, Point3::new(3.0, 1.0, 0.0));
+
+    // Touching at a vertex gives a degenerate segment.
+    let touching = triangle([1.0, 1.0, 0.0], [1.0, 2.0, 1.0], [2.0, 1.0, 1.0]);
+    let (p, q) = floor.intersection(&touching).unwrap();
+    assert_eq!(
+        (p, q),
+        (Point3::new(1.0, 1.0, 0.0), Point3::new(1.0, 1.0, 0.0))
+    );
+
+    // Above the floor, beside it with crossing planes, and coplanar.
+    let above = triangle([1.0, 1.0, 1.0], [2.0, 1.0, 1.0], [1.0, 2.0, 2.0]);
+    assert_eq!(floor.intersection(&above), None);
+    let beside = triangle([5.0, 5.0, -1.0], [6.0, 5.0, -1.0], [5.0, 5.0, 1.0]);
+    assert_eq!(floor.intersection(&beside), None);
+    let coplanar = triangle([1.0, 1.0, 0.0], [2.0, 1.0, 0.0], [1.0, 2.0, 0.0]);
+    assert_eq!(floor.intersection(&coplanar), None);
+}
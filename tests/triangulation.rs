@@ -0,0 +1,307 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+
+use cgmath::noise::random2;
+use cgmath::predicates::{incircle, orient2d};
+use cgmath::triangulation::*;
+use cgmath::{Aabb2, MetricSpace, Point2};
+
+fn random_points(count: i32, seed: u32) -> Vec<Point2<f64>> {
+    (0..count)
+        .map(|i| {
+            Point2::new(
+                random2(Point2::new(i, 0), seed),
+                random2(Point2::new(i, 1), seed),
+            )
+        })
+        .collect()
+}
+
+fn area(points: &[Point2<f64>], triangles: &[[usize; 3]]) -> f64 {
+    triangles
+        .iter()
+        .map(|t| orient2d(points[t[0]], points[t[1]], points[t[2]]) / 2.0)
+        .sum()
+}
+
+fn check_delaunay(points: &[Point2<f64>], triangles: &[[usize; 3]]) {
+    for t in triangles {
+        let (a, b, c) = (points[t[0]], points[t[1]], points[t[2]]);
+        assert!(orient2d(a, b, c) > 0.0, "{:?} is not counterclockwise", t);
+        for &p in points {
+            assert!(incircle(a, b, c, p) <= 0.0, "{:?} contains {:?}", t, p);
+        }
+    }
+}
+
+#[test]
+fn test_delaunay_degenerate() {
+    assert!(delaunay::<f64>(&[]).is_empty());
+    assert!(delaunay(&[Point2::new(1.0, 2.0); 3]).is_empty());
+
+    let line: Vec<_> = (0..5)
+        .map(|i| Point2::new(i as f64, 2.0 * i as f64))
+        .collect();
+    assert!(delaunay(&line).is_empty());
+}
+
+#[test]
+fn test_delaunay_square() {
+    let points = [
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(0.0, 1.0),
+    ];
+    let triangles = delaunay(&points);
+    assert_eq!(triangles.len(), 2);
+    assert_eq!(area(&points, &triangles), 1.0);
+    check_delaunay(&points, &triangles);
+}
+
+#[test]
+fn test_delaunay_duplicates_and_collinear() {
+    let points = [
+        Point2::new(0.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(2.0, 0.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(3.0, 0.0),
+        Point2::new(1.5, 1.0),
+        Point2::new(0.0, 0.0),
+    ];
+    let triangles = delaunay(&points);
+    assert_eq!(triangles.len(), 3);
+    assert_eq!(area(&points, &triangles), 1.5);
+    assert!(triangles.iter().all(|t| !t.contains(&3) && !t.contains(&6)));
+    check_delaunay(&points, &triangles);
+}
+
+#[test]
+fn test_delaunay_grid() {
+    let n = 8;
+    let points: Vec<_> = (0..n * n)
+        .map(|i| Point2::new((i % n) as f64, (i / n) as f64))
+        .collect();
+    let triangles = delaunay(&points);
+    assert_eq!(triangles.len(), 2 * (n - 1) * (n - 1));
+    assert_eq!(area(&points, &triangles), ((n - 1) * (n - 1)) as f64);
+    check_delaunay(&points, &triangles);
+}
+
+#[test]
+fn test_delaunay_random() {
+    let points = random_points(300, 3);
+    let triangles = delaunay(&points);
+    check_delaunay(&points, &triangles);
+
+    // Every point is a vertex, and every edge is shared by at most two
+    // triangles, with opposite directions.
+    let mut edges = std::collections::HashSet::new();
+    for t in &triangles {
+        for k in 0..3 {
+            assert!(edges.insert((t[k], t[(k + 1) % 3])));
+        }
+    }
+    for i in 0..points.len() {
+        assert!(triangles.iter().any(|t| t.contains(&i)));
+    }
+
+    // Euler's formula gives the number of triangles from the number of
+    // vertices on the convex hull, which are the unmatched edges.
+    let hull = edges
+        .iter()
+        .filter(|&&(u, v)| !edges.contains(&(v, u)))
+        .count();
+    assert_eq!(triangles.len(), 2 * points.len() - 2 - hull);
+}
+
+fn polygon_area(polygon: &[Point2<f64>]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (p, q) = (polygon[i], polygon[(i + 1) % n]);
+            p.x * q.y - q.x * p.y
+        })
+        .sum::<f64>()
+        .abs()
+        / 2.0
+}
+
+fn check_triangulation(points: &[Point2<f64>], triangles: &[[usize; 3]], expected_area: f64) {
+    for t in triangles {
+        assert!(orient2d(points[t[0]], points[t[1]], points[t[2]]) > 0.0);
+    }
+    assert!((area(points, triangles) - expected_area).abs() < 1e-9);
+}
+
+fn square(center: Point2<f64>, half: f64) -> Vec<Point2<f64>> {
+    vec![
+        Point2::new(center.x - half, center.y - half),
+        Point2::new(center.x + half, center.y - half),
+        Point2::new(center.x + half, center.y + half),
+        Point2::new(center.x - half, center.y + half),
+    ]
+}
+
+#[test]
+fn test_triangulate_convex() {
+    let hexagon: Vec<_> = (0..6)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 3.0;
+            Point2::new(angle.cos(), angle.sin())
+        })
+        .collect();
+    let triangles = triangulate(&hexagon);
+    assert_eq!(triangles.len(), 4);
+    check_triangulation(&hexagon, &triangles, polygon_area(&hexagon));
+
+    assert!(triangulate(&hexagon[..2]).is_empty());
+}
+
+#[test]
+fn test_triangulate_clockwise_and_collinear() {
+    let polygon = [
+        Point2::new(0.0, 0.0),
+        Point2::new(0.0, 2.0),
+        Point2::new(1.0, 2.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(2.0, 1.0),
+        Point2::new(1.0, 1.0),
+        Point2::new(1.0, 0.0),
+        Point2::new(0.5, 0.0),
+    ];
+    let triangles = triangulate(&polygon);
+    check_triangulation(&polygon, &triangles, 3.0);
+}
+
+#[test]
+fn test_triangulate_star() {
+    let star: Vec<_> = (0..40)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::PI / 20.0;
+            let radius = if i % 2 == 0 {
+                1.0
+            } else {
+                0.3 + 0.01 * i as f64
+            };
+            Point2::new(radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+    let triangles = triangulate(&star);
+    assert_eq!(triangles.len(), star.len() - 2);
+    check_triangulation(&star, &triangles, polygon_area(&star));
+}
+
+#[test]
+fn test_triangulate_with_holes() {
+    let outer = square(Point2::new(0.0, 0.0), 4.0);
+    let mut left = square(Point2::new(-2.0, 0.0), 1.0);
+    left.reverse();
+    let right = square(Point2::new(2.0, 1.0), 1.0);
+    let holes = [&left[..], &right[..]];
+
+    let triangles = triangulate_with_holes(&outer, &holes);
+    assert_eq!(triangles.len(), 14);
+
+    let points: Vec<_> = outer.iter().chain(&left).chain(&right).cloned().collect();
+    check_triangulation(&points, &triangles, 64.0 - 8.0);
+    for i in 0..points.len() {
+        assert!(triangles.iter().any(|t| t.contains(&i)));
+    }
+}
+
+#[test]
+fn test_triangulate_with_aligned_holes() {
+    // The holes and a vertex of the outer boundary are on the same line, so
+    // the bridge of the right hole must not be blocked by the left one.
+    let outer = [
+        Point2::new(-6.0, -3.0),
+        Point2::new(6.0, -3.0),
+        Point2::new(6.0, 0.0),
+        Point2::new(7.0, 3.0),
+        Point2::new(-6.0, 3.0),
+    ];
+    let left = square(Point2::new(-3.0, -1.0), 1.0);
+    let right = square(Point2::new(2.0, -1.0), 1.0);
+    let holes = [&left[..], &right[..]];
+
+    let triangles = triangulate_with_holes(&outer, &holes);
+    let points: Vec<_> = outer.iter().chain(&left).chain(&right).cloned().collect();
+    check_triangulation(&points, &triangles, polygon_area(&outer) - 8.0);
+}
+
+fn check_voronoi(points: &[Point2<f64>], bounds: &Aabb2<f64>, cells: &[Vec<Point2<f64>>]) {
+    assert_eq!(cells.len(), points.len());
+    let mut total = 0.0;
+    for (i, cell) in cells.iter().enumerate() {
+        if !points[..i].contains(&points[i]) {
+            total += polygon_area(cell);
+        }
+        for &v in cell {
+            assert!(bounds.contains_point(v));
+            let own = v.distance2(points[i]);
+            assert!(points.iter().all(|&p| own <= v.distance2(p) + 1e-9));
+        }
+    }
+    assert!((total - bounds.volume()).abs() < 1e-9);
+}
+
+#[test]
+fn test_voronoi_random() {
+    let points = random_points(200, 11);
+    let bounds = Aabb2::new(Point2::new(-0.25, -0.5), Point2::new(1.5, 1.0));
+    let cells = voronoi(&points, &bounds);
+    check_voronoi(&points, &bounds, &cells);
+    assert!(cells
+        .iter()
+        .all(|cell| cell.len() >= 3 && polygon_area(cell) > 0.0));
+}
+
+#[test]
+fn test_voronoi_degenerate() {
+    let bounds = Aabb2::new(Point2::new(0.0, 0.0), Point2::new(4.0, 4.0));
+    assert!(voronoi(&[], &bounds).is_empty());
+    assert_eq!(
+        voronoi(&[Point2::new(1.0, 1.0)], &bounds),
+        vec![bounds.corners().to_vec()]
+    );
+
+    // Collinear points, with a duplicate, get strips.
+    let line = [
+        Point2::new(1.0, 1.0),
+        Point2::new(2.0, 2.0),
+        Point2::new(3.0, 3.0),
+        Point2::new(2.0, 2.0),
+    ];
+    let cells = voronoi(&line, &bounds);
+    check_voronoi(&line, &bounds, &cells);
+    assert_eq!(cells[1], cells[3]);
+
+    // A grid, where the Delaunay triangulation is ambiguous, and a point
+    // outside of the bounds.
+    let mut grid: Vec<Point2<f64>> = (0..16)
+        .map(|i| Point2::new((i % 4) as f64 + 0.5, (i / 4) as f64 + 0.5))
+        .collect();
+    grid.push(Point2::new(2.0, 2.0));
+    grid.push(Point2::new(10.0, 10.0));
+    let cells = voronoi(&grid, &bounds);
+    check_voronoi(&grid, &bounds, &cells);
+    assert!(cells[17].is_empty());
+    assert_eq!(polygon_area(&cells[0]), 1.0);
+}
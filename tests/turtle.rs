@@ -0,0 +1,96 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_turtle2_branches() {
+    // The L-system "F[+F]F", with 90 degree turns.
+    let mut turtle = Turtle2::new(Point2::new(0.0, 0.0f64), Deg(90.0));
+    turtle.forward(1.0);
+    turtle.push();
+    turtle.turn(Deg(90.0));
+    turtle.forward(1.0);
+    assert!(turtle.pop());
+    turtle.forward(1.0);
+    assert!(!turtle.pop());
+
+    let paths = turtle.into_paths();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[0].len(), 3);
+    assert_relative_eq!(paths[0][1], Point2::new(0.0, 1.0), epsilon = 1e-12);
+    assert_relative_eq!(paths[0][2], Point2::new(-1.0, 1.0), epsilon = 1e-12);
+    assert_relative_eq!(paths[1][0], Point2::new(0.0, 1.0), epsilon = 1e-12);
+    assert_relative_eq!(paths[1][1], Point2::new(0.0, 2.0), epsilon = 1e-12);
+}
+
+#[test]
+fn test_turtle2_jump() {
+    let mut turtle = Turtle2::new(Point2::new(1.0, 1.0f64), Rad(0.0));
+    turtle.jump(2.0);
+    turtle.jump(1.0);
+    turtle.forward(1.0);
+    assert_eq!(turtle.paths().len(), 1);
+    assert_relative_eq!(turtle.paths()[0][0], Point2::new(4.0, 1.0));
+    assert_relative_eq!(turtle.position, Point2::new(5.0, 1.0));
+    assert_relative_eq!(turtle.direction(), Vector2::unit_x());
+}
+
+#[test]
+fn test_turtle3_axes() {
+    let mut turtle = Turtle3::new(Point3::new(0.0, 0.0, 0.0f64), Quaternion::one());
+    assert_relative_eq!(turtle.direction(), Vector3::unit_x());
+    assert_relative_eq!(turtle.left(), Vector3::unit_y());
+    assert_relative_eq!(turtle.up(), Vector3::unit_z());
+
+    turtle.turn(Deg(90.0));
+    assert_relative_eq!(turtle.direction(), Vector3::unit_y(), epsilon = 1e-12);
+    // The rotations are around the axes of the turtle, not the world.
+    turtle.pitch(Deg(90.0));
+    assert_relative_eq!(turtle.direction(), Vector3::unit_z(), epsilon = 1e-12);
+    turtle.roll(Deg(90.0));
+    assert_relative_eq!(turtle.direction(), Vector3::unit_z(), epsilon = 1e-12);
+    assert_relative_eq!(
+        turtle.left(),
+        turtle.up().cross(turtle.direction()),
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn test_turtle3_tilted_circle() {
+    let mut turtle = Turtle3::new(Point3::new(0.0, 0.0, 0.0f64), Quaternion::one());
+    turtle.pitch(Deg(10.0));
+    turtle.push();
+    for _ in 0..36 {
+        turtle.forward(0.5);
+        turtle.turn(Deg(10.0));
+    }
+    let end = turtle.position;
+    assert!(turtle.pop());
+    assert_eq!(turtle.position, Point3::origin());
+    assert_eq!(turtle.paths().len(), 2);
+    assert_eq!(turtle.paths()[0].len(), 37);
+    assert_eq!(turtle.paths()[1].len(), 1);
+    // Turning around its own up axis, the turtle walks a regular polygon in
+    // its tilted plane, and comes back to where it started.
+    assert!(end.distance(Point3::origin()) < 1e-12);
+    let highest = turtle.paths()[0].iter().map(|p| p.z).fold(0.0, f64::max);
+    assert!(highest > 0.0);
+    assert_eq!(turtle.into_paths().len(), 1);
+}
@@ -0,0 +1,64 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate cgmath;
+
+use cgmath::units::*;
+use cgmath::{Point2, Vector2};
+
+#[test]
+fn test_point_vector_arithmetic() {
+    let a = TypedPoint2::<f64, Logical>::new(1.0, 2.0);
+    let b = TypedPoint2::<f64, Logical>::new(4.0, 6.0);
+    let d = b - a;
+    assert_eq!(d, TypedVector2::new(3.0, 4.0));
+    assert_eq!(a + d, b);
+    assert_eq!(b - d, a);
+    assert_eq!(d + d, d * 2.0);
+    assert_eq!(d - d, TypedVector2::new(0.0, 0.0));
+    assert_eq!(-d, d / -1.0);
+}
+
+#[test]
+fn test_scale_conversion() {
+    let dpi: Scale<f64, Logical, Physical> = Scale::new(1.5);
+    let p = TypedPoint2::<f64, Logical>::new(10.0, -4.0);
+    assert_eq!(dpi.transform_point(p).point, Point2::new(15.0, -6.0));
+    assert_eq!(dpi.inverse().transform_point(dpi.transform_point(p)), p);
+    let v = TypedVector2::<f64, Logical>::new(2.0, 0.0);
+    assert_eq!(dpi.transform_vector(v).vec, Vector2::new(3.0, 0.0));
+}
+
+#[test]
+fn test_scale_composition() {
+    let world_to_logical: Scale<f64, World, Logical> = Scale::new(4.0);
+    let logical_to_physical: Scale<f64, Logical, Physical> = Scale::new(2.0);
+    let world_to_physical = logical_to_physical * world_to_logical;
+    let p = TypedPoint2::<f64, World>::new(1.0, 0.5);
+    assert_eq!(
+        world_to_physical.transform_point(p),
+        TypedPoint2::new(8.0, 4.0)
+    );
+}
+
+#[test]
+fn test_untyped_round_trip() {
+    let p = Point2::new(3u32, 7);
+    let typed = TypedPoint2::<u32, Physical>::from_untyped(p);
+    assert_eq!(typed.point, p);
+    assert_eq!(typed.cast_unit::<Logical>().point, p);
+    let scaled = Scale::<u32, Physical, Physical>::new(3).transform_point(typed);
+    assert_eq!(scaled.point, Point2::new(9, 21));
+}
@@ -31,6 +31,48 @@ fn test_constructor() {
     );
 }
 
+#[test]
+fn test_consts() {
+    const DIRECTIONS: [Vector3<f32>; 3] = [
+        Vector3::<f32>::UNIT_X,
+        Vector3::<f32>::UNIT_Y,
+        Vector3::<f32>::UNIT_Z,
+    ];
+    assert_eq!(DIRECTIONS[0], Vector3::unit_x());
+    assert_eq!(DIRECTIONS[1], Vector3::unit_y());
+    assert_eq!(DIRECTIONS[2], Vector3::unit_z());
+
+    assert_eq!(Vector1::<f64>::ZERO, Vector1::zero());
+    assert_eq!(Vector2::<f64>::ZERO, Vector2::zero());
+    assert_eq!(Vector3::<f64>::ZERO, Vector3::zero());
+    assert_eq!(Vector4::<f64>::ZERO, Vector4::zero());
+    assert_eq!(Vector4::<f64>::UNIT_W, Vector4::unit_w());
+    assert_eq!(Point3::<f64>::ORIGIN, Point3::origin());
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(Vector2::<i32>::default(), Vector2::zero());
+    assert_eq!(Vector4::<f32>::default(), Vector4::zero());
+    assert_eq!(Point3::<f64>::default(), Point3::origin());
+
+    let mut vectors = vec![Vector3::new(1.0f64, 2.0, 3.0)];
+    vectors.resize(3, Default::default());
+    assert_eq!(vectors[2], Vector3::zero());
+}
+
+#[test]
+fn test_precision_conversions() {
+    let v = Vector3::new(1.5f32, -2.25, 0.1);
+    let w: Vector3<f64> = v.into();
+    assert_eq!(w, Vector3::new(1.5f64, -2.25, 0.1f32 as f64));
+    assert_eq!(w.to_f32(), v);
+
+    let p = Point2::new(1.0f64 / 3.0, 2.0);
+    assert_eq!(p.to_f32(), Point2::new(1.0f32 / 3.0, 2.0));
+    assert_eq!(Point2::<f64>::from(p.to_f32()).y, 2.0);
+}
+
 #[test]
 fn test_from_value() {
     assert_eq!(
@@ -206,6 +248,30 @@ fn test_cross() {
     assert_eq!(a.cross(b), r);
 }
 
+#[test]
+fn test_extend_truncate() {
+    assert_eq!(Vector1::new(1).extend(2), Vector2::new(1, 2));
+    assert_eq!(Vector2::new(1, 2).extend(3), Vector3::new(1, 2, 3));
+    assert_eq!(Vector3::new(1, 2, 3).extend(4), Vector4::new(1, 2, 3, 4));
+    assert_eq!(Vector2::new(1, 2).truncate(), Vector1::new(1));
+    assert_eq!(Vector3::new(1, 2, 3).truncate(), Vector2::new(1, 2));
+    assert_eq!(Vector4::new(1, 2, 3, 4).truncate(), Vector3::new(1, 2, 3));
+
+    let v = Vector3::new(1, 2, 3);
+    assert_eq!(v.truncate_n(0), Vector2::new(2, 3));
+    assert_eq!(v.truncate_n(1), Vector2::new(1, 3));
+    assert_eq!(v.truncate_n(2), Vector2::new(1, 2));
+    let v = Vector4::new(1, 2, 3, 4);
+    assert_eq!(v.truncate_n(0), Vector3::new(2, 3, 4));
+    assert_eq!(v.truncate_n(3), Vector3::new(1, 2, 3));
+}
+
+#[test]
+#[should_panic]
+fn test_truncate_n_out_of_range() {
+    Vector3::new(1, 2, 3).truncate_n(3);
+}
+
 #[test]
 fn test_is_perpendicular() {
     assert!(Vector2::new(1.0f64, 0.0f64).is_perpendicular(Vector2::new(0.0f64, 1.0f64)));
@@ -319,6 +385,26 @@ fn test_normalize() {
     );
 }
 
+#[test]
+fn test_fast_normalize() {
+    assert_relative_eq!(
+        Vector2::new(3.0f64, 4.0f64).fast_normalize(),
+        Vector2::new(3.0f64, 4.0f64).normalize(),
+        max_relative = 2e-3
+    );
+    assert_relative_eq!(
+        Vector3::new(2.0f32, 3.0f32, 6.0f32).fast_normalize(),
+        Vector3::new(2.0f32, 3.0f32, 6.0f32).normalize(),
+        max_relative = 2e-3
+    );
+    assert_relative_eq!(
+        Vector4::new(1.0f64, 2.0f64, 4.0f64, 10.0f64).fast_normalize(),
+        Vector4::new(1.0f64, 2.0f64, 4.0f64, 10.0f64).normalize(),
+        max_relative = 2e-3
+    );
+    assert_eq!(Vector3::<f32>::zero().fast_normalize(), Vector3::zero());
+}
+
 #[test]
 fn test_project_on() {
     assert_ulps_eq!(
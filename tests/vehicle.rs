@@ -0,0 +1,231 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+use cgmath::vehicle::{bicycle_step, pure_pursuit, stanley, CurvaturePath, PathSegment, Steer};
+
+fn random_pose(i: i32) -> Pose2<f64> {
+    let r = |d: i32| noise::random2::<f64>(Point2::new(i, d), 7);
+    Pose2::from_heading(
+        Point2::new(10.0 * r(0) - 5.0, 10.0 * r(1) - 5.0),
+        Rad(std::f64::consts::PI * (2.0 * r(2) - 1.0)),
+    )
+}
+
+fn assert_reaches(path: &CurvaturePath<f64>, goal: &Pose2<f64>) {
+    let end = path.end();
+    assert!(
+        end.position().distance(goal.position()) < 1e-8,
+        "{:?}",
+        path
+    );
+    let turn = (end.heading() - goal.heading()).normalize_signed();
+    assert!(turn.0.abs() < 1e-8, "{:?}", path);
+}
+
+#[test]
+fn test_dubins_reaches_goal() {
+    for i in 0..200 {
+        let (start, goal) = (random_pose(2 * i), random_pose(2 * i + 1));
+        let path = CurvaturePath::dubins(&start, &goal, 1.5);
+        assert_eq!(path.segments.len(), 3);
+        assert!(path.segments.iter().all(|s| s.length >= 0.0));
+        assert_reaches(&path, &goal);
+    }
+}
+
+#[test]
+fn test_reeds_shepp_reaches_goal() {
+    for i in 0..200 {
+        let (start, goal) = (random_pose(2 * i), random_pose(2 * i + 1));
+        let path = CurvaturePath::reeds_shepp(&start, &goal, 1.5);
+        assert_reaches(&path, &goal);
+        // Reversing can only make paths shorter, and the way back is as long.
+        assert!(path.length() <= CurvaturePath::dubins(&start, &goal, 1.5).length() + 1e-9);
+        let back = CurvaturePath::reeds_shepp(&goal, &start, 1.5);
+        assert_relative_eq!(back.length(), path.length(), epsilon = 1e-8);
+    }
+}
+
+#[test]
+fn test_straight_paths() {
+    let start = Pose2::from_heading(Point2::new(1.0, 1.0), Deg(90.0f64));
+    let ahead = Pose2::from_heading(Point2::new(1.0, 4.0), Deg(90.0));
+    let behind = Pose2::from_heading(Point2::new(1.0, -2.0), Deg(90.0));
+    assert_relative_eq!(
+        CurvaturePath::dubins(&start, &ahead, 1.0).length(),
+        3.0,
+        epsilon = 1e-12
+    );
+
+    let reverse = CurvaturePath::reeds_shepp(&start, &behind, 1.0);
+    assert_relative_eq!(reverse.length(), 3.0, epsilon = 1e-12);
+    let driven: Vec<_> = reverse
+        .segments
+        .iter()
+        .filter(|s| s.length.abs() > 1e-12)
+        .collect();
+    assert_eq!(driven.len(), 1);
+    assert_eq!(driven[0].steer, Steer::Straight);
+    assert_relative_eq!(driven[0].length, -3.0, epsilon = 1e-12);
+
+    // Driving forwards, the vehicle has to turn around.
+    let around = CurvaturePath::dubins(&start, &behind, 1.0);
+    assert_reaches(&around, &behind);
+    assert!(around.length() > 3.0 + 1.0);
+}
+
+#[test]
+fn test_path_poses_and_arcs() {
+    let path = CurvaturePath {
+        start: Pose2::from_heading(Point2::new(0.0, 0.0), Deg(0.0)),
+        radius: 2.0,
+        segments: vec![
+            PathSegment {
+                steer: Steer::Left,
+                length: std::f64::consts::PI,
+            },
+            PathSegment {
+                steer: Steer::Straight,
+                length: 1.0,
+            },
+            PathSegment {
+                steer: Steer::Right,
+                length: -std::f64::consts::PI,
+            },
+        ],
+    };
+    assert_relative_eq!(path.length(), 1.0 + 2.0 * std::f64::consts::PI);
+    // A quarter turn to the left around (0, 2), then a unit up.
+    let turned = path.pose_at(std::f64::consts::PI);
+    assert_relative_eq!(turned.position(), Point2::new(2.0, 2.0), epsilon = 1e-12);
+    assert_relative_eq!(
+        turned.heading(),
+        Rad(std::f64::consts::FRAC_PI_2),
+        epsilon = 1e-12
+    );
+    let straight = path.pose_at(std::f64::consts::PI + 1.0);
+    assert_relative_eq!(straight.position(), Point2::new(2.0, 3.0), epsilon = 1e-12);
+    // Then reversing while steering right, around (4, 3).
+    let end = path.end();
+    assert_relative_eq!(end.position(), Point2::new(4.0, 1.0), epsilon = 1e-12);
+    assert_relative_eq!(end.heading(), Rad(std::f64::consts::PI), epsilon = 1e-12);
+    assert_eq!(path.pose_at(100.0), end);
+    assert_eq!(path.pose_at(-1.0), path.start);
+
+    let arcs = path.arcs();
+    assert_eq!(arcs.len(), 3);
+    assert_relative_eq!(
+        arcs[0].center().unwrap(),
+        Point2::new(0.0, 2.0),
+        epsilon = 1e-12
+    );
+    assert!(arcs[1].is_straight());
+    assert_relative_eq!(
+        arcs[2].center().unwrap(),
+        Point2::new(4.0, 3.0),
+        epsilon = 1e-12
+    );
+    assert_relative_eq!(arcs[2].end, end.position(), epsilon = 1e-12);
+
+    let polyline = path.flatten(1e-3);
+    assert_eq!(polyline.points[0], Point2::new(0.0, 0.0));
+    assert_relative_eq!(
+        *polyline.points.last().unwrap(),
+        end.position(),
+        epsilon = 1e-12
+    );
+    for i in 0..=100 {
+        let p = path.pose_at(path.length() * i as f64 / 100.0).position();
+        let (closest, _) = polyline.project(p).unwrap();
+        assert!(p.distance(closest) <= 1e-3);
+    }
+}
+
+#[test]
+fn test_bicycle_step() {
+    let pose = Pose2::from_heading(Point2::new(1.0, 0.0), Deg(90.0f64));
+    let straight = bicycle_step(&pose, 2.0, Rad(0.0), 3.0);
+    assert_relative_eq!(straight.position(), Point2::new(1.0, 3.0), epsilon = 1e-12);
+    // Turning right around a circle of radius 2 / tan(45°), through a
+    // quarter of it.
+    let turned = bicycle_step(&pose, 2.0, Deg(-45.0), std::f64::consts::PI);
+    assert_relative_eq!(turned.position(), Point2::new(3.0, 2.0), epsilon = 1e-12);
+    assert_relative_eq!(turned.heading(), Rad(0.0), epsilon = 1e-12);
+    let back = bicycle_step(&turned, 2.0, Deg(-45.0), -std::f64::consts::PI);
+    assert_relative_eq!(back.position(), pose.position(), epsilon = 1e-12);
+}
+
+#[test]
+fn test_pure_pursuit() {
+    let empty = Polyline2::new(vec![]);
+    let pose = Pose2::from_heading(Point2::new(10.0, 0.0), Deg(90.0f64));
+    assert_eq!(pure_pursuit(&pose, 2.0, &empty, 1.0), None);
+
+    // On a circle of radius 10, the vehicle steers as much as the circle
+    // turns.
+    let circle = Polyline2::new(
+        (0..=1000)
+            .map(|i| {
+                let angle = std::f64::consts::PI * i as f64 / 1000.0;
+                Point2::new(10.0 * angle.cos(), 10.0 * angle.sin())
+            })
+            .collect(),
+    );
+    let steer = pure_pursuit(&pose, 2.0, &circle, 3.0).unwrap();
+    assert_relative_eq!(steer, Rad((2.0f64 / 10.0).atan()), epsilon = 1e-4);
+
+    // Facing away from the path, the vehicle turns back towards it.
+    let road = Polyline2::new(vec![Point2::new(0.0, 0.0), Point2::new(100.0, 0.0)]);
+    let drifting = Pose2::from_heading(Point2::new(5.0, 1.0), Deg(10.0));
+    assert!(pure_pursuit(&drifting, 2.0, &road, 5.0).unwrap() < Rad(0.0));
+}
+
+#[test]
+fn test_stanley() {
+    let road = Polyline2::new(vec![Point2::new(0.0, 0.0), Point2::new(100.0, 0.0f64)]);
+    assert_eq!(
+        stanley(
+            &Pose2::from_heading(Point2::new(0.0, 0.0), Deg(0.0)),
+            2.0,
+            1.0,
+            &Polyline2::new(vec![Point2::new(0.0, 0.0)]),
+            1.0,
+            1.0
+        ),
+        None
+    );
+
+    // On the path, only the heading is corrected.
+    let on_road = Pose2::from_heading(Point2::new(5.0, 0.0), Rad(0.0));
+    assert_eq!(stanley(&on_road, 2.0, 5.0, &road, 1.0, 1.0), Some(Rad(0.0)));
+    let skewed = Pose2::from_heading(Point2::new(5.0, -2.0 * 0.2f64.sin()), Rad(0.2));
+    assert_relative_eq!(
+        stanley(&skewed, 2.0, 5.0, &road, 1.0, 1.0).unwrap(),
+        Rad(-0.2),
+        epsilon = 1e-12
+    );
+
+    // Beside the path, the vehicle turns towards it, more slowly when fast.
+    let beside = Pose2::from_heading(Point2::new(5.0, -1.0), Rad(0.0));
+    let slow = stanley(&beside, 2.0, 1.0, &road, 1.0, 1.0).unwrap();
+    let fast = stanley(&beside, 2.0, 9.0, &road, 1.0, 1.0).unwrap();
+    assert_relative_eq!(slow, Rad(0.5f64.atan()), epsilon = 1e-12);
+    assert_relative_eq!(fast, Rad(0.1f64.atan()), epsilon = 1e-12);
+}
@@ -0,0 +1,105 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+#[test]
+fn test_round_trip() {
+    let p = Point3::new(123456789.123, -0.000001, 98765.4321);
+    let w = WorldPoint3::from(p);
+    assert_relative_eq!(w.to_f64(), p, epsilon = 1e-6);
+    assert_eq!(w.high, Point3::new(p.x as f32, p.y as f32, p.z as f32));
+}
+
+#[test]
+fn test_relative_to() {
+    let origin = WorldPoint3::new(5.0e6, -3.0e6, 1.0e5);
+    let p = WorldPoint3::new(5.0e6 + 0.001, -3.0e6 - 2.5, 1.0e5);
+    assert_relative_eq!(
+        p.relative_to(origin),
+        Vector3::new(0.001, -2.5, 0.0),
+        epsilon = 1e-6
+    );
+    assert_eq!(p - origin, p.relative_to(origin));
+}
+
+#[test]
+fn test_accumulate_small_steps() {
+    let start = WorldPoint3::new(1.0e7, 0.0, 0.0);
+    let step = Vector3::new(0.01f32, 0.0, 0.0);
+
+    let mut p = start;
+    for _ in 0..1000 {
+        p += step;
+    }
+    assert_relative_eq!(p.to_f64().x, 1.0e7 + 10.0, epsilon = 1e-3);
+
+    // The same walk in plain single precision doesn't move at all.
+    let mut q = Point3::new(1.0e7f32, 0.0, 0.0);
+    for _ in 0..1000 {
+        q += step;
+    }
+    assert_eq!(q.x, 1.0e7);
+
+    p -= step * 1000.0;
+    assert_relative_eq!(p.relative_to(start), Vector3::zero(), epsilon = 1e-3);
+}
+
+#[test]
+fn test_point_relative_to() {
+    let origin = Point3::new(-3.0e8, 2.0e8, 1.0);
+    let p = Point3::new(-3.0e8 + 0.5, 2.0e8 - 0.0625, 2.0);
+    assert_eq!(p.relative_to(origin), Point3::new(0.5f32, -0.0625, 1.0));
+}
+
+#[test]
+fn test_camera_relative_model_view() {
+    let eye = Point3::new(1.0e7, -5.0e6, 2.5e6);
+    let camera = CameraRelative::look_at(eye, eye + Vector3::new(1.0, 1.0, 0.0), Vector3::unit_z());
+    let view = Matrix4::look_at(eye, eye + Vector3::new(1.0, 1.0, 0.0), Vector3::unit_z());
+    assert_relative_eq!(camera.view(), view, epsilon = 1e-6);
+
+    let model = Decomposed {
+        scale: 1.0,
+        rot: Quaternion::from_angle_z(Deg(30.0)),
+        disp: eye.to_vec() + Vector3::new(2.0, 1.0, 0.5),
+    };
+    let expected = (view * Matrix4::from(model)).to_f32();
+    assert_relative_eq!(camera.model_view(model), expected, epsilon = 1e-5);
+    assert_relative_eq!(
+        camera.relative_view() * camera.model(model),
+        expected,
+        epsilon = 1e-5
+    );
+
+    let p = eye + Vector3::new(0.001, 0.0, 0.0);
+    assert_relative_eq!(
+        camera.position(p),
+        Point3::new(0.001f32, 0.0, 0.0),
+        epsilon = 1e-7
+    );
+}
+
+#[test]
+fn test_camera_from_view() {
+    let eye = Point3::new(12.0, -4.0, 8.0);
+    let view = Matrix4::look_at(eye, Point3::origin(), Vector3::unit_y());
+    let camera = CameraRelative::from_view(view);
+    assert_relative_eq!(camera.eye, eye, epsilon = 1e-12);
+    assert_relative_eq!(camera.view(), view, epsilon = 1e-12);
+}
@@ -0,0 +1,82 @@
+// Copyright 2019 The CGMath Developers. For a full listing of the authors,
+// refer to the Cargo.toml file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+extern crate approx;
+extern crate cgmath;
+
+use cgmath::*;
+
+enum Local {}
+enum Model {}
+enum World {}
+
+#[test]
+fn test_compose_matrices() {
+    let model_from_local: Xform<Local, Model, _> = Xform::new(Matrix4::from_angle_z(Deg(90.0f64)));
+    let world_from_model: Xform<Model, World, _> =
+        Xform::new(Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0)));
+    let world_from_local = world_from_model * model_from_local;
+
+    let p = Point3::new(1.0, 0.0, 0.0);
+    assert_relative_eq!(
+        world_from_local.transform_point(p),
+        Point3::new(1.0, 3.0, 3.0)
+    );
+    assert_relative_eq!(
+        world_from_local.transform_vector(Vector3::unit_x()),
+        Vector3::unit_y()
+    );
+    assert_eq!(
+        world_from_local.transform,
+        world_from_model.transform * model_from_local.transform
+    );
+
+    let local_from_world: Xform<World, Local, _> = world_from_local.inverse().unwrap();
+    let q = local_from_world.transform_point(world_from_local.transform_point(p));
+    assert_relative_eq!(q, p);
+    let round_trip: Xform<Local, Local, _> = local_from_world * world_from_local;
+    assert_relative_eq!(
+        round_trip.transform,
+        Xform::<Local, Local, Matrix4<f64>>::identity().transform
+    );
+}
+
+#[test]
+fn test_compose_decomposed() {
+    let model_from_local: Xform<Local, Model, Decomposed<Vector2<f64>, Basis2<f64>>> =
+        Xform::new(Decomposed {
+            scale: 2.0,
+            rot: Rotation2::from_angle(Deg(90.0)),
+            disp: Vector2::new(1.0, 0.0),
+        });
+    let world_from_model = Xform::<Model, World, _>::new(Decomposed {
+        scale: 1.0,
+        rot: Basis2::one(),
+        disp: Vector2::new(0.0, 5.0),
+    });
+    let world_from_local = world_from_model * model_from_local;
+    assert_relative_eq!(
+        world_from_local.transform_point(Point2::new(1.0, 0.0)),
+        Point2::new(1.0, 7.0)
+    );
+    let inverse = world_from_local.inverse().unwrap();
+    assert_relative_eq!(
+        inverse.transform_point(Point2::new(1.0, 7.0)),
+        Point2::new(1.0, 0.0)
+    );
+
+    let retagged: Xform<World, World, _> = world_from_local.cast_spaces();
+    assert_eq!(retagged.transform, world_from_local.transform);
+}